@@ -0,0 +1,61 @@
+// Benchmarks of the calculation engine: calculating every bundled score and
+// loading a large score library
+//
+// Run with `cargo bench --bench engine`; see "Performance Budget" in the README
+// for the times these should stay within.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use klinscore::scores::{calculate_score, load_all_scores, load_bundled_scores, InputValue};
+use std::collections::HashMap;
+use std::hint::black_box;
+
+/// Size of the generated library in the loading benchmark
+const LIBRARY_SIZE: usize = 500;
+
+/// `calculate_score` for each bundled score, with the inputs of its first test case
+fn calculate(c: &mut Criterion) {
+    let library = load_bundled_scores();
+    let mut group = c.benchmark_group("calculate_score");
+    for (score_id, score) in library.iter() {
+        let Some(case) = score.test_cases.first() else {
+            continue;
+        };
+        let inputs: HashMap<String, InputValue> = case.inputs.clone().into_iter().collect();
+        group.bench_with_input(
+            BenchmarkId::from_parameter(score_id),
+            &inputs,
+            |b, inputs| b.iter(|| calculate_score(black_box(score), black_box(inputs)).unwrap()),
+        );
+    }
+    group.finish();
+}
+
+/// Loading a folder of 500 score files, copies of the bundled scores under new IDs
+fn load_library(c: &mut Criterion) {
+    let bundled = load_bundled_scores();
+    let scores: Vec<_> = bundled.iter().collect();
+    let dir = tempfile::tempdir().unwrap();
+    for (number, (score_id, score)) in scores.iter().cycle().take(LIBRARY_SIZE).enumerate() {
+        let mut score = (*score).clone();
+        let id = format!("{score_id}_{number}");
+        score.id = Some(id.clone());
+        score.superseded_by = None;
+        let yaml = serde_yaml::to_string(&score).unwrap();
+        std::fs::write(dir.path().join(format!("{id}.yaml")), yaml).unwrap();
+    }
+
+    c.bench_function("load_all_scores/500", |b| {
+        b.iter(|| {
+            let library = load_all_scores(dir.path()).unwrap();
+            assert_eq!(library.count(), LIBRARY_SIZE);
+            library
+        })
+    });
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(20);
+    targets = calculate, load_library
+}
+criterion_main!(benches);
@@ -0,0 +1,7 @@
+// build.rs
+// The bundled score library is embedded with `include_dir!`, which cannot track
+// file changes itself. Rebuild whenever anything under scores/ changes.
+
+fn main() {
+    println!("cargo:rerun-if-changed=scores");
+}
@@ -15,7 +15,7 @@ fn main() {
                 let scores = library.get_scores_for_specialty(specialty);
 
                 for score in scores {
-                    println!("  • {} ({})", score.name, score.name_de);
+                    println!("  • {} ({})", score.name.text("en"), score.name.text("de"));
                     println!("    Source: {}", score.guideline_source);
                     println!("    Inputs: {}", score.inputs.len());
                     println!("    Risk categories: {}", score.interpretation.len());
@@ -26,11 +26,11 @@ fn main() {
             // Demo: Get a specific score
             if let Some(score) = library.get_score("cha2ds2_va_example") {
                 println!("\n━━━ Example Score Details ━━━");
-                println!("Name: {}", score.name);
-                println!("Description: {}", score.description);
+                println!("Name: {}", score.name.text("en"));
+                println!("Description: {}", score.description.text("en"));
                 println!("\nInput fields:");
                 for input in &score.inputs {
-                    println!("  - {} ({})", input.label, input.field);
+                    println!("  - {} ({})", input.label.text("en"), input.field);
                 }
             }
         }
@@ -0,0 +1,81 @@
+// calculate_score.rs
+// Arbitrary inputs for the bundled scores: calculating must fail with an error,
+// never panic
+
+#![no_main]
+
+use arbitrary::Arbitrary;
+use klinscore::scores::{calculate_partial, calculate_score, load_bundled_scores, InputValue};
+use klinscore::ScoreDefinition;
+use libfuzzer_sys::fuzz_target;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// Bundled scores, sorted by ID so that a crashing input reproduces
+fn scores() -> &'static [ScoreDefinition] {
+    static SCORES: OnceLock<Vec<ScoreDefinition>> = OnceLock::new();
+    SCORES.get_or_init(|| {
+        let library = load_bundled_scores();
+        let mut ids: Vec<&String> = library.scores.keys().collect();
+        ids.sort();
+        ids.into_iter()
+            .map(|id| library.scores[id].clone())
+            .collect()
+    })
+}
+
+/// A field of the score (by index) or any other name
+#[derive(Debug, Arbitrary)]
+enum Field {
+    Known(u8),
+    Other(String),
+}
+
+/// A value as entered; `Option` picks one of the field's dropdown options by index
+#[derive(Debug, Arbitrary)]
+enum Value {
+    Boolean(bool),
+    Number(f64),
+    Option(u8),
+    Text(String),
+}
+
+#[derive(Debug, Arbitrary)]
+struct Calculation {
+    score: u8,
+    inputs: Vec<(Field, Value)>,
+}
+
+fuzz_target!(|calculation: Calculation| {
+    let scores = scores();
+    let score = &scores[usize::from(calculation.score) % scores.len()];
+
+    let mut inputs = HashMap::new();
+    for (field, value) in calculation.inputs {
+        // Loaded scores have at least one input
+        let (name, input) = match field {
+            Field::Known(index) => {
+                let input = &score.inputs[usize::from(index) % score.inputs.len()];
+                (input.field.clone(), Some(input))
+            }
+            Field::Other(name) => (name, None),
+        };
+        let value = match value {
+            Value::Boolean(checked) => InputValue::Boolean(checked),
+            Value::Number(number) => InputValue::Number(number),
+            Value::Option(index) => match input.filter(|input| !input.options.is_empty()) {
+                Some(input) => InputValue::Dropdown(
+                    input.options[usize::from(index) % input.options.len()]
+                        .value
+                        .clone(),
+                ),
+                None => continue,
+            },
+            Value::Text(text) => InputValue::Dropdown(text),
+        };
+        inputs.insert(name, value);
+    }
+
+    let _ = calculate_score(score, &inputs);
+    let _ = calculate_partial(score, &inputs);
+});
@@ -0,0 +1,26 @@
+// load_score.rs
+// Arbitrary bytes as a score file: loading must fail with an error, never panic,
+// and a score that loads must survive coverage checks and its own test cases
+
+#![no_main]
+
+use klinscore::scores::{interpretation_coverage, load_score_from_file, run_test_cases};
+use libfuzzer_sys::fuzz_target;
+use std::sync::OnceLock;
+use tempfile::TempDir;
+
+/// One directory per fuzzing process; each input overwrites the same file
+fn score_dir() -> &'static TempDir {
+    static DIR: OnceLock<TempDir> = OnceLock::new();
+    DIR.get_or_init(|| TempDir::new().expect("temporary directory"))
+}
+
+fuzz_target!(|data: &[u8]| {
+    let path = score_dir().path().join("fuzz.yaml");
+    std::fs::write(&path, data).expect("write score file");
+
+    if let Ok(score) = load_score_from_file(&path) {
+        let _ = interpretation_coverage(&score);
+        let _ = run_test_cases(&score);
+    }
+});
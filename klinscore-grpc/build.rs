@@ -0,0 +1,41 @@
+// Generate the ScoreService stubs (proto/klinscore/v1/scores.proto) without protoc:
+// the messages are written out in src/proto.rs, only the service code is generated
+
+use tonic_build::manual::{Builder, Method, Service};
+
+fn method(name: &str, route_name: &str, input: &str, output: &str) -> Method {
+    Method::builder()
+        .name(name)
+        .route_name(route_name)
+        .input_type(format!("crate::proto::{}", input))
+        .output_type(format!("crate::proto::{}", output))
+        .codec_path("tonic::codec::ProstCodec")
+        .build()
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    let service = Service::builder()
+        .name("ScoreService")
+        .package("klinscore.v1")
+        .method(method(
+            "list_scores",
+            "ListScores",
+            "ListScoresRequest",
+            "ListScoresResponse",
+        ))
+        .method(method(
+            "get_definition",
+            "GetDefinition",
+            "GetDefinitionRequest",
+            "GetDefinitionResponse",
+        ))
+        .method(method(
+            "calculate",
+            "Calculate",
+            "CalculateRequest",
+            "CalculateResponse",
+        ))
+        .build();
+    Builder::new().compile(&[service]);
+}
@@ -0,0 +1,261 @@
+// klinscore-grpc
+// gRPC service for the scoring engine, for institutions that run their clinical
+// software as gRPC microservices. Mirrors the library API: list the scores, get a
+// score's definition, calculate. The contract is proto/klinscore/v1/scores.proto.
+
+pub mod proto;
+
+use klinscore::cli::{find_score, parse_specialty, CliError};
+use klinscore::config::ScoreDefinition;
+use klinscore::scores::dates::InvalidDate;
+use klinscore::scores::{CalculationRequest, InputValue, Quantity, ScoreFilter, ScoreLibrary};
+use proto::input_value::Value;
+use proto::score_service_server::ScoreService;
+use tonic::{Request, Response, Status};
+
+pub use proto::score_service_server::ScoreServiceServer;
+
+/// Default address of the server
+pub const DEFAULT_ADDRESS: &str = "127.0.0.1:50051";
+
+/// The service over a library of scores
+pub struct ScoreServer {
+    library: ScoreLibrary,
+}
+
+impl ScoreServer {
+    pub fn new(library: ScoreLibrary) -> Self {
+        Self { library }
+    }
+
+    /// A score by ID, name or alias
+    fn score(&self, name: &str) -> Result<(&str, &ScoreDefinition), Box<Status>> {
+        find_score(&self.library, name).map_err(|e| {
+            Box::new(match e {
+                CliError::UnknownScore(_) => Status::not_found(e.to_string()),
+                _ => Status::invalid_argument(e.to_string()),
+            })
+        })
+    }
+}
+
+/// "en" unless a language is given
+fn language(language: &str) -> &str {
+    if language.is_empty() {
+        "en"
+    } else {
+        language
+    }
+}
+
+#[tonic::async_trait]
+impl ScoreService for ScoreServer {
+    async fn list_scores(
+        &self,
+        request: Request<proto::ListScoresRequest>,
+    ) -> Result<Response<proto::ListScoresResponse>, Status> {
+        let request = request.into_inner();
+        let language = language(&request.language);
+        let specialty = if request.specialty.is_empty() {
+            None
+        } else {
+            let specialty = parse_specialty(&request.specialty)
+                .map_err(|e| Status::invalid_argument(e.to_string()))?;
+            Some(specialty)
+        };
+        let filter = ScoreFilter {
+            specialty,
+            ..ScoreFilter::default()
+        };
+        let scores = self
+            .library
+            .filter(&filter)
+            .into_iter()
+            .map(|(id, score)| proto::ScoreSummary {
+                id: id.to_string(),
+                name: score.name.text(language).to_string(),
+                specialty: if language == "de" {
+                    score.specialty.german()
+                } else {
+                    score.specialty.english()
+                }
+                .to_string(),
+                version: score.version.clone(),
+                deprecated: score.is_deprecated(),
+            })
+            .collect();
+        Ok(Response::new(proto::ListScoresResponse { scores }))
+    }
+
+    async fn get_definition(
+        &self,
+        request: Request<proto::GetDefinitionRequest>,
+    ) -> Result<Response<proto::GetDefinitionResponse>, Status> {
+        let (id, score) = self.score(&request.get_ref().score_id).map_err(|e| *e)?;
+        let definition_json =
+            serde_json::to_string(score).map_err(|e| Status::internal(e.to_string()))?;
+        Ok(Response::new(proto::GetDefinitionResponse {
+            score_id: id.to_string(),
+            definition_json,
+        }))
+    }
+
+    async fn calculate(
+        &self,
+        request: Request<proto::CalculateRequest>,
+    ) -> Result<Response<proto::CalculateResponse>, Status> {
+        let request = request.into_inner();
+        let language = language(&request.language);
+        let (id, score) = self.score(&request.score_id).map_err(|e| *e)?;
+
+        let mut inputs = CalculationRequest::new();
+        for (field, value) in request.inputs {
+            let value = match value.value {
+                Some(Value::Flag(flag)) => InputValue::Boolean(flag),
+                Some(Value::Number(number)) => InputValue::Number(number),
+                Some(Value::Choice(option)) => InputValue::Dropdown(option),
+                Some(Value::Date(date)) => InputValue::Date(
+                    date.parse()
+                        .map_err(|e: InvalidDate| Status::invalid_argument(e.to_string()))?,
+                ),
+                Some(Value::Quantity(quantity)) => InputValue::Quantity(Quantity {
+                    value: quantity.value,
+                    unit: quantity.unit,
+                }),
+                // An unset value counts as not entered
+                None => continue,
+            };
+            inputs = inputs.value(field, value);
+        }
+        let result = inputs
+            .calculate(score)
+            .map_err(|e| Status::invalid_argument(e.to_string()))?;
+
+        let result_json =
+            serde_json::to_string(&result).map_err(|e| Status::internal(e.to_string()))?;
+        Ok(Response::new(proto::CalculateResponse {
+            score_id: id.to_string(),
+            total_score: result.total_score,
+            risk: result.risk.text(language).to_string(),
+            risk_level: format!("{:?}", result.risk_level),
+            recommendation: result.recommendation.text(language).to_string(),
+            details: result
+                .details
+                .as_ref()
+                .map(|details| details.text(language).to_string())
+                .unwrap_or_default(),
+            field_scores: result
+                .field_scores
+                .iter()
+                .map(|field_score| proto::FieldScore {
+                    field: field_score.field.clone(),
+                    label: field_score.label.text(language).to_string(),
+                    points: field_score.points,
+                })
+                .collect(),
+            result_json,
+            provenance: result.provenance,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use klinscore::scores::load_bundled_scores;
+    use std::collections::HashMap;
+
+    fn input(value: Value) -> proto::InputValue {
+        proto::InputValue { value: Some(value) }
+    }
+
+    #[tokio::test]
+    async fn test_score_service() {
+        let server = ScoreServer::new(load_bundled_scores());
+
+        let list = server
+            .list_scores(Request::new(proto::ListScoresRequest {
+                specialty: "cardiology".to_string(),
+                language: String::new(),
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert!(list.scores.iter().any(|score| score.id == "cha2ds2_va"));
+        assert!(list
+            .scores
+            .iter()
+            .all(|score| score.specialty == "Cardiology"));
+
+        let definition = server
+            .get_definition(Request::new(proto::GetDefinitionRequest {
+                score_id: "HAS-BLED".to_string(),
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(definition.score_id, "has_bled");
+        let definition: ScoreDefinition =
+            serde_json::from_str(&definition.definition_json).unwrap();
+        assert!(!definition.inputs.is_empty());
+
+        let mut request = proto::CalculateRequest {
+            score_id: "cha2ds2_va".to_string(),
+            inputs: HashMap::from([
+                ("age".to_string(), input(Value::Number(72.0))),
+                ("heart_failure".to_string(), input(Value::Flag(true))),
+                ("hypertension".to_string(), input(Value::Flag(true))),
+            ]),
+            language: "de".to_string(),
+        };
+        let result = server
+            .calculate(Request::new(request.clone()))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(result.total_score, 3);
+        assert_eq!(result.risk_level, "High");
+        assert!(result.result_json.contains("\"total_score\":3"));
+
+        let egfr = |creatinine| proto::CalculateRequest {
+            score_id: "egfr_ckd_epi_2021".to_string(),
+            inputs: HashMap::from([
+                ("age".to_string(), input(Value::Number(60.0))),
+                (
+                    "sex".to_string(),
+                    input(Value::Choice("female".to_string())),
+                ),
+                ("creatinine".to_string(), input(creatinine)),
+            ]),
+            language: String::new(),
+        };
+        let conventional = server
+            .calculate(Request::new(egfr(Value::Quantity(proto::Quantity {
+                value: 1.2,
+                unit: "mg/dL".to_string(),
+            }))))
+            .await
+            .unwrap()
+            .into_inner();
+        let si = server
+            .calculate(Request::new(egfr(Value::Number(106.08))))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(conventional.total_score, si.total_score);
+
+        request
+            .inputs
+            .insert("smoker".to_string(), input(Value::Flag(true)));
+        let status = server.calculate(Request::new(request)).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+
+        let status = server
+            .get_definition(Request::new(proto::GetDefinitionRequest {
+                score_id: "nonexistent".to_string(),
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::NotFound);
+    }
+}
@@ -0,0 +1,63 @@
+// KlinScore gRPC server
+// main.rs
+//
+// Usage:
+//   klinscore-grpc [--address <host:port>] [--scores-dir <folder>]...
+//
+// Serves the bundled and user scores, plus those in the given folders, on
+// 127.0.0.1:50051 unless another address is given.
+
+use klinscore::scores::{load_score_library, user_scores_dir};
+use klinscore_grpc::{ScoreServer, ScoreServiceServer, DEFAULT_ADDRESS};
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use tonic::transport::Server;
+
+const USAGE: &str = "Usage: klinscore-grpc [--address <host:port>] [--scores-dir <folder>]...";
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    klinscore::logging::init();
+    let mut address = DEFAULT_ADDRESS.to_string();
+    let mut score_dirs: Vec<PathBuf> = user_scores_dir().into_iter().collect();
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match (arg.as_str(), args.next()) {
+            ("--address", Some(value)) => address = value,
+            ("--scores-dir", Some(value)) => score_dirs.push(value.into()),
+            _ => {
+                eprintln!("{}", USAGE);
+                return ExitCode::from(2);
+            }
+        }
+    }
+    let address: SocketAddr = match address.parse() {
+        Ok(address) => address,
+        Err(e) => {
+            eprintln!("klinscore-grpc: invalid address {}: {}", address, e);
+            return ExitCode::from(2);
+        }
+    };
+
+    let library = match load_score_library(&score_dirs) {
+        Ok(library) => library,
+        Err(e) => {
+            eprintln!("klinscore-grpc: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    eprintln!(
+        "klinscore-grpc: serving {} scores on {}",
+        library.count(),
+        address
+    );
+    let service = ScoreServiceServer::new(ScoreServer::new(library));
+    match Server::builder().add_service(service).serve(address).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("klinscore-grpc: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
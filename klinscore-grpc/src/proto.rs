@@ -0,0 +1,121 @@
+// proto.rs
+// Messages of proto/klinscore/v1/scores.proto, written out with prost's derives
+// (as prost-build would generate them) so that building needs no protoc
+
+use std::collections::HashMap;
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ListScoresRequest {
+    #[prost(string, tag = "1")]
+    pub specialty: String,
+    #[prost(string, tag = "2")]
+    pub language: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ScoreSummary {
+    #[prost(string, tag = "1")]
+    pub id: String,
+    #[prost(string, tag = "2")]
+    pub name: String,
+    #[prost(string, tag = "3")]
+    pub specialty: String,
+    #[prost(string, tag = "4")]
+    pub version: String,
+    #[prost(bool, tag = "5")]
+    pub deprecated: bool,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ListScoresResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub scores: Vec<ScoreSummary>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct GetDefinitionRequest {
+    #[prost(string, tag = "1")]
+    pub score_id: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct GetDefinitionResponse {
+    #[prost(string, tag = "1")]
+    pub score_id: String,
+    #[prost(string, tag = "2")]
+    pub definition_json: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct InputValue {
+    #[prost(oneof = "input_value::Value", tags = "1, 2, 3, 4, 5")]
+    pub value: Option<input_value::Value>,
+}
+
+pub mod input_value {
+    #[derive(Clone, PartialEq, prost::Oneof)]
+    pub enum Value {
+        #[prost(bool, tag = "1")]
+        Flag(bool),
+        #[prost(double, tag = "2")]
+        Number(f64),
+        #[prost(string, tag = "3")]
+        Choice(String),
+        #[prost(string, tag = "4")]
+        Date(String),
+        #[prost(message, tag = "5")]
+        Quantity(super::Quantity),
+    }
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Quantity {
+    #[prost(double, tag = "1")]
+    pub value: f64,
+    #[prost(string, tag = "2")]
+    pub unit: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct CalculateRequest {
+    #[prost(string, tag = "1")]
+    pub score_id: String,
+    #[prost(map = "string, message", tag = "2")]
+    pub inputs: HashMap<String, InputValue>,
+    #[prost(string, tag = "3")]
+    pub language: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct FieldScore {
+    #[prost(string, tag = "1")]
+    pub field: String,
+    #[prost(string, tag = "2")]
+    pub label: String,
+    #[prost(int32, tag = "3")]
+    pub points: i32,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct CalculateResponse {
+    #[prost(string, tag = "1")]
+    pub score_id: String,
+    #[prost(int32, tag = "2")]
+    pub total_score: i32,
+    #[prost(string, tag = "3")]
+    pub risk: String,
+    #[prost(string, tag = "4")]
+    pub risk_level: String,
+    #[prost(string, tag = "5")]
+    pub recommendation: String,
+    #[prost(string, tag = "6")]
+    pub details: String,
+    #[prost(message, repeated, tag = "7")]
+    pub field_scores: Vec<FieldScore>,
+    #[prost(string, tag = "8")]
+    pub result_json: String,
+    #[prost(string, tag = "9")]
+    pub provenance: String,
+}
+
+include!(concat!(env!("OUT_DIR"), "/klinscore.v1.ScoreService.rs"));
@@ -0,0 +1,236 @@
+// klinscore-py
+// Python bindings for the scoring engine, so data scientists apply the exact
+// production scoring logic to their data (e.g., rows of a pandas DataFrame)
+//
+//     import klinscore_py as ks
+//     ks.calculate("cha2ds2_va", {"age": 72, "heart_failure": True})
+//     df.apply(lambda row: ks.calculate("cha2ds2_va", row)["total_score"], axis=1)
+
+use klinscore::batch::{calculate_row, InputRow};
+use klinscore::cli::find_score;
+use klinscore::scores::{load_bundled_scores, load_score_library, ScoreLibrary};
+use pyo3::exceptions::{PyKeyError, PyOSError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+/// The bundled scores, for the module-level functions
+fn bundled() -> &'static ScoreLibrary {
+    static BUNDLED: OnceLock<ScoreLibrary> = OnceLock::new();
+    BUNDLED.get_or_init(load_bundled_scores)
+}
+
+/// A library of scores: the bundled ones plus any loaded from folders
+#[pyclass(frozen, module = "klinscore_py")]
+struct Library {
+    library: ScoreLibrary,
+}
+
+#[pymethods]
+impl Library {
+    /// IDs of all scores, sorted
+    fn score_ids(&self) -> Vec<String> {
+        score_ids(&self.library)
+    }
+
+    /// Definition of a score (by ID, name or alias) as a dict: inputs with their
+    /// type, unit, range and points, and the interpretation bands
+    fn describe<'py>(&self, py: Python<'py>, score: &str) -> PyResult<Bound<'py, PyAny>> {
+        describe(py, &self.library, score)
+    }
+
+    /// Calculate a score from a mapping of input field to value
+    ///
+    /// Booleans may be given as `True`/`False`, 1/0 or "yes"/"no", dropdowns by
+    /// the value of their option, lab values with their unit ("1.2 mg/dL") if not
+    /// in the unit of the score. `None` and NaN count as not entered, keys that
+    /// are not inputs of the score are ignored.
+    #[pyo3(signature = (score, inputs, language = "en"))]
+    fn calculate<'py>(
+        &self,
+        py: Python<'py>,
+        score: &str,
+        inputs: &Bound<'py, PyAny>,
+        language: &str,
+    ) -> PyResult<Bound<'py, PyDict>> {
+        calculate(py, &self.library, score, inputs, language)
+    }
+
+    fn __len__(&self) -> usize {
+        self.library.count()
+    }
+}
+
+/// Load the bundled scores plus those in `score_dirs`
+#[pyfunction(name = "load_library")]
+#[pyo3(signature = (score_dirs = Vec::new()))]
+fn py_load_library(score_dirs: Vec<PathBuf>) -> PyResult<Library> {
+    let library = load_score_library(&score_dirs).map_err(|e| PyOSError::new_err(e.to_string()))?;
+    Ok(Library { library })
+}
+
+/// IDs of the bundled scores, sorted
+#[pyfunction(name = "score_ids")]
+fn py_score_ids() -> Vec<String> {
+    score_ids(bundled())
+}
+
+/// Definition of a bundled score as a dict
+#[pyfunction(name = "describe")]
+fn py_describe<'py>(py: Python<'py>, score: &str) -> PyResult<Bound<'py, PyAny>> {
+    describe(py, bundled(), score)
+}
+
+/// Calculate a bundled score; see `Library.calculate`
+#[pyfunction(name = "calculate")]
+#[pyo3(signature = (score, inputs, language = "en"))]
+fn py_calculate<'py>(
+    py: Python<'py>,
+    score: &str,
+    inputs: &Bound<'py, PyAny>,
+    language: &str,
+) -> PyResult<Bound<'py, PyDict>> {
+    calculate(py, bundled(), score, inputs, language)
+}
+
+fn score_ids(library: &ScoreLibrary) -> Vec<String> {
+    let mut ids: Vec<String> = library.scores.keys().cloned().collect();
+    ids.sort();
+    ids
+}
+
+fn describe<'py>(
+    py: Python<'py>,
+    library: &ScoreLibrary,
+    score: &str,
+) -> PyResult<Bound<'py, PyAny>> {
+    let (_, score) = find_score(library, score).map_err(|e| PyKeyError::new_err(e.to_string()))?;
+    let json = serde_json::to_string(score).map_err(|e| PyValueError::new_err(e.to_string()))?;
+    py.import("json")?.call_method1("loads", (json,))
+}
+
+fn calculate<'py>(
+    py: Python<'py>,
+    library: &ScoreLibrary,
+    score: &str,
+    inputs: &Bound<'py, PyAny>,
+    language: &str,
+) -> PyResult<Bound<'py, PyDict>> {
+    let (id, score) = find_score(library, score).map_err(|e| PyKeyError::new_err(e.to_string()))?;
+
+    // Mappings and pandas rows both have `items()`
+    let mut row = InputRow::new();
+    for item in inputs.call_method0("items")?.try_iter()? {
+        let (key, value): (String, Bound<'py, PyAny>) = item?.extract()?;
+        if let Some(text) = input_text(&value)? {
+            row.insert(key, text);
+        }
+    }
+    let result = calculate_row(score, &row).map_err(PyValueError::new_err)?;
+
+    let field_scores = PyDict::new(py);
+    for field_score in &result.field_scores {
+        field_scores.set_item(&field_score.field, field_score.points)?;
+    }
+    let output = PyDict::new(py);
+    output.set_item("score", id)?;
+    output.set_item("total_score", result.total_score)?;
+    output.set_item("risk", result.risk.text(language))?;
+    output.set_item("risk_level", format!("{:?}", result.risk_level))?;
+    output.set_item("recommendation", result.recommendation.text(language))?;
+    output.set_item(
+        "details",
+        result
+            .details
+            .as_ref()
+            .map(|details| details.text(language)),
+    )?;
+    output.set_item("field_scores", field_scores)?;
+    output.set_item("provenance", &result.provenance)?;
+    Ok(output)
+}
+
+/// Text of an input value as in a batch file; `None` for missing values
+fn input_text(value: &Bound<'_, PyAny>) -> PyResult<Option<String>> {
+    if value.is_none() {
+        return Ok(None);
+    }
+    if let Ok(flag) = value.extract::<bool>() {
+        return Ok(Some(flag.to_string()));
+    }
+    if let Ok(number) = value.extract::<f64>() {
+        return Ok((!number.is_nan()).then(|| number.to_string()));
+    }
+    Ok(Some(value.str()?.to_string()))
+}
+
+#[pymodule]
+fn klinscore_py(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<Library>()?;
+    module.add_function(wrap_pyfunction!(py_load_library, module)?)?;
+    module.add_function(wrap_pyfunction!(py_score_ids, module)?)?;
+    module.add_function(wrap_pyfunction!(py_describe, module)?)?;
+    module.add_function(wrap_pyfunction!(py_calculate, module)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_calculate_from_dict() {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let inputs = PyDict::new(py);
+            inputs.set_item("age", 72).unwrap();
+            inputs.set_item("heart_failure", true).unwrap();
+            inputs.set_item("hypertension", "yes").unwrap();
+            inputs.set_item("diabetes", py.None()).unwrap();
+            inputs.set_item("mrn", "4711").unwrap();
+
+            let result = py_calculate(py, "cha2ds2_va", inputs.as_any(), "en").unwrap();
+            let total: i32 = result
+                .get_item("total_score")
+                .unwrap()
+                .unwrap()
+                .extract()
+                .unwrap();
+            assert_eq!(total, 3);
+            let risk_level: String = result
+                .get_item("risk_level")
+                .unwrap()
+                .unwrap()
+                .extract()
+                .unwrap();
+            assert_eq!(risk_level, "High");
+
+            inputs.set_item("age", f64::NAN).unwrap();
+            let error = py_calculate(py, "cha2ds2_va", inputs.as_any(), "en").unwrap_err();
+            assert!(error.is_instance_of::<PyValueError>(py));
+            assert!(py_calculate(py, "nonexistent", inputs.as_any(), "en")
+                .unwrap_err()
+                .is_instance_of::<PyKeyError>(py));
+
+            let egfr = |creatinine: &str| {
+                let inputs = PyDict::new(py);
+                inputs.set_item("age", 60).unwrap();
+                inputs.set_item("sex", "female").unwrap();
+                inputs.set_item("creatinine", creatinine).unwrap();
+                py_calculate(py, "egfr_ckd_epi_2021", inputs.as_any(), "en")
+                    .unwrap()
+                    .get_item("total_score")
+                    .unwrap()
+                    .unwrap()
+                    .extract::<i32>()
+                    .unwrap()
+            };
+            assert_eq!(egfr("1.2 mg/dL"), egfr("106.08"));
+
+            let description = py_describe(py, "HAS-BLED").unwrap();
+            let inputs = description.get_item("inputs").unwrap();
+            assert!(inputs.len().unwrap() > 5);
+        });
+    }
+}
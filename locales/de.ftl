@@ -0,0 +1,434 @@
+# KlinScore UI texts: German (Deutsch)
+
+## Common
+
+back = ← Zurück
+cancel = Abbrechen
+delete = Löschen
+undo = Rückgängig
+add = Hinzufügen
+remove = Entfernen
+import = Importieren
+calculate = Berechnen
+reset = Zurücksetzen
+error-title = Fehler
+loading = Laden...
+score-not-found = Score nicht gefunden
+recommendation = Empfehlung:
+points-breakdown = Punkteverteilung:
+no-points = Kein Kriterium hat Punkte beigetragen.
+details-show = Details ▼
+details-hide = Details ▲
+passphrase = Passphrase
+print-report = Bericht drucken
+app-subtitle = Quelloffener klinischer Score-Rechner für evidenzbasierte Medizin
+scores-count = { $count ->
+    [one] 1 Score
+   *[other] { $count } Scores
+}
+calculations-count = { $count ->
+    [one] 1 Berechnung
+   *[other] { $count } Berechnungen
+}
+
+## Header and tabs
+
+history = Verlauf
+about = Über
+settings = Einstellungen
+new-tab = + Neuer Tab
+tab-new-calculation = Neue Berechnung
+
+## Notifications
+
+file-damaged = { $file } ist beschädigt und wurde nicht geladen ({ $reason }). { $moved ->
+        [yes] Sie wurde nach { $path } verschoben.
+       *[no] Sie konnte nicht verschoben werden.
+    } { $restored ->
+        [yes] Die vorherige Version wurde aus der Sicherung wiederhergestellt.
+       *[no] Es werden Standardwerte verwendet.
+    }
+scores-load-failed = Scores konnten nicht geladen werden: { $error }
+export-done = Exportiert: { $file }
+export-open = Datei öffnen
+export-reveal = Im Ordner zeigen
+export-failed = Export fehlgeschlagen: { $error }
+print-failed = Drucken fehlgeschlagen: { $error }
+import-failed = Import fehlgeschlagen: { $error }
+
+## Loading and welcome
+
+loading-progress = Lade Scores { $loaded }/{ $total }…
+loading-library = Lade Score-Bibliothek...
+loading-error = Fehler beim Laden
+welcome-title = Willkommen bei KlinScore
+welcome-scores-loaded = { $count ->
+    [one] 1 Score geladen
+   *[other] { $count } Scores geladen
+}
+welcome-start = Score berechnen
+resume-score = Unvollständige Berechnung vom letzten Mal: { $score } ({ $count ->
+    [one] 1 Wert eingegeben
+   *[other] { $count } Werte eingegeben
+})
+resume-specialty = Zuletzt haben Sie einen Score in { $specialty } ausgewählt.
+resume-continue = Fortsetzen
+resume-discard = Verwerfen
+
+## Specialty and score selection
+
+specialty-selection-title = Fachgebiet auswählen
+specialty-selection-subtitle = Wählen Sie das Fachgebiet für die Score-Berechnung
+no-scores-loaded = Keine Scores geladen
+score-selection-title = { $specialty } - Score auswählen
+score-selection-subtitle = Wählen Sie einen Score zur Berechnung
+score-selection-back = ← Zurück zu Fachgebieten
+score-selection-empty = Keine Scores für dieses Fachgebiet verfügbar
+score-deprecated = Veraltet
+
+## Calculation
+
+calculation-back = ← Zurück zur Score-Auswahl
+calculation-error = Fehler: { $message }
+score-info = ℹ Score-Info
+deprecated-superseded = { $name } ist durch { $replacement } abgelöst.
+deprecated-switch = Zu { $replacement } wechseln →
+deprecated-no-replacement = { $name } ist veraltet und sollte nicht mehr verwendet werden.
+patient-label = Patient (optional):
+patient-placeholder = z. B. Bett 12 oder Fallnummer
+fhir-patient-id = FHIR-Patienten-ID:
+fhir-patient-placeholder = z. B. 12345
+fhir-prefill = Aus FHIR übernehmen
+fhir-prefill-loading = Patientendaten werden abgerufen…
+fhir-prefilled = { $count ->
+    [one] 1 Eingabe aus der Patientenakte übernommen: { $fields }. Bitte prüfen.
+   *[other] { $count } Eingaben aus der Patientenakte übernommen: { $fields }. Bitte prüfen.
+}
+fhir-prefilled-none = Keine passenden Werte in der Patientenakte gefunden.
+fhir-prefill-failed = Patientendaten konnten nicht abgerufen werden: { $error }
+wizard-on = Schritt für Schritt ausfüllen
+wizard-off = Alle Felder auf einer Seite
+wizard-general = Allgemein
+wizard-review = Überprüfen
+wizard-next = Weiter →
+wizard-step = Schritt { $step } von { $total }: { $title }
+wizard-edit = Bearbeiten
+wizard-missing = fehlt
+subtotal = Zwischensumme: { $points } Pkt.
+required-open = { $count ->
+    [one] (1 Pflichtfeld offen)
+   *[other] ({ $count } Pflichtfelder offen)
+}
+invalid-number = Keine gültige Zahl (z. B. 1,36)
+invalid-date = Kein gültiges Datum (z. B. 14.03.1956)
+date-placeholder = TT.MM.JJJJ
+date-today = Heute
+date-after-reference = Das Datum liegt nach dem Datum, bis zu dem gezählt wird
+elapsed-years = { $count ->
+    [one] 1 Jahr
+   *[other] { $count } Jahre
+}
+elapsed-weeks = { $count ->
+    [one] 1 Woche
+   *[other] { $count } Wochen
+}
+elapsed-days = { $count ->
+    [one] 1 Tag
+   *[other] { $count } Tage
+}
+invalid-duration = Keine gültige Dauer (z. B. 4:30 oder 4 h 30 min)
+duration-placeholder = z. B. 4:30 oder 4 h 30 min
+select-placeholder = Bitte auswählen...
+
+## Result
+
+result-score = Score: { $score }
+result-risk = Risiko: { $risk }
+result-risk-level = Risikostufe: { $level }
+result-details = Details:
+result-calculate-again = Neu berechnen
+result-export = Exportieren:
+result-patient-copy = Patientenexemplar (PDF)
+result-copy = Kopieren
+result-copied = Zusammenfassung in die Zwischenablage kopiert
+result-copy-markdown = Als Markdown kopieren
+result-markdown-copied = Markdown-Zusammenfassung in die Zwischenablage kopiert
+result-template = Vorlage:
+result-template-placeholder = Mit Vorlage exportieren…
+template-invalid = Exportvorlage nicht geladen: { $error }
+result-print = 🖨 Drucken
+breakdown-criterion = Kriterium
+breakdown-points = Punkte
+breakdown-total = Summe
+breakdown-show-all = Kriterien ohne Punkte anzeigen
+
+risk-level-VeryLow = sehr niedrig
+risk-level-Low = niedrig
+risk-level-Moderate = mittel
+risk-level-High = hoch
+risk-level-VeryHigh = sehr hoch
+risk-level-Critical = kritisch
+risk-level-None = informativ
+
+## Sessions
+
+session-placeholder = Sitzung für mehrere Scores, z. B. Prä-OP Bett 12
+session-start = Sitzung starten
+session-status = 📋 Sitzung: { $title } ({ $count ->
+        [one] 1 Berechnung
+       *[other] { $count } Berechnungen
+    })
+session-end = Sitzung beenden
+session-report-title = Sitzungsbericht
+
+## History
+
+history-title = Berechnungsverlauf
+history-empty = Noch keine Berechnungen durchgeführt.
+history-cleared = { $count ->
+    [one] 1 Berechnung gelöscht.
+   *[other] { $count } Berechnungen gelöscht.
+}
+history-clear = Verlauf löschen
+history-research-export = Forschungsexport (anonymisiert)
+history-count-filtered = { $matching } von { $total } Berechnungen
+history-newest-shown = , die neuesten { $visible } angezeigt
+history-export-all = Alle { $count } Einträge exportieren:
+history-report-title = Berechnungsverlauf
+history-export-filtered = { $count } gefilterte Einträge exportieren:
+history-no-match = Keine Einträge für diesen Filter.
+history-reopen = Erneut öffnen
+history-trend = Verlauf ({ $count } Werte)
+history-score = Score: { $score }
+history-show-more = Weitere { $count } anzeigen ({ $remaining } verbleibend)
+history-all-specialties = Alle Fachgebiete
+history-all-scores = Alle Scores
+history-date-placeholder = TT.MM.JJJJ
+history-search-placeholder = Suchen (Score, Risiko, Patient)…
+history-from = von
+history-to = bis
+history-clear-filter = Filter zurücksetzen
+history-invalid-date = Datum nicht erkannt (z. B. 12.02.2026)
+history-transfer = Verlauf an einen anderen Arbeitsplatz übertragen:
+history-transfer-save = Als Verlaufsdatei sichern (JSON)
+history-import-placeholder = Pfad zur Verlaufsdatei (.json) eines anderen Arbeitsplatzes
+history-imported = { $added ->
+    [one] 1 Eintrag importiert
+   *[other] { $added } Einträge importiert
+}, { $present } bereits vorhanden.
+history-score-not-loaded = Dieser Score ist nicht mehr geladen.
+history-version-changed = Berechnet mit Score-Version { $recorded }, aktuell ist { $current } geladen. Regeln können sich geändert haben.
+history-version-unknown = Berechnet mit einer früheren Score-Definition (Version unbekannt), aktuell ist { $current } geladen.
+clear-history-title = Verlauf löschen?
+clear-history-body = Alle { $count } Berechnungen werden gelöscht. Bis zum Beenden der App kann das Löschen rückgängig gemacht werden.
+
+## History encryption
+
+unlock-title = Verlauf entsperren
+unlock-body = Der Berechnungsverlauf ist verschlüsselt. Ohne Passphrase werden neue Berechnungen nicht gespeichert.
+unlock = Entsperren
+unlock-skip = Ohne Verlauf fortfahren
+unlock-wrong-passphrase = Falsche Passphrase.
+unlock-failed = Entsperren fehlgeschlagen: { $error }
+history-locked = 🔒 Der verschlüsselte Verlauf ist gesperrt; neue Berechnungen werden nicht gespeichert.
+passphrase-too-short = Die Passphrase muss mindestens { $min } Zeichen lang sein.
+passphrase-mismatch = Die Passphrasen stimmen nicht überein.
+passphrase-repeat = Passphrase wiederholen
+encryption-label = Verlauf verschlüsseln:
+encryption-hint = Der Verlauf kann quasi-identifizierende Daten enthalten. Mit einer Passphrase wird er verschlüsselt gespeichert und beim Start entsperrt. Ohne Passphrase ist er nicht wiederherstellbar.
+encryption-enable = Verschlüsseln
+encryption-enabled = Verlauf verschlüsselt.
+encryption-failed = Verschlüsseln fehlgeschlagen: { $error }
+encryption-active = Der Verlauf wird verschlüsselt gespeichert.
+encryption-disable = Verschlüsselung aufheben
+encryption-disabled = Verlauf wird unverschlüsselt gespeichert.
+decryption-failed = Entschlüsseln fehlgeschlagen: { $error }
+
+## Score information
+
+info-version = { $specialty } · Version { $version }
+info-intended-use = Zweckbestimmung
+info-regulatory-note = Regulatorischer Hinweis
+info-validated-in = Validiert in
+info-clinical-question = Klinische Fragestellung
+info-target-population = Zielpopulation
+info-time-horizon = Vorhersagezeitraum
+info-contraindications = Nicht anwenden bei
+info-category = Kategorie
+info-tags = Schlagwörter
+info-source = Quelle
+info-references = Literatur
+
+category-RiskStratification = Risikostratifizierung
+category-Screening = Screening
+category-Diagnostic = Diagnostik
+category-Classification = Klassifikation
+category-Other = Sonstiges
+
+## About
+
+about-title = Über KlinScore
+about-version = Version: { $version }
+about-scores-loaded = Scores geladen: { $count }
+about-specialties = Fachgebiete: { $specialties }
+about-guidelines = Leitlinien: ESC 2024, KDIGO, ACCP, ASA
+about-license = Lizenz: MIT / Apache 2.0
+about-disclaimer-title = Haftungsausschluss
+about-disclaimer = KlinScore ist ein Hilfsmittel zur klinischen Entscheidungsunterstützung. Es ersetzt nicht die klinische Beurteilung durch einen Arzt. Alle Scores sollten im klinischen Kontext des Patienten interpretiert werden. Keine Garantie für Richtigkeit oder Vollständigkeit.
+methodology-title = Score-Methodik & Quellenangaben
+methodology-subtitle = Vollständige Transparenz über die verwendeten Berechnungsverfahren und deren wissenschaftliche Grundlage.
+methodology-method = Methode: { $method }
+methodology-formula = Formelbasiert
+methodology-points = Punktebasiert
+methodology-points-detail = Summe der Punkte aller zutreffenden Kriterien
+methodology-inputs = Eingaben: { $inputs }
+methodology-input-boolean = Ja/Nein
+methodology-input-number = Zahl
+methodology-input-dropdown = Auswahl
+methodology-input-date = Datum
+methodology-input-duration = Dauer
+methodology-range = Erreichbarer Wertebereich: { $min } – { $max }
+methodology-calculation = Berechnung:
+methodology-reference = Referenz ({ $source })
+formula-ckd-epi-2021 =
+    eGFR = 142 × min(Scr/κ, 1)^α × max(Scr/κ, 1)^(−1.200) × 0.9938^Alter × (1.012 bei Frauen)
+    Wobei: Weiblich κ=0.7, α=−0.241 | Männlich κ=0.9, α=−0.302
+    Scr in mg/dL (Eingabe μmol/L ÷ 88.4)
+formula-kfre-4var =
+    Risiko = 1 − 0.9832^exp(Summe)
+    Summe = −0.2201×(Alter/10 − 7.036) + 0.2467×(männl. − 0.5642) − 0.5567×(eGFR/5 − 7.222) + 0.4510×(ln(ACR) − 5.137)
+    ACR-Eingabe: mg/mmol, intern umgerechnet in mg/g (× 8.84)
+packs-title = --- Installierte Score-Pakete ---
+pack-unverified = Nicht geprüft (keine Prüfsummen)
+pack-verified = Prüfsummen bestätigt (unsigniert)
+pack-untrusted = Prüfsummen und Signatur gültig, aber Schlüssel { $key } gehört keinem vertrauenswürdigen Herausgeber
+pack-signed = Prüfsummen und Signatur bestätigt, vertrauenswürdiger Herausgeberschlüssel { $key }
+pack-failed = Integritätsprüfung fehlgeschlagen: { $reason }
+
+## Settings
+
+theme-label = Farbschema:
+theme-placeholder = Farbschema wählen
+theme-current = Aktuell: { $theme }
+theme-Light = Hell
+theme-Dark = Dunkel
+theme-Sepia = Sepia
+theme-HighContrast = Hoher Kontrast
+theme-Custom = Eigenes
+theme-background = Hintergrund
+theme-primary = Akzentfarbe
+theme-risk-colors = Risikofarben für
+theme-presets = Vorlagen:
+theme-invalid-color = Ungültige Farbe, erwartet z. B. #005EB8
+theme-preset-hospital-blue = Klinik-Blau
+theme-preset-high-contrast = Hoher Kontrast
+help-hints = Hilfetexte an den Eingabefeldern anzeigen (ⓘ)
+wizard-long-scores = Lange Scores (ab { $count } Feldern) Schritt für Schritt ausfüllen
+accessibility = Barrierefreiheit:
+high-contrast = Hoher Kontrast (weiße Schrift auf Schwarz)
+touch-mode = Touch-Bedienung: große Schaltflächen und deutliche Fokusrahmen
+touch-hint = Risikostufen werden immer auch mit Symbol und Text angezeigt.
+units-label = Laborwerte:
+unit-system-Si = SI-Einheiten (z. B. μmol/L)
+unit-system-Conventional = Konventionelle Einheiten (z. B. mg/dL)
+units-hint = Gilt für Laborwerte, die in beiden Einheiten angeboten werden. Werte werden für die Berechnung in SI-Einheiten umgerechnet und in SI-Einheiten gespeichert.
+research-label = Zeitstempel im Forschungsexport:
+research-hint = Fallkennungen und Notizen werden immer entfernt. Aktuell: { $precision }
+precision-Date = Nur Datum
+precision-Month = Monat
+precision-Year = Jahr
+precision-Omit = Keine Zeitstempel
+patient-labels-disabled = Keine Patientenkennung erfassen (Feld "Patient" ausblenden)
+retention-label = Verlauf automatisch bereinigen:
+retention-hint = { $mode }. Wird beim Start und nach jeder Berechnung angewendet.
+retention-invalid-limit = Bitte eine ganze Zahl ab 1 eingeben.
+retention-keep-all = Alle behalten
+retention-last-entries = Letzte N Einträge
+retention-last-days = Letzte N Tage
+score-dirs = Zusätzliche Score-Ordner:
+score-dirs-hint = YAML-Scores aus diesen Ordnern (z. B. hausinterne Scores) werden geladen und ersetzen gleichnamige mitgelieferte Scores.
+score-dirs-placeholder = Pfad zum Ordner
+score-dir-not-found = Ordner nicht gefunden: { $path }
+strict-loading = Strikter Lademodus: Start verweigern, wenn eine Score-Datei ungültig ist
+pack-import = Score-Paket importieren:
+pack-import-hint = Ein .klinpack-Paket (ZIP mit klinpack.yaml) wird geprüft und in den Benutzerordner für Scores installiert.
+pack-placeholder = Pfad zur .klinpack-Datei
+pack-no-user-dir = Kein Benutzerordner für Scores verfügbar
+trusted-keys = Vertrauenswürdige Herausgeber von Paketen:
+trusted-keys-hint = Ein signiertes Paket gilt nur als bestätigt, wenn sein Schlüssel hier eingetragen ist. Tragen Sie den öffentlichen ed25519-Schlüssel (hex) ein, den Sie vom Herausgeber erhalten haben, nicht den aus dem Paket.
+trusted-key-placeholder = Öffentlicher Schlüssel (64 Hex-Zeichen)
+trusted-key-invalid = Kein öffentlicher ed25519-Schlüssel: { $key }
+pack-installed = Score-Paket '{ $name }' { $version } installiert ({ $count ->
+        [one] 1 Score
+       *[other] { $count } Scores
+    }). { $integrity }
+data-location = Datenablage:
+data-location-platform = Benutzerprofil
+data-location-portable = Portabel (neben dem Programm)
+data-location-custom = Gewählter Ordner (--data-dir)
+portable-mode = Portabler Modus: Daten neben dem Programm speichern (z. B. auf einem USB-Stick)
+portable-mode-hint = Gilt ab dem nächsten Start. Vorhandene Daten werden nicht verschoben; der Verlauf lässt sich als Verlaufsdatei übertragen.
+portable-mode-failed = Portabler Modus konnte nicht geändert werden: { $error }
+log-file = Diagnoseprotokoll schreiben (klinscore.log im Datenordner)
+log-file-hint = Zeichnet Warnungen und Ereignisse auf, etwa übersprungene Score-Dateien oder fehlgeschlagene Exporte. Enthält keine Eingaben oder Ergebnisse.
+export-dir = Exportordner:
+export-dir-hint = Der Speichern-Dialog für Exporte öffnet sich in diesem Ordner; jeder Export kann trotzdem woanders gespeichert werden.
+export-dir-default = Dokumente-Ordner (Standard)
+export-dir-choose = Ordner wählen…
+export-dir-choose-title = Exportordner wählen
+export-dir-reset = Standard verwenden
+anonymize-label = Anonymisierte Exporte:
+anonymize-timestamps = Berechnungszeitpunkt entfernen
+anonymize-patient-labels = Patientenbezeichnungen und Sitzungstitel entfernen
+anonymize-notes = Freitext-Notizen entfernen
+anonymize-hint = Gilt für alle Exporte, Ausdrucke und kopierten Ergebnisse, z. B. für Lehrmaterial oder Forschungsdaten. Der Verlauf selbst bleibt unverändert.
+pdf-report = PDF-Berichte:
+pdf-report-header = Kopfzeile (Einrichtung):
+pdf-report-header-placeholder = z. B. Klinikum Nord – Klinik für Anästhesiologie
+pdf-report-footer = Fußzeile:
+pdf-report-footer-placeholder = z. B. Adresse, Telefonnummer
+pdf-report-bilingual = Zweisprachige Berichte: Texte zusätzlich auf { $language } drucken
+pdf-report-qr-code = QR-Code: das Ergebnis (oder seine SHA-256-Prüfsumme) als QR-Code unter jedes Ergebnis drucken
+fhir-server = FHIR-Server
+fhir-server-hint = Basis-URL eines FHIR-R4-Servers. Formulare von Scores, deren Eingaben LOINC-Codes angeben, lassen sich dann per Patienten-ID mit den neuesten Werten des Patienten vorausfüllen.
+fhir-server-placeholder = z. B. https://fhir.klinikum.example/r4
+seal-label = Fälschungssichere Exporte:
+seal-exports = PDF- und JSON-Exporte mit einem SHA-256-Hash versiegeln
+sign-exports = Siegel zusätzlich mit dem lokalen Schlüssel dieser Installation signieren
+signing-key = Öffentlicher Schlüssel: { $key }
+signing-key-failed = Signaturschlüssel nicht verfügbar: { $error }
+seal-hint = Empfänger prüfen eine versiegelte Datei mit klinscore-verify; jede Änderung wird gemeldet. Geben Sie den öffentlichen Schlüssel weiter, damit sie sehen, dass die Datei hier signiert wurde (klinscore-verify --key).
+export-save-title = Export speichern
+backups = Sicherungen:
+backups-hint = Einmal täglich wird eine Kopie der Einstellungen und des Verlaufs im Ordner „backups“ des Datenordners abgelegt; die letzten { $count } bleiben erhalten. Vor dem Wiederherstellen wird der aktuelle Stand als weitere Sicherung abgelegt. Ein verschlüsselter Verlauf (🔒) braucht die damalige Passphrase.
+backups-empty = Noch keine Sicherungen.
+backup-restore = Wiederherstellen
+backup-restored = Sicherung { $name } wiederhergestellt.
+backup-restore-failed = Wiederherstellen der Sicherung fehlgeschlagen: { $error }
+settings-transfer = Einstellungen teilen:
+settings-transfer-hint = Alle Einstellungen (Farbschema, Sprache, Barrierefreiheit, Export- und Score-Ordner, Verlaufsoptionen) in eine Datei speichern und diese an einem anderen Arbeitsplatz laden, um in der ganzen Abteilung dieselbe Konfiguration zu verwenden.
+settings-export = Einstellungen exportieren…
+settings-import = Einstellungen importieren…
+settings-import-title = Einstellungen importieren
+settings-imported = Einstellungen aus { $file } importiert.
+
+## Specialties
+
+specialty-Cardiology = Kardiologie
+specialty-Nephrology = Nephrologie
+specialty-Anesthesiology = Anästhesiologie
+specialty-Emergency = Notfallmedizin
+specialty-InternalMedicine = Innere Medizin
+specialty-Surgery = Chirurgie
+specialty-Pulmonology = Pneumologie
+specialty-Neurology = Neurologie
+specialty-Gastroenterology = Gastroenterologie
+specialty-Hematology = Hämatologie
+specialty-Oncology = Onkologie
+specialty-Endocrinology = Endokrinologie
+specialty-InfectiousDiseases = Infektiologie
+specialty-Geriatrics = Geriatrie
+specialty-Pediatrics = Pädiatrie
+specialty-IntensiveCare = Intensivmedizin
+specialty-Psychiatry = Psychiatrie
+specialty-Other = Sonstiges
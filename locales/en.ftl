@@ -0,0 +1,437 @@
+# KlinScore UI texts: English
+#
+# Every catalog in this folder defines the same messages. When adding a
+# message, add it to all of them; `cargo test` checks that none is missing.
+
+## Common
+
+back = ← Back
+cancel = Cancel
+delete = Delete
+undo = Undo
+add = Add
+remove = Remove
+import = Import
+calculate = Calculate
+reset = Reset
+error-title = Error
+loading = Loading...
+score-not-found = Score not found
+recommendation = Recommendation:
+points-breakdown = Points Breakdown:
+no-points = No criterion contributed points.
+details-show = Details ▼
+details-hide = Details ▲
+passphrase = Passphrase
+print-report = Print report
+app-subtitle = Open-source clinical score calculator for evidence-based medicine
+scores-count = { $count ->
+    [one] 1 score
+   *[other] { $count } scores
+}
+calculations-count = { $count ->
+    [one] 1 calculation
+   *[other] { $count } calculations
+}
+
+## Header and tabs
+
+history = History
+about = About
+settings = Settings
+new-tab = + New tab
+tab-new-calculation = New calculation
+
+## Notifications
+
+file-damaged = { $file } is damaged and was not loaded ({ $reason }). { $moved ->
+        [yes] It was moved to { $path }.
+       *[no] It could not be moved aside.
+    } { $restored ->
+        [yes] The previous version was restored from the backup.
+       *[no] Defaults are used.
+    }
+scores-load-failed = Failed to load scores: { $error }
+export-done = Exported: { $file }
+export-open = Open file
+export-reveal = Show in folder
+export-failed = Export failed: { $error }
+print-failed = Printing failed: { $error }
+import-failed = Import failed: { $error }
+
+## Loading and welcome
+
+loading-progress = Loading { $loaded }/{ $total } scores…
+loading-library = Loading score library...
+loading-error = Loading Error
+welcome-title = Welcome to KlinScore
+welcome-scores-loaded = { $count ->
+    [one] 1 score loaded
+   *[other] { $count } scores loaded
+}
+welcome-start = Calculate Score
+resume-score = Unfinished calculation from last time: { $score } ({ $count ->
+    [one] 1 value entered
+   *[other] { $count } values entered
+})
+resume-specialty = Last time you were choosing a score in { $specialty }.
+resume-continue = Continue
+resume-discard = Discard
+
+## Specialty and score selection
+
+specialty-selection-title = Select Medical Specialty
+specialty-selection-subtitle = Choose the medical specialty for score calculation
+no-scores-loaded = No scores loaded
+score-selection-title = { $specialty } - Select Score
+score-selection-subtitle = Choose a score to calculate
+score-selection-back = ← Back to Specialties
+score-selection-empty = No scores available for this specialty
+score-deprecated = Deprecated
+
+## Calculation
+
+calculation-back = ← Back to Score Selection
+calculation-error = Error: { $message }
+score-info = ℹ Score info
+deprecated-superseded = { $name } is superseded by { $replacement }.
+deprecated-switch = Switch to { $replacement } →
+deprecated-no-replacement = { $name } is deprecated and should no longer be used.
+patient-label = Patient (optional):
+patient-placeholder = e.g., Bed 12 or case number
+fhir-patient-id = FHIR patient ID:
+fhir-patient-placeholder = e.g., 12345
+fhir-prefill = Prefill from FHIR
+fhir-prefill-loading = Fetching patient data…
+fhir-prefilled = { $count ->
+    [one] Filled 1 input from the patient record: { $fields }. Please check it.
+   *[other] Filled { $count } inputs from the patient record: { $fields }. Please check them.
+}
+fhir-prefilled-none = No matching values found in the patient record.
+fhir-prefill-failed = Could not fetch patient data: { $error }
+wizard-on = Fill in step by step
+wizard-off = All fields on one page
+wizard-general = General
+wizard-review = Review
+wizard-next = Next →
+wizard-step = Step { $step } of { $total }: { $title }
+wizard-edit = Edit
+wizard-missing = missing
+subtotal = Subtotal: { $points } pts
+required-open = { $count ->
+    [one] (1 required field open)
+   *[other] ({ $count } required fields open)
+}
+invalid-number = Not a valid number (e.g., 1.36)
+invalid-date = Not a valid date (e.g., 1956-03-14)
+date-placeholder = YYYY-MM-DD
+date-today = Today
+date-after-reference = The date is later than the date it is counted until
+elapsed-years = { $count ->
+    [one] 1 year
+   *[other] { $count } years
+}
+elapsed-weeks = { $count ->
+    [one] 1 week
+   *[other] { $count } weeks
+}
+elapsed-days = { $count ->
+    [one] 1 day
+   *[other] { $count } days
+}
+invalid-duration = Not a valid duration (e.g., 4:30 or 4 h 30 min)
+duration-placeholder = e.g. 4:30 or 4 h 30 min
+select-placeholder = Please select...
+
+## Result
+
+result-score = Score: { $score }
+result-risk = Risk: { $risk }
+result-risk-level = Risk level: { $level }
+result-details = Details:
+result-calculate-again = Calculate Again
+result-export = Export:
+result-patient-copy = Patient copy (PDF)
+result-copy = Copy
+result-copied = Summary copied to the clipboard
+result-copy-markdown = Copy as Markdown
+result-markdown-copied = Markdown summary copied to the clipboard
+result-template = Template:
+result-template-placeholder = Export with template…
+template-invalid = Export template not loaded: { $error }
+result-print = 🖨 Print
+breakdown-criterion = Criterion
+breakdown-points = Points
+breakdown-total = Total
+breakdown-show-all = Show criteria without points
+
+risk-level-VeryLow = very low
+risk-level-Low = low
+risk-level-Moderate = moderate
+risk-level-High = high
+risk-level-VeryHigh = very high
+risk-level-Critical = critical
+risk-level-None = informational
+
+## Sessions
+
+session-placeholder = Session for several scores, e.g. Pre-op bed 12
+session-start = Start session
+session-status = 📋 Session: { $title } ({ $count ->
+        [one] 1 calculation
+       *[other] { $count } calculations
+    })
+session-end = End session
+session-report-title = Session report
+
+## History
+
+history-title = Calculation History
+history-empty = No calculations yet.
+history-cleared = { $count ->
+    [one] 1 calculation deleted.
+   *[other] { $count } calculations deleted.
+}
+history-clear = Clear History
+history-research-export = Research export (de-identified)
+history-count-filtered = { $matching } of { $total } calculations
+history-newest-shown = , newest { $visible } shown
+history-export-all = Export all { $count } entries:
+history-report-title = Calculation history
+history-export-filtered = Export { $count } filtered entries:
+history-no-match = No entries match this filter.
+history-reopen = Reopen
+history-trend = Trend ({ $count } values)
+history-score = Score: { $score }
+history-show-more = Show { $count } more ({ $remaining } remaining)
+history-all-specialties = All specialties
+history-all-scores = All scores
+history-date-placeholder = YYYY-MM-DD
+history-search-placeholder = Search (score, risk, patient)…
+history-from = from
+history-to = to
+history-clear-filter = Clear filter
+history-invalid-date = Date not recognized (e.g., 2026-02-12)
+history-transfer = Move the history to another workstation:
+history-transfer-save = Save as history file (JSON)
+history-import-placeholder = Path to the history file (.json) of another workstation
+history-imported = { $added ->
+    [one] 1 entry imported
+   *[other] { $added } entries imported
+}, { $present } already present.
+history-score-not-loaded = This score is no longer loaded.
+history-version-changed = Calculated with score version { $recorded }; version { $current } is now loaded. Rules may have changed.
+history-version-unknown = Calculated with an earlier score definition (version unknown); version { $current } is now loaded.
+clear-history-title = Clear history?
+clear-history-body = All { $count } calculations will be deleted. Until the app is closed, this can be undone.
+
+## History encryption
+
+unlock-title = Unlock history
+unlock-body = The calculation history is encrypted. Without the passphrase, new calculations are not saved.
+unlock = Unlock
+unlock-skip = Continue without history
+unlock-wrong-passphrase = Wrong passphrase.
+unlock-failed = Unlocking failed: { $error }
+history-locked = 🔒 The encrypted history is locked; new calculations are not saved.
+passphrase-too-short = The passphrase must be at least { $min } characters long.
+passphrase-mismatch = The passphrases do not match.
+passphrase-repeat = Repeat passphrase
+encryption-label = Encrypt history:
+encryption-hint = The history may contain quasi-identifying data. With a passphrase it is stored encrypted and unlocked at startup. It cannot be recovered without the passphrase.
+encryption-enable = Encrypt
+encryption-enabled = History encrypted.
+encryption-failed = Encryption failed: { $error }
+encryption-active = The history is stored encrypted.
+encryption-disable = Remove encryption
+encryption-disabled = History is stored unencrypted.
+decryption-failed = Decryption failed: { $error }
+
+## Score information
+
+info-version = { $specialty } · Version { $version }
+info-intended-use = Intended use
+info-regulatory-note = Regulatory note
+info-validated-in = Validated in
+info-clinical-question = Clinical question
+info-target-population = Target population
+info-time-horizon = Time horizon
+info-contraindications = Do not use in
+info-category = Category
+info-tags = Tags
+info-source = Source
+info-references = References
+
+category-RiskStratification = Risk stratification
+category-Screening = Screening
+category-Diagnostic = Diagnostic
+category-Classification = Classification
+category-Other = Other
+
+## About
+
+about-title = About KlinScore
+about-version = Version: { $version }
+about-scores-loaded = Scores loaded: { $count }
+about-specialties = Specialties: { $specialties }
+about-guidelines = Guidelines: ESC 2024, KDIGO, ACCP, ASA
+about-license = License: MIT / Apache 2.0
+about-disclaimer-title = Disclaimer
+about-disclaimer = KlinScore is a clinical decision support tool. It does not replace clinical judgment by a physician. All scores should be interpreted in the patient's clinical context. No guarantee of accuracy or completeness.
+methodology-title = Score Methodology & Sources
+methodology-subtitle = Full transparency on calculation methods and their scientific basis.
+methodology-method = Method: { $method }
+methodology-formula = Formula-based
+methodology-points = Point-based
+methodology-points-detail = Sum of points for all applicable criteria
+methodology-inputs = Inputs: { $inputs }
+methodology-input-boolean = Yes/No
+methodology-input-number = Number
+methodology-input-dropdown = Selection
+methodology-input-date = Date
+methodology-input-duration = Duration
+methodology-range = Achievable range: { $min } – { $max }
+methodology-calculation = Calculation:
+methodology-reference = Reference ({ $source })
+formula-ckd-epi-2021 =
+    eGFR = 142 × min(Scr/κ, 1)^α × max(Scr/κ, 1)^(−1.200) × 0.9938^age × (1.012 if female)
+    Where: Female κ=0.7, α=−0.241 | Male κ=0.9, α=−0.302
+    Scr in mg/dL (input μmol/L ÷ 88.4)
+formula-kfre-4var =
+    Risk = 1 − 0.9832^exp(sum)
+    sum = −0.2201×(age/10 − 7.036) + 0.2467×(male − 0.5642) − 0.5567×(eGFR/5 − 7.222) + 0.4510×(ln(ACR) − 5.137)
+    ACR input: mg/mmol, converted to mg/g internally (× 8.84)
+packs-title = --- Installed score packs ---
+pack-unverified = Not verified (no checksums)
+pack-verified = Checksums verified (unsigned)
+pack-untrusted = Checksums and signature valid, but key { $key } is not a trusted publisher key
+pack-signed = Checksums and signature verified, trusted publisher key { $key }
+pack-failed = Integrity check failed: { $reason }
+
+## Settings
+
+theme-label = Theme:
+theme-placeholder = Select theme
+theme-current = Current: { $theme }
+theme-Light = Light
+theme-Dark = Dark
+theme-Sepia = Sepia
+theme-HighContrast = High contrast
+theme-Custom = Custom
+theme-background = Background
+theme-primary = Primary color
+theme-risk-colors = Risk colors for
+theme-presets = Presets:
+theme-invalid-color = Invalid color, expected e.g. #005EB8
+theme-preset-hospital-blue = Hospital blue
+theme-preset-high-contrast = High contrast
+help-hints = Show help hints next to input fields (ⓘ)
+wizard-long-scores = Fill in long scores ({ $count }+ fields) step by step
+accessibility = Accessibility:
+high-contrast = High contrast (white text on black)
+touch-mode = Touch mode: large buttons and bold focus outlines
+touch-hint = Risk levels are always shown with an icon and text as well.
+units-label = Lab values:
+unit-system-Si = SI units (e.g., μmol/L)
+unit-system-Conventional = Conventional units (e.g., mg/dL)
+units-hint = Applies to lab values offered in both units. Values are converted to SI units for scoring and saved in SI units.
+research-label = Timestamps in research export:
+research-hint = Case IDs and notes are always removed. Current: { $precision }
+precision-Date = Date only
+precision-Month = Month
+precision-Year = Year
+precision-Omit = No timestamps
+patient-labels-disabled = Do not record patient labels (hide the "Patient" field)
+retention-label = Prune history automatically:
+retention-hint = { $mode }. Applied on startup and after each calculation.
+retention-invalid-limit = Please enter a whole number of at least 1.
+retention-keep-all = Keep all
+retention-last-entries = Last N entries
+retention-last-days = Last N days
+score-dirs = Additional score directories:
+score-dirs-hint = YAML scores in these folders (e.g., hospital-specific scores) are loaded and replace bundled scores with the same name.
+score-dirs-placeholder = Path to folder
+score-dir-not-found = Directory not found: { $path }
+strict-loading = Strict loading: refuse to start if any score file is invalid
+pack-import = Import score pack:
+pack-import-hint = A .klinpack bundle (zip with klinpack.yaml) is validated and installed into the user score directory.
+pack-placeholder = Path to .klinpack file
+pack-no-user-dir = No user score directory available
+trusted-keys = Trusted pack publishers:
+trusted-keys-hint = A signed pack counts as verified only if its key is listed here. Add the ed25519 public key (hex) the publisher gave you, not the one found in the pack.
+trusted-key-placeholder = Public key (64 hex characters)
+trusted-key-invalid = Not an ed25519 public key: { $key }
+pack-installed = Score pack '{ $name }' { $version } installed ({ $count ->
+        [one] 1 score
+       *[other] { $count } scores
+    }). { $integrity }
+data-location = Data location:
+data-location-platform = User profile
+data-location-portable = Portable (next to the program)
+data-location-custom = Chosen folder (--data-dir)
+portable-mode = Portable mode: keep the data next to the program (e.g., on a USB stick)
+portable-mode-hint = Takes effect on the next start. Existing data is not moved; the history can be moved as a history file.
+portable-mode-failed = Could not change portable mode: { $error }
+log-file = Write a diagnostic log (klinscore.log in the data folder)
+log-file-hint = Records warnings and events such as skipped score files or failed exports. Contains no inputs or results.
+export-dir = Export folder:
+export-dir-hint = The save dialog for exports opens in this folder; each export can still be saved elsewhere.
+export-dir-default = Documents folder (default)
+export-dir-choose = Choose folder…
+export-dir-choose-title = Choose export folder
+export-dir-reset = Use default
+anonymize-label = Anonymized exports:
+anonymize-timestamps = Remove the time of calculation
+anonymize-patient-labels = Remove patient labels and session titles
+anonymize-notes = Remove free-text notes
+anonymize-hint = Applies to every export, print and copied result, e.g. for teaching material or research sharing. The history itself is not changed.
+pdf-report = PDF reports:
+pdf-report-header = Header (institution):
+pdf-report-header-placeholder = e.g., Hospital North – Department of Anaesthesiology
+pdf-report-footer = Footer:
+pdf-report-footer-placeholder = e.g., address, phone number
+pdf-report-bilingual = Bilingual reports: also print the texts in { $language }
+pdf-report-qr-code = QR code: print the result (or its SHA-256 checksum) as a QR code under each result
+fhir-server = FHIR server
+fhir-server-hint = Base URL of a FHIR R4 server. Forms of scores whose inputs declare LOINC codes can then be prefilled with the patient's latest values by patient ID.
+fhir-server-placeholder = e.g., https://fhir.hospital.example/r4
+seal-label = Tamper-evident exports:
+seal-exports = Seal PDF and JSON exports with a SHA-256 hash
+sign-exports = Also sign the seal with the local key of this installation
+signing-key = Public key: { $key }
+signing-key-failed = Signing key not available: { $error }
+seal-hint = Recipients check a sealed file with klinscore-verify; any change to it is reported. Pass on the public key so they can see the file was signed here (klinscore-verify --key).
+export-save-title = Save export
+backups = Backups:
+backups-hint = Once a day, a copy of the settings and the history is kept in the backups folder of the data location; the last { $count } are kept. Restoring first saves the current state as another backup. An encrypted history (🔒) needs the passphrase it had then.
+backups-empty = No backups yet.
+backup-restore = Restore
+backup-restored = Backup { $name } restored.
+backup-restore-failed = Restoring the backup failed: { $error }
+settings-transfer = Share settings:
+settings-transfer-hint = Save all settings (theme, language, accessibility, export and score folders, history options) to a file, and load such a file on another workstation to use the same configuration across a department.
+settings-export = Export settings…
+settings-import = Import settings…
+settings-import-title = Import settings
+settings-imported = Settings imported from { $file }.
+
+## Specialties
+
+specialty-Cardiology = Cardiology
+specialty-Nephrology = Nephrology
+specialty-Anesthesiology = Anesthesiology
+specialty-Emergency = Emergency Medicine
+specialty-InternalMedicine = Internal Medicine
+specialty-Surgery = Surgery
+specialty-Pulmonology = Pulmonology
+specialty-Neurology = Neurology
+specialty-Gastroenterology = Gastroenterology
+specialty-Hematology = Hematology
+specialty-Oncology = Oncology
+specialty-Endocrinology = Endocrinology
+specialty-InfectiousDiseases = Infectious Diseases
+specialty-Geriatrics = Geriatrics
+specialty-Pediatrics = Pediatrics
+specialty-IntensiveCare = Intensive Care
+specialty-Psychiatry = Psychiatry
+specialty-Other = Other
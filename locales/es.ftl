@@ -0,0 +1,434 @@
+# KlinScore UI texts: Spanish (Español)
+
+## Common
+
+back = ← Volver
+cancel = Cancelar
+delete = Eliminar
+undo = Deshacer
+add = Añadir
+remove = Quitar
+import = Importar
+calculate = Calcular
+reset = Restablecer
+error-title = Error
+loading = Cargando...
+score-not-found = Escala no encontrada
+recommendation = Recomendación:
+points-breakdown = Desglose de puntos:
+no-points = Ningún criterio aportó puntos.
+details-show = Detalles ▼
+details-hide = Detalles ▲
+passphrase = Frase de contraseña
+print-report = Imprimir informe
+app-subtitle = Calculadora de escalas clínicas de código abierto para la medicina basada en la evidencia
+scores-count = { $count ->
+    [one] 1 escala
+   *[other] { $count } escalas
+}
+calculations-count = { $count ->
+    [one] 1 cálculo
+   *[other] { $count } cálculos
+}
+
+## Header and tabs
+
+history = Historial
+about = Acerca de
+settings = Ajustes
+new-tab = + Nueva pestaña
+tab-new-calculation = Nuevo cálculo
+
+## Notifications
+
+file-damaged = { $file } está dañado y no se cargó ({ $reason }). { $moved ->
+        [yes] Se movió a { $path }.
+       *[no] No se pudo apartar.
+    } { $restored ->
+        [yes] Se restauró la versión anterior desde la copia de seguridad.
+       *[no] Se usan los valores predeterminados.
+    }
+scores-load-failed = No se pudieron cargar las escalas: { $error }
+export-done = Exportado: { $file }
+export-open = Abrir archivo
+export-reveal = Mostrar en la carpeta
+export-failed = Error al exportar: { $error }
+print-failed = Error al imprimir: { $error }
+import-failed = Error al importar: { $error }
+
+## Loading and welcome
+
+loading-progress = Cargando escalas { $loaded }/{ $total }…
+loading-library = Cargando la biblioteca de escalas...
+loading-error = Error de carga
+welcome-title = Bienvenido a KlinScore
+welcome-scores-loaded = { $count ->
+    [one] 1 escala cargada
+   *[other] { $count } escalas cargadas
+}
+welcome-start = Calcular escala
+resume-score = Cálculo sin terminar de la última vez: { $score } ({ $count ->
+    [one] 1 valor introducido
+   *[other] { $count } valores introducidos
+})
+resume-specialty = La última vez estaba eligiendo una puntuación en { $specialty }.
+resume-continue = Continuar
+resume-discard = Descartar
+
+## Specialty and score selection
+
+specialty-selection-title = Seleccionar especialidad
+specialty-selection-subtitle = Elija la especialidad médica para el cálculo
+no-scores-loaded = No hay escalas cargadas
+score-selection-title = { $specialty } - Seleccionar escala
+score-selection-subtitle = Elija una escala para calcular
+score-selection-back = ← Volver a especialidades
+score-selection-empty = No hay escalas disponibles para esta especialidad
+score-deprecated = Obsoleta
+
+## Calculation
+
+calculation-back = ← Volver a la selección de escalas
+calculation-error = Error: { $message }
+score-info = ℹ Información de la escala
+deprecated-superseded = { $name } ha sido sustituida por { $replacement }.
+deprecated-switch = Cambiar a { $replacement } →
+deprecated-no-replacement = { $name } está obsoleta y ya no debería usarse.
+patient-label = Paciente (opcional):
+patient-placeholder = p. ej. cama 12 o número de caso
+fhir-patient-id = ID de paciente FHIR:
+fhir-patient-placeholder = p. ej. 12345
+fhir-prefill = Rellenar desde FHIR
+fhir-prefill-loading = Obteniendo datos del paciente…
+fhir-prefilled = { $count ->
+    [one] 1 campo rellenado desde la historia clínica: { $fields }. Compruébelo.
+   *[other] { $count } campos rellenados desde la historia clínica: { $fields }. Compruébelos.
+}
+fhir-prefilled-none = No se encontraron valores coincidentes en la historia clínica.
+fhir-prefill-failed = No se pudieron obtener los datos del paciente: { $error }
+wizard-on = Rellenar paso a paso
+wizard-off = Todos los campos en una página
+wizard-general = General
+wizard-review = Revisión
+wizard-next = Siguiente →
+wizard-step = Paso { $step } de { $total }: { $title }
+wizard-edit = Editar
+wizard-missing = falta
+subtotal = Subtotal: { $points } pts
+required-open = { $count ->
+    [one] (1 campo obligatorio pendiente)
+   *[other] ({ $count } campos obligatorios pendientes)
+}
+invalid-number = Número no válido (p. ej. 1,36)
+invalid-date = Fecha no válida (p. ej. 14/03/1956)
+date-placeholder = DD/MM/AAAA
+date-today = Hoy
+date-after-reference = La fecha es posterior a la fecha hasta la que se cuenta
+elapsed-years = { $count ->
+    [one] 1 año
+   *[other] { $count } años
+}
+elapsed-weeks = { $count ->
+    [one] 1 semana
+   *[other] { $count } semanas
+}
+elapsed-days = { $count ->
+    [one] 1 día
+   *[other] { $count } días
+}
+invalid-duration = Duración no válida (p. ej. 4:30 o 4 h 30 min)
+duration-placeholder = p. ej. 4:30 o 4 h 30 min
+select-placeholder = Seleccione...
+
+## Result
+
+result-score = Puntuación: { $score }
+result-risk = Riesgo: { $risk }
+result-risk-level = Nivel de riesgo: { $level }
+result-details = Detalles:
+result-calculate-again = Calcular de nuevo
+result-export = Exportar:
+result-patient-copy = Copia para el paciente (PDF)
+result-copy = Copiar
+result-copied = Resumen copiado al portapapeles
+result-copy-markdown = Copiar como Markdown
+result-markdown-copied = Resumen en Markdown copiado al portapapeles
+result-template = Plantilla:
+result-template-placeholder = Exportar con plantilla…
+template-invalid = Plantilla de exportación no cargada: { $error }
+result-print = 🖨 Imprimir
+breakdown-criterion = Criterio
+breakdown-points = Puntos
+breakdown-total = Total
+breakdown-show-all = Mostrar criterios sin puntos
+
+risk-level-VeryLow = muy bajo
+risk-level-Low = bajo
+risk-level-Moderate = moderado
+risk-level-High = alto
+risk-level-VeryHigh = muy alto
+risk-level-Critical = crítico
+risk-level-None = informativo
+
+## Sessions
+
+session-placeholder = Sesión para varias escalas, p. ej. preoperatorio cama 12
+session-start = Iniciar sesión
+session-status = 📋 Sesión: { $title } ({ $count ->
+        [one] 1 cálculo
+       *[other] { $count } cálculos
+    })
+session-end = Finalizar sesión
+session-report-title = Informe de sesión
+
+## History
+
+history-title = Historial de cálculos
+history-empty = Todavía no hay cálculos.
+history-cleared = { $count ->
+    [one] 1 cálculo eliminado.
+   *[other] { $count } cálculos eliminados.
+}
+history-clear = Borrar historial
+history-research-export = Exportación para investigación (anonimizada)
+history-count-filtered = { $matching } de { $total } cálculos
+history-newest-shown = , se muestran los { $visible } más recientes
+history-export-all = Exportar las { $count } entradas:
+history-report-title = Historial de cálculos
+history-export-filtered = Exportar { $count } entradas filtradas:
+history-no-match = Ninguna entrada coincide con este filtro.
+history-reopen = Reabrir
+history-trend = Evolución ({ $count } valores)
+history-score = Puntuación: { $score }
+history-show-more = Mostrar { $count } más ({ $remaining } restantes)
+history-all-specialties = Todas las especialidades
+history-all-scores = Todas las escalas
+history-date-placeholder = AAAA-MM-DD
+history-search-placeholder = Buscar (escala, riesgo, paciente)…
+history-from = desde
+history-to = hasta
+history-clear-filter = Quitar filtro
+history-invalid-date = Fecha no reconocida (p. ej. 2026-02-12)
+history-transfer = Trasladar el historial a otro puesto de trabajo:
+history-transfer-save = Guardar como archivo de historial (JSON)
+history-import-placeholder = Ruta del archivo de historial (.json) de otro puesto de trabajo
+history-imported = { $added ->
+    [one] 1 entrada importada
+   *[other] { $added } entradas importadas
+}, { $present } ya existentes.
+history-score-not-loaded = Esta escala ya no está cargada.
+history-version-changed = Calculado con la versión { $recorded } de la escala; ahora está cargada la versión { $current }. Las reglas pueden haber cambiado.
+history-version-unknown = Calculado con una definición anterior de la escala (versión desconocida); ahora está cargada la versión { $current }.
+clear-history-title = ¿Borrar el historial?
+clear-history-body = Se eliminarán los { $count } cálculos. Hasta cerrar la aplicación, se puede deshacer.
+
+## History encryption
+
+unlock-title = Desbloquear historial
+unlock-body = El historial de cálculos está cifrado. Sin la frase de contraseña, los nuevos cálculos no se guardan.
+unlock = Desbloquear
+unlock-skip = Continuar sin historial
+unlock-wrong-passphrase = Frase de contraseña incorrecta.
+unlock-failed = Error al desbloquear: { $error }
+history-locked = 🔒 El historial cifrado está bloqueado; los nuevos cálculos no se guardan.
+passphrase-too-short = La frase de contraseña debe tener al menos { $min } caracteres.
+passphrase-mismatch = Las frases de contraseña no coinciden.
+passphrase-repeat = Repetir frase de contraseña
+encryption-label = Cifrar historial:
+encryption-hint = El historial puede contener datos cuasi identificativos. Con una frase de contraseña se guarda cifrado y se desbloquea al iniciar. Sin la frase de contraseña no se puede recuperar.
+encryption-enable = Cifrar
+encryption-enabled = Historial cifrado.
+encryption-failed = Error al cifrar: { $error }
+encryption-active = El historial se guarda cifrado.
+encryption-disable = Quitar cifrado
+encryption-disabled = El historial se guarda sin cifrar.
+decryption-failed = Error al descifrar: { $error }
+
+## Score information
+
+info-version = { $specialty } · Versión { $version }
+info-intended-use = Uso previsto
+info-regulatory-note = Nota regulatoria
+info-validated-in = Validada en
+info-clinical-question = Pregunta clínica
+info-target-population = Población diana
+info-time-horizon = Horizonte temporal
+info-contraindications = No usar en
+info-category = Categoría
+info-tags = Etiquetas
+info-source = Fuente
+info-references = Referencias
+
+category-RiskStratification = Estratificación del riesgo
+category-Screening = Cribado
+category-Diagnostic = Diagnóstico
+category-Classification = Clasificación
+category-Other = Otros
+
+## About
+
+about-title = Acerca de KlinScore
+about-version = Versión: { $version }
+about-scores-loaded = Escalas cargadas: { $count }
+about-specialties = Especialidades: { $specialties }
+about-guidelines = Guías: ESC 2024, KDIGO, ACCP, ASA
+about-license = Licencia: MIT / Apache 2.0
+about-disclaimer-title = Aviso legal
+about-disclaimer = KlinScore es una herramienta de apoyo a la decisión clínica. No sustituye el juicio clínico de un médico. Todas las escalas deben interpretarse en el contexto clínico del paciente. Sin garantía de exactitud ni de integridad.
+methodology-title = Metodología de las escalas y fuentes
+methodology-subtitle = Transparencia total sobre los métodos de cálculo y su base científica.
+methodology-method = Método: { $method }
+methodology-formula = Basado en fórmula
+methodology-points = Basado en puntos
+methodology-points-detail = Suma de los puntos de todos los criterios aplicables
+methodology-inputs = Datos: { $inputs }
+methodology-input-boolean = Sí/No
+methodology-input-number = Número
+methodology-input-dropdown = Selección
+methodology-input-date = Fecha
+methodology-input-duration = Duración
+methodology-range = Rango alcanzable: { $min } – { $max }
+methodology-calculation = Cálculo:
+methodology-reference = Referencia ({ $source })
+formula-ckd-epi-2021 =
+    TFGe = 142 × min(Scr/κ, 1)^α × max(Scr/κ, 1)^(−1.200) × 0.9938^edad × (1.012 en mujeres)
+    Donde: mujer κ=0.7, α=−0.241 | hombre κ=0.9, α=−0.302
+    Scr en mg/dL (entrada μmol/L ÷ 88.4)
+formula-kfre-4var =
+    Riesgo = 1 − 0.9832^exp(suma)
+    suma = −0.2201×(edad/10 − 7.036) + 0.2467×(hombre − 0.5642) − 0.5567×(TFGe/5 − 7.222) + 0.4510×(ln(CAC) − 5.137)
+    Entrada del CAC: mg/mmol, convertido internamente a mg/g (× 8.84)
+packs-title = --- Paquetes de escalas instalados ---
+pack-unverified = Sin verificar (sin sumas de comprobación)
+pack-verified = Sumas de comprobación verificadas (sin firma)
+pack-untrusted = Sumas de comprobación y firma válidas, pero la clave { $key } no es de un editor de confianza
+pack-signed = Sumas de comprobación y firma verificadas, clave de editor de confianza { $key }
+pack-failed = Falló la comprobación de integridad: { $reason }
+
+## Settings
+
+theme-label = Tema:
+theme-placeholder = Elegir tema
+theme-current = Actual: { $theme }
+theme-Light = Claro
+theme-Dark = Oscuro
+theme-Sepia = Sepia
+theme-HighContrast = Alto contraste
+theme-Custom = Personalizado
+theme-background = Fondo
+theme-primary = Color principal
+theme-risk-colors = Colores de riesgo para
+theme-presets = Plantillas:
+theme-invalid-color = Color no válido, se espera p. ej. #005EB8
+theme-preset-hospital-blue = Azul hospital
+theme-preset-high-contrast = Alto contraste
+help-hints = Mostrar ayuda junto a los campos (ⓘ)
+wizard-long-scores = Rellenar paso a paso las escalas largas (desde { $count } campos)
+accessibility = Accesibilidad:
+high-contrast = Alto contraste (texto blanco sobre negro)
+touch-mode = Modo táctil: botones grandes y contornos de foco marcados
+touch-hint = Los niveles de riesgo se muestran siempre también con icono y texto.
+units-label = Valores de laboratorio:
+unit-system-Si = Unidades SI (p. ej., μmol/L)
+unit-system-Conventional = Unidades convencionales (p. ej., mg/dL)
+units-hint = Se aplica a los valores de laboratorio ofrecidos en ambas unidades. Los valores se convierten a unidades SI para el cálculo y se guardan en unidades SI.
+research-label = Marcas de tiempo en la exportación para investigación:
+research-hint = Los identificadores de caso y las notas se eliminan siempre. Actual: { $precision }
+precision-Date = Solo fecha
+precision-Month = Mes
+precision-Year = Año
+precision-Omit = Sin marcas de tiempo
+patient-labels-disabled = No registrar identificadores de paciente (ocultar el campo «Paciente»)
+retention-label = Depurar el historial automáticamente:
+retention-hint = { $mode }. Se aplica al iniciar y después de cada cálculo.
+retention-invalid-limit = Introduzca un número entero de al menos 1.
+retention-keep-all = Conservar todo
+retention-last-entries = Últimas N entradas
+retention-last-days = Últimos N días
+score-dirs = Carpetas de escalas adicionales:
+score-dirs-hint = Las escalas YAML de estas carpetas (p. ej. escalas propias del hospital) se cargan y sustituyen a las incluidas con el mismo nombre.
+score-dirs-placeholder = Ruta de la carpeta
+score-dir-not-found = Carpeta no encontrada: { $path }
+strict-loading = Carga estricta: no iniciar si algún archivo de escala no es válido
+pack-import = Importar paquete de escalas:
+pack-import-hint = Un paquete .klinpack (zip con klinpack.yaml) se comprueba y se instala en la carpeta de escalas del usuario.
+pack-placeholder = Ruta del archivo .klinpack
+pack-no-user-dir = No hay carpeta de escalas del usuario disponible
+trusted-keys = Editores de paquetes de confianza:
+trusted-keys-hint = Un paquete firmado solo se considera verificado si su clave figura aquí. Añada la clave pública ed25519 (hex) que le dio el editor, no la que viene en el paquete.
+trusted-key-placeholder = Clave pública (64 caracteres hexadecimales)
+trusted-key-invalid = No es una clave pública ed25519: { $key }
+pack-installed = Paquete de escalas «{ $name }» { $version } instalado ({ $count ->
+        [one] 1 escala
+       *[other] { $count } escalas
+    }). { $integrity }
+data-location = Ubicación de los datos:
+data-location-platform = Perfil de usuario
+data-location-portable = Portátil (junto al programa)
+data-location-custom = Carpeta elegida (--data-dir)
+portable-mode = Modo portátil: guardar los datos junto al programa (p. ej. en una memoria USB)
+portable-mode-hint = Se aplica en el próximo inicio. Los datos existentes no se mueven; el historial se puede trasladar como archivo de historial.
+portable-mode-failed = No se pudo cambiar el modo portátil: { $error }
+log-file = Escribir un registro de diagnóstico (klinscore.log en la carpeta de datos)
+log-file-hint = Registra advertencias y eventos como archivos de puntuación omitidos o exportaciones fallidas. No contiene entradas ni resultados.
+export-dir = Carpeta de exportación:
+export-dir-hint = El diálogo para guardar exportaciones se abre en esta carpeta; cada exportación se puede guardar igualmente en otro lugar.
+export-dir-default = Carpeta Documentos (predeterminada)
+export-dir-choose = Elegir carpeta…
+export-dir-choose-title = Elegir carpeta de exportación
+export-dir-reset = Usar predeterminada
+anonymize-label = Exportaciones anonimizadas:
+anonymize-timestamps = Eliminar la hora del cálculo
+anonymize-patient-labels = Eliminar etiquetas de paciente y títulos de sesión
+anonymize-notes = Eliminar notas de texto libre
+anonymize-hint = Se aplica a todas las exportaciones, impresiones y resultados copiados, p. ej. para docencia o para compartir en investigación. El historial no se modifica.
+pdf-report = Informes PDF:
+pdf-report-header = Encabezado (institución):
+pdf-report-header-placeholder = p. ej., Hospital Norte – Servicio de Anestesiología
+pdf-report-footer = Pie de página:
+pdf-report-footer-placeholder = p. ej., dirección, número de teléfono
+pdf-report-bilingual = Informes bilingües: imprimir también los textos en { $language }
+pdf-report-qr-code = Código QR: imprimir el resultado (o su suma de verificación SHA-256) como código QR bajo cada resultado
+fhir-server = Servidor FHIR
+fhir-server-hint = URL base de un servidor FHIR R4. Los formularios de las escalas cuyos campos declaran códigos LOINC pueden rellenarse con los últimos valores del paciente a partir de su ID.
+fhir-server-placeholder = p. ej. https://fhir.hospital.example/r4
+seal-label = Exportaciones a prueba de manipulaciones:
+seal-exports = Sellar las exportaciones PDF y JSON con un hash SHA-256
+sign-exports = Firmar también el sello con la clave local de esta instalación
+signing-key = Clave pública: { $key }
+signing-key-failed = Clave de firma no disponible: { $error }
+seal-hint = Los destinatarios comprueban un archivo sellado con klinscore-verify; se informa de cualquier cambio. Comparta la clave pública para que vean que el archivo se firmó aquí (klinscore-verify --key).
+export-save-title = Guardar exportación
+backups = Copias de seguridad:
+backups-hint = Una vez al día se guarda una copia de la configuración y del historial en la carpeta «backups» de la ubicación de datos; se conservan las últimas { $count }. Antes de restaurar, el estado actual se guarda como otra copia. Un historial cifrado (🔒) necesita la frase de contraseña de entonces.
+backups-empty = Todavía no hay copias de seguridad.
+backup-restore = Restaurar
+backup-restored = Copia de seguridad { $name } restaurada.
+backup-restore-failed = No se pudo restaurar la copia de seguridad: { $error }
+settings-transfer = Compartir configuración:
+settings-transfer-hint = Guardar toda la configuración (tema, idioma, accesibilidad, carpetas de exportación y de scores, opciones del historial) en un archivo y cargarlo en otro puesto para usar la misma configuración en todo el servicio.
+settings-export = Exportar configuración…
+settings-import = Importar configuración…
+settings-import-title = Importar configuración
+settings-imported = Configuración importada de { $file }.
+
+## Specialties
+
+specialty-Cardiology = Cardiología
+specialty-Nephrology = Nefrología
+specialty-Anesthesiology = Anestesiología
+specialty-Emergency = Medicina de urgencias
+specialty-InternalMedicine = Medicina interna
+specialty-Surgery = Cirugía
+specialty-Pulmonology = Neumología
+specialty-Neurology = Neurología
+specialty-Gastroenterology = Gastroenterología
+specialty-Hematology = Hematología
+specialty-Oncology = Oncología
+specialty-Endocrinology = Endocrinología
+specialty-InfectiousDiseases = Enfermedades infecciosas
+specialty-Geriatrics = Geriatría
+specialty-Pediatrics = Pediatría
+specialty-IntensiveCare = Cuidados intensivos
+specialty-Psychiatry = Psiquiatría
+specialty-Other = Otras
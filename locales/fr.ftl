@@ -0,0 +1,434 @@
+# KlinScore UI texts: French (Français)
+
+## Common
+
+back = ← Retour
+cancel = Annuler
+delete = Supprimer
+undo = Annuler la suppression
+add = Ajouter
+remove = Retirer
+import = Importer
+calculate = Calculer
+reset = Réinitialiser
+error-title = Erreur
+loading = Chargement...
+score-not-found = Score introuvable
+recommendation = Recommandation :
+points-breakdown = Répartition des points :
+no-points = Aucun critère n'a apporté de points.
+details-show = Détails ▼
+details-hide = Détails ▲
+passphrase = Phrase secrète
+print-report = Imprimer le rapport
+app-subtitle = Calculateur de scores cliniques open source pour la médecine fondée sur les preuves
+scores-count = { $count ->
+    [one] { $count } score
+   *[other] { $count } scores
+}
+calculations-count = { $count ->
+    [one] { $count } calcul
+   *[other] { $count } calculs
+}
+
+## Header and tabs
+
+history = Historique
+about = À propos
+settings = Paramètres
+new-tab = + Nouvel onglet
+tab-new-calculation = Nouveau calcul
+
+## Notifications
+
+file-damaged = { $file } est endommagé et n'a pas été chargé ({ $reason }). { $moved ->
+        [yes] Il a été déplacé vers { $path }.
+       *[no] Il n'a pas pu être déplacé.
+    } { $restored ->
+        [yes] La version précédente a été restaurée depuis la sauvegarde.
+       *[no] Les valeurs par défaut sont utilisées.
+    }
+scores-load-failed = Échec du chargement des scores : { $error }
+export-done = Exporté : { $file }
+export-open = Ouvrir le fichier
+export-reveal = Afficher dans le dossier
+export-failed = Échec de l'export : { $error }
+print-failed = Échec de l'impression : { $error }
+import-failed = Échec de l'import : { $error }
+
+## Loading and welcome
+
+loading-progress = Chargement des scores { $loaded }/{ $total }…
+loading-library = Chargement de la bibliothèque de scores...
+loading-error = Erreur de chargement
+welcome-title = Bienvenue dans KlinScore
+welcome-scores-loaded = { $count ->
+    [one] { $count } score chargé
+   *[other] { $count } scores chargés
+}
+welcome-start = Calculer un score
+resume-score = Calcul inachevé de la dernière fois : { $score } ({ $count ->
+    [one] 1 valeur saisie
+   *[other] { $count } valeurs saisies
+})
+resume-specialty = La dernière fois, vous choisissiez un score en { $specialty }.
+resume-continue = Reprendre
+resume-discard = Abandonner
+
+## Specialty and score selection
+
+specialty-selection-title = Choisir la spécialité
+specialty-selection-subtitle = Choisissez la spécialité médicale pour le calcul du score
+no-scores-loaded = Aucun score chargé
+score-selection-title = { $specialty } - Choisir un score
+score-selection-subtitle = Choisissez un score à calculer
+score-selection-back = ← Retour aux spécialités
+score-selection-empty = Aucun score disponible pour cette spécialité
+score-deprecated = Obsolète
+
+## Calculation
+
+calculation-back = ← Retour au choix du score
+calculation-error = Erreur : { $message }
+score-info = ℹ Infos sur le score
+deprecated-superseded = { $name } est remplacé par { $replacement }.
+deprecated-switch = Passer à { $replacement } →
+deprecated-no-replacement = { $name } est obsolète et ne devrait plus être utilisé.
+patient-label = Patient (facultatif) :
+patient-placeholder = p. ex. lit 12 ou numéro de dossier
+fhir-patient-id = ID patient FHIR :
+fhir-patient-placeholder = p. ex. 12345
+fhir-prefill = Préremplir depuis FHIR
+fhir-prefill-loading = Récupération des données du patient…
+fhir-prefilled = { $count ->
+    [one] 1 champ rempli depuis le dossier patient : { $fields }. Veuillez le vérifier.
+   *[other] { $count } champs remplis depuis le dossier patient : { $fields }. Veuillez les vérifier.
+}
+fhir-prefilled-none = Aucune valeur correspondante dans le dossier patient.
+fhir-prefill-failed = Impossible de récupérer les données du patient : { $error }
+wizard-on = Remplir étape par étape
+wizard-off = Tous les champs sur une page
+wizard-general = Général
+wizard-review = Vérification
+wizard-next = Suivant →
+wizard-step = Étape { $step } sur { $total } : { $title }
+wizard-edit = Modifier
+wizard-missing = manquant
+subtotal = Sous-total : { $points } pts
+required-open = { $count ->
+    [one] ({ $count } champ obligatoire non rempli)
+   *[other] ({ $count } champs obligatoires non remplis)
+}
+invalid-number = Nombre non valide (p. ex. 1,36)
+invalid-date = Date non valide (p. ex. 14/03/1956)
+date-placeholder = JJ/MM/AAAA
+date-today = Aujourd'hui
+date-after-reference = La date est postérieure à la date jusqu'à laquelle on compte
+elapsed-years = { $count ->
+    [one] 1 an
+   *[other] { $count } ans
+}
+elapsed-weeks = { $count ->
+    [one] 1 semaine
+   *[other] { $count } semaines
+}
+elapsed-days = { $count ->
+    [one] 1 jour
+   *[other] { $count } jours
+}
+invalid-duration = Durée non valide (p. ex. 4:30 ou 4 h 30 min)
+duration-placeholder = p. ex. 4:30 ou 4 h 30 min
+select-placeholder = Veuillez choisir...
+
+## Result
+
+result-score = Score : { $score }
+result-risk = Risque : { $risk }
+result-risk-level = Niveau de risque : { $level }
+result-details = Détails :
+result-calculate-again = Recalculer
+result-export = Exporter :
+result-patient-copy = Copie patient (PDF)
+result-copy = Copier
+result-copied = Résumé copié dans le presse-papiers
+result-copy-markdown = Copier en Markdown
+result-markdown-copied = Résumé Markdown copié dans le presse-papiers
+result-template = Modèle :
+result-template-placeholder = Exporter avec un modèle…
+template-invalid = Modèle d'export non chargé : { $error }
+result-print = 🖨 Imprimer
+breakdown-criterion = Critère
+breakdown-points = Points
+breakdown-total = Total
+breakdown-show-all = Afficher les critères sans points
+
+risk-level-VeryLow = très faible
+risk-level-Low = faible
+risk-level-Moderate = modéré
+risk-level-High = élevé
+risk-level-VeryHigh = très élevé
+risk-level-Critical = critique
+risk-level-None = informatif
+
+## Sessions
+
+session-placeholder = Session pour plusieurs scores, p. ex. pré-op lit 12
+session-start = Démarrer la session
+session-status = 📋 Session : { $title } ({ $count ->
+        [one] { $count } calcul
+       *[other] { $count } calculs
+    })
+session-end = Terminer la session
+session-report-title = Rapport de session
+
+## History
+
+history-title = Historique des calculs
+history-empty = Aucun calcul pour l'instant.
+history-cleared = { $count ->
+    [one] { $count } calcul supprimé.
+   *[other] { $count } calculs supprimés.
+}
+history-clear = Effacer l'historique
+history-research-export = Export de recherche (anonymisé)
+history-count-filtered = { $matching } calculs sur { $total }
+history-newest-shown = , les { $visible } plus récents affichés
+history-export-all = Exporter les { $count } entrées :
+history-report-title = Historique des calculs
+history-export-filtered = Exporter { $count } entrées filtrées :
+history-no-match = Aucune entrée ne correspond à ce filtre.
+history-reopen = Rouvrir
+history-trend = Évolution ({ $count } valeurs)
+history-score = Score : { $score }
+history-show-more = Afficher { $count } de plus ({ $remaining } restantes)
+history-all-specialties = Toutes les spécialités
+history-all-scores = Tous les scores
+history-date-placeholder = AAAA-MM-JJ
+history-search-placeholder = Rechercher (score, risque, patient)…
+history-from = du
+history-to = au
+history-clear-filter = Effacer le filtre
+history-invalid-date = Date non reconnue (p. ex. 2026-02-12)
+history-transfer = Transférer l'historique vers un autre poste :
+history-transfer-save = Enregistrer comme fichier d'historique (JSON)
+history-import-placeholder = Chemin du fichier d'historique (.json) d'un autre poste
+history-imported = { $added ->
+    [one] { $added } entrée importée
+   *[other] { $added } entrées importées
+}, { $present } déjà présentes.
+history-score-not-loaded = Ce score n'est plus chargé.
+history-version-changed = Calculé avec la version { $recorded } du score ; la version { $current } est chargée. Les règles ont pu changer.
+history-version-unknown = Calculé avec une définition antérieure du score (version inconnue) ; la version { $current } est chargée.
+clear-history-title = Effacer l'historique ?
+clear-history-body = Les { $count } calculs seront supprimés. Jusqu'à la fermeture de l'application, cette action peut être annulée.
+
+## History encryption
+
+unlock-title = Déverrouiller l'historique
+unlock-body = L'historique des calculs est chiffré. Sans la phrase secrète, les nouveaux calculs ne sont pas enregistrés.
+unlock = Déverrouiller
+unlock-skip = Continuer sans historique
+unlock-wrong-passphrase = Phrase secrète incorrecte.
+unlock-failed = Échec du déverrouillage : { $error }
+history-locked = 🔒 L'historique chiffré est verrouillé ; les nouveaux calculs ne sont pas enregistrés.
+passphrase-too-short = La phrase secrète doit comporter au moins { $min } caractères.
+passphrase-mismatch = Les phrases secrètes ne correspondent pas.
+passphrase-repeat = Répéter la phrase secrète
+encryption-label = Chiffrer l'historique :
+encryption-hint = L'historique peut contenir des données quasi identifiantes. Avec une phrase secrète, il est enregistré chiffré et déverrouillé au démarrage. Sans la phrase secrète, il est irrécupérable.
+encryption-enable = Chiffrer
+encryption-enabled = Historique chiffré.
+encryption-failed = Échec du chiffrement : { $error }
+encryption-active = L'historique est enregistré chiffré.
+encryption-disable = Retirer le chiffrement
+encryption-disabled = L'historique est enregistré sans chiffrement.
+decryption-failed = Échec du déchiffrement : { $error }
+
+## Score information
+
+info-version = { $specialty } · Version { $version }
+info-intended-use = Usage prévu
+info-regulatory-note = Note réglementaire
+info-validated-in = Validé chez
+info-clinical-question = Question clinique
+info-target-population = Population cible
+info-time-horizon = Horizon temporel
+info-contraindications = Ne pas utiliser chez
+info-category = Catégorie
+info-tags = Mots-clés
+info-source = Source
+info-references = Références
+
+category-RiskStratification = Stratification du risque
+category-Screening = Dépistage
+category-Diagnostic = Diagnostic
+category-Classification = Classification
+category-Other = Autre
+
+## About
+
+about-title = À propos de KlinScore
+about-version = Version : { $version }
+about-scores-loaded = Scores chargés : { $count }
+about-specialties = Spécialités : { $specialties }
+about-guidelines = Recommandations : ESC 2024, KDIGO, ACCP, ASA
+about-license = Licence : MIT / Apache 2.0
+about-disclaimer-title = Avertissement
+about-disclaimer = KlinScore est un outil d'aide à la décision clinique. Il ne remplace pas le jugement clinique d'un médecin. Tous les scores doivent être interprétés dans le contexte clinique du patient. Aucune garantie d'exactitude ni d'exhaustivité.
+methodology-title = Méthodologie des scores et sources
+methodology-subtitle = Transparence totale sur les méthodes de calcul et leur base scientifique.
+methodology-method = Méthode : { $method }
+methodology-formula = Par formule
+methodology-points = Par points
+methodology-points-detail = Somme des points de tous les critères applicables
+methodology-inputs = Données : { $inputs }
+methodology-input-boolean = Oui/Non
+methodology-input-number = Nombre
+methodology-input-dropdown = Sélection
+methodology-input-date = Date
+methodology-input-duration = Durée
+methodology-range = Plage atteignable : { $min } – { $max }
+methodology-calculation = Calcul :
+methodology-reference = Référence ({ $source })
+formula-ckd-epi-2021 =
+    DFGe = 142 × min(Scr/κ, 1)^α × max(Scr/κ, 1)^(−1.200) × 0.9938^âge × (1.012 chez la femme)
+    Où : femme κ=0.7, α=−0.241 | homme κ=0.9, α=−0.302
+    Scr en mg/dL (saisie μmol/L ÷ 88.4)
+formula-kfre-4var =
+    Risque = 1 − 0.9832^exp(somme)
+    somme = −0.2201×(âge/10 − 7.036) + 0.2467×(homme − 0.5642) − 0.5567×(DFGe/5 − 7.222) + 0.4510×(ln(RAC) − 5.137)
+    Saisie du RAC : mg/mmol, converti en interne en mg/g (× 8.84)
+packs-title = --- Paquets de scores installés ---
+pack-unverified = Non vérifié (pas de sommes de contrôle)
+pack-verified = Sommes de contrôle vérifiées (non signé)
+pack-untrusted = Sommes de contrôle et signature valides, mais la clé { $key } n'est pas celle d'un éditeur de confiance
+pack-signed = Sommes de contrôle et signature vérifiées, clé d'éditeur de confiance { $key }
+pack-failed = Échec du contrôle d'intégrité : { $reason }
+
+## Settings
+
+theme-label = Thème :
+theme-placeholder = Choisir un thème
+theme-current = Actuel : { $theme }
+theme-Light = Clair
+theme-Dark = Sombre
+theme-Sepia = Sépia
+theme-HighContrast = Contraste élevé
+theme-Custom = Personnalisé
+theme-background = Arrière-plan
+theme-primary = Couleur principale
+theme-risk-colors = Couleurs de risque pour
+theme-presets = Modèles :
+theme-invalid-color = Couleur non valide, attendu p. ex. #005EB8
+theme-preset-hospital-blue = Bleu hôpital
+theme-preset-high-contrast = Contraste élevé
+help-hints = Afficher l'aide à côté des champs de saisie (ⓘ)
+wizard-long-scores = Remplir les scores longs (à partir de { $count } champs) étape par étape
+accessibility = Accessibilité :
+high-contrast = Contraste élevé (texte blanc sur noir)
+touch-mode = Mode tactile : grands boutons et contours de focus marqués
+touch-hint = Les niveaux de risque sont toujours aussi affichés avec une icône et du texte.
+units-label = Valeurs biologiques :
+unit-system-Si = Unités SI (p. ex. μmol/L)
+unit-system-Conventional = Unités conventionnelles (p. ex. mg/dL)
+units-hint = S'applique aux valeurs biologiques proposées dans les deux unités. Les valeurs sont converties en unités SI pour le calcul et enregistrées en unités SI.
+research-label = Horodatage dans l'export de recherche :
+research-hint = Les identifiants de cas et les notes sont toujours retirés. Actuel : { $precision }
+precision-Date = Date seulement
+precision-Month = Mois
+precision-Year = Année
+precision-Omit = Sans horodatage
+patient-labels-disabled = Ne pas enregistrer d'identifiant patient (masquer le champ « Patient »)
+retention-label = Nettoyer l'historique automatiquement :
+retention-hint = { $mode }. Appliqué au démarrage et après chaque calcul.
+retention-invalid-limit = Veuillez saisir un nombre entier d'au moins 1.
+retention-keep-all = Tout conserver
+retention-last-entries = N dernières entrées
+retention-last-days = N derniers jours
+score-dirs = Dossiers de scores supplémentaires :
+score-dirs-hint = Les scores YAML de ces dossiers (p. ex. scores propres à l'hôpital) sont chargés et remplacent les scores fournis de même nom.
+score-dirs-placeholder = Chemin du dossier
+score-dir-not-found = Dossier introuvable : { $path }
+strict-loading = Chargement strict : refuser de démarrer si un fichier de score n'est pas valide
+pack-import = Importer un paquet de scores :
+pack-import-hint = Un paquet .klinpack (zip contenant klinpack.yaml) est vérifié et installé dans le dossier de scores de l'utilisateur.
+pack-placeholder = Chemin du fichier .klinpack
+pack-no-user-dir = Aucun dossier de scores utilisateur disponible
+trusted-keys = Éditeurs de paquets de confiance :
+trusted-keys-hint = Un paquet signé n'est considéré comme vérifié que si sa clé figure ici. Ajoutez la clé publique ed25519 (hex) fournie par l'éditeur, pas celle contenue dans le paquet.
+trusted-key-placeholder = Clé publique (64 caractères hexadécimaux)
+trusted-key-invalid = Pas une clé publique ed25519 : { $key }
+pack-installed = Paquet de scores « { $name } » { $version } installé ({ $count ->
+        [one] { $count } score
+       *[other] { $count } scores
+    }). { $integrity }
+data-location = Emplacement des données :
+data-location-platform = Profil utilisateur
+data-location-portable = Portable (à côté du programme)
+data-location-custom = Dossier choisi (--data-dir)
+portable-mode = Mode portable : garder les données à côté du programme (p. ex. sur une clé USB)
+portable-mode-hint = Prend effet au prochain démarrage. Les données existantes ne sont pas déplacées ; l'historique peut être transféré comme fichier d'historique.
+portable-mode-failed = Impossible de changer le mode portable : { $error }
+log-file = Écrire un journal de diagnostic (klinscore.log dans le dossier de données)
+log-file-hint = Enregistre les avertissements et événements, p. ex. fichiers de score ignorés ou exportations échouées. Ne contient ni saisies ni résultats.
+export-dir = Dossier d'export :
+export-dir-hint = La boîte de dialogue d'enregistrement des exports s'ouvre dans ce dossier ; chaque export peut tout de même être enregistré ailleurs.
+export-dir-default = Dossier Documents (par défaut)
+export-dir-choose = Choisir un dossier…
+export-dir-choose-title = Choisir le dossier d'export
+export-dir-reset = Utiliser le dossier par défaut
+anonymize-label = Exports anonymisés :
+anonymize-timestamps = Supprimer l'heure du calcul
+anonymize-patient-labels = Supprimer les libellés patient et les titres de session
+anonymize-notes = Supprimer les notes en texte libre
+anonymize-hint = S'applique à tous les exports, impressions et résultats copiés, p. ex. pour l'enseignement ou le partage en recherche. L'historique lui-même n'est pas modifié.
+pdf-report = Rapports PDF :
+pdf-report-header = En-tête (établissement) :
+pdf-report-header-placeholder = p. ex. Hôpital Nord – Service d'anesthésiologie
+pdf-report-footer = Pied de page :
+pdf-report-footer-placeholder = p. ex. adresse, numéro de téléphone
+pdf-report-bilingual = Rapports bilingues : imprimer aussi les textes en { $language }
+pdf-report-qr-code = Code QR : imprimer le résultat (ou sa somme de contrôle SHA-256) sous forme de code QR sous chaque résultat
+fhir-server = Serveur FHIR
+fhir-server-hint = URL de base d'un serveur FHIR R4. Les formulaires des scores dont les champs déclarent des codes LOINC peuvent alors être préremplis avec les dernières valeurs du patient à partir de son ID.
+fhir-server-placeholder = p. ex. https://fhir.hopital.example/r4
+seal-label = Exports infalsifiables :
+seal-exports = Sceller les exports PDF et JSON avec un hachage SHA-256
+sign-exports = Signer aussi le sceau avec la clé locale de cette installation
+signing-key = Clé publique : { $key }
+signing-key-failed = Clé de signature indisponible : { $error }
+seal-hint = Les destinataires vérifient un fichier scellé avec klinscore-verify ; toute modification est signalée. Transmettez la clé publique pour qu'ils voient que le fichier a été signé ici (klinscore-verify --key).
+export-save-title = Enregistrer l'export
+backups = Sauvegardes :
+backups-hint = Une fois par jour, une copie des paramètres et de l'historique est conservée dans le dossier « backups » de l'emplacement des données ; les { $count } dernières sont gardées. Avant une restauration, l'état actuel est enregistré comme nouvelle sauvegarde. Un historique chiffré (🔒) nécessite la phrase secrète de l'époque.
+backups-empty = Aucune sauvegarde pour l'instant.
+backup-restore = Restaurer
+backup-restored = Sauvegarde { $name } restaurée.
+backup-restore-failed = La restauration de la sauvegarde a échoué : { $error }
+settings-transfer = Partager les paramètres :
+settings-transfer-hint = Enregistrer tous les paramètres (thème, langue, accessibilité, dossiers d'export et de scores, options de l'historique) dans un fichier, puis charger ce fichier sur un autre poste pour utiliser la même configuration dans tout le service.
+settings-export = Exporter les paramètres…
+settings-import = Importer des paramètres…
+settings-import-title = Importer des paramètres
+settings-imported = Paramètres importés depuis { $file }.
+
+## Specialties
+
+specialty-Cardiology = Cardiologie
+specialty-Nephrology = Néphrologie
+specialty-Anesthesiology = Anesthésiologie
+specialty-Emergency = Médecine d'urgence
+specialty-InternalMedicine = Médecine interne
+specialty-Surgery = Chirurgie
+specialty-Pulmonology = Pneumologie
+specialty-Neurology = Neurologie
+specialty-Gastroenterology = Gastroentérologie
+specialty-Hematology = Hématologie
+specialty-Oncology = Oncologie
+specialty-Endocrinology = Endocrinologie
+specialty-InfectiousDiseases = Maladies infectieuses
+specialty-Geriatrics = Gériatrie
+specialty-Pediatrics = Pédiatrie
+specialty-IntensiveCare = Soins intensifs
+specialty-Psychiatry = Psychiatrie
+specialty-Other = Autre
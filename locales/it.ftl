@@ -0,0 +1,434 @@
+# KlinScore UI texts: Italian (Italiano)
+
+## Common
+
+back = ← Indietro
+cancel = Annulla
+delete = Elimina
+undo = Annulla eliminazione
+add = Aggiungi
+remove = Rimuovi
+import = Importa
+calculate = Calcola
+reset = Reimposta
+error-title = Errore
+loading = Caricamento...
+score-not-found = Score non trovato
+recommendation = Raccomandazione:
+points-breakdown = Ripartizione dei punti:
+no-points = Nessun criterio ha contribuito con punti.
+details-show = Dettagli ▼
+details-hide = Dettagli ▲
+passphrase = Passphrase
+print-report = Stampa referto
+app-subtitle = Calcolatore open source di score clinici per la medicina basata sulle evidenze
+scores-count = { $count ->
+    [one] 1 score
+   *[other] { $count } score
+}
+calculations-count = { $count ->
+    [one] 1 calcolo
+   *[other] { $count } calcoli
+}
+
+## Header and tabs
+
+history = Cronologia
+about = Informazioni
+settings = Impostazioni
+new-tab = + Nuova scheda
+tab-new-calculation = Nuovo calcolo
+
+## Notifications
+
+file-damaged = { $file } è danneggiato e non è stato caricato ({ $reason }). { $moved ->
+        [yes] È stato spostato in { $path }.
+       *[no] Non è stato possibile spostarlo.
+    } { $restored ->
+        [yes] La versione precedente è stata ripristinata dal backup.
+       *[no] Vengono usati i valori predefiniti.
+    }
+scores-load-failed = Impossibile caricare gli score: { $error }
+export-done = Esportato: { $file }
+export-open = Apri file
+export-reveal = Mostra nella cartella
+export-failed = Esportazione non riuscita: { $error }
+print-failed = Stampa non riuscita: { $error }
+import-failed = Importazione non riuscita: { $error }
+
+## Loading and welcome
+
+loading-progress = Caricamento score { $loaded }/{ $total }…
+loading-library = Caricamento della libreria di score...
+loading-error = Errore di caricamento
+welcome-title = Benvenuto in KlinScore
+welcome-scores-loaded = { $count ->
+    [one] 1 score caricato
+   *[other] { $count } score caricati
+}
+welcome-start = Calcola score
+resume-score = Calcolo non terminato dall'ultima volta: { $score } ({ $count ->
+    [one] 1 valore inserito
+   *[other] { $count } valori inseriti
+})
+resume-specialty = L'ultima volta stava scegliendo un punteggio in { $specialty }.
+resume-continue = Continua
+resume-discard = Scarta
+
+## Specialty and score selection
+
+specialty-selection-title = Seleziona la specialità
+specialty-selection-subtitle = Scegli la specialità medica per il calcolo dello score
+no-scores-loaded = Nessuno score caricato
+score-selection-title = { $specialty } - Seleziona score
+score-selection-subtitle = Scegli uno score da calcolare
+score-selection-back = ← Torna alle specialità
+score-selection-empty = Nessuno score disponibile per questa specialità
+score-deprecated = Obsoleto
+
+## Calculation
+
+calculation-back = ← Torna alla scelta dello score
+calculation-error = Errore: { $message }
+score-info = ℹ Info score
+deprecated-superseded = { $name } è stato sostituito da { $replacement }.
+deprecated-switch = Passa a { $replacement } →
+deprecated-no-replacement = { $name } è obsoleto e non dovrebbe più essere usato.
+patient-label = Paziente (facoltativo):
+patient-placeholder = es. letto 12 o numero di caso
+fhir-patient-id = ID paziente FHIR:
+fhir-patient-placeholder = es. 12345
+fhir-prefill = Precompila da FHIR
+fhir-prefill-loading = Recupero dei dati del paziente…
+fhir-prefilled = { $count ->
+    [one] 1 campo compilato dalla cartella clinica: { $fields }. Verificarlo.
+   *[other] { $count } campi compilati dalla cartella clinica: { $fields }. Verificarli.
+}
+fhir-prefilled-none = Nessun valore corrispondente nella cartella clinica.
+fhir-prefill-failed = Impossibile recuperare i dati del paziente: { $error }
+wizard-on = Compila passo dopo passo
+wizard-off = Tutti i campi in una pagina
+wizard-general = Generale
+wizard-review = Verifica
+wizard-next = Avanti →
+wizard-step = Passo { $step } di { $total }: { $title }
+wizard-edit = Modifica
+wizard-missing = mancante
+subtotal = Subtotale: { $points } pt
+required-open = { $count ->
+    [one] (1 campo obbligatorio da compilare)
+   *[other] ({ $count } campi obbligatori da compilare)
+}
+invalid-number = Numero non valido (es. 1,36)
+invalid-date = Data non valida (es. 14/03/1956)
+date-placeholder = GG/MM/AAAA
+date-today = Oggi
+date-after-reference = La data è successiva alla data fino alla quale si conta
+elapsed-years = { $count ->
+    [one] 1 anno
+   *[other] { $count } anni
+}
+elapsed-weeks = { $count ->
+    [one] 1 settimana
+   *[other] { $count } settimane
+}
+elapsed-days = { $count ->
+    [one] 1 giorno
+   *[other] { $count } giorni
+}
+invalid-duration = Durata non valida (es. 4:30 o 4 h 30 min)
+duration-placeholder = es. 4:30 o 4 h 30 min
+select-placeholder = Selezionare...
+
+## Result
+
+result-score = Punteggio: { $score }
+result-risk = Rischio: { $risk }
+result-risk-level = Livello di rischio: { $level }
+result-details = Dettagli:
+result-calculate-again = Ricalcola
+result-export = Esporta:
+result-patient-copy = Copia per il paziente (PDF)
+result-copy = Copia
+result-copied = Riepilogo copiato negli appunti
+result-copy-markdown = Copia come Markdown
+result-markdown-copied = Riepilogo Markdown copiato negli appunti
+result-template = Modello:
+result-template-placeholder = Esporta con modello…
+template-invalid = Modello di esportazione non caricato: { $error }
+result-print = 🖨 Stampa
+breakdown-criterion = Criterio
+breakdown-points = Punti
+breakdown-total = Totale
+breakdown-show-all = Mostra i criteri senza punti
+
+risk-level-VeryLow = molto basso
+risk-level-Low = basso
+risk-level-Moderate = moderato
+risk-level-High = alto
+risk-level-VeryHigh = molto alto
+risk-level-Critical = critico
+risk-level-None = informativo
+
+## Sessions
+
+session-placeholder = Sessione per più score, es. pre-op letto 12
+session-start = Avvia sessione
+session-status = 📋 Sessione: { $title } ({ $count ->
+        [one] 1 calcolo
+       *[other] { $count } calcoli
+    })
+session-end = Termina sessione
+session-report-title = Report della sessione
+
+## History
+
+history-title = Cronologia dei calcoli
+history-empty = Ancora nessun calcolo.
+history-cleared = { $count ->
+    [one] 1 calcolo eliminato.
+   *[other] { $count } calcoli eliminati.
+}
+history-clear = Cancella cronologia
+history-research-export = Esportazione per la ricerca (anonimizzata)
+history-count-filtered = { $matching } di { $total } calcoli
+history-newest-shown = , mostrati i { $visible } più recenti
+history-export-all = Esporta tutte le { $count } voci:
+history-report-title = Cronologia dei calcoli
+history-export-filtered = Esporta { $count } voci filtrate:
+history-no-match = Nessuna voce corrisponde a questo filtro.
+history-reopen = Riapri
+history-trend = Andamento ({ $count } valori)
+history-score = Punteggio: { $score }
+history-show-more = Mostra altre { $count } ({ $remaining } rimanenti)
+history-all-specialties = Tutte le specialità
+history-all-scores = Tutti gli score
+history-date-placeholder = AAAA-MM-GG
+history-search-placeholder = Cerca (score, rischio, paziente)…
+history-from = dal
+history-to = al
+history-clear-filter = Rimuovi filtro
+history-invalid-date = Data non riconosciuta (es. 2026-02-12)
+history-transfer = Trasferire la cronologia su un'altra postazione:
+history-transfer-save = Salva come file di cronologia (JSON)
+history-import-placeholder = Percorso del file di cronologia (.json) di un'altra postazione
+history-imported = { $added ->
+    [one] 1 voce importata
+   *[other] { $added } voci importate
+}, { $present } già presenti.
+history-score-not-loaded = Questo score non è più caricato.
+history-version-changed = Calcolato con la versione { $recorded } dello score; ora è caricata la versione { $current }. Le regole potrebbero essere cambiate.
+history-version-unknown = Calcolato con una definizione precedente dello score (versione sconosciuta); ora è caricata la versione { $current }.
+clear-history-title = Cancellare la cronologia?
+clear-history-body = Tutti i { $count } calcoli saranno eliminati. Fino alla chiusura dell'app è possibile annullare.
+
+## History encryption
+
+unlock-title = Sblocca cronologia
+unlock-body = La cronologia dei calcoli è cifrata. Senza la passphrase i nuovi calcoli non vengono salvati.
+unlock = Sblocca
+unlock-skip = Continua senza cronologia
+unlock-wrong-passphrase = Passphrase errata.
+unlock-failed = Sblocco non riuscito: { $error }
+history-locked = 🔒 La cronologia cifrata è bloccata; i nuovi calcoli non vengono salvati.
+passphrase-too-short = La passphrase deve contenere almeno { $min } caratteri.
+passphrase-mismatch = Le passphrase non coincidono.
+passphrase-repeat = Ripeti la passphrase
+encryption-label = Cifra la cronologia:
+encryption-hint = La cronologia può contenere dati quasi identificativi. Con una passphrase viene salvata cifrata e sbloccata all'avvio. Senza la passphrase non è recuperabile.
+encryption-enable = Cifra
+encryption-enabled = Cronologia cifrata.
+encryption-failed = Cifratura non riuscita: { $error }
+encryption-active = La cronologia viene salvata cifrata.
+encryption-disable = Rimuovi cifratura
+encryption-disabled = La cronologia viene salvata senza cifratura.
+decryption-failed = Decifratura non riuscita: { $error }
+
+## Score information
+
+info-version = { $specialty } · Versione { $version }
+info-intended-use = Destinazione d'uso
+info-regulatory-note = Nota regolatoria
+info-validated-in = Validato in
+info-clinical-question = Quesito clinico
+info-target-population = Popolazione target
+info-time-horizon = Orizzonte temporale
+info-contraindications = Non usare in
+info-category = Categoria
+info-tags = Parole chiave
+info-source = Fonte
+info-references = Bibliografia
+
+category-RiskStratification = Stratificazione del rischio
+category-Screening = Screening
+category-Diagnostic = Diagnostica
+category-Classification = Classificazione
+category-Other = Altro
+
+## About
+
+about-title = Informazioni su KlinScore
+about-version = Versione: { $version }
+about-scores-loaded = Score caricati: { $count }
+about-specialties = Specialità: { $specialties }
+about-guidelines = Linee guida: ESC 2024, KDIGO, ACCP, ASA
+about-license = Licenza: MIT / Apache 2.0
+about-disclaimer-title = Esclusione di responsabilità
+about-disclaimer = KlinScore è uno strumento di supporto alle decisioni cliniche. Non sostituisce il giudizio clinico di un medico. Tutti gli score vanno interpretati nel contesto clinico del paziente. Nessuna garanzia di correttezza o completezza.
+methodology-title = Metodologia degli score e fonti
+methodology-subtitle = Piena trasparenza sui metodi di calcolo e sulla loro base scientifica.
+methodology-method = Metodo: { $method }
+methodology-formula = Basato su formula
+methodology-points = Basato su punti
+methodology-points-detail = Somma dei punti di tutti i criteri applicabili
+methodology-inputs = Dati: { $inputs }
+methodology-input-boolean = Sì/No
+methodology-input-number = Numero
+methodology-input-dropdown = Selezione
+methodology-input-date = Data
+methodology-input-duration = Durata
+methodology-range = Intervallo raggiungibile: { $min } – { $max }
+methodology-calculation = Calcolo:
+methodology-reference = Riferimento ({ $source })
+formula-ckd-epi-2021 =
+    eGFR = 142 × min(Scr/κ, 1)^α × max(Scr/κ, 1)^(−1.200) × 0.9938^età × (1.012 se donna)
+    Dove: donna κ=0.7, α=−0.241 | uomo κ=0.9, α=−0.302
+    Scr in mg/dL (inserimento μmol/L ÷ 88.4)
+formula-kfre-4var =
+    Rischio = 1 − 0.9832^exp(somma)
+    somma = −0.2201×(età/10 − 7.036) + 0.2467×(uomo − 0.5642) − 0.5567×(eGFR/5 − 7.222) + 0.4510×(ln(ACR) − 5.137)
+    Inserimento ACR: mg/mmol, convertito internamente in mg/g (× 8.84)
+packs-title = --- Pacchetti di score installati ---
+pack-unverified = Non verificato (nessun checksum)
+pack-verified = Checksum verificati (non firmato)
+pack-untrusted = Checksum e firma validi, ma la chiave { $key } non è di un editore attendibile
+pack-signed = Checksum e firma verificati, chiave di editore attendibile { $key }
+pack-failed = Verifica di integrità non riuscita: { $reason }
+
+## Settings
+
+theme-label = Tema:
+theme-placeholder = Scegli il tema
+theme-current = Attuale: { $theme }
+theme-Light = Chiaro
+theme-Dark = Scuro
+theme-Sepia = Seppia
+theme-HighContrast = Contrasto elevato
+theme-Custom = Personalizzato
+theme-background = Sfondo
+theme-primary = Colore principale
+theme-risk-colors = Colori di rischio per
+theme-presets = Modelli:
+theme-invalid-color = Colore non valido, atteso es. #005EB8
+theme-preset-hospital-blue = Blu ospedale
+theme-preset-high-contrast = Contrasto elevato
+help-hints = Mostra suggerimenti accanto ai campi (ⓘ)
+wizard-long-scores = Compila passo dopo passo gli score lunghi (da { $count } campi)
+accessibility = Accessibilità:
+high-contrast = Contrasto elevato (testo bianco su nero)
+touch-mode = Modalità touch: pulsanti grandi e contorni di focus evidenti
+touch-hint = I livelli di rischio sono sempre mostrati anche con icona e testo.
+units-label = Valori di laboratorio:
+unit-system-Si = Unità SI (ad es. μmol/L)
+unit-system-Conventional = Unità convenzionali (ad es. mg/dL)
+units-hint = Si applica ai valori di laboratorio offerti in entrambe le unità. I valori vengono convertiti in unità SI per il calcolo e salvati in unità SI.
+research-label = Date nell'esportazione per la ricerca:
+research-hint = Identificativi dei casi e note vengono sempre rimossi. Attuale: { $precision }
+precision-Date = Solo data
+precision-Month = Mese
+precision-Year = Anno
+precision-Omit = Nessuna data
+patient-labels-disabled = Non registrare identificativi del paziente (nascondi il campo "Paziente")
+retention-label = Pulisci la cronologia automaticamente:
+retention-hint = { $mode }. Applicato all'avvio e dopo ogni calcolo.
+retention-invalid-limit = Inserire un numero intero di almeno 1.
+retention-keep-all = Conserva tutto
+retention-last-entries = Ultime N voci
+retention-last-days = Ultimi N giorni
+score-dirs = Cartelle di score aggiuntive:
+score-dirs-hint = Gli score YAML in queste cartelle (es. score interni dell'ospedale) vengono caricati e sostituiscono quelli forniti con lo stesso nome.
+score-dirs-placeholder = Percorso della cartella
+score-dir-not-found = Cartella non trovata: { $path }
+strict-loading = Caricamento rigoroso: non avviare se un file di score non è valido
+pack-import = Importa pacchetto di score:
+pack-import-hint = Un pacchetto .klinpack (zip con klinpack.yaml) viene verificato e installato nella cartella degli score dell'utente.
+pack-placeholder = Percorso del file .klinpack
+pack-no-user-dir = Nessuna cartella degli score dell'utente disponibile
+trusted-keys = Editori di pacchetti attendibili:
+trusted-keys-hint = Un pacchetto firmato è considerato verificato solo se la sua chiave è elencata qui. Aggiungere la chiave pubblica ed25519 (hex) fornita dall'editore, non quella contenuta nel pacchetto.
+trusted-key-placeholder = Chiave pubblica (64 caratteri esadecimali)
+trusted-key-invalid = Non è una chiave pubblica ed25519: { $key }
+pack-installed = Pacchetto di score '{ $name }' { $version } installato ({ $count ->
+        [one] 1 score
+       *[other] { $count } score
+    }). { $integrity }
+data-location = Posizione dei dati:
+data-location-platform = Profilo utente
+data-location-portable = Portatile (accanto al programma)
+data-location-custom = Cartella scelta (--data-dir)
+portable-mode = Modalità portatile: conserva i dati accanto al programma (es. su una chiavetta USB)
+portable-mode-hint = Ha effetto al prossimo avvio. I dati esistenti non vengono spostati; la cronologia può essere trasferita come file di cronologia.
+portable-mode-failed = Impossibile cambiare la modalità portatile: { $error }
+log-file = Scrivi un registro diagnostico (klinscore.log nella cartella dati)
+log-file-hint = Registra avvisi ed eventi, ad es. file di punteggio ignorati o esportazioni non riuscite. Non contiene input né risultati.
+export-dir = Cartella di esportazione:
+export-dir-hint = La finestra di salvataggio delle esportazioni si apre in questa cartella; ogni esportazione può comunque essere salvata altrove.
+export-dir-default = Cartella Documenti (predefinita)
+export-dir-choose = Scegli cartella…
+export-dir-choose-title = Scegli la cartella di esportazione
+export-dir-reset = Usa predefinita
+anonymize-label = Esportazioni anonimizzate:
+anonymize-timestamps = Rimuovi l'ora del calcolo
+anonymize-patient-labels = Rimuovi etichette paziente e titoli di sessione
+anonymize-notes = Rimuovi le note a testo libero
+anonymize-hint = Si applica a tutte le esportazioni, stampe e risultati copiati, ad es. per la didattica o la condivisione nella ricerca. La cronologia stessa non viene modificata.
+pdf-report = Report PDF:
+pdf-report-header = Intestazione (struttura):
+pdf-report-header-placeholder = ad es. Ospedale Nord – Reparto di Anestesiologia
+pdf-report-footer = Piè di pagina:
+pdf-report-footer-placeholder = ad es. indirizzo, numero di telefono
+pdf-report-bilingual = Report bilingui: stampa i testi anche in { $language }
+pdf-report-qr-code = Codice QR: stampare il risultato (o il suo checksum SHA-256) come codice QR sotto ogni risultato
+fhir-server = Server FHIR
+fhir-server-hint = URL di base di un server FHIR R4. I moduli dei punteggi i cui campi dichiarano codici LOINC possono quindi essere precompilati con gli ultimi valori del paziente tramite il suo ID.
+fhir-server-placeholder = es. https://fhir.ospedale.example/r4
+seal-label = Esportazioni a prova di manomissione:
+seal-exports = Sigilla le esportazioni PDF e JSON con un hash SHA-256
+sign-exports = Firma anche il sigillo con la chiave locale di questa installazione
+signing-key = Chiave pubblica: { $key }
+signing-key-failed = Chiave di firma non disponibile: { $error }
+seal-hint = I destinatari verificano un file sigillato con klinscore-verify; ogni modifica viene segnalata. Condividi la chiave pubblica perché vedano che il file è stato firmato qui (klinscore-verify --key).
+export-save-title = Salva esportazione
+backups = Backup:
+backups-hint = Una volta al giorno viene conservata una copia delle impostazioni e della cronologia nella cartella «backups» della posizione dei dati; vengono mantenute le ultime { $count }. Prima del ripristino, lo stato attuale viene salvato come ulteriore backup. Una cronologia cifrata (🔒) richiede la passphrase di allora.
+backups-empty = Ancora nessun backup.
+backup-restore = Ripristina
+backup-restored = Backup { $name } ripristinato.
+backup-restore-failed = Ripristino del backup non riuscito: { $error }
+settings-transfer = Condividi impostazioni:
+settings-transfer-hint = Salvare tutte le impostazioni (tema, lingua, accessibilità, cartelle di esportazione e dei punteggi, opzioni della cronologia) in un file e caricarlo su un'altra postazione per usare la stessa configurazione in tutto il reparto.
+settings-export = Esporta impostazioni…
+settings-import = Importa impostazioni…
+settings-import-title = Importa impostazioni
+settings-imported = Impostazioni importate da { $file }.
+
+## Specialties
+
+specialty-Cardiology = Cardiologia
+specialty-Nephrology = Nefrologia
+specialty-Anesthesiology = Anestesiologia
+specialty-Emergency = Medicina d'urgenza
+specialty-InternalMedicine = Medicina interna
+specialty-Surgery = Chirurgia
+specialty-Pulmonology = Pneumologia
+specialty-Neurology = Neurologia
+specialty-Gastroenterology = Gastroenterologia
+specialty-Hematology = Ematologia
+specialty-Oncology = Oncologia
+specialty-Endocrinology = Endocrinologia
+specialty-InfectiousDiseases = Malattie infettive
+specialty-Geriatrics = Geriatria
+specialty-Pediatrics = Pediatria
+specialty-IntensiveCare = Terapia intensiva
+specialty-Psychiatry = Psichiatria
+specialty-Other = Altro
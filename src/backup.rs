@@ -0,0 +1,324 @@
+// backup.rs
+// Daily snapshots of the settings and the history, and restoring them
+
+use std::fs;
+use std::path::Path;
+
+use chrono::{Local, NaiveDateTime};
+use thiserror::Error;
+
+use crate::history_store::{HistoryStoreError, SqliteHistoryStore};
+use crate::persistence::{
+    self, write_atomic, ENCRYPTED_HISTORY_FILE, HISTORY_DATABASE, SETTINGS_FILE,
+};
+
+/// Subfolder of the data directory holding one folder per snapshot
+const BACKUP_DIR: &str = "backups";
+/// Name of a snapshot folder; these sort by age
+const SNAPSHOT_FORMAT: &str = "%Y-%m-%d_%H-%M-%S";
+/// Snapshots kept; the oldest are removed when a new one is taken
+pub const MAX_SNAPSHOTS: usize = 7;
+
+#[derive(Debug, Error)]
+pub enum BackupError {
+    #[error("backup {0} not found")]
+    NotFound(String),
+
+    #[error(transparent)]
+    History(#[from] HistoryStoreError),
+
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// Copy of the settings and the history taken at one point in time
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snapshot {
+    /// Folder name in `backups/`
+    pub name: String,
+    pub created: NaiveDateTime,
+    /// The history in the snapshot is encrypted and needs its passphrase
+    pub encrypted: bool,
+}
+
+/// Available snapshots, newest first
+pub fn snapshots() -> Vec<Snapshot> {
+    persistence::data_dir()
+        .map(|dir| snapshots_in(&dir))
+        .unwrap_or_default()
+}
+
+fn snapshots_in(dir: &Path) -> Vec<Snapshot> {
+    let Ok(entries) = fs::read_dir(dir.join(BACKUP_DIR)) else {
+        return Vec::new();
+    };
+    let mut snapshots: Vec<Snapshot> = entries
+        .filter_map(Result::ok)
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| {
+            let name = entry.file_name().into_string().ok()?;
+            // Skips snapshots still being written
+            let created = NaiveDateTime::parse_from_str(&name, SNAPSHOT_FORMAT).ok()?;
+            Some(Snapshot {
+                encrypted: entry.path().join(ENCRYPTED_HISTORY_FILE).exists(),
+                name,
+                created,
+            })
+        })
+        .collect();
+    snapshots.sort_by_key(|snapshot| std::cmp::Reverse(snapshot.created));
+    snapshots
+}
+
+/// Take a snapshot unless there already is one from today; returns the new snapshot
+pub fn create_daily_snapshot() -> Result<Option<Snapshot>, BackupError> {
+    let dir = persistence::require_data_dir()?;
+    create_daily_snapshot_in(&dir, Local::now().naive_local())
+}
+
+fn create_daily_snapshot_in(
+    dir: &Path,
+    now: NaiveDateTime,
+) -> Result<Option<Snapshot>, BackupError> {
+    if snapshots_in(dir)
+        .iter()
+        .any(|snapshot| snapshot.created.date() == now.date())
+    {
+        return Ok(None);
+    }
+    let snapshot = create_snapshot_in(dir, now)?;
+    rotate_in(dir)?;
+    Ok(snapshot)
+}
+
+/// Copy the settings and the history into a new snapshot folder; `None` if
+/// nothing has been saved yet
+fn create_snapshot_in(dir: &Path, now: NaiveDateTime) -> Result<Option<Snapshot>, BackupError> {
+    let settings = dir.join(SETTINGS_FILE);
+    let database = dir.join(HISTORY_DATABASE);
+    let encrypted = dir.join(ENCRYPTED_HISTORY_FILE);
+    if !settings.exists() && !database.exists() && !encrypted.exists() {
+        return Ok(None);
+    }
+
+    let name = now.format(SNAPSHOT_FORMAT).to_string();
+    let backups = dir.join(BACKUP_DIR);
+    // Written under a temporary name, so an interrupted snapshot is never listed
+    let temp = backups.join(format!("{name}.tmp"));
+    if temp.exists() {
+        fs::remove_dir_all(&temp)?;
+    }
+    fs::create_dir_all(&temp)?;
+
+    if settings.exists() {
+        fs::copy(&settings, temp.join(SETTINGS_FILE))?;
+    }
+    // The encrypted file is copied as is, so no passphrase is needed
+    if encrypted.exists() {
+        fs::copy(&encrypted, temp.join(ENCRYPTED_HISTORY_FILE))?;
+    } else if database.exists() {
+        SqliteHistoryStore::new(&database).backup_to(&temp.join(HISTORY_DATABASE))?;
+    }
+
+    let target = backups.join(&name);
+    if target.exists() {
+        fs::remove_dir_all(&target)?;
+    }
+    fs::rename(&temp, &target)?;
+    Ok(Some(Snapshot {
+        name,
+        created: now,
+        encrypted: encrypted.exists(),
+    }))
+}
+
+/// Remove all but the newest `MAX_SNAPSHOTS` snapshots
+fn rotate_in(dir: &Path) -> Result<(), BackupError> {
+    for snapshot in snapshots_in(dir).into_iter().skip(MAX_SNAPSHOTS) {
+        fs::remove_dir_all(dir.join(BACKUP_DIR).join(&snapshot.name))?;
+    }
+    Ok(())
+}
+
+/// Replace the settings and the history with those of snapshot `name`.
+///
+/// The current state is saved as a snapshot first, so a restore can be undone.
+/// The caller has to load the settings and the history again.
+pub fn restore_snapshot(name: &str) -> Result<(), BackupError> {
+    let dir = persistence::require_data_dir()?;
+    restore_snapshot_in(&dir, name, Local::now().naive_local())
+}
+
+fn restore_snapshot_in(dir: &Path, name: &str, now: NaiveDateTime) -> Result<(), BackupError> {
+    if !snapshots_in(dir)
+        .iter()
+        .any(|snapshot| snapshot.name == name)
+    {
+        return Err(BackupError::NotFound(name.to_string()));
+    }
+    let source = dir.join(BACKUP_DIR).join(name);
+    if now.format(SNAPSHOT_FORMAT).to_string() != name {
+        create_snapshot_in(dir, now)?;
+    }
+
+    let settings = source.join(SETTINGS_FILE);
+    if settings.exists() {
+        write_atomic(&dir.join(SETTINGS_FILE), &fs::read(&settings)?)?;
+    }
+
+    let database = dir.join(HISTORY_DATABASE);
+    let encrypted = dir.join(ENCRYPTED_HISTORY_FILE);
+    if source.join(ENCRYPTED_HISTORY_FILE).exists() {
+        write_atomic(&encrypted, &fs::read(source.join(ENCRYPTED_HISTORY_FILE))?)?;
+        persistence::remove_database(&database)?;
+    } else if source.join(HISTORY_DATABASE).exists() {
+        // The log of the current database must not be applied to the restored one
+        let mut temp = database.as_os_str().to_owned();
+        temp.push(".tmp");
+        fs::copy(source.join(HISTORY_DATABASE), &temp)?;
+        persistence::remove_database(&database)?;
+        fs::rename(&temp, &database)?;
+        persistence::remove_if_exists(&encrypted)?;
+    }
+
+    // Only now, as the restored snapshot may have been the oldest
+    rotate_in(dir)
+}
+
+/// Remove the plaintext history from all snapshots, once the history is encrypted
+pub fn remove_plain_history_in(dir: &Path) -> std::io::Result<()> {
+    for snapshot in snapshots_in(dir) {
+        let database = dir
+            .join(BACKUP_DIR)
+            .join(&snapshot.name)
+            .join(HISTORY_DATABASE);
+        persistence::remove_if_exists(&database)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Specialty;
+    use crate::history::tests::entry;
+    use crate::history_store::HistoryStore;
+
+    fn at(timestamp: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(timestamp, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    fn history_len(dir: &Path) -> usize {
+        SqliteHistoryStore::new(dir.join(HISTORY_DATABASE))
+            .load()
+            .unwrap()
+            .len()
+    }
+
+    #[test]
+    fn test_daily_snapshot_rotation() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        assert_eq!(
+            create_daily_snapshot_in(dir, at("2026-03-01 08:00")).unwrap(),
+            None
+        );
+
+        fs::write(dir.join(SETTINGS_FILE), "{}").unwrap();
+        SqliteHistoryStore::new(dir.join(HISTORY_DATABASE))
+            .save(&[entry("egfr", Specialty::Nephrology, "2026-03-01 07:55")])
+            .unwrap();
+        let first = create_daily_snapshot_in(dir, at("2026-03-01 08:00"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(first.name, "2026-03-01_08-00-00");
+        assert!(!first.encrypted);
+        // Once per day
+        assert_eq!(
+            create_daily_snapshot_in(dir, at("2026-03-01 17:00")).unwrap(),
+            None
+        );
+
+        for day in 2..=10 {
+            let now = at(&format!("2026-03-{day:02} 08:00"));
+            assert!(create_daily_snapshot_in(dir, now).unwrap().is_some());
+        }
+        let snapshots = snapshots_in(dir);
+        assert_eq!(snapshots.len(), MAX_SNAPSHOTS);
+        assert_eq!(snapshots[0].name, "2026-03-10_08-00-00");
+        assert_eq!(snapshots[MAX_SNAPSHOTS - 1].name, "2026-03-04_08-00-00");
+    }
+
+    #[test]
+    fn test_restore_snapshot() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        let store = SqliteHistoryStore::new(dir.join(HISTORY_DATABASE));
+        fs::write(dir.join(SETTINGS_FILE), "monday").unwrap();
+        store
+            .save(&[entry("egfr", Specialty::Nephrology, "2026-03-02 09:00")])
+            .unwrap();
+        create_daily_snapshot_in(dir, at("2026-03-02 09:30")).unwrap();
+
+        fs::write(dir.join(SETTINGS_FILE), "tuesday").unwrap();
+        store.save(&[]).unwrap();
+        restore_snapshot_in(dir, "2026-03-02_09-30-00", at("2026-03-03 11:00")).unwrap();
+
+        assert_eq!(fs::read(dir.join(SETTINGS_FILE)).unwrap(), b"monday");
+        assert_eq!(history_len(dir), 1);
+        // The state before the restore was kept
+        let names: Vec<String> = snapshots_in(dir).into_iter().map(|s| s.name).collect();
+        assert_eq!(names, ["2026-03-03_11-00-00", "2026-03-02_09-30-00"]);
+        let before = dir.join(BACKUP_DIR).join("2026-03-03_11-00-00");
+        assert_eq!(fs::read(before.join(SETTINGS_FILE)).unwrap(), b"tuesday");
+
+        assert!(matches!(
+            restore_snapshot_in(dir, "2026-01-01_00-00-00", at("2026-03-03 11:05")),
+            Err(BackupError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_restore_encrypted_snapshot() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        fs::write(dir.join(ENCRYPTED_HISTORY_FILE), "age ciphertext").unwrap();
+        let snapshot = create_daily_snapshot_in(dir, at("2026-03-02 09:30"))
+            .unwrap()
+            .unwrap();
+        assert!(snapshot.encrypted);
+
+        // Encryption switched off afterwards
+        fs::remove_file(dir.join(ENCRYPTED_HISTORY_FILE)).unwrap();
+        SqliteHistoryStore::new(dir.join(HISTORY_DATABASE))
+            .save(&[entry("asa", Specialty::Anesthesiology, "2026-03-03 07:00")])
+            .unwrap();
+
+        restore_snapshot_in(dir, &snapshot.name, at("2026-03-03 11:00")).unwrap();
+        assert_eq!(
+            fs::read(dir.join(ENCRYPTED_HISTORY_FILE)).unwrap(),
+            b"age ciphertext"
+        );
+        assert!(!dir.join(HISTORY_DATABASE).exists());
+    }
+
+    #[test]
+    fn test_encryption_removes_plain_snapshots() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        SqliteHistoryStore::new(dir.join(HISTORY_DATABASE))
+            .save(&[entry("egfr", Specialty::Nephrology, "2026-03-02 09:00")])
+            .unwrap();
+        let snapshot = create_daily_snapshot_in(dir, at("2026-03-02 09:30"))
+            .unwrap()
+            .unwrap();
+        let copy = dir
+            .join(BACKUP_DIR)
+            .join(&snapshot.name)
+            .join(HISTORY_DATABASE);
+        assert!(copy.exists());
+
+        remove_plain_history_in(dir).unwrap();
+        assert!(!copy.exists());
+    }
+}
@@ -0,0 +1,262 @@
+// batch.rs
+// Batch calculation over a file of patient rows (CSV or a JSON array of objects),
+// for research cohorts and retrospective audits: one or several scores are run
+// over every row and the results written to a CSV or JSON file
+//
+// Columns are matched to score inputs by field name; a column named `id` is copied
+// to the results, other columns are ignored. Empty cells count as not entered.
+
+use crate::cli::{fill_defaults, parse_value};
+use crate::config::{RiskLevel, ScoreDefinition};
+use crate::scores::{calculate_score, CalculationResult};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+use thiserror::Error;
+
+/// Column copied to the results to identify a row
+pub const ID_COLUMN: &str = "id";
+
+#[derive(Debug, Error)]
+pub enum BatchError {
+    #[error("Failed to read {path}: {source}")]
+    Read {
+        path: String,
+        source: std::io::Error,
+    },
+
+    #[error("Failed to write {path}: {source}")]
+    Write {
+        path: String,
+        source: std::io::Error,
+    },
+
+    #[error("Invalid CSV: {0}")]
+    Csv(#[from] csv::Error),
+
+    #[error("Invalid JSON: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("Row {0} of the JSON file is not an object")]
+    NotAnObject(usize),
+
+    #[error("Unsupported file type of {0} (expected .csv or .json)")]
+    UnsupportedFormat(String),
+}
+
+/// Layout of an input or results file, from its extension
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BatchFormat {
+    Csv,
+    Json,
+}
+
+impl BatchFormat {
+    pub fn from_path(path: &Path) -> Result<Self, BatchError> {
+        let extension = path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(str::to_ascii_lowercase);
+        match extension.as_deref() {
+            Some("csv") => Ok(Self::Csv),
+            Some("json") => Ok(Self::Json),
+            _ => Err(BatchError::UnsupportedFormat(path.display().to_string())),
+        }
+    }
+}
+
+/// One row of input: the text of every non-empty cell, by column
+pub type InputRow = BTreeMap<String, String>;
+
+/// Result of one score for one row
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BatchResult {
+    /// Row in the input file, counting from 1
+    pub row: usize,
+    pub id: Option<String>,
+    pub score: String,
+    pub total_score: Option<i32>,
+    pub risk: Option<String>,
+    pub risk_level: Option<RiskLevel>,
+    /// Why the row could not be calculated
+    pub error: Option<String>,
+}
+
+/// Rows of a CSV file (with a header line) or a JSON array of objects
+pub fn read_rows(path: &Path) -> Result<Vec<InputRow>, BatchError> {
+    let format = BatchFormat::from_path(path)?;
+    let source = fs::read_to_string(path).map_err(|source| BatchError::Read {
+        path: path.display().to_string(),
+        source,
+    })?;
+    match format {
+        BatchFormat::Csv => rows_from_csv(&source),
+        BatchFormat::Json => rows_from_json(&source),
+    }
+}
+
+pub fn rows_from_csv(source: &str) -> Result<Vec<InputRow>, BatchError> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_reader(source.as_bytes());
+    let headers = reader.headers()?.clone();
+    reader
+        .records()
+        .map(|record| {
+            Ok(headers
+                .iter()
+                .zip(record?.iter())
+                .filter(|(_, value)| !value.is_empty())
+                .map(|(column, value)| (column.to_string(), value.to_string()))
+                .collect())
+        })
+        .collect()
+}
+
+/// Rows of a JSON array; numbers and booleans are taken as their text, `null`
+/// as not entered
+pub fn rows_from_json(source: &str) -> Result<Vec<InputRow>, BatchError> {
+    let rows: Vec<serde_json::Value> = serde_json::from_str(source)?;
+    rows.into_iter()
+        .enumerate()
+        .map(|(index, row)| {
+            let serde_json::Value::Object(row) = row else {
+                return Err(BatchError::NotAnObject(index + 1));
+            };
+            Ok(row
+                .into_iter()
+                .filter_map(|(column, value)| {
+                    let text = match value {
+                        serde_json::Value::Null => return None,
+                        serde_json::Value::String(text) => text,
+                        value => value.to_string(),
+                    };
+                    Some((column, text))
+                })
+                .collect())
+        })
+        .collect()
+}
+
+/// Run each score over each row
+///
+/// A row that cannot be calculated (e.g., a required input is missing or a value
+/// is invalid) gets an error instead of a result; the other rows are not affected.
+pub fn run_batch(
+    scores: &[(&str, &ScoreDefinition)],
+    rows: &[InputRow],
+    use_german: bool,
+) -> Vec<BatchResult> {
+    let language = if use_german { "de" } else { "en" };
+    let mut results = Vec::with_capacity(rows.len() * scores.len());
+    for (index, row) in rows.iter().enumerate() {
+        for (score_id, score) in scores {
+            let mut result = BatchResult {
+                row: index + 1,
+                id: row.get(ID_COLUMN).cloned(),
+                score: score_id.to_string(),
+                total_score: None,
+                risk: None,
+                risk_level: None,
+                error: None,
+            };
+            match calculate_row(score, row) {
+                Ok(calculation) => {
+                    result.total_score = Some(calculation.total_score);
+                    result.risk = Some(calculation.risk.text(language).to_string());
+                    result.risk_level = Some(calculation.risk_level);
+                }
+                Err(e) => result.error = Some(e),
+            }
+            results.push(result);
+        }
+    }
+    results
+}
+
+/// Calculate `score` from the cells of one row, as [`run_batch`] does for each row
+pub fn calculate_row(score: &ScoreDefinition, row: &InputRow) -> Result<CalculationResult, String> {
+    let mut inputs = HashMap::new();
+    for field in &score.inputs {
+        if let Some(value) = row.get(&field.field) {
+            let value = parse_value(field, value).map_err(|e| e.to_string())?;
+            inputs.insert(field.field.clone(), value);
+        }
+    }
+    fill_defaults(score, &mut inputs);
+    calculate_score(score, &inputs).map_err(|e| e.to_string())
+}
+
+/// The results as CSV, one line per row and score
+pub fn results_to_csv(results: &[BatchResult]) -> Result<String, BatchError> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    for result in results {
+        writer.serialize(result)?;
+    }
+    let bytes = writer
+        .into_inner()
+        .map_err(|e| BatchError::Csv(e.into_error().into()))?;
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+/// Write the results as CSV or JSON, by the extension of `path`
+pub fn write_results(results: &[BatchResult], path: &Path) -> Result<(), BatchError> {
+    let contents = match BatchFormat::from_path(path)? {
+        BatchFormat::Csv => results_to_csv(results)?,
+        BatchFormat::Json => serde_json::to_string_pretty(results)?,
+    };
+    fs::write(path, contents).map_err(|source| BatchError::Write {
+        path: path.display().to_string(),
+        source,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scores::load_bundled_scores;
+
+    #[test]
+    fn test_batch_over_csv_and_json() {
+        let library = load_bundled_scores();
+        let scores = [
+            ("cha2ds2_va", &library.scores["cha2ds2_va"]),
+            ("has_bled", &library.scores["has_bled"]),
+        ];
+
+        // Both scores read the hypertension column
+        let csv = "id,age,heart_failure,hypertension,elderly,mrn\n\
+                   P1,72,yes,1,1,4711\n\
+                   P2,,no,0,,4712\n";
+        let rows = rows_from_csv(csv).unwrap();
+        assert_eq!(rows.len(), 2);
+        assert!(!rows[1].contains_key("elderly"));
+
+        let results = run_batch(&scores, &rows, false);
+        assert_eq!(results.len(), 4);
+        assert_eq!(results[0].id.as_deref(), Some("P1"));
+        assert_eq!(results[0].score, "cha2ds2_va");
+        assert_eq!(results[0].total_score, Some(3));
+        assert_eq!(results[1].score, "has_bled");
+        assert_eq!(results[1].total_score, Some(2));
+        // P2 has no age, which CHA2DS2-VA requires
+        assert_eq!(results[2].total_score, None);
+        assert!(results[2].error.as_deref().unwrap().contains("age"));
+        assert_eq!(results[3].total_score, Some(0));
+
+        let json = r#"[{"id": "P1", "age": 72, "heart_failure": true, "hypertension": null}]"#;
+        let rows = rows_from_json(json).unwrap();
+        assert_eq!(rows[0]["age"], "72");
+        let results = run_batch(&scores[..1], &rows, false);
+        assert_eq!(results[0].total_score, Some(2));
+
+        let output = results_to_csv(&results).unwrap();
+        assert!(output.starts_with("row,id,score,total_score,risk,risk_level,error\n"));
+        assert!(output.contains("1,P1,cha2ds2_va,2,"));
+        assert!(matches!(
+            rows_from_json("[1]"),
+            Err(BatchError::NotAnObject(1))
+        ));
+    }
+}
@@ -0,0 +1,252 @@
+// KlinScore command-line calculator
+// cli.rs
+//
+// Usage:
+//   klinscore-cli calc <score> [--<input> [value]]... [--format text|json|fhir|result]
+//                      [--lang en|de] [--scores-dir <folder>]
+//                                               Calculate a score and print the result
+//   klinscore-cli batch <rows.csv|rows.json> --score <score>... [--output <results.csv|json>]
+//                                               Run scores over every row of a file
+//   klinscore-cli list [--specialty <specialty>] List the available scores
+//   klinscore-cli describe <score>              Print a score's inputs and interpretation
+//   klinscore-cli test [<score>...]             Run the test cases of the score files
+//   klinscore-cli import <questionnaire.json|calculator.json>
+//                                               Convert a FHIR Questionnaire or another
+//                                               calculator's JSON into a score file
+
+use klinscore::batch::{results_to_csv, write_results};
+use klinscore::cli::{
+    BatchCommand, CalcCommand, CliError, ImportCommand, InfoCommand, TestCommand,
+};
+use klinscore::scores::{load_score_library, user_scores_dir, ScoreLibrary};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+const USAGE: &str = "Usage: klinscore-cli calc <score> [--<input> [value]]... \
+[--format text|json|fhir|result] [--lang en|de] [--scores-dir <folder>]
+       klinscore-cli batch <rows.csv|rows.json> --score <score>... \
+[--output <results.csv|results.json>] [--lang en|de] [--scores-dir <folder>]
+       klinscore-cli list [--specialty <specialty>] [--lang en|de] [--scores-dir <folder>]
+       klinscore-cli describe <score> [--lang en|de] [--scores-dir <folder>]
+       klinscore-cli test [<score>...] [--scores-dir <folder>]
+       klinscore-cli import <questionnaire.json|calculator.json> [--output <score.yaml>]
+
+Inputs are named as in the score file (e.g. --heart_failure or --heart-failure).
+A boolean input given without a value is true, one left out is false.
+Numbers are in the score's units (SI units, e.g. creatinine in µmol/L), dates
+are written YYYY-MM-DD or today (e.g. --assessment-date today), durations as
+hours:minutes or with units (e.g. --onset 4:30 or --onset '1d 6h').
+
+In batch files, columns are named like the inputs and a column `id` is copied
+to the results. Without --output, the results are printed as CSV.
+
+test runs the test cases of every score (or the given ones) and exits with 1 if
+any fails.
+
+import converts a FHIR Questionnaire with scoring extensions, or an MDCalc- or
+OpenRiskCalc-style calculator description, into a score file; save it to the
+scores folder to calculate it. Parts that could not be converted are listed.
+
+Example: klinscore-cli calc cha2ds2_va --age 72 --heart-failure --hypertension --format json";
+
+fn main() -> ExitCode {
+    klinscore::logging::init();
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    if args.iter().any(|a| a == "--help" || a == "-h") || args.is_empty() {
+        eprintln!("{}", USAGE);
+        return if args.is_empty() {
+            ExitCode::from(2)
+        } else {
+            ExitCode::SUCCESS
+        };
+    }
+
+    match args[0].as_str() {
+        "calc" => calc(&args[1..]),
+        "batch" => batch(&args[1..]),
+        "list" | "describe" => info(&args[0], &args[1..]),
+        "test" => test(&args[1..]),
+        "import" => import(&args[1..]),
+        other => {
+            eprintln!("klinscore-cli: unknown command '{}'\n\n{}", other, USAGE);
+            ExitCode::from(2)
+        }
+    }
+}
+
+fn calc(args: &[String]) -> ExitCode {
+    let command = match CalcCommand::parse(args) {
+        Ok(command) => command,
+        Err(e) => {
+            eprintln!("klinscore-cli: {}\n\n{}", e, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    let Some(library) = load_library(&command.score_dirs) else {
+        return ExitCode::FAILURE;
+    };
+
+    match command.run(&library) {
+        Ok(output) => {
+            println!("{}", output.trim_end());
+            ExitCode::SUCCESS
+        }
+        Err(e @ (CliError::Calculation(_) | CliError::Output(_))) => {
+            eprintln!("klinscore-cli: {}", e);
+            ExitCode::FAILURE
+        }
+        Err(e) => {
+            eprintln!("klinscore-cli: {}", e);
+            ExitCode::from(2)
+        }
+    }
+}
+
+fn batch(args: &[String]) -> ExitCode {
+    let command = match BatchCommand::parse(args) {
+        Ok(command) => command,
+        Err(e) => {
+            eprintln!("klinscore-cli: {}\n\n{}", e, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+    let Some(library) = load_library(&command.score_dirs) else {
+        return ExitCode::FAILURE;
+    };
+
+    let results = match command.run(&library) {
+        Ok(results) => results,
+        Err(e @ CliError::Batch(_)) => {
+            eprintln!("klinscore-cli: {}", e);
+            return ExitCode::FAILURE;
+        }
+        Err(e) => {
+            eprintln!("klinscore-cli: {}", e);
+            return ExitCode::from(2);
+        }
+    };
+    let written = match &command.output {
+        Some(path) => write_results(&results, path),
+        None => results_to_csv(&results).map(|csv| print!("{}", csv)),
+    };
+    if let Err(e) = written {
+        eprintln!("klinscore-cli: {}", e);
+        return ExitCode::FAILURE;
+    }
+
+    let failed = results
+        .iter()
+        .filter(|result| result.error.is_some())
+        .count();
+    eprintln!(
+        "{} result(s) calculated, {} failed",
+        results.len() - failed,
+        failed
+    );
+    ExitCode::SUCCESS
+}
+
+fn info(subcommand: &str, args: &[String]) -> ExitCode {
+    let command = match InfoCommand::parse(args) {
+        Ok(command) => command,
+        Err(e) => {
+            eprintln!("klinscore-cli: {}\n\n{}", e, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+    let Some(library) = load_library(&command.score_dirs) else {
+        return ExitCode::FAILURE;
+    };
+
+    let output = if subcommand == "list" {
+        Ok(command.list(&library))
+    } else {
+        command.describe(&library)
+    };
+    match output {
+        Ok(output) => {
+            print!("{}", output);
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("klinscore-cli: {}", e);
+            ExitCode::from(2)
+        }
+    }
+}
+
+fn test(args: &[String]) -> ExitCode {
+    let command = match TestCommand::parse(args) {
+        Ok(command) => command,
+        Err(e) => {
+            eprintln!("klinscore-cli: {}\n\n{}", e, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+    let Some(library) = load_library(&command.score_dirs) else {
+        return ExitCode::FAILURE;
+    };
+
+    let report = match command.run(&library) {
+        Ok(report) => report,
+        Err(e) => {
+            eprintln!("klinscore-cli: {}", e);
+            return ExitCode::from(2);
+        }
+    };
+    print!("{}", report.output);
+    if !report.untested.is_empty() {
+        println!("no test cases: {}", report.untested.join(", "));
+    }
+    println!("{} case(s) run, {} failed", report.cases, report.failed);
+    if report.failed == 0 {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+fn import(args: &[String]) -> ExitCode {
+    let command = match ImportCommand::parse(args) {
+        Ok(command) => command,
+        Err(e) => {
+            eprintln!("klinscore-cli: {}\n\n{}", e, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+    let (yaml, warnings) = match command.run() {
+        Ok(imported) => imported,
+        Err(e) => {
+            eprintln!("klinscore-cli: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let written = match &command.output {
+        Some(path) => std::fs::write(path, yaml),
+        None => {
+            print!("{}", yaml);
+            Ok(())
+        }
+    };
+    if let Err(e) = written {
+        eprintln!("klinscore-cli: {}", e);
+        return ExitCode::FAILURE;
+    }
+    for warning in &warnings {
+        eprintln!("klinscore-cli: not converted: {}", warning);
+    }
+    ExitCode::SUCCESS
+}
+
+/// Bundled and user scores, plus those in `score_dirs`
+fn load_library(score_dirs: &[PathBuf]) -> Option<ScoreLibrary> {
+    let dirs: Vec<_> = user_scores_dir()
+        .into_iter()
+        .chain(score_dirs.iter().cloned())
+        .collect();
+    load_score_library(&dirs)
+        .map_err(|e| eprintln!("klinscore-cli: {}", e))
+        .ok()
+}
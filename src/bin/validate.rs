@@ -0,0 +1,157 @@
+// KlinScore score definition validator
+// validate.rs
+//
+// Usage:
+//   klinscore-validate <file-or-directory>...   Validate score files (YAML, JSON, TOML)
+//   klinscore-validate --schema                  Print the JSON Schema for score files
+
+use klinscore::scores::bases::BaseDefinitions;
+use klinscore::scores::validation::{
+    score_files_in, score_json_schema, validate_score_source_with,
+};
+use klinscore::scores::{interpretation_coverage, run_test_cases, score_id_for, ScoreFormat};
+use std::collections::HashMap;
+use std::fs;
+use std::io::{IsTerminal, Write};
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    klinscore::logging::init();
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    if args.iter().any(|a| a == "--help" || a == "-h") || args.is_empty() {
+        eprintln!("Usage: klinscore-validate <file-or-directory>...");
+        eprintln!("       klinscore-validate --schema");
+        return if args.is_empty() {
+            ExitCode::FAILURE
+        } else {
+            ExitCode::SUCCESS
+        };
+    }
+
+    if args.iter().any(|a| a == "--schema") {
+        let schema = score_json_schema();
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&schema).expect("schema serializes")
+        );
+        return ExitCode::SUCCESS;
+    }
+
+    let mut checked = 0;
+    let mut failed = 0;
+    let mut ids: HashMap<String, String> = HashMap::new();
+
+    let mut files = Vec::new();
+    for arg in &args {
+        match score_files_in(arg) {
+            Ok(found) => files.extend(found),
+            Err(e) => {
+                eprintln!("{}: error: {}", arg, e);
+                failed += 1;
+            }
+        }
+    }
+
+    // Progress bar on interactive terminals; cleared before every diagnostic
+    let progress = Progress {
+        total: files.len(),
+        enabled: std::io::stderr().is_terminal(),
+    };
+
+    for file in files {
+        progress.show(checked);
+        checked += 1;
+        let report = |message: String| {
+            progress.clear();
+            eprintln!("{}", message);
+        };
+
+        let display = file.display().to_string();
+        let source = match fs::read_to_string(&file) {
+            Ok(source) => source,
+            Err(e) => {
+                report(format!("{}: error: {}\n", display, e));
+                failed += 1;
+                continue;
+            }
+        };
+
+        let format = ScoreFormat::from_path(&file).unwrap_or(ScoreFormat::Yaml);
+        let bases = match BaseDefinitions::for_path(&file) {
+            Ok(bases) => bases,
+            Err(e) => {
+                report(format!("{}: error: {}\n", display, e));
+                failed += 1;
+                continue;
+            }
+        };
+        match validate_score_source_with(&source, format, &bases) {
+            Ok(score) => {
+                for issue in interpretation_coverage(&score) {
+                    report(format!("{}: warning: {}", display, issue));
+                }
+                let failures = run_test_cases(&score);
+                for failure in &failures {
+                    report(format!("{}: error: {}", display, failure));
+                }
+                if !failures.is_empty() {
+                    failed += 1;
+                }
+                let id = score_id_for(&score, &file);
+                if let Some(first) = ids.get(&id) {
+                    report(format!(
+                        "{}: error: duplicate score id '{}' (already used by {})\n",
+                        display, id, first
+                    ));
+                    failed += 1;
+                } else {
+                    ids.insert(id, display);
+                }
+            }
+            Err(diagnostic) => {
+                report(format!("{}\n", diagnostic.render(&display, &source)));
+                failed += 1;
+            }
+        }
+    }
+
+    progress.clear();
+    println!("{} file(s) checked, {} with errors", checked, failed);
+    if failed == 0 {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+/// Single-line progress bar on stderr
+struct Progress {
+    total: usize,
+    enabled: bool,
+}
+
+impl Progress {
+    const WIDTH: usize = 30;
+
+    fn show(&self, done: usize) {
+        if !self.enabled || self.total == 0 {
+            return;
+        }
+        let filled = done * Self::WIDTH / self.total;
+        eprint!(
+            "\r[{}{}] {}/{}",
+            "#".repeat(filled),
+            " ".repeat(Self::WIDTH - filled),
+            done,
+            self.total
+        );
+        let _ = std::io::stderr().flush();
+    }
+
+    fn clear(&self) {
+        if self.enabled {
+            eprint!("\r\x1b[K");
+        }
+    }
+}
@@ -0,0 +1,81 @@
+// KlinScore export verifier
+// verify.rs
+//
+// Usage:
+//   klinscore-verify <file>...                  Check sealed PDF and JSON exports
+//   klinscore-verify --key <public-key> <file>...
+//                                               Also require a signature by that key
+
+use klinscore::export::integrity::{verify_file, Verification};
+use std::path::Path;
+use std::process::ExitCode;
+
+const USAGE: &str = "Usage: klinscore-verify [--key <public-key>] <file>...";
+
+fn main() -> ExitCode {
+    klinscore::logging::init();
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    if args.iter().any(|a| a == "--help" || a == "-h") || args.is_empty() {
+        eprintln!("{}", USAGE);
+        return if args.is_empty() {
+            ExitCode::FAILURE
+        } else {
+            ExitCode::SUCCESS
+        };
+    }
+
+    let mut required_key = None;
+    let mut files = Vec::new();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        if arg == "--key" {
+            match args.next() {
+                Some(key) => required_key = Some(key.trim().to_ascii_lowercase()),
+                None => {
+                    eprintln!("klinscore-verify: --key needs a public key\n\n{}", USAGE);
+                    return ExitCode::FAILURE;
+                }
+            }
+        } else {
+            files.push(arg);
+        }
+    }
+
+    let mut failed = 0;
+    for file in &files {
+        let (message, ok) = match verify_file(Path::new(file)) {
+            Ok(Verification::Intact { signed_by: None }) if required_key.is_some() => {
+                ("error: intact, but not signed".to_string(), false)
+            }
+            Ok(Verification::Intact { signed_by: None }) => ("intact".to_string(), true),
+            Ok(Verification::Intact {
+                signed_by: Some(key),
+            }) => match &required_key {
+                Some(required) if *required != key => (
+                    format!("error: intact, but signed by another key ({})", key),
+                    false,
+                ),
+                _ => (format!("intact, signed by {}", key), true),
+            },
+            Ok(Verification::Altered) => (
+                "error: ALTERED - the content does not match its hash".to_string(),
+                false,
+            ),
+            Ok(Verification::InvalidSignature) => ("error: invalid signature".to_string(), false),
+            Ok(Verification::Unsealed) => ("error: not sealed".to_string(), false),
+            Err(e) => (format!("error: {}", e), false),
+        };
+        println!("{}: {}", file, message);
+        if !ok {
+            failed += 1;
+        }
+    }
+
+    println!("{} file(s) checked, {} failed", files.len(), failed);
+    if failed == 0 {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
@@ -0,0 +1,985 @@
+// cli.rs
+// Headless calculation of scores from the command line (`klinscore-cli`), for
+// scripts and EHR glue code: `--field value` arguments become score inputs, and the
+// result is written with the text, JSON or FHIR exporters. `list` and `describe`
+// print the available scores and what they expect, for integrators.
+//
+// Finding scores and parsing inputs only need the `loader` feature, so the
+// language bindings share them; `calc` and its output formats need `app`.
+
+use crate::batch::{read_rows, run_batch, BatchError, BatchResult};
+#[cfg(feature = "app")]
+use crate::config::Reference;
+use crate::config::{InputField, InputType, PointsValue, ScoreDefinition, ScoreRange, Specialty};
+#[cfg(feature = "app")]
+use crate::export::{fhir_export, json_export, text_export, ExportRecord};
+#[cfg(feature = "app")]
+use crate::scores::calculate_score;
+use crate::scores::durations::parse_duration;
+use crate::scores::numbers::parse_decimal;
+use crate::scores::questionnaire::{score_from_questionnaire, QuestionnaireError};
+use crate::scores::third_party::{score_from_calculator_json, ThirdPartyError};
+use crate::scores::{
+    run_test_cases, CalculationError, CalendarDate, InputValue, Quantity, ScoreFilter, ScoreLibrary,
+};
+use chrono::Datelike;
+use std::collections::HashMap;
+use std::path::PathBuf;
+#[cfg(feature = "app")]
+use std::str::FromStr;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum CliError {
+    #[error("Unknown score '{0}'")]
+    UnknownScore(String),
+
+    #[error("'{name}' matches several scores: {}", .ids.join(", "))]
+    AmbiguousScore { name: String, ids: Vec<String> },
+
+    #[error("Unknown input --{field} for score '{score}'")]
+    UnknownField { field: String, score: String },
+
+    #[error("Invalid value '{value}' for --{field}: {reason}")]
+    InvalidValue {
+        field: String,
+        value: String,
+        reason: String,
+    },
+
+    #[error("Missing value for --{0}")]
+    MissingValue(String),
+
+    #[error("Unexpected argument '{0}'")]
+    UnexpectedArgument(String),
+
+    #[error("Missing score ID")]
+    MissingScore,
+
+    #[error("Unknown specialty '{0}'")]
+    UnknownSpecialty(String),
+
+    #[error(transparent)]
+    Calculation(#[from] CalculationError),
+
+    #[error(transparent)]
+    Batch(#[from] BatchError),
+
+    #[error("Failed to read {path}: {source}")]
+    Read {
+        path: String,
+        source: std::io::Error,
+    },
+
+    #[error(transparent)]
+    Questionnaire(#[from] QuestionnaireError),
+
+    #[error(transparent)]
+    ThirdParty(#[from] ThirdPartyError),
+
+    #[error("Failed to write the result: {0}")]
+    Output(String),
+}
+
+/// Format the result is printed in
+#[cfg(feature = "app")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    /// Plain-text summary, as copied from the result view
+    #[default]
+    Text,
+    /// KlinScore's JSON export format
+    Json,
+    /// FHIR R4 Observation
+    Fhir,
+    /// The full [`CalculationResult`](crate::scores::CalculationResult) as JSON
+    Result,
+}
+
+#[cfg(feature = "app")]
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(format: &str) -> Result<Self, Self::Err> {
+        match format.to_ascii_lowercase().as_str() {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            "fhir" => Ok(Self::Fhir),
+            "result" => Ok(Self::Result),
+            _ => Err("expected text, json, fhir or result".to_string()),
+        }
+    }
+}
+
+/// Arguments of `klinscore-cli calc`
+#[cfg(feature = "app")]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CalcCommand {
+    /// Score ID, name or alias
+    pub score: String,
+    pub format: OutputFormat,
+    /// Texts in German instead of English
+    pub use_german: bool,
+    /// Directories with additional scores (`--scores-dir`)
+    pub score_dirs: Vec<PathBuf>,
+    /// The `--field value` arguments, parsed once the score is known
+    pub input_args: Vec<String>,
+}
+
+#[cfg(feature = "app")]
+impl CalcCommand {
+    /// Split the arguments after `calc` into options and score inputs
+    pub fn parse(args: &[String]) -> Result<Self, CliError> {
+        let mut command = Self::default();
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let (name, inline) = match arg.split_once('=') {
+                Some((name, value)) => (name, Some(value.to_string())),
+                None => (arg.as_str(), None),
+            };
+            let mut value = |option: &str| {
+                inline
+                    .clone()
+                    .or_else(|| args.next().cloned())
+                    .ok_or_else(|| CliError::MissingValue(option.to_string()))
+            };
+            match name {
+                "--format" => {
+                    let format = value("format")?;
+                    command.format = format.parse().map_err(|reason| CliError::InvalidValue {
+                        field: "format".to_string(),
+                        value: format,
+                        reason,
+                    })?;
+                }
+                "--lang" => command.use_german = parse_lang(&value("lang")?)?,
+                "--scores-dir" => command.score_dirs.push(value("scores-dir")?.into()),
+                _ if arg.starts_with("--") => command.input_args.push(arg.clone()),
+                _ if command.score.is_empty() => command.score = arg.clone(),
+                // Values of inputs follow their `--field`
+                _ if !command.input_args.is_empty() => command.input_args.push(arg.clone()),
+                _ => return Err(CliError::UnexpectedArgument(arg.clone())),
+            }
+        }
+        if command.score.is_empty() {
+            return Err(CliError::MissingScore);
+        }
+        Ok(command)
+    }
+
+    /// Calculate the score and render the result
+    pub fn run(&self, library: &ScoreLibrary) -> Result<String, CliError> {
+        let (_, score) = find_score(library, &self.score)?;
+        let inputs = parse_inputs(score, &self.input_args)?;
+        let result = calculate_score(score, &inputs)?;
+
+        let language = if self.use_german { "de" } else { "en" };
+        let mut record =
+            ExportRecord::from_result(&result, score.name.text(language), self.use_german);
+        record.score_version = Some(score.version.clone());
+        record.references = score.references.iter().map(Reference::formatted).collect();
+
+        match self.format {
+            OutputFormat::Text => Ok(text_export::summary_text(&record, self.use_german)),
+            OutputFormat::Json => json_export::export_to_json(&record).map_err(CliError::Output),
+            OutputFormat::Fhir => {
+                let observation =
+                    fhir_export::fhir_observation(score, &result, &record, self.use_german);
+                serde_json::to_string_pretty(&observation)
+                    .map_err(|e| CliError::Output(e.to_string()))
+            }
+            OutputFormat::Result => {
+                serde_json::to_string_pretty(&result).map_err(|e| CliError::Output(e.to_string()))
+            }
+        }
+    }
+}
+
+/// Arguments of `klinscore-cli batch`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BatchCommand {
+    /// CSV or JSON file with one patient per row
+    pub input: PathBuf,
+    /// Score IDs, names or aliases (`--score`, repeatable)
+    pub scores: Vec<String>,
+    /// Results file (`--output`, CSV or JSON); standard output as CSV if not given
+    pub output: Option<PathBuf>,
+    pub use_german: bool,
+    pub score_dirs: Vec<PathBuf>,
+}
+
+impl BatchCommand {
+    pub fn parse(args: &[String]) -> Result<Self, CliError> {
+        let mut command = Self::default();
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let (name, inline) = match arg.split_once('=') {
+                Some((name, value)) => (name, Some(value.to_string())),
+                None => (arg.as_str(), None),
+            };
+            let mut value = |option: &str| {
+                inline
+                    .clone()
+                    .or_else(|| args.next().cloned())
+                    .ok_or_else(|| CliError::MissingValue(option.to_string()))
+            };
+            match name {
+                "--score" => command.scores.push(value("score")?),
+                "--output" | "-o" => command.output = Some(value("output")?.into()),
+                "--lang" => command.use_german = parse_lang(&value("lang")?)?,
+                "--scores-dir" => command.score_dirs.push(value("scores-dir")?.into()),
+                _ if !arg.starts_with('-') && command.input.as_os_str().is_empty() => {
+                    command.input = arg.into()
+                }
+                _ => return Err(CliError::UnexpectedArgument(arg.clone())),
+            }
+        }
+        if command.input.as_os_str().is_empty() {
+            return Err(CliError::MissingValue("input file".to_string()));
+        }
+        if command.scores.is_empty() {
+            return Err(CliError::MissingScore);
+        }
+        Ok(command)
+    }
+
+    /// Read the input file and run the scores over every row
+    pub fn run(&self, library: &ScoreLibrary) -> Result<Vec<BatchResult>, CliError> {
+        let scores = self
+            .scores
+            .iter()
+            .map(|name| find_score(library, name))
+            .collect::<Result<Vec<_>, _>>()?;
+        let rows = read_rows(&self.input)?;
+        Ok(run_batch(&scores, &rows, self.use_german))
+    }
+}
+
+/// Arguments of `klinscore-cli list` and `klinscore-cli describe`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct InfoCommand {
+    /// Score to describe
+    pub score: Option<String>,
+    /// Only list scores of this specialty (`--specialty`)
+    pub specialty: Option<Specialty>,
+    pub use_german: bool,
+    pub score_dirs: Vec<PathBuf>,
+}
+
+impl InfoCommand {
+    pub fn parse(args: &[String]) -> Result<Self, CliError> {
+        let mut command = Self::default();
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let (name, inline) = match arg.split_once('=') {
+                Some((name, value)) => (name, Some(value.to_string())),
+                None => (arg.as_str(), None),
+            };
+            let mut value = |option: &str| {
+                inline
+                    .clone()
+                    .or_else(|| args.next().cloned())
+                    .ok_or_else(|| CliError::MissingValue(option.to_string()))
+            };
+            match name {
+                "--specialty" => command.specialty = Some(parse_specialty(&value("specialty")?)?),
+                "--lang" => command.use_german = parse_lang(&value("lang")?)?,
+                "--scores-dir" => command.score_dirs.push(value("scores-dir")?.into()),
+                _ if !arg.starts_with('-') && command.score.is_none() => {
+                    command.score = Some(arg.clone())
+                }
+                _ => return Err(CliError::UnexpectedArgument(arg.clone())),
+            }
+        }
+        Ok(command)
+    }
+
+    /// One line per score (ID, name, specialty), sorted by ID
+    pub fn list(&self, library: &ScoreLibrary) -> String {
+        let language = if self.use_german { "de" } else { "en" };
+        let filter = ScoreFilter {
+            specialty: self.specialty,
+            ..ScoreFilter::default()
+        };
+        let scores = library.filter(&filter);
+        let width = scores.iter().map(|(id, _)| id.len()).max().unwrap_or(0);
+        scores
+            .iter()
+            .map(|(id, score)| {
+                let specialty = if self.use_german {
+                    score.specialty.german()
+                } else {
+                    score.specialty.english()
+                };
+                let deprecated = if score.is_deprecated() {
+                    " (deprecated)"
+                } else {
+                    ""
+                };
+                format!(
+                    "{:width$}  {} [{}]{}\n",
+                    id,
+                    score.name.text(language),
+                    specialty,
+                    deprecated
+                )
+            })
+            .collect()
+    }
+
+    /// Inputs (with type, unit, range and points) and interpretation bands of the score
+    pub fn describe(&self, library: &ScoreLibrary) -> Result<String, CliError> {
+        let name = self.score.as_deref().ok_or(CliError::MissingScore)?;
+        let (id, score) = find_score(library, name)?;
+        Ok(describe_score(id, score, self.use_german))
+    }
+}
+
+/// Arguments of `klinscore-cli import`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ImportCommand {
+    /// FHIR Questionnaire or third-party calculator description (JSON)
+    pub source: PathBuf,
+    /// Score file to write (`--output`); standard output if not given
+    pub output: Option<PathBuf>,
+}
+
+impl ImportCommand {
+    pub fn parse(args: &[String]) -> Result<Self, CliError> {
+        let mut command = Self::default();
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--output" | "-o" => {
+                    let output = args
+                        .next()
+                        .ok_or_else(|| CliError::MissingValue("output".to_string()))?;
+                    command.output = Some(output.into());
+                }
+                _ if !arg.starts_with('-') && command.source.as_os_str().is_empty() => {
+                    command.source = arg.into()
+                }
+                _ => return Err(CliError::UnexpectedArgument(arg.clone())),
+            }
+        }
+        if command.source.as_os_str().is_empty() {
+            return Err(CliError::MissingValue("file to import".to_string()));
+        }
+        Ok(command)
+    }
+
+    /// The file converted to a YAML score definition, with what could not be converted
+    ///
+    /// FHIR Questionnaires are recognized by their `resourceType`; any other JSON is
+    /// read as a third-party calculator description. The warnings are also listed in
+    /// a comment at the top of the YAML.
+    pub fn run(&self) -> Result<(String, Vec<String>), CliError> {
+        let source = std::fs::read_to_string(&self.source).map_err(|source| CliError::Read {
+            path: self.source.display().to_string(),
+            source,
+        })?;
+        let is_questionnaire = serde_json::from_str::<serde_json::Value>(&source)
+            .is_ok_and(|json| json.get("resourceType").is_some());
+        let (score, warnings) = if is_questionnaire {
+            (score_from_questionnaire(&source)?.1, Vec::new())
+        } else {
+            let imported = score_from_calculator_json(&source)?;
+            (imported.score, imported.warnings)
+        };
+        let yaml = score
+            .to_yaml()
+            .map_err(|e| CliError::Output(e.to_string()))?;
+        if warnings.is_empty() {
+            return Ok((yaml, warnings));
+        }
+        let mut header = "# Draft import; not converted, to be added by hand:\n".to_string();
+        for warning in &warnings {
+            header.push_str(&format!("# - {}\n", warning));
+        }
+        Ok((header + &yaml, warnings))
+    }
+}
+
+/// Arguments of `klinscore-cli test`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TestCommand {
+    /// Scores to test; all if empty
+    pub scores: Vec<String>,
+    pub score_dirs: Vec<PathBuf>,
+}
+
+/// Outcome of `klinscore-cli test`
+#[derive(Debug, Clone, PartialEq)]
+pub struct TestReport {
+    /// One line per score with test cases, plus one per failed case
+    pub output: String,
+    pub cases: usize,
+    pub failed: usize,
+    /// Scores without test cases
+    pub untested: Vec<String>,
+}
+
+impl TestCommand {
+    pub fn parse(args: &[String]) -> Result<Self, CliError> {
+        let mut command = Self::default();
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--scores-dir" => {
+                    let dir = args
+                        .next()
+                        .ok_or_else(|| CliError::MissingValue("scores-dir".to_string()))?;
+                    command.score_dirs.push(dir.into());
+                }
+                _ if !arg.starts_with('-') => command.scores.push(arg.clone()),
+                _ => return Err(CliError::UnexpectedArgument(arg.clone())),
+            }
+        }
+        Ok(command)
+    }
+
+    /// Run the test cases of the requested scores, in ID order
+    pub fn run(&self, library: &ScoreLibrary) -> Result<TestReport, CliError> {
+        let mut scores: Vec<(&str, &ScoreDefinition)> = if self.scores.is_empty() {
+            library
+                .scores
+                .iter()
+                .map(|(id, score)| (id.as_str(), score))
+                .collect()
+        } else {
+            self.scores
+                .iter()
+                .map(|name| find_score(library, name))
+                .collect::<Result<_, _>>()?
+        };
+        scores.sort_by_key(|(id, _)| *id);
+
+        let mut report = TestReport {
+            output: String::new(),
+            cases: 0,
+            failed: 0,
+            untested: Vec::new(),
+        };
+        for (id, score) in scores {
+            if score.test_cases.is_empty() {
+                report.untested.push(id.to_string());
+                continue;
+            }
+            let failures = run_test_cases(score);
+            report.cases += score.test_cases.len();
+            report.failed += failures.len();
+            let status = if failures.is_empty() { "ok  " } else { "FAIL" };
+            report.output.push_str(&format!(
+                "{} {} ({} case(s))\n",
+                status,
+                id,
+                score.test_cases.len()
+            ));
+            for failure in failures {
+                report.output.push_str(&format!("     {}\n", failure));
+            }
+        }
+        Ok(report)
+    }
+}
+
+/// Specialty by its name in English, German or the score files
+/// (`cardiology`, `Kardiologie`, `InternalMedicine`, `internal-medicine`)
+pub fn parse_specialty(name: &str) -> Result<Specialty, CliError> {
+    let normalize = |name: &str| {
+        name.chars()
+            .filter(|c| c.is_alphanumeric())
+            .flat_map(char::to_lowercase)
+            .collect::<String>()
+    };
+    let wanted = normalize(name);
+    Specialty::ALL
+        .into_iter()
+        .find(|specialty| {
+            [
+                specialty.english().to_string(),
+                specialty.german().to_string(),
+                format!("{:?}", specialty),
+            ]
+            .iter()
+            .any(|candidate| normalize(candidate) == wanted)
+        })
+        .ok_or_else(|| CliError::UnknownSpecialty(name.to_string()))
+}
+
+/// Plain-text description of a score for `klinscore-cli describe`
+pub fn describe_score(id: &str, score: &ScoreDefinition, use_german: bool) -> String {
+    let language = if use_german { "de" } else { "en" };
+    let specialty = if use_german {
+        score.specialty.german()
+    } else {
+        score.specialty.english()
+    };
+    let mut out = format!("{} ({})\n", score.name.text(language), id);
+    out.push_str(&format!(
+        "{} · {} · version {}\n",
+        specialty, score.guideline_source, score.version
+    ));
+    if score.is_deprecated() {
+        match &score.superseded_by {
+            Some(replacement) => out.push_str(&format!("Deprecated, use {}\n", replacement)),
+            None => out.push_str("Deprecated\n"),
+        }
+    }
+    let description = score.description.text(language);
+    if !description.is_empty() {
+        out.push_str(&format!("{}\n", description));
+    }
+    if let Some(formula) = &score.formula {
+        out.push_str(&format!("Formula: {}\n", formula));
+    }
+
+    out.push_str("\nInputs:\n");
+    for field in &score.inputs {
+        let mut kind = match field.input_type {
+            InputType::Boolean => "boolean".to_string(),
+            InputType::Number => "number".to_string(),
+            InputType::Dropdown => "dropdown".to_string(),
+            InputType::Date => "date".to_string(),
+            InputType::Duration => "duration".to_string(),
+        };
+        if let Some(elapsed) = &field.elapsed {
+            let unit = field.unit.clone().unwrap_or_else(|| elapsed.unit.label());
+            kind.push_str(&format!(
+                ", {} until --{}",
+                unit.text(language),
+                elapsed.until
+            ));
+        } else if let Some(unit) = field
+            .unit
+            .clone()
+            .or_else(|| field.duration_unit.map(|unit| unit.label()))
+        {
+            kind.push_str(&format!(", {}", unit.text(language)));
+        }
+        match (field.min, field.max) {
+            (Some(min), Some(max)) => kind.push_str(&format!(", {}–{}", min, max)),
+            (Some(min), None) => kind.push_str(&format!(", ≥{}", min)),
+            (None, Some(max)) => kind.push_str(&format!(", ≤{}", max)),
+            (None, None) => {}
+        }
+        if field.required && field.input_type != InputType::Boolean {
+            kind.push_str(", required");
+        }
+        out.push_str(&format!(
+            "  --{}  {} ({})\n",
+            field.field,
+            field.label.text(language),
+            kind
+        ));
+
+        if field.input_type == InputType::Dropdown {
+            for option in &field.options {
+                let mut notes = Vec::new();
+                // Formula scores do not use the points of options
+                if score.formula.is_none() {
+                    notes.push(format!("{:+}", option.points));
+                }
+                if field.default_option() == Some(option.value.as_str()) {
+                    notes.push("preselected".to_string());
+                }
+                let notes = if notes.is_empty() {
+                    String::new()
+                } else {
+                    format!(" ({})", notes.join(", "))
+                };
+                out.push_str(&format!(
+                    "      {}: {}{}\n",
+                    option.value,
+                    option.label.text(language),
+                    notes
+                ));
+            }
+            continue;
+        }
+        match &field.points {
+            PointsValue::Fixed(0) => {}
+            PointsValue::Fixed(points) => out.push_str(&format!("      {:+}\n", points)),
+            PointsValue::Conditional(conditions) => {
+                for condition in conditions {
+                    out.push_str(&format!(
+                        "      {}: {:+}\n",
+                        condition.condition, condition.points
+                    ));
+                }
+            }
+            PointsValue::Table { table } => out.push_str(&format!(
+                "      points from a table with --{}\n",
+                table.column_field
+            )),
+        }
+    }
+
+    out.push_str("\nInterpretation:\n");
+    let ranges: Vec<String> = score
+        .interpretation
+        .iter()
+        .map(|rule| match &rule.score {
+            ScoreRange::Exact(value) => value.to_string(),
+            ScoreRange::Range(range) => range.clone(),
+        })
+        .collect();
+    let width = ranges
+        .iter()
+        .map(|range| range.chars().count())
+        .max()
+        .unwrap_or(0);
+    for (range, rule) in ranges.iter().zip(&score.interpretation) {
+        let padding = " ".repeat(width - range.chars().count());
+        out.push_str(&format!(
+            "  {}{}  {} ({})\n",
+            range,
+            padding,
+            rule.risk.text(language),
+            rule.risk_level.label(language)
+        ));
+    }
+    out
+}
+
+/// A score by ID, or else by name or alias in any language
+pub fn find_score<'a>(
+    library: &'a ScoreLibrary,
+    name: &str,
+) -> Result<(&'a str, &'a ScoreDefinition), CliError> {
+    if let Some((id, score)) = library.scores.get_key_value(name) {
+        return Ok((id, score));
+    }
+    match library.find_by_alias(name).as_slice() {
+        [] => Err(CliError::UnknownScore(name.to_string())),
+        [id] => Ok((id, &library.scores[*id])),
+        ids => Err(CliError::AmbiguousScore {
+            name: name.to_string(),
+            ids: ids.iter().map(|id| id.to_string()).collect(),
+        }),
+    }
+}
+
+/// Inputs of `score` from `--field value` arguments
+///
+/// Field names may use `-` for `_`, values may follow after `=`. A boolean flag on
+/// its own is true; booleans not given are false, dropdowns not given take their
+/// preselected option. Numbers are in the units the score defines (SI units)
+/// unless followed by the field's conventional unit (`--creatinine "1.2 mg/dL"`),
+/// dates are written YYYY-MM-DD or `today`.
+pub fn parse_inputs(
+    score: &ScoreDefinition,
+    args: &[String],
+) -> Result<HashMap<String, InputValue>, CliError> {
+    let mut inputs = HashMap::new();
+    let mut args = args.iter().peekable();
+    while let Some(arg) = args.next() {
+        let Some(option) = arg.strip_prefix("--") else {
+            return Err(CliError::UnexpectedArgument(arg.clone()));
+        };
+        let (name, inline) = match option.split_once('=') {
+            Some((name, value)) => (name, Some(value.to_string())),
+            None => (option, None),
+        };
+        let field_name = name.replace('-', "_");
+        let field = score
+            .inputs
+            .iter()
+            .find(|input| input.field == field_name)
+            .ok_or_else(|| CliError::UnknownField {
+                field: name.to_string(),
+                score: score.name.text("en").to_string(),
+            })?;
+
+        let value = match field.input_type {
+            // A boolean flag may stand on its own
+            InputType::Boolean => inline
+                .or_else(|| args.next_if(|next| parse_bool(next).is_some()).cloned())
+                .unwrap_or_else(|| "yes".to_string()),
+            InputType::Number | InputType::Dropdown | InputType::Date | InputType::Duration => {
+                inline
+                    .or_else(|| args.next().cloned())
+                    .ok_or_else(|| CliError::MissingValue(name.to_string()))?
+            }
+        };
+        let value = parse_value(field, &value)?;
+        inputs.insert(field.field.clone(), value);
+    }
+
+    fill_defaults(score, &mut inputs);
+    Ok(inputs)
+}
+
+/// Value of `field` from its text: yes/no (or true/false, 1/0) for booleans, a
+/// number with `.` or `,` as decimal separator (optionally followed by a unit of
+/// the field), the value of a dropdown option, a date as YYYY-MM-DD (or `today`),
+/// or a duration such as `4:30` or `4h30m`
+pub(crate) fn parse_value(field: &InputField, value: &str) -> Result<InputValue, CliError> {
+    match field.input_type {
+        InputType::Boolean => parse_bool(value)
+            .map(InputValue::Boolean)
+            .ok_or_else(|| invalid(field, value, "expected yes or no")),
+        InputType::Number => parse_number(value)
+            .map(InputValue::Number)
+            .or_else(|| parse_quantity(field, value).map(InputValue::Quantity))
+            .ok_or_else(|| {
+                invalid(
+                    field,
+                    value,
+                    "expected a number such as 1.36 or 1,36, without thousands separators, \
+                     and optionally a unit of the field",
+                )
+            }),
+        InputType::Dropdown => field
+            .options
+            .iter()
+            .find(|option| option.value.eq_ignore_ascii_case(value.trim()))
+            .map(|option| InputValue::Dropdown(option.value.clone()))
+            .ok_or_else(|| {
+                let options: Vec<&str> = field.options.iter().map(|o| o.value.as_str()).collect();
+                invalid(
+                    field,
+                    value,
+                    &format!("expected one of {}", options.join(", ")),
+                )
+            }),
+        InputType::Date => match value.trim() {
+            "today" => {
+                let today = chrono::Local::now().date_naive();
+                CalendarDate::new(today.year(), today.month(), today.day())
+                    .map(InputValue::Date)
+                    .ok_or_else(|| invalid(field, value, "today is out of range"))
+            }
+            date => date
+                .parse()
+                .map(InputValue::Date)
+                .map_err(|_| invalid(field, value, "expected a date (YYYY-MM-DD) or today")),
+        },
+        InputType::Duration => field
+            .duration_unit
+            .and_then(|unit| parse_duration(value, unit))
+            .map(InputValue::Number)
+            .ok_or_else(|| invalid(field, value, "expected a duration such as 4:30 or 4h30m")),
+    }
+}
+
+/// A number with `.` or `,` as decimal separator, read as [`parse_decimal`] reads
+/// it with a decimal comma
+///
+/// A comma before exactly three digits ("10,000") is rejected rather than guessed,
+/// as it may group thousands.
+fn parse_number(value: &str) -> Option<f64> {
+    let value = value.trim();
+    let grouped = value.split_once(',').is_some_and(|(_, decimals)| {
+        decimals.len() == 3 && decimals.bytes().all(|b| b.is_ascii_digit())
+    });
+    if grouped {
+        return None;
+    }
+    parse_decimal(value, true)
+}
+
+/// A number followed by a unit of `field`, e.g. `1,2 mg/dL`; the calculation
+/// converts it to the unit the score defines
+fn parse_quantity(field: &InputField, value: &str) -> Option<Quantity> {
+    let value = value.trim();
+    let unit_start = value.find(|c: char| !(c.is_ascii_digit() || matches!(c, '.' | ',' | '-')))?;
+    let (number, unit) = value.split_at(unit_start);
+    let unit = unit.trim();
+    field.unit_system_of(unit)?;
+    Some(Quantity {
+        value: parse_number(number)?,
+        unit: unit.to_string(),
+    })
+}
+
+/// Set booleans not given to false and dropdowns not given to their preselected
+/// option; numbers, dates and durations stay missing
+pub(crate) fn fill_defaults(score: &ScoreDefinition, inputs: &mut HashMap<String, InputValue>) {
+    for field in &score.inputs {
+        if inputs.contains_key(&field.field) {
+            continue;
+        }
+        match field.input_type {
+            InputType::Boolean => {
+                inputs.insert(field.field.clone(), InputValue::Boolean(false));
+            }
+            InputType::Dropdown => {
+                if let Some(default) = field.default_option() {
+                    inputs.insert(
+                        field.field.clone(),
+                        InputValue::Dropdown(default.to_string()),
+                    );
+                }
+            }
+            InputType::Number | InputType::Date | InputType::Duration => {}
+        }
+    }
+}
+
+fn parse_lang(lang: &str) -> Result<bool, CliError> {
+    match lang {
+        "de" => Ok(true),
+        "en" => Ok(false),
+        _ => Err(CliError::InvalidValue {
+            field: "lang".to_string(),
+            value: lang.to_string(),
+            reason: "expected en or de".to_string(),
+        }),
+    }
+}
+
+fn parse_bool(value: &str) -> Option<bool> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "yes" | "true" | "1" | "ja" => Some(true),
+        "no" | "false" | "0" | "nein" => Some(false),
+        _ => None,
+    }
+}
+
+fn invalid(field: &InputField, value: &str, reason: &str) -> CliError {
+    CliError::InvalidValue {
+        field: field.field.clone(),
+        value: value.to_string(),
+        reason: reason.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scores::load_bundled_scores;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_calc_command() {
+        let library = load_bundled_scores();
+        let command = CalcCommand::parse(&args(&[
+            "cha2ds2_va",
+            "--age",
+            "72",
+            "--heart-failure",
+            "--hypertension=yes",
+            "--diabetes",
+            "no",
+            "--format",
+            "json",
+        ]))
+        .unwrap();
+        assert_eq!(command.format, OutputFormat::Json);
+
+        let (_, score) = find_score(&library, &command.score).unwrap();
+        let inputs = parse_inputs(score, &command.input_args).unwrap();
+        assert_eq!(inputs["age"], InputValue::Number(72.0));
+        assert_eq!(inputs["heart_failure"], InputValue::Boolean(true));
+        assert_eq!(inputs["diabetes"], InputValue::Boolean(false));
+        assert_eq!(inputs["stroke_tia"], InputValue::Boolean(false));
+
+        let json: serde_json::Value =
+            serde_json::from_str(&command.run(&library).unwrap()).unwrap();
+        // Age 65-74 (1), heart failure (1), hypertension (1)
+        assert_eq!(json["total_score"], 3);
+
+        let fhir = CalcCommand {
+            format: OutputFormat::Fhir,
+            ..command.clone()
+        };
+        assert!(fhir.run(&library).unwrap().contains("\"Observation\""));
+
+        let full = CalcCommand {
+            format: OutputFormat::Result,
+            ..command.clone()
+        };
+        let json: serde_json::Value = serde_json::from_str(&full.run(&library).unwrap()).unwrap();
+        assert_eq!(json["risk_level"], "High");
+    }
+
+    #[test]
+    fn test_calc_conventional_units() {
+        let library = load_bundled_scores();
+        let (_, score) = find_score(&library, "egfr_ckd_epi_2021").unwrap();
+        let egfr = |creatinine: &str| {
+            let inputs = parse_inputs(
+                score,
+                &args(&["--age", "60", "--sex", "female", "--creatinine", creatinine]),
+            )
+            .unwrap();
+            calculate_score(score, &inputs).unwrap().total_score
+        };
+        // 1,2 mg/dL is 106,08 μmol/L
+        assert_eq!(egfr("1,2 mg/dL"), egfr("106.08"));
+        assert_eq!(egfr("1.2mg/dl"), egfr("106.08 µmol/L"));
+    }
+
+    #[test]
+    fn test_calc_errors() {
+        let library = load_bundled_scores();
+        let run = |arguments: &[&str]| CalcCommand::parse(&args(arguments))?.run(&library);
+
+        assert!(matches!(
+            run(&["nonexistent"]),
+            Err(CliError::UnknownScore(_))
+        ));
+        assert!(matches!(
+            run(&["cha2ds2_va", "--age", "72", "--smoker"]),
+            Err(CliError::UnknownField { .. })
+        ));
+        for age in ["old", "NaN", "inf", "1e3", "10,000", "7.2,5"] {
+            assert!(
+                matches!(
+                    run(&["cha2ds2_va", "--age", age]),
+                    Err(CliError::InvalidValue { .. })
+                ),
+                "{age}"
+            );
+        }
+        assert!(run(&["cha2ds2_va", "--age", "72,5"]).is_ok());
+        assert!(matches!(
+            run(&["cha2ds2_va", "--age", "72 mg/dL"]),
+            Err(CliError::InvalidValue { .. })
+        ));
+        assert!(matches!(
+            run(&["cha2ds2_va"]),
+            Err(CliError::Calculation(
+                CalculationError::MissingRequiredField { .. }
+            ))
+        ));
+        assert!(matches!(
+            CalcCommand::parse(&[]),
+            Err(CliError::MissingScore)
+        ));
+    }
+
+    #[test]
+    fn test_list_and_describe() {
+        let library = load_bundled_scores();
+        let command = InfoCommand::parse(&args(&["--specialty", "cardiology"])).unwrap();
+        assert_eq!(command.specialty, Some(Specialty::Cardiology));
+        let list = command.list(&library);
+        assert_eq!(list.lines().count(), 3);
+        assert!(list.starts_with("cha2ds2_va  CHA2DS2-VA Score [Cardiology]\n"));
+        assert_eq!(
+            parse_specialty("internal-medicine").unwrap(),
+            Specialty::InternalMedicine
+        );
+        assert_eq!(
+            parse_specialty("Anästhesiologie").unwrap(),
+            Specialty::Anesthesiology
+        );
+        assert!(matches!(
+            parse_specialty("astrology"),
+            Err(CliError::UnknownSpecialty(_))
+        ));
+
+        let command = InfoCommand::parse(&args(&["cha2ds2_va"])).unwrap();
+        let description = command.describe(&library).unwrap();
+        assert!(description.starts_with("CHA2DS2-VA Score (cha2ds2_va)\n"));
+        assert!(description.contains("  --age  Age (number, years"));
+        assert!(description
+            .contains("  --heart_failure  Congestive Heart Failure (boolean)\n      +1\n"));
+        assert!(description.contains("\nInterpretation:\n  0 "));
+    }
+}
@@ -0,0 +1,314 @@
+// localized.rs
+// Per-language values for translatable score definition fields
+
+use schemars::gen::SchemaGenerator;
+use schemars::schema::{Schema, SchemaObject, SubschemaValidation};
+use schemars::JsonSchema;
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_yaml::{Mapping, Value};
+use std::collections::BTreeMap;
+
+/// Language used when a requested translation is missing
+pub const FALLBACK_LANGUAGE: &str = "en";
+
+/// A value in several languages, keyed by language code ("en", "de", "fr", ...).
+///
+/// In YAML either a map (`label: {en: "Age", de: "Alter"}`) or a single value,
+/// which is taken as English (`label: "Age"`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(transparent)]
+pub struct Localized<T>(BTreeMap<String, T>);
+
+/// Translatable text
+pub type LocalizedText = Localized<String>;
+
+impl<T> Localized<T> {
+    /// Empty value without any translation
+    pub fn new() -> Self {
+        Self(BTreeMap::new())
+    }
+
+    /// Add a translation (builder style)
+    pub fn with(mut self, language: impl Into<String>, value: T) -> Self {
+        self.insert(language, value);
+        self
+    }
+
+    /// Add or replace a translation
+    pub fn insert(&mut self, language: impl Into<String>, value: T) {
+        self.0.insert(language.into(), value);
+    }
+
+    /// Value in `language`, with graceful fallback.
+    ///
+    /// Tries the exact code, then its primary subtag ("de" for "de-AT"),
+    /// then English, then any available translation.
+    pub fn get(&self, language: &str) -> Option<&T> {
+        let primary = language.split(['-', '_']).next().unwrap_or(language);
+        self.0
+            .get(language)
+            .or_else(|| self.0.get(primary))
+            .or_else(|| self.0.get(FALLBACK_LANGUAGE))
+            .or_else(|| self.0.values().next())
+    }
+
+    /// Value in exactly `language`, without fallback
+    pub fn exact(&self, language: &str) -> Option<&T> {
+        self.0.get(language)
+    }
+
+    /// Language codes with a translation, in alphabetical order
+    pub fn languages(&self) -> impl Iterator<Item = &str> {
+        self.0.keys().map(String::as_str)
+    }
+
+    /// All translations
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.0.values()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl LocalizedText {
+    /// English and German text, the two languages shipped with the app
+    pub fn bilingual(en: impl Into<String>, de: impl Into<String>) -> Self {
+        Self::new()
+            .with(FALLBACK_LANGUAGE, en.into())
+            .with("de", de.into())
+    }
+
+    /// Text in `language` (see [`Localized::get`]), or "" if there is none
+    pub fn text(&self, language: &str) -> &str {
+        self.get(language).map(String::as_str).unwrap_or("")
+    }
+
+    /// Whether there is no non-empty translation
+    pub fn is_blank(&self) -> bool {
+        self.values().all(|text| text.trim().is_empty())
+    }
+}
+
+impl<T> Default for Localized<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<&str> for LocalizedText {
+    /// English-only text
+    fn from(text: &str) -> Self {
+        Self::new().with(FALLBACK_LANGUAGE, text.to_string())
+    }
+}
+
+impl From<String> for LocalizedText {
+    /// English-only text
+    fn from(text: String) -> Self {
+        Self::new().with(FALLBACK_LANGUAGE, text)
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Localized<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr<T> {
+            PerLanguage(BTreeMap<String, T>),
+            Single(T),
+        }
+
+        match Repr::deserialize(deserializer) {
+            Ok(Repr::PerLanguage(map)) => Ok(Self(map)),
+            Ok(Repr::Single(value)) => Ok(Self::new().with(FALLBACK_LANGUAGE, value)),
+            Err(_) => Err(serde::de::Error::custom(
+                "expected a value or a map of language codes to values",
+            )),
+        }
+    }
+}
+
+impl<T: JsonSchema> JsonSchema for Localized<T> {
+    fn schema_name() -> String {
+        format!("Localized_{}", T::schema_name())
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        let single = gen.subschema_for::<T>();
+        let per_language = gen.subschema_for::<BTreeMap<String, T>>();
+        SchemaObject {
+            subschemas: Some(Box::new(SubschemaValidation {
+                any_of: Some(vec![per_language, single]),
+                ..Default::default()
+            })),
+            ..Default::default()
+        }
+        .into()
+    }
+}
+
+/// Rewrite the legacy bilingual layout (`label` + `label_de`) into per-language
+/// maps, in place and recursively. Returns whether anything was rewritten.
+///
+/// Also converts comma-separated `tags` strings into lists, treats the old
+/// `keywords_de` key as German tags, and drops `specialty_de` (the German
+/// specialty name comes from `Specialty::german`).
+pub fn upgrade_legacy_translations(value: &mut Value) -> bool {
+    match value {
+        Value::Sequence(items) => {
+            let mut changed = false;
+            for item in items {
+                changed |= upgrade_legacy_translations(item);
+            }
+            changed
+        }
+        Value::Mapping(map) => {
+            let mut changed = false;
+            for (_, child) in map.iter_mut() {
+                changed |= upgrade_legacy_translations(child);
+            }
+
+            if let Some(tags) = map.get_mut("tags") {
+                changed |= split_comma_separated(tags);
+            }
+
+            let legacy_keys: Vec<String> = map
+                .keys()
+                .filter_map(Value::as_str)
+                .filter(|key| key.ends_with("_de"))
+                .map(String::from)
+                .collect();
+
+            for key in legacy_keys {
+                let Some(mut german) = map.remove(key.as_str()) else {
+                    continue;
+                };
+                changed = true;
+
+                let base = match key.as_str() {
+                    "specialty_de" => continue,
+                    "keywords_de" => "tags",
+                    other => other.strip_suffix("_de").unwrap_or(other),
+                };
+                if german.is_null() {
+                    continue;
+                }
+                if base == "tags" {
+                    split_comma_separated(&mut german);
+                }
+
+                match map.get_mut(base) {
+                    Some(Value::Mapping(per_language)) => {
+                        per_language.insert("de".into(), german);
+                    }
+                    Some(existing) if !existing.is_null() => {
+                        let english = std::mem::replace(existing, Value::Null);
+                        let mut per_language = Mapping::new();
+                        per_language.insert(FALLBACK_LANGUAGE.into(), english);
+                        per_language.insert("de".into(), german);
+                        *existing = Value::Mapping(per_language);
+                    }
+                    _ => {
+                        let mut per_language = Mapping::new();
+                        per_language.insert("de".into(), german);
+                        map.insert(base.into(), Value::Mapping(per_language));
+                    }
+                }
+            }
+            changed
+        }
+        _ => false,
+    }
+}
+
+/// Turn `"a, b"` into `["a", "b"]`; returns whether the value was a string
+fn split_comma_separated(value: &mut Value) -> bool {
+    let Value::String(text) = value else {
+        return false;
+    };
+    let items = text
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(|item| Value::String(item.to_string()))
+        .collect();
+    *value = Value::Sequence(items);
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fallback_order() {
+        let text = LocalizedText::new()
+            .with("de", "Alter".to_string())
+            .with("en", "Age".to_string());
+
+        assert_eq!(text.text("de"), "Alter");
+        assert_eq!(text.text("de-AT"), "Alter");
+        assert_eq!(text.text("fr"), "Age");
+
+        let german_only = LocalizedText::new().with("de", "Alter".to_string());
+        assert_eq!(german_only.text("en"), "Alter");
+        assert_eq!(LocalizedText::new().text("en"), "");
+    }
+
+    #[test]
+    fn test_deserialize_single_or_map() {
+        let single: LocalizedText = serde_yaml::from_str("\"Age\"").unwrap();
+        assert_eq!(single.exact("en").map(String::as_str), Some("Age"));
+
+        let map: LocalizedText =
+            serde_yaml::from_str("{en: \"Age\", de: \"Alter\", fr: \"Âge\"}").unwrap();
+        assert_eq!(map.text("fr"), "Âge");
+        assert_eq!(map.languages().collect::<Vec<_>>(), vec!["de", "en", "fr"]);
+
+        let lists: Localized<Vec<String>> =
+            serde_yaml::from_str("{en: [\"stroke\"], de: [\"Schlaganfall\"]}").unwrap();
+        assert_eq!(lists.get("de").unwrap(), &vec!["Schlaganfall".to_string()]);
+
+        assert!(serde_yaml::from_str::<LocalizedText>("[1, 2]").is_err());
+    }
+
+    #[test]
+    fn test_upgrade_legacy_translations() {
+        let mut value: Value = serde_yaml::from_str(
+            r#"
+name: "Test"
+name_de: "Test-DE"
+specialty: Cardiology
+specialty_de: "Kardiologie"
+inputs:
+  - field: "age"
+    label: "Age"
+    label_de: "Alter"
+    unit_de: "Jahre"
+    help_de: ~
+metadata:
+  tags: "stroke, anticoagulation"
+  keywords_de: "Schlaganfall, Antikoagulation"
+"#,
+        )
+        .unwrap();
+
+        assert!(upgrade_legacy_translations(&mut value));
+        assert_eq!(value["name"]["en"], "Test");
+        assert_eq!(value["name"]["de"], "Test-DE");
+        assert!(value.get("specialty_de").is_none());
+        assert_eq!(value["inputs"][0]["label"]["de"], "Alter");
+        assert_eq!(value["inputs"][0]["unit"]["de"], "Jahre");
+        assert!(value["inputs"][0].get("help").is_none());
+        assert_eq!(value["metadata"]["tags"]["en"][1], "anticoagulation");
+        assert_eq!(value["metadata"]["tags"]["de"][0], "Schlaganfall");
+
+        // Already upgraded documents are left alone
+        assert!(!upgrade_legacy_translations(&mut value));
+    }
+}
@@ -1,4 +1,7 @@
 // Configuration module for score definitions
+pub mod localized;
+mod round_trip;
 pub mod score_definition;
 
+pub use localized::*;
 pub use score_definition::*;
@@ -0,0 +1,246 @@
+// round_trip.rs
+// Writes score definitions back as YAML in the layout of the file they came from
+
+use serde_yaml::{Mapping, Value};
+
+/// Reorder the keys of every mapping in `value` like the corresponding mapping in `original`
+///
+/// Keys missing from `original` (e.g., fields added by the loader) follow the key
+/// they come after in `value`. Sequence items are matched by position.
+pub(crate) fn reorder_like(value: &mut Value, original: &Value) {
+    match (value, original) {
+        (Value::Mapping(mapping), Value::Mapping(original)) => {
+            let mut keys: Vec<Value> = original
+                .keys()
+                .filter(|key| mapping.contains_key(*key))
+                .cloned()
+                .collect();
+            let own_order: Vec<Value> = mapping.keys().cloned().collect();
+            for (i, key) in own_order.iter().enumerate() {
+                if keys.contains(key) {
+                    continue;
+                }
+                let position = own_order[..i]
+                    .iter()
+                    .rev()
+                    .find_map(|previous| keys.iter().position(|k| k == previous))
+                    .map_or(0, |p| p + 1);
+                keys.insert(position, key.clone());
+            }
+
+            let mut reordered = Mapping::new();
+            for key in keys {
+                if let Some(mut entry) = mapping.remove(&key) {
+                    if let Some(original_entry) = original.get(&key) {
+                        reorder_like(&mut entry, original_entry);
+                    }
+                    reordered.insert(key, entry);
+                }
+            }
+            *mapping = reordered;
+        }
+        (Value::Sequence(items), Value::Sequence(original)) => {
+            for (item, original_item) in items.iter_mut().zip(original) {
+                reorder_like(item, original_item);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Comments of a YAML file, keyed by the (normalized) line they belong to
+#[derive(Debug, Default)]
+struct Comments {
+    /// Full-line comment blocks and the content line following each block
+    blocks: Vec<(String, CommentBlock)>,
+    /// Trailing comments and the content they follow on the same line
+    trailing: Vec<(String, String)>,
+    /// Comments after the last content line
+    end: CommentBlock,
+}
+
+#[derive(Debug, Default)]
+struct CommentBlock {
+    /// Whether a blank line separated the block from the preceding content
+    spaced: bool,
+    /// Comment lines, with blank lines between them as empty strings
+    lines: Vec<String>,
+}
+
+impl Comments {
+    fn parse(source: &str) -> Self {
+        let mut comments = Comments::default();
+        let mut block = CommentBlock::default();
+        let mut after_content = false;
+        let mut blank = false;
+
+        for line in source.lines() {
+            let trimmed = line.trim();
+            if trimmed.is_empty() {
+                if block.lines.is_empty() {
+                    block.spaced |= after_content;
+                } else {
+                    blank = true;
+                }
+                continue;
+            }
+            if trimmed.starts_with('#') {
+                if std::mem::take(&mut blank) {
+                    block.lines.push(String::new());
+                }
+                block.lines.push(trimmed.to_string());
+                continue;
+            }
+            blank = false;
+
+            let (content, trailing) = split_trailing_comment(line);
+            if !block.lines.is_empty() {
+                comments
+                    .blocks
+                    .push((normalize(content), std::mem::take(&mut block)));
+            }
+            block = CommentBlock::default();
+            if let Some(comment) = trailing {
+                comments
+                    .trailing
+                    .push((normalize(content), comment.to_string()));
+            }
+            after_content = true;
+        }
+
+        comments.end = block;
+        comments
+    }
+}
+
+/// Copy the comments of `original` into `output` (YAML of the same document)
+///
+/// A comment is kept when the line it precedes or ends appears unchanged in
+/// `output`, ignoring indentation and quoting. Lines are matched in order, so
+/// comments never move before an earlier comment's line.
+pub(crate) fn restore_comments(original: &str, output: &str) -> String {
+    let comments = Comments::parse(original);
+    let mut next_block = 0;
+    let mut next_trailing = 0;
+    let mut result = String::new();
+
+    for line in output.lines() {
+        let normalized = normalize(line);
+        let indent = &line[..line.len() - line.trim_start().len()];
+
+        if let Some(offset) = comments.blocks[next_block..]
+            .iter()
+            .position(|(anchor, _)| *anchor == normalized)
+        {
+            let (_, block) = &comments.blocks[next_block + offset];
+            if block.spaced && !result.is_empty() {
+                result.push('\n');
+            }
+            for comment in &block.lines {
+                if !comment.is_empty() {
+                    result.push_str(indent);
+                    result.push_str(comment);
+                }
+                result.push('\n');
+            }
+            next_block += offset + 1;
+        }
+
+        result.push_str(line);
+        if let Some(offset) = comments.trailing[next_trailing..]
+            .iter()
+            .position(|(anchor, _)| *anchor == normalized)
+        {
+            result.push_str("  ");
+            result.push_str(&comments.trailing[next_trailing + offset].1);
+            next_trailing += offset + 1;
+        }
+        result.push('\n');
+    }
+
+    if !comments.end.lines.is_empty() {
+        if comments.end.spaced {
+            result.push('\n');
+        }
+        for comment in &comments.end.lines {
+            result.push_str(comment);
+            result.push('\n');
+        }
+    }
+
+    result
+}
+
+/// Split a content line into the content and a trailing `# comment` outside quotes
+fn split_trailing_comment(line: &str) -> (&str, Option<&str>) {
+    let mut quote = None;
+    let mut previous = ' ';
+    for (i, c) in line.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), _) if c == q && previous != '\\' => quote = None,
+            (None, '#') if previous.is_whitespace() => {
+                return (line[..i].trim_end(), Some(&line[i..]));
+            }
+            _ => {}
+        }
+        previous = c;
+    }
+    (line, None)
+}
+
+/// Line content for matching: no surrounding whitespace or quotes, `~` as `null`,
+/// and whole numbers without a trailing `.0` (serialized floats)
+fn normalize(line: &str) -> String {
+    let line: String = line
+        .trim()
+        .chars()
+        .filter(|c| !matches!(c, '"' | '\''))
+        .collect();
+    if let Some(key) = line.strip_suffix('~') {
+        if key.ends_with(": ") || key.is_empty() {
+            return format!("{}null", key);
+        }
+    }
+    match line.strip_suffix(".0") {
+        Some(number) if number.ends_with(|c: char| c.is_ascii_digit()) => number.to_string(),
+        _ => line,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reorder_like() {
+        let original: Value =
+            serde_yaml::from_str("b: 1\na: {y: 1, x: 2}\nlist: [{k: 1, j: 2}]").unwrap();
+        let mut value: Value =
+            serde_yaml::from_str("a: {x: 2, y: 1}\nb: 1\nnew: 3\nlist: [{j: 2, k: 1}]").unwrap();
+
+        // `new` follows `b`, the key before it in `value`
+        reorder_like(&mut value, &original);
+        assert_eq!(
+            serde_yaml::to_string(&value).unwrap(),
+            "b: 1\nnew: 3\na:\n  y: 1\n  x: 2\nlist:\n- k: 1\n  j: 2\n"
+        );
+    }
+
+    #[test]
+    fn test_restore_comments() {
+        let original = "# Header\n\n# Name\nname: \"Test\"  # shown in lists\ndefault: ~  # user must choose\nmin: 0  # lowest value\nurl: \"https://example.org/#top\"\n# Trailer\n";
+        let output = "name: Test\ndefault: null\nmin: 0.0\nurl: https://example.org/#top\n";
+
+        assert_eq!(
+            restore_comments(original, output),
+            "# Header\n\n# Name\nname: Test  # shown in lists\ndefault: null  # user must choose\nmin: 0.0  # lowest value\nurl: https://example.org/#top\n# Trailer\n"
+        );
+
+        // Comments of changed lines are dropped
+        assert_eq!(
+            restore_comments(original, "name: Other\n"),
+            "name: Other\n# Trailer\n"
+        );
+    }
+}
@@ -1,23 +1,37 @@
 // score_definition.rs
 // Core data structures for clinical score definitions
 
+use super::localized::{Localized, LocalizedText};
+use crate::scores::condition::CompiledConditions;
+use crate::scores::InputValue;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use serde_yaml::{Mapping, Value};
+use std::collections::BTreeMap;
 
 /// Complete definition of a clinical score, loaded from YAML
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ScoreDefinition {
-    /// Score name in English
-    pub name: String,
+    /// Stable score identifier (e.g., "has_bled").
+    /// Legacy files without an `id` are identified by their filename.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
 
-    /// Score name in German
-    pub name_de: String,
+    /// Score name
+    pub name: LocalizedText,
+
+    /// Alternative and colloquial names per language, for search
+    /// (e.g., `en: ["CHA2DS2-VASc", "AF stroke risk"]`)
+    #[serde(default, skip_serializing_if = "Localized::is_empty")]
+    pub aliases: Localized<Vec<String>>,
 
     /// Medical specialty
     pub specialty: Specialty,
 
-    /// Medical specialty in German
-    pub specialty_de: String,
+    /// Position in its specialty's score list, lowest first; scores without one
+    /// follow, ordered by name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sort_order: Option<i32>,
 
     /// Version of the score definition
     pub version: String,
@@ -25,42 +39,484 @@ pub struct ScoreDefinition {
     /// Source guideline (e.g., "ESC 2024", "KDIGO 2024")
     pub guideline_source: String,
 
-    /// Full reference citation
-    pub reference: String,
-
-    /// URL to the primary reference (DOI link, PubMed, or guideline page)
-    #[serde(default)]
-    pub reference_url: String,
+    /// Publications and guidelines the score is based on, primary reference first
+    pub references: Vec<Reference>,
 
     /// Validation status (e.g., "peer_reviewed", "draft")
     pub validation_status: String,
 
-    /// Brief description in English
-    #[serde(default)]
-    pub description: String,
+    /// Whether this score should no longer be used
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub deprecated: bool,
 
-    /// Brief description in German
-    #[serde(default)]
-    pub description_de: String,
+    /// ID of the score that replaces this one (implies `deprecated`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub superseded_by: Option<String>,
+
+    /// Brief description
+    #[serde(default, skip_serializing_if = "Localized::is_empty")]
+    pub description: LocalizedText,
+
+    /// Intended use: purpose, intended users and clinical setting
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub intended_use: Option<LocalizedText>,
+
+    /// Regulatory status or caveat (e.g., "Not a certified medical device")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub regulatory_note: Option<LocalizedText>,
+
+    /// Regions or countries the score was validated in (e.g., "EU", "US", "DE")
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub regions_validated: Vec<String>,
 
     /// List of input fields for the score
     pub inputs: Vec<InputField>,
 
+    /// Optional grouping of inputs into sections (for long instruments)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sections: Vec<InputSection>,
+
+    /// How the input form is presented; unset follows the user's setting
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input_mode: Option<InputMode>,
+
+    /// Cross-field validation constraints, checked before calculation
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub constraints: Vec<Constraint>,
+
     /// Interpretation rules mapping scores to risk categories
     pub interpretation: Vec<InterpretationRule>,
 
     /// Optional formula identifier for formula-based scores (e.g., "ckd_epi_2021", "kfre_4var")
     /// When set, the calculator uses a built-in formula instead of point summation.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub formula: Option<String>,
 
-    /// Optional metadata (e.g., tags, keywords)
+    /// Clinical context and search metadata
+    #[serde(default, skip_serializing_if = "ScoreMetadata::is_empty")]
+    pub metadata: ScoreMetadata,
+
+    /// Validation cases with their expected results, run by `klinscore-cli test`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub test_cases: Vec<TestCase>,
+
+    /// Conditions and score ranges parsed when the score was validated
+    #[serde(skip)]
+    pub conditions: CompiledConditions,
+}
+
+impl ScoreDefinition {
+    /// Serialize as YAML, fields in definition order
+    ///
+    /// Unset optional fields are left out. Scores that `extends` base definitions are
+    /// written with the inherited inputs in full.
+    pub fn to_yaml(&self) -> Result<String, serde_yaml::Error> {
+        serde_yaml::to_string(self)
+    }
+
+    /// Serialize as YAML in the layout of `original`, the file the score was loaded from
+    ///
+    /// Keys keep their order in `original`, and comments are kept where the line they
+    /// belong to is unchanged (ignoring indentation and quoting). Falls back to
+    /// [`Self::to_yaml`] if `original` is not YAML.
+    pub fn to_yaml_preserving(&self, original: &str) -> Result<String, serde_yaml::Error> {
+        let Ok(original_value) = serde_yaml::from_str::<Value>(original) else {
+            return self.to_yaml();
+        };
+        let mut value = serde_yaml::to_value(self)?;
+        super::round_trip::reorder_like(&mut value, &original_value);
+        let output = serde_yaml::to_string(&value)?;
+        Ok(super::round_trip::restore_comments(original, &output))
+    }
+
+    /// Whether the score is deprecated, either explicitly or by naming a replacement
+    pub fn is_deprecated(&self) -> bool {
+        self.deprecated || self.superseded_by.is_some()
+    }
+
+    /// Inputs grouped for display, in definition order.
+    ///
+    /// Fields not listed in any section come first as an unnamed group (`None`),
+    /// followed by each section with its fields in the order the section lists them.
+    /// Without sections, all inputs form a single unnamed group.
+    pub fn grouped_inputs(&self) -> Vec<(Option<&InputSection>, Vec<&InputField>)> {
+        let ungrouped: Vec<&InputField> = self
+            .inputs
+            .iter()
+            .filter(|input| {
+                !self
+                    .sections
+                    .iter()
+                    .any(|s| s.fields.contains(&input.field))
+            })
+            .collect();
+
+        let mut groups = Vec::new();
+        if !ungrouped.is_empty() {
+            groups.push((None, ungrouped));
+        }
+
+        for section in &self.sections {
+            let fields: Vec<&InputField> = section
+                .fields
+                .iter()
+                .filter_map(|name| self.inputs.iter().find(|input| &input.field == name))
+                .collect();
+            groups.push((Some(section), fields));
+        }
+
+        groups
+    }
+
+    /// Whether the input form opens as a wizard
+    ///
+    /// `input_mode` decides if set; otherwise scores with at least
+    /// [`InputMode::WIZARD_MIN_INPUTS`] inputs use the wizard when
+    /// `wizard_for_long_scores` is enabled.
+    pub fn uses_wizard(&self, wizard_for_long_scores: bool) -> bool {
+        match self.input_mode {
+            Some(mode) => mode == InputMode::Wizard,
+            None => wizard_for_long_scores && self.inputs.len() >= InputMode::WIZARD_MIN_INPUTS,
+        }
+    }
+}
+
+/// Presentation of the input form
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum InputMode {
+    /// All inputs on one page
+    Form,
+
+    /// One page per section, followed by a review page
+    Wizard,
+}
+
+impl InputMode {
+    /// Scores with at least this many inputs count as long (e.g., Caprini)
+    pub const WIZARD_MIN_INPUTS: usize = 20;
+}
+
+/// A named group of input fields, rendered as a collapsible block in the form
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct InputSection {
+    /// Unique identifier for this section
+    pub id: String,
+
+    /// Section title
+    pub label: LocalizedText,
+
+    /// Field identifiers belonging to this section, in display order
+    pub fields: Vec<String>,
+}
+
+/// A cross-field validation rule (e.g., "diastolic_bp < systolic_bp")
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Constraint {
+    /// Comparison of two operands (field identifiers or numbers),
+    /// using <, <=, >, >=, == or !=
+    pub expression: String,
+
+    /// Message shown when the constraint is violated
+    pub message: LocalizedText,
+}
+
+/// A publication or guideline the score is based on.
+///
+/// Either structured (authors, title, journal, year, identifiers) or a free-text
+/// `citation`, e.g. for guideline documents without a journal citation.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Reference {
+    /// Author list as cited (e.g., "Lip GY, Nieuwlaat R, et al.")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub authors: Option<String>,
+
+    /// Title of the publication
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+
+    /// Journal with volume and pages (e.g., "Chest. 2010;137(2):263-272")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub journal: Option<String>,
+
+    /// Year of publication
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub year: Option<u16>,
+
+    /// Digital Object Identifier, without the "https://doi.org/" prefix
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub doi: Option<String>,
+
+    /// PubMed identifier
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pmid: Option<String>,
+
+    /// Link to the publication or guideline page, if there is no DOI or PMID
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+
+    /// Free-text citation, used instead of the structured fields
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub citation: Option<String>,
+}
+
+impl Reference {
+    /// Human-readable citation: "Authors. Title. Journal (Year)." or the free-text citation
+    pub fn formatted(&self) -> String {
+        if let Some(citation) = &self.citation {
+            return citation.clone();
+        }
+
+        let mut parts: Vec<String> = [&self.authors, &self.title, &self.journal]
+            .into_iter()
+            .flatten()
+            .map(|part| part.trim_end_matches('.').to_string())
+            .collect();
+        if let Some(year) = self.year {
+            match parts.last_mut() {
+                Some(last) => last.push_str(&format!(" ({})", year)),
+                None => parts.push(year.to_string()),
+            }
+        }
+        if parts.is_empty() {
+            return String::new();
+        }
+        format!("{}.", parts.join(". "))
+    }
+
+    /// Link to the reference: the DOI resolver, PubMed, or the explicit URL (in that order)
+    pub fn link(&self) -> Option<String> {
+        self.doi
+            .as_ref()
+            .map(|doi| format!("https://doi.org/{}", doi))
+            .or_else(|| {
+                self.pmid
+                    .as_ref()
+                    .map(|pmid| format!("https://pubmed.ncbi.nlm.nih.gov/{}/", pmid))
+            })
+            .or_else(|| self.url.clone())
+    }
+
+    /// Whether the reference has anything to cite
+    pub fn is_empty(&self) -> bool {
+        self.citation.is_none()
+            && self.authors.is_none()
+            && self.title.is_none()
+            && self.journal.is_none()
+    }
+}
+
+/// Rewrite the legacy single `reference` string (plus `reference_url`) into a
+/// one-item `references` list, in place. Returns whether anything was rewritten.
+pub fn upgrade_legacy_references(document: &mut Value) -> bool {
+    let Some(map) = document.as_mapping_mut() else {
+        return false;
+    };
+    let citation = map.remove("reference");
+    let url = map.remove("reference_url");
+    if citation.is_none() && url.is_none() {
+        return false;
+    }
+
+    if !map.contains_key("references") {
+        let mut reference = Mapping::new();
+        for (key, value) in [("citation", citation), ("url", url)] {
+            match value {
+                Some(Value::String(text)) if text.trim().is_empty() => {}
+                Some(value) if !value.is_null() => {
+                    reference.insert(key.into(), value);
+                }
+                _ => {}
+            }
+        }
+        let references = if reference.is_empty() {
+            Vec::new()
+        } else {
+            vec![Value::Mapping(reference)]
+        };
+        map.insert("references".into(), Value::Sequence(references));
+    }
+    true
+}
+
+/// Validation case of a score: inputs and the result they must give
+///
+/// ```yaml
+/// test_cases:
+///   - name: "72-year-old with heart failure and hypertension"
+///     inputs: {age: 72, heart_failure: true, hypertension: true}
+///     expected: {total: 3, risk_level: High}
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct TestCase {
+    /// What the case checks, shown when it fails
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    /// Input values by field, as in history entries (`true`, numbers in the field's
+    /// unit, option values); boolean inputs left out are unchecked
     #[serde(default)]
-    pub metadata: HashMap<String, String>,
+    pub inputs: BTreeMap<String, InputValue>,
+
+    /// Expected result; at least one of its fields must be set
+    pub expected: ExpectedResult,
+}
+
+/// Expected result of a [`TestCase`]; unset fields are not checked
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ExpectedResult {
+    /// Total score (the rounded result for formula scores)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total: Option<i32>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub risk_level: Option<RiskLevel>,
+
+    /// Risk category of the matched interpretation, in any language of the score
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub risk: Option<String>,
+}
+
+impl ExpectedResult {
+    pub fn is_empty(&self) -> bool {
+        self.total.is_none() && self.risk_level.is_none() && self.risk.is_none()
+    }
+}
+
+/// Clinical context of a score: what it answers, for whom, and when not to use it
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ScoreMetadata {
+    /// Search tags per language
+    #[serde(default, skip_serializing_if = "Localized::is_empty")]
+    pub tags: Localized<Vec<String>>,
+
+    /// Kind of instrument
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<ScoreCategory>,
+
+    /// The clinical question the score answers
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub clinical_question: Option<LocalizedText>,
+
+    /// Population the score was validated in
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_population: Option<LocalizedText>,
+
+    /// Situations in which the score should not be used
+    #[serde(default, skip_serializing_if = "Localized::is_empty")]
+    pub contraindications: Localized<Vec<String>>,
+
+    /// Period the prediction refers to (e.g., "2 and 5 years")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time_horizon: Option<LocalizedText>,
+
+    /// DOI of the primary publication
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub doi: Option<String>,
+
+    /// Year of the underlying guideline or publication
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub guideline_year: Option<String>,
+
+    /// LOINC or SNOMED CT codes of the score's result, for the FHIR export
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub codes: Vec<ScoreCode>,
+
+    /// UCUM unit of the result, for scores reported as a quantity
+    /// (e.g., "mL/min/{1.73_m2}" for the eGFR); unset for point scores
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result_unit: Option<String>,
+
+    /// Free-text remarks for maintainers (not shown to users)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+}
+
+impl ScoreMetadata {
+    /// Whether no metadata is set
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Whether any tag (in any language) matches `tag`, ignoring case
+    pub fn has_tag(&self, tag: &str) -> bool {
+        let tag = tag.to_lowercase();
+        self.tags
+            .values()
+            .flatten()
+            .any(|t| t.to_lowercase() == tag)
+    }
+}
+
+/// Standard terminology code of a score's result
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ScoreCode {
+    pub system: CodeSystem,
+    /// Code in the system (e.g., "98979-8")
+    pub code: String,
+    /// Display name as defined by the system
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display: Option<String>,
+}
+
+/// Terminology a [`ScoreCode`] is taken from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CodeSystem {
+    Loinc,
+    Snomed,
+}
+
+impl CodeSystem {
+    /// System URI used in FHIR codings
+    pub fn uri(self) -> &'static str {
+        match self {
+            CodeSystem::Loinc => "http://loinc.org",
+            CodeSystem::Snomed => "http://snomed.info/sct",
+        }
+    }
+}
+
+/// Kind of clinical instrument
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ScoreCategory {
+    RiskStratification,
+    Screening,
+    Diagnostic,
+    Classification,
+    #[serde(other)]
+    Other,
+}
+
+impl ScoreCategory {
+    /// Get English name of category
+    pub fn english(self) -> &'static str {
+        match self {
+            ScoreCategory::RiskStratification => "Risk stratification",
+            ScoreCategory::Screening => "Screening",
+            ScoreCategory::Diagnostic => "Diagnostic",
+            ScoreCategory::Classification => "Classification",
+            ScoreCategory::Other => "Other",
+        }
+    }
+
+    /// Get German translation of category
+    pub fn german(self) -> &'static str {
+        match self {
+            ScoreCategory::RiskStratification => "Risikostratifizierung",
+            ScoreCategory::Screening => "Screening",
+            ScoreCategory::Diagnostic => "Diagnostik",
+            ScoreCategory::Classification => "Klassifikation",
+            ScoreCategory::Other => "Sonstiges",
+        }
+    }
 }
 
 /// Medical specialty classification
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "PascalCase")]
 pub enum Specialty {
     Cardiology,
@@ -69,11 +525,45 @@ pub enum Specialty {
     Emergency,
     InternalMedicine,
     Surgery,
+    Pulmonology,
+    Neurology,
+    Gastroenterology,
+    Hematology,
+    Oncology,
+    Endocrinology,
+    InfectiousDiseases,
+    Geriatrics,
+    Pediatrics,
+    IntensiveCare,
+    Psychiatry,
     #[serde(other)]
     Other,
 }
 
 impl Specialty {
+    /// All specialties, in display order. Code that lists specialties iterates
+    /// over this instead of naming variants.
+    pub const ALL: [Specialty; 18] = [
+        Specialty::Cardiology,
+        Specialty::Nephrology,
+        Specialty::Anesthesiology,
+        Specialty::Emergency,
+        Specialty::IntensiveCare,
+        Specialty::InternalMedicine,
+        Specialty::Pulmonology,
+        Specialty::Gastroenterology,
+        Specialty::Endocrinology,
+        Specialty::Hematology,
+        Specialty::Oncology,
+        Specialty::InfectiousDiseases,
+        Specialty::Neurology,
+        Specialty::Psychiatry,
+        Specialty::Geriatrics,
+        Specialty::Pediatrics,
+        Specialty::Surgery,
+        Specialty::Other,
+    ];
+
     /// Get German translation of specialty
     pub fn german(self) -> &'static str {
         match self {
@@ -83,6 +573,17 @@ impl Specialty {
             Specialty::Emergency => "Notfallmedizin",
             Specialty::InternalMedicine => "Innere Medizin",
             Specialty::Surgery => "Chirurgie",
+            Specialty::Pulmonology => "Pneumologie",
+            Specialty::Neurology => "Neurologie",
+            Specialty::Gastroenterology => "Gastroenterologie",
+            Specialty::Hematology => "Hämatologie",
+            Specialty::Oncology => "Onkologie",
+            Specialty::Endocrinology => "Endokrinologie",
+            Specialty::InfectiousDiseases => "Infektiologie",
+            Specialty::Geriatrics => "Geriatrie",
+            Specialty::Pediatrics => "Pädiatrie",
+            Specialty::IntensiveCare => "Intensivmedizin",
+            Specialty::Psychiatry => "Psychiatrie",
             Specialty::Other => "Sonstiges",
         }
     }
@@ -96,13 +597,48 @@ impl Specialty {
             Specialty::Emergency => "Emergency Medicine",
             Specialty::InternalMedicine => "Internal Medicine",
             Specialty::Surgery => "Surgery",
+            Specialty::Pulmonology => "Pulmonology",
+            Specialty::Neurology => "Neurology",
+            Specialty::Gastroenterology => "Gastroenterology",
+            Specialty::Hematology => "Hematology",
+            Specialty::Oncology => "Oncology",
+            Specialty::Endocrinology => "Endocrinology",
+            Specialty::InfectiousDiseases => "Infectious Diseases",
+            Specialty::Geriatrics => "Geriatrics",
+            Specialty::Pediatrics => "Pediatrics",
+            Specialty::IntensiveCare => "Intensive Care",
+            Specialty::Psychiatry => "Psychiatry",
             Specialty::Other => "Other",
         }
     }
+
+    /// Icon shown next to the specialty name
+    pub fn icon(self) -> &'static str {
+        match self {
+            Specialty::Cardiology => "🫀",
+            Specialty::Nephrology => "🩺",
+            Specialty::Anesthesiology => "💉",
+            Specialty::Emergency => "🚑",
+            Specialty::InternalMedicine => "🏥",
+            Specialty::Surgery => "🩹",
+            Specialty::Pulmonology => "🫁",
+            Specialty::Neurology => "🧠",
+            Specialty::Gastroenterology => "🔬",
+            Specialty::Hematology => "🩸",
+            Specialty::Oncology => "🎗",
+            Specialty::Endocrinology => "🧪",
+            Specialty::InfectiousDiseases => "🦠",
+            Specialty::Geriatrics => "🧓",
+            Specialty::Pediatrics => "🧒",
+            Specialty::IntensiveCare => "🛏",
+            Specialty::Psychiatry => "💭",
+            Specialty::Other => "📋",
+        }
+    }
 }
 
 /// Definition of a single input field
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct InputField {
     /// Unique identifier for this field (used as key in input map)
     pub field: String,
@@ -111,54 +647,264 @@ pub struct InputField {
     #[serde(rename = "type")]
     pub input_type: InputType,
 
-    /// Display label in English
-    pub label: String,
-
-    /// Display label in German
-    pub label_de: String,
+    /// Display label
+    pub label: LocalizedText,
 
     /// Unit of measurement (e.g., "μmol/L", "years", "kg")
-    #[serde(default)]
-    pub unit: Option<String>,
-
-    /// Unit in German (if different)
-    #[serde(default)]
-    pub unit_de: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unit: Option<LocalizedText>,
 
     /// Points assigned based on this input
     pub points: PointsValue,
 
-    /// Optional help text in English
-    #[serde(default)]
-    pub help: Option<String>,
-
-    /// Optional help text in German
-    #[serde(default)]
-    pub help_de: Option<String>,
+    /// Optional help text
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub help: Option<LocalizedText>,
 
     /// For number inputs: minimum allowed value
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub min: Option<f64>,
 
     /// For number inputs: maximum allowed value
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub max: Option<f64>,
 
+    /// For date inputs: score the time elapsed since the date (e.g., the age from
+    /// the date of birth) instead of the date itself. `min`, `max` and the points
+    /// rules then apply to the elapsed time.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub elapsed: Option<Elapsed>,
+
+    /// For duration inputs: unit the duration is scored in; `min`, `max` and the
+    /// points rules use it, whatever units the duration is entered in
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_unit: Option<DurationUnit>,
+
     /// For dropdown inputs: available options
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub options: Vec<DropdownOption>,
 
+    /// For dropdown inputs: value of the preselected option. `default: ~` states
+    /// explicitly that nothing is preselected; required dropdowns must set either.
+    #[serde(
+        default,
+        deserialize_with = "explicit_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[schemars(with = "Option<String>")]
+    pub default: Option<Option<String>>,
+
+    /// For number inputs: the same value in conventional units (e.g., mg/dL),
+    /// offered when the user prefers conventional over SI units
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub conventional_unit: Option<ConventionalUnit>,
+
+    /// Form widget; chosen from the field type and option count if unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub widget: Option<InputWidget>,
+
+    /// LOINC codes of the Observations that fill this input when prefilling from a
+    /// FHIR server (e.g., "2160-0" for serum creatinine); most specific first.
+    /// Age ("30525-0"), date of birth ("21112-8") and sex ("46098-0") are taken
+    /// from the Patient resource.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub loinc: Vec<String>,
+
     /// Whether this field is required
     #[serde(default = "default_true")]
     pub required: bool,
 }
 
+impl InputField {
+    /// Value of the preselected dropdown option, if any
+    pub fn default_option(&self) -> Option<&str> {
+        self.default.as_ref()?.as_deref()
+    }
+
+    /// Unit entered in the given unit system; `unit` unless the field declares a
+    /// conventional unit and the user prefers it
+    pub fn unit_in(&self, system: UnitSystem) -> Option<&LocalizedText> {
+        match (system, &self.conventional_unit) {
+            (UnitSystem::Conventional, Some(conventional)) => Some(&conventional.unit),
+            _ => self.unit.as_ref(),
+        }
+    }
+
+    /// Factor turning a value entered in the given unit system into the `unit`
+    /// the scoring rules use (1 if no conversion applies)
+    pub fn unit_factor(&self, system: UnitSystem) -> f64 {
+        match (system, &self.conventional_unit) {
+            (UnitSystem::Conventional, Some(conventional)) => conventional.factor,
+            _ => 1.0,
+        }
+    }
+
+    /// Unit system in which this field's unit is `unit` (in any language,
+    /// ignoring case and whether micro is written µ or μ); SI if both systems
+    /// share it
+    pub fn unit_system_of(&self, unit: &str) -> Option<UnitSystem> {
+        let normalize = |unit: &str| unit.trim().replace('\u{b5}', "\u{3bc}").to_lowercase();
+        let unit = normalize(unit);
+        UnitSystem::ALL.into_iter().find(|&system| {
+            self.unit_in(system)
+                .is_some_and(|text| text.values().any(|candidate| normalize(candidate) == unit))
+        })
+    }
+
+    /// Whether the scoring rules see a number: number and duration fields, and
+    /// dates scored by the time elapsed since them
+    pub fn is_numeric(&self) -> bool {
+        match self.input_type {
+            InputType::Number | InputType::Duration => true,
+            InputType::Date => self.elapsed.is_some(),
+            InputType::Boolean | InputType::Dropdown => false,
+        }
+    }
+
+    /// Widget used in the form: the `widget` hint, else radio buttons for dropdowns
+    /// with up to [`InputWidget::MAX_RADIO_OPTIONS`] options and a pick list otherwise
+    pub fn effective_widget(&self) -> Option<InputWidget> {
+        match (self.widget, self.input_type) {
+            (Some(widget), _) => Some(widget),
+            (None, InputType::Dropdown) if self.options.len() <= InputWidget::MAX_RADIO_OPTIONS => {
+                Some(InputWidget::Radio)
+            }
+            (None, InputType::Dropdown) => Some(InputWidget::PickList),
+            (None, _) => None,
+        }
+    }
+}
+
+/// Units lab values are entered in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum UnitSystem {
+    /// SI units (e.g., μmol/L), in which scores define their rules
+    #[default]
+    Si,
+
+    /// Conventional units (e.g., mg/dL), where a field declares them
+    Conventional,
+}
+
+impl UnitSystem {
+    pub const ALL: [UnitSystem; 2] = [UnitSystem::Si, UnitSystem::Conventional];
+}
+
+/// Conventional unit of a number field defined in SI units
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ConventionalUnit {
+    /// Unit label (e.g., "mg/dL")
+    pub unit: LocalizedText,
+
+    /// Factor from this unit to the field's `unit`: SI value = conventional value × factor
+    pub factor: f64,
+}
+
+/// Time elapsed since a date input, counted until another date input
+///
+/// ```yaml
+/// - field: birth_date
+///   type: date
+///   elapsed: { unit: years, until: assessment_date }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct Elapsed {
+    /// Unit the elapsed time is counted in, in whole units
+    pub unit: ElapsedUnit,
+
+    /// Date input the time is measured until (e.g., the date of assessment),
+    /// which the form fills in with today's date
+    pub until: String,
+}
+
+/// Unit of an elapsed time; only complete units count
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ElapsedUnit {
+    /// Years, counted like an age
+    Years,
+
+    /// Weeks (e.g., of gestation)
+    Weeks,
+
+    /// Days
+    Days,
+}
+
+impl ElapsedUnit {
+    /// Unit label when a date field declares no `unit`
+    pub fn label(self) -> LocalizedText {
+        match self {
+            ElapsedUnit::Years => LocalizedText::bilingual("years", "Jahre"),
+            ElapsedUnit::Weeks => LocalizedText::bilingual("weeks", "Wochen"),
+            ElapsedUnit::Days => LocalizedText::bilingual("days", "Tage"),
+        }
+    }
+}
+
+/// Unit a duration input is scored in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum DurationUnit {
+    Minutes,
+    Hours,
+    Days,
+}
+
+impl DurationUnit {
+    /// Length of the unit in minutes
+    pub fn minutes(self) -> f64 {
+        match self {
+            DurationUnit::Minutes => 1.0,
+            DurationUnit::Hours => 60.0,
+            DurationUnit::Days => 1440.0,
+        }
+    }
+
+    /// Unit symbol when a duration field declares no `unit`
+    pub fn label(self) -> LocalizedText {
+        match self {
+            DurationUnit::Minutes => LocalizedText::bilingual("min", "min"),
+            DurationUnit::Hours => LocalizedText::bilingual("h", "h"),
+            DurationUnit::Days => LocalizedText::bilingual("d", "d"),
+        }
+    }
+}
+
+/// Form widget for an input, overriding the default for its type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum InputWidget {
+    /// Dropdown as a pick list (default for more than four options)
+    PickList,
+
+    /// Dropdown as a radio group showing every option with its points
+    Radio,
+
+    /// Number field with `min` and `max` as a slider in whole steps
+    Slider,
+}
+
+impl InputWidget {
+    /// Dropdowns with at most this many options are shown as radio groups by default
+    pub const MAX_RADIO_OPTIONS: usize = 4;
+}
+
 fn default_true() -> bool {
     true
 }
 
+/// Distinguish a key set to null (`Some(None)`) from a missing key (`None`, via `#[serde(default)]`)
+fn explicit_option<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
 /// Type of input field
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum InputType {
     /// Boolean checkbox (yes/no)
@@ -169,10 +915,17 @@ pub enum InputType {
 
     /// Dropdown selection
     Dropdown,
+
+    /// Calendar date (`YYYY-MM-DD`), e.g. a date of birth or of symptom onset
+    Date,
+
+    /// Time span (e.g., hours since symptom onset), entered as `4:30` or `4 h 30 min`
+    /// and scored as a number in its `duration_unit`
+    Duration,
 }
 
-/// Points value - can be fixed or conditional
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Points value - can be fixed, conditional or looked up in a table
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(untagged)]
 pub enum PointsValue {
     /// Fixed point value (e.g., for boolean: true = 1 point)
@@ -180,10 +933,40 @@ pub enum PointsValue {
 
     /// Conditional points based on value ranges
     Conditional(Vec<PointCondition>),
+
+    /// Points from a table indexed by this field's value and another field's value
+    Table { table: PointsTable },
+}
+
+/// Two-dimensional points table (e.g., heart rate × age band)
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PointsTable {
+    /// Number or boolean input whose value selects the column (booleans count as 1/0)
+    pub column_field: String,
+
+    /// Column conditions on the `column_field` value (e.g., "< 65"), first match wins
+    pub columns: Vec<String>,
+
+    /// Rows with a condition on this field's value, first match wins
+    pub rows: Vec<PointsTableRow>,
+}
+
+/// Row of a [`PointsTable`]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PointsTableRow {
+    /// Condition on this field's value (e.g., ">= 110")
+    pub condition: String,
+
+    /// Points for each column, in column order
+    pub points: Vec<i32>,
+
+    /// Optional label for this row (e.g., "Heart rate ≥110")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<LocalizedText>,
 }
 
 /// Conditional point assignment
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct PointCondition {
     /// Condition to check (e.g., ">= 65", "< 50", "== true")
     pub condition: String,
@@ -192,70 +975,53 @@ pub struct PointCondition {
     pub points: i32,
 
     /// Optional label for this condition (e.g., "Age 65-74")
-    #[serde(default)]
-    pub label: Option<String>,
-
-    /// Optional German label
-    #[serde(default)]
-    pub label_de: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<LocalizedText>,
 }
 
 /// Option for dropdown inputs
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct DropdownOption {
     /// Internal value identifier
     pub value: String,
 
-    /// Display label in English
-    pub label: String,
-
-    /// Display label in German
-    pub label_de: String,
+    /// Display label
+    pub label: LocalizedText,
 
     /// Points awarded for this selection
     pub points: i32,
 
-    /// Optional description in English
-    #[serde(default)]
-    pub description: Option<String>,
-
-    /// Optional description in German
-    #[serde(default)]
-    pub description_de: Option<String>,
+    /// Optional description
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<LocalizedText>,
 }
 
 /// Interpretation rule mapping score to risk category
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct InterpretationRule {
     /// Score value or range (e.g., 0, "0-1", "≥2")
     pub score: ScoreRange,
 
-    /// Risk level in English
-    pub risk: String,
+    /// Risk level
+    pub risk: LocalizedText,
 
-    /// Risk level in German
-    pub risk_de: String,
+    /// Optional lay-language interpretation (used for patient copies)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub risk_lay: Option<LocalizedText>,
 
     /// Risk category for color coding
     pub risk_level: RiskLevel,
 
-    /// Clinical recommendation in English
-    pub recommendation: String,
-
-    /// Clinical recommendation in German
-    pub recommendation_de: String,
-
-    /// Optional additional information in English
-    #[serde(default)]
-    pub details: Option<String>,
+    /// Clinical recommendation
+    pub recommendation: LocalizedText,
 
-    /// Optional additional information in German
-    #[serde(default)]
-    pub details_de: Option<String>,
+    /// Optional additional information
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub details: Option<LocalizedText>,
 }
 
 /// Score range for interpretation
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 #[serde(untagged)]
 pub enum ScoreRange {
     /// Exact score value
@@ -266,7 +1032,7 @@ pub enum ScoreRange {
 }
 
 /// Risk level for color coding and categorization
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "PascalCase")]
 pub enum RiskLevel {
     /// Very low risk (green)
@@ -318,17 +1084,160 @@ impl RiskLevel {
             RiskLevel::None => (0.620, 0.620, 0.620),
         }
     }
+
+    /// Symbol shown next to the risk text, so the level does not depend on color alone
+    pub fn icon(&self) -> &'static str {
+        match self {
+            RiskLevel::VeryLow | RiskLevel::Low => "✔",
+            RiskLevel::Moderate => "!",
+            RiskLevel::High => "▲",
+            RiskLevel::VeryHigh => "▲▲",
+            RiskLevel::Critical => "⛔",
+            RiskLevel::None => "ℹ",
+        }
+    }
+
+    /// Name of the level ("en" or "de"; other languages get English)
+    pub fn label(&self, language: &str) -> &'static str {
+        let german = language == "de";
+        match self {
+            RiskLevel::VeryLow if german => "sehr niedrig",
+            RiskLevel::VeryLow => "very low",
+            RiskLevel::Low if german => "niedrig",
+            RiskLevel::Low => "low",
+            RiskLevel::Moderate if german => "mittel",
+            RiskLevel::Moderate => "moderate",
+            RiskLevel::High if german => "hoch",
+            RiskLevel::High => "high",
+            RiskLevel::VeryHigh if german => "sehr hoch",
+            RiskLevel::VeryHigh => "very high",
+            RiskLevel::Critical if german => "kritisch",
+            RiskLevel::Critical => "critical",
+            RiskLevel::None if german => "informativ",
+            RiskLevel::None => "informational",
+        }
+    }
+
+    /// RGB color for text and accents on the background of `palette`
+    ///
+    /// Unlike [`Self::rgb`], every color has a contrast ratio of at least 4.5:1
+    /// against the palette's background (WCAG AA for normal text).
+    pub fn rgb_in(&self, palette: RiskPalette) -> (f32, f32, f32) {
+        let hex = match (palette, self) {
+            (RiskPalette::Light, RiskLevel::VeryLow) => 0x2E7D32,
+            (RiskPalette::Light, RiskLevel::Low) => 0x4F7A1F,
+            (RiskPalette::Light, RiskLevel::Moderate) => 0x8A6100,
+            (RiskPalette::Light, RiskLevel::High) => 0xB34700,
+            (RiskPalette::Light, RiskLevel::VeryHigh) => 0xC62828,
+            (RiskPalette::Light, RiskLevel::Critical) => 0x8E0000,
+            (RiskPalette::Light, RiskLevel::None) => 0x616161,
+
+            (RiskPalette::Dark, RiskLevel::VeryLow) => 0x81C784,
+            (RiskPalette::Dark, RiskLevel::Low) => 0xC5E1A5,
+            (RiskPalette::Dark, RiskLevel::Moderate) => 0xFFD54F,
+            (RiskPalette::Dark, RiskLevel::High) => 0xFFB74D,
+            (RiskPalette::Dark, RiskLevel::VeryHigh) => 0xEF9A9A,
+            (RiskPalette::Dark, RiskLevel::Critical) => 0xFF6F6F,
+            (RiskPalette::Dark, RiskLevel::None) => 0xBDBDBD,
+
+            (RiskPalette::Sepia, RiskLevel::VeryLow) => 0x2E6B30,
+            (RiskPalette::Sepia, RiskLevel::Low) => 0x4A6518,
+            (RiskPalette::Sepia, RiskLevel::Moderate) => 0x775200,
+            (RiskPalette::Sepia, RiskLevel::High) => 0x9A3D00,
+            (RiskPalette::Sepia, RiskLevel::VeryHigh) => 0xAA2020,
+            (RiskPalette::Sepia, RiskLevel::Critical) => 0x7A0000,
+            (RiskPalette::Sepia, RiskLevel::None) => 0x5D534A,
+        };
+        let channel = |shift: u32| ((hex >> shift) & 0xFF) as f32 / 255.0;
+        (channel(16), channel(8), channel(0))
+    }
+}
+
+/// Set of risk colors matched to a theme's background
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum RiskPalette {
+    /// Darker colors for light backgrounds
+    #[default]
+    Light,
+
+    /// Lighter colors for dark gray backgrounds
+    Dark,
+
+    /// Warm, dark colors for the beige Sepia background
+    Sepia,
+}
+
+impl RiskPalette {
+    pub const ALL: [RiskPalette; 3] = [RiskPalette::Light, RiskPalette::Dark, RiskPalette::Sepia];
+}
+
+impl std::fmt::Display for RiskPalette {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let label = match self {
+            RiskPalette::Light => "Light",
+            RiskPalette::Dark => "Dark",
+            RiskPalette::Sepia => "Sepia",
+        };
+        write!(f, "{}", label)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_effective_widget() {
+        let mut field: InputField = serde_yaml::from_str(
+            r#"
+field: "killip_class"
+type: "dropdown"
+label: "Killip class"
+points: 0
+options:
+  - {value: "1", label: "I", points: 0}
+  - {value: "2", label: "II", points: 20}
+"#,
+        )
+        .unwrap();
+        assert_eq!(field.effective_widget(), Some(InputWidget::Radio));
+
+        let option = field.options[0].clone();
+        field.options.extend(vec![option; 3]);
+        assert_eq!(field.effective_widget(), Some(InputWidget::PickList));
+
+        field.widget = Some(InputWidget::Radio);
+        assert_eq!(field.effective_widget(), Some(InputWidget::Radio));
+
+        field.input_type = InputType::Number;
+        field.widget = None;
+        assert_eq!(field.effective_widget(), None);
+        field.widget = Some(InputWidget::Slider);
+        assert_eq!(field.effective_widget(), Some(InputWidget::Slider));
+    }
+
     #[test]
     fn test_specialty_translations() {
         assert_eq!(Specialty::Cardiology.german(), "Kardiologie");
         assert_eq!(Specialty::Cardiology.english(), "Cardiology");
         assert_eq!(Specialty::Nephrology.german(), "Nephrologie");
+        assert_eq!(Specialty::IntensiveCare.german(), "Intensivmedizin");
+
+        let parsed: Specialty = serde_yaml::from_str("InfectiousDiseases").unwrap();
+        assert_eq!(parsed, Specialty::InfectiousDiseases);
+
+        // The list is complete and every entry has distinct names
+        let names: std::collections::HashSet<_> =
+            Specialty::ALL.iter().map(|s| s.english()).collect();
+        assert_eq!(names.len(), Specialty::ALL.len());
+        assert_eq!(
+            Specialty::ALL
+                .iter()
+                .map(|s| s.german())
+                .collect::<std::collections::HashSet<_>>()
+                .len(),
+            Specialty::ALL.len()
+        );
     }
 
     #[test]
@@ -338,64 +1247,176 @@ mod tests {
 
         let (r, _g, _b) = RiskLevel::VeryHigh.rgb();
         assert!((r - 0.957).abs() < 0.001);
+
+        // Levels are distinguishable without color
+        assert_ne!(RiskLevel::High.icon(), RiskLevel::VeryHigh.icon());
+        assert_eq!(RiskLevel::VeryHigh.label("de"), "sehr hoch");
+        assert_eq!(RiskLevel::VeryHigh.label("fr"), "very high");
+    }
+
+    #[test]
+    fn test_metadata_parsing() {
+        let metadata: ScoreMetadata = serde_yaml::from_str(
+            r#"
+tags:
+  en: ["atrial fibrillation", "stroke risk"]
+  fr: ["fibrillation auriculaire"]
+category: risk_stratification
+time_horizon: "1 year"
+contraindications:
+  - "Mechanical heart valves"
+max_score: "9"
+"#,
+        )
+        .unwrap();
+        assert_eq!(metadata.tags.get("en").unwrap().len(), 2);
+        assert_eq!(metadata.category, Some(ScoreCategory::RiskStratification));
+        assert_eq!(metadata.time_horizon.as_ref().unwrap().text("de"), "1 year");
+        assert_eq!(metadata.contraindications.get("de").unwrap().len(), 1);
+        assert!(metadata.has_tag("Stroke Risk"));
+        assert!(metadata.has_tag("Fibrillation auriculaire"));
+
+        let unknown: ScoreMetadata = serde_yaml::from_str("category: \"something_new\"").unwrap();
+        assert_eq!(unknown.category, Some(ScoreCategory::Other));
     }
 
     #[test]
     fn test_score_definition_serde() {
         let yaml = r#"
-name: "Test Score"
-name_de: "Test-Score"
+name: {en: "Test Score", de: "Test-Score"}
 specialty: Cardiology
-specialty_de: "Kardiologie"
 version: "1.0"
 guideline_source: "Test 2024"
-reference: "Test et al."
+references: [{citation: "Test et al."}]
 validation_status: "draft"
 inputs:
   - field: "age"
     type: "number"
-    label: "Age"
-    label_de: "Alter"
+    label: {en: "Age", de: "Alter"}
     unit: "years"
     points: 1
     min: 0
     max: 120
 interpretation:
   - score: 0
-    risk: "Low"
-    risk_de: "Niedrig"
+    risk: {en: "Low", de: "Niedrig"}
     risk_level: Low
-    recommendation: "No action needed"
-    recommendation_de: "Keine Maßnahmen erforderlich"
+    recommendation: {en: "No action needed", de: "Keine Maßnahmen erforderlich"}
 "#;
 
         let score: Result<ScoreDefinition, _> = serde_yaml::from_str(yaml);
         assert!(score.is_ok());
 
         let score = score.unwrap();
-        assert_eq!(score.name, "Test Score");
+        assert_eq!(score.name.text("en"), "Test Score");
+        assert_eq!(score.name.text("fr"), "Test Score");
+        assert_eq!(score.inputs[0].unit.as_ref().unwrap().text("de"), "years");
         assert_eq!(score.specialty, Specialty::Cardiology);
         assert_eq!(score.inputs.len(), 1);
         assert_eq!(score.inputs[0].field, "age");
         assert_eq!(score.interpretation.len(), 1);
+        assert!(score.interpretation[0].risk_lay.is_none());
+        assert!(score.intended_use.is_none());
+        assert!(score.regions_validated.is_empty());
+
+        let yaml = yaml.replace(
+            "validation_status:",
+            "intended_use: \"Decision support\"\nregulatory_note: {en: \"Not CE-marked\", de: \"Nicht CE-gekennzeichnet\"}\nregions_validated: [\"EU\", \"US\"]\nvalidation_status:",
+        );
+        let score: ScoreDefinition = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(score.intended_use.unwrap().text("de"), "Decision support");
+        assert_eq!(
+            score.regulatory_note.unwrap().text("de"),
+            "Nicht CE-gekennzeichnet"
+        );
+        assert_eq!(score.regions_validated, vec!["EU", "US"]);
+    }
+
+    #[test]
+    fn test_references() {
+        let references: Vec<Reference> = serde_yaml::from_str(
+            r#"
+- authors: "Lip GY, et al."
+  title: "Refining clinical risk stratification."
+  journal: "Chest. 2010;137(2):263-272"
+  year: 2010
+  pmid: "19762550"
+- citation: "Society guideline, 2024"
+  url: "https://example.org"
+- title: "Untitled"
+  doi: "10.1234/x"
+  pmid: "1"
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            references[0].formatted(),
+            "Lip GY, et al. Refining clinical risk stratification. Chest. 2010;137(2):263-272 (2010)."
+        );
+        assert_eq!(
+            references[0].link().as_deref(),
+            Some("https://pubmed.ncbi.nlm.nih.gov/19762550/")
+        );
+        assert_eq!(references[1].formatted(), "Society guideline, 2024");
+        assert_eq!(references[1].link().as_deref(), Some("https://example.org"));
+        assert_eq!(
+            references[2].link().as_deref(),
+            Some("https://doi.org/10.1234/x")
+        );
+        assert!(Reference::default().is_empty());
+    }
+
+    #[test]
+    fn test_upgrade_legacy_references() {
+        let mut document: Value =
+            serde_yaml::from_str("reference: \"Old citation\"\nreference_url: \"https://x.org\"")
+                .unwrap();
+        assert!(upgrade_legacy_references(&mut document));
+        assert_eq!(document["references"][0]["citation"], "Old citation");
+        assert_eq!(document["references"][0]["url"], "https://x.org");
+        assert!(document.get("reference").is_none());
+        assert!(!upgrade_legacy_references(&mut document));
+
+        let mut empty: Value = serde_yaml::from_str("reference: \"\"").unwrap();
+        assert!(upgrade_legacy_references(&mut empty));
+        assert!(empty["references"].as_sequence().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_lay_interpretation_parsing() {
+        let yaml = r#"
+score: "≥2"
+risk: {en: "Moderate-High", de: "Mittel-Hoch"}
+risk_lay:
+  en: "Your risk of a stroke is increased."
+  de: "Ihr Schlaganfallrisiko ist erhöht."
+  fr: "Votre risque d'AVC est augmenté."
+risk_level: High
+recommendation: {en: "Oral anticoagulation recommended", de: "Orale Antikoagulation empfohlen"}
+"#;
+
+        let rule: InterpretationRule = serde_yaml::from_str(yaml).unwrap();
+        let lay = rule.risk_lay.unwrap();
+        assert_eq!(lay.text("en"), "Your risk of a stroke is increased.");
+        assert_eq!(lay.text("de"), "Ihr Schlaganfallrisiko ist erhöht.");
+        assert_eq!(lay.text("fr"), "Votre risque d'AVC est augmenté.");
+        assert_eq!(lay.text("it"), "Your risk of a stroke is increased.");
     }
 
     #[test]
     fn test_conditional_points_parsing() {
         let yaml = r#"
-name: "Age Test"
-name_de: "Alter Test"
+name: {en: "Age Test", de: "Alter Test"}
 specialty: Cardiology
-specialty_de: "Kardiologie"
 version: "1.0"
 guideline_source: "Test"
-reference: "Test"
+references: [{citation: "Test"}]
 validation_status: "draft"
 inputs:
   - field: "age"
     type: "number"
-    label: "Age"
-    label_de: "Alter"
+    label: {en: "Age", de: "Alter"}
     points:
       - condition: ">= 75"
         points: 2
@@ -403,11 +1424,9 @@ inputs:
         points: 1
 interpretation:
   - score: 0
-    risk: "Low"
-    risk_de: "Niedrig"
+    risk: {en: "Low", de: "Niedrig"}
     risk_level: Low
-    recommendation: "Test"
-    recommendation_de: "Test"
+    recommendation: {en: "Test", de: "Test"}
 "#;
 
         let score: Result<ScoreDefinition, _> = serde_yaml::from_str(yaml);
@@ -425,39 +1444,92 @@ interpretation:
         }
     }
 
+    #[test]
+    fn test_sections_grouping() {
+        let yaml = r#"
+name: {en: "Section Test", de: "Abschnitt-Test"}
+specialty: Anesthesiology
+version: "1.0"
+guideline_source: "Test"
+references: [{citation: "Test"}]
+validation_status: "draft"
+inputs:
+  - field: "age"
+    type: "number"
+    label: {en: "Age", de: "Alter"}
+    points: 0
+  - field: "minor_surgery"
+    type: "boolean"
+    label: {en: "Minor surgery", de: "Kleine Operation"}
+    points: 1
+  - field: "stroke"
+    type: "boolean"
+    label: {en: "Stroke", de: "Schlaganfall"}
+    points: 5
+sections:
+  - id: "five_points"
+    label: {en: "5-point factors", de: "Faktoren mit 5 Punkten"}
+    fields: ["stroke"]
+  - id: "one_point"
+    label: {en: "1-point factors", de: "Faktoren mit 1 Punkt"}
+    fields: ["minor_surgery"]
+interpretation:
+  - score: 0
+    risk: {en: "Low", de: "Niedrig"}
+    risk_level: Low
+    recommendation: {en: "Test", de: "Test"}
+"#;
+
+        let score: ScoreDefinition = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(score.sections.len(), 2);
+
+        let groups = score.grouped_inputs();
+        assert_eq!(groups.len(), 3);
+
+        // Ungrouped fields first, then sections in declared order
+        assert!(groups[0].0.is_none());
+        assert_eq!(groups[0].1[0].field, "age");
+        assert_eq!(groups[1].0.unwrap().id, "five_points");
+        assert_eq!(groups[1].1[0].field, "stroke");
+        assert_eq!(
+            groups[2].0.unwrap().label.text("de"),
+            "Faktoren mit 1 Punkt"
+        );
+        assert_eq!(groups[2].1[0].field, "minor_surgery");
+
+        // Three inputs are too few for the wizard setting, but `input_mode` always wins
+        assert!(!score.uses_wizard(true));
+        let score: ScoreDefinition =
+            serde_yaml::from_str(&format!("{}input_mode: wizard\n", yaml)).unwrap();
+        assert!(score.uses_wizard(false));
+    }
+
     #[test]
     fn test_dropdown_options_parsing() {
         let yaml = r#"
-name: "Dropdown Test"
-name_de: "Dropdown Test"
+name: {en: "Dropdown Test", de: "Dropdown Test"}
 specialty: Cardiology
-specialty_de: "Kardiologie"
 version: "1.0"
 guideline_source: "Test"
-reference: "Test"
+references: [{citation: "Test"}]
 validation_status: "draft"
 inputs:
   - field: "severity"
     type: "dropdown"
-    label: "Severity"
-    label_de: "Schweregrad"
+    label: {en: "Severity", de: "Schweregrad"}
     points: 0
     options:
       - value: "mild"
-        label: "Mild"
-        label_de: "Leicht"
+        label: {en: "Mild", de: "Leicht"}
         points: 0
       - value: "severe"
-        label: "Severe"
-        label_de: "Schwer"
+        label: {en: "Severe", de: "Schwer"}
         points: 2
 interpretation:
   - score: 0
-    risk: "Low"
-    risk_de: "Niedrig"
+    risk: {en: "Low", de: "Niedrig"}
     risk_level: Low
-    recommendation: "Test"
-    recommendation_de: "Test"
+    recommendation: {en: "Test", de: "Test"}
 "#;
 
         let score: Result<ScoreDefinition, _> = serde_yaml::from_str(yaml);
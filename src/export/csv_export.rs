@@ -3,6 +3,7 @@
 
 use super::ExportRecord;
 use std::io::Write;
+use std::path::Path;
 
 /// Export a single calculation result to CSV string
 pub fn export_to_csv(record: &ExportRecord) -> Result<String, String> {
@@ -15,6 +16,10 @@ pub fn export_to_csv(record: &ExportRecord) -> Result<String, String> {
     // Write main result
     wtr.write_record(["Score", &record.score_name])
         .map_err(|e| e.to_string())?;
+    if let Some(case_id) = &record.case_id {
+        wtr.write_record(["Patient", case_id])
+            .map_err(|e| e.to_string())?;
+    }
     wtr.write_record(["Total Score", &record.total_score.to_string()])
         .map_err(|e| e.to_string())?;
     wtr.write_record(["Risk", &record.risk])
@@ -25,8 +30,22 @@ pub fn export_to_csv(record: &ExportRecord) -> Result<String, String> {
         wtr.write_record(["Details", &record.details])
             .map_err(|e| e.to_string())?;
     }
-    wtr.write_record(["Timestamp", &record.timestamp])
-        .map_err(|e| e.to_string())?;
+    if !record.timestamp.is_empty() {
+        wtr.write_record(["Timestamp", &record.timestamp])
+            .map_err(|e| e.to_string())?;
+    }
+    if let Some(version) = &record.score_version {
+        wtr.write_record(["Score Version", version])
+            .map_err(|e| e.to_string())?;
+    }
+    if let Some(warning) = &record.version_warning {
+        wtr.write_record(["Warning", warning])
+            .map_err(|e| e.to_string())?;
+    }
+    if let Some(provenance) = &record.provenance {
+        wtr.write_record(["Provenance", provenance])
+            .map_err(|e| e.to_string())?;
+    }
 
     // Write field breakdown
     wtr.write_record(["", ""]).map_err(|e| e.to_string())?;
@@ -43,13 +62,69 @@ pub fn export_to_csv(record: &ExportRecord) -> Result<String, String> {
 }
 
 /// Export a single result to CSV and write to file
-pub fn export_to_csv_file(record: &ExportRecord, path: &str) -> Result<(), String> {
+pub fn export_to_csv_file(record: &ExportRecord, path: &Path) -> Result<(), String> {
     let csv = export_to_csv(record)?;
     let mut file = std::fs::File::create(path).map_err(|e| e.to_string())?;
     file.write_all(csv.as_bytes()).map_err(|e| e.to_string())?;
     Ok(())
 }
 
+/// Export several results (e.g., the calculation history) as a table with one row per result
+///
+/// The points breakdown is written to a single column as "Label: +1; Label: +2".
+pub fn export_records_to_csv(records: &[ExportRecord]) -> Result<String, String> {
+    let mut wtr = csv::Writer::from_writer(Vec::new());
+
+    wtr.write_record([
+        "Timestamp",
+        "Score",
+        "Patient",
+        "Total Score",
+        "Risk",
+        "Recommendation",
+        "Details",
+        "Breakdown",
+        "Score Version",
+        "Warning",
+        "Provenance",
+    ])
+    .map_err(|e| e.to_string())?;
+
+    for record in records {
+        let breakdown = record
+            .field_breakdown
+            .iter()
+            .map(|field| format!("{}: {:+}", field.label, field.points))
+            .collect::<Vec<_>>()
+            .join("; ");
+        wtr.write_record([
+            record.timestamp.as_str(),
+            &record.score_name,
+            record.case_id.as_deref().unwrap_or_default(),
+            &record.total_score.to_string(),
+            &record.risk,
+            &record.recommendation,
+            &record.details,
+            &breakdown,
+            record.score_version.as_deref().unwrap_or_default(),
+            record.version_warning.as_deref().unwrap_or_default(),
+            record.provenance.as_deref().unwrap_or_default(),
+        ])
+        .map_err(|e| e.to_string())?;
+    }
+
+    let bytes = wtr.into_inner().map_err(|e| e.to_string())?;
+    String::from_utf8(bytes).map_err(|e| e.to_string())
+}
+
+/// Export several results to CSV and write to file
+pub fn export_records_to_csv_file(records: &[ExportRecord], path: &Path) -> Result<(), String> {
+    let csv = export_records_to_csv(records)?;
+    let mut file = std::fs::File::create(path).map_err(|e| e.to_string())?;
+    file.write_all(csv.as_bytes()).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -81,12 +156,94 @@ mod tests {
                 },
             ],
             timestamp: "2026-02-12 10:00:00".to_string(),
+            case_id: Some("Bett 12".to_string()),
+            notes: None,
+            patient_copy: false,
+            score_version: None,
+            version_warning: None,
+            references: Vec::new(),
+            provenance: None,
         };
 
         let csv = export_to_csv(&record).unwrap();
         assert!(csv.contains("CHA2DS2-VA Score"));
         assert!(csv.contains("High Risk"));
         assert!(csv.contains("Hypertension"));
+        assert!(csv.contains("Patient,Bett 12"));
         assert!(csv.contains("3"));
+        assert!(!csv.contains("Score Version"));
+    }
+
+    #[test]
+    fn test_csv_export_with_version_warning() {
+        let record = ExportRecord {
+            score_name: "HAS-BLED Score".to_string(),
+            total_score: 2,
+            risk: "Moderate".to_string(),
+            recommendation: String::new(),
+            details: String::new(),
+            field_breakdown: vec![],
+            timestamp: "2026-02-12 10:00".to_string(),
+            case_id: None,
+            notes: None,
+            patient_copy: false,
+            score_version: Some("2023-v1".to_string()),
+            version_warning: Some(
+                "Calculated with score version 2023-v1; version 2024-v1 is now loaded.".to_string(),
+            ),
+            references: Vec::new(),
+            provenance: Some("9f2c".to_string()),
+        };
+
+        let csv = export_to_csv(&record).unwrap();
+        assert!(!csv.contains("Patient,"));
+        assert!(csv.contains("Score Version,2023-v1"));
+        assert!(csv.contains("Warning,"));
+        assert!(csv.contains("Provenance,9f2c"));
+    }
+
+    #[test]
+    fn test_csv_export_records() {
+        let record = |score_name: &str, case_id: Option<&str>| ExportRecord {
+            score_name: score_name.to_string(),
+            total_score: 2,
+            risk: "Moderate".to_string(),
+            recommendation: "Review, then decide".to_string(),
+            details: String::new(),
+            field_breakdown: vec![
+                ExportFieldEntry {
+                    field: "age".to_string(),
+                    label: "Age 65-74".to_string(),
+                    points: 1,
+                },
+                ExportFieldEntry {
+                    field: "hypertension".to_string(),
+                    label: "Hypertension".to_string(),
+                    points: 1,
+                },
+            ],
+            timestamp: "2026-02-12 10:00".to_string(),
+            case_id: case_id.map(str::to_string),
+            notes: None,
+            patient_copy: false,
+            score_version: Some("2024-v1".to_string()),
+            version_warning: None,
+            references: Vec::new(),
+            provenance: None,
+        };
+
+        let csv = export_records_to_csv(&[
+            record("HAS-BLED Score", Some("Bett 12")),
+            record("CHA2DS2-VA Score", None),
+        ])
+        .unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("Timestamp,Score,Patient,Total Score"));
+        assert_eq!(
+            lines[1],
+            "2026-02-12 10:00,HAS-BLED Score,Bett 12,2,Moderate,\"Review, then decide\",,Age 65-74: +1; Hypertension: +1,2024-v1,,"
+        );
+        assert!(lines[2].starts_with("2026-02-12 10:00,CHA2DS2-VA Score,,2,"));
     }
 }
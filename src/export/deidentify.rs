@@ -0,0 +1,390 @@
+// deidentify.rs
+// De-identified (research-safe) export of calculation records, and anonymization
+// of every other export
+
+use super::ExportRecord;
+use chrono::{NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::io::Write;
+use std::path::Path;
+
+/// How much of a timestamp survives de-identification
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimestampPrecision {
+    /// Keep the calendar date only (e.g., "2026-02-12")
+    #[default]
+    Date,
+    /// Keep year and month (e.g., "2026-02")
+    Month,
+    /// Keep the year only (e.g., "2026")
+    Year,
+    /// Remove timestamps entirely
+    Omit,
+}
+
+pub const ALL_TIMESTAMP_PRECISIONS: [TimestampPrecision; 4] = [
+    TimestampPrecision::Date,
+    TimestampPrecision::Month,
+    TimestampPrecision::Year,
+    TimestampPrecision::Omit,
+];
+
+impl TimestampPrecision {
+    pub fn all() -> &'static [TimestampPrecision] {
+        &ALL_TIMESTAMP_PRECISIONS
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            TimestampPrecision::Date => "Date only",
+            TimestampPrecision::Month => "Month",
+            TimestampPrecision::Year => "Year",
+            TimestampPrecision::Omit => "No timestamps",
+        }
+    }
+
+    /// Reduce a timestamp to this precision.
+    ///
+    /// Accepts the formats written by KlinScore ("%Y-%m-%d %H:%M:%S", "%Y-%m-%d %H:%M")
+    /// as well as bare dates. Anything that cannot be parsed is dropped rather than
+    /// passed through, so unrecognized formats can never leak a time of day.
+    pub fn apply(self, timestamp: &str) -> Option<String> {
+        let format = match self {
+            TimestampPrecision::Date => "%Y-%m-%d",
+            TimestampPrecision::Month => "%Y-%m",
+            TimestampPrecision::Year => "%Y",
+            TimestampPrecision::Omit => return None,
+        };
+
+        let timestamp = timestamp.trim();
+        let date = NaiveDateTime::parse_from_str(timestamp, "%Y-%m-%d %H:%M:%S")
+            .or_else(|_| NaiveDateTime::parse_from_str(timestamp, "%Y-%m-%d %H:%M"))
+            .map(|dt| dt.date())
+            .or_else(|_| NaiveDate::parse_from_str(timestamp, "%Y-%m-%d"))
+            .ok()?;
+
+        Some(date.format(format).to_string())
+    }
+}
+
+impl fmt::Display for TimestampPrecision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.label())
+    }
+}
+
+/// Rules applied when producing a research-safe export
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeidentificationPolicy {
+    /// Remove case identifiers (bed numbers, case numbers, pseudonyms)
+    pub strip_case_ids: bool,
+    /// Remove free-text notes
+    pub strip_notes: bool,
+    /// Precision to which timestamps are truncated
+    pub timestamp_precision: TimestampPrecision,
+}
+
+impl Default for DeidentificationPolicy {
+    fn default() -> Self {
+        Self {
+            strip_case_ids: true,
+            strip_notes: true,
+            timestamp_precision: TimestampPrecision::Date,
+        }
+    }
+}
+
+/// Fields removed from every export (PDF, CSV, JSON, Markdown, FHIR, templates,
+/// print and clipboard) while switched on, e.g. for teaching material
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExportAnonymization {
+    /// Remove the time of calculation
+    pub strip_timestamps: bool,
+    /// Remove patient labels and session titles
+    pub strip_patient_labels: bool,
+    /// Remove free-text notes
+    pub strip_notes: bool,
+}
+
+impl ExportAnonymization {
+    /// Whether any field is removed
+    pub fn is_active(&self) -> bool {
+        self.strip_timestamps || self.strip_patient_labels || self.strip_notes
+    }
+
+    /// Remove the switched-on fields from a record
+    pub fn apply(&self, record: &mut ExportRecord) {
+        if self.strip_timestamps {
+            record.timestamp.clear();
+        }
+        if self.strip_patient_labels {
+            record.case_id = None;
+        }
+        if self.strip_notes {
+            record.notes = None;
+        }
+    }
+}
+
+/// A single calculation with identifying fields removed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeidentifiedRecord {
+    pub score_name: String,
+    pub total_score: i32,
+    pub risk: String,
+    pub recommendation: String,
+    pub details: String,
+    pub field_breakdown: Vec<super::ExportFieldEntry>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub case_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub score_version: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version_warning: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provenance: Option<String>,
+}
+
+/// Metadata describing how a de-identified dataset was produced
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeidentificationMetadata {
+    pub generator: String,
+    /// Export date, truncated with the same policy as the records
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exported: Option<String>,
+    pub record_count: usize,
+    pub policy: DeidentificationPolicy,
+    /// Names of the record fields removed or truncated by the policy
+    pub removed_fields: Vec<String>,
+}
+
+/// A complete de-identified dataset: policy metadata plus records
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeidentifiedDataset {
+    pub metadata: DeidentificationMetadata,
+    pub records: Vec<DeidentifiedRecord>,
+}
+
+impl DeidentifiedRecord {
+    pub fn from_record(record: &ExportRecord, policy: &DeidentificationPolicy) -> Self {
+        Self {
+            score_name: record.score_name.clone(),
+            total_score: record.total_score,
+            risk: record.risk.clone(),
+            recommendation: record.recommendation.clone(),
+            details: record.details.clone(),
+            field_breakdown: record.field_breakdown.clone(),
+            date: policy.timestamp_precision.apply(&record.timestamp),
+            case_id: if policy.strip_case_ids {
+                None
+            } else {
+                record.case_id.clone()
+            },
+            notes: if policy.strip_notes {
+                None
+            } else {
+                record.notes.clone()
+            },
+            score_version: record.score_version.clone(),
+            version_warning: record.version_warning.clone(),
+            provenance: record.provenance.clone(),
+        }
+    }
+}
+
+/// Apply a de-identification policy to a set of records
+pub fn deidentify(
+    records: &[ExportRecord],
+    policy: &DeidentificationPolicy,
+    exported_at: &str,
+) -> DeidentifiedDataset {
+    let mut removed_fields = Vec::new();
+    if policy.strip_case_ids {
+        removed_fields.push("case_id".to_string());
+    }
+    if policy.strip_notes {
+        removed_fields.push("notes".to_string());
+    }
+    removed_fields.push(match policy.timestamp_precision {
+        TimestampPrecision::Omit => "timestamp".to_string(),
+        _ => "timestamp (time of day)".to_string(),
+    });
+
+    DeidentifiedDataset {
+        metadata: DeidentificationMetadata {
+            generator: format!("KlinScore {}", env!("CARGO_PKG_VERSION")),
+            exported: policy.timestamp_precision.apply(exported_at),
+            record_count: records.len(),
+            policy: *policy,
+            removed_fields,
+        },
+        records: records
+            .iter()
+            .map(|r| DeidentifiedRecord::from_record(r, policy))
+            .collect(),
+    }
+}
+
+/// Export a de-identified dataset to JSON string
+pub fn export_deidentified_json(
+    records: &[ExportRecord],
+    policy: &DeidentificationPolicy,
+    exported_at: &str,
+) -> Result<String, String> {
+    let dataset = deidentify(records, policy, exported_at);
+    serde_json::to_string_pretty(&dataset).map_err(|e| e.to_string())
+}
+
+/// Export a de-identified dataset to JSON file
+pub fn export_deidentified_json_file(
+    records: &[ExportRecord],
+    policy: &DeidentificationPolicy,
+    exported_at: &str,
+    path: &Path,
+) -> Result<(), String> {
+    let json = export_deidentified_json(records, policy, exported_at)?;
+    let mut file = std::fs::File::create(path).map_err(|e| e.to_string())?;
+    file.write_all(json.as_bytes()).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_record() -> ExportRecord {
+        ExportRecord {
+            score_name: "HAS-BLED Score".to_string(),
+            total_score: 3,
+            risk: "High".to_string(),
+            recommendation: "Address modifiable bleeding risk factors".to_string(),
+            details: String::new(),
+            field_breakdown: vec![],
+            timestamp: "2026-02-12 10:42:17".to_string(),
+            case_id: Some("Bed 12".to_string()),
+            notes: Some("Patient Müller, follow-up Friday".to_string()),
+            patient_copy: false,
+            score_version: None,
+            version_warning: None,
+            references: Vec::new(),
+            provenance: None,
+        }
+    }
+
+    #[test]
+    fn test_timestamp_precision() {
+        let ts = "2026-02-12 10:42:17";
+        assert_eq!(
+            TimestampPrecision::Date.apply(ts),
+            Some("2026-02-12".to_string())
+        );
+        assert_eq!(
+            TimestampPrecision::Month.apply(ts),
+            Some("2026-02".to_string())
+        );
+        assert_eq!(TimestampPrecision::Year.apply(ts), Some("2026".to_string()));
+        assert_eq!(TimestampPrecision::Omit.apply(ts), None);
+
+        // History entries are stored without seconds
+        assert_eq!(
+            TimestampPrecision::Date.apply("2026-02-12 10:42"),
+            Some("2026-02-12".to_string())
+        );
+
+        // Unparseable timestamps are dropped, never passed through
+        assert_eq!(TimestampPrecision::Date.apply("12.02.2026 10:42"), None);
+    }
+
+    #[test]
+    fn test_default_policy_strips_identifiers() {
+        let dataset = deidentify(
+            &[sample_record()],
+            &DeidentificationPolicy::default(),
+            "2026-03-01 08:00:00",
+        );
+
+        assert_eq!(dataset.metadata.record_count, 1);
+        assert_eq!(dataset.metadata.exported.as_deref(), Some("2026-03-01"));
+        assert!(dataset
+            .metadata
+            .removed_fields
+            .contains(&"case_id".to_string()));
+        assert!(dataset
+            .metadata
+            .removed_fields
+            .contains(&"notes".to_string()));
+
+        let record = &dataset.records[0];
+        assert_eq!(record.date.as_deref(), Some("2026-02-12"));
+        assert!(record.case_id.is_none());
+        assert!(record.notes.is_none());
+        assert_eq!(record.total_score, 3);
+    }
+
+    #[test]
+    fn test_deidentified_json_contains_no_identifiers() {
+        let json = export_deidentified_json(
+            &[sample_record()],
+            &DeidentificationPolicy::default(),
+            "2026-03-01 08:00:00",
+        )
+        .unwrap();
+
+        assert!(!json.contains("Bed 12"));
+        assert!(!json.contains("Müller"));
+        assert!(!json.contains("10:42"));
+
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed["metadata"]["policy"]["timestamp_precision"], "date");
+        assert_eq!(parsed["metadata"]["policy"]["strip_case_ids"], true);
+    }
+
+    #[test]
+    fn test_export_anonymization() {
+        let mut record = sample_record();
+        ExportAnonymization::default().apply(&mut record);
+        assert_eq!(record.case_id.as_deref(), Some("Bed 12"));
+        assert!(!ExportAnonymization::default().is_active());
+
+        let anonymization = ExportAnonymization {
+            strip_timestamps: true,
+            strip_patient_labels: true,
+            strip_notes: true,
+        };
+        anonymization.apply(&mut record);
+        let json = crate::export::json_export::export_to_json(&record).unwrap();
+        assert!(!json.contains("timestamp"));
+        assert!(!json.contains("Bed 12"));
+        assert!(!json.contains("Müller"));
+
+        let summary = crate::export::text_export::summary_text(&record, false);
+        assert!(summary.ends_with("\nKlinScore\n"), "{summary}");
+        let markdown = crate::export::markdown_export::export_to_markdown(&record, &[], false);
+        assert!(!markdown.contains("Calculated"));
+    }
+
+    #[test]
+    fn test_permissive_policy_keeps_case_id() {
+        let policy = DeidentificationPolicy {
+            strip_case_ids: false,
+            strip_notes: true,
+            timestamp_precision: TimestampPrecision::Omit,
+        };
+        let dataset = deidentify(&[sample_record()], &policy, "2026-03-01 08:00:00");
+
+        assert_eq!(dataset.records[0].case_id.as_deref(), Some("Bed 12"));
+        assert!(dataset.records[0].date.is_none());
+        assert!(dataset.metadata.exported.is_none());
+        assert!(dataset
+            .metadata
+            .removed_fields
+            .contains(&"timestamp".to_string()));
+    }
+}
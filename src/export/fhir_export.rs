@@ -0,0 +1,217 @@
+// fhir_export.rs
+// Export a calculation result as a FHIR R4 Observation, for EHRs with a FHIR interface
+
+use super::print::PrintLabels;
+use super::{language_code, ExportRecord};
+use crate::config::ScoreDefinition;
+use crate::scores::CalculationResult;
+use chrono::{Local, NaiveDateTime, SecondsFormat, TimeZone};
+use serde_json::{json, Map, Value};
+use std::path::Path;
+
+/// Code system of Observation categories
+const OBSERVATION_CATEGORY: &str = "http://terminology.hl7.org/CodeSystem/observation-category";
+
+/// Code system of UCUM units
+const UCUM: &str = "http://unitsofmeasure.org";
+
+/// Identifier system of KlinScore's reproducibility hash of a calculation
+const PROVENANCE_SYSTEM: &str = "urn:klinscore:provenance";
+
+/// Build a FHIR R4 Observation of a result
+///
+/// The Observation is coded with the LOINC and SNOMED CT codes of the score's
+/// metadata and has one component per input: its points for point scores, the
+/// entered value for formula scores. The provenance hash of the calculation is
+/// its identifier. It has no subject; the receiving system links it to the patient.
+pub fn fhir_observation(
+    score: &ScoreDefinition,
+    result: &CalculationResult,
+    record: &ExportRecord,
+    use_german: bool,
+) -> Value {
+    let language = language_code(use_german);
+    let mut observation = Map::new();
+    observation.insert("resourceType".into(), "Observation".into());
+    if let Some(provenance) = &record.provenance {
+        observation.insert(
+            "identifier".into(),
+            json!([{ "system": PROVENANCE_SYSTEM, "value": provenance }]),
+        );
+    }
+    observation.insert("status".into(), "final".into());
+    observation.insert(
+        "category".into(),
+        json!([{
+            "coding": [{
+                "system": OBSERVATION_CATEGORY,
+                "code": "survey",
+                "display": "Survey"
+            }]
+        }]),
+    );
+
+    let mut code = Map::new();
+    if !score.metadata.codes.is_empty() {
+        let codings: Vec<Value> = score
+            .metadata
+            .codes
+            .iter()
+            .map(|score_code| {
+                let mut coding = json!({
+                    "system": score_code.system.uri(),
+                    "code": score_code.code,
+                });
+                if let Some(display) = &score_code.display {
+                    coding["display"] = display.as_str().into();
+                }
+                coding
+            })
+            .collect();
+        code.insert("coding".into(), codings.into());
+    }
+    code.insert("text".into(), record.score_name.as_str().into());
+    observation.insert("code".into(), code.into());
+
+    if let Some(effective) = fhir_date_time(&record.timestamp) {
+        observation.insert("effectiveDateTime".into(), effective.into());
+    }
+
+    match &score.metadata.result_unit {
+        Some(unit) => observation.insert(
+            "valueQuantity".into(),
+            json!({
+                "value": record.total_score,
+                "unit": unit,
+                "system": UCUM,
+                "code": unit
+            }),
+        ),
+        None => observation.insert("valueInteger".into(), record.total_score.into()),
+    };
+
+    observation.insert("interpretation".into(), json!([{ "text": record.risk }]));
+
+    let notes: Vec<Value> = [&record.recommendation, &record.details]
+        .into_iter()
+        .chain(record.notes.as_ref())
+        .filter(|note| !note.is_empty())
+        .map(|note| json!({ "text": note }))
+        .collect();
+    if !notes.is_empty() {
+        observation.insert("note".into(), notes.into());
+    }
+
+    if let Some(version) = &record.score_version {
+        let labels = PrintLabels::for_language(use_german);
+        observation.insert(
+            "method".into(),
+            json!({ "text": format!("KlinScore, {} {}", labels.definition, version) }),
+        );
+    }
+
+    let components: Vec<Value> = result
+        .field_scores
+        .iter()
+        .filter_map(|fs| {
+            // Formula results list the computed value as well, which is not an input
+            let input = score.inputs.iter().find(|input| input.field == fs.field)?;
+            let mut component = json!({ "code": { "text": input.label.text(language) } });
+            if score.formula.is_some() {
+                // Formula breakdowns describe the entered value in the label
+                let value = fs.selection.as_ref().unwrap_or(&fs.label);
+                component["valueString"] = value.text(language).into();
+            } else {
+                component["valueInteger"] = fs.points.into();
+            }
+            Some(component)
+        })
+        .collect();
+    if !components.is_empty() {
+        observation.insert("component".into(), components.into());
+    }
+
+    Value::Object(observation)
+}
+
+/// Export an Observation to a JSON file
+pub fn export_to_fhir_file(observation: &Value, path: &Path) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(observation).map_err(|e| e.to_string())?;
+    std::fs::write(path, json).map_err(|e| e.to_string())
+}
+
+/// Export timestamp (local time) as a FHIR dateTime with time zone offset
+fn fhir_date_time(timestamp: &str) -> Option<String> {
+    let naive = NaiveDateTime::parse_from_str(timestamp, "%Y-%m-%d %H:%M:%S").ok()?;
+    let local = Local.from_local_datetime(&naive).earliest()?;
+    Some(local.to_rfc3339_opts(SecondsFormat::Secs, false))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scores::{calculate_score, load_score_from_file, InputValue};
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_fhir_observation() {
+        let score = load_score_from_file("scores/nephrology/egfr_ckd_epi_2021.yaml").unwrap();
+        let inputs = HashMap::from([
+            ("age".to_string(), InputValue::Number(60.0)),
+            (
+                "sex".to_string(),
+                InputValue::Dropdown("female".to_string()),
+            ),
+            ("creatinine".to_string(), InputValue::Number(80.0)),
+        ]);
+        let result = calculate_score(&score, &inputs).unwrap();
+        let mut record = ExportRecord::from_result(&result, score.name.text("en"), false);
+        record.timestamp = "2026-02-12 10:00:00".to_string();
+        record.score_version = Some(score.version.clone());
+
+        let observation = fhir_observation(&score, &result, &record, false);
+        assert_eq!(observation["resourceType"], "Observation");
+        assert_eq!(observation["identifier"][0]["value"], result.provenance);
+        assert_eq!(observation["status"], "final");
+        assert_eq!(
+            observation["code"]["coding"][0]["system"],
+            "http://loinc.org"
+        );
+        assert_eq!(observation["code"]["coding"][0]["code"], "98979-8");
+        assert_eq!(
+            observation["valueQuantity"]["value"],
+            json!(result.total_score)
+        );
+        assert_eq!(observation["valueQuantity"]["code"], "mL/min/{1.73_m2}");
+        assert!(observation.get("valueInteger").is_none());
+        assert!(observation["effectiveDateTime"]
+            .as_str()
+            .unwrap()
+            .starts_with("2026-02-12T10:00:00"));
+        assert_eq!(observation["interpretation"][0]["text"], record.risk);
+        let components = observation["component"].as_array().unwrap();
+        assert_eq!(components.len(), 3);
+        assert_eq!(components[0]["code"]["text"], "Age");
+        assert_eq!(components[0]["valueString"], "Age: 60 years");
+    }
+
+    #[test]
+    fn test_point_score_components() {
+        let score = load_score_from_file("scores/anesthesiology/stop_bang.yaml").unwrap();
+        let inputs: HashMap<String, InputValue> = score
+            .inputs
+            .iter()
+            .map(|input| (input.field.clone(), InputValue::Boolean(true)))
+            .collect();
+        let result = calculate_score(&score, &inputs).unwrap();
+        let record = ExportRecord::from_result(&result, score.name.text("de"), true);
+
+        let observation = fhir_observation(&score, &result, &record, true);
+        assert!(observation["code"].get("coding").is_none());
+        assert_eq!(observation["code"]["text"], record.score_name);
+        assert_eq!(observation["valueInteger"], json!(result.total_score));
+        let components = observation["component"].as_array().unwrap();
+        assert_eq!(components.len(), score.inputs.len());
+        assert_eq!(components[0]["valueInteger"], 1);
+    }
+}
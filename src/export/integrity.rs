@@ -0,0 +1,382 @@
+// integrity.rs
+// Tamper-evident exports: a SHA-256 of the content, optionally signed with a local
+// ed25519 key, appended to PDF and JSON exports, and its verification
+//
+// PDF files get the seal as comment lines after the end of the document, covering
+// every byte before them. JSON exports get an `integrity` member, covering the rest
+// of the document in compact form with sorted keys, so reformatting the file does
+// not break the seal but changing a value does.
+
+use crate::scores::pack::{decode_hex, encode_hex, sha256_hex, verify_signature};
+use ed25519_dalek::{Signer, SigningKey};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Member of sealed JSON exports holding the seal
+pub const JSON_SEAL_KEY: &str = "integrity";
+
+/// Seal lines of a PDF file, the first starting a line of its own
+const PDF_HASH_PREFIX: &str = "%KlinScore-SHA256: ";
+const PDF_KEY_PREFIX: &str = "%KlinScore-Key: ";
+const PDF_SIGNATURE_PREFIX: &str = "%KlinScore-Signature: ";
+
+/// File of the local signing key in the data folder
+const SIGNING_KEY_FILE: &str = "export_signing.key";
+
+#[derive(Debug, Error)]
+pub enum IntegrityError {
+    #[error("Failed to read {path}: {source}")]
+    Read {
+        path: String,
+        source: std::io::Error,
+    },
+
+    #[error("Failed to write {path}: {source}")]
+    Write {
+        path: String,
+        source: std::io::Error,
+    },
+
+    #[error("Invalid signing key in {0}")]
+    InvalidKey(String),
+
+    #[error("Not a PDF file or JSON object")]
+    Unsupported,
+
+    #[error("Invalid seal: {0}")]
+    InvalidSeal(String),
+}
+
+/// SHA-256 of an export and, if signed, the signature over the hash (hex)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Seal {
+    pub sha256: String,
+    /// ed25519 public key of the signature
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub public_key: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+}
+
+/// Outcome of verifying an export
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verification {
+    /// Unchanged since export; with the public key of its valid signature, if signed
+    Intact { signed_by: Option<String> },
+    /// The content no longer matches its hash
+    Altered,
+    /// The content matches its hash, but the signature is not valid for it
+    InvalidSignature,
+    /// The file has no seal
+    Unsealed,
+}
+
+/// Local ed25519 key signing exports
+pub struct ExportSigner {
+    key: SigningKey,
+}
+
+impl std::fmt::Debug for ExportSigner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ExportSigner({})", self.public_key())
+    }
+}
+
+impl ExportSigner {
+    /// The key stored at `path`, or a new one saved there
+    pub fn load_or_create(path: &Path) -> Result<Self, IntegrityError> {
+        let display = path.display().to_string();
+        if path.exists() {
+            let text = fs::read_to_string(path).map_err(|source| IntegrityError::Read {
+                path: display.clone(),
+                source,
+            })?;
+            let secret = decode_hex(text.trim())
+                .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+                .ok_or(IntegrityError::InvalidKey(display))?;
+            return Ok(Self {
+                key: SigningKey::from_bytes(&secret),
+            });
+        }
+
+        let mut secret = [0u8; 32];
+        rand::rngs::OsRng.fill_bytes(&mut secret);
+        let write_error = |source| IntegrityError::Write {
+            path: display.clone(),
+            source,
+        };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(write_error)?;
+        }
+        // Whoever can read the key can sign exports in this workstation's name
+        crate::paths::write_private(path, encode_hex(&secret).as_bytes()).map_err(write_error)?;
+        Ok(Self {
+            key: SigningKey::from_bytes(&secret),
+        })
+    }
+
+    /// Public key (hex) to pass on to those verifying the exports
+    pub fn public_key(&self) -> String {
+        encode_hex(self.key.verifying_key().as_bytes())
+    }
+
+    /// Seal of content with the hash `sha256`
+    fn seal(&self, sha256: String) -> Seal {
+        let signature = encode_hex(&self.key.sign(sha256.as_bytes()).to_bytes());
+        Seal {
+            sha256,
+            public_key: Some(self.public_key()),
+            signature: Some(signature),
+        }
+    }
+}
+
+/// Location of the local signing key
+/// (e.g., `~/.local/share/klinscore/export_signing.key` on Linux)
+pub fn signing_key_path() -> Option<PathBuf> {
+    Some(crate::paths::data_dir()?.join(SIGNING_KEY_FILE))
+}
+
+/// Seal an export that was just written to `path`, signing it if a signer is given
+pub fn seal_file(path: &Path, signer: Option<&ExportSigner>) -> Result<Seal, IntegrityError> {
+    let bytes = fs::read(path).map_err(|source| IntegrityError::Read {
+        path: path.display().to_string(),
+        source,
+    })?;
+    let (sealed, seal) = seal_bytes(&bytes, signer)?;
+    fs::write(path, sealed).map_err(|source| IntegrityError::Write {
+        path: path.display().to_string(),
+        source,
+    })?;
+    Ok(seal)
+}
+
+/// Seal a PDF document or JSON object, returning the sealed bytes
+pub fn seal_bytes(
+    bytes: &[u8],
+    signer: Option<&ExportSigner>,
+) -> Result<(Vec<u8>, Seal), IntegrityError> {
+    let seal_of = |sha256| match signer {
+        Some(signer) => signer.seal(sha256),
+        None => Seal {
+            sha256,
+            public_key: None,
+            signature: None,
+        },
+    };
+
+    if bytes.starts_with(b"%PDF") {
+        let seal = seal_of(sha256_hex(bytes));
+        let mut sealed = bytes.to_vec();
+        sealed.extend_from_slice(format!("\n{PDF_HASH_PREFIX}{}\n", seal.sha256).as_bytes());
+        if let (Some(key), Some(signature)) = (&seal.public_key, &seal.signature) {
+            sealed.extend_from_slice(format!("{PDF_KEY_PREFIX}{key}\n").as_bytes());
+            sealed.extend_from_slice(format!("{PDF_SIGNATURE_PREFIX}{signature}\n").as_bytes());
+        }
+        return Ok((sealed, seal));
+    }
+
+    let text = std::str::from_utf8(bytes).map_err(|_| IntegrityError::Unsupported)?;
+    let value: Value = serde_json::from_str(text).map_err(|_| IntegrityError::Unsupported)?;
+    let object = value.as_object().ok_or(IntegrityError::Unsupported)?;
+    if object.contains_key(JSON_SEAL_KEY) {
+        return Err(IntegrityError::InvalidSeal("already sealed".to_string()));
+    }
+    let seal = seal_of(sha256_hex(&canonical_json(&value)));
+
+    // Appended as the last member, leaving the exported text as it was
+    let body = text
+        .trim_end()
+        .strip_suffix('}')
+        .ok_or(IntegrityError::Unsupported)?;
+    let body = body.trim_end();
+    let separator = if body.ends_with('{') { "" } else { "," };
+    let seal_json =
+        serde_json::to_string(&seal).map_err(|e| IntegrityError::InvalidSeal(e.to_string()))?;
+    let sealed = format!("{body}{separator}\n  \"{JSON_SEAL_KEY}\": {seal_json}\n}}\n");
+    Ok((sealed.into_bytes(), seal))
+}
+
+/// Verify the seal of the export at `path`
+pub fn verify_file(path: &Path) -> Result<Verification, IntegrityError> {
+    let bytes = fs::read(path).map_err(|source| IntegrityError::Read {
+        path: path.display().to_string(),
+        source,
+    })?;
+    verify_bytes(&bytes)
+}
+
+/// Verify the seal of a PDF document or JSON object
+pub fn verify_bytes(bytes: &[u8]) -> Result<Verification, IntegrityError> {
+    let (content_hash, seal) = if bytes.starts_with(b"%PDF") {
+        let marker = format!("\n{PDF_HASH_PREFIX}");
+        let Some(start) = bytes
+            .windows(marker.len())
+            .rposition(|window| window == marker.as_bytes())
+        else {
+            return Ok(Verification::Unsealed);
+        };
+        // Anything written after the seal changed the file as well
+        let Some(seal) = pdf_seal(&bytes[start + 1..]) else {
+            return Ok(Verification::Altered);
+        };
+        (sha256_hex(&bytes[..start]), seal)
+    } else {
+        let mut value: Value =
+            serde_json::from_slice(bytes).map_err(|_| IntegrityError::Unsupported)?;
+        let object = value.as_object_mut().ok_or(IntegrityError::Unsupported)?;
+        let Some(seal) = object.remove(JSON_SEAL_KEY) else {
+            return Ok(Verification::Unsealed);
+        };
+        let seal: Seal =
+            serde_json::from_value(seal).map_err(|e| IntegrityError::InvalidSeal(e.to_string()))?;
+        (sha256_hex(&canonical_json(&value)), seal)
+    };
+
+    if !content_hash.eq_ignore_ascii_case(&seal.sha256) {
+        return Ok(Verification::Altered);
+    }
+    match (seal.public_key, seal.signature) {
+        (None, None) => Ok(Verification::Intact { signed_by: None }),
+        (Some(key), Some(signature)) => {
+            if verify_signature(&key, &signature, seal.sha256.as_bytes()) {
+                Ok(Verification::Intact {
+                    signed_by: Some(key.to_ascii_lowercase()),
+                })
+            } else {
+                Ok(Verification::InvalidSignature)
+            }
+        }
+        _ => Ok(Verification::InvalidSignature),
+    }
+}
+
+/// Seal from the comment lines at the end of a PDF file; `None` if anything
+/// else follows them
+fn pdf_seal(lines: &[u8]) -> Option<Seal> {
+    let mut lines = std::str::from_utf8(lines)
+        .ok()?
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty());
+    let mut seal = Seal {
+        sha256: lines.next()?.strip_prefix(PDF_HASH_PREFIX)?.to_string(),
+        public_key: None,
+        signature: None,
+    };
+    for line in lines {
+        if let Some(key) = line.strip_prefix(PDF_KEY_PREFIX) {
+            seal.public_key = Some(key.to_string());
+        } else if let Some(signature) = line.strip_prefix(PDF_SIGNATURE_PREFIX) {
+            seal.signature = Some(signature.to_string());
+        } else {
+            return None;
+        }
+    }
+    Some(seal)
+}
+
+/// Compact JSON with sorted keys, the form a JSON seal covers
+fn canonical_json(value: &Value) -> Vec<u8> {
+    // Objects of `serde_json::Value` keep their keys sorted
+    serde_json::to_vec(value).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const JSON: &str = "{\n  \"score_name\": \"RCRI\",\n  \"total_score\": 1\n}";
+
+    #[test]
+    fn test_sealed_json() {
+        let (sealed, seal) = seal_bytes(JSON.as_bytes(), None).unwrap();
+        let text = String::from_utf8(sealed.clone()).unwrap();
+        assert!(text
+            .starts_with("{\n  \"score_name\": \"RCRI\",\n  \"total_score\": 1,\n  \"integrity\""));
+        assert!(seal.signature.is_none());
+        assert_eq!(
+            verify_bytes(&sealed).unwrap(),
+            Verification::Intact { signed_by: None }
+        );
+
+        // Formatting does not matter, values do
+        let reformatted: Value = serde_json::from_slice(&sealed).unwrap();
+        let compact = serde_json::to_vec(&reformatted).unwrap();
+        assert_eq!(
+            verify_bytes(&compact).unwrap(),
+            Verification::Intact { signed_by: None }
+        );
+        let altered = text.replace("\"total_score\": 1", "\"total_score\": 4");
+        assert_eq!(
+            verify_bytes(altered.as_bytes()).unwrap(),
+            Verification::Altered
+        );
+
+        assert_eq!(
+            verify_bytes(JSON.as_bytes()).unwrap(),
+            Verification::Unsealed
+        );
+        assert!(matches!(
+            seal_bytes(b"[1, 2]", None),
+            Err(IntegrityError::Unsupported)
+        ));
+    }
+
+    #[test]
+    fn test_signed_pdf() {
+        let temp_dir = TempDir::new().unwrap();
+        let key_path = temp_dir.path().join("keys").join(SIGNING_KEY_FILE);
+        let signer = ExportSigner::load_or_create(&key_path).unwrap();
+        let reloaded = ExportSigner::load_or_create(&key_path).unwrap();
+        assert_eq!(signer.public_key(), reloaded.public_key());
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&key_path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        let pdf = b"%PDF-1.3\n1 0 obj\n(Score: 5)\nendobj\n%%EOF";
+        let path = temp_dir.path().join("report.pdf");
+        fs::write(&path, pdf).unwrap();
+        let seal = seal_file(&path, Some(&signer)).unwrap();
+        assert_eq!(seal.sha256, sha256_hex(pdf));
+        let sealed = fs::read(&path).unwrap();
+        assert!(sealed.starts_with(pdf));
+        assert_eq!(
+            verify_file(&path).unwrap(),
+            Verification::Intact {
+                signed_by: Some(signer.public_key())
+            }
+        );
+
+        let altered = String::from_utf8(sealed.clone())
+            .unwrap()
+            .replace("Score: 5", "Score: 2");
+        assert_eq!(
+            verify_bytes(altered.as_bytes()).unwrap(),
+            Verification::Altered
+        );
+
+        // A signature of another key over the altered content does not pass as ours
+        let other = ExportSigner::load_or_create(&temp_dir.path().join("other.key")).unwrap();
+        let forged = String::from_utf8(sealed)
+            .unwrap()
+            .replace(&signer.public_key(), &other.public_key());
+        assert_eq!(
+            verify_bytes(forged.as_bytes()).unwrap(),
+            Verification::InvalidSignature
+        );
+
+        let mut appended = fs::read(&path).unwrap();
+        appended.extend_from_slice(b"2 0 obj\n(Score: 2)\nendobj\n");
+        assert_eq!(verify_bytes(&appended).unwrap(), Verification::Altered);
+        assert_eq!(verify_bytes(pdf).unwrap(), Verification::Unsealed);
+    }
+}
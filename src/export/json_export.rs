@@ -3,6 +3,7 @@
 
 use super::ExportRecord;
 use std::io::Write;
+use std::path::Path;
 
 /// Export a single calculation result to JSON string
 pub fn export_to_json(record: &ExportRecord) -> Result<String, String> {
@@ -10,13 +11,26 @@ pub fn export_to_json(record: &ExportRecord) -> Result<String, String> {
 }
 
 /// Export a single result to JSON file
-pub fn export_to_json_file(record: &ExportRecord, path: &str) -> Result<(), String> {
+pub fn export_to_json_file(record: &ExportRecord, path: &Path) -> Result<(), String> {
     let json = export_to_json(record)?;
     let mut file = std::fs::File::create(path).map_err(|e| e.to_string())?;
     file.write_all(json.as_bytes()).map_err(|e| e.to_string())?;
     Ok(())
 }
 
+/// Export several results (e.g., the calculation history) as a JSON array
+pub fn export_records_to_json(records: &[ExportRecord]) -> Result<String, String> {
+    serde_json::to_string_pretty(records).map_err(|e| e.to_string())
+}
+
+/// Export several results to a JSON file
+pub fn export_records_to_json_file(records: &[ExportRecord], path: &Path) -> Result<(), String> {
+    let json = export_records_to_json(records)?;
+    let mut file = std::fs::File::create(path).map_err(|e| e.to_string())?;
+    file.write_all(json.as_bytes()).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -36,6 +50,13 @@ mod tests {
                 points: 1,
             }],
             timestamp: "2026-02-12 10:00:00".to_string(),
+            case_id: None,
+            notes: None,
+            patient_copy: false,
+            score_version: None,
+            version_warning: None,
+            references: Vec::new(),
+            provenance: None,
         };
 
         let json = export_to_json(&record).unwrap();
@@ -55,11 +76,24 @@ mod tests {
             details: String::new(),
             field_breakdown: vec![],
             timestamp: "2026-02-12 10:00:00".to_string(),
+            case_id: None,
+            notes: None,
+            patient_copy: false,
+            score_version: None,
+            version_warning: None,
+            references: Vec::new(),
+            provenance: None,
         };
 
         let json = export_to_json(&record).unwrap();
         let loaded: ExportRecord = serde_json::from_str(&json).unwrap();
         assert_eq!(loaded.score_name, "Test");
         assert_eq!(loaded.total_score, 2);
+
+        // Several records as one array
+        let json = export_records_to_json(&[record.clone(), record]).unwrap();
+        let loaded: Vec<ExportRecord> = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(loaded[1].risk, "Low");
     }
 }
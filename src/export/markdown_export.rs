@@ -0,0 +1,180 @@
+// markdown_export.rs
+// Export a calculation result as Markdown, for wikis, handover documents and
+// free-text fields of the EHR
+
+use super::print::{PrintLabels, PrintedInput};
+use super::{disclaimer_text, ExportRecord};
+use std::fmt::Write;
+use std::path::Path;
+
+/// Render a result as a Markdown summary
+///
+/// Inputs and the points breakdown are tables, the disclaimer is a blockquote.
+pub fn export_to_markdown(
+    record: &ExportRecord,
+    inputs: &[PrintedInput],
+    use_german: bool,
+) -> String {
+    let labels = PrintLabels::for_language(use_german);
+    let mut md = String::new();
+
+    let _ = writeln!(md, "# {}\n", single_line(&record.score_name));
+    let meta = labels.meta(record, single_line);
+    if !meta.is_empty() {
+        let _ = writeln!(md, "{}\n", meta.join(" · "));
+    }
+    if let Some(warning) = &record.version_warning {
+        let _ = writeln!(md, "**{}**\n", single_line(warning));
+    }
+
+    // Result and interpretation
+    let _ = writeln!(
+        md,
+        "**{}: {}** · **{}: {}**\n",
+        labels.score,
+        record.total_score,
+        labels.risk,
+        single_line(&record.risk)
+    );
+    for (label, value) in [
+        (labels.recommendation, &record.recommendation),
+        (labels.details, &record.details),
+    ] {
+        if !value.is_empty() {
+            let _ = writeln!(md, "## {label}\n\n{value}\n");
+        }
+    }
+
+    // Inputs as entered
+    if !inputs.is_empty() {
+        let _ = writeln!(
+            md,
+            "## {}\n\n| {} | {} |\n| --- | --- |",
+            labels.inputs, labels.criterion, labels.value
+        );
+        for input in inputs {
+            let value = if input.value.is_empty() {
+                "–".to_string()
+            } else {
+                table_cell(&input.value)
+            };
+            let _ = writeln!(md, "| {} | {} |", table_cell(&input.label), value);
+        }
+        md.push('\n');
+    }
+
+    // Points breakdown
+    if !record.field_breakdown.is_empty() {
+        let _ = writeln!(
+            md,
+            "## {}\n\n| {} | {} |\n| --- | ---: |",
+            labels.breakdown, labels.criterion, labels.points
+        );
+        for field in &record.field_breakdown {
+            let _ = writeln!(md, "| {} | {:+} |", table_cell(&field.label), field.points);
+        }
+        let _ = writeln!(
+            md,
+            "| **{}** | **{}** |\n",
+            labels.total, record.total_score
+        );
+    }
+
+    if let Some(notes) = &record.notes {
+        let _ = writeln!(md, "## {}\n\n{}\n", labels.notes, notes);
+    }
+
+    if !record.references.is_empty() {
+        let _ = writeln!(md, "## {}\n", labels.references);
+        for (number, reference) in record.references.iter().enumerate() {
+            let _ = writeln!(md, "{}. {}", number + 1, single_line(reference));
+        }
+        md.push('\n');
+    }
+
+    let _ = writeln!(md, "> {}\n", disclaimer_text(use_german));
+    let mut footer = vec!["KlinScore".to_string()];
+    if let Some(version) = &record.score_version {
+        footer.push(format!("{} {}", labels.definition, version));
+    }
+    if let Some(provenance) = &record.provenance {
+        footer.push(format!("{} `{}`", labels.provenance, provenance));
+    }
+    let _ = writeln!(md, "*{}*", footer.join(" · "));
+    md
+}
+
+/// Export a result to a Markdown file
+pub fn export_to_markdown_file(
+    record: &ExportRecord,
+    inputs: &[PrintedInput],
+    use_german: bool,
+    path: &Path,
+) -> Result<(), String> {
+    std::fs::write(path, export_to_markdown(record, inputs, use_german)).map_err(|e| e.to_string())
+}
+
+/// Text on one line, for headings, list items and table cells
+fn single_line(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Text for a table cell, where `|` would end the cell
+fn table_cell(text: &str) -> String {
+    single_line(text).replace('|', "\\|")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::ExportFieldEntry;
+
+    #[test]
+    fn test_markdown_export() {
+        let record = ExportRecord {
+            score_name: "HAS-BLED Score".to_string(),
+            total_score: 2,
+            risk: "Moderate".to_string(),
+            recommendation: "Review bleeding risk".to_string(),
+            details: String::new(),
+            field_breakdown: vec![ExportFieldEntry {
+                field: "labile_inr".to_string(),
+                label: "Labile INR | TTR <60%".to_string(),
+                points: 1,
+            }],
+            timestamp: "2026-02-12 10:00:00".to_string(),
+            case_id: Some("Bed 4".to_string()),
+            notes: None,
+            patient_copy: false,
+            score_version: Some("2024-v1".to_string()),
+            version_warning: None,
+            references: vec!["Pisters R, et al.\nChest. 2010".to_string()],
+            provenance: Some("9f2c".to_string()),
+        };
+        let inputs = vec![
+            PrintedInput {
+                label: "Hypertension".to_string(),
+                value: "Yes".to_string(),
+            },
+            PrintedInput {
+                label: "Age".to_string(),
+                value: String::new(),
+            },
+        ];
+
+        let md = export_to_markdown(&record, &inputs, false);
+        assert!(
+            md.starts_with("# HAS-BLED Score\n\nCalculated: 2026-02-12 10:00:00 · Case: Bed 4\n")
+        );
+        assert!(md.contains("**Score: 2** · **Risk: Moderate**"));
+        assert!(md.contains("## Recommendation\n\nReview bleeding risk\n"));
+        assert!(!md.contains("## Details"));
+        assert!(md.contains(
+            "| Criterion | Value |\n| --- | --- |\n| Hypertension | Yes |\n| Age | – |\n"
+        ));
+        assert!(md.contains("| Labile INR \\| TTR <60% | +1 |\n| **Total** | **2** |"));
+        assert!(md.contains("1. Pisters R, et al. Chest. 2010\n"));
+        assert!(md.contains(&format!("> {}\n", disclaimer_text(false))));
+        assert!(md.ends_with("*KlinScore · Score definition 2024-v1 · Provenance `9f2c`*\n"));
+    }
+}
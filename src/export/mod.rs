@@ -1,8 +1,18 @@
-// Export module - CSV, JSON, and PDF export of calculation results
+// Export module - CSV, JSON, FHIR, Markdown, plain-text, template, and PDF export
+// and printing of calculation results, with optional tamper-evident seals
 
 pub mod csv_export;
+pub mod deidentify;
+pub mod fhir_export;
+pub mod integrity;
 pub mod json_export;
+pub mod markdown_export;
 pub mod pdf_export;
+pub mod print;
+pub mod qr;
+pub mod session_report;
+pub mod templates;
+pub mod text_export;
 
 use crate::scores::CalculationResult;
 use chrono::Local;
@@ -17,7 +27,30 @@ pub struct ExportRecord {
     pub recommendation: String,
     pub details: String,
     pub field_breakdown: Vec<ExportFieldEntry>,
+    /// Time of the calculation; empty in exports anonymized to strip timestamps
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub timestamp: String,
+    /// Optional case identifier (e.g., bed or case number)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub case_id: Option<String>,
+    /// Optional free-text notes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    /// Simplified patient-facing copy (lay interpretation, no recommendations)
+    #[serde(default)]
+    pub patient_copy: bool,
+    /// Version of the score definition the result was computed with
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub score_version: Option<String>,
+    /// Set when the result was computed with an older definition than the one now loaded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version_warning: Option<String>,
+    /// Formatted citations of the publications the score is based on
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub references: Vec<String>,
+    /// Reproducibility hash of the calculation (see `scores::provenance_hash`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provenance: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -27,19 +60,38 @@ pub struct ExportFieldEntry {
     pub points: i32,
 }
 
+/// Translation key for score content in an export (exports are English or German)
+fn language_code(use_german: bool) -> &'static str {
+    if use_german {
+        "de"
+    } else {
+        "en"
+    }
+}
+
+/// Disclaimer shown on printouts
+pub fn disclaimer_text(use_german: bool) -> &'static str {
+    if use_german {
+        "KlinScore ist ein Hilfsmittel zur klinischen Entscheidungsunterstützung. \
+         Es ersetzt nicht die klinische Beurteilung durch einen Arzt. \
+         Alle Scores sollten im klinischen Kontext des Patienten interpretiert werden. \
+         Keine Garantie für Richtigkeit oder Vollständigkeit."
+    } else {
+        "KlinScore is a clinical decision support tool. \
+         It does not replace clinical judgment by a physician. \
+         All scores should be interpreted in the patient's clinical context. \
+         No guarantee of accuracy or completeness."
+    }
+}
+
 impl ExportRecord {
     pub fn from_result(result: &CalculationResult, score_name: &str, use_german: bool) -> Self {
+        let language = language_code(use_german);
         let field_breakdown = result
-            .field_scores
-            .iter()
-            .filter(|fs| fs.points != 0)
+            .contributing_fields()
             .map(|fs| ExportFieldEntry {
                 field: fs.field.clone(),
-                label: if use_german {
-                    fs.label_de.clone()
-                } else {
-                    fs.label.clone()
-                },
+                label: fs.label.text(language).to_string(),
                 points: fs.points,
             })
             .collect();
@@ -47,24 +99,38 @@ impl ExportRecord {
         Self {
             score_name: score_name.to_string(),
             total_score: result.total_score,
-            risk: if use_german {
-                result.risk_de.clone()
-            } else {
-                result.risk.clone()
-            },
-            recommendation: if use_german {
-                result.recommendation_de.clone()
-            } else {
-                result.recommendation.clone()
-            },
-            details: if use_german {
-                result.details_de.clone().unwrap_or_default()
-            } else {
-                result.details.clone().unwrap_or_default()
-            },
+            risk: result.risk.text(language).to_string(),
+            recommendation: result.recommendation.text(language).to_string(),
+            details: result
+                .details
+                .as_ref()
+                .map(|details| details.text(language).to_string())
+                .unwrap_or_default(),
             field_breakdown,
             timestamp: Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+            case_id: None,
+            notes: None,
+            patient_copy: false,
+            score_version: None,
+            version_warning: None,
+            references: Vec::new(),
+            provenance: Some(result.provenance.clone()).filter(|hash| !hash.is_empty()),
+        }
+    }
+
+    /// Build a patient-facing copy of a result.
+    ///
+    /// Uses the lay-language interpretation (`risk_lay`) where the score provides one
+    /// and omits clinician-directed recommendations and details.
+    pub fn patient_copy(result: &CalculationResult, score_name: &str, use_german: bool) -> Self {
+        let mut record = Self::from_result(result, score_name, use_german);
+        if let Some(lay_risk) = &result.interpretation.risk_lay {
+            record.risk = lay_risk.text(language_code(use_german)).to_string();
         }
+        record.recommendation = String::new();
+        record.details = String::new();
+        record.patient_copy = true;
+        record
     }
 }
 
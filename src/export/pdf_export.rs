@@ -1,7 +1,12 @@
 // pdf_export.rs
-// Export calculation results as PDF
+// Export calculation results as PDF reports
 
-use super::ExportRecord;
+use super::print::{PrintLabels, PrintedInput};
+use super::qr::{checksum, QrPayload};
+use super::session_report::SessionReport;
+use super::{disclaimer_text, ExportRecord};
+use crate::config::RiskLevel;
+use printpdf::path::PaintMode;
 use printpdf::*;
 use std::fs::File;
 use std::io::BufWriter;
@@ -10,187 +15,599 @@ const PAGE_WIDTH_MM: f32 = 210.0;
 const PAGE_HEIGHT_MM: f32 = 297.0;
 const MARGIN_MM: f32 = 25.0;
 const LINE_HEIGHT_MM: f32 = 6.0;
+const TEXT_WIDTH_MM: f32 = PAGE_WIDTH_MM - 2.0 * MARGIN_MM;
+/// Space kept free at the bottom of each page for the footer
+const FOOTER_HEIGHT_MM: f32 = 12.0;
+/// Start of the value column in the inputs and breakdown tables
+const VALUE_COLUMN_MM: f32 = 110.0;
+/// Columns of the result and the risk on the overview page of session reports
+const SUMMARY_RESULT_MM: f32 = 100.0;
+const SUMMARY_RISK_MM: f32 = 120.0;
+/// Edge length of the QR code under each result
+const QR_SIZE_MM: f32 = 30.0;
 
-/// Export a single calculation result to PDF file
-pub fn export_to_pdf_file(record: &ExportRecord, path: &str) -> Result<(), String> {
-    let (doc, page1, layer1) = PdfDocument::new(
-        format!("KlinScore - {}", record.score_name),
-        Mm(PAGE_WIDTH_MM),
-        Mm(PAGE_HEIGHT_MM),
-        "Content",
-    );
-
-    let font = doc
-        .add_builtin_font(BuiltinFont::Helvetica)
-        .map_err(|e| e.to_string())?;
-    let font_bold = doc
-        .add_builtin_font(BuiltinFont::HelveticaBold)
-        .map_err(|e| e.to_string())?;
-
-    let layer = doc.get_page(page1).get_layer(layer1);
-    let mut y = PAGE_HEIGHT_MM - MARGIN_MM;
-
-    // Title
-    write_text(&layer, &font_bold, 18.0, MARGIN_MM, y, "KlinScore");
-    y -= LINE_HEIGHT_MM * 1.5;
-
-    // Score name
-    write_text(&layer, &font_bold, 14.0, MARGIN_MM, y, &record.score_name);
-    y -= LINE_HEIGHT_MM * 1.5;
-
-    // Separator line
-    draw_line(&layer, MARGIN_MM, y, PAGE_WIDTH_MM - MARGIN_MM, y);
-    y -= LINE_HEIGHT_MM;
-
-    // Total Score
-    write_text(
-        &layer,
-        &font_bold,
-        12.0,
-        MARGIN_MM,
-        y,
-        &format!("Total Score: {}", record.total_score),
-    );
-    y -= LINE_HEIGHT_MM * 1.2;
-
-    // Risk
-    write_text(
-        &layer,
-        &font_bold,
-        11.0,
-        MARGIN_MM,
-        y,
-        &format!("Risk: {}", record.risk),
-    );
-    y -= LINE_HEIGHT_MM * 1.5;
-
-    // Recommendation
-    write_text(&layer, &font_bold, 10.0, MARGIN_MM, y, "Recommendation:");
-    y -= LINE_HEIGHT_MM;
-
-    // Word-wrap the recommendation text
-    y = write_wrapped_text(
-        &layer,
-        &font,
-        9.0,
-        MARGIN_MM + 2.0,
-        y,
-        &record.recommendation,
-        PAGE_WIDTH_MM - 2.0 * MARGIN_MM - 2.0,
-    );
-    y -= LINE_HEIGHT_MM * 0.5;
-
-    // Details if present
-    if !record.details.is_empty() {
-        write_text(&layer, &font_bold, 10.0, MARGIN_MM, y, "Details:");
-        y -= LINE_HEIGHT_MM;
-        y = write_wrapped_text(
-            &layer,
-            &font,
-            9.0,
-            MARGIN_MM + 2.0,
-            y,
-            &record.details,
-            PAGE_WIDTH_MM - 2.0 * MARGIN_MM - 2.0,
+/// Picks a section heading from the labels of a language
+type Heading = fn(&PrintLabels) -> &'static str;
+
+/// Institution lines, language and QR code of PDF reports, set in the settings
+#[derive(Debug, Clone, Default)]
+pub struct PdfOptions {
+    /// Printed at the top of every page (e.g., hospital and department)
+    pub header: String,
+    /// Printed at the bottom of every page (e.g., address and phone number)
+    pub footer: String,
+    /// Headings in German rather than English
+    pub use_german: bool,
+    /// Print a QR code with the result, or its checksum, under each result
+    pub qr_code: bool,
+}
+
+/// A calculation result as laid out in a PDF report
+#[derive(Debug, Clone)]
+pub struct PdfReport {
+    pub record: ExportRecord,
+    /// Every input with its entered value, including those scoring no points
+    pub inputs: Vec<PrintedInput>,
+    /// Colors the interpretation (unknown for old history entries)
+    pub risk_level: Option<RiskLevel>,
+    /// The same result in the other report language (German or English), for
+    /// bilingual reports
+    pub translation: Option<(ExportRecord, Vec<PrintedInput>)>,
+}
+
+/// Export a single calculation result to a PDF report
+///
+/// The report holds the inputs, the points breakdown, the interpretation in the
+/// color of its risk level, the references and the disclaimer, with the
+/// institution header and footer and page numbers on every page.
+pub fn export_to_pdf_file(
+    report: &PdfReport,
+    options: &PdfOptions,
+    path: &std::path::Path,
+) -> Result<(), String> {
+    let mut writer = PdfWriter::new(&report.record.score_name, options)?;
+    writer.result(report)?;
+    writer.disclaimer(report.translation.is_some());
+    writer.save(
+        path,
+        &report.record.timestamp,
+        report.record.score_version.as_deref(),
+    )
+}
+
+/// Export several results as one report under `title`, e.g. the scores of a
+/// pre-op assessment or the filtered history
+///
+/// Each result is laid out as in [`export_to_pdf_file`]; the disclaimer follows
+/// the last one.
+pub fn export_reports_to_pdf_file(
+    title: &str,
+    reports: &[PdfReport],
+    options: &PdfOptions,
+    path: &std::path::Path,
+) -> Result<(), String> {
+    let mut writer = PdfWriter::new(title, options)?;
+    writer.paragraph(true, 16.0, 0.0, title);
+    for report in reports {
+        writer.y -= LINE_HEIGHT_MM;
+        writer.result(report)?;
+    }
+    writer.disclaimer(reports.iter().any(|report| report.translation.is_some()));
+    let timestamp = reports
+        .last()
+        .map(|report| report.record.timestamp.as_str())
+        .unwrap_or_default();
+    writer.save(path, timestamp, None)
+}
+
+/// Export the calculations of a session, e.g. a pre-anaesthesia assessment
+///
+/// The first page gives the score, result and risk of every calculation; then
+/// each result follows as in [`export_to_pdf_file`], and the disclaimer last.
+pub fn export_session_to_pdf_file(
+    report: &SessionReport,
+    options: &PdfOptions,
+    path: &std::path::Path,
+) -> Result<(), String> {
+    let mut writer = PdfWriter::new(&report.title, options)?;
+    writer.summary(report);
+    writer.new_page();
+    for (index, result) in report.reports.iter().enumerate() {
+        if index > 0 {
+            writer.y -= LINE_HEIGHT_MM;
+        }
+        writer.result(result)?;
+    }
+    writer.disclaimer(report.reports.iter().any(|r| r.translation.is_some()));
+    writer.save(path, report.period().1, None)
+}
+
+/// Writes text top to bottom, starting a new page when one is full
+struct PdfWriter<'a> {
+    doc: PdfDocumentReference,
+    /// Layer of every page so far; the last one is written to
+    pages: Vec<PdfLayerReference>,
+    font: IndirectFontRef,
+    font_bold: IndirectFontRef,
+    y: f32,
+    options: &'a PdfOptions,
+    labels: PrintLabels,
+    /// Headings of the other language in bilingual reports
+    other_labels: PrintLabels,
+}
+
+impl<'a> PdfWriter<'a> {
+    fn new(title: &str, options: &'a PdfOptions) -> Result<Self, String> {
+        let (doc, page, layer) = PdfDocument::new(
+            format!("KlinScore - {}", title),
+            Mm(PAGE_WIDTH_MM),
+            Mm(PAGE_HEIGHT_MM),
+            "Content",
         );
-        y -= LINE_HEIGHT_MM * 0.5;
+        let font = doc
+            .add_builtin_font(BuiltinFont::Helvetica)
+            .map_err(|e| e.to_string())?;
+        let font_bold = doc
+            .add_builtin_font(BuiltinFont::HelveticaBold)
+            .map_err(|e| e.to_string())?;
+        let layer = doc.get_page(page).get_layer(layer);
+
+        let mut writer = Self {
+            doc,
+            pages: vec![layer],
+            font,
+            font_bold,
+            y: PAGE_HEIGHT_MM - MARGIN_MM,
+            options,
+            labels: PrintLabels::for_language(options.use_german),
+            other_labels: PrintLabels::for_language(!options.use_german),
+        };
+        writer.page_header();
+        Ok(writer)
+    }
+
+    fn layer(&self) -> &PdfLayerReference {
+        self.pages.last().expect("a report has at least one page")
+    }
+
+    /// Title and institution header at the top of a page
+    fn page_header(&mut self) {
+        self.line(true, 18.0, 0.0, "KlinScore");
+        self.y -= LINE_HEIGHT_MM * 0.5;
+        if !self.options.header.trim().is_empty() {
+            for line in self.options.header.lines() {
+                self.line(false, 9.0, 0.0, line);
+            }
+            self.rule();
+        }
+    }
+
+    /// Make room for `height` mm, continuing on a new page if needed
+    fn ensure_space(&mut self, height: f32) {
+        if self.y - height < MARGIN_MM + FOOTER_HEIGHT_MM {
+            self.new_page();
+        }
+    }
+
+    /// Continue on a new page
+    fn new_page(&mut self) {
+        let (page, layer) = self
+            .doc
+            .add_page(Mm(PAGE_WIDTH_MM), Mm(PAGE_HEIGHT_MM), "Content");
+        self.pages.push(self.doc.get_page(page).get_layer(layer));
+        self.y = PAGE_HEIGHT_MM - MARGIN_MM;
+        self.page_header();
+    }
+
+    /// One line of text, indented by `indent` mm
+    fn line(&mut self, bold: bool, size: f32, indent: f32, text: &str) {
+        self.ensure_space(LINE_HEIGHT_MM);
+        let font = if bold { &self.font_bold } else { &self.font };
+        self.layer()
+            .use_text(text, size, Mm(MARGIN_MM + indent), Mm(self.y), font);
+        self.y -= LINE_HEIGHT_MM;
+    }
+
+    /// Word-wrapped paragraph, indented by `indent` mm
+    fn paragraph(&mut self, bold: bool, size: f32, indent: f32, text: &str) {
+        for line in wrap_lines(text, size, TEXT_WIDTH_MM - indent) {
+            self.line(bold, size, indent, &line);
+        }
     }
 
-    // Points breakdown
-    if !record.field_breakdown.is_empty() {
-        y -= LINE_HEIGHT_MM * 0.5;
-        draw_line(&layer, MARGIN_MM, y, PAGE_WIDTH_MM - MARGIN_MM, y);
-        y -= LINE_HEIGHT_MM;
+    /// Paragraph with its translation below it in grey, if there is one and it differs
+    fn translated_paragraph(&mut self, size: f32, text: &str, translation: Option<&str>) {
+        self.paragraph(false, size, 2.0, text);
+        if let Some(translation) = translation.filter(|t| !t.is_empty() && *t != text) {
+            self.layer()
+                .set_fill_color(Color::Rgb(Rgb::new(0.35, 0.35, 0.35, None)));
+            self.paragraph(false, size, 2.0, translation);
+            self.layer()
+                .set_fill_color(Color::Rgb(Rgb::new(0.0, 0.0, 0.0, None)));
+        }
+    }
+
+    /// Section heading, in both languages in bilingual reports
+    fn heading(&mut self, heading: Heading, bilingual: bool) {
+        // Keep a heading together with the first lines below it
+        self.ensure_space(LINE_HEIGHT_MM * 3.0);
+        self.y -= LINE_HEIGHT_MM * 0.3;
+        let text = if bilingual && heading(&self.labels) != heading(&self.other_labels) {
+            format!(
+                "{} / {}",
+                heading(&self.labels),
+                heading(&self.other_labels)
+            )
+        } else {
+            heading(&self.labels).to_string()
+        };
+        self.line(true, 10.0, 0.0, &text);
+    }
+
+    /// Table row with the label wrapped in the left column and `value` on the right
+    fn table_row(&mut self, label: &str, value: &str) {
+        let lines = wrap_lines(label, 9.0, VALUE_COLUMN_MM - MARGIN_MM - 4.0);
+        self.ensure_space(LINE_HEIGHT_MM * lines.len() as f32);
+        let top = self.y;
+        for line in &lines {
+            self.line(false, 9.0, 2.0, line);
+        }
+        self.layer()
+            .use_text(value, 9.0, Mm(VALUE_COLUMN_MM), Mm(top), &self.font);
+    }
+
+    fn rule(&mut self) {
+        self.ensure_space(LINE_HEIGHT_MM);
+        let y = self.y + LINE_HEIGHT_MM * 0.6;
+        draw_line(self.layer(), MARGIN_MM, y, PAGE_WIDTH_MM - MARGIN_MM, y);
+        self.y -= LINE_HEIGHT_MM * 0.4;
+    }
+
+    /// Score name, case, interpretation, inputs, breakdown, notes, references and,
+    /// if enabled, the QR code
+    fn result(&mut self, report: &PdfReport) -> Result<(), String> {
+        let record = &report.record;
+        let other = report.translation.as_ref().map(|(record, _)| record);
+        let bilingual = other.is_some();
+
+        self.ensure_space(LINE_HEIGHT_MM * 6.0);
+        self.paragraph(true, 14.0, 0.0, &record.score_name);
+        if let Some(name) = other
+            .map(|o| &o.score_name)
+            .filter(|n| **n != record.score_name)
+        {
+            self.paragraph(false, 10.0, 0.0, name);
+        }
+        let meta = self.labels.meta(record, str::to_string);
+        if !meta.is_empty() {
+            self.line(false, 9.0, 0.0, &meta.join("  |  "));
+        }
+        if record.patient_copy {
+            self.line(false, 10.0, 0.0, "Patient copy / Patientenexemplar");
+        }
+        if let Some(warning) = &record.version_warning {
+            self.paragraph(true, 10.0, 0.0, warning);
+        }
+        self.rule();
 
-        write_text(&layer, &font_bold, 10.0, MARGIN_MM, y, "Points Breakdown:");
-        y -= LINE_HEIGHT_MM * 1.2;
+        // Interpretation, marked with the color of its risk level
+        self.ensure_space(LINE_HEIGHT_MM * 3.0);
+        let mut risk = format!("{}: {}", self.labels.risk, record.risk);
+        if let Some(level) = report.risk_level {
+            let (r, g, b) = level.rgb();
+            let layer = self.layer().clone();
+            layer.set_fill_color(Color::Rgb(Rgb::new(r, g, b, None)));
+            layer.add_rect(
+                Rect::new(
+                    Mm(MARGIN_MM),
+                    Mm(self.y - LINE_HEIGHT_MM * 1.6),
+                    Mm(MARGIN_MM + 2.5),
+                    Mm(self.y + LINE_HEIGHT_MM * 0.8),
+                )
+                .with_mode(PaintMode::Fill),
+            );
+            layer.set_fill_color(Color::Rgb(Rgb::new(0.0, 0.0, 0.0, None)));
+            let language = if self.options.use_german { "de" } else { "en" };
+            risk.push_str(&format!(" ({})", level.label(language)));
+        }
+        self.line(
+            true,
+            12.0,
+            5.0,
+            &format!("{}: {}", self.labels.score, record.total_score),
+        );
+        for line in wrap_lines(&risk, 11.0, TEXT_WIDTH_MM - 5.0) {
+            self.line(true, 11.0, 5.0, &line);
+        }
+        if let Some(risk) = other.map(|o| &o.risk).filter(|risk| **risk != record.risk) {
+            self.paragraph(false, 10.0, 5.0, risk);
+        }
 
-        for field in &record.field_breakdown {
-            let line = format!("  {} pts  {}", field.points, field.label);
-            write_text(&layer, &font, 9.0, MARGIN_MM, y, &line);
-            y -= LINE_HEIGHT_MM;
+        let sections: [(Heading, &str, Option<&str>); 2] = [
+            (
+                |l| l.recommendation,
+                &record.recommendation,
+                other.map(|o| o.recommendation.as_str()),
+            ),
+            (
+                |l| l.details,
+                &record.details,
+                other.map(|o| o.details.as_str()),
+            ),
+        ];
+        for (heading, text, translation) in sections {
+            if !text.is_empty() {
+                self.heading(heading, bilingual);
+                self.translated_paragraph(9.0, text, translation);
+            }
+        }
 
-            // Start new page if running out of space
-            if y < MARGIN_MM + LINE_HEIGHT_MM * 3.0 {
-                break;
+        if !report.inputs.is_empty() {
+            self.heading(|l| l.inputs, bilingual);
+            let translated = report.translation.as_ref().map(|(_, inputs)| inputs);
+            for (i, input) in report.inputs.iter().enumerate() {
+                let other = translated.and_then(|inputs| inputs.get(i));
+                let value = if input.value.is_empty() {
+                    "-".to_string()
+                } else {
+                    with_translation(&input.value, other.map(|o| o.value.as_str()))
+                };
+                self.table_row(
+                    &with_translation(&input.label, other.map(|o| o.label.as_str())),
+                    &value,
+                );
             }
         }
+
+        if !record.field_breakdown.is_empty() {
+            self.heading(|l| l.breakdown, bilingual);
+            for (i, field) in record.field_breakdown.iter().enumerate() {
+                let other = other.and_then(|o| o.field_breakdown.get(i));
+                // ASCII sign: the built-in PDF fonts have no typographic minus
+                self.table_row(
+                    &with_translation(&field.label, other.map(|o| o.label.as_str())),
+                    &format!("{:+}", field.points),
+                );
+            }
+            self.ensure_space(LINE_HEIGHT_MM);
+            let top = self.y;
+            self.line(true, 9.0, 2.0, self.labels.total);
+            let layer = self.layer().clone();
+            layer.use_text(
+                record.total_score.to_string(),
+                9.0,
+                Mm(VALUE_COLUMN_MM),
+                Mm(top),
+                &self.font_bold,
+            );
+        }
+
+        if let Some(notes) = &record.notes {
+            self.heading(|l| l.notes, bilingual);
+            self.paragraph(false, 9.0, 2.0, notes);
+        }
+
+        if !record.references.is_empty() {
+            self.heading(|l| l.references, bilingual);
+            for reference in &record.references {
+                self.paragraph(false, 8.0, 2.0, reference);
+            }
+        }
+
+        if self.options.qr_code {
+            self.qr_code(record)?;
+        }
+        Ok(())
+    }
+
+    /// Title, patient label, period and a table of the score, result and risk
+    /// of every calculation of a session
+    fn summary(&mut self, report: &SessionReport) {
+        self.paragraph(true, 16.0, 0.0, &report.title);
+        let (started, finished) = report.period();
+        let mut meta = Vec::new();
+        if !started.is_empty() {
+            let mut calculated = format!("{}: {}", self.labels.calculated, started);
+            if finished != started {
+                calculated.push_str(&format!(" - {}", finished));
+            }
+            meta.push(calculated);
+        }
+        if let Some(label) = report.patient_label() {
+            meta.push(format!("{}: {}", self.labels.case, label));
+        }
+        if !meta.is_empty() {
+            self.line(false, 9.0, 0.0, &meta.join("  |  "));
+        }
+        self.rule();
+        self.line(true, 12.0, 0.0, self.labels.summary);
+
+        let layer = self.layer().clone();
+        for (x, heading) in [
+            (MARGIN_MM + 5.0, self.labels.score),
+            (SUMMARY_RESULT_MM, self.labels.result),
+            (SUMMARY_RISK_MM, self.labels.risk),
+        ] {
+            layer.use_text(heading, 9.0, Mm(x), Mm(self.y), &self.font_bold);
+        }
+        self.y -= LINE_HEIGHT_MM * 0.5;
+        self.rule();
+
+        for row in report.summary() {
+            let names = wrap_lines(&row.score_name, 10.0, SUMMARY_RESULT_MM - MARGIN_MM - 7.0);
+            let risks = wrap_lines(&row.risk, 9.0, PAGE_WIDTH_MM - MARGIN_MM - SUMMARY_RISK_MM);
+            let lines = names.len().max(risks.len()).max(1) as f32;
+            self.ensure_space(LINE_HEIGHT_MM * lines);
+            let top = self.y;
+            let layer = self.layer().clone();
+            if let Some(level) = row.risk_level {
+                let (r, g, b) = level.rgb();
+                layer.set_fill_color(Color::Rgb(Rgb::new(r, g, b, None)));
+                layer.add_rect(
+                    Rect::new(
+                        Mm(MARGIN_MM),
+                        Mm(top - LINE_HEIGHT_MM * (lines - 1.0) - 1.5),
+                        Mm(MARGIN_MM + 2.5),
+                        Mm(top + 3.5),
+                    )
+                    .with_mode(PaintMode::Fill),
+                );
+                layer.set_fill_color(Color::Rgb(Rgb::new(0.0, 0.0, 0.0, None)));
+            }
+            for (i, name) in names.iter().enumerate() {
+                let y = top - LINE_HEIGHT_MM * i as f32;
+                layer.use_text(name, 10.0, Mm(MARGIN_MM + 5.0), Mm(y), &self.font_bold);
+            }
+            layer.use_text(
+                row.total_score.to_string(),
+                10.0,
+                Mm(SUMMARY_RESULT_MM),
+                Mm(top),
+                &self.font_bold,
+            );
+            for (i, risk) in risks.iter().enumerate() {
+                let y = top - LINE_HEIGHT_MM * i as f32;
+                layer.use_text(risk, 9.0, Mm(SUMMARY_RISK_MM), Mm(y), &self.font);
+            }
+            self.y = top - LINE_HEIGHT_MM * lines;
+        }
     }
 
-    // Footer
-    y = MARGIN_MM;
-    draw_line(
-        &layer,
-        MARGIN_MM,
-        y + LINE_HEIGHT_MM,
-        PAGE_WIDTH_MM - MARGIN_MM,
-        y + LINE_HEIGHT_MM,
-    );
-    write_text(
-        &layer,
-        &font,
-        7.0,
-        MARGIN_MM,
-        y,
-        &format!("Generated by KlinScore | {}", record.timestamp),
-    );
-
-    // Save to file
-    let file = File::create(path).map_err(|e| e.to_string())?;
-    let mut buf = BufWriter::new(file);
-    doc.save(&mut buf).map_err(|e| e.to_string())?;
-
-    Ok(())
+    /// QR code of a result, captioned with what it holds and the checksum
+    fn qr_code(&mut self, record: &ExportRecord) -> Result<(), String> {
+        let payload = QrPayload::for_record(record)?;
+        let code = payload.qr_code()?;
+        self.y -= LINE_HEIGHT_MM * 0.5;
+        self.ensure_space(QR_SIZE_MM + LINE_HEIGHT_MM * 0.5);
+
+        let width = code.width();
+        let module = QR_SIZE_MM / width as f32;
+        let top = self.y + LINE_HEIGHT_MM * 0.5;
+        let layer = self.layer().clone();
+        layer.set_fill_color(Color::Rgb(Rgb::new(0.0, 0.0, 0.0, None)));
+        for (index, color) in code.to_colors().into_iter().enumerate() {
+            if color == qrcode::Color::Dark {
+                let (row, column) = ((index / width) as f32, (index % width) as f32);
+                layer.add_rect(
+                    Rect::new(
+                        Mm(MARGIN_MM + column * module),
+                        Mm(top - (row + 1.0) * module),
+                        Mm(MARGIN_MM + (column + 1.0) * module),
+                        Mm(top - row * module),
+                    )
+                    .with_mode(PaintMode::Fill),
+                );
+            }
+        }
+
+        let caption = match payload {
+            QrPayload::Record(_) => self.labels.qr_import,
+            QrPayload::Checksum(_) => self.labels.qr_verify,
+        };
+        let hash = checksum(record)?;
+        let x = MARGIN_MM + QR_SIZE_MM + 5.0;
+        layer.use_text(caption, 8.0, Mm(x), Mm(top - 5.0), &self.font);
+        layer.use_text("SHA-256:", 7.0, Mm(x), Mm(top - 10.0), &self.font);
+        for (line, chunk) in [&hash[..32], &hash[32..]].into_iter().enumerate() {
+            let y = top - 14.0 - line as f32 * 4.0;
+            layer.use_text(chunk, 7.0, Mm(x), Mm(y), &self.font);
+        }
+        self.y = top - QR_SIZE_MM - LINE_HEIGHT_MM;
+        Ok(())
+    }
+
+    fn disclaimer(&mut self, bilingual: bool) {
+        self.y -= LINE_HEIGHT_MM * 0.5;
+        self.rule();
+        self.paragraph(false, 7.5, 0.0, disclaimer_text(self.options.use_german));
+        if bilingual {
+            self.paragraph(false, 7.5, 0.0, disclaimer_text(!self.options.use_german));
+        }
+    }
+
+    /// Write the footer with page numbers on every page and save the document
+    fn save(
+        self,
+        path: &std::path::Path,
+        timestamp: &str,
+        score_version: Option<&str>,
+    ) -> Result<(), String> {
+        let mut generated = vec!["Generated by KlinScore".to_string()];
+        if !timestamp.is_empty() {
+            generated.push(timestamp.to_string());
+        }
+        if let Some(version) = score_version {
+            generated.push(format!("{} {}", self.labels.definition, version));
+        }
+        let generated = generated.join(" | ");
+        let count = self.pages.len();
+        for (index, layer) in self.pages.iter().enumerate() {
+            let y = MARGIN_MM;
+            draw_line(
+                layer,
+                MARGIN_MM,
+                y + LINE_HEIGHT_MM,
+                PAGE_WIDTH_MM - MARGIN_MM,
+                y + LINE_HEIGHT_MM,
+            );
+            let footer = self.options.footer.lines().collect::<Vec<_>>().join(" | ");
+            if !footer.trim().is_empty() {
+                layer.use_text(&footer, 7.0, Mm(MARGIN_MM), Mm(y + 1.5), &self.font);
+            }
+            layer.use_text(&generated, 7.0, Mm(MARGIN_MM), Mm(y - 2.5), &self.font);
+            layer.use_text(
+                format!(
+                    "{} {} {} {}",
+                    self.labels.page,
+                    index + 1,
+                    self.labels.page_of,
+                    count
+                ),
+                7.0,
+                Mm(PAGE_WIDTH_MM - MARGIN_MM - 20.0),
+                Mm(y - 2.5),
+                &self.font,
+            );
+        }
+
+        let file = File::create(path).map_err(|e| e.to_string())?;
+        let mut buf = BufWriter::new(file);
+        self.doc.save(&mut buf).map_err(|e| e.to_string())?;
+        Ok(())
+    }
 }
 
-fn write_text(
-    layer: &PdfLayerReference,
-    font: &IndirectFontRef,
-    size: f32,
-    x: f32,
-    y: f32,
-    text: &str,
-) {
-    layer.use_text(text, size, Mm(x), Mm(y), font);
+/// "text / translation", or just `text` if there is no different translation
+fn with_translation(text: &str, translation: Option<&str>) -> String {
+    match translation.filter(|t| !t.is_empty() && *t != text) {
+        Some(translation) => format!("{} / {}", text, translation),
+        None => text.to_string(),
+    }
 }
 
-/// Write text with word wrapping, return the new y position
-fn write_wrapped_text(
-    layer: &PdfLayerReference,
-    font: &IndirectFontRef,
-    size: f32,
-    x: f32,
-    mut y: f32,
-    text: &str,
-    max_width_mm: f32,
-) -> f32 {
+/// Split text into lines of at most `max_width_mm` at font `size`
+fn wrap_lines(text: &str, size: f32, max_width_mm: f32) -> Vec<String> {
     // Approximate: 1 char ~ 0.5 * font_size / 2.83 mm (rough)
     let chars_per_line = (max_width_mm / (size * 0.18)) as usize;
     if chars_per_line == 0 {
-        return y;
+        return Vec::new();
     }
 
-    let words: Vec<&str> = text.split_whitespace().collect();
+    let mut lines = Vec::new();
     let mut line = String::new();
-
-    for word in words {
-        if line.len() + word.len() + 1 > chars_per_line && !line.is_empty() {
-            write_text(layer, font, size, x, y, &line);
-            y -= LINE_HEIGHT_MM;
-            line.clear();
+    for word in text.split_whitespace() {
+        if line.chars().count() + word.chars().count() + 1 > chars_per_line && !line.is_empty() {
+            lines.push(std::mem::take(&mut line));
         }
         if !line.is_empty() {
             line.push(' ');
         }
         line.push_str(word);
     }
-
     if !line.is_empty() {
-        write_text(layer, font, size, x, y, &line);
-        y -= LINE_HEIGHT_MM;
+        lines.push(line);
     }
-
-    y
+    lines
 }
 
 fn draw_line(layer: &PdfLayerReference, x1: f32, y1: f32, x2: f32, y2: f32) {
@@ -206,3 +623,120 @@ fn draw_line(layer: &PdfLayerReference, x1: f32, y1: f32, x2: f32, y2: f32) {
     layer.set_outline_thickness(0.5);
     layer.add_line(line);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::ExportFieldEntry;
+
+    fn report(inputs: usize) -> PdfReport {
+        PdfReport {
+            record: ExportRecord {
+                score_name: "Caprini Score".to_string(),
+                total_score: 3,
+                risk: "Moderate".to_string(),
+                recommendation: "Consider pharmacological prophylaxis".to_string(),
+                details: String::new(),
+                field_breakdown: vec![ExportFieldEntry {
+                    field: "age".to_string(),
+                    label: "Age 41-60 years".to_string(),
+                    points: 1,
+                }],
+                timestamp: "2026-03-02 07:45:00".to_string(),
+                case_id: Some("Bed 12".to_string()),
+                notes: None,
+                patient_copy: false,
+                score_version: Some("2005-v1".to_string()),
+                version_warning: None,
+                references: vec!["Caprini JA. Dis Mon. 2005".to_string()],
+                provenance: None,
+            },
+            inputs: (0..inputs)
+                .map(|i| PrintedInput {
+                    label: format!("Risk factor {}", i),
+                    value: "No".to_string(),
+                })
+                .collect(),
+            risk_level: Some(RiskLevel::Moderate),
+            translation: None,
+        }
+    }
+
+    #[test]
+    fn test_wrap_lines() {
+        let lines = wrap_lines("one two three four five six", 10.0, 18.0);
+        assert_eq!(lines, vec!["one two", "three four", "five six"]);
+        assert!(wrap_lines("", 10.0, 100.0).is_empty());
+        assert_eq!(with_translation("Alter", Some("Age")), "Alter / Age");
+        assert_eq!(with_translation("Score", Some("Score")), "Score");
+    }
+
+    #[test]
+    fn test_long_report_continues_on_new_pages() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let options = PdfOptions {
+            header: "Klinikum Nord\nAnästhesiologie".to_string(),
+            footer: "Tel. 1234".to_string(),
+            use_german: false,
+            qr_code: false,
+        };
+
+        let mut short = report(3);
+        short.translation = Some((short.record.clone(), short.inputs.clone()));
+        let mut writer = PdfWriter::new("Caprini", &options).unwrap();
+        writer.result(&short).unwrap();
+        assert_eq!(writer.pages.len(), 1);
+
+        let mut writer = PdfWriter::new("Caprini", &options).unwrap();
+        writer.result(&report(80)).unwrap();
+        assert!(writer.pages.len() >= 2);
+
+        let path = temp_dir.path().join("caprini.pdf");
+        export_to_pdf_file(&report(80), &options, &path).unwrap();
+        let pdf = std::fs::read(&path).unwrap();
+        assert!(pdf.starts_with(b"%PDF"));
+
+        let mut old_entry = report(2);
+        old_entry.risk_level = None;
+        let path = temp_dir.path().join("pre_op.pdf");
+        export_reports_to_pdf_file("Pre-op Bed 12", &[report(5), old_entry], &options, &path)
+            .unwrap();
+        assert!(std::fs::read(&path).unwrap().starts_with(b"%PDF"));
+    }
+
+    #[test]
+    fn test_session_report_starts_with_summary_page() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let report = crate::export::session_report::tests::session_report();
+        let options = PdfOptions::default();
+
+        let mut writer = PdfWriter::new(&report.title, &options).unwrap();
+        writer.summary(&report);
+        assert_eq!(writer.pages.len(), 1);
+        let path = temp_dir.path().join("session.pdf");
+        export_session_to_pdf_file(&report, &options, &path).unwrap();
+        assert!(std::fs::read(&path).unwrap().starts_with(b"%PDF"));
+    }
+
+    #[test]
+    fn test_qr_code_takes_room_below_the_result() {
+        let mut options = PdfOptions::default();
+        let mut writer = PdfWriter::new("Caprini", &options).unwrap();
+        writer.result(&report(3)).unwrap();
+        let without_qr = writer.y;
+
+        options.qr_code = true;
+        let mut writer = PdfWriter::new("Caprini", &options).unwrap();
+        writer.result(&report(3)).unwrap();
+        assert!(writer.y <= without_qr - QR_SIZE_MM);
+
+        // Near the bottom of the page the code moves to the next one
+        let pages = |options: &PdfOptions, inputs| {
+            let mut writer = PdfWriter::new("Caprini", options).unwrap();
+            writer.result(&report(inputs)).unwrap();
+            writer.pages.len()
+        };
+        let without = PdfOptions::default();
+        assert!((0..60).any(|inputs| pages(&without, inputs) == 1 && pages(&options, inputs) == 2));
+    }
+}
@@ -0,0 +1,418 @@
+// print.rs
+// Print layout of a calculation result, opened in the browser's print dialog
+
+use super::{disclaimer_text, language_code, ExportRecord};
+use crate::scores::CalculationResult;
+use serde::Serialize;
+use std::fmt::Write;
+
+/// An input as entered, for the print layout and export templates
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PrintedInput {
+    pub label: String,
+    /// Entered value or selected option; empty if left blank
+    pub value: String,
+}
+
+/// Every input of `result` with its entered value, in definition order
+pub fn printed_inputs(result: &CalculationResult, use_german: bool) -> Vec<PrintedInput> {
+    let language = language_code(use_german);
+    result
+        .field_scores
+        .iter()
+        .map(|fs| PrintedInput {
+            label: fs.label.text(language).to_string(),
+            value: fs
+                .selection
+                .as_ref()
+                .map(|selection| selection.text(language).to_string())
+                .unwrap_or_default(),
+        })
+        .collect()
+}
+
+/// Render a result as a self-contained HTML page laid out for A4 paper
+///
+/// Contains the inputs, the points breakdown, the interpretation, the references
+/// and the disclaimer. The page opens the print dialog as soon as it is loaded.
+pub fn print_html(record: &ExportRecord, inputs: &[PrintedInput], use_german: bool) -> String {
+    let labels = PrintLabels::for_language(use_german);
+    let mut html = page_start(&record.score_name, use_german);
+    write_result(&mut html, record, inputs, &labels, 1);
+    page_end(
+        &mut html,
+        &record.timestamp,
+        record.score_version.as_deref(),
+        use_german,
+        &labels,
+    );
+    html
+}
+
+/// Render several results as one report, e.g. the scores of a pre-op assessment
+///
+/// Each result is laid out as in [`print_html`], under the report `title`.
+pub fn report_html(
+    title: &str,
+    results: &[(ExportRecord, Vec<PrintedInput>)],
+    use_german: bool,
+) -> String {
+    let labels = PrintLabels::for_language(use_german);
+    let mut html = page_start(title, use_german);
+    let _ = writeln!(html, "<h1>{}</h1>", escape_html(title));
+    for (record, inputs) in results {
+        html.push_str("<section class=\"result\">\n");
+        write_result(&mut html, record, inputs, &labels, 2);
+        html.push_str("</section>\n");
+    }
+    let timestamp = results
+        .last()
+        .map(|(record, _)| record.timestamp.as_str())
+        .unwrap_or_default();
+    page_end(&mut html, timestamp, None, use_german, &labels);
+    html
+}
+
+fn page_start(title: &str, use_german: bool) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"{}\">\n<head>\n<meta charset=\"utf-8\">\n<title>KlinScore – {}</title>\n<style>{}</style>\n</head>\n<body onload=\"window.print()\">\n",
+        language_code(use_german),
+        escape_html(title),
+        PRINT_CSS
+    )
+}
+
+/// Disclaimer and footer, closing the page
+fn page_end(
+    html: &mut String,
+    timestamp: &str,
+    score_version: Option<&str>,
+    use_german: bool,
+    labels: &PrintLabels,
+) {
+    let _ = writeln!(
+        html,
+        "<p class=\"disclaimer\">{}</p>",
+        escape_html(disclaimer_text(use_german))
+    );
+    let mut footer = vec!["KlinScore".to_string()];
+    if !timestamp.is_empty() {
+        footer.push(escape_html(timestamp));
+    }
+    if let Some(version) = score_version {
+        footer.push(format!("{} {}", labels.definition, escape_html(version)));
+    }
+    let footer = footer.join(" | ");
+    let _ = writeln!(html, "<p class=\"footer\">{}</p>\n</body>\n</html>", footer);
+}
+
+/// One result: the score name as a heading of `level`, its sections one level below
+fn write_result(
+    html: &mut String,
+    record: &ExportRecord,
+    inputs: &[PrintedInput],
+    labels: &PrintLabels,
+    level: u8,
+) {
+    let section = level + 1;
+    let _ = writeln!(
+        html,
+        "<h{level}>{}</h{level}>",
+        escape_html(&record.score_name)
+    );
+    let meta = labels.meta(record, escape_html);
+    if !meta.is_empty() {
+        let _ = writeln!(html, "<p class=\"meta\">{}</p>", meta.join(" · "));
+    }
+    if let Some(warning) = &record.version_warning {
+        let _ = writeln!(html, "<p class=\"warning\">{}</p>", escape_html(warning));
+    }
+
+    // Result and interpretation
+    let _ = writeln!(
+        html,
+        "<p class=\"total\">{}: {}</p>\n<p class=\"risk\">{}: {}</p>",
+        labels.score,
+        record.total_score,
+        labels.risk,
+        escape_html(&record.risk)
+    );
+    for (label, value) in [
+        (labels.recommendation, &record.recommendation),
+        (labels.details, &record.details),
+    ] {
+        if !value.is_empty() {
+            let _ = writeln!(
+                html,
+                "<h{section}>{}</h{section}>\n<p>{}</p>",
+                label,
+                escape_html(value)
+            );
+        }
+    }
+
+    // Inputs as entered
+    if !inputs.is_empty() {
+        let _ = writeln!(
+            html,
+            "<h{section}>{}</h{section}>\n<table>\n<tr><th>{}</th><th>{}</th></tr>",
+            labels.inputs, labels.criterion, labels.value
+        );
+        for input in inputs {
+            let value = if input.value.is_empty() {
+                "–".to_string()
+            } else {
+                escape_html(&input.value)
+            };
+            let _ = writeln!(
+                html,
+                "<tr><td>{}</td><td>{}</td></tr>",
+                escape_html(&input.label),
+                value
+            );
+        }
+        html.push_str("</table>\n");
+    }
+
+    // Points breakdown
+    if !record.field_breakdown.is_empty() {
+        let _ = writeln!(
+            html,
+            "<h{section}>{}</h{section}>\n<table>\n<tr><th>{}</th><th class=\"points\">{}</th></tr>",
+            labels.breakdown, labels.criterion, labels.points
+        );
+        for field in &record.field_breakdown {
+            let _ = writeln!(
+                html,
+                "<tr><td>{}</td><td class=\"points\">{:+}</td></tr>",
+                escape_html(&field.label),
+                field.points
+            );
+        }
+        let _ = writeln!(
+            html,
+            "<tr class=\"sum\"><td>{}</td><td class=\"points\">{}</td></tr>\n</table>",
+            labels.total, record.total_score
+        );
+    }
+
+    if let Some(notes) = &record.notes {
+        let _ = writeln!(
+            html,
+            "<h{section}>{}</h{section}>\n<p>{}</p>",
+            labels.notes,
+            escape_html(notes)
+        );
+    }
+
+    if !record.references.is_empty() {
+        let _ = writeln!(html, "<h{section}>{}</h{section}>\n<ol>", labels.references);
+        for reference in &record.references {
+            let _ = writeln!(html, "<li>{}</li>", escape_html(reference));
+        }
+        html.push_str("</ol>\n");
+    }
+}
+
+/// Write the print layout to the temp directory and open it in the default browser,
+/// which shows the print dialog
+pub fn open_print_dialog(html: &str, filename: &str) -> Result<(), String> {
+    let path = std::env::temp_dir().join(filename);
+    std::fs::write(&path, html).map_err(|e| e.to_string())?;
+    opener::open(&path).map_err(|e| e.to_string())
+}
+
+const PRINT_CSS: &str = "@page { size: A4; margin: 20mm; }
+body { font-family: Helvetica, Arial, sans-serif; font-size: 10pt; color: #000; }
+h1 { font-size: 16pt; margin: 0 0 4pt; }
+h2 { font-size: 11pt; margin: 14pt 0 4pt; border-bottom: 1px solid #999; }
+.result { margin-top: 18pt; }
+.result h2 { font-size: 14pt; border-bottom: none; margin: 0 0 4pt; }
+h3 { font-size: 10pt; margin: 10pt 0 4pt; border-bottom: 1px solid #ccc; }
+table { width: 100%; border-collapse: collapse; }
+th, td { text-align: left; padding: 2pt 4pt; border-bottom: 1px solid #ddd; vertical-align: top; }
+.points { text-align: right; width: 20%; }
+.sum td { font-weight: bold; border-top: 1px solid #000; }
+.meta, .footer { font-size: 8pt; color: #444; }
+.total { font-size: 14pt; font-weight: bold; margin: 10pt 0 2pt; }
+.risk { font-size: 12pt; font-weight: bold; margin: 0; }
+.warning { font-weight: bold; }
+.disclaimer { font-size: 8pt; margin-top: 16pt; border-top: 1px solid #999; padding-top: 4pt; }
+tr, li { page-break-inside: avoid; }";
+
+/// Headings and labels of the print layout and PDF reports in one language
+pub(super) struct PrintLabels {
+    pub(super) calculated: &'static str,
+    pub(super) case: &'static str,
+    pub(super) score: &'static str,
+    pub(super) risk: &'static str,
+    pub(super) recommendation: &'static str,
+    pub(super) details: &'static str,
+    pub(super) inputs: &'static str,
+    pub(super) criterion: &'static str,
+    pub(super) value: &'static str,
+    pub(super) breakdown: &'static str,
+    pub(super) points: &'static str,
+    pub(super) total: &'static str,
+    pub(super) notes: &'static str,
+    pub(super) references: &'static str,
+    pub(super) definition: &'static str,
+    pub(super) provenance: &'static str,
+    /// "Page 1 of 2"
+    pub(super) page: &'static str,
+    pub(super) page_of: &'static str,
+    /// Overview page of session reports
+    pub(super) summary: &'static str,
+    pub(super) result: &'static str,
+    /// Captions of the QR code on PDF reports
+    pub(super) qr_import: &'static str,
+    pub(super) qr_verify: &'static str,
+}
+
+impl PrintLabels {
+    pub(super) fn for_language(use_german: bool) -> Self {
+        if use_german {
+            Self::GERMAN
+        } else {
+            Self::ENGLISH
+        }
+    }
+
+    /// "Calculated: …" and "Case: …" of a result, for those it has (anonymized
+    /// exports may have neither), with `escape` applied to the values
+    pub(super) fn meta(
+        &self,
+        record: &ExportRecord,
+        escape: impl Fn(&str) -> String,
+    ) -> Vec<String> {
+        let mut meta = Vec::new();
+        if !record.timestamp.is_empty() {
+            meta.push(format!(
+                "{}: {}",
+                self.calculated,
+                escape(&record.timestamp)
+            ));
+        }
+        if let Some(case_id) = &record.case_id {
+            meta.push(format!("{}: {}", self.case, escape(case_id)));
+        }
+        meta
+    }
+
+    const GERMAN: Self = Self {
+        calculated: "Berechnet",
+        case: "Fall",
+        score: "Score",
+        risk: "Risiko",
+        recommendation: "Empfehlung",
+        details: "Details",
+        inputs: "Eingaben",
+        criterion: "Kriterium",
+        value: "Wert",
+        breakdown: "Punkteverteilung",
+        points: "Punkte",
+        total: "Summe",
+        notes: "Notizen",
+        references: "Literatur",
+        definition: "Score-Definition",
+        provenance: "Herkunftsnachweis",
+        page: "Seite",
+        page_of: "von",
+        summary: "Zusammenfassung",
+        result: "Ergebnis",
+        qr_import: "QR-Code scannen, um dieses Ergebnis zu importieren",
+        qr_verify: "QR-Code scannen, um dieses Ergebnis zu prüfen",
+    };
+
+    const ENGLISH: Self = Self {
+        calculated: "Calculated",
+        case: "Case",
+        score: "Score",
+        risk: "Risk",
+        recommendation: "Recommendation",
+        details: "Details",
+        inputs: "Inputs",
+        criterion: "Criterion",
+        value: "Value",
+        breakdown: "Points Breakdown",
+        points: "Points",
+        total: "Total",
+        notes: "Notes",
+        references: "References",
+        definition: "Score definition",
+        provenance: "Provenance",
+        page: "Page",
+        page_of: "of",
+        summary: "Summary",
+        result: "Result",
+        qr_import: "Scan the QR code to import this result",
+        qr_verify: "Scan the QR code to verify this result",
+    };
+}
+
+/// Escape text for use in HTML element content
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::ExportFieldEntry;
+
+    #[test]
+    fn test_print_html() {
+        let record = ExportRecord {
+            score_name: "HAS-BLED Score".to_string(),
+            total_score: 2,
+            risk: "Moderate".to_string(),
+            recommendation: "Review bleeding risk <3 months".to_string(),
+            details: String::new(),
+            field_breakdown: vec![ExportFieldEntry {
+                field: "hypertension".to_string(),
+                label: "Hypertonie".to_string(),
+                points: 1,
+            }],
+            timestamp: "2026-02-12 10:00:00".to_string(),
+            case_id: Some("Bett 4".to_string()),
+            notes: None,
+            patient_copy: false,
+            score_version: Some("2024-v1".to_string()),
+            version_warning: None,
+            references: vec!["Pisters R, et al. Chest. 2010".to_string()],
+            provenance: None,
+        };
+        let inputs = vec![
+            PrintedInput {
+                label: "Hypertonie".to_string(),
+                value: "Ja".to_string(),
+            },
+            PrintedInput {
+                label: "Alter".to_string(),
+                value: String::new(),
+            },
+        ];
+
+        let html = print_html(&record, &inputs, true);
+        assert!(html.contains("<html lang=\"de\">"));
+        assert!(html.contains("onload=\"window.print()\""));
+        assert!(html.contains("<tr><td>Hypertonie</td><td>Ja</td></tr>"));
+        assert!(html.contains("<tr><td>Alter</td><td>–</td></tr>"));
+        assert!(html.contains("<td class=\"points\">+1</td>"));
+        assert!(html.contains("Review bleeding risk &lt;3 months"));
+        assert!(html.contains("Fall: Bett 4"));
+        assert!(html.contains("Pisters R"));
+        assert!(html.contains(disclaimer_text(true)));
+        assert!(html.contains("Score-Definition 2024-v1"));
+    }
+}
@@ -0,0 +1,136 @@
+// qr.rs
+// QR code on PDF reports, holding the result or its checksum
+//
+// A short result is encoded as compact JSON, so a scanned report can be imported
+// again. Longer results only fit as their SHA-256, which verifies a printed report
+// against the result in KlinScore's JSON export format.
+
+use super::json_export::export_to_json;
+use super::ExportRecord;
+use crate::scores::pack::sha256_hex;
+use qrcode::{EcLevel, QrCode};
+
+/// Prefix of a checksum payload
+const CHECKSUM_PREFIX: &str = "klinscore:sha256:";
+
+/// Longest JSON encoded in full; larger codes are too dense to scan from paper
+const MAX_JSON_BYTES: usize = 800;
+
+/// Content of the QR code of a report
+#[derive(Debug, Clone)]
+pub enum QrPayload {
+    /// The whole result
+    Record(Box<ExportRecord>),
+    /// SHA-256 (hex) of the result as exported to JSON
+    Checksum(String),
+}
+
+impl QrPayload {
+    /// The result itself if it is short enough, its checksum otherwise
+    pub fn for_record(record: &ExportRecord) -> Result<Self, String> {
+        let json = serde_json::to_string(record).map_err(|e| e.to_string())?;
+        if json.len() <= MAX_JSON_BYTES {
+            Ok(Self::Record(Box::new(record.clone())))
+        } else {
+            Ok(Self::Checksum(checksum(record)?))
+        }
+    }
+
+    /// Text stored in the QR code
+    pub fn encode(&self) -> Result<String, String> {
+        match self {
+            Self::Record(record) => serde_json::to_string(record).map_err(|e| e.to_string()),
+            Self::Checksum(hash) => Ok(format!("{CHECKSUM_PREFIX}{hash}")),
+        }
+    }
+
+    /// Read the text of a scanned QR code
+    pub fn decode(text: &str) -> Result<Self, String> {
+        match text.trim().strip_prefix(CHECKSUM_PREFIX) {
+            Some(hash) => Ok(Self::Checksum(hash.to_ascii_lowercase())),
+            None => serde_json::from_str(text)
+                .map(|record| Self::Record(Box::new(record)))
+                .map_err(|e| e.to_string()),
+        }
+    }
+
+    /// Whether the payload belongs to `record`
+    pub fn matches(&self, record: &ExportRecord) -> bool {
+        let Ok(hash) = checksum(record) else {
+            return false;
+        };
+        match self {
+            Self::Record(scanned) => checksum(scanned).is_ok_and(|scanned| scanned == hash),
+            Self::Checksum(scanned) => *scanned == hash,
+        }
+    }
+
+    /// The QR code, with error correction for printed and scanned paper
+    pub fn qr_code(&self) -> Result<QrCode, String> {
+        QrCode::with_error_correction_level(self.encode()?, EcLevel::M).map_err(|e| e.to_string())
+    }
+}
+
+/// SHA-256 (hex) of a result in the JSON export format
+pub fn checksum(record: &ExportRecord) -> Result<String, String> {
+    Ok(sha256_hex(export_to_json(record)?.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::ExportFieldEntry;
+
+    fn record(details: &str) -> ExportRecord {
+        ExportRecord {
+            score_name: "STOP-BANG Score".to_string(),
+            total_score: 5,
+            risk: "High Risk for OSA".to_string(),
+            recommendation: "Consider sleep study".to_string(),
+            details: details.to_string(),
+            field_breakdown: vec![ExportFieldEntry {
+                field: "snoring".to_string(),
+                label: "Loud snoring".to_string(),
+                points: 1,
+            }],
+            timestamp: "2026-02-12 10:00:00".to_string(),
+            case_id: None,
+            notes: None,
+            patient_copy: false,
+            score_version: Some("2024-v1".to_string()),
+            version_warning: None,
+            references: Vec::new(),
+            provenance: None,
+        }
+    }
+
+    #[test]
+    fn test_short_result_is_encoded_in_full() {
+        let record = record("");
+        let payload = QrPayload::for_record(&record).unwrap();
+        assert!(matches!(payload, QrPayload::Record(_)));
+
+        let scanned = QrPayload::decode(&payload.encode().unwrap()).unwrap();
+        let QrPayload::Record(scanned_record) = &scanned else {
+            panic!("expected the record, got {:?}", scanned);
+        };
+        assert_eq!(scanned_record.total_score, 5);
+        assert!(scanned.matches(&record));
+        assert!(payload.qr_code().unwrap().width() > 21);
+    }
+
+    #[test]
+    fn test_long_result_is_encoded_as_checksum() {
+        let record = record(&"Long details. ".repeat(100));
+        let payload = QrPayload::for_record(&record).unwrap();
+        let text = payload.encode().unwrap();
+        assert!(text.starts_with("klinscore:sha256:"));
+        assert_eq!(text.len(), CHECKSUM_PREFIX.len() + 64);
+
+        let scanned = QrPayload::decode(&text).unwrap();
+        assert!(scanned.matches(&record));
+        let mut changed = record.clone();
+        changed.total_score = 6;
+        assert!(!scanned.matches(&changed));
+    }
+}
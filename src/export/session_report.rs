@@ -0,0 +1,186 @@
+// session_report.rs
+// All calculations of a session (e.g., a pre-anaesthesia assessment) as one
+// structured report: an overview of every score, then one section per score
+
+use super::pdf_export::PdfReport;
+use super::print::{report_html, PrintedInput};
+use super::ExportRecord;
+use crate::config::RiskLevel;
+use serde::Serialize;
+use std::path::Path;
+
+/// The calculations of a session, oldest first
+#[derive(Debug, Clone)]
+pub struct SessionReport {
+    /// Title entered when the session was started (e.g., "Pre-op Bed 12")
+    pub title: String,
+    pub reports: Vec<PdfReport>,
+}
+
+/// One line of the overview
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SummaryRow {
+    pub score_name: String,
+    pub total_score: i32,
+    pub risk: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub risk_level: Option<RiskLevel>,
+}
+
+/// JSON layout of a session report
+#[derive(Serialize)]
+struct SessionJson<'a> {
+    title: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    patient_label: Option<&'a str>,
+    /// Time of the first and the last calculation; anonymized reports have none
+    #[serde(skip_serializing_if = "str::is_empty")]
+    started: &'a str,
+    #[serde(skip_serializing_if = "str::is_empty")]
+    finished: &'a str,
+    summary: Vec<SummaryRow>,
+    results: Vec<ResultJson<'a>>,
+}
+
+#[derive(Serialize)]
+struct ResultJson<'a> {
+    #[serde(flatten)]
+    record: &'a ExportRecord,
+    inputs: &'a [PrintedInput],
+}
+
+impl SessionReport {
+    /// Score, result and risk of every calculation, for the overview
+    pub fn summary(&self) -> Vec<SummaryRow> {
+        self.reports
+            .iter()
+            .map(|report| SummaryRow {
+                score_name: report.record.score_name.clone(),
+                total_score: report.record.total_score,
+                risk: report.record.risk.clone(),
+                risk_level: report.risk_level,
+            })
+            .collect()
+    }
+
+    /// Patient label of the calculations, if they all carry the same one
+    pub fn patient_label(&self) -> Option<&str> {
+        let mut labels = self
+            .reports
+            .iter()
+            .map(|report| report.record.case_id.as_deref());
+        let first = labels.next()??;
+        labels.all(|label| label == Some(first)).then_some(first)
+    }
+
+    /// Time of the first and the last calculation
+    pub fn period(&self) -> (&str, &str) {
+        let mut timestamps = self
+            .reports
+            .iter()
+            .map(|report| report.record.timestamp.as_str());
+        let first = timestamps.next().unwrap_or_default();
+        (first, timestamps.next_back().unwrap_or(first))
+    }
+
+    /// The report as JSON: title, patient label, period, overview and every
+    /// result with its inputs
+    pub fn to_json(&self) -> Result<String, String> {
+        let (started, finished) = self.period();
+        let json = SessionJson {
+            title: &self.title,
+            patient_label: self.patient_label(),
+            started,
+            finished,
+            summary: self.summary(),
+            results: self
+                .reports
+                .iter()
+                .map(|report| ResultJson {
+                    record: &report.record,
+                    inputs: &report.inputs,
+                })
+                .collect(),
+        };
+        serde_json::to_string_pretty(&json).map_err(|e| e.to_string())
+    }
+
+    /// Print layout of the report, each result as in [`print_html`](super::print::print_html)
+    pub fn to_html(&self, use_german: bool) -> String {
+        let results: Vec<(ExportRecord, Vec<PrintedInput>)> = self
+            .reports
+            .iter()
+            .map(|report| (report.record.clone(), report.inputs.clone()))
+            .collect();
+        report_html(&self.title, &results, use_german)
+    }
+
+    /// Export the report to a JSON file
+    pub fn export_to_json_file(&self, path: &Path) -> Result<(), String> {
+        std::fs::write(path, self.to_json()?).map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::export::ExportFieldEntry;
+
+    pub(crate) fn session_report() -> SessionReport {
+        let report = |score_name: &str, total_score, risk: &str, timestamp: &str| PdfReport {
+            record: ExportRecord {
+                score_name: score_name.to_string(),
+                total_score,
+                risk: risk.to_string(),
+                recommendation: String::new(),
+                details: String::new(),
+                field_breakdown: vec![ExportFieldEntry {
+                    field: "criterion".to_string(),
+                    label: "Criterion".to_string(),
+                    points: total_score,
+                }],
+                timestamp: timestamp.to_string(),
+                case_id: Some("Bed 12".to_string()),
+                notes: None,
+                patient_copy: false,
+                score_version: None,
+                version_warning: None,
+                references: Vec::new(),
+                provenance: None,
+            },
+            inputs: vec![PrintedInput {
+                label: "Criterion".to_string(),
+                value: "Yes".to_string(),
+            }],
+            risk_level: Some(RiskLevel::Moderate),
+            translation: None,
+        };
+        SessionReport {
+            title: "Pre-op Bed 12".to_string(),
+            reports: vec![
+                report("ASA", 3, "Severe systemic disease", "2026-02-12 10:00"),
+                report("RCRI", 1, "Low risk", "2026-02-12 10:05"),
+                report("STOP-BANG", 5, "High risk of OSA", "2026-02-12 10:09"),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_session_json() {
+        let mut report = session_report();
+        assert_eq!(report.patient_label(), Some("Bed 12"));
+        assert_eq!(report.period(), ("2026-02-12 10:00", "2026-02-12 10:09"));
+
+        let json: serde_json::Value = serde_json::from_str(&report.to_json().unwrap()).unwrap();
+        assert_eq!(json["title"], "Pre-op Bed 12");
+        assert_eq!(json["patient_label"], "Bed 12");
+        assert_eq!(json["summary"].as_array().unwrap().len(), 3);
+        assert_eq!(json["summary"][2]["score_name"], "STOP-BANG");
+        assert_eq!(json["summary"][2]["risk_level"], "Moderate");
+        assert_eq!(json["results"][1]["score_name"], "RCRI");
+        assert_eq!(json["results"][1]["inputs"][0]["value"], "Yes");
+
+        report.reports[1].record.case_id = None;
+        assert_eq!(report.patient_label(), None);
+    }
+}
@@ -0,0 +1,317 @@
+// templates.rs
+// User-supplied export templates (text, Markdown, HTML) rendered with MiniJinja
+//
+// Templates are named `<name>.<txt|md|html>.j2`. The defaults in `templates/`
+// are embedded at compile time; files of the same name in the user's templates
+// folder replace them, other files there are added.
+
+use super::print::PrintedInput;
+use super::{disclaimer_text, language_code, ExportRecord};
+use include_dir::{include_dir, Dir};
+use minijinja::{context, Environment};
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Templates shipped with the application
+static BUNDLED_TEMPLATES: Dir<'_> = include_dir!("$CARGO_MANIFEST_DIR/templates");
+
+/// Suffix of template files
+const TEMPLATE_SUFFIX: &str = ".j2";
+
+#[derive(Debug, Error)]
+pub enum TemplateError {
+    #[error("Failed to read templates folder {path}: {source}")]
+    DirectoryRead {
+        path: String,
+        source: std::io::Error,
+    },
+
+    #[error("Failed to read template {path}: {source}")]
+    FileRead {
+        path: String,
+        source: std::io::Error,
+    },
+
+    #[error("Template {file_name}: {source}")]
+    Invalid {
+        file_name: String,
+        source: minijinja::Error,
+    },
+}
+
+/// Output format of a template, from its file name
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TemplateFormat {
+    Text,
+    Markdown,
+    Html,
+}
+
+impl TemplateFormat {
+    /// Format of a file named `<name>.<extension>.j2`
+    fn from_file_name(file_name: &str) -> Option<Self> {
+        let (_, extension) = file_name.strip_suffix(TEMPLATE_SUFFIX)?.rsplit_once('.')?;
+        match extension {
+            "txt" => Some(Self::Text),
+            "md" => Some(Self::Markdown),
+            "html" => Some(Self::Html),
+            _ => None,
+        }
+    }
+
+    /// Extension of the exported file
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Text => "txt",
+            Self::Markdown => "md",
+            Self::Html => "html",
+        }
+    }
+}
+
+/// An export template
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportTemplate {
+    /// File name, e.g. `handover.md.j2`
+    pub file_name: String,
+    pub format: TemplateFormat,
+    pub source: String,
+    /// Shipped with KlinScore rather than supplied by the user
+    pub bundled: bool,
+}
+
+impl ExportTemplate {
+    fn new(file_name: &str, source: String, bundled: bool) -> Option<Self> {
+        Some(Self {
+            format: TemplateFormat::from_file_name(file_name)?,
+            file_name: file_name.to_string(),
+            source,
+            bundled,
+        })
+    }
+
+    /// Name shown in the template list, e.g. "handover (Markdown)"
+    pub fn display_name(&self) -> String {
+        let name = self
+            .file_name
+            .split('.')
+            .next()
+            .unwrap_or_default()
+            .replace('_', " ");
+        let format = match self.format {
+            TemplateFormat::Text => "Text",
+            TemplateFormat::Markdown => "Markdown",
+            TemplateFormat::Html => "HTML",
+        };
+        format!("{} ({})", name, format)
+    }
+
+    /// Render a result
+    ///
+    /// Templates see `result` (the fields of [`ExportRecord`]), `inputs` (every
+    /// input with `label` and `value`), `language` ("en" or "de") and `disclaimer`.
+    /// The `signed` filter writes points with their sign (`+1`). HTML templates
+    /// escape the values they insert.
+    pub fn render(
+        &self,
+        record: &ExportRecord,
+        inputs: &[PrintedInput],
+        use_german: bool,
+    ) -> Result<String, TemplateError> {
+        let invalid = |source| TemplateError::Invalid {
+            file_name: self.file_name.clone(),
+            source,
+        };
+        let environment = environment();
+        let template = environment
+            .template_from_named_str(&self.file_name, &self.source)
+            .map_err(invalid)?;
+        template
+            .render(context! {
+                result => record,
+                inputs => inputs,
+                language => language_code(use_german),
+                disclaimer => disclaimer_text(use_german),
+            })
+            .map_err(invalid)
+    }
+}
+
+fn environment() -> Environment<'static> {
+    let mut environment = Environment::new();
+    environment.set_keep_trailing_newline(true);
+    environment.add_filter("signed", |points: i32| format!("{:+}", points));
+    environment
+}
+
+/// Folder for the user's templates
+/// (e.g., `~/.local/share/klinscore/templates` on Linux, or below the portable data folder)
+pub fn user_templates_dir() -> Option<PathBuf> {
+    Some(crate::paths::data_dir()?.join("templates"))
+}
+
+/// The bundled templates, replaced or extended by those in `user_dir`, by name
+///
+/// Templates that do not compile are left out and reported in the errors.
+/// A missing `user_dir` is not an error.
+pub fn load_templates(user_dir: Option<&Path>) -> (Vec<ExportTemplate>, Vec<TemplateError>) {
+    let mut templates: Vec<ExportTemplate> = BUNDLED_TEMPLATES
+        .files()
+        .filter_map(|file| {
+            let file_name = file.path().file_name()?.to_str()?;
+            ExportTemplate::new(file_name, file.contents_utf8()?.to_string(), true)
+        })
+        .collect();
+    let mut errors = Vec::new();
+
+    if let Some(dir) = user_dir.filter(|dir| dir.is_dir()) {
+        match user_templates(dir) {
+            Ok((user, user_errors)) => {
+                errors.extend(user_errors);
+                for template in user {
+                    templates.retain(|t| t.file_name != template.file_name);
+                    templates.push(template);
+                }
+            }
+            Err(e) => errors.push(e),
+        }
+    }
+
+    templates.sort_by(|a, b| a.file_name.cmp(&b.file_name));
+    (templates, errors)
+}
+
+/// Templates in `dir` that compile, and errors of those that do not
+fn user_templates(dir: &Path) -> Result<(Vec<ExportTemplate>, Vec<TemplateError>), TemplateError> {
+    let entries = fs::read_dir(dir).map_err(|source| TemplateError::DirectoryRead {
+        path: dir.display().to_string(),
+        source,
+    })?;
+    let environment = environment();
+    let mut templates = Vec::new();
+    let mut errors = Vec::new();
+    for path in entries.flatten().map(|entry| entry.path()) {
+        let Some(file_name) = path.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        if TemplateFormat::from_file_name(file_name).is_none() {
+            continue;
+        }
+        let source = match fs::read_to_string(&path) {
+            Ok(source) => source,
+            Err(source) => {
+                errors.push(TemplateError::FileRead {
+                    path: path.display().to_string(),
+                    source,
+                });
+                continue;
+            }
+        };
+        if let Err(source) = environment.template_from_named_str(file_name, &source) {
+            errors.push(TemplateError::Invalid {
+                file_name: file_name.to_string(),
+                source,
+            });
+            continue;
+        }
+        templates.extend(ExportTemplate::new(file_name, source, false));
+    }
+    Ok((templates, errors))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::ExportFieldEntry;
+    use tempfile::TempDir;
+
+    fn record() -> ExportRecord {
+        ExportRecord {
+            score_name: "HAS-BLED Score".to_string(),
+            total_score: 2,
+            risk: "Moderate".to_string(),
+            recommendation: "Review bleeding risk <3 months".to_string(),
+            details: String::new(),
+            field_breakdown: vec![ExportFieldEntry {
+                field: "hypertension".to_string(),
+                label: "Hypertension".to_string(),
+                points: 1,
+            }],
+            timestamp: "2026-02-12 10:00:00".to_string(),
+            case_id: Some("Bed 4".to_string()),
+            notes: None,
+            patient_copy: false,
+            score_version: Some("2024-v1".to_string()),
+            version_warning: None,
+            references: Vec::new(),
+            provenance: None,
+        }
+    }
+
+    fn inputs() -> Vec<PrintedInput> {
+        vec![PrintedInput {
+            label: "Hypertension".to_string(),
+            value: "Yes".to_string(),
+        }]
+    }
+
+    #[test]
+    fn test_bundled_templates_render() {
+        let (templates, errors) = load_templates(None);
+        assert!(errors.is_empty());
+        assert_eq!(
+            templates.iter().map(|t| t.format).collect::<Vec<_>>(),
+            vec![
+                TemplateFormat::Text,
+                TemplateFormat::Markdown,
+                TemplateFormat::Html
+            ]
+        );
+        for template in &templates {
+            assert!(template.bundled);
+            let output = template.render(&record(), &inputs(), true).unwrap();
+            assert!(output.contains("HAS-BLED Score"), "{}", template.file_name);
+        }
+
+        let note = templates[0].render(&record(), &inputs(), true).unwrap();
+        assert!(note.contains("Risiko: Moderate\n"));
+        assert!(note.contains("  - Hypertension (+1)\n"));
+        let handover = templates[1].render(&record(), &inputs(), false).unwrap();
+        assert!(handover.contains("### HAS-BLED Score – Bed 4"));
+        assert!(handover.contains("| Hypertension | Yes |"));
+        let html = templates[2].render(&record(), &inputs(), false).unwrap();
+        assert!(html.contains("Review bleeding risk &lt;3 months"));
+        assert_eq!(templates[2].display_name(), "report (HTML)");
+    }
+
+    #[test]
+    fn test_user_templates_replace_and_extend() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        fs::write(dir.join("handover.md.j2"), "Ward: {{ result.risk }}").unwrap();
+        fs::write(dir.join("letter.txt.j2"), "{{ result.total_score }}").unwrap();
+        fs::write(dir.join("broken.txt.j2"), "{% if %}").unwrap();
+        fs::write(dir.join("notes.txt"), "not a template").unwrap();
+
+        let (templates, errors) = load_templates(Some(dir));
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].to_string().contains("broken.txt.j2"));
+        let names: Vec<&str> = templates.iter().map(|t| t.file_name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "clinical_note.txt.j2",
+                "handover.md.j2",
+                "letter.txt.j2",
+                "report.html.j2"
+            ]
+        );
+        let handover = &templates[1];
+        assert!(!handover.bundled);
+        assert_eq!(
+            handover.render(&record(), &inputs(), false).unwrap(),
+            "Ward: Moderate"
+        );
+    }
+}
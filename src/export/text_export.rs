@@ -0,0 +1,89 @@
+// text_export.rs
+// Plain-text summary of a calculation result, for pasting into clinical notes
+
+use super::print::PrintLabels;
+use super::ExportRecord;
+use std::fmt::Write;
+
+/// Summarize a result as plain text: score, risk, recommendation and breakdown
+pub fn summary_text(record: &ExportRecord, use_german: bool) -> String {
+    let labels = PrintLabels::for_language(use_german);
+    let mut summary = String::new();
+
+    let _ = writeln!(summary, "{}: {}", record.score_name, record.total_score);
+    let _ = writeln!(summary, "{}: {}", labels.risk, record.risk);
+    for (label, value) in [
+        (labels.recommendation, &record.recommendation),
+        (labels.details, &record.details),
+    ] {
+        if !value.is_empty() {
+            let _ = writeln!(summary, "{label}: {value}");
+        }
+    }
+
+    if !record.field_breakdown.is_empty() {
+        let _ = writeln!(summary, "{}:", labels.breakdown);
+        for field in &record.field_breakdown {
+            let _ = writeln!(summary, "  {}: {:+}", field.label, field.points);
+        }
+        let _ = writeln!(summary, "  {}: {}", labels.total, record.total_score);
+    }
+
+    if let Some(notes) = &record.notes {
+        let _ = writeln!(summary, "{}: {}", labels.notes, notes);
+    }
+
+    let mut footer = labels.meta(record, str::to_string);
+    footer.push("KlinScore".to_string());
+    let _ = writeln!(summary, "{}", footer.join(" · "));
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::ExportFieldEntry;
+
+    #[test]
+    fn test_summary_text() {
+        let record = ExportRecord {
+            score_name: "HAS-BLED Score".to_string(),
+            total_score: 2,
+            risk: "Moderat".to_string(),
+            recommendation: "Blutungsrisiko überprüfen".to_string(),
+            details: String::new(),
+            field_breakdown: vec![
+                ExportFieldEntry {
+                    field: "hypertension".to_string(),
+                    label: "Hypertonie".to_string(),
+                    points: 1,
+                },
+                ExportFieldEntry {
+                    field: "elderly".to_string(),
+                    label: "Alter >65".to_string(),
+                    points: 1,
+                },
+            ],
+            timestamp: "2026-02-12 10:00:00".to_string(),
+            case_id: Some("Bett 4".to_string()),
+            notes: None,
+            patient_copy: false,
+            score_version: None,
+            version_warning: None,
+            references: Vec::new(),
+            provenance: None,
+        };
+
+        assert_eq!(
+            summary_text(&record, true),
+            "HAS-BLED Score: 2\n\
+             Risiko: Moderat\n\
+             Empfehlung: Blutungsrisiko überprüfen\n\
+             Punkteverteilung:\n  \
+             Hypertonie: +1\n  \
+             Alter >65: +1\n  \
+             Summe: 2\n\
+             Berechnet: 2026-02-12 10:00:00 · Fall: Bett 4 · KlinScore\n"
+        );
+    }
+}
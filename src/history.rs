@@ -0,0 +1,662 @@
+// history.rs
+// Calculation history entries and filtering of the history list
+
+use std::collections::{HashMap, HashSet};
+
+use chrono::{Days, Local, NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+
+use crate::config::{self, LocalizedText, RiskLevel, Specialty};
+use crate::export::pdf_export::PdfReport;
+use crate::export::print::PrintedInput;
+use crate::export::{self, ExportRecord};
+use crate::scores::{CalculationResult, InputValue, ScoreLibrary};
+use crate::settings::HistoryRetention;
+use crate::tr;
+use crate::ui::{Language, ScoreInputState, TrendPoint};
+
+/// A single calculation history entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub score_name: LocalizedText,
+    pub score_id: String,
+    pub specialty: Specialty,
+    pub total_score: i32,
+    pub risk: LocalizedText,
+    pub timestamp: String,
+    /// Version of the score definition used (absent in entries saved before versions were recorded)
+    #[serde(default)]
+    pub score_version: Option<String>,
+    /// Inputs as entered, for reopening the calculation (empty in older entries)
+    #[serde(default)]
+    pub inputs: HashMap<String, InputValue>,
+    // Interpretation and points per criterion (empty in older entries)
+    #[serde(default)]
+    pub recommendation: LocalizedText,
+    #[serde(default)]
+    pub details: LocalizedText,
+    #[serde(default)]
+    pub field_scores: Vec<HistoryFieldScore>,
+    /// Optional patient label entered with the calculation (e.g., "Bed 12")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub patient_label: Option<String>,
+    /// Session the calculation was part of, if one was running
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session: Option<Session>,
+    /// Risk level of the interpretation (absent in older entries)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub risk_level: Option<RiskLevel>,
+    /// Reproducibility hash of the calculation (absent in older entries)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provenance: Option<String>,
+}
+
+/// Several calculations for one patient, e.g. ASA, RCRI and STOP-BANG of a
+/// pre-op assessment, grouped in the history and printed as one report
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Session {
+    /// Unique id, from the start time
+    pub id: String,
+    /// Title entered when starting the session (e.g., "Pre-op Bed 12")
+    pub title: String,
+}
+
+impl Session {
+    pub fn start(title: &str) -> Self {
+        Self {
+            id: Local::now().format("%Y%m%d-%H%M%S%.3f").to_string(),
+            title: title.trim().to_string(),
+        }
+    }
+}
+
+/// Points of one criterion in a history entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryFieldScore {
+    pub field: String,
+    pub label: LocalizedText,
+    /// Entered value or selected option; `None` if left empty
+    #[serde(default)]
+    pub selection: Option<LocalizedText>,
+    pub points: i32,
+}
+
+impl HistoryEntry {
+    /// Record a calculation of `score_def` (loaded as `score_id`) with its inputs,
+    /// breakdown and interpretation
+    pub fn new(
+        score_id: &str,
+        score_def: &config::ScoreDefinition,
+        specialty: Specialty,
+        result: &CalculationResult,
+        input_state: &ScoreInputState,
+    ) -> Self {
+        let field_scores = result
+            .field_scores
+            .iter()
+            .map(|fs| HistoryFieldScore {
+                field: fs.field.clone(),
+                label: fs.label.clone(),
+                selection: fs.selection.clone(),
+                points: fs.points,
+            })
+            .collect();
+
+        Self {
+            score_name: score_def.name.clone(),
+            score_id: score_id.to_string(),
+            specialty,
+            total_score: result.total_score,
+            risk: result.risk.clone(),
+            timestamp: Local::now().format("%Y-%m-%d %H:%M").to_string(),
+            score_version: Some(score_def.version.clone()),
+            inputs: input_state.inputs.clone(),
+            recommendation: result.recommendation.clone(),
+            details: result.details.clone().unwrap_or_default(),
+            field_scores,
+            patient_label: input_state.patient_label(),
+            session: None,
+            risk_level: Some(result.risk_level),
+            provenance: Some(result.provenance.clone()).filter(|hash| !hash.is_empty()),
+        }
+    }
+
+    /// Lowercase text the history search matches against: score names and
+    /// risk in every language, the patient label and the session title
+    fn search_text(&self) -> String {
+        self.score_name
+            .values()
+            .chain(self.risk.values())
+            .map(String::as_str)
+            .chain(self.patient_label.as_deref())
+            .chain(self.session.as_ref().map(|session| session.title.as_str()))
+            .map(str::to_lowercase)
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Id of the session the calculation was part of
+    pub fn session_id(&self) -> Option<&str> {
+        self.session.as_ref().map(|session| session.id.as_str())
+    }
+
+    /// Inputs as entered, for the print layout
+    pub fn printed_inputs(&self, language: Language) -> Vec<PrintedInput> {
+        self.field_scores
+            .iter()
+            .map(|fs| PrintedInput {
+                label: fs.label.text(language.code()).to_string(),
+                value: fs
+                    .selection
+                    .as_ref()
+                    .map(|selection| selection.text(language.code()).to_string())
+                    .unwrap_or_default(),
+            })
+            .collect()
+    }
+
+    /// Day of the calculation, from the timestamp
+    pub fn date(&self) -> Option<NaiveDate> {
+        let day = self.timestamp.get(..10)?;
+        NaiveDate::parse_from_str(day, "%Y-%m-%d").ok()
+    }
+
+    /// Warning shown when this entry was computed with a different definition version
+    /// than the one currently loaded. `None` if up to date or the score is no longer loaded.
+    pub fn version_warning(
+        &self,
+        library: Option<&ScoreLibrary>,
+        language: Language,
+    ) -> Option<String> {
+        let current = &library?.get_score(&self.score_id)?.version;
+        if self.score_version.as_ref() == Some(current) {
+            return None;
+        }
+
+        Some(match self.score_version.as_deref() {
+            Some(recorded) => tr!(
+                language,
+                "history-version-changed",
+                recorded = recorded,
+                current = current.as_str()
+            ),
+            None => tr!(
+                language,
+                "history-version-unknown",
+                current = current.as_str()
+            ),
+        })
+    }
+
+    /// Convert to an export record
+    pub fn to_export_record(
+        &self,
+        language: Language,
+        library: Option<&ScoreLibrary>,
+    ) -> ExportRecord {
+        let code = language.code();
+        // Same criteria as exports of a fresh result: those that contributed points
+        let field_breakdown = self
+            .field_scores
+            .iter()
+            .filter(|fs| fs.points != 0)
+            .map(|fs| export::ExportFieldEntry {
+                field: fs.field.clone(),
+                label: fs.label.text(code).to_string(),
+                points: fs.points,
+            })
+            .collect();
+        ExportRecord {
+            score_name: self.score_name.text(code).to_string(),
+            total_score: self.total_score,
+            risk: self.risk.text(code).to_string(),
+            recommendation: self.recommendation.text(code).to_string(),
+            details: self.details.text(code).to_string(),
+            field_breakdown,
+            timestamp: self.timestamp.clone(),
+            case_id: self.patient_label.clone(),
+            notes: None,
+            patient_copy: false,
+            score_version: self.score_version.clone(),
+            version_warning: self.version_warning(library, language),
+            references: Vec::new(),
+            provenance: self.provenance.clone(),
+        }
+    }
+
+    /// Convert to a PDF report, with the other language of a bilingual report
+    /// (German or English)
+    pub fn to_pdf_report(
+        &self,
+        language: Language,
+        library: Option<&ScoreLibrary>,
+        bilingual: bool,
+    ) -> PdfReport {
+        let translation = bilingual.then(|| {
+            let other = if language == Language::German {
+                Language::English
+            } else {
+                Language::German
+            };
+            (
+                self.to_export_record(other, library),
+                self.printed_inputs(other),
+            )
+        });
+        PdfReport {
+            record: self.to_export_record(language, library),
+            inputs: self.printed_inputs(language),
+            risk_level: self.risk_level,
+            translation,
+        }
+    }
+}
+
+/// Criteria narrowing down the history list; unset criteria match every entry
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HistoryFilter {
+    pub specialty: Option<Specialty>,
+    pub score_id: Option<String>,
+    /// First day shown (inclusive)
+    pub from: Option<NaiveDate>,
+    /// Last day shown (inclusive)
+    pub to: Option<NaiveDate>,
+    /// Free text; every word must occur in the entry's searchable text
+    pub query: String,
+}
+
+impl HistoryFilter {
+    pub fn is_active(&self) -> bool {
+        self.specialty.is_some()
+            || self.score_id.is_some()
+            || self.from.is_some()
+            || self.to.is_some()
+            || !self.query.trim().is_empty()
+    }
+
+    pub fn matches(&self, entry: &HistoryEntry) -> bool {
+        if !self.query.trim().is_empty() {
+            let haystack = entry.search_text();
+            if !self
+                .query
+                .to_lowercase()
+                .split_whitespace()
+                .all(|word| haystack.contains(word))
+            {
+                return false;
+            }
+        }
+        if self
+            .specialty
+            .is_some_and(|specialty| specialty != entry.specialty)
+        {
+            return false;
+        }
+        if self
+            .score_id
+            .as_ref()
+            .is_some_and(|score_id| *score_id != entry.score_id)
+        {
+            return false;
+        }
+        if self.from.is_none() && self.to.is_none() {
+            return true;
+        }
+        // Entries without a readable date only show up without a date range
+        entry.date().is_some_and(|date| {
+            self.from.is_none_or(|from| date >= from) && self.to.is_none_or(|to| date <= to)
+        })
+    }
+}
+
+/// Number of history entries rendered at first and added by each "show more"
+pub const PAGE_SIZE: usize = 50;
+
+/// Entries matching `filter` with their index in `history`, newest first
+pub fn filter_history<'a>(
+    history: &'a [HistoryEntry],
+    filter: &'a HistoryFilter,
+) -> impl Iterator<Item = (usize, &'a HistoryEntry)> + 'a {
+    history
+        .iter()
+        .enumerate()
+        .rev()
+        .filter(move |(_, entry)| filter.matches(entry))
+}
+
+/// Entries of the session `session_id`, oldest first
+pub fn session_entries<'a>(
+    history: &'a [HistoryEntry],
+    session_id: &'a str,
+) -> impl Iterator<Item = &'a HistoryEntry> + 'a {
+    history
+        .iter()
+        .filter(move |entry| entry.session_id() == Some(session_id))
+}
+
+/// Repeated calculations of formula scores (e.g., eGFR, KFRE) for the same
+/// patient label, as trend values oldest first
+///
+/// Only series with at least two values are returned, keyed by the index of
+/// their newest entry, where the history shows the chart.
+pub fn formula_trends(
+    history: &[HistoryEntry],
+    is_formula: impl Fn(&str) -> bool,
+) -> HashMap<usize, Vec<TrendPoint>> {
+    let mut series: HashMap<(&str, &str), (usize, Vec<TrendPoint>)> = HashMap::new();
+    for (index, entry) in history.iter().enumerate() {
+        let Some(label) = entry.patient_label.as_deref() else {
+            continue;
+        };
+        if !is_formula(&entry.score_id) {
+            continue;
+        }
+        let Ok(time) = NaiveDateTime::parse_from_str(&entry.timestamp, "%Y-%m-%d %H:%M") else {
+            continue;
+        };
+        let (newest, points) = series.entry((&entry.score_id, label)).or_default();
+        *newest = index;
+        points.push(TrendPoint {
+            time: time.and_utc().timestamp(),
+            value: f64::from(entry.total_score),
+            label: entry.timestamp[..10].to_string(),
+        });
+    }
+    series
+        .into_values()
+        .filter(|(_, points)| points.len() >= 2)
+        .collect()
+}
+
+/// Merge entries imported from another installation into `history`, skipping
+/// those already present (same timestamp and score). Keeps `history` in
+/// chronological order; returns the entries added.
+pub fn merge_history(
+    history: &mut Vec<HistoryEntry>,
+    imported: Vec<HistoryEntry>,
+) -> Vec<HistoryEntry> {
+    let mut known: HashSet<(String, String)> = history
+        .iter()
+        .map(|entry| (entry.timestamp.clone(), entry.score_id.clone()))
+        .collect();
+    let added: Vec<HistoryEntry> = imported
+        .into_iter()
+        .filter(|entry| known.insert((entry.timestamp.clone(), entry.score_id.clone())))
+        .collect();
+    history.extend(added.iter().cloned());
+    // Timestamps are ISO formatted, so they sort chronologically; the sort is
+    // stable, so entries of the same minute keep their order
+    history.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+    added
+}
+
+/// Remove the entries `retention` does not keep, as of `today`; returns the
+/// removed entries. Entries without a readable date are only removed by an entry limit.
+pub fn apply_retention(
+    history: &mut Vec<HistoryEntry>,
+    retention: HistoryRetention,
+    today: NaiveDate,
+) -> Vec<HistoryEntry> {
+    match retention {
+        HistoryRetention::KeepAll => Vec::new(),
+        HistoryRetention::LastEntries(n) => {
+            let excess = history.len().saturating_sub(n as usize);
+            history.drain(..excess).collect()
+        }
+        HistoryRetention::LastDays(n) => {
+            let first_kept = today
+                .checked_sub_days(Days::new(u64::from(n.saturating_sub(1))))
+                .unwrap_or(NaiveDate::MIN);
+            let (kept, removed) = std::mem::take(history)
+                .into_iter()
+                .partition(|entry| entry.date().is_none_or(|date| date >= first_kept));
+            *history = kept;
+            removed
+        }
+    }
+}
+
+/// Parse a date typed into a filter field: "2026-02-12" or German "12.02.2026"
+pub fn parse_date(input: &str) -> Option<NaiveDate> {
+    let input = input.trim();
+    NaiveDate::parse_from_str(input, "%Y-%m-%d")
+        .or_else(|_| NaiveDate::parse_from_str(input, "%d.%m.%Y"))
+        .ok()
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::export::session_report::SessionReport;
+
+    pub(crate) fn entry(score_id: &str, specialty: Specialty, timestamp: &str) -> HistoryEntry {
+        HistoryEntry {
+            score_name: LocalizedText::from(score_id),
+            score_id: score_id.to_string(),
+            specialty,
+            total_score: 1,
+            risk: LocalizedText::bilingual("Low", "Niedrig"),
+            timestamp: timestamp.to_string(),
+            score_version: None,
+            inputs: HashMap::new(),
+            recommendation: LocalizedText::new(),
+            details: LocalizedText::new(),
+            field_scores: Vec::new(),
+            patient_label: None,
+            session: None,
+            risk_level: None,
+            provenance: None,
+        }
+    }
+
+    #[test]
+    fn test_history_filter() {
+        let history = vec![
+            entry("has_bled", Specialty::Cardiology, "2026-01-10 08:00"),
+            entry("rcri", Specialty::Anesthesiology, "2026-02-01 09:30"),
+            entry("has_bled", Specialty::Cardiology, "2026-02-12 10:00"),
+        ];
+
+        let all = HistoryFilter::default();
+        assert!(!all.is_active());
+        let indices: Vec<usize> = filter_history(&history, &all).map(|(i, _)| i).collect();
+        assert_eq!(indices, vec![2, 1, 0]);
+
+        let cardiology = HistoryFilter {
+            specialty: Some(Specialty::Cardiology),
+            ..HistoryFilter::default()
+        };
+        assert_eq!(filter_history(&history, &cardiology).count(), 2);
+
+        // Date range is inclusive on both ends
+        let february_has_bled = HistoryFilter {
+            score_id: Some("has_bled".to_string()),
+            from: parse_date("01.02.2026"),
+            to: parse_date("2026-02-12"),
+            ..HistoryFilter::default()
+        };
+        let indices: Vec<usize> = filter_history(&history, &february_has_bled)
+            .map(|(i, _)| i)
+            .collect();
+        assert_eq!(indices, vec![2]);
+
+        // Search works in both languages, word by word
+        let search = |query: &str| HistoryFilter {
+            query: query.to_string(),
+            ..HistoryFilter::default()
+        };
+        assert_eq!(filter_history(&history, &search("niedrig RCRI")).count(), 1);
+        assert_eq!(filter_history(&history, &search("low")).count(), 3);
+        assert_eq!(filter_history(&history, &search("high")).count(), 0);
+        let mut history = history;
+        history[0].patient_label = Some("Bett 12".to_string());
+        assert_eq!(filter_history(&history, &search("bett 12")).count(), 1);
+        assert!(!search("  ").is_active());
+
+        assert_eq!(parse_date("31.02.2026"), None);
+        assert_eq!(parse_date("02/12/2026"), None);
+        assert_eq!(parse_date(""), None);
+    }
+
+    #[test]
+    fn test_session_report() {
+        let session = Session {
+            id: "20260212-100000.000".to_string(),
+            title: "Prä-OP Bett 12".to_string(),
+        };
+        let mut history = vec![
+            entry("asa", Specialty::Anesthesiology, "2026-02-12 10:00"),
+            entry("has_bled", Specialty::Cardiology, "2026-02-12 10:02"),
+            entry("rcri", Specialty::Anesthesiology, "2026-02-12 10:05"),
+        ];
+        history[0].session = Some(session.clone());
+        history[2].session = Some(session.clone());
+        history[2].field_scores.push(HistoryFieldScore {
+            field: "high_risk_surgery".to_string(),
+            label: LocalizedText::bilingual("High-risk surgery", "Hochrisiko-Eingriff"),
+            selection: Some(LocalizedText::bilingual("Yes", "Ja")),
+            points: 1,
+        });
+
+        let ids: Vec<&str> = session_entries(&history, &session.id)
+            .map(|entry| entry.score_id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["asa", "rcri"]);
+
+        let report = SessionReport {
+            title: session.title.clone(),
+            reports: session_entries(&history, &session.id)
+                .map(|entry| entry.to_pdf_report(Language::German, None, false))
+                .collect(),
+        };
+        let html = report.to_html(true);
+        assert!(html.contains("<h1>Prä-OP Bett 12</h1>"));
+        assert!(html.contains("<h2>asa</h2>"));
+        assert!(html.contains("<h2>rcri</h2>"));
+        assert!(!html.contains("has_bled"));
+        assert!(html.contains("<tr><td>Hochrisiko-Eingriff</td><td>Ja</td></tr>"));
+        assert_eq!(session_entries(&history, "other").count(), 0);
+
+        // Bilingual PDF reports carry the English texts alongside the German ones
+        let report = history[2].to_pdf_report(Language::German, None, true);
+        assert_eq!(report.inputs[0].label, "Hochrisiko-Eingriff");
+        let (translation, inputs) = report.translation.unwrap();
+        assert_eq!(translation.risk, "Low");
+        assert_eq!(inputs[0].value, "Yes");
+        assert!(history[2]
+            .to_pdf_report(Language::English, None, false)
+            .translation
+            .is_none());
+
+        // The session title is searchable
+        let filter = HistoryFilter {
+            query: "prä-op".to_string(),
+            ..HistoryFilter::default()
+        };
+        assert_eq!(filter_history(&history, &filter).count(), 2);
+    }
+
+    #[test]
+    fn test_formula_trends() {
+        let mut history = vec![
+            entry("egfr", Specialty::Nephrology, "2026-01-10 08:00"),
+            entry("egfr", Specialty::Nephrology, "2026-01-12 08:00"),
+            entry("has_bled", Specialty::Cardiology, "2026-01-20 09:00"),
+            entry("egfr", Specialty::Nephrology, "2026-02-12 10:00"),
+            entry("egfr", Specialty::Nephrology, "2026-02-13 10:00"),
+        ];
+        for (index, value) in [(0, 58), (1, 40), (3, 52), (4, 45)] {
+            history[index].total_score = value;
+        }
+        for index in [0, 2, 3] {
+            history[index].patient_label = Some("Bett 12".to_string());
+        }
+        history[1].patient_label = Some("Bett 4".to_string());
+
+        // Only "Bett 12" has two eGFR values; "Bett 4" has one, entry 4 no label
+        let trends = formula_trends(&history, |score_id| score_id == "egfr");
+        assert_eq!(trends.len(), 1);
+        let points = &trends[&3];
+        let values: Vec<f64> = points.iter().map(|p| p.value).collect();
+        assert_eq!(values, vec![58.0, 52.0]);
+        assert_eq!(points[0].label, "2026-01-10");
+        assert!(points[0].time < points[1].time);
+
+        // Point scores get no chart
+        assert!(formula_trends(&history, |_| false).is_empty());
+    }
+
+    #[test]
+    fn test_apply_retention() {
+        let history = vec![
+            entry("rcri", Specialty::Anesthesiology, "2026-01-10 08:00"),
+            entry("rcri", Specialty::Anesthesiology, "unknown"),
+            entry("asa", Specialty::Anesthesiology, "2026-02-11 09:30"),
+            entry("asa", Specialty::Anesthesiology, "2026-02-12 10:00"),
+        ];
+        let today = parse_date("2026-02-12").unwrap();
+
+        let mut kept = history.clone();
+        assert_eq!(
+            apply_retention(&mut kept, HistoryRetention::KeepAll, today).len(),
+            0
+        );
+        assert_eq!(kept.len(), 4);
+
+        // The newest entries are at the end
+        let mut kept = history.clone();
+        let removed = apply_retention(&mut kept, HistoryRetention::LastEntries(2), today);
+        assert_eq!(removed.len(), 2);
+        assert_eq!(removed[0].timestamp, "2026-01-10 08:00");
+        assert_eq!(kept[0].timestamp, "2026-02-11 09:30");
+
+        // Two days: yesterday and today; the undated entry stays
+        let mut kept = history.clone();
+        assert_eq!(
+            apply_retention(&mut kept, HistoryRetention::LastDays(2), today).len(),
+            1
+        );
+        assert_eq!(kept[0].timestamp, "unknown");
+
+        let mut kept = history;
+        apply_retention(&mut kept, HistoryRetention::LastDays(1), today);
+        assert_eq!(kept.len(), 2);
+    }
+
+    #[test]
+    fn test_merge_history() {
+        let mut history = vec![
+            entry("rcri", Specialty::Anesthesiology, "2026-02-01 09:30"),
+            entry("asa", Specialty::Anesthesiology, "2026-02-12 10:00"),
+        ];
+        let imported = vec![
+            entry("rcri", Specialty::Anesthesiology, "2026-01-10 08:00"),
+            // Already present
+            entry("asa", Specialty::Anesthesiology, "2026-02-12 10:00"),
+            // Same time, different score
+            entry("stop_bang", Specialty::Anesthesiology, "2026-02-12 10:00"),
+            // Twice in the imported file
+            entry("egfr", Specialty::Nephrology, "2026-02-13 11:00"),
+            entry("egfr", Specialty::Nephrology, "2026-02-13 11:00"),
+        ];
+
+        assert_eq!(merge_history(&mut history, imported.clone()).len(), 3);
+        let order: Vec<(&str, &str)> = history
+            .iter()
+            .map(|e| (e.timestamp.as_str(), e.score_id.as_str()))
+            .collect();
+        assert_eq!(
+            order,
+            vec![
+                ("2026-01-10 08:00", "rcri"),
+                ("2026-02-01 09:30", "rcri"),
+                ("2026-02-12 10:00", "asa"),
+                ("2026-02-12 10:00", "stop_bang"),
+                ("2026-02-13 11:00", "egfr"),
+            ]
+        );
+
+        // Importing the same file again adds nothing
+        assert!(merge_history(&mut history, imported).is_empty());
+    }
+}
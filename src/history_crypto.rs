@@ -0,0 +1,163 @@
+// history_crypto.rs
+// Passphrase encryption of the history file (age, with an argon2id-derived key)
+
+use std::str::FromStr;
+
+use age::x25519::{Identity, Recipient};
+use argon2::Argon2;
+use bech32::{ToBase32, Variant};
+use rand::RngCore;
+use thiserror::Error;
+
+/// First line of an encrypted history file, followed by the salt line and the
+/// age ciphertext
+const HEADER: &str = "klinscore-encrypted-history v1";
+const SALT_PREFIX: &str = "salt: ";
+const SALT_BYTES: usize = 16;
+
+/// Shortest passphrase accepted when enabling encryption
+pub const MIN_PASSPHRASE_CHARS: usize = 8;
+
+#[derive(Debug, Error)]
+pub enum HistoryCryptoError {
+    #[error("wrong passphrase")]
+    WrongPassphrase,
+
+    #[error("not an encrypted KlinScore history: {0}")]
+    InvalidFile(String),
+
+    #[error("key derivation failed: {0}")]
+    KeyDerivation(String),
+
+    #[error("encryption failed: {0}")]
+    Encryption(String),
+
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// Key of an encrypted history file: an age identity derived from the passphrase.
+/// The passphrase itself is not kept.
+#[derive(Clone)]
+pub struct HistoryKey {
+    salt: [u8; SALT_BYTES],
+    identity: Identity,
+}
+
+impl std::fmt::Debug for HistoryKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("HistoryKey(..)")
+    }
+}
+
+impl HistoryKey {
+    /// Key for a new encrypted file, with a random salt
+    pub fn new(passphrase: &str) -> Result<Self, HistoryCryptoError> {
+        let mut salt = [0u8; SALT_BYTES];
+        rand::rngs::OsRng.fill_bytes(&mut salt);
+        Self::derive(passphrase, salt)
+    }
+
+    /// Derive the age identity from the passphrase with argon2id (default parameters)
+    fn derive(passphrase: &str, salt: [u8; SALT_BYTES]) -> Result<Self, HistoryCryptoError> {
+        let mut secret = [0u8; 32];
+        Argon2::default()
+            .hash_password_into(passphrase.as_bytes(), &salt, &mut secret)
+            .map_err(|e| HistoryCryptoError::KeyDerivation(e.to_string()))?;
+        // age only builds identities from their Bech32 text form
+        let encoded = bech32::encode("age-secret-key-", secret.to_base32(), Variant::Bech32)
+            .map_err(|e| HistoryCryptoError::KeyDerivation(e.to_string()))?;
+        secret.fill(0);
+        let identity = Identity::from_str(&encoded.to_uppercase())
+            .map_err(|e| HistoryCryptoError::KeyDerivation(e.to_string()))?;
+        Ok(Self { salt, identity })
+    }
+
+    /// Open an encrypted file: derive the key from `passphrase` and the file's
+    /// salt, then decrypt. Returns the key (for saving again) and the plaintext.
+    pub fn unlock(passphrase: &str, file: &[u8]) -> Result<(Self, Vec<u8>), HistoryCryptoError> {
+        let (salt, _) = parse_file(file)?;
+        let key = Self::derive(passphrase, salt)?;
+        let plaintext = key.decrypt(file)?;
+        Ok((key, plaintext))
+    }
+
+    /// Plaintext of an encrypted file written with this key
+    pub fn decrypt(&self, file: &[u8]) -> Result<Vec<u8>, HistoryCryptoError> {
+        let (_, ciphertext) = parse_file(file)?;
+        age::decrypt(&self.identity, ciphertext).map_err(|e| match e {
+            age::DecryptError::NoMatchingKeys => HistoryCryptoError::WrongPassphrase,
+            e => HistoryCryptoError::InvalidFile(e.to_string()),
+        })
+    }
+
+    /// Encrypted file contents for `plaintext`
+    pub fn encrypt(&self, plaintext: &[u8]) -> Result<Vec<u8>, HistoryCryptoError> {
+        let recipient: Recipient = self.identity.to_public();
+        let ciphertext = age::encrypt(&recipient, plaintext)
+            .map_err(|e| HistoryCryptoError::Encryption(e.to_string()))?;
+        let salt_hex: String = self.salt.iter().map(|b| format!("{:02x}", b)).collect();
+        let mut file = format!("{}\n{}{}\n", HEADER, SALT_PREFIX, salt_hex).into_bytes();
+        file.extend_from_slice(&ciphertext);
+        Ok(file)
+    }
+}
+
+/// Split an encrypted file into salt and age ciphertext
+fn parse_file(file: &[u8]) -> Result<([u8; SALT_BYTES], &[u8]), HistoryCryptoError> {
+    let invalid = |reason: &str| HistoryCryptoError::InvalidFile(reason.to_string());
+    let mut lines = file.splitn(3, |&b| b == b'\n');
+    if lines.next() != Some(HEADER.as_bytes()) {
+        return Err(invalid("missing header"));
+    }
+    let salt_line = lines
+        .next()
+        .and_then(|line| std::str::from_utf8(line).ok())
+        .and_then(|line| line.strip_prefix(SALT_PREFIX))
+        .ok_or_else(|| invalid("missing salt"))?;
+    let ciphertext = lines.next().ok_or_else(|| invalid("missing ciphertext"))?;
+
+    let mut salt = [0u8; SALT_BYTES];
+    if salt_line.len() != SALT_BYTES * 2 {
+        return Err(invalid("invalid salt"));
+    }
+    for (i, byte) in salt.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&salt_line[i * 2..i * 2 + 2], 16)
+            .map_err(|_| invalid("invalid salt"))?;
+    }
+    Ok((salt, ciphertext))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encrypt_and_unlock() {
+        let key = HistoryKey::new("correct horse battery staple").unwrap();
+        let plaintext = br#"[{"score_id":"egfr"}]"#;
+        let file = key.encrypt(plaintext).unwrap();
+
+        assert!(file.starts_with(HEADER.as_bytes()));
+        assert!(!file.windows(4).any(|w| w == b"egfr"));
+        assert_eq!(key.decrypt(&file).unwrap(), plaintext);
+
+        let (unlocked, decrypted) =
+            HistoryKey::unlock("correct horse battery staple", &file).unwrap();
+        assert_eq!(decrypted, plaintext);
+
+        // The unlocked key writes files the passphrase opens again
+        let again = unlocked.encrypt(b"[]").unwrap();
+        let (_, decrypted) = HistoryKey::unlock("correct horse battery staple", &again).unwrap();
+        assert_eq!(decrypted, b"[]");
+
+        assert!(matches!(
+            HistoryKey::unlock("wrong", &file),
+            Err(HistoryCryptoError::WrongPassphrase)
+        ));
+        assert!(matches!(
+            HistoryKey::unlock("correct horse battery staple", b"[]"),
+            Err(HistoryCryptoError::InvalidFile(_))
+        ));
+    }
+}
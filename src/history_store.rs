@@ -0,0 +1,482 @@
+// history_store.rs
+// Storage backends of the calculation history (SQLite, JSON file, encrypted file)
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use rusqlite::{params, params_from_iter, Connection, Transaction, TransactionBehavior};
+use thiserror::Error;
+
+use crate::config::Specialty;
+use crate::history::{HistoryEntry, HistoryFilter};
+use crate::history_crypto::{HistoryCryptoError, HistoryKey};
+use crate::migration::{self, HistoryFile, MigrationError};
+use crate::persistence::{replace_with_backup, write_atomic};
+
+#[derive(Debug, Error)]
+pub enum HistoryStoreError {
+    #[error("database error: {0}")]
+    Database(#[from] rusqlite::Error),
+
+    #[error("invalid history data: {0}")]
+    InvalidData(#[from] serde_json::Error),
+
+    #[error("invalid history file: {0}")]
+    Format(#[from] MigrationError),
+
+    #[error(transparent)]
+    Crypto(#[from] HistoryCryptoError),
+
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// Where the history is kept on disk
+pub trait HistoryStore {
+    /// All entries, oldest first
+    fn load(&self) -> Result<Vec<HistoryEntry>, HistoryStoreError>;
+
+    /// Replace all stored entries with `history`, e.g. when the history moves to
+    /// another store. Entries another running instance stored in the meantime are
+    /// lost; record calculations with [`insert`](Self::insert) and
+    /// [`remove`](Self::remove) instead.
+    fn save(&self, history: &[HistoryEntry]) -> Result<(), HistoryStoreError>;
+
+    /// Add `entries` to the stored ones
+    fn insert(&self, entries: &[HistoryEntry]) -> Result<(), HistoryStoreError> {
+        if entries.is_empty() {
+            return Ok(());
+        }
+        let mut history = self.load()?;
+        history.extend_from_slice(entries);
+        self.save(&history)
+    }
+
+    /// Remove one stored entry with the same timestamp and score as each of
+    /// `entries`, the oldest first
+    fn remove(&self, entries: &[HistoryEntry]) -> Result<(), HistoryStoreError> {
+        if entries.is_empty() {
+            return Ok(());
+        }
+        let mut history = self.load()?;
+        for entry in entries {
+            if let Some(index) = history.iter().position(|stored| same_entry(stored, entry)) {
+                history.remove(index);
+            }
+        }
+        self.save(&history)
+    }
+
+    /// Entries matching `filter`, oldest first
+    fn query(&self, filter: &HistoryFilter) -> Result<Vec<HistoryEntry>, HistoryStoreError> {
+        let mut history = self.load()?;
+        history.retain(|entry| filter.matches(entry));
+        Ok(history)
+    }
+}
+
+/// Layout of the `history` table; stored in `PRAGMA user_version`
+///
+/// 1: entries with English texts and `_de` twins
+/// 2: entries with texts per language
+const SCHEMA_VERSION: i32 = 2;
+
+/// Each entry is kept as JSON, next to the columns the filters query
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS history (
+    id INTEGER PRIMARY KEY,
+    timestamp TEXT NOT NULL,
+    date TEXT,
+    score_id TEXT NOT NULL,
+    specialty TEXT NOT NULL,
+    entry TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS history_date ON history (date);
+CREATE INDEX IF NOT EXISTS history_score ON history (score_id, date);
+CREATE INDEX IF NOT EXISTS history_specialty ON history (specialty, date);
+";
+
+/// How long a write waits for another instance holding the database lock
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// History in an SQLite database. Every write is one transaction, so a crash
+/// never leaves a half-written history, and [`insert`](HistoryStore::insert) and
+/// [`remove`](HistoryStore::remove) leave the entries of other running instances
+/// in place.
+pub struct SqliteHistoryStore {
+    path: PathBuf,
+}
+
+impl SqliteHistoryStore {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    fn open(&self) -> Result<Connection, HistoryStoreError> {
+        let mut connection = Connection::open(&self.path)?;
+        connection.busy_timeout(BUSY_TIMEOUT)?;
+        // Readers do not block the writer (and vice versa)
+        connection
+            .pragma_update_and_check(None, "journal_mode", "WAL", |row| row.get::<_, String>(0))?;
+        let version: i32 = connection.pragma_query_value(None, "user_version", |row| row.get(0))?;
+        if version < SCHEMA_VERSION {
+            upgrade_schema(&mut connection)?;
+        }
+        Ok(connection)
+    }
+
+    /// Write a consistent copy of the database to `path`, replacing it atomically
+    pub fn backup_to(&self, path: &Path) -> Result<(), HistoryStoreError> {
+        let mut temp = path.as_os_str().to_owned();
+        temp.push(".tmp");
+        let temp = PathBuf::from(temp);
+        if temp.exists() {
+            fs::remove_file(&temp)?;
+        }
+        self.open()?
+            .execute("VACUUM INTO ?1", [temp.to_string_lossy()])?;
+        fs::rename(&temp, path)?;
+        Ok(())
+    }
+}
+
+/// Create the table, or bring the stored entries up to `SCHEMA_VERSION`
+fn upgrade_schema(connection: &mut Connection) -> Result<(), HistoryStoreError> {
+    let transaction = connection.transaction_with_behavior(TransactionBehavior::Immediate)?;
+    // Another instance may have upgraded while this one waited for the lock
+    let version: i32 = transaction.pragma_query_value(None, "user_version", |row| row.get(0))?;
+    if version >= SCHEMA_VERSION {
+        return Ok(());
+    }
+    transaction.execute_batch(SCHEMA)?;
+    if version == 1 {
+        let rows = transaction
+            .prepare("SELECT id, entry FROM history")?
+            .query_map([], |row| {
+                Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut update = transaction.prepare("UPDATE history SET entry = ?1 WHERE id = ?2")?;
+        for (id, json) in rows {
+            let mut entry: serde_json::Value = serde_json::from_str(&json)?;
+            migration::upgrade_bilingual_entry(&mut entry);
+            update.execute(params![serde_json::to_string(&entry)?, id])?;
+        }
+    }
+    transaction.pragma_update(None, "user_version", SCHEMA_VERSION)?;
+    transaction.commit()?;
+    Ok(())
+}
+
+/// Whether `a` and `b` are the same calculation: same timestamp and score, as
+/// when merging an imported history
+fn same_entry(a: &HistoryEntry, b: &HistoryEntry) -> bool {
+    a.timestamp == b.timestamp && a.score_id == b.score_id
+}
+
+/// Value of the `specialty` column: the variant name
+fn specialty_key(specialty: Specialty) -> String {
+    format!("{:?}", specialty)
+}
+
+fn insert_entries(
+    transaction: &Transaction,
+    entries: &[HistoryEntry],
+) -> Result<(), HistoryStoreError> {
+    let mut insert = transaction.prepare(
+        "INSERT INTO history (timestamp, date, score_id, specialty, entry)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+    )?;
+    for entry in entries {
+        insert.execute(params![
+            entry.timestamp,
+            entry.date().map(|date| date.format("%Y-%m-%d").to_string()),
+            entry.score_id,
+            specialty_key(entry.specialty),
+            serde_json::to_string(entry)?,
+        ])?;
+    }
+    Ok(())
+}
+
+fn parse_entries(rows: Vec<String>) -> Result<Vec<HistoryEntry>, HistoryStoreError> {
+    rows.iter()
+        .map(|json| serde_json::from_str(json).map_err(HistoryStoreError::from))
+        .collect()
+}
+
+impl HistoryStore for SqliteHistoryStore {
+    fn load(&self) -> Result<Vec<HistoryEntry>, HistoryStoreError> {
+        self.query(&HistoryFilter::default())
+    }
+
+    fn save(&self, history: &[HistoryEntry]) -> Result<(), HistoryStoreError> {
+        let mut connection = self.open()?;
+        let transaction = connection.transaction_with_behavior(TransactionBehavior::Immediate)?;
+        transaction.execute("DELETE FROM history", [])?;
+        insert_entries(&transaction, history)?;
+        transaction.commit()?;
+        Ok(())
+    }
+
+    fn insert(&self, entries: &[HistoryEntry]) -> Result<(), HistoryStoreError> {
+        let mut connection = self.open()?;
+        let transaction = connection.transaction_with_behavior(TransactionBehavior::Immediate)?;
+        insert_entries(&transaction, entries)?;
+        transaction.commit()?;
+        Ok(())
+    }
+
+    fn remove(&self, entries: &[HistoryEntry]) -> Result<(), HistoryStoreError> {
+        let mut connection = self.open()?;
+        let transaction = connection.transaction_with_behavior(TransactionBehavior::Immediate)?;
+        {
+            let mut delete = transaction.prepare(
+                "DELETE FROM history WHERE id = (
+                     SELECT id FROM history WHERE timestamp = ?1 AND score_id = ?2
+                     ORDER BY id LIMIT 1
+                 )",
+            )?;
+            for entry in entries {
+                delete.execute(params![entry.timestamp, entry.score_id])?;
+            }
+        }
+        transaction.commit()?;
+        Ok(())
+    }
+
+    /// Narrows down by specialty, score and date in SQL; the free text is
+    /// matched on the loaded entries
+    fn query(&self, filter: &HistoryFilter) -> Result<Vec<HistoryEntry>, HistoryStoreError> {
+        let mut sql = "SELECT entry FROM history WHERE 1 = 1".to_string();
+        let mut values = Vec::new();
+        let conditions = [
+            ("specialty = ", filter.specialty.map(specialty_key)),
+            ("score_id = ", filter.score_id.clone()),
+            (
+                "date >= ",
+                filter.from.map(|from| from.format("%Y-%m-%d").to_string()),
+            ),
+            (
+                "date <= ",
+                filter.to.map(|to| to.format("%Y-%m-%d").to_string()),
+            ),
+        ];
+        for (condition, value) in conditions {
+            if let Some(value) = value {
+                values.push(value);
+                sql.push_str(&format!(" AND {}?{}", condition, values.len()));
+            }
+        }
+        // Entries inserted later may be older (imported, or restored after clearing)
+        sql.push_str(" ORDER BY timestamp, id");
+
+        let connection = self.open()?;
+        let mut statement = connection.prepare(&sql)?;
+        let rows = statement
+            .query_map(params_from_iter(&values), |row| row.get::<_, String>(0))?
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut history = parse_entries(rows)?;
+        history.retain(|entry| filter.matches(entry));
+        Ok(history)
+    }
+}
+
+/// History as a JSON [`HistoryFile`]: the format of `history.json` before the
+/// database, and of history files moved to another workstation
+pub struct JsonHistoryStore {
+    path: PathBuf,
+}
+
+impl JsonHistoryStore {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+impl HistoryStore for JsonHistoryStore {
+    fn load(&self) -> Result<Vec<HistoryEntry>, HistoryStoreError> {
+        let file: HistoryFile = migration::from_slice(&fs::read(&self.path)?)?;
+        Ok(file.entries)
+    }
+
+    fn save(&self, history: &[HistoryEntry]) -> Result<(), HistoryStoreError> {
+        let file = HistoryFile {
+            entries: history.to_vec(),
+        };
+        write_atomic(&self.path, migration::to_string_pretty(&file)?.as_bytes())?;
+        Ok(())
+    }
+}
+
+/// History as a JSON [`HistoryFile`] encrypted with a passphrase-derived key
+pub struct EncryptedHistoryStore {
+    path: PathBuf,
+    key: HistoryKey,
+}
+
+impl EncryptedHistoryStore {
+    pub fn new(path: impl Into<PathBuf>, key: HistoryKey) -> Self {
+        Self {
+            path: path.into(),
+            key,
+        }
+    }
+}
+
+impl HistoryStore for EncryptedHistoryStore {
+    fn load(&self) -> Result<Vec<HistoryEntry>, HistoryStoreError> {
+        let json = self.key.decrypt(&fs::read(&self.path)?)?;
+        let file: HistoryFile = migration::from_slice(&json)?;
+        Ok(file.entries)
+    }
+
+    fn save(&self, history: &[HistoryEntry]) -> Result<(), HistoryStoreError> {
+        let file = HistoryFile {
+            entries: history.to_vec(),
+        };
+        let json = migration::to_string_pretty(&file)?;
+        replace_with_backup(&self.path, &self.key.encrypt(json.as_bytes())?)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::history::tests::entry;
+    use chrono::NaiveDate;
+
+    fn keys(history: &[HistoryEntry]) -> Vec<(String, String)> {
+        history
+            .iter()
+            .map(|entry| (entry.timestamp.clone(), entry.score_id.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn test_sqlite_store() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let store = SqliteHistoryStore::new(temp_dir.path().join("history.sqlite3"));
+        assert!(store.load().unwrap().is_empty());
+
+        let mut history = vec![
+            entry("has_bled", Specialty::Cardiology, "2026-01-10 08:00"),
+            entry("rcri", Specialty::Anesthesiology, "2026-02-01 09:30"),
+            entry("has_bled", Specialty::Cardiology, "2026-02-12 10:00"),
+            entry("egfr", Specialty::Nephrology, "unreadable"),
+        ];
+        history[2].patient_label = Some("Bett 4".to_string());
+        store.save(&history).unwrap();
+        let loaded = store.load().unwrap();
+        assert_eq!(keys(&loaded), keys(&history));
+        assert_eq!(loaded[2].patient_label.as_deref(), Some("Bett 4"));
+
+        // Saving replaces the stored entries
+        history.remove(0);
+        store.save(&history).unwrap();
+        assert_eq!(keys(&store.load().unwrap()), keys(&history));
+
+        let filter = HistoryFilter {
+            specialty: Some(Specialty::Cardiology),
+            from: NaiveDate::from_ymd_opt(2026, 2, 1),
+            ..HistoryFilter::default()
+        };
+        let found = store.query(&filter).unwrap();
+        assert_eq!(keys(&found), keys(&history[1..2]));
+
+        let filter = HistoryFilter {
+            query: "bett".to_string(),
+            ..HistoryFilter::default()
+        };
+        assert_eq!(store.query(&filter).unwrap().len(), 1);
+
+        // Undated entries only match without a date range
+        let filter = HistoryFilter {
+            to: NaiveDate::from_ymd_opt(2026, 12, 31),
+            ..HistoryFilter::default()
+        };
+        assert_eq!(store.query(&filter).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_sqlite_store_upgrades_bilingual_entries() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("history.sqlite3");
+        let connection = Connection::open(&path).unwrap();
+        connection.execute_batch(SCHEMA).unwrap();
+        connection
+            .execute(
+                "INSERT INTO history (timestamp, date, score_id, specialty, entry)
+                 VALUES ('2026-02-01 09:30', '2026-02-01', 'rcri', 'Anesthesiology', ?1)",
+                [
+                    r#"{"timestamp":"2026-02-01 09:30","score_name":"RCRI","score_name_de":"RCRI",
+                    "score_id":"rcri","specialty":"Anesthesiology","total_score":1,
+                    "risk":"Low","risk_de":"Niedrig"}"#,
+                ],
+            )
+            .unwrap();
+        connection.pragma_update(None, "user_version", 1).unwrap();
+        drop(connection);
+
+        let loaded = SqliteHistoryStore::new(&path).load().unwrap();
+        assert_eq!(loaded[0].risk.text("de"), "Niedrig");
+        assert_eq!(loaded[0].risk.text("en"), "Low");
+        let version: i32 = Connection::open(&path)
+            .unwrap()
+            .pragma_query_value(None, "user_version", |row| row.get(0))
+            .unwrap();
+        assert_eq!(version, SCHEMA_VERSION);
+    }
+
+    #[test]
+    fn test_sqlite_store_keeps_entries_of_other_instances() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("history.sqlite3");
+        let (first, second) = (
+            SqliteHistoryStore::new(&path),
+            SqliteHistoryStore::new(&path),
+        );
+
+        let asa = entry("asa", Specialty::Anesthesiology, "2026-03-01 08:00");
+        let egfr = entry("egfr", Specialty::Nephrology, "2026-03-01 09:00");
+        first.insert(std::slice::from_ref(&asa)).unwrap();
+        second.insert(std::slice::from_ref(&egfr)).unwrap();
+        // Imported later, but older
+        second
+            .insert(&[entry("rcri", Specialty::Anesthesiology, "2026-02-01 10:00")])
+            .unwrap();
+        assert_eq!(
+            keys(&first.load().unwrap()),
+            [
+                ("2026-02-01 10:00".to_string(), "rcri".to_string()),
+                ("2026-03-01 08:00".to_string(), "asa".to_string()),
+                ("2026-03-01 09:00".to_string(), "egfr".to_string()),
+            ]
+        );
+
+        // Removing one of two calculations of the same minute keeps the other
+        first.insert(std::slice::from_ref(&asa)).unwrap();
+        first.remove(&[asa, egfr]).unwrap();
+        assert_eq!(
+            keys(&second.load().unwrap()),
+            [
+                ("2026-02-01 10:00".to_string(), "rcri".to_string()),
+                ("2026-03-01 08:00".to_string(), "asa".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_file_store_insert_and_remove() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let store = JsonHistoryStore::new(temp_dir.path().join("history.json"));
+        let asa = entry("asa", Specialty::Anesthesiology, "2026-03-01 08:00");
+        let egfr = entry("egfr", Specialty::Nephrology, "2026-03-01 09:00");
+        store.save(std::slice::from_ref(&asa)).unwrap();
+        store.insert(std::slice::from_ref(&egfr)).unwrap();
+        store.remove(&[asa]).unwrap();
+        assert_eq!(keys(&store.load().unwrap()), keys(&[egfr]));
+    }
+}
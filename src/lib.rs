@@ -1,9 +1,30 @@
 // KlinScore library - reusable components
+//
+// The calculation core (`config` and the calculator, formulas, constraints and
+// requests in `scores`) has no file system or GUI dependencies and builds for
+// WebAssembly with `--no-default-features`. Loading scores from disk, batch
+// calculation and the command-line input parsing need the `loader` feature, which
+// is all the language bindings use; everything else needs the `app` feature.
+#[cfg(feature = "loader")]
+pub mod batch;
+#[cfg(feature = "loader")]
+pub mod cli;
 pub mod config;
+#[cfg(feature = "app")]
 pub mod export;
+#[cfg(feature = "loader")]
+pub mod logging;
+#[cfg(feature = "loader")]
+pub mod paths;
+#[cfg(feature = "app")]
+pub mod prefill;
+#[cfg(feature = "app")]
+pub mod rpc;
 pub mod scores;
+#[cfg(feature = "app")]
 pub mod ui;
 
 pub use config::*;
 pub use scores::*;
+#[cfg(feature = "app")]
 pub use ui::*;
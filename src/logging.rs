@@ -0,0 +1,111 @@
+// logging.rs
+// Diagnostics output of the application and the command-line tools
+//
+// Warnings (a skipped score file, a history that could not be saved, a failed
+// export) go to stderr. The application can also append them, together with
+// informational events, to `klinscore.log` in the data folder, which helps to
+// diagnose problems on a workstation where nobody watches the terminal.
+//
+// The stderr level is taken from the `KLINSCORE_LOG` environment variable
+// (`error`, `warn`, `info`, `debug`, `trace` or `off`; default `warn`).
+
+use std::fs::{File, OpenOptions};
+use std::io;
+use std::path::Path;
+use std::sync::{Mutex, OnceLock};
+use tracing::Level;
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::fmt::writer::MakeWriterExt;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{fmt, reload, Layer, Registry};
+
+/// Name of the log file in the data folder
+pub const LOG_FILE: &str = "klinscore.log";
+
+/// Environment variable with the stderr log level
+pub const LEVEL_VARIABLE: &str = "KLINSCORE_LOG";
+
+/// Most detailed level written to the log file
+const FILE_LEVEL: Level = Level::INFO;
+
+type FileLayer = Box<dyn Layer<Registry> + Send + Sync>;
+
+/// Switches the log file on and off after [`init`]
+static FILE_LAYER: OnceLock<reload::Handle<Option<FileLayer>, Registry>> = OnceLock::new();
+
+/// Send diagnostics to stderr; the log file stays off until [`set_log_file`]
+///
+/// Does nothing if diagnostics are already set up (e.g., called twice, or by a
+/// test harness).
+pub fn init() {
+    let stderr_level = std::env::var(LEVEL_VARIABLE)
+        .ok()
+        .and_then(|level| level.trim().parse::<LevelFilter>().ok())
+        .unwrap_or(LevelFilter::WARN);
+    let (file_layer, handle) = reload::Layer::new(None::<FileLayer>);
+    let stderr_layer = fmt::layer()
+        .without_time()
+        .with_target(false)
+        .with_ansi(false)
+        .with_writer(io::stderr.with_filter(move |meta| stderr_level >= *meta.level()));
+
+    let initialized = tracing_subscriber::registry()
+        .with(file_layer)
+        .with(stderr_layer)
+        .with(stderr_level.max(LevelFilter::from_level(FILE_LEVEL)))
+        .try_init()
+        .is_ok();
+    if initialized {
+        let _ = FILE_LAYER.set(handle);
+    }
+}
+
+/// Append diagnostics to the log file at `path`, or stop with `None`
+///
+/// Without [`init`] this does nothing.
+pub fn set_log_file(path: Option<&Path>) -> io::Result<()> {
+    let Some(handle) = FILE_LAYER.get() else {
+        return Ok(());
+    };
+    let layer = match path {
+        Some(path) => Some(file_layer(open_log_file(path)?)),
+        None => None,
+    };
+    handle.reload(layer).map_err(io::Error::other)
+}
+
+fn open_log_file(path: &Path) -> io::Result<File> {
+    OpenOptions::new().create(true).append(true).open(path)
+}
+
+fn file_layer(file: File) -> FileLayer {
+    fmt::layer()
+        .with_ansi(false)
+        .with_writer(Mutex::new(file).with_max_level(FILE_LEVEL))
+        .boxed()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_log_file() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join(LOG_FILE);
+        let subscriber =
+            tracing_subscriber::registry().with(file_layer(open_log_file(&path).unwrap()));
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::warn!(file = "broken.yaml", "Skipping score file");
+            tracing::debug!("Not written at the file level");
+        });
+
+        let log = std::fs::read_to_string(&path).unwrap();
+        assert!(log.contains("WARN"));
+        assert!(log.contains("Skipping score file"));
+        assert!(log.contains("file=\"broken.yaml\""));
+        assert!(!log.contains("Not written"));
+    }
+}
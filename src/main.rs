@@ -1,45 +1,274 @@
 // KlinScore - Clinical Score Calculator
 // main.rs
 
-mod config;
-mod export;
+mod backup;
+mod history;
+mod history_crypto;
+mod history_store;
+mod migration;
 mod persistence;
-mod scores;
 mod settings;
-mod ui;
+
+use klinscore::{config, export, paths, prefill, rpc, scores, tr, ui};
 
 use config::Specialty;
+use export::deidentify::{DeidentificationPolicy, ExportAnonymization, TimestampPrecision};
+use export::integrity;
+use export::templates::ExportTemplate;
 use export::ExportRecord;
-use scores::{calculate_score, load_all_scores, CalculationResult, ScoreLibrary};
-use settings::{AppTheme, Settings};
-use ui::{InputMessage, Language, ScoreInputState};
+use history::{HistoryEntry, HistoryFilter, Session};
+use persistence::{HistoryStorage, LastScreen, PersistedSettings};
+use scores::pack::PackIntegrity;
+use scores::{
+    calculate_score, load_score_library_stream, CalculationResult, LoadEvent, LoadProgress,
+    ScoreLibrary,
+};
+use settings::{AppTheme, HistoryRetention, Settings};
+use ui::{i18n, InputMessage, Language, ScoreInputState, Toast};
 
 use chrono::Local;
 use iced::{
-    widget::{button, column, container, horizontal_rule, pick_list, row, scrollable, text},
-    Alignment, Element, Length, Task,
+    widget::{
+        button, checkbox, column, container, horizontal_rule, pick_list, progress_bar, radio, row,
+        scrollable, text, text_input,
+    },
+    window, Alignment, Element, Length, Subscription, Task,
 };
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Entry of a filter pick list; `value: None` stands for "all"
+#[derive(Debug, Clone, PartialEq)]
+struct FilterChoice<T> {
+    value: Option<T>,
+    label: String,
+}
+
+impl<T> fmt::Display for FilterChoice<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.label)
+    }
+}
+
+/// Apply the history retention setting, then write the entries `added` to
+/// `history` and those pruned to disk. Returns how many entries were pruned.
+fn prune_and_save_history(
+    history: &mut Vec<HistoryEntry>,
+    added: &[HistoryEntry],
+    settings: &Settings,
+    storage: &HistoryStorage,
+) -> usize {
+    let removed = history::apply_retention(
+        history,
+        settings.history_retention,
+        Local::now().date_naive(),
+    );
+    persistence::save_history_changes(added, &removed, storage);
+    // Also covers a day change while the app stays open
+    take_daily_snapshot();
+    removed.len()
+}
+
+/// Take today's snapshot if there is none yet; a failure only costs the snapshot
+fn take_daily_snapshot() {
+    match backup::create_daily_snapshot() {
+        Ok(Some(snapshot)) => tracing::info!(snapshot = %snapshot.name, "Took the daily backup"),
+        Ok(None) => {}
+        Err(e) => tracing::warn!("Could not take the daily backup: {}", e),
+    }
+}
+
+/// Write diagnostics to `klinscore.log` in the data folder, or stop
+fn apply_log_file_setting(enabled: bool) {
+    let path = enabled
+        .then(persistence::data_dir)
+        .flatten()
+        .map(|dir| dir.join(klinscore::logging::LOG_FILE));
+    if let Err(e) = klinscore::logging::set_log_file(path.as_deref()) {
+        tracing::warn!("Cannot write the log file: {}", e);
+    }
+}
+
+/// Load the saved history and apply the retention setting; damaged files are
+/// reported in `toasts`. An encrypted history stays empty until it is unlocked.
+fn load_history(
+    storage: &HistoryStorage,
+    settings: &Settings,
+    toasts: &mut Vec<Toast>,
+) -> Vec<HistoryEntry> {
+    let mut history = match storage {
+        HistoryStorage::Locked => Vec::new(),
+        _ => {
+            let loaded = persistence::load_history();
+            toasts.extend(loaded.corrupt.as_ref().map(corrupt_file_toast));
+            loaded.value.unwrap_or_default()
+        }
+    };
+    let pruned = history::apply_retention(
+        &mut history,
+        settings.history_retention,
+        Local::now().date_naive(),
+    );
+    if !pruned.is_empty() {
+        persistence::save_history_changes(&[], &pruned, storage);
+    }
+    history
+}
+
+/// The local key signing sealed exports, created on first use
+fn export_signer() -> Result<integrity::ExportSigner, String> {
+    let path = integrity::signing_key_path().ok_or("No data folder for the signing key")?;
+    integrity::ExportSigner::load_or_create(&path).map_err(|e| e.to_string())
+}
+
+/// Bundled and user export templates; templates that fail to load are reported
+fn load_export_templates(toasts: &mut Vec<Toast>) -> Vec<ExportTemplate> {
+    let user_dir = export::templates::user_templates_dir();
+    let (templates, errors) = export::templates::load_templates(user_dir.as_deref());
+    for error in errors {
+        tracing::warn!("{}", error);
+        toasts.push(Toast::new("template-invalid").with_arg("error", error.to_string()));
+    }
+    templates
+}
+
+/// Build the startup notification for a persisted file that failed validation
+fn corrupt_file_toast(corrupt: &persistence::CorruptFile) -> Toast {
+    let location = corrupt
+        .quarantined_to
+        .as_ref()
+        .map(|path| path.display().to_string());
+    Toast::new("file-damaged")
+        .with_arg("file", corrupt.file_name.clone())
+        .with_arg("reason", corrupt.reason.clone())
+        .with_arg("moved", if location.is_some() { "yes" } else { "no" })
+        .with_arg("path", location.unwrap_or_default())
+        .with_arg(
+            "restored",
+            if corrupt.restored_backup { "yes" } else { "no" },
+        )
+}
+
+/// Fresh form state for a score, with its dropdown defaults preselected
+fn input_state_for(
+    library: Option<&ScoreLibrary>,
+    score_id: &str,
+    settings: &Settings,
+) -> Box<ScoreInputState> {
+    let mut state = library
+        .and_then(|lib| lib.get_score(score_id))
+        .map(|score| {
+            let mut state = ScoreInputState::for_score(score, settings.unit_system);
+            if score.uses_wizard(settings.wizard_for_long_scores) {
+                state.wizard_step = Some(0);
+            }
+            state
+        })
+        .unwrap_or_default();
+    if !settings.disable_patient_labels {
+        state.patient_label = Some(String::new());
+    }
+    if let Some(score) = library.and_then(|lib| lib.get_score(score_id)) {
+        show_fhir_prefill(&mut state, score, settings);
+    }
+    Box::new(state)
+}
+
+/// Show the FHIR patient ID on the form if a FHIR server is configured and an input
+/// of the score declares LOINC codes; hide it (and its status) otherwise
+fn show_fhir_prefill(
+    state: &mut ScoreInputState,
+    score: &config::ScoreDefinition,
+    settings: &Settings,
+) {
+    let available = !settings.fhir_endpoint.trim().is_empty()
+        && score.inputs.iter().any(|input| !input.loinc.is_empty());
+    if !available {
+        state.fhir_patient_id = None;
+        state.fhir_status = None;
+    } else if state.fhir_patient_id.is_none() {
+        state.fhir_patient_id = Some(String::new());
+    }
+}
 
-/// A single calculation history entry
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
-struct HistoryEntry {
-    score_name: String,
-    score_name_de: String,
-    score_id: String,
-    specialty: Specialty,
-    total_score: i32,
-    risk: String,
-    risk_de: String,
-    timestamp: String,
+/// Citations of a score, each clickable when it has a DOI, PMID or URL
+fn reference_list(references: &[config::Reference], size: u16) -> Element<'_, Message> {
+    references
+        .iter()
+        .fold(column![].spacing(4), |list, reference| {
+            let citation = reference.formatted();
+            let item: Element<'_, Message> = match reference.link() {
+                Some(url) => button(text(format!("\u{1f517} {}", citation)).size(size))
+                    .on_press(Message::OpenUrl(url))
+                    .padding(0)
+                    .style(button::text)
+                    .into(),
+                None => text(citation).size(size).into(),
+            };
+            list.push(item)
+        })
+        .into()
 }
 
+const USAGE: &str = "Usage: klinscore [--portable | --data-dir <folder>] [--stdio]
+
+  --portable           Keep settings, history and user scores in klinscore-data
+                       next to the program (also enabled by a klinscore.portable
+                       file next to the program)
+  --data-dir <folder>  Keep them in <folder>
+  --stdio              Answer JSON-RPC requests (listScores, calculate) on
+                       stdin/stdout instead of opening the window";
+
 fn main() -> iced::Result {
+    klinscore::logging::init();
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    if args.iter().any(|arg| arg == "--help" || arg == "-h") {
+        println!("{}", USAGE);
+        return Ok(());
+    }
+    let stdio = args.iter().any(|arg| arg == "--stdio");
+    args.retain(|arg| arg != "--stdio");
+    let marker_present = paths::portable_marker().is_some_and(|marker| marker.exists());
+    match paths::DataLocation::from_args(&args, marker_present) {
+        Ok(location) => {
+            paths::set_data_location(location);
+        }
+        Err(message) => {
+            eprintln!("klinscore: {}\n\n{}", message, USAGE);
+            std::process::exit(2);
+        }
+    }
+    if stdio {
+        serve_stdio();
+    }
+
     iced::application("KlinScore", KlinScore::update, KlinScore::view)
         .theme(KlinScore::theme)
+        .subscription(KlinScore::subscription)
         .window_size((1000.0, 700.0))
         .run_with(KlinScore::new)
 }
 
+/// Answer JSON-RPC requests on stdin/stdout over the bundled and user scores, then exit
+fn serve_stdio() -> ! {
+    let dirs: Vec<_> = scores::user_scores_dir().into_iter().collect();
+    let library = match scores::load_score_library(&dirs) {
+        Ok(library) => library,
+        Err(e) => {
+            eprintln!("klinscore: {}", e);
+            std::process::exit(1);
+        }
+    };
+    let server = rpc::RpcServer::new(library);
+    if let Err(e) = server.serve(std::io::stdin().lock(), std::io::stdout().lock()) {
+        eprintln!("klinscore: {}", e);
+        std::process::exit(1);
+    }
+    std::process::exit(0);
+}
+
 // Application State
 #[derive(Debug, Clone)]
 enum AppState {
@@ -52,131 +281,546 @@ enum AppState {
     ScoreCalculation {
         specialty: Specialty,
         score_id: String,
-        input_state: ScoreInputState,
+        input_state: Box<ScoreInputState>,
         result: Option<Box<CalculationResult>>,
         error: Option<String>,
     },
     History,
     About,
+    ScoreInfo {
+        score_id: String,
+    },
     Settings,
     Error(String),
 }
 
+/// Identity of a tab that stays the same while other tabs open and close, for
+/// results of background work (prefilling, exports) to find their tab
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct TabId(u64);
+
+/// An open tab with its own view and entered inputs
+#[derive(Debug, Clone)]
+struct Tab {
+    id: TabId,
+    state: AppState,
+    /// Tracks the previous state to return to from About/History/score info
+    previous_state: Option<Box<AppState>>,
+}
+
+impl Tab {
+    fn new(state: AppState) -> Self {
+        static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+        Self {
+            id: TabId(NEXT_ID.fetch_add(1, Ordering::Relaxed)),
+            state,
+            previous_state: None,
+        }
+    }
+
+    /// Show `state`, remembering the current view for `go_back`
+    fn open(&mut self, state: AppState) {
+        self.previous_state = Some(Box::new(std::mem::replace(&mut self.state, state)));
+    }
+
+    /// Screen to restore after a restart: the calculation or score list shown,
+    /// or the one an overlay such as the history was opened from. `Some(None)`
+    /// if there is nothing to restore, `None` on screens that leave it unchanged.
+    fn last_screen(&self, include_inputs: bool) -> Option<Option<LastScreen>> {
+        let state = match &self.state {
+            AppState::History | AppState::About | AppState::ScoreInfo { .. } => {
+                self.previous_state.as_deref()?
+            }
+            state => state,
+        };
+        match state {
+            AppState::ScoreSelection { specialty } => Some(Some(LastScreen {
+                specialty: *specialty,
+                score_id: None,
+                inputs: HashMap::new(),
+                patient_label: None,
+            })),
+            // A finished calculation is in the history
+            AppState::ScoreCalculation {
+                specialty,
+                score_id,
+                input_state,
+                result: None,
+                ..
+            } => Some(Some(LastScreen {
+                specialty: *specialty,
+                score_id: Some(score_id.clone()),
+                inputs: if include_inputs {
+                    input_state.inputs.clone()
+                } else {
+                    HashMap::new()
+                },
+                patient_label: input_state.patient_label().filter(|_| include_inputs),
+            })),
+            AppState::Welcome
+            | AppState::SpecialtySelection
+            | AppState::ScoreCalculation { .. } => Some(None),
+            AppState::Loading
+            | AppState::Settings
+            | AppState::Error(_)
+            | AppState::History
+            | AppState::About
+            | AppState::ScoreInfo { .. } => None,
+        }
+    }
+
+    /// Return to the view before the last `open`
+    fn go_back(&mut self) {
+        self.state = self
+            .previous_state
+            .take()
+            .map(|s| *s)
+            .unwrap_or(AppState::Welcome);
+    }
+}
+
 // Main Application
 struct KlinScore {
-    state: AppState,
+    /// Open tabs, so several calculations can be in progress at once
+    tabs: Vec<Tab>,
+    /// Index of the shown tab in `tabs`
+    active_tab: usize,
     language: Language,
     score_library: Option<ScoreLibrary>,
     settings: Settings,
     history: Vec<HistoryEntry>,
-    /// Tracks the previous state to return to from About/History
-    previous_state: Option<Box<AppState>>,
+    /// Status line shown in the history view (e.g., after a research export)
+    history_status: Option<String>,
+    /// Indices of history entries expanded to show their breakdown
+    expanded_history: HashSet<usize>,
+    /// Criteria of the history list
+    history_filter: HistoryFilter,
+    /// Number of matching history entries rendered; grows by `history::PAGE_SIZE`
+    history_visible: usize,
+    /// Dates typed into the history filter (parsed into `history_filter`)
+    history_from_input: String,
+    history_to_input: String,
+    /// Whether the "clear history" confirmation dialog is shown
+    confirm_clear_history: bool,
+    /// Entries removed by the last "clear history", kept until the app closes for undo
+    cleared_history: Option<Vec<HistoryEntry>>,
+    /// Running session; new calculations are recorded as part of it
+    active_session: Option<Session>,
+    /// Path being typed into the "import history" field
+    history_import_input: String,
+    /// Plain or encrypted history file; while `Locked`, nothing is saved
+    history_storage: HistoryStorage,
+    /// Whether the dialog asking for the history passphrase is shown
+    unlock_dialog_open: bool,
+    /// Passphrase typed to unlock or to enable encryption, and its confirmation
+    passphrase_input: String,
+    passphrase_confirm_input: String,
+    /// Error or result of the last unlock/encryption attempt
+    encryption_status: Option<String>,
+    /// Number typed for the history retention limit (entries or days)
+    retention_limit_input: String,
+    /// Title being typed for the next session
+    session_title_input: String,
+    /// Notifications shown above the main content until dismissed
+    toasts: Vec<Toast>,
+    /// Path being typed into the "add score directory" field in Settings
+    score_dir_input: String,
+    /// Status line shown in the settings view (e.g., invalid score directory)
+    settings_status: Option<String>,
+    /// Portable mode marker present next to the executable (takes effect on the next start)
+    portable_marker: bool,
+    /// Path being typed into the "import score pack" field in Settings
+    pack_path_input: String,
+    /// Public key being typed into the "trusted publisher keys" field in Settings
+    trusted_key_input: String,
+    /// Files processed by the running score library load
+    load_progress: Option<LoadProgress>,
+    /// Whether the result breakdown also lists criteria that scored no points
+    show_zero_point_fields: bool,
+    /// Bundled and user export templates, offered in the result view
+    export_templates: Vec<ExportTemplate>,
+    /// Public key (hex) of the local key signing exports, once signing is enabled
+    signing_key: Option<String>,
+    /// File of the last successful export, offered to open until dismissed
+    last_export: Option<PathBuf>,
+    /// Layout for the current window width
+    layout: ui::ScreenLayout,
+    /// Whether the header buttons are expanded in the compact layout
+    header_menu_open: bool,
+    /// Snapshots in the backup folder, newest first; read when the settings open
+    backups: Vec<backup::Snapshot>,
+    /// Screen saved in `last_screen.json`, rewritten when the shown screen changes
+    last_screen: Option<LastScreen>,
+    /// Unfinished calculation from the previous run, offered on the welcome page
+    /// until it is restored or discarded
+    resume_offer: Option<LastScreen>,
 }
 
 // Messages (user interactions)
 #[derive(Debug, Clone)]
 enum Message {
-    LanguageToggled,
+    WindowResized(iced::Size),
+    HeaderMenuToggled,
+    LanguageSelected(Language),
+    LoadProgressed(LoadProgress),
     ScoresLoaded(Result<ScoreLibrary, String>),
     SpecialtySelected(Specialty),
     ScoreSelected(String),
+    JumpToScore(String),
     Input(InputMessage),
     BackToWelcome,
+    /// Reopen the unfinished calculation of the previous run
+    ResumeLastScreen,
+    DiscardLastScreen,
     BackToSpecialtySelection,
     BackToScoreSelection,
+    NewTab,
+    TabSelected(usize),
+    TabClosed(usize),
     OpenSettings,
     CloseSettings,
     ThemeChanged(AppTheme),
+    CustomThemeBackgroundChanged(String),
+    CustomThemePrimaryChanged(String),
+    CustomThemeRiskPaletteChanged(config::RiskPalette),
+    CustomThemePresetSelected(usize),
+    ResearchPrecisionChanged(TimestampPrecision),
+    UnitSystemChanged(config::UnitSystem),
+    ReportHeaderChanged(String),
+    ReportFooterChanged(String),
+    FhirEndpointChanged(String),
+    /// Result of prefilling the form of a score in a tab
+    FhirPrefilled {
+        tab: TabId,
+        score_id: String,
+        result: Result<Vec<prefill::PrefilledValue>, String>,
+    },
+    BilingualReportsToggled(bool),
+    ReportQrCodeToggled(bool),
+    ExportAnonymizationChanged(ExportAnonymization),
+    SealExportsToggled(bool),
+    SignExportsToggled(bool),
+    CopySigningKey,
+    ScoreDirInputChanged(String),
+    AddScoreDir,
+    RemoveScoreDir(usize),
+    StrictLoadingToggled(bool),
+    PortableModeToggled(bool),
+    LogFileToggled(bool),
+    ChooseExportDir,
+    RestoreBackup(String),
+    ExportSettings,
+    ImportSettings,
+    SettingsFileChosen(Option<PathBuf>),
+    ExportDirChosen(Option<PathBuf>),
+    ResetExportDir,
+    HelpHintsToggled(bool),
+    WizardModeToggled(bool),
+    HighContrastToggled(bool),
+    TouchModeToggled(bool),
+    PatientLabelsDisabled(bool),
+    HistoryRetentionChanged(HistoryRetention),
+    PassphraseChanged(String),
+    PassphraseConfirmChanged(String),
+    OpenUnlockDialog,
+    UnlockHistory,
+    UnlockDialogDismissed,
+    EnableHistoryEncryption,
+    DisableHistoryEncryption,
+    RetentionLimitChanged(String),
+    PackPathInputChanged(String),
+    ImportPack,
+    TrustedKeyInputChanged(String),
+    AddTrustedKey,
+    RemoveTrustedKey(usize),
     OpenHistory,
     CloseHistory,
+    ReopenHistoryEntry(usize),
+    HistoryEntryToggled(usize),
+    HistorySpecialtyFilterChanged(Option<Specialty>),
+    HistoryScoreFilterChanged(Option<String>),
+    HistoryFromChanged(String),
+    HistoryToChanged(String),
+    HistorySearchChanged(String),
+    HistoryFilterCleared,
+    HistoryShowMore,
+    SessionTitleChanged(String),
+    StartSession,
+    EndSession,
+    PrintSessionReport(String),
+    ExportSessionPdf(String),
+    ExportSessionCsv(String),
+    ExportSessionJson(String),
     ClearHistory,
+    ClearHistoryConfirmed,
+    ClearHistoryCancelled,
+    UndoClearHistory,
+    ExportHistoryDeidentified,
+    ExportHistoryCsv,
+    ExportHistoryTransfer,
+    HistoryImportPathChanged(String),
+    ImportHistory,
+    ExportHistoryJson,
+    ExportHistoryPdf,
     OpenAbout,
     CloseAbout,
+    OpenScoreInfo(String),
+    CloseScoreInfo,
     OpenUrl(String),
+    ZeroPointFieldsToggled(bool),
     ExportCsv,
     ExportJson,
     ExportPdf,
-    ExportComplete(Result<String, String>),
+    CopySummary,
+    ExportMarkdown,
+    CopyMarkdown,
+    ExportFhir,
+    /// Display name of the export template
+    ExportWithTemplate(String),
+    ExportPatientPdf,
+    Print,
+    /// Result of printing from a tab
+    PrintComplete {
+        tab: TabId,
+        result: Result<(), String>,
+    },
+    /// Result of an export started from a tab
+    ExportComplete {
+        tab: TabId,
+        result: Result<PathBuf, String>,
+    },
+    OpenExport,
+    RevealExport,
+    DismissExport,
+    ExportCancelled,
+    DismissToast(usize),
 }
 
 impl KlinScore {
     fn new() -> (Self, Task<Message>) {
-        // Load persisted settings and history
-        let (settings, language) = match persistence::load_settings() {
-            Some(persisted) => {
-                let mut settings = Settings::new();
-                settings.theme = persisted.theme;
-                settings.show_help_hints = persisted.show_help_hints;
-                settings.auto_calculate = persisted.auto_calculate;
-                (settings, persisted.language)
-            }
-            None => (Settings::new(), Language::German),
-        };
-        let history: Vec<HistoryEntry> = persistence::load_history();
+        // Load persisted settings and history; corrupt files are quarantined
+        // and reported instead of silently replaced with defaults
+        let mut toasts = Vec::new();
+        let loaded_settings = persistence::load_settings();
+        toasts.extend(loaded_settings.corrupt.as_ref().map(corrupt_file_toast));
+        let (settings, language) = loaded_settings
+            .value
+            .map(PersistedSettings::into_settings)
+            .unwrap_or_else(|| (Settings::new(), Language::German));
+        let history_storage = persistence::history_storage();
+        apply_log_file_setting(settings.log_file);
+        let history = load_history(&history_storage, &settings, &mut toasts);
+        take_daily_snapshot();
+        let retention_limit_input = settings
+            .history_retention
+            .limit()
+            .map(|limit| limit.to_string())
+            .unwrap_or_default();
+
+        let layout = ui::ScreenLayout::default().with_touch(settings.touch_mode);
+        let last_screen = persistence::load_last_screen();
+        let export_templates = load_export_templates(&mut toasts);
+        let signing_key = settings
+            .sign_exports
+            .then(|| match export_signer() {
+                Ok(signer) => Some(signer.public_key()),
+                Err(e) => {
+                    tracing::warn!("{}", e);
+                    None
+                }
+            })
+            .flatten();
 
         let app = Self {
-            state: AppState::Loading,
+            tabs: vec![Tab::new(AppState::Loading)],
+            active_tab: 0,
             language,
             score_library: None,
             settings,
             history,
-            previous_state: None,
+            history_status: None,
+            expanded_history: HashSet::new(),
+            history_filter: HistoryFilter::default(),
+            history_visible: history::PAGE_SIZE,
+            history_from_input: String::new(),
+            history_to_input: String::new(),
+            confirm_clear_history: false,
+            cleared_history: None,
+            history_import_input: String::new(),
+            unlock_dialog_open: matches!(history_storage, HistoryStorage::Locked),
+            history_storage,
+            passphrase_input: String::new(),
+            passphrase_confirm_input: String::new(),
+            encryption_status: None,
+            retention_limit_input,
+            active_session: None,
+            session_title_input: String::new(),
+            toasts,
+            score_dir_input: String::new(),
+            settings_status: None,
+            portable_marker: paths::portable_marker().is_some_and(|marker| marker.exists()),
+            pack_path_input: String::new(),
+            trusted_key_input: String::new(),
+            load_progress: None,
+            show_zero_point_fields: false,
+            export_templates,
+            signing_key,
+            last_export: None,
+            layout,
+            header_menu_open: false,
+            backups: Vec::new(),
+            last_screen: last_screen.clone(),
+            resume_offer: last_screen,
         };
 
-        // Load scores asynchronously
-        let task = Task::perform(
-            async {
-                match load_all_scores("scores/") {
-                    Ok(library) => Ok(library),
-                    Err(e) => Err(format!("Failed to load scores: {}", e)),
-                }
-            },
-            Message::ScoresLoaded,
-        );
-
+        let task = app.load_scores_task();
         (app, task)
     }
 
+    /// Load the bundled scores plus user and additional directories asynchronously
+    fn load_scores_task(&self) -> Task<Message> {
+        let dirs: Vec<_> = scores::user_scores_dir()
+            .into_iter()
+            .chain(self.settings.extra_score_dirs.iter().cloned())
+            .collect();
+        let strict = self.settings.strict_score_loading;
+        let language = self.language;
+        Task::run(
+            load_score_library_stream(dirs, strict),
+            move |event| match event {
+                LoadEvent::Progress(progress) => Message::LoadProgressed(progress),
+                LoadEvent::Finished(loaded) => Message::ScoresLoaded(
+                    loaded.map_err(|e| tr!(language, "scores-load-failed", error = e.to_string())),
+                ),
+            },
+        )
+    }
+
     fn update(&mut self, message: Message) -> Task<Message> {
+        let task = self.handle_message(message);
+        self.remember_screen();
+        task
+    }
+
+    /// Save the shown screen, so an interrupted calculation can be restored
+    /// after a restart. Inputs are left out while the history is encrypted.
+    fn remember_screen(&mut self) {
+        if self.resume_offer.is_some() {
+            if matches!(self.tab().state, AppState::Loading | AppState::Welcome) {
+                // Keep the saved screen until the offer is answered
+                return;
+            }
+            // Starting something else discards the offer
+            self.resume_offer = None;
+        }
+        let include_inputs = matches!(self.history_storage, HistoryStorage::Plain);
+        let Some(screen) = self.tab().last_screen(include_inputs) else {
+            return;
+        };
+        if screen != self.last_screen {
+            persistence::save_last_screen(screen.as_ref());
+            self.last_screen = screen;
+        }
+    }
+
+    fn handle_message(&mut self, message: Message) -> Task<Message> {
         match message {
-            Message::LanguageToggled => {
-                self.language = match self.language {
-                    Language::German => Language::English,
-                    Language::English => Language::German,
-                };
+            Message::WindowResized(size) => {
+                self.layout =
+                    ui::ScreenLayout::for_width(size.width).with_touch(self.settings.touch_mode);
+                if !self.layout.is_compact() {
+                    self.header_menu_open = false;
+                }
+            }
+            Message::HeaderMenuToggled => {
+                self.header_menu_open = !self.header_menu_open;
+            }
+            Message::LanguageSelected(language) => {
+                self.language = language;
                 persistence::save_settings(&self.settings, self.language);
             }
-            Message::ScoresLoaded(result) => match result {
-                Ok(library) => {
-                    self.score_library = Some(library);
-                    self.state = AppState::Welcome;
+            Message::LoadProgressed(progress) => {
+                self.load_progress = Some(progress);
+            }
+            Message::ScoresLoaded(result) => {
+                self.load_progress = None;
+                match result {
+                    Ok(library) => {
+                        self.score_library = Some(library);
+                        // New or edited templates are picked up with the scores
+                        self.export_templates = load_export_templates(&mut self.toasts);
+                        // Reloads triggered from Settings stay on the settings page
+                        for tab in &mut self.tabs {
+                            if matches!(tab.state, AppState::Loading) {
+                                tab.state = AppState::Welcome;
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        tracing::warn!("Score library not loaded: {}", e);
+                        if matches!(self.tab().state, AppState::Settings) {
+                            self.settings_status = Some(e);
+                        } else {
+                            self.tab_mut().state = AppState::Error(e);
+                        }
+                    }
                 }
-                Err(e) => {
-                    self.state = AppState::Error(e);
+            }
+            Message::ResumeLastScreen => {
+                if let Some(screen) = self.resume_offer.take() {
+                    self.tab_mut().state = self.resumed_state(screen);
                 }
-            },
+            }
+            Message::DiscardLastScreen => {
+                self.resume_offer = None;
+            }
             Message::SpecialtySelected(specialty) => {
-                self.state = AppState::ScoreSelection { specialty };
+                self.tab_mut().state = AppState::ScoreSelection { specialty };
             }
             Message::ScoreSelected(score_id) => {
-                if let AppState::ScoreSelection { specialty } = self.state {
-                    self.state = AppState::ScoreCalculation {
+                if let AppState::ScoreSelection { specialty } = self.tab().state {
+                    self.tab_mut().state = AppState::ScoreCalculation {
+                        specialty,
+                        input_state: input_state_for(
+                            self.score_library.as_ref(),
+                            &score_id,
+                            &self.settings,
+                        ),
+                        score_id,
+                        result: None,
+                        error: None,
+                    };
+                }
+            }
+            Message::JumpToScore(score_id) => {
+                let specialty = self
+                    .score_library
+                    .as_ref()
+                    .and_then(|lib| lib.get_score(&score_id))
+                    .map(|score| score.specialty);
+                if let Some(specialty) = specialty {
+                    self.tab_mut().state = AppState::ScoreCalculation {
                         specialty,
+                        input_state: input_state_for(
+                            self.score_library.as_ref(),
+                            &score_id,
+                            &self.settings,
+                        ),
                         score_id,
-                        input_state: ScoreInputState::new(),
                         result: None,
                         error: None,
                     };
                 }
             }
             Message::Input(input_msg) => {
+                let tab_id = self.tab().id;
                 if let AppState::ScoreCalculation {
                     specialty,
                     ref score_id,
                     ref mut input_state,
                     ref mut result,
                     ref mut error,
-                } = self.state
+                } = self.tabs[self.active_tab].state
                 {
                     match input_msg {
                         InputMessage::BooleanChanged(field, value) => {
@@ -185,7 +829,22 @@ impl KlinScore {
                             *error = None;
                         }
                         InputMessage::NumberTextChanged(field, value) => {
-                            input_state.update_number_text(field, value);
+                            input_state.update_number_text(field, value, self.language);
+                            // Clear error when user makes changes
+                            *error = None;
+                        }
+                        InputMessage::DateTextChanged(field, value) => {
+                            input_state.update_date_text(field, value, self.language);
+                            // Clear error when user makes changes
+                            *error = None;
+                        }
+                        InputMessage::DateSelected(field, date) => {
+                            input_state.set_date(field, date);
+                            // Clear error when user makes changes
+                            *error = None;
+                        }
+                        InputMessage::DurationTextChanged(field, value) => {
+                            input_state.update_duration_text(field, value);
                             // Clear error when user makes changes
                             *error = None;
                         }
@@ -194,6 +853,56 @@ impl KlinScore {
                             // Clear error when user makes changes
                             *error = None;
                         }
+                        InputMessage::SectionToggled(section_id) => {
+                            input_state.toggle_section(section_id);
+                        }
+                        InputMessage::WizardToggled => {
+                            input_state.toggle_wizard();
+                        }
+                        InputMessage::WizardStepSelected(step) => {
+                            input_state.wizard_step = Some(step);
+                        }
+                        InputMessage::PatientLabelChanged(label) => {
+                            input_state.patient_label = Some(label);
+                        }
+                        InputMessage::FhirPatientIdChanged(patient_id) => {
+                            input_state.fhir_patient_id = Some(patient_id);
+                        }
+                        InputMessage::PrefillFromFhir => {
+                            let (Some(score), Some(patient_id)) = (
+                                self.score_library
+                                    .as_ref()
+                                    .and_then(|library| library.get_score(score_id)),
+                                input_state.fhir_patient_id.clone(),
+                            ) else {
+                                return Task::none();
+                            };
+                            if patient_id.trim().is_empty() {
+                                return Task::none();
+                            }
+                            input_state.fhir_status =
+                                Some(tr!(self.language, "fhir-prefill-loading"));
+                            let score = score.clone();
+                            let endpoint = self.settings.fhir_endpoint.clone();
+                            let score_id = score_id.clone();
+                            let today = Local::now().date_naive();
+                            return Task::perform(
+                                async move {
+                                    prefill::prefill_from_server(
+                                        &endpoint,
+                                        &patient_id,
+                                        &score,
+                                        today,
+                                    )
+                                    .map_err(|e| e.to_string())
+                                },
+                                move |result| Message::FhirPrefilled {
+                                    tab: tab_id,
+                                    score_id: score_id.clone(),
+                                    result,
+                                },
+                            );
+                        }
                         InputMessage::Calculate => {
                             // Perform calculation
                             if let Some(library) = &self.score_library {
@@ -201,34 +910,53 @@ impl KlinScore {
                                     match calculate_score(score_def, &input_state.inputs) {
                                         Ok(calc_result) => {
                                             // Save to history
-                                            let entry = HistoryEntry {
-                                                score_name: score_def.name.clone(),
-                                                score_name_de: score_def.name_de.clone(),
-                                                score_id: score_id.clone(),
+                                            let mut entry = HistoryEntry::new(
+                                                score_id,
+                                                score_def,
                                                 specialty,
-                                                total_score: calc_result.total_score,
-                                                risk: calc_result.risk.clone(),
-                                                risk_de: calc_result.risk_de.clone(),
-                                                timestamp: Local::now()
-                                                    .format("%Y-%m-%d %H:%M")
-                                                    .to_string(),
-                                            };
-                                            self.history.push(entry);
-                                            persistence::save_history(&self.history);
+                                                &calc_result,
+                                                input_state,
+                                            );
+                                            entry.session = self.active_session.clone();
+                                            self.history.push(entry.clone());
+                                            if prune_and_save_history(
+                                                &mut self.history,
+                                                &[entry],
+                                                &self.settings,
+                                                &self.history_storage,
+                                            ) > 0
+                                            {
+                                                self.expanded_history.clear();
+                                            }
 
                                             *result = Some(Box::new(calc_result));
                                             *error = None;
                                         }
                                         Err(e) => {
                                             *result = None;
-                                            *error = Some(format!("{}", e));
+                                            *error =
+                                                Some(e.localized_message(self.language.code()));
                                         }
                                     }
                                 }
                             }
                         }
                         InputMessage::Reset => {
-                            *input_state = ScoreInputState::new();
+                            // Start over in the same mode (whole form or wizard)
+                            // and for the same patient
+                            let wizard = input_state.wizard_step.is_some();
+                            let patient_label = input_state.patient_label.take();
+                            let fhir_patient_id = input_state.fhir_patient_id.take();
+                            *input_state = input_state_for(
+                                self.score_library.as_ref(),
+                                score_id,
+                                &self.settings,
+                            );
+                            input_state.wizard_step = wizard.then_some(0);
+                            input_state.patient_label = patient_label;
+                            if input_state.fhir_patient_id.is_some() {
+                                input_state.fhir_patient_id = fhir_patient_id;
+                            }
                             *result = None;
                             *error = None;
                         }
@@ -236,161 +964,1410 @@ impl KlinScore {
                 }
             }
             Message::BackToWelcome => {
-                self.state = AppState::Welcome;
+                self.tab_mut().state = AppState::Welcome;
             }
             Message::BackToSpecialtySelection => {
-                self.state = AppState::SpecialtySelection;
+                self.tab_mut().state = AppState::SpecialtySelection;
             }
             Message::BackToScoreSelection => {
-                if let AppState::ScoreCalculation { specialty, .. } = self.state {
-                    self.state = AppState::ScoreSelection { specialty };
+                if let AppState::ScoreCalculation { specialty, .. } = self.tab().state {
+                    self.tab_mut().state = AppState::ScoreSelection { specialty };
+                }
+            }
+            Message::NewTab => {
+                let state = if self.score_library.is_some() {
+                    AppState::SpecialtySelection
+                } else {
+                    AppState::Loading
+                };
+                self.tabs.push(Tab::new(state));
+                self.active_tab = self.tabs.len() - 1;
+            }
+            Message::TabSelected(index) => {
+                if index < self.tabs.len() {
+                    self.active_tab = index;
+                }
+            }
+            Message::TabClosed(index) => {
+                // The last tab stays open
+                if index < self.tabs.len() && self.tabs.len() > 1 {
+                    self.tabs.remove(index);
+                    if self.active_tab > index || self.active_tab == self.tabs.len() {
+                        self.active_tab -= 1;
+                    }
                 }
             }
             Message::OpenSettings => {
-                self.state = AppState::Settings;
+                self.header_menu_open = false;
+                self.settings_status = None;
+                self.backups = backup::snapshots();
+                self.tab_mut().state = AppState::Settings;
             }
             Message::CloseSettings => {
-                self.state = AppState::Welcome;
+                self.tab_mut().state = AppState::Welcome;
             }
             Message::ThemeChanged(theme) => {
                 self.settings.theme = theme;
                 persistence::save_settings(&self.settings, self.language);
             }
-            Message::OpenHistory => {
-                self.previous_state = Some(Box::new(self.state.clone()));
-                self.state = AppState::History;
+            Message::CustomThemeBackgroundChanged(color) => {
+                self.settings.custom_theme.background = color;
+                persistence::save_settings(&self.settings, self.language);
             }
-            Message::CloseHistory => {
-                self.state = self
-                    .previous_state
-                    .take()
-                    .map(|s| *s)
-                    .unwrap_or(AppState::Welcome);
+            Message::CustomThemePrimaryChanged(color) => {
+                self.settings.custom_theme.primary = color;
+                persistence::save_settings(&self.settings, self.language);
             }
-            Message::ClearHistory => {
-                self.history.clear();
-                persistence::save_history(&self.history);
+            Message::CustomThemeRiskPaletteChanged(palette) => {
+                self.settings.custom_theme.risk_palette = palette;
+                persistence::save_settings(&self.settings, self.language);
             }
-            Message::OpenAbout => {
-                self.previous_state = Some(Box::new(self.state.clone()));
-                self.state = AppState::About;
+            Message::CustomThemePresetSelected(index) => {
+                if let Some(preset) = settings::THEME_PRESETS.get(index) {
+                    self.settings.custom_theme = preset.theme();
+                    persistence::save_settings(&self.settings, self.language);
+                }
             }
-            Message::CloseAbout => {
-                self.state = self
-                    .previous_state
-                    .take()
-                    .map(|s| *s)
-                    .unwrap_or(AppState::Welcome);
+            Message::ResearchPrecisionChanged(precision) => {
+                self.settings.research_timestamp_precision = precision;
+                persistence::save_settings(&self.settings, self.language);
             }
-            Message::OpenUrl(url) => {
-                let _ = opener::open(&url);
+            Message::UnitSystemChanged(unit_system) => {
+                self.settings.unit_system = unit_system;
+                self.apply_unit_system();
+                persistence::save_settings(&self.settings, self.language);
             }
-            Message::ExportCsv => {
-                if let Some(record) = self.current_export_record() {
-                    let filename = export::default_filename(&record.score_name, "csv");
-                    return Task::perform(
-                        async move {
-                            export::csv_export::export_to_csv_file(&record, &filename)
-                                .map(|()| filename)
-                        },
-                        Message::ExportComplete,
-                    );
+            Message::ReportHeaderChanged(header) => {
+                self.settings.report_header = header;
+                persistence::save_settings(&self.settings, self.language);
+            }
+            Message::ReportFooterChanged(footer) => {
+                self.settings.report_footer = footer;
+                persistence::save_settings(&self.settings, self.language);
+            }
+            Message::FhirEndpointChanged(endpoint) => {
+                self.settings.fhir_endpoint = endpoint;
+                persistence::save_settings(&self.settings, self.language);
+                for tab in &mut self.tabs {
+                    if let AppState::ScoreCalculation {
+                        score_id,
+                        input_state,
+                        ..
+                    } = &mut tab.state
+                    {
+                        if let Some(score) = self
+                            .score_library
+                            .as_ref()
+                            .and_then(|library| library.get_score(score_id))
+                        {
+                            show_fhir_prefill(input_state, score, &self.settings);
+                        }
+                    }
                 }
             }
-            Message::ExportJson => {
-                if let Some(record) = self.current_export_record() {
-                    let filename = export::default_filename(&record.score_name, "json");
-                    return Task::perform(
-                        async move {
-                            export::json_export::export_to_json_file(&record, &filename)
-                                .map(|()| filename)
+            Message::FhirPrefilled {
+                tab,
+                score_id,
+                result,
+            } => {
+                let language = self.language;
+                let Some(score) = self
+                    .score_library
+                    .as_ref()
+                    .and_then(|library| library.get_score(&score_id))
+                else {
+                    return Task::none();
+                };
+                // The tab may have moved on to another score in the meantime
+                let Some(Tab {
+                    state:
+                        AppState::ScoreCalculation {
+                            score_id: tab_score_id,
+                            input_state,
+                            error,
+                            ..
                         },
-                        Message::ExportComplete,
-                    );
+                    ..
+                }) = self.tabs.iter_mut().find(|open| open.id == tab)
+                else {
+                    return Task::none();
+                };
+                if *tab_score_id != score_id {
+                    return Task::none();
+                }
+                input_state.fhir_status = Some(match result {
+                    Ok(values) if values.is_empty() => tr!(language, "fhir-prefilled-none"),
+                    Ok(values) => {
+                        let fields = values
+                            .iter()
+                            .filter_map(|value| {
+                                let input = score
+                                    .inputs
+                                    .iter()
+                                    .find(|input| input.field == value.field)?;
+                                let label = input.label.text(language.code());
+                                Some(match &value.date {
+                                    Some(date) => format!("{} ({})", label, date),
+                                    None => label.to_string(),
+                                })
+                            })
+                            .collect::<Vec<_>>()
+                            .join(", ");
+                        let inputs = values
+                            .iter()
+                            .map(|value| (value.field.clone(), value.value.clone()))
+                            .collect();
+                        input_state.fill_inputs(score, &inputs, language);
+                        *error = None;
+                        tr!(
+                            language,
+                            "fhir-prefilled",
+                            count = values.len(),
+                            fields = fields
+                        )
+                    }
+                    Err(e) => tr!(language, "fhir-prefill-failed", error = e),
+                });
+            }
+            Message::BilingualReportsToggled(bilingual) => {
+                self.settings.bilingual_reports = bilingual;
+                persistence::save_settings(&self.settings, self.language);
+            }
+            Message::ReportQrCodeToggled(qr_code) => {
+                self.settings.report_qr_code = qr_code;
+                persistence::save_settings(&self.settings, self.language);
+            }
+            Message::ExportAnonymizationChanged(anonymization) => {
+                self.settings.export_anonymization = anonymization;
+                persistence::save_settings(&self.settings, self.language);
+            }
+            Message::SealExportsToggled(seal) => {
+                self.settings.seal_exports = seal;
+                persistence::save_settings(&self.settings, self.language);
+            }
+            Message::SignExportsToggled(sign) => {
+                if sign {
+                    match export_signer() {
+                        Ok(signer) => {
+                            self.signing_key = Some(signer.public_key());
+                            self.settings_status = None;
+                        }
+                        Err(e) => {
+                            self.settings_status =
+                                Some(tr!(self.language, "signing-key-failed", error = e));
+                            return Task::none();
+                        }
+                    }
                 }
+                self.settings.sign_exports = sign;
+                persistence::save_settings(&self.settings, self.language);
             }
-            Message::ExportPdf => {
-                if let Some(record) = self.current_export_record() {
-                    let filename = export::default_filename(&record.score_name, "pdf");
-                    return Task::perform(
-                        async move {
-                            export::pdf_export::export_to_pdf_file(&record, &filename)
-                                .map(|()| filename)
-                        },
-                        Message::ExportComplete,
-                    );
+            Message::CopySigningKey => {
+                if let Some(key) = &self.signing_key {
+                    return iced::clipboard::write(key.clone());
                 }
             }
-            Message::ExportComplete(result) => {
-                if let AppState::ScoreCalculation { ref mut error, .. } = self.state {
-                    match result {
-                        Ok(filename) => {
-                            let msg = match self.language {
-                                Language::German => format!("Exportiert: {}", filename),
-                                Language::English => format!("Exported: {}", filename),
-                            };
-                            *error = Some(msg); // Reuse error field for status messages
+            Message::HistoryRetentionChanged(retention) => {
+                self.settings.history_retention = retention;
+                self.retention_limit_input = retention
+                    .limit()
+                    .map(|limit| limit.to_string())
+                    .unwrap_or_default();
+                persistence::save_settings(&self.settings, self.language);
+            }
+            Message::RetentionLimitChanged(value) => {
+                if let Some(limit) = value.trim().parse::<u32>().ok().filter(|&n| n > 0) {
+                    self.settings.history_retention =
+                        self.settings.history_retention.with_limit(limit);
+                    persistence::save_settings(&self.settings, self.language);
+                }
+                self.retention_limit_input = value;
+            }
+            Message::PassphraseChanged(value) => {
+                self.passphrase_input = value;
+                self.encryption_status = None;
+            }
+            Message::PassphraseConfirmChanged(value) => {
+                self.passphrase_confirm_input = value;
+                self.encryption_status = None;
+            }
+            Message::OpenUnlockDialog => {
+                self.unlock_dialog_open = true;
+            }
+            Message::UnlockDialogDismissed => {
+                self.unlock_dialog_open = false;
+                self.passphrase_input.clear();
+                self.encryption_status = None;
+            }
+            Message::UnlockHistory => {
+                match persistence::unlock_history(&self.passphrase_input) {
+                    Ok((loaded, key)) => {
+                        if let Some(corrupt) = &loaded.corrupt {
+                            self.toasts.push(corrupt_file_toast(corrupt));
+                        }
+                        let mut unlocked = loaded.value.unwrap_or_default();
+                        // Calculations made while locked are newer than the saved ones
+                        let locked = std::mem::take(&mut self.history);
+                        unlocked.extend(locked.iter().cloned());
+                        self.history = unlocked;
+                        self.history_storage = HistoryStorage::Encrypted(key);
+                        self.expanded_history.clear();
+                        prune_and_save_history(
+                            &mut self.history,
+                            &locked,
+                            &self.settings,
+                            &self.history_storage,
+                        );
+                        self.unlock_dialog_open = false;
+                        self.passphrase_input.clear();
+                        self.encryption_status = None;
+                    }
+                    Err(history_crypto::HistoryCryptoError::WrongPassphrase) => {
+                        self.encryption_status =
+                            Some(tr!(self.language, "unlock-wrong-passphrase"));
+                    }
+                    Err(e) => {
+                        self.encryption_status =
+                            Some(tr!(self.language, "unlock-failed", error = e.to_string()));
+                    }
+                }
+            }
+            Message::EnableHistoryEncryption => {
+                if let Some(problem) = self.passphrase_problem() {
+                    self.encryption_status = Some(problem);
+                    return Task::none();
+                }
+                match persistence::encrypt_history(&self.history, &self.passphrase_input) {
+                    Ok(key) => {
+                        self.history_storage = HistoryStorage::Encrypted(key);
+                        self.passphrase_input.clear();
+                        self.passphrase_confirm_input.clear();
+                        self.encryption_status = Some(tr!(self.language, "encryption-enabled"));
+                    }
+                    Err(e) => {
+                        self.encryption_status = Some(tr!(
+                            self.language,
+                            "encryption-failed",
+                            error = e.to_string()
+                        ));
+                    }
+                }
+            }
+            Message::DisableHistoryEncryption => {
+                if let HistoryStorage::Encrypted(_) = self.history_storage {
+                    match persistence::decrypt_history(&self.history) {
+                        Ok(()) => {
+                            self.history_storage = HistoryStorage::Plain;
+                            self.encryption_status =
+                                Some(tr!(self.language, "encryption-disabled"));
                         }
                         Err(e) => {
-                            let msg = match self.language {
-                                Language::German => format!("Export fehlgeschlagen: {}", e),
-                                Language::English => format!("Export failed: {}", e),
-                            };
-                            *error = Some(msg);
+                            self.encryption_status = Some(tr!(
+                                self.language,
+                                "decryption-failed",
+                                error = e.to_string()
+                            ));
                         }
                     }
                 }
             }
-        }
-        Task::none()
-    }
-
-    /// Build an ExportRecord from the current calculation result (if any)
-    fn current_export_record(&self) -> Option<ExportRecord> {
-        if let AppState::ScoreCalculation {
-            ref score_id,
-            ref result,
-            ..
-        } = self.state
-        {
-            let calc_result = result.as_ref()?;
-            let score_def = self
-                .score_library
-                .as_ref()
-                .and_then(|lib| lib.get_score(score_id))?;
-            let score_name = match self.language {
-                Language::German => &score_def.name_de,
-                Language::English => &score_def.name,
-            };
-            let use_german = self.language == Language::German;
-            Some(ExportRecord::from_result(
-                calc_result,
-                score_name,
-                use_german,
-            ))
-        } else {
-            None
-        }
-    }
-
-    fn theme(&self) -> iced::Theme {
-        self.settings.theme.iced_theme()
+            Message::ScoreDirInputChanged(value) => {
+                self.score_dir_input = value;
+                self.settings_status = None;
+            }
+            Message::AddScoreDir => {
+                let input = self.score_dir_input.trim();
+                if input.is_empty() {
+                    return Task::none();
+                }
+                let dir = PathBuf::from(input);
+                if !dir.is_dir() {
+                    self.settings_status = Some(tr!(
+                        self.language,
+                        "score-dir-not-found",
+                        path = dir.display().to_string()
+                    ));
+                    return Task::none();
+                }
+                if !self.settings.extra_score_dirs.contains(&dir) {
+                    self.settings.extra_score_dirs.push(dir);
+                    persistence::save_settings(&self.settings, self.language);
+                }
+                self.score_dir_input.clear();
+                self.settings_status = None;
+                return self.load_scores_task();
+            }
+            Message::RemoveScoreDir(index) => {
+                if index < self.settings.extra_score_dirs.len() {
+                    self.settings.extra_score_dirs.remove(index);
+                    persistence::save_settings(&self.settings, self.language);
+                    self.settings_status = None;
+                    return self.load_scores_task();
+                }
+            }
+            Message::PortableModeToggled(portable) => {
+                let result = match paths::portable_marker() {
+                    Some(marker) if portable => std::fs::write(&marker, "").map(|()| marker),
+                    Some(marker) => std::fs::remove_file(&marker).map(|()| marker),
+                    None => Err(std::io::Error::other("program folder unknown")),
+                };
+                match result {
+                    Ok(_) => {
+                        self.portable_marker = portable;
+                        self.settings_status = None;
+                    }
+                    Err(e) => {
+                        self.settings_status = Some(tr!(
+                            self.language,
+                            "portable-mode-failed",
+                            error = e.to_string()
+                        ));
+                    }
+                }
+            }
+            Message::LogFileToggled(enabled) => {
+                self.settings.log_file = enabled;
+                apply_log_file_setting(enabled);
+                persistence::save_settings(&self.settings, self.language);
+            }
+            Message::ChooseExportDir => {
+                let mut dialog = rfd::AsyncFileDialog::new()
+                    .set_title(tr!(self.language, "export-dir-choose-title"));
+                if let Some(dir) = self.settings.export_dir() {
+                    dialog = dialog.set_directory(dir);
+                }
+                return Task::perform(
+                    async move {
+                        dialog
+                            .pick_folder()
+                            .await
+                            .map(|handle| handle.path().to_path_buf())
+                    },
+                    Message::ExportDirChosen,
+                );
+            }
+            Message::RestoreBackup(name) => {
+                match backup::restore_snapshot(&name) {
+                    Ok(()) => {
+                        self.reload_persisted_state();
+                        self.settings_status = None;
+                        self.toasts
+                            .push(Toast::new("backup-restored").with_arg("name", name));
+                        return self.load_scores_task();
+                    }
+                    Err(e) => {
+                        self.settings_status = Some(tr!(
+                            self.language,
+                            "backup-restore-failed",
+                            error = e.to_string()
+                        ));
+                    }
+                }
+                self.backups = backup::snapshots();
+            }
+            Message::ExportSettings => {
+                let persisted = PersistedSettings::from((&self.settings, self.language));
+                let filename = export::default_filename("settings", "json");
+                return self.save_export(filename, move |path| {
+                    persistence::write_settings_file(path, &persisted).map_err(|e| e.to_string())
+                });
+            }
+            Message::ImportSettings => {
+                let mut dialog = rfd::AsyncFileDialog::new()
+                    .set_title(tr!(self.language, "settings-import-title"))
+                    .add_filter("JSON", &["json"]);
+                if let Some(dir) = self.settings.export_dir() {
+                    dialog = dialog.set_directory(dir);
+                }
+                return Task::perform(
+                    async move {
+                        dialog
+                            .pick_file()
+                            .await
+                            .map(|handle| handle.path().to_path_buf())
+                    },
+                    Message::SettingsFileChosen,
+                );
+            }
+            Message::SettingsFileChosen(Some(path)) => {
+                match persistence::read_settings_file(&path) {
+                    Ok(persisted) => {
+                        let (settings, language) = persisted.into_settings();
+                        self.apply_settings(settings, language);
+                        persistence::save_settings(&self.settings, self.language);
+                        self.settings_status = None;
+                        self.toasts.push(
+                            Toast::new("settings-imported")
+                                .with_arg("file", path.display().to_string()),
+                        );
+                        return self.load_scores_task();
+                    }
+                    Err(e) => {
+                        self.settings_status =
+                            Some(tr!(self.language, "import-failed", error = e.to_string()));
+                    }
+                }
+            }
+            Message::SettingsFileChosen(None) => {}
+            Message::ExportDirChosen(Some(dir)) => {
+                self.settings.export_dir = Some(dir);
+                persistence::save_settings(&self.settings, self.language);
+            }
+            Message::ExportDirChosen(None) => {}
+            Message::ResetExportDir => {
+                self.settings.export_dir = None;
+                persistence::save_settings(&self.settings, self.language);
+            }
+            Message::StrictLoadingToggled(strict) => {
+                self.settings.strict_score_loading = strict;
+                persistence::save_settings(&self.settings, self.language);
+                self.settings_status = None;
+                return self.load_scores_task();
+            }
+            Message::HelpHintsToggled(show) => {
+                self.settings.show_help_hints = show;
+                persistence::save_settings(&self.settings, self.language);
+            }
+            Message::WizardModeToggled(enabled) => {
+                self.settings.wizard_for_long_scores = enabled;
+                persistence::save_settings(&self.settings, self.language);
+            }
+            Message::HighContrastToggled(enabled) => {
+                self.settings.theme = if enabled {
+                    AppTheme::HighContrast
+                } else {
+                    AppTheme::Light
+                };
+                persistence::save_settings(&self.settings, self.language);
+            }
+            Message::PatientLabelsDisabled(disabled) => {
+                self.settings.disable_patient_labels = disabled;
+                persistence::save_settings(&self.settings, self.language);
+                // Open calculations drop the field (and anything typed into it) right away
+                for tab in &mut self.tabs {
+                    if let AppState::ScoreCalculation { input_state, .. } = &mut tab.state {
+                        input_state.patient_label = (!disabled).then(String::new);
+                    }
+                }
+            }
+            Message::TouchModeToggled(enabled) => {
+                self.settings.touch_mode = enabled;
+                self.layout = self.layout.with_touch(enabled);
+                persistence::save_settings(&self.settings, self.language);
+            }
+            Message::PackPathInputChanged(value) => {
+                self.pack_path_input = value;
+                self.settings_status = None;
+            }
+            Message::ImportPack => {
+                let input = self.pack_path_input.trim();
+                if input.is_empty() {
+                    return Task::none();
+                }
+                let Some(user_dir) = scores::user_scores_dir() else {
+                    self.settings_status = Some(tr!(self.language, "pack-no-user-dir"));
+                    return Task::none();
+                };
+                match scores::pack::install_pack(input, user_dir) {
+                    Ok(installed) => {
+                        let count = installed.score_ids.len();
+                        self.toasts.push(
+                            Toast::new("pack-installed")
+                                .with_arg(
+                                    "name",
+                                    installed
+                                        .manifest
+                                        .name
+                                        .text(self.language.code())
+                                        .to_string(),
+                                )
+                                .with_arg("version", installed.manifest.version.clone())
+                                .with_arg("count", count)
+                                .with_arg(
+                                    "integrity",
+                                    i18n::pack_integrity_text(
+                                        self.language,
+                                        &installed
+                                            .integrity
+                                            .trusted(&self.settings.trusted_pack_keys),
+                                    ),
+                                ),
+                        );
+                        self.pack_path_input.clear();
+                        self.settings_status = None;
+                        return self.load_scores_task();
+                    }
+                    Err(e) => {
+                        self.settings_status =
+                            Some(tr!(self.language, "import-failed", error = e.to_string()));
+                    }
+                }
+            }
+            Message::TrustedKeyInputChanged(value) => {
+                self.trusted_key_input = value;
+                self.settings_status = None;
+            }
+            Message::AddTrustedKey => {
+                let input = self.trusted_key_input.trim();
+                if input.is_empty() {
+                    return Task::none();
+                }
+                let Some(key) = scores::pack::parse_public_key(input) else {
+                    self.settings_status =
+                        Some(tr!(self.language, "trusted-key-invalid", key = input));
+                    return Task::none();
+                };
+                if !self.settings.trusted_pack_keys.contains(&key) {
+                    self.settings.trusted_pack_keys.push(key);
+                    persistence::save_settings(&self.settings, self.language);
+                }
+                self.trusted_key_input.clear();
+                self.settings_status = None;
+            }
+            Message::RemoveTrustedKey(index) => {
+                if index < self.settings.trusted_pack_keys.len() {
+                    self.settings.trusted_pack_keys.remove(index);
+                    persistence::save_settings(&self.settings, self.language);
+                    self.settings_status = None;
+                }
+            }
+            Message::OpenHistory => {
+                self.header_menu_open = false;
+                self.history_status = None;
+                self.history_visible = history::PAGE_SIZE;
+                self.tab_mut().open(AppState::History);
+            }
+            Message::CloseHistory => {
+                self.tab_mut().go_back();
+            }
+            Message::ReopenHistoryEntry(index) => {
+                let Some(entry) = self.history.get(index) else {
+                    return Task::none();
+                };
+                let Some(score) = self
+                    .score_library
+                    .as_ref()
+                    .and_then(|lib| lib.get_score(&entry.score_id))
+                else {
+                    self.history_status = Some(tr!(self.language, "history-score-not-loaded"));
+                    return Task::none();
+                };
+
+                let mut input_state = ScoreInputState::with_inputs(
+                    score,
+                    &entry.inputs,
+                    self.language,
+                    self.settings.unit_system,
+                );
+                if !self.settings.disable_patient_labels {
+                    input_state.patient_label =
+                        Some(entry.patient_label.clone().unwrap_or_default());
+                }
+                show_fhir_prefill(&mut input_state, score, &self.settings);
+                if score.uses_wizard(self.settings.wizard_for_long_scores) {
+                    // Straight to the review page
+                    input_state.wizard_step = Some(score.grouped_inputs().len());
+                }
+                // A new tab keeps the calculation the history was opened from
+                self.tabs.push(Tab::new(AppState::ScoreCalculation {
+                    specialty: score.specialty,
+                    score_id: entry.score_id.clone(),
+                    input_state: Box::new(input_state),
+                    result: None,
+                    error: None,
+                }));
+                self.active_tab = self.tabs.len() - 1;
+            }
+            Message::HistoryEntryToggled(index) => {
+                if !self.expanded_history.remove(&index) {
+                    self.expanded_history.insert(index);
+                }
+            }
+            Message::HistorySpecialtyFilterChanged(specialty) => {
+                self.history_filter.specialty = specialty;
+                self.history_visible = history::PAGE_SIZE;
+            }
+            Message::HistoryScoreFilterChanged(score_id) => {
+                self.history_filter.score_id = score_id;
+                self.history_visible = history::PAGE_SIZE;
+            }
+            Message::HistoryFromChanged(value) => {
+                self.history_filter.from = history::parse_date(&value);
+                self.history_visible = history::PAGE_SIZE;
+                self.history_from_input = value;
+            }
+            Message::HistoryToChanged(value) => {
+                self.history_filter.to = history::parse_date(&value);
+                self.history_visible = history::PAGE_SIZE;
+                self.history_to_input = value;
+            }
+            Message::HistorySearchChanged(query) => {
+                self.history_filter.query = query;
+                self.history_visible = history::PAGE_SIZE;
+            }
+            Message::HistoryShowMore => {
+                self.history_visible += history::PAGE_SIZE;
+            }
+            Message::SessionTitleChanged(title) => {
+                self.session_title_input = title;
+            }
+            Message::StartSession => {
+                if !self.session_title_input.trim().is_empty() {
+                    self.active_session = Some(Session::start(&self.session_title_input));
+                    self.session_title_input.clear();
+                }
+            }
+            Message::EndSession => {
+                self.active_session = None;
+            }
+            Message::PrintSessionReport(session_id) => {
+                if let Some(report) = self.session_report(&session_id) {
+                    let html = report.to_html(self.language == Language::German);
+                    let filename = export::default_filename("session_report", "html");
+                    let tab = self.tab().id;
+                    return Task::perform(
+                        async move { export::print::open_print_dialog(&html, &filename) },
+                        move |result| Message::PrintComplete { tab, result },
+                    );
+                }
+            }
+            Message::ExportSessionPdf(session_id) => {
+                if let Some(report) = self.session_report(&session_id) {
+                    let filename = export::default_filename(&report.title, "pdf");
+                    let options = self.pdf_options();
+                    return self.save_sealed_export(filename, move |path| {
+                        export::pdf_export::export_session_to_pdf_file(&report, &options, path)
+                    });
+                }
+            }
+            Message::ExportSessionJson(session_id) => {
+                if let Some(report) = self.session_report(&session_id) {
+                    let filename = export::default_filename(&report.title, "json");
+                    return self.save_sealed_export(filename, move |path| {
+                        report.export_to_json_file(path)
+                    });
+                }
+            }
+            Message::ExportSessionCsv(session_id) => {
+                let entries: Vec<&HistoryEntry> =
+                    history::session_entries(&self.history, &session_id).collect();
+                if let Some(session) = entries.first().and_then(|entry| entry.session.clone()) {
+                    let records: Vec<ExportRecord> = entries
+                        .iter()
+                        .map(|entry| self.history_export_record(entry))
+                        .collect();
+                    let filename = export::default_filename(&self.session_title(&session), "csv");
+                    return self.save_export(filename, move |path| {
+                        export::csv_export::export_records_to_csv_file(&records, path)
+                    });
+                }
+            }
+            Message::HistoryFilterCleared => {
+                self.history_filter = HistoryFilter::default();
+                self.history_visible = history::PAGE_SIZE;
+                self.history_from_input.clear();
+                self.history_to_input.clear();
+            }
+            Message::ClearHistory => {
+                self.confirm_clear_history = true;
+            }
+            Message::ClearHistoryCancelled => {
+                self.confirm_clear_history = false;
+            }
+            Message::ClearHistoryConfirmed => {
+                self.confirm_clear_history = false;
+                let cleared = std::mem::take(&mut self.history);
+                persistence::save_history_changes(&[], &cleared, &self.history_storage);
+                self.cleared_history = Some(cleared);
+                self.expanded_history.clear();
+                self.history_status = None;
+            }
+            Message::UndoClearHistory => {
+                if let Some(cleared) = self.cleared_history.take() {
+                    // Calculations made since clearing are newer, so they go last
+                    let mut restored = cleared.clone();
+                    restored.append(&mut self.history);
+                    self.history = restored;
+                    self.expanded_history.clear();
+                    prune_and_save_history(
+                        &mut self.history,
+                        &cleared,
+                        &self.settings,
+                        &self.history_storage,
+                    );
+                }
+            }
+            Message::ExportHistoryDeidentified => {
+                let records: Vec<ExportRecord> = self
+                    .history
+                    .iter()
+                    .map(|entry| entry.to_export_record(self.language, self.score_library.as_ref()))
+                    .collect();
+                let policy = DeidentificationPolicy {
+                    timestamp_precision: self.settings.research_timestamp_precision,
+                    ..DeidentificationPolicy::default()
+                };
+                let exported_at = Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+                let filename = export::default_filename("research_deidentified", "json");
+                return self.save_sealed_export(filename, move |path| {
+                    export::deidentify::export_deidentified_json_file(
+                        &records,
+                        &policy,
+                        &exported_at,
+                        path,
+                    )
+                });
+            }
+            Message::OpenAbout => {
+                self.header_menu_open = false;
+                self.tab_mut().open(AppState::About);
+            }
+            Message::CloseAbout => {
+                self.tab_mut().go_back();
+            }
+            Message::OpenScoreInfo(score_id) => {
+                self.tab_mut().open(AppState::ScoreInfo { score_id });
+            }
+            Message::CloseScoreInfo => {
+                self.tab_mut().go_back();
+            }
+            Message::OpenUrl(url) => {
+                if let Err(e) = opener::open(&url) {
+                    tracing::warn!("Failed to open {}: {}", url, e);
+                }
+            }
+            Message::ExportHistoryTransfer => {
+                let history = self.history.clone();
+                let filename = export::default_filename("history_transfer", "json");
+                return self.save_export(filename, move |path| {
+                    persistence::write_history_file(path, &history).map_err(|e| e.to_string())
+                });
+            }
+            Message::HistoryImportPathChanged(path) => {
+                self.history_import_input = path;
+            }
+            Message::ImportHistory => {
+                let path = self.history_import_input.trim();
+                if path.is_empty() {
+                    return Task::none();
+                }
+                match persistence::read_history_file(Path::new(path)) {
+                    Ok(imported) => {
+                        let total = imported.len();
+                        let added = history::merge_history(&mut self.history, imported);
+                        self.expanded_history.clear();
+                        prune_and_save_history(
+                            &mut self.history,
+                            &added,
+                            &self.settings,
+                            &self.history_storage,
+                        );
+                        self.history_import_input.clear();
+                        self.history_status = Some(tr!(
+                            self.language,
+                            "history-imported",
+                            added = added.len(),
+                            present = total - added.len()
+                        ));
+                    }
+                    Err(e) => {
+                        self.history_status =
+                            Some(tr!(self.language, "import-failed", error = e.to_string()));
+                    }
+                }
+            }
+            Message::ExportHistoryCsv => {
+                let records = self.filtered_history_records();
+                let filename = export::default_filename("history", "csv");
+                return self.save_export(filename, move |path| {
+                    export::csv_export::export_records_to_csv_file(&records, path)
+                });
+            }
+            Message::ExportHistoryPdf => {
+                let reports: Vec<_> = self
+                    .filtered_history()
+                    .iter()
+                    .map(|entry| self.history_pdf_report(entry))
+                    .collect();
+                let title = tr!(self.language, "history-report-title");
+                let filename = export::default_filename("history", "pdf");
+                let options = self.pdf_options();
+                return self.save_sealed_export(filename, move |path| {
+                    export::pdf_export::export_reports_to_pdf_file(&title, &reports, &options, path)
+                });
+            }
+            Message::ExportHistoryJson => {
+                let records = self.filtered_history_records();
+                let filename = export::default_filename("history", "json");
+                return self.save_export(filename, move |path| {
+                    export::json_export::export_records_to_json_file(&records, path)
+                });
+            }
+            Message::ExportCsv => {
+                if let Some(record) = self.current_export_record(false) {
+                    let filename = export::default_filename(&record.score_name, "csv");
+                    return self.save_export(filename, move |path| {
+                        export::csv_export::export_to_csv_file(&record, path)
+                    });
+                }
+            }
+            Message::ExportJson => {
+                if let Some(record) = self.current_export_record(false) {
+                    let filename = export::default_filename(&record.score_name, "json");
+                    return self.save_sealed_export(filename, move |path| {
+                        export::json_export::export_to_json_file(&record, path)
+                    });
+                }
+            }
+            Message::ExportPdf => {
+                if let Some(report) = self.current_pdf_report(false) {
+                    let filename = export::default_filename(&report.record.score_name, "pdf");
+                    let options = self.pdf_options();
+                    return self.save_sealed_export(filename, move |path| {
+                        export::pdf_export::export_to_pdf_file(&report, &options, path)
+                    });
+                }
+            }
+            Message::ExportMarkdown => {
+                if let Some((record, inputs)) = self.current_printed_result() {
+                    let filename = export::default_filename(&record.score_name, "md");
+                    let use_german = self.language == Language::German;
+                    return self.save_export(filename, move |path| {
+                        export::markdown_export::export_to_markdown_file(
+                            &record, &inputs, use_german, path,
+                        )
+                    });
+                }
+            }
+            Message::CopySummary => {
+                if let Some(record) = self.current_export_record(false) {
+                    let use_german = self.language == Language::German;
+                    let summary = export::text_export::summary_text(&record, use_german);
+                    return self.copy_to_clipboard(summary, "result-copied");
+                }
+            }
+            Message::CopyMarkdown => {
+                if let Some((record, inputs)) = self.current_printed_result() {
+                    let use_german = self.language == Language::German;
+                    let markdown =
+                        export::markdown_export::export_to_markdown(&record, &inputs, use_german);
+                    return self.copy_to_clipboard(markdown, "result-markdown-copied");
+                }
+            }
+            Message::ExportFhir => {
+                if let Some((score_name, observation)) = self.current_fhir_observation() {
+                    let filename = export::default_filename(&format!("{score_name}_fhir"), "json");
+                    return self.save_export(filename, move |path| {
+                        export::fhir_export::export_to_fhir_file(&observation, path)
+                    });
+                }
+            }
+            Message::ExportWithTemplate(name) => {
+                let template = self
+                    .export_templates
+                    .iter()
+                    .find(|template| template.display_name() == name);
+                if let (Some(template), Some((record, inputs))) =
+                    (template, self.current_printed_result())
+                {
+                    let use_german = self.language == Language::German;
+                    let rendered = template.render(&record, &inputs, use_german);
+                    let filename =
+                        export::default_filename(&record.score_name, template.format.extension());
+                    match rendered {
+                        Ok(contents) => {
+                            return self.save_export(filename, move |path| {
+                                std::fs::write(path, &contents).map_err(|e| e.to_string())
+                            });
+                        }
+                        Err(e) => {
+                            if let AppState::ScoreCalculation { ref mut error, .. } =
+                                self.tab_mut().state
+                            {
+                                *error = Some(e.to_string());
+                            }
+                        }
+                    }
+                }
+            }
+            Message::ZeroPointFieldsToggled(show) => {
+                self.show_zero_point_fields = show;
+            }
+            Message::ExportPatientPdf => {
+                if let Some(report) = self.current_pdf_report(true) {
+                    let filename = export::default_filename(
+                        &format!("{}_patient", report.record.score_name),
+                        "pdf",
+                    );
+                    let options = self.pdf_options();
+                    return self.save_sealed_export(filename, move |path| {
+                        export::pdf_export::export_to_pdf_file(&report, &options, path)
+                    });
+                }
+            }
+            Message::Print => {
+                if let Some(html) = self.current_print_html() {
+                    let filename = export::default_filename("print", "html");
+                    let tab = self.tab().id;
+                    return Task::perform(
+                        async move { export::print::open_print_dialog(&html, &filename) },
+                        move |result| Message::PrintComplete { tab, result },
+                    );
+                }
+            }
+            Message::PrintComplete { result: Ok(()), .. } => {}
+            Message::PrintComplete {
+                tab,
+                result: Err(e),
+            } => {
+                tracing::warn!("Printing failed: {}", e);
+                // The tab may have been closed in the meantime
+                match self.tabs.iter_mut().find(|open| open.id == tab) {
+                    Some(Tab {
+                        state: AppState::ScoreCalculation { error, .. },
+                        ..
+                    }) => {
+                        *error = Some(tr!(self.language, "print-failed", error = e.to_string()));
+                    }
+                    // Session reports are printed from the history or the session bar
+                    _ => self
+                        .toasts
+                        .push(Toast::new("print-failed").with_arg("error", e.to_string())),
+                }
+            }
+            Message::ExportComplete {
+                result: Ok(path), ..
+            } => {
+                tracing::info!(path = %path.display(), "Exported");
+                // Shown with buttons to open the file or its folder
+                self.last_export = Some(path);
+            }
+            Message::ExportComplete {
+                tab,
+                result: Err(e),
+            } => {
+                tracing::warn!("Export failed: {}", e);
+                let toast = Toast::new("export-failed").with_arg("error", e);
+                let msg = toast.text(self.language);
+                // The tab may have been closed in the meantime
+                match self.tabs.iter_mut().find(|open| open.id == tab) {
+                    Some(Tab {
+                        state: AppState::ScoreCalculation { error, .. },
+                        ..
+                    }) => {
+                        *error = Some(msg); // Reuse error field for status messages
+                    }
+                    Some(Tab {
+                        state: AppState::History,
+                        ..
+                    }) => {
+                        self.history_status = Some(msg);
+                    }
+                    // E.g. the settings, exported from the settings page
+                    _ => self.toasts.push(toast),
+                }
+            }
+            Message::ExportCancelled => {}
+            Message::OpenExport => {
+                if let Some(path) = &self.last_export {
+                    if let Err(e) = opener::open(path) {
+                        tracing::warn!("Failed to open {}: {}", path.display(), e);
+                    }
+                }
+            }
+            Message::RevealExport => {
+                if let Some(path) = &self.last_export {
+                    if let Err(e) = opener::reveal(path) {
+                        tracing::warn!("Failed to show {}: {}", path.display(), e);
+                    }
+                }
+            }
+            Message::DismissExport => {
+                self.last_export = None;
+            }
+            Message::DismissToast(index) => {
+                if index < self.toasts.len() {
+                    self.toasts.remove(index);
+                }
+            }
+        }
+        Task::none()
     }
 
-    fn view(&self) -> Element<'_, Message> {
-        let language_label = match self.language {
-            Language::German => "🇬🇧 English",
-            Language::English => "🇩🇪 Deutsch",
+    /// Switch to `settings` and `language`, e.g. after importing a settings file.
+    /// The caller saves them and reloads the scores.
+    fn apply_settings(&mut self, settings: Settings, language: Language) {
+        self.settings = settings;
+        self.language = language;
+        self.layout = self.layout.with_touch(self.settings.touch_mode);
+        self.retention_limit_input = self
+            .settings
+            .history_retention
+            .limit()
+            .map(|limit| limit.to_string())
+            .unwrap_or_default();
+        self.apply_unit_system();
+    }
+
+    /// Show the values entered in open score tabs in the selected unit system
+    fn apply_unit_system(&mut self) {
+        let Some(library) = &self.score_library else {
+            return;
         };
+        for tab in &mut self.tabs {
+            if let AppState::ScoreCalculation {
+                score_id,
+                input_state,
+                ..
+            } = &mut tab.state
+            {
+                if let Some(score) = library.get_score(score_id) {
+                    input_state.set_unit_system(score, self.settings.unit_system, self.language);
+                }
+            }
+        }
+    }
 
-        let language_button = button(text(language_label))
-            .on_press(Message::LanguageToggled)
-            .padding(10);
+    /// Screen of a previous run with its inputs; the score list if the score is
+    /// no longer loaded
+    fn resumed_state(&self, screen: LastScreen) -> AppState {
+        let Some(score) = screen
+            .score_id
+            .as_deref()
+            .and_then(|id| self.score_library.as_ref()?.get_score(id))
+        else {
+            return AppState::ScoreSelection {
+                specialty: screen.specialty,
+            };
+        };
+        let mut input_state = ScoreInputState::with_inputs(
+            score,
+            &screen.inputs,
+            self.language,
+            self.settings.unit_system,
+        );
+        if !self.settings.disable_patient_labels {
+            input_state.patient_label = Some(screen.patient_label.unwrap_or_default());
+        }
+        show_fhir_prefill(&mut input_state, score, &self.settings);
+        if score.uses_wizard(self.settings.wizard_for_long_scores) {
+            input_state.wizard_step = Some(0);
+        }
+        AppState::ScoreCalculation {
+            specialty: screen.specialty,
+            score_id: screen.score_id.unwrap_or_default(),
+            input_state: Box::new(input_state),
+            result: None,
+            error: None,
+        }
+    }
+
+    /// Read the settings and the history from disk again, after a backup was restored
+    fn reload_persisted_state(&mut self) {
+        let loaded_settings = persistence::load_settings();
+        self.toasts
+            .extend(loaded_settings.corrupt.as_ref().map(corrupt_file_toast));
+        let (settings, language) = loaded_settings
+            .value
+            .map(PersistedSettings::into_settings)
+            .unwrap_or_else(|| (Settings::new(), self.language));
+        self.apply_settings(settings, language);
+
+        self.history_storage = persistence::history_storage();
+        self.history = load_history(&self.history_storage, &self.settings, &mut self.toasts);
+        self.unlock_dialog_open = matches!(self.history_storage, HistoryStorage::Locked);
+        self.expanded_history.clear();
+        self.cleared_history = None;
+        self.active_session = None;
+        self.backups = backup::snapshots();
+    }
+
+    /// [`Self::save_export`], sealed (and signed) afterwards if enabled in the
+    /// settings; for PDF reports and JSON objects
+    fn save_sealed_export<F>(&self, filename: String, write: F) -> Task<Message>
+    where
+        F: FnOnce(&Path) -> Result<(), String> + Send + 'static,
+    {
+        let seal = self.settings.seal_exports;
+        let sign = self.settings.sign_exports;
+        self.save_export(filename, move |path| {
+            write(path)?;
+            if seal {
+                let signer = if sign { Some(export_signer()?) } else { None };
+                integrity::seal_file(path, signer.as_ref()).map_err(|e| e.to_string())?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Ask where to save an export, starting in the export folder with `filename`
+    /// suggested, and write it there with `write`. Cancelling the dialog writes nothing.
+    fn save_export<F>(&self, filename: String, write: F) -> Task<Message>
+    where
+        F: FnOnce(&Path) -> Result<(), String> + Send + 'static,
+    {
+        let extension = Path::new(&filename)
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .unwrap_or_default()
+            .to_string();
+        let mut dialog = rfd::AsyncFileDialog::new()
+            .set_title(tr!(self.language, "export-save-title"))
+            .set_file_name(&filename)
+            .add_filter(extension.to_uppercase(), &[extension.as_str()]);
+        if let Some(dir) = self.settings.export_dir() {
+            dialog = dialog.set_directory(dir);
+        }
+
+        let tab = self.tab().id;
+        Task::perform(
+            async move {
+                let path = dialog.save_file().await?.path().to_path_buf();
+                Some(write(&path).map(|()| path))
+            },
+            move |result| {
+                result.map_or(Message::ExportCancelled, |result| Message::ExportComplete {
+                    tab,
+                    result,
+                })
+            },
+        )
+    }
+
+    fn tab(&self) -> &Tab {
+        &self.tabs[self.active_tab]
+    }
+
+    fn tab_mut(&mut self) -> &mut Tab {
+        &mut self.tabs[self.active_tab]
+    }
+
+    /// Build an ExportRecord from the current calculation result (if any).
+    /// With `patient_copy`, builds the simplified patient-facing variant.
+    fn current_export_record(&self, patient_copy: bool) -> Option<ExportRecord> {
+        self.current_export_record_in(patient_copy, self.language)
+    }
+
+    /// PDF report of the current calculation result (if any), with its
+    /// translation if bilingual reports are enabled
+    fn current_pdf_report(&self, patient_copy: bool) -> Option<export::pdf_export::PdfReport> {
+        let AppState::ScoreCalculation {
+            result: Some(ref calc_result),
+            ..
+        } = self.tab().state
+        else {
+            return None;
+        };
+        let use_german = self.language == Language::German;
+        let translation = if self.settings.bilingual_reports {
+            let other = if use_german {
+                Language::English
+            } else {
+                Language::German
+            };
+            Some((
+                self.current_export_record_in(patient_copy, other)?,
+                export::print::printed_inputs(calc_result, !use_german),
+            ))
+        } else {
+            None
+        };
+        Some(export::pdf_export::PdfReport {
+            record: self.current_export_record(patient_copy)?,
+            inputs: export::print::printed_inputs(calc_result, use_german),
+            risk_level: Some(calc_result.risk_level),
+            translation,
+        })
+    }
+
+    /// Export record of a history entry, anonymized as set in the settings
+    fn history_export_record(&self, entry: &HistoryEntry) -> ExportRecord {
+        let mut record = entry.to_export_record(self.language, self.score_library.as_ref());
+        self.settings.export_anonymization.apply(&mut record);
+        record
+    }
+
+    /// PDF report of a history entry, bilingual if enabled in the settings
+    fn history_pdf_report(&self, entry: &HistoryEntry) -> export::pdf_export::PdfReport {
+        let mut report = entry.to_pdf_report(
+            self.language,
+            self.score_library.as_ref(),
+            self.settings.bilingual_reports,
+        );
+        let anonymization = self.settings.export_anonymization;
+        anonymization.apply(&mut report.record);
+        if let Some((record, _)) = report.translation.as_mut() {
+            anonymization.apply(record);
+        }
+        report
+    }
+
+    /// Title of a session in exports; a generic one if patient labels are removed
+    fn session_title(&self, session: &Session) -> String {
+        if self.settings.export_anonymization.strip_patient_labels {
+            tr!(self.language, "session-report-title")
+        } else {
+            session.title.clone()
+        }
+    }
+
+    /// Calculations of a session as one report; `None` if it has none
+    fn session_report(&self, session_id: &str) -> Option<export::session_report::SessionReport> {
+        let entries: Vec<&HistoryEntry> =
+            history::session_entries(&self.history, session_id).collect();
+        let session = entries.first()?.session.as_ref()?;
+        Some(export::session_report::SessionReport {
+            title: self.session_title(session),
+            reports: entries
+                .iter()
+                .map(|entry| self.history_pdf_report(entry))
+                .collect(),
+        })
+    }
+
+    /// Institution lines and language of PDF reports
+    fn pdf_options(&self) -> export::pdf_export::PdfOptions {
+        export::pdf_export::PdfOptions {
+            header: self.settings.report_header.clone(),
+            footer: self.settings.report_footer.clone(),
+            use_german: self.language == Language::German,
+            qr_code: self.settings.report_qr_code,
+        }
+    }
+
+    /// [`Self::current_export_record`] with score texts in `language`
+    fn current_export_record_in(
+        &self,
+        patient_copy: bool,
+        language: Language,
+    ) -> Option<ExportRecord> {
+        if let AppState::ScoreCalculation {
+            ref score_id,
+            ref input_state,
+            ref result,
+            ..
+        } = self.tab().state
+        {
+            let calc_result = result.as_ref()?;
+            let score_def = self
+                .score_library
+                .as_ref()
+                .and_then(|lib| lib.get_score(score_id))?;
+            let score_name = score_def.name.text(language.code());
+            let use_german = language == Language::German;
+            let mut record = if patient_copy {
+                ExportRecord::patient_copy(calc_result, score_name, use_german)
+            } else {
+                ExportRecord::from_result(calc_result, score_name, use_german)
+            };
+            record.score_version = Some(score_def.version.clone());
+            record.case_id = input_state.patient_label();
+            record.references = score_def
+                .references
+                .iter()
+                .map(config::Reference::formatted)
+                .collect();
+            self.settings.export_anonymization.apply(&mut record);
+            Some(record)
+        } else {
+            None
+        }
+    }
 
-        let history_label = match self.language {
-            Language::German => "Verlauf",
-            Language::English => "History",
+    /// History entries matching the current filter as export records, oldest first
+    fn filtered_history_records(&self) -> Vec<ExportRecord> {
+        self.filtered_history()
+            .iter()
+            .map(|entry| self.history_export_record(entry))
+            .collect()
+    }
+
+    /// Entries matching the history filter, oldest first. Saved entries are
+    /// queried from the store; a locked history only exists in memory.
+    fn filtered_history(&self) -> Vec<HistoryEntry> {
+        match persistence::query_history(&self.history_filter, &self.history_storage) {
+            Some(Ok(entries)) => entries,
+            _ => {
+                let mut entries: Vec<HistoryEntry> =
+                    history::filter_history(&self.history, &self.history_filter)
+                        .map(|(_, entry)| entry.clone())
+                        .collect();
+                entries.reverse();
+                entries
+            }
+        }
+    }
+
+    /// Put `contents` on the clipboard and confirm with the message `status_id`
+    /// below the result
+    fn copy_to_clipboard(&mut self, contents: String, status_id: &str) -> Task<Message> {
+        let status = tr!(self.language, status_id);
+        if let AppState::ScoreCalculation { ref mut error, .. } = self.tab_mut().state {
+            *error = Some(status); // Reuse error field for status messages
+        }
+        iced::clipboard::write(contents)
+    }
+
+    /// The current result with its inputs as entered, for the print layout and Markdown
+    fn current_printed_result(&self) -> Option<(ExportRecord, Vec<export::print::PrintedInput>)> {
+        let record = self.current_export_record(false)?;
+        let AppState::ScoreCalculation {
+            result: Some(ref calc_result),
+            ..
+        } = self.tab().state
+        else {
+            return None;
         };
+        let use_german = self.language == Language::German;
+        let inputs = export::print::printed_inputs(calc_result, use_german);
+        Some((record, inputs))
+    }
+
+    /// FHIR Observation of the current calculation result, with the score name
+    fn current_fhir_observation(&self) -> Option<(String, serde_json::Value)> {
+        let record = self.current_export_record(false)?;
+        let AppState::ScoreCalculation {
+            ref score_id,
+            result: Some(ref calc_result),
+            ..
+        } = self.tab().state
+        else {
+            return None;
+        };
+        let score_def = self
+            .score_library
+            .as_ref()
+            .and_then(|lib| lib.get_score(score_id))?;
+        let use_german = self.language == Language::German;
+        let observation =
+            export::fhir_export::fhir_observation(score_def, calc_result, &record, use_german);
+        Some((record.score_name, observation))
+    }
+
+    /// Print layout of the current calculation result (if any)
+    fn current_print_html(&self) -> Option<String> {
+        let (record, inputs) = self.current_printed_result()?;
+        let use_german = self.language == Language::German;
+        Some(export::print::print_html(&record, &inputs, use_german))
+    }
+
+    fn subscription(&self) -> Subscription<Message> {
+        window::resize_events().map(|(_id, size)| Message::WindowResized(size))
+    }
+
+    fn theme(&self) -> iced::Theme {
+        self.settings.iced_theme()
+    }
+
+    fn view(&self) -> Element<'_, Message> {
+        let language_picker = pick_list(
+            Language::ALL,
+            Some(self.language),
+            Message::LanguageSelected,
+        )
+        .padding(self.layout.button_padding(10));
+
+        let history_label = tr!(self.language, "history");
 
         let history_count = if self.history.is_empty() {
             String::new()
@@ -400,38 +2377,53 @@ impl KlinScore {
 
         let history_button = button(text(format!("{}{}", history_label, history_count)))
             .on_press(Message::OpenHistory)
-            .padding(10);
+            .padding(self.layout.button_padding(10));
 
-        let about_label = match self.language {
-            Language::German => "Über",
-            Language::English => "About",
-        };
-
-        let about_button = button(text(about_label))
+        let about_button = button(text(tr!(self.language, "about")))
             .on_press(Message::OpenAbout)
-            .padding(10);
-
-        let settings_label = match self.language {
-            Language::German => "Einstellungen",
-            Language::English => "Settings",
-        };
+            .padding(self.layout.button_padding(10));
 
-        let settings_button = button(text(settings_label))
+        let settings_button = button(text(tr!(self.language, "settings")))
             .on_press(Message::OpenSettings)
-            .padding(10);
-
-        let header = row![
-            text("KlinScore").size(32).width(Length::Fill),
-            history_button,
-            settings_button,
-            about_button,
-            language_button
-        ]
-        .spacing(10)
-        .align_y(Alignment::Center)
-        .padding(20);
+            .padding(self.layout.button_padding(10));
 
-        let content = match &self.state {
+        // In narrow windows, history/settings/about collapse behind a menu button
+        let header: Element<'_, Message> = if self.layout.is_compact() {
+            let menu_button = button(text(if self.header_menu_open { "✕" } else { "☰" }))
+                .on_press(Message::HeaderMenuToggled)
+                .padding(self.layout.button_padding(10));
+            let title_row = row![
+                text("KlinScore").size(24).width(Length::Fill),
+                language_picker,
+                menu_button
+            ]
+            .spacing(10)
+            .align_y(Alignment::Center);
+
+            let mut header = column![title_row].spacing(10).padding(10);
+            if self.header_menu_open {
+                header = header.push(
+                    row![history_button, settings_button, about_button]
+                        .spacing(10)
+                        .wrap(),
+                );
+            }
+            header.into()
+        } else {
+            row![
+                text("KlinScore").size(32).width(Length::Fill),
+                history_button,
+                settings_button,
+                about_button,
+                language_picker
+            ]
+            .spacing(10)
+            .align_y(Alignment::Center)
+            .padding(20)
+            .into()
+        };
+
+        let content = match &self.tab().state {
             AppState::Loading => self.loading_view(),
             AppState::Welcome => self.welcome_view(),
             AppState::SpecialtySelection => self.specialty_view(),
@@ -451,34 +2443,263 @@ impl KlinScore {
             ),
             AppState::History => self.history_view(),
             AppState::About => self.about_view(),
+            AppState::ScoreInfo { score_id } => self.score_info_view(score_id),
             AppState::Settings => self.settings_view(),
             AppState::Error(error) => self.error_view(error),
         };
 
         let scrollable_content = scrollable(content);
 
-        let main_column = column![header, scrollable_content]
-            .spacing(20)
-            .width(Length::Fill)
-            .height(Length::Fill);
+        let toasts = ui::toast_stack_view(&self.toasts, self.language, Message::DismissToast);
 
-        container(main_column)
+        let main_column = column![
+            header,
+            self.tab_bar(),
+            self.session_bar(),
+            toasts,
+            self.export_bar(),
+            scrollable_content
+        ]
+        .spacing(20)
+        .width(Length::Fill)
+        .height(Length::Fill);
+
+        let main = container(main_column)
             .width(Length::Fill)
             .height(Length::Fill)
             .center_x(Length::Fill)
-            .padding(20)
+            .padding(self.layout.padding(20));
+
+        if self.unlock_dialog_open {
+            ui::modal(main, self.unlock_dialog(), Message::UnlockDialogDismissed)
+        } else if self.confirm_clear_history {
+            ui::modal(
+                main,
+                self.clear_history_dialog(),
+                Message::ClearHistoryCancelled,
+            )
+        } else {
+            main.into()
+        }
+    }
+
+    /// Passphrase prompt for the encrypted history
+    fn unlock_dialog(&self) -> Element<'_, Message> {
+        column![
+            text(tr!(self.language, "unlock-title")).size(20),
+            text(tr!(self.language, "unlock-body")).size(14),
+            text_input(&tr!(self.language, "passphrase"), &self.passphrase_input)
+                .secure(true)
+                .on_input(Message::PassphraseChanged)
+                .on_submit(Message::UnlockHistory)
+                .padding(self.layout.button_padding(8))
+                .style(self.layout.text_input_style()),
+        ]
+        .push_maybe(self.encryption_status.as_ref().map(|status| {
+            text(status)
+                .size(13)
+                .color(iced::Color::from_rgb(0.8, 0.1, 0.1))
+        }))
+        .push(
+            row![
+                button(text(tr!(self.language, "unlock-skip")).size(14))
+                    .on_press(Message::UnlockDialogDismissed)
+                    .padding(self.layout.button_padding(8))
+                    .style(button::secondary),
+                button(text(tr!(self.language, "unlock")).size(14))
+                    .on_press_maybe(
+                        (!self.passphrase_input.is_empty()).then_some(Message::UnlockHistory),
+                    )
+                    .padding(self.layout.button_padding(8)),
+            ]
+            .spacing(10),
+        )
+        .spacing(15)
+        .into()
+    }
+
+    /// Why the typed passphrase cannot be used to enable encryption, if it cannot
+    fn passphrase_problem(&self) -> Option<String> {
+        if self.passphrase_input.chars().count() < history_crypto::MIN_PASSPHRASE_CHARS {
+            Some(tr!(
+                self.language,
+                "passphrase-too-short",
+                min = history_crypto::MIN_PASSPHRASE_CHARS
+            ))
+        } else if self.passphrase_input != self.passphrase_confirm_input {
+            Some(tr!(self.language, "passphrase-mismatch"))
+        } else {
+            None
+        }
+    }
+
+    /// Confirmation before all history entries are deleted
+    fn clear_history_dialog(&self) -> Element<'_, Message> {
+        column![
+            text(tr!(self.language, "clear-history-title")).size(20),
+            text(tr!(
+                self.language,
+                "clear-history-body",
+                count = self.history.len()
+            ))
+            .size(14),
+            row![
+                button(text(tr!(self.language, "cancel")).size(14))
+                    .on_press(Message::ClearHistoryCancelled)
+                    .padding(self.layout.button_padding(8))
+                    .style(button::secondary),
+                button(text(tr!(self.language, "delete")).size(14))
+                    .on_press(Message::ClearHistoryConfirmed)
+                    .padding(self.layout.button_padding(8))
+                    .style(button::danger),
+            ]
+            .spacing(10),
+        ]
+        .spacing(15)
+        .into()
+    }
+
+    /// Row of open tabs with a close button each, plus a button for a new tab
+    fn tab_bar(&self) -> Element<'_, Message> {
+        let closable = self.tabs.len() > 1;
+        let tabs = self.tabs.iter().enumerate().map(|(index, tab)| {
+            let style = if index == self.active_tab {
+                button::primary
+            } else {
+                button::secondary
+            };
+            let mut tab_row = row![button(text(self.tab_title(tab)).size(14))
+                .on_press(Message::TabSelected(index))
+                .padding(self.layout.button_padding(8))
+                .style(style)]
+            .align_y(Alignment::Center);
+            if closable {
+                tab_row = tab_row.push(
+                    button(text("✕").size(14))
+                        .on_press(Message::TabClosed(index))
+                        .padding(self.layout.button_padding(8))
+                        .style(button::text),
+                );
+            }
+            tab_row.into()
+        });
+
+        row(tabs)
+            .push(
+                button(text(tr!(self.language, "new-tab")).size(14))
+                    .on_press(Message::NewTab)
+                    .padding(self.layout.button_padding(8))
+                    .style(button::text),
+            )
+            .spacing(10)
+            .align_y(Alignment::Center)
+            .wrap()
             .into()
     }
 
+    /// Running session with its report button, or a field to start one
+    fn session_bar(&self) -> Element<'_, Message> {
+        let Some(session) = &self.active_session else {
+            let title = self.session_title_input.trim();
+            return row![
+                text_input(
+                    &tr!(self.language, "session-placeholder"),
+                    &self.session_title_input
+                )
+                .on_input(Message::SessionTitleChanged)
+                .on_submit(Message::StartSession)
+                .size(14)
+                .padding(self.layout.button_padding(6))
+                .style(self.layout.text_input_style())
+                .width(self.layout.fixed_width(360.0)),
+                button(text(tr!(self.language, "session-start")).size(14))
+                    .on_press_maybe((!title.is_empty()).then_some(Message::StartSession))
+                    .padding(self.layout.button_padding(6))
+                    .style(button::secondary),
+            ]
+            .spacing(10)
+            .align_y(Alignment::Center)
+            .into();
+        };
+
+        let count = history::session_entries(&self.history, &session.id).count();
+        let status = tr!(
+            self.language,
+            "session-status",
+            title = session.title.as_str(),
+            count = count
+        );
+        row![
+            text(status).size(14),
+            button(text(tr!(self.language, "print-report")).size(14))
+                .on_press_maybe(
+                    (count > 0).then(|| Message::PrintSessionReport(session.id.clone()))
+                )
+                .padding(self.layout.button_padding(6))
+                .style(button::secondary),
+            button(text("PDF").size(14))
+                .on_press_maybe((count > 0).then(|| Message::ExportSessionPdf(session.id.clone())))
+                .padding(self.layout.button_padding(6))
+                .style(button::secondary),
+            button(text(tr!(self.language, "session-end")).size(14))
+                .on_press(Message::EndSession)
+                .padding(self.layout.button_padding(6))
+                .style(button::text),
+        ]
+        .spacing(10)
+        .align_y(Alignment::Center)
+        .wrap()
+        .into()
+    }
+
+    /// Short label of a tab: the score being calculated, else the current view
+    fn tab_title(&self, tab: &Tab) -> String {
+        let score_name = |score_id: &str| {
+            self.score_library
+                .as_ref()
+                .and_then(|lib| lib.get_score(score_id))
+                .map_or_else(
+                    || score_id.to_string(),
+                    |score| score.name.text(self.language.code()).to_string(),
+                )
+        };
+        match &tab.state {
+            AppState::ScoreCalculation { score_id, .. } | AppState::ScoreInfo { score_id } => {
+                score_name(score_id)
+            }
+            AppState::ScoreSelection { specialty } => {
+                i18n::specialty_name(self.language, *specialty)
+            }
+            AppState::History => tr!(self.language, "history"),
+            AppState::About => tr!(self.language, "about"),
+            AppState::Settings => tr!(self.language, "settings"),
+            AppState::Error(_) => tr!(self.language, "error-title"),
+            AppState::Loading | AppState::Welcome | AppState::SpecialtySelection => {
+                tr!(self.language, "tab-new-calculation")
+            }
+        }
+    }
+
     fn loading_view(&self) -> Element<'_, Message> {
-        let message = match self.language {
-            Language::German => "Lade Score-Bibliothek...",
-            Language::English => "Loading score library...",
+        let message = match self.load_progress {
+            Some(p) if p.total > 0 => tr!(
+                self.language,
+                "loading-progress",
+                loaded = p.loaded,
+                total = p.total
+            ),
+            _ => tr!(self.language, "loading-library"),
         };
 
-        let content = column![text(message).size(24)]
+        let mut content = column![text(message).size(24)]
+            .spacing(20)
             .align_x(Alignment::Center)
-            .padding(50);
+            .padding(self.layout.padding(50));
+        if let Some(p) = self.load_progress.filter(|p| p.total > 0) {
+            content = content.push(
+                container(progress_bar(0.0..=p.total as f32, p.loaded as f32).height(8)).width(400),
+            );
+        }
 
         container(content)
             .width(Length::Fill)
@@ -487,46 +2708,31 @@ impl KlinScore {
     }
 
     fn welcome_view(&self) -> Element<'_, Message> {
-        let title = match self.language {
-            Language::German => "Willkommen bei KlinScore",
-            Language::English => "Welcome to KlinScore",
-        };
-
-        let subtitle = match self.language {
-            Language::German => "Quelloffener klinischer Score-Rechner für evidenzbasierte Medizin",
-            Language::English => {
-                "Open-source clinical score calculator for evidence-based medicine"
-            }
-        };
-
         let scores_loaded = match &self.score_library {
-            Some(library) => {
-                let msg = match self.language {
-                    Language::German => format!("{} Scores geladen", library.count()),
-                    Language::English => format!("{} scores loaded", library.count()),
-                };
-                text(msg).size(14)
-            }
+            Some(library) => text(tr!(
+                self.language,
+                "welcome-scores-loaded",
+                count = library.count()
+            ))
+            .size(14),
             None => text(""),
         };
 
-        let start_button_label = match self.language {
-            Language::German => "Score berechnen",
-            Language::English => "Calculate Score",
-        };
-
         let content = column![
-            text(title).size(40),
-            text(subtitle).size(16),
+            text(tr!(self.language, "welcome-title")).size(40),
+            text(tr!(self.language, "app-subtitle")).size(16),
             scores_loaded,
-            button(text(start_button_label).size(20))
-                .on_press(Message::BackToSpecialtySelection)
-                .padding(15),
         ]
+        .push_maybe(self.resume_offer_view())
+        .push(
+            button(text(tr!(self.language, "welcome-start")).size(20))
+                .on_press(Message::BackToSpecialtySelection)
+                .padding(self.layout.button_padding(15)),
+        )
         .spacing(30)
         .align_x(Alignment::Center)
         .width(Length::Fill)
-        .padding(50);
+        .padding(self.layout.padding(50));
 
         container(content)
             .width(Length::Fill)
@@ -534,67 +2740,105 @@ impl KlinScore {
             .into()
     }
 
+    /// Offer to continue the calculation that was open when KlinScore was closed
+    fn resume_offer_view(&self) -> Option<Element<'_, Message>> {
+        let screen = self.resume_offer.as_ref()?;
+        let library = self.score_library.as_ref()?;
+        let specialty = i18n::specialty_name(self.language, screen.specialty);
+        let description = match screen
+            .score_id
+            .as_deref()
+            .and_then(|id| library.get_score(id))
+        {
+            Some(score) => tr!(
+                self.language,
+                "resume-score",
+                score = score.name.text(self.language.code()),
+                count = screen.inputs.len()
+            ),
+            None => tr!(self.language, "resume-specialty", specialty = specialty),
+        };
+
+        Some(
+            container(
+                column![
+                    text(description).size(16),
+                    row![
+                        button(text(tr!(self.language, "resume-continue")).size(14))
+                            .on_press(Message::ResumeLastScreen)
+                            .padding(self.layout.button_padding(8)),
+                        button(text(tr!(self.language, "resume-discard")).size(14))
+                            .on_press(Message::DiscardLastScreen)
+                            .padding(self.layout.button_padding(8))
+                            .style(button::secondary),
+                    ]
+                    .spacing(10),
+                ]
+                .spacing(10)
+                .align_x(Alignment::Center),
+            )
+            .padding(15)
+            .style(container::rounded_box)
+            .into(),
+        )
+    }
+
     fn specialty_view(&self) -> Element<'_, Message> {
+        let specialties = self
+            .score_library
+            .as_ref()
+            .map(|lib| lib.specialty_counts())
+            .unwrap_or_default();
         ui::specialty_selection_view(
             self.language,
+            self.layout,
+            &specialties,
             Message::SpecialtySelected,
             Message::BackToWelcome,
         )
-    }
-
-    fn score_selection_view(&self, specialty: Specialty) -> Element<'_, Message> {
-        let title = match self.language {
-            Language::German => format!("{} - Score auswählen", specialty.german()),
-            Language::English => format!("{} - Select Score", specialty.english()),
-        };
-
-        let subtitle = match self.language {
-            Language::German => "Wählen Sie einen Score zur Berechnung",
-            Language::English => "Choose a score to calculate",
-        };
+    }
 
-        let back_label = match self.language {
-            Language::German => "← Zurück zu Fachgebieten",
-            Language::English => "← Back to Specialties",
-        };
+    fn score_selection_view(&self, specialty: Specialty) -> Element<'_, Message> {
+        let title = tr!(
+            self.language,
+            "score-selection-title",
+            specialty = i18n::specialty_name(self.language, specialty)
+        );
 
         // Get scores for this specialty
         let score_buttons: Element<Message> = if let Some(library) = &self.score_library {
-            let scores = library.get_scores_for_specialty(specialty);
+            let scores = library.specialty_scores(specialty);
 
             if scores.is_empty() {
-                let msg = match self.language {
-                    Language::German => "Keine Scores für dieses Fachgebiet verfügbar",
-                    Language::English => "No scores available for this specialty",
-                };
-                column![text(msg).size(18)]
+                column![text(tr!(self.language, "score-selection-empty")).size(18)]
                     .align_x(Alignment::Center)
                     .into()
             } else {
                 let score_buttons_vec: Vec<Element<Message>> = scores
                     .into_iter()
-                    .map(|score| {
-                        let label = match self.language {
-                            Language::German => &score.name_de,
-                            Language::English => &score.name,
-                        };
-
-                        // Get score ID from library
-                        let score_id = library
-                            .scores
-                            .iter()
-                            .find(|(_, s)| s.name == score.name)
-                            .map(|(id, _)| id.clone())
-                            .unwrap_or_default();
+                    .map(|(score_id, score)| {
+                        let label = score.name.text(self.language.code());
 
-                        button(
+                        let mut details =
                             column![text(label).size(20), text(&score.guideline_source).size(14)]
-                                .spacing(5),
-                        )
-                        .on_press(Message::ScoreSelected(score_id))
-                        .padding(15)
-                        .width(Length::Fixed(400.0))
-                        .into()
+                                .spacing(5);
+                        if score.is_deprecated() {
+                            details = details.push(
+                                text(tr!(self.language, "score-deprecated"))
+                                    .size(12)
+                                    .color(iced::Color::from_rgb(0.8, 0.45, 0.0)),
+                            );
+                        }
+                        if let Some(scores::ScoreOrigin::Directory(dir)) = library.origin(score_id)
+                        {
+                            details = details.push(text(format!("📁 {}", dir.display())).size(12));
+                        }
+
+                        button(details)
+                            .on_press(Message::ScoreSelected(score_id.to_string()))
+                            .padding(self.layout.button_padding(15))
+                            .width(self.layout.fixed_width(400.0))
+                            .into()
                     })
                     .collect();
 
@@ -604,20 +2848,20 @@ impl KlinScore {
                     .into()
             }
         } else {
-            text("Loading...").into()
+            text(tr!(self.language, "loading")).into()
         };
 
         let content = column![
             text(title).size(32),
-            text(subtitle).size(16),
+            text(tr!(self.language, "score-selection-subtitle")).size(16),
             score_buttons,
-            button(text(back_label).size(18))
+            button(text(tr!(self.language, "score-selection-back")).size(18))
                 .on_press(Message::BackToSpecialtySelection)
-                .padding(10),
+                .padding(self.layout.button_padding(10)),
         ]
         .spacing(25)
         .align_x(Alignment::Center)
-        .padding(40);
+        .padding(self.layout.padding(40));
 
         container(content)
             .width(Length::Fill)
@@ -638,39 +2882,56 @@ impl KlinScore {
             .as_ref()
             .and_then(|lib| lib.get_score(score_id));
 
-        let back_label = match self.language {
-            Language::German => "← Zurück zur Score-Auswahl",
-            Language::English => "← Back to Score Selection",
-        };
-
         if let Some(score_def) = score {
             // Show result if available, otherwise show input form
             if let Some(calc_result) = result {
                 ui::result_display_view(
                     calc_result,
                     self.language,
-                    Message::Input(InputMessage::Reset),
-                    Message::BackToScoreSelection,
-                    Message::ExportCsv,
-                    Message::ExportJson,
-                    Message::ExportPdf,
+                    self.layout,
+                    self.settings.risk_palette(),
+                    self.show_zero_point_fields,
+                    self.export_templates
+                        .iter()
+                        .map(ExportTemplate::display_name)
+                        .collect(),
+                    ui::ResultActions {
+                        recalculate: Message::Input(InputMessage::Reset),
+                        back: Message::BackToScoreSelection,
+                        export_csv: Message::ExportCsv,
+                        export_json: Message::ExportJson,
+                        export_pdf: Message::ExportPdf,
+                        copy_summary: Message::CopySummary,
+                        export_markdown: Message::ExportMarkdown,
+                        copy_markdown: Message::CopyMarkdown,
+                        export_fhir: Message::ExportFhir,
+                        export_template: Message::ExportWithTemplate,
+                        export_patient_pdf: Message::ExportPatientPdf,
+                        print: Message::Print,
+                        toggle_zero_points: Message::ZeroPointFieldsToggled,
+                    },
                 )
             } else {
-                let form = ui::score_input_form(score_def, input_state, self.language, |msg| {
-                    Message::Input(msg)
-                });
-
-                let error_label = match self.language {
-                    Language::German => "Fehler: ",
-                    Language::English => "Error: ",
-                };
+                let form = ui::score_input_form(
+                    score_def,
+                    input_state,
+                    self.language,
+                    self.layout,
+                    self.settings.risk_palette(),
+                    self.settings.show_help_hints,
+                    Message::Input,
+                );
 
-                let mut content = vec![form];
+                let mut content = Vec::new();
+                if score_def.is_deprecated() {
+                    content.push(self.deprecation_banner(score_id, score_def));
+                }
+                content.push(form);
 
                 // Display error if present
                 if let Some(err) = error {
                     let error_box = container(
-                        text(format!("{}{}", error_label, err))
+                        text(tr!(self.language, "calculation-error", message = err))
                             .size(16)
                             .color(iced::Color::from_rgb(0.8, 0.1, 0.1)),
                     )
@@ -690,10 +2951,16 @@ impl KlinScore {
                 }
 
                 content.push(
-                    button(text(back_label).size(18))
-                        .on_press(Message::BackToScoreSelection)
-                        .padding(10)
-                        .into(),
+                    row![
+                        button(text(tr!(self.language, "calculation-back")).size(18))
+                            .on_press(Message::BackToScoreSelection)
+                            .padding(self.layout.button_padding(10)),
+                        button(text(tr!(self.language, "score-info")).size(18))
+                            .on_press(Message::OpenScoreInfo(score_id.to_string()))
+                            .padding(self.layout.button_padding(10)),
+                    ]
+                    .spacing(15)
+                    .into(),
                 );
 
                 column(content)
@@ -702,89 +2969,480 @@ impl KlinScore {
                     .into()
             }
         } else {
-            text("Score not found").into()
+            text(tr!(self.language, "score-not-found")).into()
         }
     }
 
-    fn history_view(&self) -> Element<'_, Message> {
-        let title = match self.language {
-            Language::German => "Berechnungsverlauf",
-            Language::English => "Calculation History",
+    /// Banner for deprecated scores, with a jump to the replacement if one is loaded
+    fn deprecation_banner<'a>(
+        &self,
+        score_id: &str,
+        score_def: &config::ScoreDefinition,
+    ) -> Element<'a, Message> {
+        let name = score_def.name.text(self.language.code());
+        let replacement = self
+            .score_library
+            .as_ref()
+            .and_then(|lib| lib.replacement_for(score_id));
+
+        let mut banner = row![].spacing(15).align_y(Alignment::Center);
+        match replacement {
+            Some((replacement_id, replacement_def)) => {
+                let replacement_name = replacement_def.name.text(self.language.code());
+                let message = tr!(
+                    self.language,
+                    "deprecated-superseded",
+                    name = name,
+                    replacement = replacement_name
+                );
+                let jump_label = tr!(
+                    self.language,
+                    "deprecated-switch",
+                    replacement = replacement_name
+                );
+                banner = banner
+                    .push(text(message).size(15).width(Length::Fill))
+                    .push(
+                        button(text(jump_label).size(14))
+                            .on_press(Message::JumpToScore(replacement_id.to_string()))
+                            .padding(self.layout.button_padding(8)),
+                    );
+            }
+            None => {
+                let message = tr!(self.language, "deprecated-no-replacement", name = name);
+                banner = banner.push(text(message).size(15).width(Length::Fill));
+            }
+        }
+
+        container(banner)
+            .padding(12)
+            .width(Length::Fill)
+            .max_width(700)
+            .style(|_theme: &iced::Theme| container::Style {
+                background: Some(iced::Background::Color(iced::Color::from_rgb(
+                    1.0, 0.95, 0.85,
+                ))),
+                text_color: Some(iced::Color::from_rgb(0.45, 0.25, 0.0)),
+                border: iced::Border {
+                    color: iced::Color::from_rgb(0.9, 0.6, 0.1),
+                    width: 2.0,
+                    radius: 5.0.into(),
+                },
+                ..Default::default()
+            })
+            .into()
+    }
+
+    /// Specialty, score and date range pickers above the history list
+    fn history_filter_bar(&self) -> Element<'_, Message> {
+        let mut specialties: Vec<Specialty> = Vec::new();
+        let mut scores: BTreeMap<String, String> = BTreeMap::new();
+        for entry in &self.history {
+            if !specialties.contains(&entry.specialty) {
+                specialties.push(entry.specialty);
+            }
+            scores.insert(
+                entry.score_id.clone(),
+                entry.score_name.text(self.language.code()).to_string(),
+            );
+        }
+
+        let specialty_choices: Vec<FilterChoice<Specialty>> = std::iter::once(FilterChoice {
+            value: None,
+            label: tr!(self.language, "history-all-specialties"),
+        })
+        .chain(specialties.into_iter().map(|specialty| FilterChoice {
+            value: Some(specialty),
+            label: i18n::specialty_name(self.language, specialty),
+        }))
+        .collect();
+        let selected_specialty = specialty_choices
+            .iter()
+            .find(|choice| choice.value == self.history_filter.specialty)
+            .cloned();
+
+        let mut score_choices: Vec<FilterChoice<String>> = scores
+            .into_iter()
+            .map(|(score_id, name)| FilterChoice {
+                value: Some(score_id),
+                label: name,
+            })
+            .collect();
+        score_choices.sort_by(|a, b| a.label.cmp(&b.label));
+        score_choices.insert(
+            0,
+            FilterChoice {
+                value: None,
+                label: tr!(self.language, "history-all-scores"),
+            },
+        );
+        let selected_score = score_choices
+            .iter()
+            .find(|choice| choice.value == self.history_filter.score_id)
+            .cloned();
+
+        let date_placeholder = tr!(self.language, "history-date-placeholder");
+        let date_input = |value: &str, on_input: fn(String) -> Message| {
+            text_input(&date_placeholder, value)
+                .on_input(on_input)
+                .padding(self.layout.button_padding(6))
+                .width(Length::Fixed(120.0))
+                .style(self.layout.text_input_style())
         };
 
-        let back_label = match self.language {
-            Language::German => "← Zurück",
-            Language::English => "← Back",
+        let mut bar = row![
+            text_input(
+                &tr!(self.language, "history-search-placeholder"),
+                &self.history_filter.query
+            )
+            .on_input(Message::HistorySearchChanged)
+            .padding(self.layout.button_padding(6))
+            .width(self.layout.fixed_width(220.0))
+            .style(self.layout.text_input_style()),
+            pick_list(specialty_choices, selected_specialty, |choice| {
+                Message::HistorySpecialtyFilterChanged(choice.value)
+            })
+            .padding(self.layout.button_padding(6)),
+            pick_list(score_choices, selected_score, |choice| {
+                Message::HistoryScoreFilterChanged(choice.value)
+            })
+            .padding(self.layout.button_padding(6)),
+            text(tr!(self.language, "history-from")).size(14),
+            date_input(&self.history_from_input, Message::HistoryFromChanged),
+            text(tr!(self.language, "history-to")).size(14),
+            date_input(&self.history_to_input, Message::HistoryToChanged),
+        ]
+        .spacing(10)
+        .align_y(Alignment::Center);
+
+        if self.history_filter.is_active()
+            || !self.history_from_input.is_empty()
+            || !self.history_to_input.is_empty()
+        {
+            bar = bar.push(
+                button(text(tr!(self.language, "history-clear-filter")).size(14))
+                    .on_press(Message::HistoryFilterCleared)
+                    .padding(self.layout.button_padding(6))
+                    .style(button::text),
+            );
+        }
+
+        let invalid_date = [&self.history_from_input, &self.history_to_input]
+            .into_iter()
+            .any(|input| !input.trim().is_empty() && history::parse_date(input).is_none());
+
+        column![bar.wrap()]
+            .push_maybe(invalid_date.then(|| {
+                text(tr!(self.language, "history-invalid-date"))
+                    .size(13)
+                    .color(iced::Color::from_rgb(0.8, 0.1, 0.1))
+            }))
+            .spacing(5)
+            .into()
+    }
+
+    /// Name of the last exported file with buttons to open it or show it in its folder
+    fn export_bar(&self) -> Element<'_, Message> {
+        let Some(path) = &self.last_export else {
+            return column![].into();
         };
+        let name = path.file_name().map_or_else(
+            || path.display().to_string(),
+            |name| name.to_string_lossy().into(),
+        );
+        container(
+            row![
+                text(tr!(self.language, "export-done", file = name))
+                    .size(14)
+                    .width(Length::Fill),
+                button(text(tr!(self.language, "export-open")).size(14))
+                    .on_press(Message::OpenExport)
+                    .padding(self.layout.button_padding(6))
+                    .style(button::secondary),
+                button(text(tr!(self.language, "export-reveal")).size(14))
+                    .on_press(Message::RevealExport)
+                    .padding(self.layout.button_padding(6))
+                    .style(button::secondary),
+                button(text("✕").size(14))
+                    .on_press(Message::DismissExport)
+                    .padding(4)
+                    .style(button::text),
+            ]
+            .spacing(10)
+            .align_y(Alignment::Center),
+        )
+        .padding(10)
+        .width(Length::Fill)
+        .style(container::rounded_box)
+        .into()
+    }
+
+    /// Title, size and report button above the calculations of a session
+    fn history_session_header<'a>(&self, session: &'a Session) -> Element<'a, Message> {
+        let count = history::session_entries(&self.history, &session.id).count();
+        row![
+            text(format!("📋 {}", session.title))
+                .size(16)
+                .width(Length::Fill),
+            text(tr!(self.language, "calculations-count", count = count)).size(13),
+            button(text(tr!(self.language, "print-report")).size(13))
+                .on_press(Message::PrintSessionReport(session.id.clone()))
+                .padding(self.layout.button_padding(6))
+                .style(button::secondary),
+            button(text("PDF").size(13))
+                .on_press(Message::ExportSessionPdf(session.id.clone()))
+                .padding(self.layout.button_padding(6))
+                .style(button::secondary),
+            button(text("CSV").size(13))
+                .on_press(Message::ExportSessionCsv(session.id.clone()))
+                .padding(self.layout.button_padding(6))
+                .style(button::secondary),
+            button(text("JSON").size(13))
+                .on_press(Message::ExportSessionJson(session.id.clone()))
+                .padding(self.layout.button_padding(6))
+                .style(button::secondary),
+        ]
+        .spacing(10)
+        .align_y(Alignment::Center)
+        .into()
+    }
+
+    /// Interpretation and points breakdown of an expanded history entry
+    fn history_entry_details<'a>(&self, entry: &'a HistoryEntry) -> Element<'a, Message> {
+        let code = self.language.code();
+        let recommendation = entry.recommendation.text(code);
+        let details = entry.details.text(code);
+
+        let mut content = column![].spacing(6).padding(iced::Padding::ZERO.top(6));
+        if !recommendation.is_empty() {
+            content = content.push(
+                column![
+                    text(tr!(self.language, "recommendation")).size(13),
+                    text(recommendation).size(13)
+                ]
+                .spacing(2),
+            );
+        }
+        if !details.is_empty() {
+            content = content.push(text(details).size(12));
+        }
+
+        let rows: Vec<Element<'a, Message>> = entry
+            .field_scores
+            .iter()
+            .filter(|fs| fs.points != 0)
+            .map(|fs| {
+                let label = fs.label.text(code);
+                let criterion = match &fs.selection {
+                    Some(selection) => format!("{} ({})", label, selection.text(code)),
+                    None => label.to_string(),
+                };
+                row![
+                    text(criterion).size(13).width(Length::Fill),
+                    text(scores::format_signed_points(fs.points)).size(13),
+                ]
+                .spacing(10)
+                .into()
+            })
+            .collect();
+        if !entry.field_scores.is_empty() {
+            content = content.push(text(tr!(self.language, "points-breakdown")).size(13));
+            if rows.is_empty() {
+                content = content.push(text(tr!(self.language, "no-points")).size(12));
+            } else {
+                content = content.push(column(rows).spacing(2));
+            }
+        }
+        content.into()
+    }
+
+    fn history_view(&self) -> Element<'_, Message> {
+        let mut content_widgets: Vec<Element<'_, Message>> =
+            vec![text(tr!(self.language, "history-title")).size(32).into()];
+
+        if let HistoryStorage::Locked = self.history_storage {
+            content_widgets.push(self.history_locked_notice());
+        }
 
-        let mut content_widgets: Vec<Element<'_, Message>> = vec![text(title).size(32).into()];
+        if let Some(cleared) = &self.cleared_history {
+            content_widgets.push(
+                row![
+                    text(tr!(self.language, "history-cleared", count = cleared.len())).size(14),
+                    button(text(tr!(self.language, "undo")).size(14))
+                        .on_press(Message::UndoClearHistory)
+                        .padding(self.layout.button_padding(8))
+                        .style(button::secondary),
+                ]
+                .spacing(10)
+                .align_y(Alignment::Center)
+                .into(),
+            );
+        }
 
         if self.history.is_empty() {
-            let empty_msg = match self.language {
-                Language::German => "Noch keine Berechnungen durchgeführt.",
-                Language::English => "No calculations yet.",
-            };
-            content_widgets.push(text(empty_msg).size(16).into());
+            content_widgets.push(text(tr!(self.language, "history-empty")).size(16).into());
+            if let Some(status) = &self.history_status {
+                content_widgets.push(text(status).size(13).into());
+            }
         } else {
-            let clear_label = match self.language {
-                Language::German => "Verlauf löschen",
-                Language::English => "Clear History",
+            let matching = history::filter_history(&self.history, &self.history_filter).count();
+            let mut count_text = if self.history_filter.is_active() {
+                tr!(
+                    self.language,
+                    "history-count-filtered",
+                    matching = matching,
+                    total = self.history.len()
+                )
+            } else {
+                tr!(self.language, "calculations-count", count = matching)
             };
+            if matching > self.history_visible {
+                count_text.push_str(&tr!(
+                    self.language,
+                    "history-newest-shown",
+                    visible = self.history_visible
+                ));
+            }
 
             content_widgets.push(
                 row![
-                    text(format!(
-                        "{} {}",
-                        self.history.len(),
-                        match self.language {
-                            Language::German => "Berechnungen",
-                            Language::English => "calculations",
-                        }
-                    ))
-                    .size(14)
-                    .width(Length::Fill),
-                    button(text(clear_label).size(14))
+                    text(count_text).size(14).width(Length::Fill),
+                    button(text(tr!(self.language, "history-research-export")).size(14))
+                        .on_press(Message::ExportHistoryDeidentified)
+                        .padding(self.layout.button_padding(8)),
+                    button(text(tr!(self.language, "history-clear")).size(14))
                         .on_press(Message::ClearHistory)
-                        .padding(8),
+                        .padding(self.layout.button_padding(8)),
                 ]
+                .spacing(10)
                 .align_y(Alignment::Center)
                 .into(),
             );
 
+            if let Some(status) = &self.history_status {
+                content_widgets.push(text(status).size(13).into());
+            }
+
+            content_widgets.push(self.history_filter_bar());
+
+            // Export of the whole list, or of the entries matching the filter
+            let export_text = if self.history_filter.is_active() {
+                tr!(self.language, "history-export-filtered", count = matching)
+            } else {
+                tr!(self.language, "history-export-all", count = matching)
+            };
+            content_widgets.push(
+                row![
+                    text(export_text).size(14),
+                    button(text("CSV").size(14))
+                        .on_press_maybe((matching > 0).then_some(Message::ExportHistoryCsv))
+                        .padding(self.layout.button_padding(8))
+                        .style(button::secondary),
+                    button(text("JSON").size(14))
+                        .on_press_maybe((matching > 0).then_some(Message::ExportHistoryJson))
+                        .padding(self.layout.button_padding(8))
+                        .style(button::secondary),
+                    button(text("PDF").size(14))
+                        .on_press_maybe((matching > 0).then_some(Message::ExportHistoryPdf))
+                        .padding(self.layout.button_padding(8))
+                        .style(button::secondary),
+                ]
+                .spacing(10)
+                .align_y(Alignment::Center)
+                .into(),
+            );
             content_widgets.push(horizontal_rule(1).into());
 
+            if matching == 0 {
+                content_widgets.push(text(tr!(self.language, "history-no-match")).size(14).into());
+            }
+
+            let reopen_label = tr!(self.language, "history-reopen");
+            let show_details_label = tr!(self.language, "details-show");
+            let hide_details_label = tr!(self.language, "details-hide");
+
             // Show history entries in reverse chronological order
-            for entry in self.history.iter().rev() {
-                let score_name = match self.language {
-                    Language::German => &entry.score_name_de,
-                    Language::English => &entry.score_name,
-                };
+            // Only a page at a time, so long histories stay responsive
+            // Values of repeated formula scores per patient, shown as a chart
+            // in the newest entry of each series
+            let trends = history::formula_trends(&self.history, |score_id| {
+                self.score_library
+                    .as_ref()
+                    .and_then(|lib| lib.get_score(score_id))
+                    .is_some_and(|score| score.formula.is_some())
+            });
 
-                let risk_text = match self.language {
-                    Language::German => &entry.risk_de,
-                    Language::English => &entry.risk,
-                };
+            let mut previous_session = None;
+            for (index, entry) in history::filter_history(&self.history, &self.history_filter)
+                .take(self.history_visible)
+            {
+                // Calculations of a session are grouped under a header
+                if let Some(session) = &entry.session {
+                    if previous_session != Some(session.id.as_str()) {
+                        content_widgets.push(self.history_session_header(session));
+                    }
+                }
+                previous_session = entry.session_id();
 
-                let specialty_text = match self.language {
-                    Language::German => entry.specialty.german(),
-                    Language::English => entry.specialty.english(),
-                };
+                let score_name = entry.score_name.text(self.language.code());
+                let risk_text = entry.risk.text(self.language.code());
+
+                let specialty_text = i18n::specialty_name(self.language, entry.specialty);
+
+                let has_details =
+                    !entry.recommendation.is_blank() || !entry.field_scores.is_empty();
+                let expanded = has_details && self.expanded_history.contains(&index);
+
+                let version_warning = entry
+                    .version_warning(self.score_library.as_ref(), self.language)
+                    .map(|warning| {
+                        text(format!("⚠ {}", warning))
+                            .size(12)
+                            .color(iced::Color::from_rgb(0.8, 0.45, 0.0))
+                    });
+
+                let trend = trends.get(&index).map(|points| {
+                    let title = tr!(self.language, "history-trend", count = points.len());
+                    column![text(title).size(12), ui::trend_chart(points.clone())].spacing(4)
+                });
 
                 let entry_widget = container(
                     column![
+                        row![text(score_name).size(18).width(Length::Fill),]
+                            .push_maybe(
+                                entry.patient_label.as_ref().map(|label| text(format!(
+                                    "👤 {}",
+                                    label
+                                ))
+                                .size(13)),
+                            )
+                            .push(text(&entry.timestamp).size(12))
+                            .push_maybe(has_details.then(|| {
+                                button(
+                                    text(if expanded {
+                                        hide_details_label.clone()
+                                    } else {
+                                        show_details_label.clone()
+                                    })
+                                    .size(13),
+                                )
+                                .on_press(Message::HistoryEntryToggled(index))
+                                .padding(self.layout.button_padding(6))
+                                .style(button::text)
+                            }))
+                            .push_maybe((!entry.inputs.is_empty()).then(|| {
+                                button(text(reopen_label.clone()).size(13))
+                                    .on_press(Message::ReopenHistoryEntry(index))
+                                    .padding(self.layout.button_padding(6))
+                                    .style(button::secondary)
+                            }))
+                            .spacing(10)
+                            .align_y(Alignment::Center),
                         row![
-                            text(score_name).size(18).width(Length::Fill),
-                            text(&entry.timestamp).size(12),
-                        ]
-                        .align_y(Alignment::Center),
-                        row![
-                            text(format!(
-                                "{}: {}",
-                                match self.language {
-                                    Language::German => "Score",
-                                    Language::English => "Score",
-                                },
-                                entry.total_score
+                            text(tr!(
+                                self.language,
+                                "history-score",
+                                score = entry.total_score.to_string()
                             ))
                             .size(14),
                             text(" | ").size(14),
@@ -794,6 +3452,9 @@ impl KlinScore {
                         ]
                         .spacing(5),
                     ]
+                    .push_maybe(version_warning)
+                    .push_maybe(trend)
+                    .push_maybe(expanded.then(|| self.history_entry_details(entry)))
                     .spacing(5),
                 )
                 .padding(12)
@@ -817,21 +3478,74 @@ impl KlinScore {
                     }
                 });
 
-                content_widgets.push(entry_widget.into());
+                content_widgets.push(if entry.session.is_some() {
+                    container(entry_widget)
+                        .padding(iced::Padding::ZERO.left(24))
+                        .into()
+                } else {
+                    entry_widget.into()
+                });
+            }
+
+            if matching > self.history_visible {
+                let remaining = matching - self.history_visible;
+                let more_label = tr!(
+                    self.language,
+                    "history-show-more",
+                    count = remaining.min(history::PAGE_SIZE),
+                    remaining = remaining
+                );
+                content_widgets.push(
+                    button(text(more_label).size(14))
+                        .on_press(Message::HistoryShowMore)
+                        .padding(self.layout.button_padding(8))
+                        .style(button::secondary)
+                        .into(),
+                );
             }
         }
 
+        // Moving the records to or from another workstation
+        content_widgets.push(horizontal_rule(1).into());
         content_widgets.push(
-            button(text(back_label).size(18))
+            column![
+                text(tr!(self.language, "history-transfer")).size(14),
+                button(text(tr!(self.language, "history-transfer-save")).size(14))
+                    .on_press_maybe(
+                        (!self.history.is_empty()).then_some(Message::ExportHistoryTransfer)
+                    )
+                    .padding(self.layout.button_padding(8))
+                    .style(button::secondary),
+                row![
+                    text_input(
+                        &tr!(self.language, "history-import-placeholder"),
+                        &self.history_import_input
+                    )
+                    .on_input(Message::HistoryImportPathChanged)
+                    .on_submit(Message::ImportHistory)
+                    .padding(self.layout.button_padding(8))
+                    .style(self.layout.text_input_style()),
+                    button(text(tr!(self.language, "import")).size(14))
+                        .on_press(Message::ImportHistory)
+                        .padding(self.layout.button_padding(8)),
+                ]
+                .spacing(10),
+            ]
+            .spacing(8)
+            .into(),
+        );
+
+        content_widgets.push(
+            button(text(tr!(self.language, "back")).size(18))
                 .on_press(Message::CloseHistory)
-                .padding(10)
+                .padding(self.layout.button_padding(10))
                 .into(),
         );
 
         let content = column(content_widgets)
             .spacing(15)
             .align_x(Alignment::Center)
-            .padding(40)
+            .padding(self.layout.padding(40))
             .max_width(700);
 
         container(content)
@@ -840,77 +3554,226 @@ impl KlinScore {
             .into()
     }
 
-    fn about_view(&self) -> Element<'_, Message> {
-        let is_de = self.language == Language::German;
+    /// Clinical context of a score: question, population, time horizon, contraindications, tags
+    fn score_info_view(&self, score_id: &str) -> Element<'_, Message> {
+        let back_label = tr!(self.language, "back");
+
+        let Some(score_def) = self
+            .score_library
+            .as_ref()
+            .and_then(|lib| lib.get_score(score_id))
+        else {
+            return column![
+                text(tr!(self.language, "score-not-found")),
+                button(text(back_label).size(18))
+                    .on_press(Message::CloseScoreInfo)
+                    .padding(self.layout.button_padding(10)),
+            ]
+            .spacing(20)
+            .padding(self.layout.padding(40))
+            .into();
+        };
+        let metadata = &score_def.metadata;
+        let language = self.language.code();
+        let localized = |value: &Option<config::LocalizedText>| {
+            value.as_ref().map(|value| value.text(language).to_string())
+        };
+
+        // Label from the catalog above its value
+        let field = |id: &str, value: String| {
+            column![text(tr!(self.language, id)).size(14), text(value).size(16)].spacing(4)
+        };
+
+        let mut content = column![
+            text(score_def.name.text(language)).size(32),
+            text(tr!(
+                self.language,
+                "info-version",
+                specialty = i18n::specialty_name(self.language, score_def.specialty),
+                version = score_def.version.as_str()
+            ))
+            .size(14),
+            horizontal_rule(1),
+        ]
+        .spacing(15)
+        .padding(self.layout.padding(40))
+        .max_width(800);
 
-        let back_label = if is_de { "← Zurück" } else { "← Back" };
+        // Intended use and regulatory status come first: they decide whether
+        // the score may be used in a given setting at all
+        let mut regulatory = column![].spacing(10);
+        if let Some(intended_use) = localized(&score_def.intended_use) {
+            regulatory = regulatory.push(field("info-intended-use", intended_use));
+        }
+        if let Some(note) = localized(&score_def.regulatory_note) {
+            regulatory = regulatory.push(field("info-regulatory-note", note));
+        }
+        if !score_def.regions_validated.is_empty() {
+            regulatory = regulatory.push(field(
+                "info-validated-in",
+                score_def.regions_validated.join(", "),
+            ));
+        }
+        if score_def.intended_use.is_some()
+            || score_def.regulatory_note.is_some()
+            || !score_def.regions_validated.is_empty()
+        {
+            content = content.push(container(regulatory).padding(12).width(Length::Fill).style(
+                |theme: &iced::Theme| {
+                    let accent = theme.palette().primary;
+                    container::Style {
+                        background: Some(iced::Background::Color(iced::Color {
+                            a: 0.08,
+                            ..accent
+                        })),
+                        border: iced::Border {
+                            color: iced::Color { a: 0.3, ..accent },
+                            width: 1.0,
+                            radius: 6.0.into(),
+                        },
+                        ..Default::default()
+                    }
+                },
+            ));
+        }
+
+        if let Some(question) = localized(&metadata.clinical_question) {
+            content = content.push(field("info-clinical-question", question));
+        }
+        if let Some(population) = localized(&metadata.target_population) {
+            content = content.push(field("info-target-population", population));
+        }
+        if let Some(horizon) = localized(&metadata.time_horizon) {
+            content = content.push(field("info-time-horizon", horizon));
+        }
+
+        let contraindications = metadata
+            .contraindications
+            .get(language)
+            .cloned()
+            .unwrap_or_default();
+        if !contraindications.is_empty() {
+            let list = contraindications
+                .iter()
+                .map(|item| format!("• {}", item))
+                .collect::<Vec<_>>()
+                .join("\n");
+            content = content.push(
+                container(field("info-contraindications", list))
+                    .padding(12)
+                    .width(Length::Fill)
+                    .style(|theme: &iced::Theme| {
+                        let accent = theme.palette().danger;
+                        container::Style {
+                            background: Some(iced::Background::Color(iced::Color {
+                                a: 0.08,
+                                ..accent
+                            })),
+                            border: iced::Border {
+                                color: iced::Color { a: 0.3, ..accent },
+                                width: 1.0,
+                                radius: 6.0.into(),
+                            },
+                            ..Default::default()
+                        }
+                    }),
+            );
+        }
+
+        if let Some(category) = metadata.category {
+            content = content.push(field(
+                "info-category",
+                i18n::category_name(self.language, category),
+            ));
+        }
+
+        let tags = metadata.tags.get(language).cloned().unwrap_or_default();
+        if !tags.is_empty() {
+            content = content.push(field("info-tags", tags.join(", ")));
+        }
+
+        let mut source = score_def.guideline_source.clone();
+        if let Some(year) = &metadata.guideline_year {
+            source = format!("{} ({})", source, year);
+        }
+        content = content.push(field("info-source", source));
+        if !score_def.references.is_empty() {
+            content = content.push(
+                column![
+                    text(tr!(self.language, "info-references")).size(14),
+                    reference_list(&score_def.references, 14),
+                ]
+                .spacing(4),
+            );
+        }
+        if let Some(doi) = &metadata.doi {
+            content = content.push(
+                button(text(format!("DOI: {}", doi)).size(14))
+                    .on_press(Message::OpenUrl(format!("https://doi.org/{}", doi)))
+                    .padding(0)
+                    .style(button::text),
+            );
+        }
+
+        content = content.push(horizontal_rule(1)).push(
+            button(text(back_label).size(18))
+                .on_press(Message::CloseScoreInfo)
+                .padding(self.layout.button_padding(10)),
+        );
 
+        container(content)
+            .width(Length::Fill)
+            .center_x(Length::Fill)
+            .into()
+    }
+
+    fn about_view(&self) -> Element<'_, Message> {
         let scores_count = self
             .score_library
             .as_ref()
             .map(|lib| lib.count())
             .unwrap_or(0);
-
-        let title = if is_de {
-            "Über KlinScore"
-        } else {
-            "About KlinScore"
-        };
-        let subtitle = if is_de {
-            "Quelloffener klinischer Score-Rechner für evidenzbasierte Medizin"
-        } else {
-            "Open-source clinical score calculator for evidence-based medicine"
-        };
+        let specialty_names = self
+            .score_library
+            .as_ref()
+            .map(|lib| lib.get_specialties())
+            .unwrap_or_default()
+            .into_iter()
+            .map(|specialty| i18n::specialty_name(self.language, specialty))
+            .collect::<Vec<_>>()
+            .join(", ");
 
         // -- Version / stats --
-        let version_label = "Version";
-        let scores_label = if is_de {
-            "Scores geladen"
-        } else {
-            "Scores loaded"
-        };
         let stats_section = column![
-            text(format!("{}: 0.1.0", version_label)).size(14),
-            text(format!("{}: {}", scores_label, scores_count)).size(14),
-            text(if is_de {
-                "Fachgebiete: Kardiologie, Nephrologie, Anästhesiologie"
-            } else {
-                "Specialties: Cardiology, Nephrology, Anesthesiology"
-            })
+            text(tr!(
+                self.language,
+                "about-version",
+                version = env!("CARGO_PKG_VERSION")
+            ))
             .size(14),
-            text(if is_de {
-                "Leitlinien: ESC 2024, KDIGO, ACCP, ASA"
-            } else {
-                "Guidelines: ESC 2024, KDIGO, ACCP, ASA"
-            })
+            text(tr!(
+                self.language,
+                "about-scores-loaded",
+                count = scores_count
+            ))
             .size(14),
-            text("Lizenz / License: MIT / Apache 2.0").size(14),
+            text(tr!(
+                self.language,
+                "about-specialties",
+                specialties = specialty_names
+            ))
+            .size(14),
+            text(tr!(self.language, "about-guidelines")).size(14),
+            text(tr!(self.language, "about-license")).size(14),
         ]
         .spacing(5)
         .padding(10);
 
         // -- Disclaimer --
-        let disclaimer_title = if is_de {
-            "Haftungsausschluss"
-        } else {
-            "Disclaimer"
-        };
-        let disclaimer_text = if is_de {
-            "KlinScore ist ein Hilfsmittel zur klinischen Entscheidungsunterstützung. \
-             Es ersetzt nicht die klinische Beurteilung durch einen Arzt. \
-             Alle Scores sollten im klinischen Kontext des Patienten interpretiert werden. \
-             Keine Garantie für Richtigkeit oder Vollständigkeit."
-        } else {
-            "KlinScore is a clinical decision support tool. \
-             It does not replace clinical judgment by a physician. \
-             All scores should be interpreted in the patient's clinical context. \
-             No guarantee of accuracy or completeness."
-        };
-
         let disclaimer_box = container(
             column![
-                text(disclaimer_title).size(16),
-                text(disclaimer_text).size(13),
+                text(tr!(self.language, "about-disclaimer-title")).size(16),
+                text(tr!(self.language, "about-disclaimer")).size(13),
             ]
             .spacing(5),
         )
@@ -938,8 +3801,8 @@ impl KlinScore {
         let methodology_section = self.about_methodology_section();
 
         let content = column![
-            text(title).size(32),
-            text(subtitle).size(16),
+            text(tr!(self.language, "about-title")).size(32),
+            text(tr!(self.language, "app-subtitle")).size(16),
             horizontal_rule(1),
             stats_section,
             horizontal_rule(1),
@@ -948,13 +3811,13 @@ impl KlinScore {
             methodology_section,
             horizontal_rule(1),
             // Back button
-            button(text(back_label).size(18))
+            button(text(tr!(self.language, "back")).size(18))
                 .on_press(Message::CloseAbout)
-                .padding(10),
+                .padding(self.layout.button_padding(10)),
         ]
         .spacing(15)
         .align_x(Alignment::Center)
-        .padding(40)
+        .padding(self.layout.padding(40))
         .max_width(900);
 
         container(content)
@@ -966,20 +3829,6 @@ impl KlinScore {
     /// Build the methodology & sources section for the About page.
     /// Shows each score with: calculation method, inputs, formula/logic, and clickable reference.
     fn about_methodology_section(&self) -> Element<'_, Message> {
-        let is_de = self.language == Language::German;
-
-        let section_title = if is_de {
-            "Score-Methodik & Quellenangaben"
-        } else {
-            "Score Methodology & Sources"
-        };
-
-        let section_subtitle = if is_de {
-            "Vollständige Transparenz über die verwendeten Berechnungsverfahren und deren wissenschaftliche Grundlage."
-        } else {
-            "Full transparency on calculation methods and their scientific basis."
-        };
-
         let library = match &self.score_library {
             Some(lib) => lib,
             None => return text("").into(),
@@ -987,33 +3836,26 @@ impl KlinScore {
 
         let mut all_cards: Vec<Element<'_, Message>> = Vec::new();
 
-        for specialty in &[
-            Specialty::Cardiology,
-            Specialty::Nephrology,
-            Specialty::Anesthesiology,
-        ] {
-            let scores = library.get_scores_for_specialty(*specialty);
+        for specialty in &Specialty::ALL {
+            let scores = library.specialty_scores(*specialty);
             if scores.is_empty() {
                 continue;
             }
 
-            let specialty_name = if is_de {
-                specialty.german()
-            } else {
-                specialty.english()
-            };
+            let specialty_name = i18n::specialty_name(self.language, *specialty);
 
             // Specialty header
             all_cards.push(text(format!("--- {} ---", specialty_name)).size(18).into());
 
-            for score in &scores {
+            for (_, score) in scores {
                 all_cards.push(self.score_methodology_card(score));
             }
         }
 
         column![
-            text(section_title).size(24),
-            text(section_subtitle).size(14),
+            text(tr!(self.language, "methodology-title")).size(24),
+            text(tr!(self.language, "methodology-subtitle")).size(14),
+            self.about_packs_section(library),
             column(all_cards).spacing(12),
         ]
         .spacing(12)
@@ -1021,102 +3863,88 @@ impl KlinScore {
         .into()
     }
 
+    /// Installed score packs with the result of their integrity check
+    fn about_packs_section<'a>(&self, library: &'a ScoreLibrary) -> Element<'a, Message> {
+        if library.packs.is_empty() {
+            return column![].into();
+        }
+        let mut packs = column![text(tr!(self.language, "packs-title")).size(18)].spacing(8);
+
+        for pack in &library.packs {
+            let title = match &pack.manifest {
+                Some(manifest) => {
+                    let mut title = format!(
+                        "{} {}",
+                        manifest.name.text(self.language.code()),
+                        manifest.version
+                    );
+                    if let Some(publisher) = &manifest.publisher {
+                        title.push_str(&format!(" ({})", publisher));
+                    }
+                    title
+                }
+                None => pack.dir.display().to_string(),
+            };
+            let integrity = pack
+                .integrity
+                .clone()
+                .trusted(&self.settings.trusted_pack_keys);
+            let status = i18n::pack_integrity_text(self.language, &integrity);
+            let color = match integrity {
+                PackIntegrity::Failed(_) => iced::Color::from_rgb(0.8, 0.1, 0.1),
+                PackIntegrity::Unverified | PackIntegrity::Untrusted { .. } => {
+                    iced::Color::from_rgb(0.8, 0.5, 0.0)
+                }
+                _ => iced::Color::from_rgb(0.2, 0.6, 0.2),
+            };
+            packs = packs
+                .push(column![text(title).size(14), text(status).size(12).color(color)].spacing(2));
+        }
+
+        packs.into()
+    }
+
     /// Build a single score methodology card with: name, method, inputs, calculation, reference link.
     fn score_methodology_card<'a>(
         &self,
         score: &'a config::ScoreDefinition,
     ) -> Element<'a, Message> {
-        let is_de = self.language == Language::German;
-
-        let name = if is_de { &score.name_de } else { &score.name };
-        let description = if is_de {
-            &score.description_de
-        } else {
-            &score.description
-        };
+        let name = score.name.text(self.language.code());
+        let description = score.description.text(self.language.code());
 
         // Determine calculation method
         let (method_label, method_detail) = if let Some(ref formula) = score.formula {
-            let label = if is_de {
-                "Formelbasiert"
-            } else {
-                "Formula-based"
-            };
             let detail = match formula.as_str() {
-                "ckd_epi_2021" => {
-                    if is_de {
-                        "eGFR = 142 \u{00d7} min(Scr/\u{03ba}, 1)^\u{03b1} \u{00d7} max(Scr/\u{03ba}, 1)^(\u{2212}1.200) \u{00d7} 0.9938^Alter \u{00d7} (1.012 bei Frauen)\n\
-                         Wobei: Weiblich \u{03ba}=0.7, \u{03b1}=\u{2212}0.241 | Männlich \u{03ba}=0.9, \u{03b1}=\u{2212}0.302\n\
-                         Scr in mg/dL (Eingabe \u{03bc}mol/L \u{00f7} 88.4)"
-                            .to_string()
-                    } else {
-                        "eGFR = 142 \u{00d7} min(Scr/\u{03ba}, 1)^\u{03b1} \u{00d7} max(Scr/\u{03ba}, 1)^(\u{2212}1.200) \u{00d7} 0.9938^age \u{00d7} (1.012 if female)\n\
-                         Where: Female \u{03ba}=0.7, \u{03b1}=\u{2212}0.241 | Male \u{03ba}=0.9, \u{03b1}=\u{2212}0.302\n\
-                         Scr in mg/dL (input \u{03bc}mol/L \u{00f7} 88.4)"
-                            .to_string()
-                    }
-                }
-                "kfre_4var" => {
-                    if is_de {
-                        "Risiko = 1 \u{2212} 0.9832^exp(Summe)\n\
-                         Summe = \u{2212}0.2201\u{00d7}(Alter/10 \u{2212} 7.036) + 0.2467\u{00d7}(männl. \u{2212} 0.5642) \u{2212} 0.5567\u{00d7}(eGFR/5 \u{2212} 7.222) + 0.4510\u{00d7}(ln(ACR) \u{2212} 5.137)\n\
-                         ACR-Eingabe: mg/mmol, intern umgerechnet in mg/g (\u{00d7} 8.84)"
-                            .to_string()
-                    } else {
-                        "Risk = 1 \u{2212} 0.9832^exp(sum)\n\
-                         sum = \u{2212}0.2201\u{00d7}(age/10 \u{2212} 7.036) + 0.2467\u{00d7}(male \u{2212} 0.5642) \u{2212} 0.5567\u{00d7}(eGFR/5 \u{2212} 7.222) + 0.4510\u{00d7}(ln(ACR) \u{2212} 5.137)\n\
-                         ACR input: mg/mmol, converted to mg/g internally (\u{00d7} 8.84)"
-                            .to_string()
-                    }
-                }
+                "ckd_epi_2021" => tr!(self.language, "formula-ckd-epi-2021"),
+                "kfre_4var" => tr!(self.language, "formula-kfre-4var"),
                 _ => formula.clone(),
             };
-            (label, detail)
+            (tr!(self.language, "methodology-formula"), detail)
         } else {
-            let label = if is_de {
-                "Punktebasiert"
-            } else {
-                "Point-based"
-            };
-            let detail = if is_de {
-                "Summe der Punkte aller zutreffenden Kriterien".to_string()
-            } else {
-                "Sum of points for all applicable criteria".to_string()
-            };
-            (label, detail)
+            (
+                tr!(self.language, "methodology-points"),
+                tr!(self.language, "methodology-points-detail"),
+            )
         };
 
         // Build inputs summary
-        let inputs_label = if is_de { "Eingaben" } else { "Inputs" };
         let inputs_summary: String = score
             .inputs
             .iter()
             .map(|input| {
-                let label = if is_de { &input.label_de } else { &input.label };
+                let label = input.label.text(self.language.code());
                 let type_str = match input.input_type {
-                    config::InputType::Boolean => {
-                        if is_de {
-                            "Ja/Nein"
-                        } else {
-                            "Yes/No"
-                        }
-                    }
+                    config::InputType::Boolean => tr!(self.language, "methodology-input-boolean"),
                     config::InputType::Number => {
                         if let Some(ref unit) = input.unit {
-                            return format!("{} ({})", label, unit);
-                        }
-                        if is_de {
-                            "Zahl"
-                        } else {
-                            "Number"
+                            return format!("{} ({})", label, unit.text(self.language.code()));
                         }
+                        tr!(self.language, "methodology-input-number")
                     }
-                    config::InputType::Dropdown => {
-                        if is_de {
-                            "Auswahl"
-                        } else {
-                            "Selection"
-                        }
+                    config::InputType::Dropdown => tr!(self.language, "methodology-input-dropdown"),
+                    config::InputType::Date => tr!(self.language, "methodology-input-date"),
+                    config::InputType::Duration => {
+                        tr!(self.language, "methodology-input-duration")
                     }
                 };
                 format!("{} ({})", label, type_str)
@@ -1124,29 +3952,14 @@ impl KlinScore {
             .collect::<Vec<_>>()
             .join(", ");
 
-        // Build the reference row with a clickable link button
-        let reference_elements: Element<'_, Message> = if score.reference_url.is_empty() {
-            text(&score.reference).size(12).into()
-        } else {
-            let url = score.reference_url.clone();
-            let link_label = if is_de {
-                "Quelle öffnen"
-            } else {
-                "Open source"
-            };
-            column![
-                text(&score.reference).size(12),
-                button(text(format!("\u{1f517} {}", link_label)).size(12))
-                    .on_press(Message::OpenUrl(url))
-                    .padding(4),
-            ]
-            .spacing(4)
-            .into()
-        };
-
-        let method_heading = if is_de { "Methode" } else { "Method" };
-        let calc_heading = if is_de { "Berechnung" } else { "Calculation" };
-        let ref_heading = if is_de { "Referenz" } else { "Reference" };
+        let range_line = scores::score_bounds(score).map(|(min, max)| {
+            tr!(
+                self.language,
+                "methodology-range",
+                min = scores::format_signed_points(min),
+                max = scores::format_signed_points(max)
+            )
+        });
 
         // Build the card
         let card = container(
@@ -1155,17 +3968,25 @@ impl KlinScore {
                 text(name).size(16),
                 text(description).size(13),
                 // Method
-                row![
-                    text(format!("{}: ", method_heading)).size(13),
-                    text(method_label).size(13),
-                ]
-                .spacing(4),
+                text(tr!(
+                    self.language,
+                    "methodology-method",
+                    method = method_label
+                ))
+                .size(13),
                 // Inputs
-                text(format!("{}: {}", inputs_label, inputs_summary)).size(12),
+                text(tr!(
+                    self.language,
+                    "methodology-inputs",
+                    inputs = inputs_summary
+                ))
+                .size(12),
+                // Achievable range (point-based scores only)
+                text(range_line.unwrap_or_default()).size(12),
                 // Calculation detail
                 container(
                     column![
-                        text(format!("{}:", calc_heading)).size(13),
+                        text(tr!(self.language, "methodology-calculation")).size(13),
                         text(method_detail).size(12),
                     ]
                     .spacing(2),
@@ -1188,8 +4009,13 @@ impl KlinScore {
                 }),
                 // Reference
                 column![
-                    text(format!("{} ({})", ref_heading, score.guideline_source)).size(13),
-                    reference_elements,
+                    text(tr!(
+                        self.language,
+                        "methodology-reference",
+                        source = score.guideline_source.as_str()
+                    ))
+                    .size(13),
+                    reference_list(&score.references, 12),
                 ]
                 .spacing(2),
             ]
@@ -1219,21 +4045,16 @@ impl KlinScore {
     }
 
     fn error_view<'a>(&self, error: &'a str) -> Element<'a, Message> {
-        let title = match self.language {
-            Language::German => "Fehler beim Laden",
-            Language::English => "Loading Error",
-        };
-
         let content = column![
-            text(title).size(32),
+            text(tr!(self.language, "loading-error")).size(32),
             text(error).size(16),
             button(text("OK"))
                 .on_press(Message::BackToWelcome)
-                .padding(10),
+                .padding(self.layout.button_padding(10)),
         ]
         .spacing(20)
         .align_x(Alignment::Center)
-        .padding(50);
+        .padding(self.layout.padding(50));
 
         container(content)
             .width(Length::Fill)
@@ -1241,51 +4062,717 @@ impl KlinScore {
             .into()
     }
 
-    fn settings_view<'a>(&'a self) -> Element<'a, Message> {
-        let title = match self.language {
-            Language::German => "Einstellungen",
-            Language::English => "Settings",
+    /// Color fields and presets for the custom theme
+    fn custom_theme_editor(&self) -> Element<'_, Message> {
+        let custom = &self.settings.custom_theme;
+        let color_row = |label: String, value: &str, on_input: fn(String) -> Message| {
+            let mut field = column![row![
+                text(label).size(14).width(Length::Fixed(140.0)),
+                text_input("#RRGGBB", value)
+                    .on_input(on_input)
+                    .padding(self.layout.button_padding(6))
+                    .style(self.layout.text_input_style())
+                    .width(Length::Fixed(120.0)),
+            ]
+            .spacing(10)
+            .align_y(Alignment::Center)];
+            if settings::parse_hex_color(value).is_none() {
+                field = field.push(
+                    text(tr!(self.language, "theme-invalid-color"))
+                        .size(12)
+                        .color(iced::Color::from_rgb(0.8, 0.1, 0.1)),
+                );
+            }
+            field
+        };
+
+        let presets = settings::THEME_PRESETS.iter().enumerate().fold(
+            row![text(tr!(self.language, "theme-presets")).size(14)]
+                .spacing(10)
+                .align_y(Alignment::Center),
+            |presets, (index, preset)| {
+                presets.push(
+                    button(text(tr!(self.language, preset.message_id)).size(13))
+                        .on_press(Message::CustomThemePresetSelected(index))
+                        .padding(self.layout.button_padding(6))
+                        .style(button::secondary),
+                )
+            },
+        );
+
+        column![
+            color_row(
+                tr!(self.language, "theme-background"),
+                &custom.background,
+                Message::CustomThemeBackgroundChanged
+            ),
+            color_row(
+                tr!(self.language, "theme-primary"),
+                &custom.primary,
+                Message::CustomThemePrimaryChanged
+            ),
+            row![
+                text(tr!(self.language, "theme-risk-colors"))
+                    .size(14)
+                    .width(Length::Fixed(140.0)),
+                pick_list(
+                    config::RiskPalette::ALL,
+                    Some(custom.risk_palette),
+                    Message::CustomThemeRiskPaletteChanged,
+                )
+                .width(Length::Fixed(120.0)),
+            ]
+            .spacing(10)
+            .align_y(Alignment::Center),
+            presets.wrap(),
+        ]
+        .spacing(8)
+        .padding(iced::Padding::ZERO.left(10))
+        .into()
+    }
+
+    /// Encryption of the history file: enable with a passphrase, disable, or unlock
+    fn encryption_settings(&self) -> Element<'_, Message> {
+        let mut content = column![text(tr!(self.language, "encryption-label")).size(14)].spacing(8);
+
+        content = match self.history_storage {
+            HistoryStorage::Plain => content
+                .push(text(tr!(self.language, "encryption-hint")).size(13))
+                .push(
+                    row![
+                        text_input(&tr!(self.language, "passphrase"), &self.passphrase_input)
+                            .secure(true)
+                            .on_input(Message::PassphraseChanged)
+                            .style(self.layout.text_input_style()),
+                        text_input(
+                            &tr!(self.language, "passphrase-repeat"),
+                            &self.passphrase_confirm_input
+                        )
+                        .secure(true)
+                        .on_input(Message::PassphraseConfirmChanged)
+                        .on_submit(Message::EnableHistoryEncryption)
+                        .style(self.layout.text_input_style()),
+                        button(text(tr!(self.language, "encryption-enable")).size(14))
+                            .on_press(Message::EnableHistoryEncryption)
+                            .padding(self.layout.button_padding(6)),
+                    ]
+                    .spacing(10)
+                    .align_y(Alignment::Center),
+                ),
+            HistoryStorage::Encrypted(_) => content.push(
+                row![
+                    text(tr!(self.language, "encryption-active")).size(13),
+                    button(text(tr!(self.language, "encryption-disable")).size(14))
+                        .on_press(Message::DisableHistoryEncryption)
+                        .padding(self.layout.button_padding(6))
+                        .style(button::secondary),
+                ]
+                .spacing(10)
+                .align_y(Alignment::Center),
+            ),
+            HistoryStorage::Locked => content.push(self.history_locked_notice()),
+        };
+
+        content
+            .push_maybe(
+                self.encryption_status
+                    .as_ref()
+                    .filter(|_| !self.unlock_dialog_open)
+                    .map(|status| text(status).size(13)),
+            )
+            .into()
+    }
+
+    /// Shown while the encrypted history is locked, with a button to unlock it
+    fn history_locked_notice(&self) -> Element<'_, Message> {
+        row![
+            text(tr!(self.language, "history-locked")).size(13),
+            button(text(tr!(self.language, "unlock")).size(14))
+                .on_press(Message::OpenUnlockDialog)
+                .padding(self.layout.button_padding(6))
+                .style(button::secondary),
+        ]
+        .spacing(10)
+        .align_y(Alignment::Center)
+        .into()
+    }
+
+    /// Where the data of this run is kept, and the portable mode switch
+    fn data_location_settings(&self) -> Element<'_, Message> {
+        let location = paths::data_dir()
+            .map(|dir| dir.display().to_string())
+            .unwrap_or_else(|| "–".to_string());
+        let mode = match paths::data_location() {
+            paths::DataLocation::Platform => tr!(self.language, "data-location-platform"),
+            paths::DataLocation::Portable => tr!(self.language, "data-location-portable"),
+            paths::DataLocation::Custom(_) => tr!(self.language, "data-location-custom"),
         };
 
-        let theme_label = match self.language {
-            Language::German => "Farbschema:",
-            Language::English => "Theme:",
+        column![
+            text(tr!(self.language, "data-location")).size(18),
+            text(format!("{}: {}", mode, location)).size(14),
+            checkbox(tr!(self.language, "portable-mode"), self.portable_marker)
+                .on_toggle(Message::PortableModeToggled)
+                .size(self.layout.toggle_size(16.0))
+                .text_size(14),
+            text(tr!(self.language, "portable-mode-hint")).size(13),
+            checkbox(tr!(self.language, "log-file"), self.settings.log_file)
+                .on_toggle(Message::LogFileToggled)
+                .size(self.layout.toggle_size(16.0))
+                .text_size(14),
+            text(tr!(self.language, "log-file-hint")).size(13),
+        ]
+        .spacing(10)
+        .padding(20)
+        .max_width(600)
+        .into()
+    }
+
+    /// Folder the save dialog for exports opens in
+    fn export_dir_settings(&self) -> Element<'_, Message> {
+        let folder = match &self.settings.export_dir {
+            Some(dir) => dir.display().to_string(),
+            None => tr!(self.language, "export-dir-default"),
         };
 
-        let back_label = match self.language {
-            Language::German => "← Zurück",
-            Language::English => "← Back",
+        column![
+            text(tr!(self.language, "export-dir")).size(18),
+            text(folder).size(14),
+            row![
+                button(text(tr!(self.language, "export-dir-choose")).size(14))
+                    .on_press(Message::ChooseExportDir)
+                    .padding(self.layout.button_padding(8)),
+                button(text(tr!(self.language, "export-dir-reset")).size(14))
+                    .on_press_maybe(
+                        self.settings
+                            .export_dir
+                            .is_some()
+                            .then_some(Message::ResetExportDir)
+                    )
+                    .padding(self.layout.button_padding(8)),
+            ]
+            .spacing(10),
+            text(tr!(self.language, "export-dir-hint")).size(13),
+        ]
+        .spacing(10)
+        .padding(20)
+        .max_width(600)
+        .into()
+    }
+
+    /// Switches removing identifying fields from every export
+    fn anonymization_settings(&self) -> Element<'_, Message> {
+        type Setter = fn(&mut ExportAnonymization, bool);
+        let anonymization = self.settings.export_anonymization;
+        let switches: [(&str, bool, Setter); 3] = [
+            (
+                "anonymize-timestamps",
+                anonymization.strip_timestamps,
+                |a, on| a.strip_timestamps = on,
+            ),
+            (
+                "anonymize-patient-labels",
+                anonymization.strip_patient_labels,
+                |a, on| a.strip_patient_labels = on,
+            ),
+            ("anonymize-notes", anonymization.strip_notes, |a, on| {
+                a.strip_notes = on
+            }),
+        ];
+        switches
+            .into_iter()
+            .fold(
+                column![text(tr!(self.language, "anonymize-label")).size(18)],
+                |section, (label_id, checked, set)| {
+                    section.push(
+                        checkbox(tr!(self.language, label_id), checked)
+                            .on_toggle(move |on| {
+                                let mut anonymization = anonymization;
+                                set(&mut anonymization, on);
+                                Message::ExportAnonymizationChanged(anonymization)
+                            })
+                            .size(self.layout.toggle_size(16.0))
+                            .text_size(14),
+                    )
+                },
+            )
+            .push(text(tr!(self.language, "anonymize-hint")).size(13))
+            .spacing(10)
+            .padding(20)
+            .max_width(600)
+            .into()
+    }
+
+    /// Units lab values are entered in, for scores that offer both
+    fn unit_settings(&self) -> Element<'_, Message> {
+        config::UnitSystem::ALL
+            .into_iter()
+            .fold(
+                column![text(tr!(self.language, "units-label")).size(18)],
+                |section, unit_system| {
+                    section.push(
+                        radio(
+                            i18n::unit_system_name(self.language, unit_system),
+                            unit_system,
+                            Some(self.settings.unit_system),
+                            Message::UnitSystemChanged,
+                        )
+                        .size(self.layout.toggle_size(16.0))
+                        .text_size(14),
+                    )
+                },
+            )
+            .push(text(tr!(self.language, "units-hint")).size(13))
+            .spacing(10)
+            .padding(20)
+            .max_width(600)
+            .into()
+    }
+
+    /// Institution header and footer and the language of PDF reports
+    fn report_settings(&self) -> Element<'_, Message> {
+        let other_language = if self.language == Language::German {
+            Language::English
+        } else {
+            Language::German
         };
+        column![
+            text(tr!(self.language, "pdf-report")).size(18),
+            text(tr!(self.language, "pdf-report-header")).size(14),
+            text_input(
+                &tr!(self.language, "pdf-report-header-placeholder"),
+                &self.settings.report_header
+            )
+            .on_input(Message::ReportHeaderChanged)
+            .padding(self.layout.button_padding(8))
+            .style(self.layout.text_input_style()),
+            text(tr!(self.language, "pdf-report-footer")).size(14),
+            text_input(
+                &tr!(self.language, "pdf-report-footer-placeholder"),
+                &self.settings.report_footer
+            )
+            .on_input(Message::ReportFooterChanged)
+            .padding(self.layout.button_padding(8))
+            .style(self.layout.text_input_style()),
+            checkbox(
+                tr!(
+                    self.language,
+                    "pdf-report-bilingual",
+                    language = other_language.to_string()
+                ),
+                self.settings.bilingual_reports
+            )
+            .on_toggle(Message::BilingualReportsToggled)
+            .size(self.layout.toggle_size(16.0))
+            .text_size(14),
+            checkbox(
+                tr!(self.language, "pdf-report-qr-code"),
+                self.settings.report_qr_code
+            )
+            .on_toggle(Message::ReportQrCodeToggled)
+            .size(self.layout.toggle_size(16.0))
+            .text_size(14),
+        ]
+        .spacing(10)
+        .padding(20)
+        .max_width(600)
+        .into()
+    }
+
+    /// FHIR server the input forms are prefilled from
+    fn fhir_settings(&self) -> Element<'_, Message> {
+        column![
+            text(tr!(self.language, "fhir-server")).size(18),
+            text(tr!(self.language, "fhir-server-hint")).size(13),
+            text_input(
+                &tr!(self.language, "fhir-server-placeholder"),
+                &self.settings.fhir_endpoint
+            )
+            .on_input(Message::FhirEndpointChanged)
+            .padding(self.layout.button_padding(8))
+            .style(self.layout.text_input_style()),
+        ]
+        .spacing(10)
+        .padding(20)
+        .max_width(600)
+        .into()
+    }
+
+    /// Sealing and signing of PDF and JSON exports, with the public key to pass on
+    fn integrity_settings(&self) -> Element<'_, Message> {
+        let mut section = column![
+            text(tr!(self.language, "seal-label")).size(18),
+            checkbox(
+                tr!(self.language, "seal-exports"),
+                self.settings.seal_exports
+            )
+            .on_toggle(Message::SealExportsToggled)
+            .size(self.layout.toggle_size(16.0))
+            .text_size(14),
+            checkbox(
+                tr!(self.language, "sign-exports"),
+                self.settings.sign_exports
+            )
+            .on_toggle_maybe(
+                self.settings
+                    .seal_exports
+                    .then_some(Message::SignExportsToggled)
+            )
+            .size(self.layout.toggle_size(16.0))
+            .text_size(14),
+        ]
+        .spacing(10)
+        .padding(20)
+        .max_width(600);
+        if let Some(key) = self
+            .signing_key
+            .as_ref()
+            .filter(|_| self.settings.sign_exports)
+        {
+            section = section.push(
+                row![
+                    text(tr!(self.language, "signing-key", key = key.as_str()))
+                        .size(13)
+                        .width(Length::Fill),
+                    button(text(tr!(self.language, "result-copy")).size(13))
+                        .on_press(Message::CopySigningKey)
+                        .padding(self.layout.button_padding(6))
+                        .style(button::secondary),
+                ]
+                .spacing(10)
+                .align_y(Alignment::Center),
+            );
+        }
+        section
+            .push(text(tr!(self.language, "seal-hint")).size(13))
+            .into()
+    }
+
+    /// Daily snapshots of the settings and the history, each with a restore button
+    fn backup_settings(&self) -> Element<'_, Message> {
+        let mut section = column![
+            text(tr!(self.language, "backups")).size(18),
+            text(tr!(
+                self.language,
+                "backups-hint",
+                count = backup::MAX_SNAPSHOTS
+            ))
+            .size(13),
+        ]
+        .spacing(10)
+        .padding(20)
+        .max_width(600);
+
+        if self.backups.is_empty() {
+            section = section.push(text(tr!(self.language, "backups-empty")).size(14));
+        }
+        for snapshot in &self.backups {
+            let created = snapshot.created.format("%Y-%m-%d %H:%M").to_string();
+            let label = if snapshot.encrypted {
+                format!("{} 🔒", created)
+            } else {
+                created
+            };
+            section = section.push(
+                row![
+                    text(label).size(14).width(Length::Fill),
+                    button(text(tr!(self.language, "backup-restore")).size(13))
+                        .on_press(Message::RestoreBackup(snapshot.name.clone()))
+                        .padding(self.layout.button_padding(6)),
+                ]
+                .spacing(10)
+                .align_y(Alignment::Center),
+            );
+        }
+        section.into()
+    }
+
+    /// Export of the settings to a file, and import of such a file
+    fn settings_transfer(&self) -> Element<'_, Message> {
+        column![
+            text(tr!(self.language, "settings-transfer")).size(18),
+            text(tr!(self.language, "settings-transfer-hint")).size(13),
+            row![
+                button(text(tr!(self.language, "settings-export")).size(14))
+                    .on_press(Message::ExportSettings)
+                    .padding(self.layout.button_padding(8)),
+                button(text(tr!(self.language, "settings-import")).size(14))
+                    .on_press(Message::ImportSettings)
+                    .padding(self.layout.button_padding(8)),
+            ]
+            .spacing(10),
+        ]
+        .spacing(10)
+        .padding(20)
+        .max_width(600)
+        .into()
+    }
+
+    /// Automatic pruning of the history: mode and number of entries or days
+    fn retention_settings(&self) -> Element<'_, Message> {
+        let retention = self.settings.history_retention;
+        let hint = tr!(
+            self.language,
+            "retention-hint",
+            mode = tr!(self.language, retention.message_id())
+        );
+
+        let limit_input = retention.limit().map(|_| {
+            text_input("", &self.retention_limit_input)
+                .on_input(Message::RetentionLimitChanged)
+                .style(self.layout.text_input_style())
+                .width(Length::Fixed(80.0))
+        });
+        let invalid_limit = limit_input.is_some()
+            && self
+                .retention_limit_input
+                .trim()
+                .parse::<u32>()
+                .map_or(true, |n| n == 0);
+
+        column![
+            text(tr!(self.language, "retention-label")).size(14),
+            row![pick_list(
+                retention.choices(),
+                Some(retention),
+                Message::HistoryRetentionChanged,
+            )
+            .width(Length::Fixed(200.0))]
+            .push_maybe(limit_input)
+            .spacing(10)
+            .align_y(Alignment::Center),
+            text(hint).size(13),
+        ]
+        .push_maybe(invalid_limit.then(|| {
+            text(tr!(self.language, "retention-invalid-limit"))
+                .size(13)
+                .color(iced::Color::from_rgb(0.8, 0.1, 0.1))
+        }))
+        .spacing(8)
+        .into()
+    }
+
+    fn settings_view<'a>(&'a self) -> Element<'a, Message> {
+        let language = self.language;
 
         let theme_picker = pick_list(
             AppTheme::all(),
             Some(self.settings.theme),
             Message::ThemeChanged,
         )
-        .placeholder("Select theme")
+        .placeholder(tr!(language, "theme-placeholder"))
         .width(Length::Fixed(200.0));
 
-        let theme_display = match self.language {
-            Language::German => self.settings.theme.german(),
-            Language::English => self.settings.theme.label(),
-        };
+        let theme_display = tr!(language, &format!("theme-{:?}", self.settings.theme));
+
+        let research_hint = tr!(
+            language,
+            "research-hint",
+            precision = i18n::precision_name(language, self.settings.research_timestamp_precision)
+        );
+
+        let research_picker = pick_list(
+            TimestampPrecision::all(),
+            Some(self.settings.research_timestamp_precision),
+            Message::ResearchPrecisionChanged,
+        )
+        .width(Length::Fixed(200.0));
+
+        let wizard_label = tr!(
+            language,
+            "wizard-long-scores",
+            count = config::InputMode::WIZARD_MIN_INPUTS
+        );
+
+        let remove_label = tr!(language, "remove");
+
+        let mut score_dirs_section = column![
+            text(tr!(language, "score-dirs")).size(18),
+            text(tr!(language, "score-dirs-hint")).size(13)
+        ]
+        .spacing(10)
+        .padding(20)
+        .max_width(600);
+
+        for (index, dir) in self.settings.extra_score_dirs.iter().enumerate() {
+            score_dirs_section = score_dirs_section.push(
+                row![
+                    text(dir.display().to_string()).size(14).width(Length::Fill),
+                    button(text(remove_label.clone()).size(13))
+                        .on_press(Message::RemoveScoreDir(index))
+                        .padding(self.layout.button_padding(6)),
+                ]
+                .spacing(10)
+                .align_y(Alignment::Center),
+            );
+        }
+
+        score_dirs_section = score_dirs_section.push(
+            row![
+                text_input(
+                    &tr!(language, "score-dirs-placeholder"),
+                    &self.score_dir_input
+                )
+                .on_input(Message::ScoreDirInputChanged)
+                .on_submit(Message::AddScoreDir)
+                .padding(self.layout.button_padding(8))
+                .style(self.layout.text_input_style()),
+                button(text(tr!(language, "add")).size(14))
+                    .on_press(Message::AddScoreDir)
+                    .padding(self.layout.button_padding(8)),
+            ]
+            .spacing(10),
+        );
+
+        score_dirs_section = score_dirs_section.push(
+            checkbox(
+                tr!(language, "strict-loading"),
+                self.settings.strict_score_loading,
+            )
+            .on_toggle(Message::StrictLoadingToggled)
+            .size(self.layout.toggle_size(16.0))
+            .text_size(14),
+        );
+
+        score_dirs_section = score_dirs_section.push(
+            column![
+                text(tr!(language, "pack-import")).size(18),
+                text(tr!(language, "pack-import-hint")).size(13),
+                row![
+                    text_input(&tr!(language, "pack-placeholder"), &self.pack_path_input)
+                        .on_input(Message::PackPathInputChanged)
+                        .on_submit(Message::ImportPack)
+                        .padding(self.layout.button_padding(8))
+                        .style(self.layout.text_input_style()),
+                    button(text(tr!(language, "import")).size(14))
+                        .on_press(Message::ImportPack)
+                        .padding(self.layout.button_padding(8)),
+                ]
+                .spacing(10),
+            ]
+            .spacing(10)
+            .padding(iced::Padding::ZERO.top(20)),
+        );
+
+        let mut trusted_keys_section = column![
+            text(tr!(language, "trusted-keys")).size(18),
+            text(tr!(language, "trusted-keys-hint")).size(13),
+        ]
+        .spacing(10)
+        .padding(iced::Padding::ZERO.top(20));
+        for (index, key) in self.settings.trusted_pack_keys.iter().enumerate() {
+            trusted_keys_section = trusted_keys_section.push(
+                row![
+                    text(key).size(13).width(Length::Fill),
+                    button(text(remove_label.clone()).size(13))
+                        .on_press(Message::RemoveTrustedKey(index))
+                        .padding(self.layout.button_padding(6)),
+                ]
+                .spacing(10)
+                .align_y(Alignment::Center),
+            );
+        }
+        score_dirs_section = score_dirs_section.push(
+            trusted_keys_section.push(
+                row![
+                    text_input(
+                        &tr!(language, "trusted-key-placeholder"),
+                        &self.trusted_key_input
+                    )
+                    .on_input(Message::TrustedKeyInputChanged)
+                    .on_submit(Message::AddTrustedKey)
+                    .padding(self.layout.button_padding(8))
+                    .style(self.layout.text_input_style()),
+                    button(text(tr!(language, "add")).size(14))
+                        .on_press(Message::AddTrustedKey)
+                        .padding(self.layout.button_padding(8)),
+                ]
+                .spacing(10),
+            ),
+        );
+
+        if let Some(status) = &self.settings_status {
+            score_dirs_section = score_dirs_section.push(
+                text(status)
+                    .size(13)
+                    .color(iced::Color::from_rgb(0.8, 0.1, 0.1)),
+            );
+        }
 
         let content = column![
-            text(title).size(32),
+            text(tr!(language, "settings")).size(32),
             column![
-                text(theme_label).size(18),
+                text(tr!(language, "theme-label")).size(18),
                 theme_picker,
-                text(format!("Current: {}", theme_display)).size(14),
+                text(tr!(language, "theme-current", theme = theme_display)).size(14),
             ]
+            .push_maybe(
+                (self.settings.theme == AppTheme::Custom).then(|| self.custom_theme_editor()),
+            )
+            .push(
+                checkbox(tr!(language, "help-hints"), self.settings.show_help_hints)
+                    .on_toggle(Message::HelpHintsToggled)
+                    .size(self.layout.toggle_size(16.0))
+                    .text_size(14),
+            )
+            .push(
+                checkbox(wizard_label, self.settings.wizard_for_long_scores)
+                    .on_toggle(Message::WizardModeToggled)
+                    .size(self.layout.toggle_size(16.0))
+                    .text_size(14),
+            )
             .spacing(10)
             .padding(20),
-            button(text(back_label).size(18))
+            self.unit_settings(),
+            column![
+                text(tr!(language, "accessibility")).size(18),
+                checkbox(
+                    tr!(language, "high-contrast"),
+                    self.settings.theme == AppTheme::HighContrast
+                )
+                .on_toggle(Message::HighContrastToggled)
+                .size(self.layout.toggle_size(16.0))
+                .text_size(14),
+                checkbox(tr!(language, "touch-mode"), self.settings.touch_mode)
+                    .on_toggle(Message::TouchModeToggled)
+                    .size(self.layout.toggle_size(16.0))
+                    .text_size(14),
+                text(tr!(language, "touch-hint")).size(13),
+            ]
+            .spacing(10)
+            .padding(20),
+            column![
+                text(tr!(language, "research-label")).size(18),
+                research_picker,
+                text(research_hint).size(14),
+                checkbox(
+                    tr!(language, "patient-labels-disabled"),
+                    self.settings.disable_patient_labels
+                )
+                .on_toggle(Message::PatientLabelsDisabled)
+                .size(self.layout.toggle_size(16.0))
+                .text_size(14),
+                self.retention_settings(),
+                self.encryption_settings(),
+            ]
+            .spacing(10)
+            .padding(20),
+            self.export_dir_settings(),
+            self.anonymization_settings(),
+            self.report_settings(),
+            self.integrity_settings(),
+            self.fhir_settings(),
+            score_dirs_section,
+            self.data_location_settings(),
+            self.backup_settings(),
+            self.settings_transfer(),
+            button(text(tr!(language, "back")).size(18))
                 .on_press(Message::CloseSettings)
-                .padding(10),
+                .padding(self.layout.button_padding(10)),
         ]
         .spacing(30)
         .align_x(Alignment::Center)
-        .padding(50);
+        .padding(self.layout.padding(50));
 
         container(content)
             .width(Length::Fill)
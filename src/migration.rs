@@ -0,0 +1,291 @@
+// migration.rs
+// Format versions of the settings and history files, and upgrading older files
+//
+// Every JSON file KlinScore writes carries a `format_version`. When the layout
+// of `PersistedSettings` or `HistoryEntry` changes, bump the version of the file
+// and append a migration that turns the previous layout into the new one, so
+// old files are converted instead of losing data to `#[serde(default)]`.
+// The history database has its own schema version (`PRAGMA user_version`).
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use thiserror::Error;
+
+use crate::history::HistoryEntry;
+use crate::persistence::{LastScreen, PersistedSettings};
+
+/// Field holding the format version; files without it are version 1
+const VERSION_FIELD: &str = "format_version";
+
+/// Upgrade a file from one version to the next
+pub type Migration = fn(Value) -> Result<Value, MigrationError>;
+
+#[derive(Debug, Error)]
+pub enum MigrationError {
+    #[error("invalid JSON: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("written by a newer version of KlinScore (format {found}, this version reads up to {supported})")]
+    TooNew { found: u32, supported: u32 },
+
+    #[error("cannot upgrade from format {version}: {reason}")]
+    Failed { version: u32, reason: String },
+}
+
+/// A file format with a version and the migrations from its earlier versions
+pub trait Versioned: Serialize + DeserializeOwned {
+    /// Version written by this build
+    const FORMAT_VERSION: u32;
+
+    /// `MIGRATIONS[n]` upgrades version `n + 1` to `n + 2`
+    const MIGRATIONS: &'static [Migration];
+}
+
+/// Contents of a history file (`history.json` of earlier versions, history
+/// files moved between workstations, and the encrypted `history.age`)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HistoryFile {
+    pub entries: Vec<HistoryEntry>,
+}
+
+impl Versioned for HistoryFile {
+    /// 1: a bare array of entries
+    /// 2: an object with `format_version` and `entries`
+    /// 3: texts per language instead of English fields with `_de` twins
+    const FORMAT_VERSION: u32 = 3;
+    const MIGRATIONS: &'static [Migration] = &[history_v1_to_v2, history_v2_to_v3];
+}
+
+fn history_v1_to_v2(value: Value) -> Result<Value, MigrationError> {
+    match value {
+        Value::Array(entries) => Ok(serde_json::json!({ "entries": entries })),
+        _ => Err(MigrationError::Failed {
+            version: 1,
+            reason: "expected a list of entries".to_string(),
+        }),
+    }
+}
+
+fn history_v2_to_v3(mut value: Value) -> Result<Value, MigrationError> {
+    let Some(Value::Array(entries)) = value.get_mut("entries") else {
+        return Err(MigrationError::Failed {
+            version: 2,
+            reason: "expected a list of entries".to_string(),
+        });
+    };
+    entries.iter_mut().for_each(upgrade_bilingual_entry);
+    Ok(value)
+}
+
+/// Merge the English texts of a history entry and their `_de` twins into
+/// per-language maps, in place. Entries already in this layout are left as
+/// they are, so the history database can run it over rows of any age.
+pub fn upgrade_bilingual_entry(entry: &mut Value) {
+    let Value::Object(fields) = entry else {
+        return;
+    };
+    for key in ["score_name", "risk", "recommendation", "details"] {
+        merge_bilingual(fields, key);
+    }
+    if let Some(Value::Array(field_scores)) = fields.get_mut("field_scores") {
+        for field_score in field_scores {
+            if let Value::Object(field_score) = field_score {
+                merge_bilingual(field_score, "label");
+                merge_bilingual(field_score, "selection");
+            }
+        }
+    }
+}
+
+/// Replace `key` and `key_de` by a map of language codes to the non-empty texts
+fn merge_bilingual(fields: &mut serde_json::Map<String, Value>, key: &str) {
+    let german = fields.remove(&format!("{key}_de"));
+    if fields.get(key).is_some_and(Value::is_object) {
+        return;
+    }
+    let english = fields.remove(key);
+    if english.as_ref().is_none_or(Value::is_null) && german.as_ref().is_none_or(Value::is_null) {
+        if let Some(english) = english {
+            fields.insert(key.to_string(), english);
+        }
+        return;
+    }
+    let texts: serde_json::Map<String, Value> = [("en", english), ("de", german)]
+        .into_iter()
+        .filter_map(|(language, text)| match text {
+            Some(Value::String(text)) if !text.is_empty() => {
+                Some((language.to_string(), Value::String(text)))
+            }
+            _ => None,
+        })
+        .collect();
+    fields.insert(key.to_string(), Value::Object(texts));
+}
+
+impl Versioned for PersistedSettings {
+    /// 1: no `format_version`
+    /// 2: the same fields with `format_version`
+    const FORMAT_VERSION: u32 = 2;
+    const MIGRATIONS: &'static [Migration] = &[settings_v1_to_v2];
+}
+
+fn settings_v1_to_v2(value: Value) -> Result<Value, MigrationError> {
+    // Fields added before versioning have serde defaults
+    Ok(value)
+}
+
+impl Versioned for LastScreen {
+    const FORMAT_VERSION: u32 = 1;
+    const MIGRATIONS: &'static [Migration] = &[];
+}
+
+/// Version of a parsed file
+fn version_of(value: &Value) -> Result<u32, MigrationError> {
+    let Some(version) = value.get(VERSION_FIELD) else {
+        return Ok(1);
+    };
+    version
+        .as_u64()
+        .and_then(|version| u32::try_from(version).ok())
+        .filter(|&version| version > 0)
+        .ok_or_else(|| MigrationError::Failed {
+            version: 0,
+            reason: format!("invalid {VERSION_FIELD} {version}"),
+        })
+}
+
+/// Bring a parsed file of any earlier version up to `T::FORMAT_VERSION`
+pub fn upgrade<T: Versioned>(mut value: Value) -> Result<Value, MigrationError> {
+    let mut version = version_of(&value)?;
+    if version > T::FORMAT_VERSION {
+        return Err(MigrationError::TooNew {
+            found: version,
+            supported: T::FORMAT_VERSION,
+        });
+    }
+    while version < T::FORMAT_VERSION {
+        let migration = T::MIGRATIONS[version as usize - 1];
+        value = migration(value)?;
+        version += 1;
+    }
+    Ok(value)
+}
+
+/// Parse a file of any supported version
+pub fn from_slice<T: Versioned>(bytes: &[u8]) -> Result<T, MigrationError> {
+    let value = upgrade::<T>(serde_json::from_slice(bytes)?)?;
+    Ok(serde_json::from_value(value)?)
+}
+
+/// Serialize in the current format, with its version
+pub fn to_string_pretty<T: Versioned>(file: &T) -> Result<String, MigrationError> {
+    let mut value = serde_json::to_value(file)?;
+    if let Value::Object(fields) = &mut value {
+        fields.insert(VERSION_FIELD.to_string(), T::FORMAT_VERSION.into());
+    }
+    Ok(serde_json::to_string_pretty(&value)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Specialty;
+    use crate::history::tests::entry;
+    use crate::settings::AppTheme;
+    use crate::ui::Language;
+
+    #[test]
+    fn test_every_version_has_a_migration() {
+        assert_eq!(
+            HistoryFile::MIGRATIONS.len() as u32,
+            HistoryFile::FORMAT_VERSION - 1
+        );
+        assert_eq!(
+            PersistedSettings::MIGRATIONS.len() as u32,
+            PersistedSettings::FORMAT_VERSION - 1
+        );
+        assert_eq!(
+            LastScreen::MIGRATIONS.len() as u32,
+            LastScreen::FORMAT_VERSION - 1
+        );
+    }
+
+    #[test]
+    fn test_history_roundtrip() {
+        let file = HistoryFile {
+            entries: vec![entry("egfr", Specialty::Nephrology, "2026-02-01 09:30")],
+        };
+        let json = to_string_pretty(&file).unwrap();
+        assert!(json.contains("\"format_version\": 3"));
+        let loaded: HistoryFile = from_slice(json.as_bytes()).unwrap();
+        assert_eq!(loaded.entries[0].score_id, "egfr");
+    }
+
+    #[test]
+    fn test_unversioned_history_is_upgraded() {
+        let legacy = serde_json::to_string(&vec![
+            entry("has_bled", Specialty::Cardiology, "2026-01-10 08:00"),
+            entry("rcri", Specialty::Anesthesiology, "2026-02-01 09:30"),
+        ])
+        .unwrap();
+        let loaded: HistoryFile = from_slice(legacy.as_bytes()).unwrap();
+        assert_eq!(loaded.entries.len(), 2);
+    }
+
+    #[test]
+    fn test_bilingual_history_is_upgraded() {
+        let json = r#"{"format_version":2,"entries":[{
+            "timestamp":"2026-02-01 09:30","score_name":"RCRI","score_name_de":"RCRI",
+            "score_id":"rcri","specialty":"Anesthesiology","total_score":1,
+            "risk":"Low","risk_de":"Niedrig","recommendation":"","recommendation_de":"",
+            "field_scores":[{"field":"surgery","label":"High-risk surgery",
+                "label_de":"Hochrisiko-Eingriff","selection":null,"selection_de":null,
+                "points":1}]
+        }]}"#;
+        let loaded: HistoryFile = from_slice(json.as_bytes()).unwrap();
+        let entry = &loaded.entries[0];
+        assert_eq!(entry.risk.text("de"), "Niedrig");
+        assert_eq!(entry.risk.text("fr"), "Low");
+        assert!(entry.recommendation.is_blank());
+        assert!(entry.details.is_blank());
+        assert_eq!(
+            entry.field_scores[0].label.text("de"),
+            "Hochrisiko-Eingriff"
+        );
+        assert_eq!(entry.field_scores[0].selection, None);
+
+        let mut current = serde_json::to_value(entry).unwrap();
+        let before = current.clone();
+        upgrade_bilingual_entry(&mut current);
+        assert_eq!(current, before);
+    }
+
+    #[test]
+    fn test_unversioned_settings_are_upgraded() {
+        let json = r#"{"theme":"Dark","language":"English","show_help_hints":true,"auto_calculate":false}"#;
+        let loaded: PersistedSettings = from_slice(json.as_bytes()).unwrap();
+        assert_eq!(loaded.theme, AppTheme::Dark);
+        assert_eq!(loaded.language, Language::English);
+    }
+
+    #[test]
+    fn test_newer_format_is_rejected() {
+        let json = r#"{"format_version":99,"entries":[]}"#;
+        assert!(matches!(
+            from_slice::<HistoryFile>(json.as_bytes()),
+            Err(MigrationError::TooNew {
+                found: 99,
+                supported: 3
+            })
+        ));
+        assert!(matches!(
+            from_slice::<HistoryFile>(br#"{"format_version":"2","entries":[]}"#),
+            Err(MigrationError::Failed { .. })
+        ));
+        assert!(matches!(
+            from_slice::<HistoryFile>(br#"{"rows":[]}"#),
+            Err(MigrationError::Failed { version: 1, .. })
+        ));
+    }
+}
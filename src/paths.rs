@@ -0,0 +1,169 @@
+// paths.rs
+// Location of the user data (settings, history, user scores), and writing files there
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Data folder next to the executable in portable mode
+pub const PORTABLE_DIR: &str = "klinscore-data";
+
+/// File next to the executable that turns on portable mode without `--portable`
+pub const PORTABLE_MARKER: &str = "klinscore.portable";
+
+/// Where the settings, the history and the user scores are kept
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum DataLocation {
+    /// The platform's data directory (e.g., `~/.local/share/klinscore` on Linux)
+    #[default]
+    Platform,
+    /// `klinscore-data` next to the executable, e.g., on a USB stick
+    Portable,
+    /// A folder chosen with `--data-dir`
+    Custom(PathBuf),
+}
+
+impl DataLocation {
+    /// Location requested on the command line (`--portable` or `--data-dir <folder>`);
+    /// without either, portable mode if the marker file is present
+    pub fn from_args(args: &[String], portable_marker: bool) -> Result<Self, String> {
+        let mut location = None;
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let requested = match arg.as_str() {
+                "--portable" => DataLocation::Portable,
+                "--data-dir" => match args.next() {
+                    Some(dir) if !dir.trim().is_empty() => DataLocation::Custom(PathBuf::from(dir)),
+                    _ => return Err("--data-dir needs a folder".to_string()),
+                },
+                other => return Err(format!("unknown argument: {}", other)),
+            };
+            if location.replace(requested).is_some() {
+                return Err("use either --portable or --data-dir, once".to_string());
+            }
+        }
+
+        Ok(location.unwrap_or(if portable_marker {
+            DataLocation::Portable
+        } else {
+            DataLocation::Platform
+        }))
+    }
+}
+
+static DATA_LOCATION: OnceLock<DataLocation> = OnceLock::new();
+
+/// Use `location` for the data of this run. Must be called before the data is
+/// first accessed; returns `false` (and changes nothing) if it was set already.
+pub fn set_data_location(location: DataLocation) -> bool {
+    DATA_LOCATION.set(location).is_ok()
+}
+
+/// Location of the data of this run
+pub fn data_location() -> &'static DataLocation {
+    DATA_LOCATION.get_or_init(DataLocation::default)
+}
+
+/// Data directory of this run; it may not exist yet
+pub fn data_dir() -> Option<PathBuf> {
+    match data_location() {
+        DataLocation::Platform => {
+            let proj_dirs = directories::ProjectDirs::from("org", "klinscore", "klinscore")?;
+            Some(proj_dirs.data_dir().to_path_buf())
+        }
+        DataLocation::Portable => Some(executable_dir()?.join(PORTABLE_DIR)),
+        DataLocation::Custom(dir) => Some(dir.clone()),
+    }
+}
+
+/// The user's documents folder, where exports go unless another folder is set
+pub fn documents_dir() -> Option<PathBuf> {
+    let user_dirs = directories::UserDirs::new()?;
+    Some(
+        user_dirs
+            .document_dir()
+            .unwrap_or(user_dirs.home_dir())
+            .to_path_buf(),
+    )
+}
+
+/// Path of the portable mode marker next to the executable
+pub fn portable_marker() -> Option<PathBuf> {
+    Some(executable_dir()?.join(PORTABLE_MARKER))
+}
+
+fn executable_dir() -> Option<PathBuf> {
+    std::env::current_exe()
+        .ok()?
+        .parent()
+        .map(Path::to_path_buf)
+}
+
+/// Write `contents` to `path` without ever leaving a partly written file: the
+/// data goes to a temporary file next to it, which then replaces `path`
+pub fn write_atomic(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    write_atomic_with(path, contents, OpenOptions::new())
+}
+
+/// [`write_atomic`] for secrets: on Unix, only the owner may read the file
+pub fn write_private(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    let mut options = OpenOptions::new();
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    write_atomic_with(path, contents, options)
+}
+
+fn write_atomic_with(
+    path: &Path,
+    contents: &[u8],
+    mut options: OpenOptions,
+) -> std::io::Result<()> {
+    let mut temp = path.as_os_str().to_owned();
+    temp.push(".tmp");
+    let temp = PathBuf::from(temp);
+    // A leftover temporary file would keep its permissions
+    match fs::remove_file(&temp) {
+        Err(error) if error.kind() != std::io::ErrorKind::NotFound => return Err(error),
+        _ => {}
+    }
+    let mut file = options.write(true).create_new(true).open(&temp)?;
+    file.write_all(contents)?;
+    file.sync_all()?;
+    drop(file);
+    fs::rename(&temp, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_data_location_from_args() {
+        assert_eq!(
+            DataLocation::from_args(&[], false),
+            Ok(DataLocation::Platform)
+        );
+        assert_eq!(
+            DataLocation::from_args(&[], true),
+            Ok(DataLocation::Portable)
+        );
+        assert_eq!(
+            DataLocation::from_args(&args(&["--portable"]), false),
+            Ok(DataLocation::Portable)
+        );
+        // An explicit folder wins over the marker
+        assert_eq!(
+            DataLocation::from_args(&args(&["--data-dir", "D:\\KlinScore"]), true),
+            Ok(DataLocation::Custom(PathBuf::from("D:\\KlinScore")))
+        );
+
+        assert!(DataLocation::from_args(&args(&["--data-dir"]), false).is_err());
+        assert!(DataLocation::from_args(&args(&["--portable", "--data-dir", "x"]), false).is_err());
+        assert!(DataLocation::from_args(&args(&["--verbose"]), false).is_err());
+    }
+}
@@ -1,17 +1,85 @@
 // persistence.rs
 // Save and load application state (settings, history) to disk
 
+use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
 
-use crate::settings::{AppTheme, Settings};
+use crate::backup;
+use crate::config::{Specialty, UnitSystem};
+use crate::export::deidentify::{ExportAnonymization, TimestampPrecision};
+use crate::history::{HistoryEntry, HistoryFilter};
+use crate::history_crypto::{HistoryCryptoError, HistoryKey};
+use crate::history_store::{
+    EncryptedHistoryStore, HistoryStore, HistoryStoreError, JsonHistoryStore, SqliteHistoryStore,
+};
+use crate::migration::{self, HistoryFile, MigrationError, Versioned};
+use crate::paths;
+use crate::scores::InputValue;
+use crate::settings::{AppTheme, CustomTheme, HistoryRetention, Settings};
 use crate::ui::Language;
 
-const APP_NAME: &str = "klinscore";
-const SETTINGS_FILE: &str = "settings.json";
+pub const SETTINGS_FILE: &str = "settings.json";
+/// Plain history of earlier versions, moved into the database on first start
 const HISTORY_FILE: &str = "history.json";
+pub const HISTORY_DATABASE: &str = "history.sqlite3";
+pub const ENCRYPTED_HISTORY_FILE: &str = "history.age";
+/// Screen and inputs of the last unfinished calculation
+const LAST_SCREEN_FILE: &str = "last_screen.json";
+const CORRUPT_SUFFIX: &str = "corrupt";
+const BACKUP_SUFFIX: &str = "bak";
+const MIGRATED_SUFFIX: &str = "migrated";
+
+/// A persisted file that failed validation on load and was moved aside
+#[derive(Debug, Clone)]
+pub struct CorruptFile {
+    /// Name of the original file (e.g., "history.json")
+    pub file_name: String,
+    /// Where the file was moved, or `None` if renaming failed
+    pub quarantined_to: Option<PathBuf>,
+    /// Parse/validation error
+    pub reason: String,
+    /// The previous version was restored from `<name>.bak`
+    pub restored_backup: bool,
+}
+
+/// Contents of a persisted file
+#[derive(Debug)]
+pub struct Loaded<T> {
+    /// `None` if nothing has been saved yet, or neither the file nor its
+    /// backup could be read
+    pub value: Option<T>,
+    /// The file was damaged and moved aside
+    pub corrupt: Option<CorruptFile>,
+}
+
+/// Where the user was when KlinScore was last closed, offered for restoring at
+/// the next start
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LastScreen {
+    pub specialty: Specialty,
+    /// Score being filled in; `None` if the score list of `specialty` was open
+    #[serde(default)]
+    pub score_id: Option<String>,
+    /// Values entered so far (empty with an encrypted history)
+    #[serde(default)]
+    pub inputs: HashMap<String, InputValue>,
+    #[serde(default)]
+    pub patient_label: Option<String>,
+}
+
+/// A settings file that could not be written or read
+#[derive(Debug, Error)]
+pub enum SettingsFileError {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("not a KlinScore settings file: {0}")]
+    Invalid(#[from] MigrationError),
+}
 
 /// Persistable settings (subset of Settings that should survive restarts)
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -20,6 +88,46 @@ pub struct PersistedSettings {
     pub language: Language,
     pub show_help_hints: bool,
     pub auto_calculate: bool,
+    #[serde(default)]
+    pub research_timestamp_precision: TimestampPrecision,
+    #[serde(default)]
+    pub extra_score_dirs: Vec<PathBuf>,
+    #[serde(default)]
+    pub strict_score_loading: bool,
+    #[serde(default)]
+    pub wizard_for_long_scores: bool,
+    #[serde(default)]
+    pub custom_theme: CustomTheme,
+    #[serde(default)]
+    pub touch_mode: bool,
+    #[serde(default)]
+    pub disable_patient_labels: bool,
+    #[serde(default)]
+    pub history_retention: HistoryRetention,
+    #[serde(default)]
+    pub export_dir: Option<PathBuf>,
+    #[serde(default)]
+    pub unit_system: UnitSystem,
+    #[serde(default)]
+    pub report_header: String,
+    #[serde(default)]
+    pub report_footer: String,
+    #[serde(default)]
+    pub bilingual_reports: bool,
+    #[serde(default)]
+    pub report_qr_code: bool,
+    #[serde(default)]
+    pub export_anonymization: ExportAnonymization,
+    #[serde(default)]
+    pub seal_exports: bool,
+    #[serde(default)]
+    pub sign_exports: bool,
+    #[serde(default)]
+    pub fhir_endpoint: String,
+    #[serde(default)]
+    pub log_file: bool,
+    #[serde(default)]
+    pub trusted_pack_keys: Vec<String>,
 }
 
 impl From<(&Settings, Language)> for PersistedSettings {
@@ -29,15 +137,68 @@ impl From<(&Settings, Language)> for PersistedSettings {
             language,
             show_help_hints: settings.show_help_hints,
             auto_calculate: settings.auto_calculate,
+            research_timestamp_precision: settings.research_timestamp_precision,
+            extra_score_dirs: settings.extra_score_dirs.clone(),
+            strict_score_loading: settings.strict_score_loading,
+            wizard_for_long_scores: settings.wizard_for_long_scores,
+            custom_theme: settings.custom_theme.clone(),
+            touch_mode: settings.touch_mode,
+            disable_patient_labels: settings.disable_patient_labels,
+            history_retention: settings.history_retention,
+            export_dir: settings.export_dir.clone(),
+            unit_system: settings.unit_system,
+            report_header: settings.report_header.clone(),
+            report_footer: settings.report_footer.clone(),
+            bilingual_reports: settings.bilingual_reports,
+            report_qr_code: settings.report_qr_code,
+            export_anonymization: settings.export_anonymization,
+            seal_exports: settings.seal_exports,
+            sign_exports: settings.sign_exports,
+            fhir_endpoint: settings.fhir_endpoint.clone(),
+            log_file: settings.log_file,
+            trusted_pack_keys: settings.trusted_pack_keys.clone(),
         }
     }
 }
 
+impl PersistedSettings {
+    /// Settings and UI language to start with
+    pub fn into_settings(self) -> (Settings, Language) {
+        let mut settings = Settings::new();
+        settings.theme = self.theme;
+        settings.show_help_hints = self.show_help_hints;
+        settings.auto_calculate = self.auto_calculate;
+        settings.research_timestamp_precision = self.research_timestamp_precision;
+        settings.extra_score_dirs = self.extra_score_dirs;
+        settings.strict_score_loading = self.strict_score_loading;
+        settings.wizard_for_long_scores = self.wizard_for_long_scores;
+        settings.custom_theme = self.custom_theme;
+        settings.touch_mode = self.touch_mode;
+        settings.disable_patient_labels = self.disable_patient_labels;
+        settings.history_retention = self.history_retention;
+        settings.export_dir = self.export_dir;
+        settings.unit_system = self.unit_system;
+        settings.report_header = self.report_header;
+        settings.report_footer = self.report_footer;
+        settings.bilingual_reports = self.bilingual_reports;
+        settings.report_qr_code = self.report_qr_code;
+        settings.export_anonymization = self.export_anonymization;
+        settings.seal_exports = self.seal_exports;
+        settings.sign_exports = self.sign_exports;
+        settings.fhir_endpoint = self.fhir_endpoint;
+        settings.log_file = self.log_file;
+        settings.trusted_pack_keys = self.trusted_pack_keys;
+        (settings, self.language)
+    }
+}
+
 /// Get the application data directory, creating it if needed
-fn data_dir() -> Option<PathBuf> {
-    let proj_dirs = directories::ProjectDirs::from("org", "klinscore", APP_NAME)?;
-    let dir = proj_dirs.data_dir().to_path_buf();
-    fs::create_dir_all(&dir).ok()?;
+pub fn data_dir() -> Option<PathBuf> {
+    let dir = paths::data_dir()?;
+    if let Err(e) = fs::create_dir_all(&dir) {
+        tracing::warn!(path = %dir.display(), "Cannot create the data folder: {}", e);
+        return None;
+    }
     Some(dir)
 }
 
@@ -46,43 +207,475 @@ pub fn save_settings(settings: &Settings, language: Language) {
     let Some(dir) = data_dir() else { return };
     let persisted = PersistedSettings::from((settings, language));
     let path = dir.join(SETTINGS_FILE);
-    if let Ok(json) = serde_json::to_string_pretty(&persisted) {
-        let _ = fs::write(path, json);
+    let saved = migration::to_string_pretty(&persisted)
+        .map_err(|e| e.to_string())
+        .and_then(|json| replace_with_backup(&path, json.as_bytes()).map_err(|e| e.to_string()));
+    if let Err(e) = saved {
+        tracing::warn!(path = %path.display(), "Could not save the settings: {}", e);
+    }
+}
+
+/// Load settings from disk.
+///
+/// A file that does not match the settings schema is quarantined and replaced
+/// by its backup, if that one is valid.
+pub fn load_settings() -> Loaded<PersistedSettings> {
+    let Some(dir) = data_dir() else {
+        return Loaded {
+            value: None,
+            corrupt: None,
+        };
+    };
+    load_json_with_backup(&dir.join(SETTINGS_FILE))
+}
+
+/// Remember the current screen, or forget it with `None`
+pub fn save_last_screen(screen: Option<&LastScreen>) {
+    let Some(dir) = data_dir() else { return };
+    let path = dir.join(LAST_SCREEN_FILE);
+    let saved = match screen {
+        Some(screen) => migration::to_string_pretty(screen)
+            .map_err(|e| e.to_string())
+            .and_then(|json| write_atomic(&path, json.as_bytes()).map_err(|e| e.to_string())),
+        None => remove_if_exists(&path).map_err(|e| e.to_string()),
+    };
+    if let Err(e) = saved {
+        tracing::warn!(path = %path.display(), "Could not save the last screen: {}", e);
     }
 }
 
-/// Load settings from disk
-pub fn load_settings() -> Option<PersistedSettings> {
+/// Screen remembered when KlinScore was last closed; a damaged file is set
+/// aside and nothing is offered
+pub fn load_last_screen() -> Option<LastScreen> {
     let dir = data_dir()?;
-    let path = dir.join(SETTINGS_FILE);
-    let data = fs::read_to_string(path).ok()?;
-    serde_json::from_str(&data).ok()
+    load_json_file(&dir.join(LAST_SCREEN_FILE)).ok().flatten()
+}
+
+pub use paths::write_atomic;
+
+/// Write `path` atomically, keeping its previous version as `<name>.bak`
+pub fn replace_with_backup(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    if path.exists() {
+        fs::copy(path, sibling(path, BACKUP_SUFFIX))?;
+    }
+    write_atomic(path, contents)
+}
+
+/// `<name>.<suffix>` next to `path`
+fn sibling(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_owned();
+    name.push(".");
+    name.push(suffix);
+    path.with_file_name(name)
+}
+
+/// Put the backup of a quarantined file in its place; `false` if there is none
+fn restore_backup(path: &Path) -> bool {
+    let backup = sibling(path, BACKUP_SUFFIX);
+    backup.exists() && fs::copy(&backup, path).is_ok()
+}
+
+/// How the history is written to disk
+#[derive(Debug, Clone, Default)]
+pub enum HistoryStorage {
+    /// SQLite database `history.sqlite3`
+    #[default]
+    Plain,
+    /// Encrypted with a passphrase in `history.age`
+    Encrypted(HistoryKey),
+    /// Encrypted history not unlocked yet; nothing is written, so the file stays intact
+    Locked,
 }
 
-/// Save history to disk
-pub fn save_history<T: Serialize>(history: &[T]) {
+/// Storage of the history on disk: `Locked` if an encrypted history exists
+pub fn history_storage() -> HistoryStorage {
+    match data_dir() {
+        Some(dir) => history_storage_in(&dir),
+        None => HistoryStorage::Plain,
+    }
+}
+
+fn history_storage_in(dir: &Path) -> HistoryStorage {
+    if dir.join(ENCRYPTED_HISTORY_FILE).exists() {
+        HistoryStorage::Locked
+    } else {
+        HistoryStorage::Plain
+    }
+}
+
+/// Store behind `storage`; `None` while the encrypted history is locked
+fn history_store_in(dir: &Path, storage: &HistoryStorage) -> Option<Box<dyn HistoryStore>> {
+    match storage {
+        HistoryStorage::Plain => Some(Box::new(SqliteHistoryStore::new(
+            dir.join(HISTORY_DATABASE),
+        ))),
+        HistoryStorage::Encrypted(key) => Some(Box::new(EncryptedHistoryStore::new(
+            dir.join(ENCRYPTED_HISTORY_FILE),
+            key.clone(),
+        ))),
+        HistoryStorage::Locked => None,
+    }
+}
+
+/// Write entries `added` to and `removed` from the history to disk. Entries
+/// another running instance saved meanwhile stay.
+pub fn save_history_changes(
+    added: &[HistoryEntry],
+    removed: &[HistoryEntry],
+    storage: &HistoryStorage,
+) {
     let Some(dir) = data_dir() else { return };
-    let path = dir.join(HISTORY_FILE);
-    if let Ok(json) = serde_json::to_string_pretty(history) {
-        let _ = fs::write(path, json);
+    let Some(store) = history_store_in(&dir, storage) else {
+        return;
+    };
+    // Retention may remove entries just added, so they are inserted first
+    if let Err(e) = store.insert(added).and_then(|()| store.remove(removed)) {
+        tracing::warn!(
+            added = added.len(),
+            removed = removed.len(),
+            "Could not save the history: {}",
+            e
+        );
     }
 }
 
-/// Load history from disk
-pub fn load_history<T: for<'de> Deserialize<'de>>() -> Vec<T> {
+fn save_history_in(
+    dir: &Path,
+    history: &[HistoryEntry],
+    storage: &HistoryStorage,
+) -> Result<(), HistoryStoreError> {
+    match history_store_in(dir, storage) {
+        Some(store) => store.save(history),
+        None => Ok(()),
+    }
+}
+
+/// Saved entries matching `filter`, oldest first; `None` while the history is
+/// locked or no data directory is available
+pub fn query_history(
+    filter: &HistoryFilter,
+    storage: &HistoryStorage,
+) -> Option<Result<Vec<HistoryEntry>, HistoryStoreError>> {
+    let store = history_store_in(&data_dir()?, storage)?;
+    Some(store.query(filter))
+}
+
+/// Decrypt the encrypted history with `passphrase`; returns the entries and
+/// the key for saving them again.
+///
+/// A damaged file is quarantined and replaced by its backup, if that one
+/// opens with the same passphrase.
+pub fn unlock_history(
+    passphrase: &str,
+) -> Result<(Loaded<Vec<HistoryEntry>>, HistoryKey), HistoryCryptoError> {
+    unlock_history_in(&require_data_dir()?, passphrase)
+}
+
+fn unlock_history_in(
+    dir: &Path,
+    passphrase: &str,
+) -> Result<(Loaded<Vec<HistoryEntry>>, HistoryKey), HistoryCryptoError> {
+    let path = dir.join(ENCRYPTED_HISTORY_FILE);
+    match unlock_file(&path, passphrase) {
+        Ok((history, key)) => Ok((
+            Loaded {
+                value: Some(history),
+                corrupt: None,
+            },
+            key,
+        )),
+        Err(HistoryCryptoError::InvalidFile(reason)) => {
+            let backup = sibling(&path, BACKUP_SUFFIX);
+            let (history, key) = unlock_file(&backup, passphrase)
+                .map_err(|_| HistoryCryptoError::InvalidFile(reason.clone()))?;
+            let mut corrupt = quarantine(&path, reason);
+            corrupt.restored_backup = restore_backup(&path);
+            Ok((
+                Loaded {
+                    value: Some(history),
+                    corrupt: Some(corrupt),
+                },
+                key,
+            ))
+        }
+        Err(e) => Err(e),
+    }
+}
+
+fn unlock_file(
+    path: &Path,
+    passphrase: &str,
+) -> Result<(Vec<HistoryEntry>, HistoryKey), HistoryCryptoError> {
+    let file = fs::read(path)?;
+    let (key, json) = HistoryKey::unlock(passphrase, &file)?;
+    let file: HistoryFile =
+        migration::from_slice(&json).map_err(|e| HistoryCryptoError::InvalidFile(e.to_string()))?;
+    Ok((file.entries, key))
+}
+
+/// Switch to encrypted storage: write `history.age`, then remove the plaintext
+/// database
+pub fn encrypt_history(
+    history: &[HistoryEntry],
+    passphrase: &str,
+) -> Result<HistoryKey, HistoryStoreError> {
+    encrypt_history_in(&require_data_dir()?, history, passphrase)
+}
+
+fn encrypt_history_in(
+    dir: &Path,
+    history: &[HistoryEntry],
+    passphrase: &str,
+) -> Result<HistoryKey, HistoryStoreError> {
+    let key = HistoryKey::new(passphrase)?;
+    let store = EncryptedHistoryStore::new(dir.join(ENCRYPTED_HISTORY_FILE), key.clone());
+    store.save(history)?;
+    // Check the new file opens before the plaintext copy is deleted
+    store.load()?;
+    // Including the backups and the file migrated from an earlier version
+    let database = dir.join(HISTORY_DATABASE);
+    let legacy = dir.join(HISTORY_FILE);
+    remove_database(&database)?;
+    for path in [
+        sibling(&database, BACKUP_SUFFIX),
+        legacy.clone(),
+        sibling(&legacy, BACKUP_SUFFIX),
+        sibling(&legacy, MIGRATED_SUFFIX),
+    ] {
+        remove_if_exists(&path)?;
+    }
+    backup::remove_plain_history_in(dir)?;
+    Ok(key)
+}
+
+/// Switch back to plaintext storage: write the database, then remove `history.age`
+pub fn decrypt_history(history: &[HistoryEntry]) -> Result<(), HistoryStoreError> {
+    decrypt_history_in(&require_data_dir()?, history)
+}
+
+fn decrypt_history_in(dir: &Path, history: &[HistoryEntry]) -> Result<(), HistoryStoreError> {
+    save_history_in(dir, history, &HistoryStorage::Plain)?;
+    let encrypted = dir.join(ENCRYPTED_HISTORY_FILE);
+    remove_if_exists(&encrypted)?;
+    remove_if_exists(&sibling(&encrypted, BACKUP_SUFFIX))?;
+    Ok(())
+}
+
+pub fn require_data_dir() -> std::io::Result<PathBuf> {
+    data_dir().ok_or_else(|| std::io::Error::other("no data directory available"))
+}
+
+pub fn remove_if_exists(path: &Path) -> std::io::Result<()> {
+    match fs::remove_file(path) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
+
+/// Remove an SQLite database with its write-ahead log
+pub fn remove_database(path: &Path) -> std::io::Result<()> {
+    remove_if_exists(path)?;
+    remove_journal(path)
+}
+
+/// Remove the write-ahead log of an SQLite database, which must not be applied
+/// to another database put in its place
+fn remove_journal(path: &Path) -> std::io::Result<()> {
+    for suffix in ["-wal", "-shm"] {
+        let mut file = path.as_os_str().to_owned();
+        file.push(suffix);
+        remove_if_exists(Path::new(&file))?;
+    }
+    Ok(())
+}
+
+/// Load history from disk.
+///
+/// A `history.json` of an earlier version is moved into the database first. A
+/// database that cannot be read is quarantined and replaced by the copy taken
+/// at the previous start (`history.sqlite3.bak`).
+pub fn load_history() -> Loaded<Vec<HistoryEntry>> {
     let Some(dir) = data_dir() else {
-        return Vec::new();
+        return Loaded {
+            value: None,
+            corrupt: None,
+        };
     };
-    let path = dir.join(HISTORY_FILE);
-    let Ok(data) = fs::read_to_string(path) else {
-        return Vec::new();
+    load_history_in(&dir)
+}
+
+fn load_history_in(dir: &Path) -> Loaded<Vec<HistoryEntry>> {
+    let database = dir.join(HISTORY_DATABASE);
+    let store = SqliteHistoryStore::new(&database);
+    if !database.exists() {
+        if let Some(migration) = migrate_legacy_history(dir, &store) {
+            return migration;
+        }
+    }
+
+    let loaded = match store.load() {
+        Ok(history) => Loaded {
+            value: Some(history),
+            corrupt: None,
+        },
+        Err(e) => {
+            let mut corrupt = quarantine(&database, e.to_string());
+            let value = restore_backup(&database)
+                .then(|| store.load().ok())
+                .flatten();
+            corrupt.restored_backup = value.is_some();
+            Loaded {
+                value,
+                corrupt: Some(corrupt),
+            }
+        }
     };
-    serde_json::from_str(&data).unwrap_or_default()
+    // A copy of the database as it was at this start, for the next recovery
+    if loaded.value.is_some() {
+        if let Err(e) = store.backup_to(&sibling(&database, BACKUP_SUFFIX)) {
+            tracing::warn!("Could not back up the history database: {}", e);
+        }
+    }
+    loaded
+}
+
+/// Move the `history.json` of an earlier version into the database; `None` if
+/// there is nothing to migrate
+fn migrate_legacy_history(
+    dir: &Path,
+    store: &SqliteHistoryStore,
+) -> Option<Loaded<Vec<HistoryEntry>>> {
+    let legacy = dir.join(HISTORY_FILE);
+    let loaded = load_json_with_backup::<HistoryFile>(&legacy);
+    let loaded = Loaded {
+        value: loaded.value.map(|file| file.entries),
+        corrupt: loaded.corrupt,
+    };
+    let Some(history) = &loaded.value else {
+        // Nothing to migrate, or nothing readable
+        return loaded.corrupt.is_some().then_some(loaded);
+    };
+    if let Err(e) = store.save(history) {
+        return Some(Loaded {
+            value: None,
+            corrupt: Some(quarantine(&legacy, e.to_string())),
+        });
+    }
+    // Kept next to the database until the migration is known to be good
+    match fs::rename(&legacy, sibling(&legacy, MIGRATED_SUFFIX)) {
+        Ok(()) => tracing::info!(
+            entries = history.len(),
+            "Moved the history into the database"
+        ),
+        Err(e) => tracing::warn!(
+            path = %legacy.display(),
+            "History moved into the database, but the old file was not renamed: {}",
+            e
+        ),
+    }
+    Some(loaded)
+}
+
+/// Read a history file of another installation (e.g., `history.json` copied
+/// from another workstation)
+pub fn read_history_file(path: &Path) -> Result<Vec<HistoryEntry>, HistoryStoreError> {
+    JsonHistoryStore::new(path).load()
+}
+
+/// Write the history as JSON, for moving it to another workstation
+pub fn write_history_file(path: &Path, history: &[HistoryEntry]) -> Result<(), HistoryStoreError> {
+    JsonHistoryStore::new(path).save(history)
+}
+
+/// Write the settings as JSON, for applying them on other workstations
+pub fn write_settings_file(
+    path: &Path,
+    settings: &PersistedSettings,
+) -> Result<(), SettingsFileError> {
+    let json = migration::to_string_pretty(settings)?;
+    write_atomic(path, json.as_bytes())?;
+    Ok(())
+}
+
+/// Read a settings file written by `write_settings_file` (or the `settings.json`
+/// of another installation)
+pub fn read_settings_file(path: &Path) -> Result<PersistedSettings, SettingsFileError> {
+    Ok(migration::from_slice(&fs::read(path)?)?)
+}
+
+/// Read and deserialize a JSON file, quarantining it if it is unreadable or invalid
+fn load_json_file<T: Versioned>(path: &Path) -> Result<Option<T>, CorruptFile> {
+    let bytes = match fs::read(path) {
+        Ok(bytes) => bytes,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(quarantine(path, e.to_string())),
+    };
+
+    migration::from_slice(&bytes)
+        .map(Some)
+        .map_err(|e| quarantine(path, e.to_string()))
+}
+
+/// Read a JSON file; if it is damaged, fall back to its backup
+fn load_json_with_backup<T: Versioned>(path: &Path) -> Loaded<T> {
+    match load_json_file(path) {
+        Ok(value) => Loaded {
+            value,
+            corrupt: None,
+        },
+        Err(mut corrupt) => {
+            let backup: Option<T> = fs::read(sibling(path, BACKUP_SUFFIX))
+                .ok()
+                .and_then(|bytes| migration::from_slice(&bytes).ok());
+            corrupt.restored_backup = backup.is_some() && restore_backup(path);
+            Loaded {
+                value: backup,
+                corrupt: Some(corrupt),
+            }
+        }
+    }
+}
+
+/// Move a corrupt file aside (`<name>.corrupt`) so it is not overwritten by
+/// the next save and can be inspected or recovered manually
+fn quarantine(path: &Path, reason: String) -> CorruptFile {
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    tracing::warn!(path = %path.display(), "Damaged file: {}", reason);
+    let target = sibling(path, CORRUPT_SUFFIX);
+    let quarantined_to = match fs::rename(path, &target) {
+        Ok(()) => Some(target),
+        Err(e) => {
+            tracing::warn!(path = %path.display(), "Could not move the damaged file aside: {}", e);
+            None
+        }
+    };
+    if let Err(e) = remove_journal(path) {
+        tracing::warn!(path = %path.display(), "Could not remove the database journal: {}", e);
+    }
+
+    CorruptFile {
+        file_name,
+        quarantined_to,
+        reason,
+        restored_backup: false,
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::Specialty;
+    use crate::history::tests::entry;
+
+    fn score_ids(history: &[HistoryEntry]) -> Vec<&str> {
+        history
+            .iter()
+            .map(|entry| entry.score_id.as_str())
+            .collect()
+    }
 
     #[test]
     fn test_persisted_settings_roundtrip() {
@@ -92,6 +685,134 @@ mod tests {
         let loaded: PersistedSettings = serde_json::from_str(&json).unwrap();
         assert_eq!(loaded.language, Language::German);
         assert_eq!(loaded.theme, AppTheme::Light);
+        assert_eq!(
+            loaded.research_timestamp_precision,
+            TimestampPrecision::Date
+        );
+    }
+
+    #[test]
+    fn test_persisted_settings_without_research_precision() {
+        // Settings files written before research exports existed must still load
+        let json = r#"{"theme":"Dark","language":"English","show_help_hints":true,"auto_calculate":false}"#;
+        let loaded: PersistedSettings = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            loaded.research_timestamp_precision,
+            TimestampPrecision::Date
+        );
+        assert!(loaded.extra_score_dirs.is_empty());
+        assert!(!loaded.strict_score_loading);
+        assert!(!loaded.wizard_for_long_scores);
+        assert!(!loaded.touch_mode);
+        assert!(!loaded.disable_patient_labels);
+        assert_eq!(loaded.history_retention, HistoryRetention::KeepAll);
+        assert_eq!(loaded.export_dir, None);
+        assert!(!loaded.log_file);
+    }
+
+    #[test]
+    fn test_persisted_settings_keeps_score_dirs() {
+        let mut settings = Settings::new();
+        settings
+            .extra_score_dirs
+            .push(PathBuf::from("/srv/klinik/scores"));
+        settings.history_retention = HistoryRetention::LastDays(30);
+        settings.export_dir = Some(PathBuf::from("/home/arzt/Exporte"));
+        let persisted = PersistedSettings::from((&settings, Language::English));
+        let json = serde_json::to_string(&persisted).unwrap();
+        let loaded: PersistedSettings = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            loaded.extra_score_dirs,
+            vec![PathBuf::from("/srv/klinik/scores")]
+        );
+        assert_eq!(loaded.history_retention, HistoryRetention::LastDays(30));
+        assert_eq!(loaded.export_dir, Some(PathBuf::from("/home/arzt/Exporte")));
+    }
+
+    #[test]
+    fn test_last_screen_roundtrip() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join(LAST_SCREEN_FILE);
+        let screen = LastScreen {
+            specialty: Specialty::Cardiology,
+            score_id: Some("grace".to_string()),
+            inputs: HashMap::from([("age".to_string(), InputValue::Number(71.0))]),
+            patient_label: Some("Bett 12".to_string()),
+        };
+        fs::write(&path, migration::to_string_pretty(&screen).unwrap()).unwrap();
+        assert_eq!(load_json_file::<LastScreen>(&path).unwrap(), Some(screen));
+
+        // A damaged file is set aside rather than offered
+        fs::write(&path, r#"{"specialty": "Cardiology", "inputs": 3}"#).unwrap();
+        assert!(load_json_file::<LastScreen>(&path).is_err());
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_settings_file_roundtrip() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("klinscore_settings.json");
+        let mut settings = Settings::new();
+        settings.theme = AppTheme::Sepia;
+        settings.touch_mode = true;
+        settings
+            .extra_score_dirs
+            .push(PathBuf::from("/srv/klinik/scores"));
+        write_settings_file(
+            &path,
+            &PersistedSettings::from((&settings, Language::Italian)),
+        )
+        .unwrap();
+
+        let (imported, language) = read_settings_file(&path).unwrap().into_settings();
+        assert_eq!(language, Language::Italian);
+        assert_eq!(imported.theme, AppTheme::Sepia);
+        assert!(imported.touch_mode);
+        assert_eq!(imported.extra_score_dirs, settings.extra_score_dirs);
+
+        // A history file is not a settings file
+        let history = vec![entry("egfr", Specialty::Nephrology, "2026-02-01 09:30")];
+        write_history_file(&path, &history).unwrap();
+        assert!(matches!(
+            read_settings_file(&path),
+            Err(SettingsFileError::Invalid(_))
+        ));
+    }
+
+    #[test]
+    fn test_load_json_file_missing() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let loaded: Result<Option<PersistedSettings>, _> =
+            load_json_file(&temp_dir.path().join(SETTINGS_FILE));
+        assert!(matches!(loaded, Ok(None)));
+    }
+
+    #[test]
+    fn test_corrupt_file_is_quarantined() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join(HISTORY_FILE);
+        fs::write(&path, "[{\"score_name\": \"HAS-BLED\"").unwrap();
+
+        let loaded: Result<Option<HistoryFile>, _> = load_json_file(&path);
+        let corrupt = loaded.unwrap_err();
+
+        assert_eq!(corrupt.file_name, HISTORY_FILE);
+        assert!(!path.exists());
+        let quarantined = corrupt.quarantined_to.unwrap();
+        assert_eq!(quarantined, temp_dir.path().join("history.json.corrupt"));
+        assert!(quarantined.exists());
+    }
+
+    #[test]
+    fn test_schema_mismatch_is_quarantined() {
+        // Valid JSON that does not match the settings schema
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join(SETTINGS_FILE);
+        fs::write(&path, r#"{"theme":"Neon"}"#).unwrap();
+
+        let loaded: Result<Option<PersistedSettings>, _> = load_json_file(&path);
+        assert!(loaded.is_err());
+        assert!(temp_dir.path().join("settings.json.corrupt").exists());
     }
 
     #[test]
@@ -103,4 +824,140 @@ mod tests {
             assert!(d.exists());
         }
     }
+
+    #[test]
+    fn test_history_encryption_migration() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        let history = vec![
+            entry("egfr", Specialty::Nephrology, "2026-02-01 09:30"),
+            entry("kfre", Specialty::Nephrology, "2026-02-01 09:35"),
+        ];
+        save_history_in(dir, &history, &HistoryStorage::Plain).unwrap();
+        assert!(matches!(history_storage_in(dir), HistoryStorage::Plain));
+
+        // Plaintext database is replaced by the encrypted file
+        load_history_in(dir);
+        let key = encrypt_history_in(dir, &history, "Stationszimmer 3").unwrap();
+        assert!(!dir.join(HISTORY_DATABASE).exists());
+        assert!(!dir.join("history.sqlite3.bak").exists());
+        assert!(matches!(history_storage_in(dir), HistoryStorage::Locked));
+
+        // Nothing is written while locked
+        let encrypted = fs::read(dir.join(ENCRYPTED_HISTORY_FILE)).unwrap();
+        save_history_in(dir, &[], &HistoryStorage::Locked).unwrap();
+        assert_eq!(
+            fs::read(dir.join(ENCRYPTED_HISTORY_FILE)).unwrap(),
+            encrypted
+        );
+
+        let mut unlocked = unlock_history_in(dir, "Stationszimmer 3")
+            .unwrap()
+            .0
+            .value
+            .unwrap();
+        assert_eq!(score_ids(&unlocked), ["egfr", "kfre"]);
+        assert!(matches!(
+            unlock_history_in(dir, "falsch"),
+            Err(HistoryCryptoError::WrongPassphrase)
+        ));
+
+        unlocked.push(entry("asa", Specialty::Anesthesiology, "2026-02-02 07:00"));
+        save_history_in(dir, &unlocked, &HistoryStorage::Encrypted(key)).unwrap();
+        let (reloaded, _) = unlock_history_in(dir, "Stationszimmer 3").unwrap();
+        let reloaded = reloaded.value.unwrap();
+        assert_eq!(reloaded.len(), 3);
+
+        // And back to plaintext
+        decrypt_history_in(dir, &reloaded).unwrap();
+        assert!(!dir.join(ENCRYPTED_HISTORY_FILE).exists());
+        let plain = load_history_in(dir).value.unwrap();
+        assert_eq!(score_ids(&plain), ["egfr", "kfre", "asa"]);
+    }
+
+    #[test]
+    fn test_legacy_history_is_migrated() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        let history = vec![
+            entry("has_bled", Specialty::Cardiology, "2026-01-10 08:00"),
+            entry("rcri", Specialty::Anesthesiology, "2026-02-01 09:30"),
+        ];
+        write_history_file(&dir.join(HISTORY_FILE), &history).unwrap();
+
+        let loaded = load_history_in(dir).value.unwrap();
+        assert_eq!(score_ids(&loaded), ["has_bled", "rcri"]);
+        assert!(dir.join(HISTORY_DATABASE).exists());
+        assert!(!dir.join(HISTORY_FILE).exists());
+        assert!(dir.join("history.json.migrated").exists());
+
+        // Later starts read the database
+        assert_eq!(load_history_in(dir).value.unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_corrupt_database_is_quarantined() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        fs::write(dir.join(HISTORY_DATABASE), "not a database, just text").unwrap();
+
+        let loaded = load_history_in(dir);
+        let corrupt = loaded.corrupt.unwrap();
+        assert_eq!(corrupt.file_name, HISTORY_DATABASE);
+        assert!(!corrupt.restored_backup);
+        assert!(loaded.value.is_none());
+        assert!(dir.join("history.sqlite3.corrupt").exists());
+        // The next start begins with an empty database
+        assert!(load_history_in(dir).value.unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_write_keeps_backup() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join(SETTINGS_FILE);
+        replace_with_backup(&path, b"first").unwrap();
+        assert!(!sibling(&path, BACKUP_SUFFIX).exists());
+        replace_with_backup(&path, b"second").unwrap();
+
+        assert_eq!(fs::read(&path).unwrap(), b"second");
+        assert_eq!(fs::read(sibling(&path, BACKUP_SUFFIX)).unwrap(), b"first");
+        assert!(!sibling(&path, "tmp").exists());
+    }
+
+    #[test]
+    fn test_settings_restored_from_backup() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join(SETTINGS_FILE);
+        let persisted = PersistedSettings::from((&Settings::new(), Language::English));
+        let json = serde_json::to_string(&persisted).unwrap();
+        replace_with_backup(&path, json.as_bytes()).unwrap();
+        // Torn write of the next version
+        replace_with_backup(&path, &json.as_bytes()[..json.len() / 2]).unwrap();
+
+        let loaded: Loaded<PersistedSettings> = load_json_with_backup(&path);
+        assert_eq!(loaded.value.unwrap().language, Language::English);
+        let corrupt = loaded.corrupt.unwrap();
+        assert!(corrupt.restored_backup);
+        assert!(temp_dir.path().join("settings.json.corrupt").exists());
+        // The backup is the settings file again
+        let reloaded: Loaded<PersistedSettings> = load_json_with_backup(&path);
+        assert!(reloaded.value.is_some() && reloaded.corrupt.is_none());
+    }
+
+    #[test]
+    fn test_database_restored_from_backup() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        let history = vec![entry("egfr", Specialty::Nephrology, "2026-02-01 09:30")];
+        save_history_in(dir, &history, &HistoryStorage::Plain).unwrap();
+        // Each start keeps a copy of the database
+        assert_eq!(load_history_in(dir).value.unwrap().len(), 1);
+        assert!(dir.join("history.sqlite3.bak").exists());
+
+        fs::write(dir.join(HISTORY_DATABASE), "overwritten by a crash").unwrap();
+        let loaded = load_history_in(dir);
+        assert!(loaded.corrupt.unwrap().restored_backup);
+        assert_eq!(score_ids(&loaded.value.unwrap()), ["egfr"]);
+        assert_eq!(load_history_in(dir).value.unwrap().len(), 1);
+    }
 }
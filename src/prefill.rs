@@ -0,0 +1,437 @@
+// prefill.rs
+// Prefill score inputs from a FHIR R4 server: the latest Observation for each
+// input's LOINC codes (see `InputField::loinc`), and age, date of birth and sex from
+// the Patient resource, so lab values and vitals are not transcribed by hand
+//
+// Values are only taken when their unit is the field's unit or its conventional
+// unit (for durations: minutes, hours or days), and Observations only when they are
+// at most `MAX_OBSERVATION_AGE_DAYS` old; anything else is left for the user to enter.
+
+use crate::config::{InputField, InputType, LocalizedText, ScoreDefinition};
+use crate::scores::InputValue;
+use chrono::{Days, NaiveDate};
+use serde_json::Value;
+use std::time::Duration;
+use thiserror::Error;
+
+/// LOINC code of the patient's age, filled from `Patient.birthDate`
+pub const AGE_LOINC: &str = "30525-0";
+
+/// LOINC code of the patient's date of birth, filled from `Patient.birthDate`
+pub const BIRTH_DATE_LOINC: &str = "21112-8";
+
+/// LOINC code of the patient's sex, filled from `Patient.gender`
+pub const SEX_LOINC: &str = "46098-0";
+
+/// Observations fetched per request, newest first
+const OBSERVATION_COUNT: &str = "200";
+
+/// Age in days of the oldest Observation taken; older values no longer describe
+/// the patient's current state
+pub const MAX_OBSERVATION_AGE_DAYS: u64 = 30;
+
+const TIMEOUT: Duration = Duration::from_secs(15);
+
+#[derive(Debug, Error)]
+pub enum PrefillError {
+    #[error("Invalid patient ID '{0}'")]
+    InvalidPatientId(String),
+
+    #[error("FHIR server request failed: {0}")]
+    Request(String),
+
+    #[error("Invalid response from the FHIR server: {0}")]
+    InvalidResponse(String),
+}
+
+/// An input value taken from the patient's record
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrefilledValue {
+    pub field: String,
+    /// Value in the field's (SI) unit
+    pub value: InputValue,
+    /// LOINC code the value was found under
+    pub code: String,
+    /// Date of the Observation (`None` for Patient data)
+    pub date: Option<String>,
+}
+
+/// Fetch the patient's data from the server at `endpoint` and prefill the inputs of
+/// `score` that declare LOINC codes
+pub fn prefill_from_server(
+    endpoint: &str,
+    patient_id: &str,
+    score: &ScoreDefinition,
+    today: NaiveDate,
+) -> Result<Vec<PrefilledValue>, PrefillError> {
+    let patient_id = patient_id.trim();
+    // FHIR resource IDs; anything else would change the request URL
+    if patient_id.is_empty()
+        || patient_id.len() > 64
+        || !patient_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '.')
+    {
+        return Err(PrefillError::InvalidPatientId(patient_id.to_string()));
+    }
+    let base = endpoint.trim().trim_end_matches('/');
+    let agent = ureq::AgentBuilder::new().timeout(TIMEOUT).build();
+
+    let patient = get_json(agent.get(&format!("{}/Patient/{}", base, patient_id)))?;
+
+    let codes: Vec<String> = score
+        .inputs
+        .iter()
+        .flat_map(|input| &input.loinc)
+        .filter(|code| !matches!(code.as_str(), AGE_LOINC | BIRTH_DATE_LOINC | SEX_LOINC))
+        .map(|code| format!("http://loinc.org|{}", code))
+        .collect();
+    let observations = if codes.is_empty() {
+        Vec::new()
+    } else {
+        let bundle = get_json(
+            agent
+                .get(&format!("{}/Observation", base))
+                .query("patient", patient_id)
+                // Matches the code of an Observation or of one of its components
+                // (e.g., systolic pressure in a blood pressure panel)
+                .query("combo-code", &codes.join(","))
+                .query("date", &format!("ge{}", oldest_observation_date(today)))
+                .query("_sort", "-date")
+                .query("_count", OBSERVATION_COUNT),
+        )?;
+        bundle["entry"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(|entry| entry["resource"].clone())
+            .collect()
+    };
+
+    Ok(prefill_values(score, &patient, &observations, today))
+}
+
+fn get_json(request: ureq::Request) -> Result<Value, PrefillError> {
+    request
+        .set("Accept", "application/fhir+json")
+        .call()
+        .map_err(|e| PrefillError::Request(e.to_string()))?
+        .into_json()
+        .map_err(|e| PrefillError::InvalidResponse(e.to_string()))
+}
+
+/// Input values of `score` from a Patient resource and Observations
+///
+/// Each input takes the newest usable Observation of its first LOINC code that has
+/// one. Observations entered in error or cancelled are ignored, and so are those
+/// older than [`MAX_OBSERVATION_AGE_DAYS`] or without a date.
+pub fn prefill_values(
+    score: &ScoreDefinition,
+    patient: &Value,
+    observations: &[Value],
+    today: NaiveDate,
+) -> Vec<PrefilledValue> {
+    let mut values = Vec::new();
+    for input in &score.inputs {
+        let found = input.loinc.iter().find_map(|code| {
+            let value = match code.as_str() {
+                AGE_LOINC => patient_age(patient, today).map(|age| (InputValue::Number(age), None)),
+                BIRTH_DATE_LOINC => patient["birthDate"]
+                    .as_str()
+                    .and_then(|date| date.parse().ok())
+                    .map(|date| (InputValue::Date(date), None)),
+                SEX_LOINC => patient["gender"]
+                    .as_str()
+                    .and_then(|gender| dropdown_value(input, gender))
+                    .map(|value| (value, None)),
+                _ => latest_observation(input, code, observations, today),
+            };
+            value.map(|(value, date)| PrefilledValue {
+                field: input.field.clone(),
+                value,
+                code: code.clone(),
+                date,
+            })
+        });
+        values.extend(found);
+    }
+    values
+}
+
+/// Date of the oldest Observation taken as of `today`
+fn oldest_observation_date(today: NaiveDate) -> NaiveDate {
+    today
+        .checked_sub_days(Days::new(MAX_OBSERVATION_AGE_DAYS))
+        .unwrap_or(NaiveDate::MIN)
+}
+
+/// Full years from `Patient.birthDate` to `today`
+fn patient_age(patient: &Value, today: NaiveDate) -> Option<f64> {
+    let birth_date = patient["birthDate"].as_str()?;
+    let birth_date = NaiveDate::parse_from_str(birth_date, "%Y-%m-%d").ok()?;
+    today.years_since(birth_date).map(f64::from)
+}
+
+/// Value and date of the newest Observation (or component) coded `code` that
+/// `input` can take, if it is recent enough as of `today`
+fn latest_observation(
+    input: &InputField,
+    code: &str,
+    observations: &[Value],
+    today: NaiveDate,
+) -> Option<(InputValue, Option<String>)> {
+    let oldest = oldest_observation_date(today);
+    observations
+        .iter()
+        .filter(|observation| {
+            !matches!(
+                observation["status"].as_str(),
+                Some("entered-in-error" | "cancelled")
+            )
+        })
+        .filter_map(|observation| {
+            let date = observation["effectiveDateTime"]
+                .as_str()
+                .or_else(|| observation["effectivePeriod"]["start"].as_str())
+                .or_else(|| observation["issued"].as_str())?;
+            // Date and time, or only the date
+            let day: NaiveDate = date.get(..10)?.parse().ok()?;
+            if day < oldest {
+                return None;
+            }
+            let element = std::iter::once(observation)
+                .chain(observation["component"].as_array().into_iter().flatten())
+                .find(|element| has_code(element, code))?;
+            Some((observation_value(input, element)?, Some(date.to_string())))
+        })
+        // ISO dates compare in time order
+        .max_by(|(_, a), (_, b)| a.cmp(b))
+}
+
+fn has_code(element: &Value, code: &str) -> bool {
+    element["code"]["coding"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .any(|coding| {
+            coding["system"].as_str() == Some("http://loinc.org")
+                && coding["code"].as_str() == Some(code)
+        })
+}
+
+/// The value of an Observation or component as an input of `input`'s type
+fn observation_value(input: &InputField, element: &Value) -> Option<InputValue> {
+    match input.input_type {
+        InputType::Number => {
+            let quantity = &element["valueQuantity"];
+            let value = quantity["value"].as_f64()?;
+            let factor = unit_factor(input, quantity)?;
+            Some(InputValue::Number(value * factor))
+        }
+        InputType::Boolean => element["valueBoolean"].as_bool().map(InputValue::Boolean),
+        InputType::Dropdown => element["valueCodeableConcept"]["coding"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|coding| coding["code"].as_str())
+            .chain(element["valueString"].as_str())
+            .find_map(|value| dropdown_value(input, value)),
+        // The day of a date and time (e.g., of symptom onset)
+        InputType::Date => element["valueDateTime"]
+            .as_str()
+            .and_then(|value| value.get(..10)?.parse().ok())
+            .map(InputValue::Date),
+        // A quantity of time in UCUM units (e.g., minutes of surgery)
+        InputType::Duration => {
+            let quantity = &element["valueQuantity"];
+            let value = quantity["value"].as_f64()?;
+            let minutes = match quantity["code"].as_str()? {
+                "min" => 1.0,
+                "h" => 60.0,
+                "d" => 1440.0,
+                _ => return None,
+            };
+            Some(InputValue::Number(
+                value * minutes / input.duration_unit?.minutes(),
+            ))
+        }
+    }
+}
+
+fn dropdown_value(input: &InputField, value: &str) -> Option<InputValue> {
+    input
+        .options
+        .iter()
+        .find(|option| option.value == value)
+        .map(|option| InputValue::Dropdown(option.value.clone()))
+}
+
+/// Factor from the quantity's unit to the field's unit: 1 for the field's unit,
+/// the conventional unit's factor for that unit, `None` for any other unit
+///
+/// Fields without a unit take any quantity.
+fn unit_factor(input: &InputField, quantity: &Value) -> Option<f64> {
+    let Some(unit) = &input.unit else {
+        return Some(1.0);
+    };
+    let units: Vec<String> = ["code", "unit"]
+        .into_iter()
+        .filter_map(|key| quantity[key].as_str())
+        .map(normalize_unit)
+        .collect();
+    let matches = |labels: &LocalizedText| {
+        labels
+            .values()
+            .any(|label| units.contains(&normalize_unit(label)))
+    };
+    if matches(unit) {
+        return Some(1.0);
+    }
+    input
+        .conventional_unit
+        .as_ref()
+        .filter(|conventional| matches(&conventional.unit))
+        .map(|conventional| conventional.factor)
+}
+
+/// Unit in a comparable form: UCUM codes ("umol/L", "mm[Hg]", "mL/min/{1.73_m2}",
+/// "/min") and the labels of score files ("μmol/L", "mmHg", "mL/min/1.73m²", "bpm")
+/// become the same
+fn normalize_unit(unit: &str) -> String {
+    let unit: String = unit
+        .chars()
+        .filter(|c| !c.is_whitespace() && !matches!(c, '[' | ']' | '{' | '}' | '_'))
+        .map(|c| match c {
+            'µ' | 'μ' => 'u',
+            '²' => '2',
+            c => c,
+        })
+        .flat_map(char::to_lowercase)
+        .collect();
+    match unit.as_str() {
+        "/min" | "{beats}/min" | "beats/min" | "beats/minute" => "bpm".to_string(),
+        "a" | "year" => "years".to_string(),
+        _ => unit,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scores::load_bundled_scores;
+    use serde_json::json;
+
+    fn observation(code: &str, date: &str, value: Value) -> Value {
+        json!({
+            "resourceType": "Observation",
+            "status": "final",
+            "code": {"coding": [{"system": "http://loinc.org", "code": code}]},
+            "effectiveDateTime": date,
+            "valueQuantity": value
+        })
+    }
+
+    #[test]
+    fn test_prefill_values() {
+        let library = load_bundled_scores();
+        let egfr = library.get_score("egfr_ckd_epi_2021").unwrap();
+        let patient =
+            json!({"resourceType": "Patient", "birthDate": "1960-06-15", "gender": "female"});
+        let today = NaiveDate::from_ymd_opt(2025, 6, 14).unwrap();
+        let mut observations = vec![
+            observation(
+                "2160-0",
+                "2025-05-20",
+                json!({"value": 1.0, "unit": "mg/dL"}),
+            ),
+            observation(
+                "2160-0",
+                "2025-06-02",
+                json!({"value": 1.5, "unit": "mg/dL", "code": "mg/dL"}),
+            ),
+            // Newer, but in a unit the field does not know
+            observation(
+                "2160-0",
+                "2025-06-10",
+                json!({"value": 0.13, "unit": "mmol/L"}),
+            ),
+        ];
+
+        let values = prefill_values(egfr, &patient, &observations, today);
+        let value = |field: &str| {
+            values
+                .iter()
+                .find(|value| value.field == field)
+                .map(|value| value.value.clone())
+        };
+        assert_eq!(value("age"), Some(InputValue::Number(64.0)));
+        assert_eq!(
+            value("sex"),
+            Some(InputValue::Dropdown("female".to_string()))
+        );
+        assert_eq!(value("creatinine"), Some(InputValue::Number(1.5 * 88.4)));
+        let creatinine = values.iter().find(|v| v.field == "creatinine").unwrap();
+        assert_eq!(creatinine.date.as_deref(), Some("2025-06-02"));
+
+        observations[1]["status"] = json!("entered-in-error");
+        observations.push(observation(
+            "14682-9",
+            "2025-06-12",
+            json!({"value": 97, "unit": "µmol/L", "code": "umol/L"}),
+        ));
+        let values = prefill_values(egfr, &patient, &observations, today);
+        let creatinine = values.iter().find(|v| v.field == "creatinine").unwrap();
+        // The first code with a usable Observation wins over newer ones of later codes
+        assert_eq!(creatinine.code, "2160-0");
+        assert_eq!(creatinine.value, InputValue::Number(88.4));
+
+        // Values older than the maximum age, or undated, are left for the user
+        let stale = [
+            observation(
+                "2160-0",
+                "2025-05-14T23:59:00Z",
+                json!({"value": 1.0, "unit": "mg/dL"}),
+            ),
+            observation("2160-0", "", json!({"value": 1.0, "unit": "mg/dL"})),
+        ];
+        let values = prefill_values(egfr, &patient, &stale, today);
+        assert!(values.iter().all(|value| value.field != "creatinine"));
+        let recent = observation(
+            "2160-0",
+            "2025-05-15",
+            json!({"value": 1.0, "unit": "mg/dL"}),
+        );
+        let values = prefill_values(egfr, &patient, &[recent], today);
+        assert!(values.iter().any(|value| value.field == "creatinine"));
+
+        // Systolic pressure as a component of a blood pressure panel
+        let grace = library.get_score("grace").unwrap();
+        let panel = json!({
+            "resourceType": "Observation",
+            "status": "final",
+            "code": {"coding": [{"system": "http://loinc.org", "code": "85354-9"}]},
+            "effectiveDateTime": "2025-06-14T08:30:00Z",
+            "component": [
+                {"code": {"coding": [{"system": "http://loinc.org", "code": "8480-6"}]},
+                 "valueQuantity": {"value": 128, "unit": "mmHg", "code": "mm[Hg]"}},
+                {"code": {"coding": [{"system": "http://loinc.org", "code": "8462-4"}]},
+                 "valueQuantity": {"value": 82, "unit": "mmHg", "code": "mm[Hg]"}}
+            ]
+        });
+        let values = prefill_values(grace, &json!({}), &[panel], today);
+        assert_eq!(values.len(), 1);
+        assert_eq!(values[0].field, "systolic_bp");
+        assert_eq!(values[0].value, InputValue::Number(128.0));
+    }
+
+    #[test]
+    fn test_invalid_patient_id() {
+        let library = load_bundled_scores();
+        let score = library.get_score("egfr_ckd_epi_2021").unwrap();
+        let today = NaiveDate::from_ymd_opt(2025, 6, 14).unwrap();
+        assert!(matches!(
+            prefill_from_server("http://localhost:1", "../Patient?_id=1", score, today),
+            Err(PrefillError::InvalidPatientId(_))
+        ));
+    }
+}
@@ -0,0 +1,304 @@
+// rpc.rs
+// JSON-RPC 2.0 over stdin/stdout (`klinscore --stdio`), so EHR plugins and editors
+// can run KlinScore as a subprocess. One request per line, one response per line.
+//
+// Methods:
+//   listScores {specialty?, language?}       -> [{id, name, specialty, version, deprecated}]
+//   calculate  {score, inputs, language?}    -> {score_id, total_score, risk, risk_level,
+//                                                recommendation, details?, result}
+
+use crate::cli::{find_score, parse_specialty, CliError};
+use crate::scores::{CalculationRequest, InputValue, ScoreFilter, ScoreLibrary};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::io::{self, BufRead, Write};
+
+/// The line is not JSON
+pub const PARSE_ERROR: i64 = -32700;
+/// The JSON is not a request
+pub const INVALID_REQUEST: i64 = -32600;
+pub const METHOD_NOT_FOUND: i64 = -32601;
+/// Missing or mistyped parameters, or inputs the score does not accept
+pub const INVALID_PARAMS: i64 = -32602;
+/// No score (or more than one) has the requested ID, name or alias
+pub const UNKNOWN_SCORE: i64 = -32001;
+/// The inputs are valid, but the score cannot be calculated from them
+pub const CALCULATION_FAILED: i64 = -32002;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RpcError {
+    pub code: i64,
+    pub message: String,
+}
+
+impl RpcError {
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct Request {
+    jsonrpc: String,
+    /// Absent for notifications, which get no response
+    #[serde(default)]
+    id: Option<Value>,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+struct ListScoresParams {
+    specialty: Option<String>,
+    language: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CalculateParams {
+    score: String,
+    #[serde(default)]
+    inputs: HashMap<String, InputValue>,
+    #[serde(default)]
+    language: Option<String>,
+}
+
+/// Answers requests over a library of scores
+pub struct RpcServer {
+    library: ScoreLibrary,
+}
+
+impl RpcServer {
+    pub fn new(library: ScoreLibrary) -> Self {
+        Self { library }
+    }
+
+    /// Answer requests line by line until `input` ends
+    pub fn serve(&self, input: impl BufRead, mut output: impl Write) -> io::Result<()> {
+        for line in input.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            if let Some(response) = self.handle_line(&line) {
+                writeln!(output, "{}", response)?;
+                output.flush()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Response to one line, `None` for notifications
+    pub fn handle_line(&self, line: &str) -> Option<String> {
+        let response = match serde_json::from_str::<Value>(line) {
+            Err(e) => response(Value::Null, Err(RpcError::new(PARSE_ERROR, e.to_string()))),
+            Ok(message) => {
+                let id = message.get("id").cloned().unwrap_or(Value::Null);
+                match serde_json::from_value::<Request>(message) {
+                    Ok(request) if request.jsonrpc == "2.0" => {
+                        let result = self.call(&request.method, request.params);
+                        // Notifications get no answer, not even errors
+                        response(request.id?, result)
+                    }
+                    _ => response(
+                        id,
+                        Err(RpcError::new(
+                            INVALID_REQUEST,
+                            "expected a JSON-RPC 2.0 request",
+                        )),
+                    ),
+                }
+            }
+        };
+        Some(response.to_string())
+    }
+
+    /// Result of calling `method` with `params`
+    pub fn call(&self, method: &str, params: Value) -> Result<Value, RpcError> {
+        match method {
+            "listScores" => self.list_scores(params_of(params)?),
+            "calculate" => self.calculate(params_of(params)?),
+            other => Err(RpcError::new(
+                METHOD_NOT_FOUND,
+                format!("unknown method '{}'", other),
+            )),
+        }
+    }
+
+    fn list_scores(&self, params: ListScoresParams) -> Result<Value, RpcError> {
+        let language = params.language.as_deref().unwrap_or("en");
+        let specialty = params
+            .specialty
+            .as_deref()
+            .map(parse_specialty)
+            .transpose()
+            .map_err(|e| RpcError::new(INVALID_PARAMS, e.to_string()))?;
+        let filter = ScoreFilter {
+            specialty,
+            ..ScoreFilter::default()
+        };
+        let scores: Vec<Value> = self
+            .library
+            .filter(&filter)
+            .into_iter()
+            .map(|(id, score)| {
+                json!({
+                    "id": id,
+                    "name": score.name.text(language),
+                    "specialty": if language == "de" {
+                        score.specialty.german()
+                    } else {
+                        score.specialty.english()
+                    },
+                    "version": score.version,
+                    "deprecated": score.is_deprecated(),
+                })
+            })
+            .collect();
+        Ok(Value::Array(scores))
+    }
+
+    fn calculate(&self, params: CalculateParams) -> Result<Value, RpcError> {
+        let language = params.language.as_deref().unwrap_or("en");
+        let (id, score) = find_score(&self.library, &params.score).map_err(|e| match e {
+            CliError::UnknownScore(_) | CliError::AmbiguousScore { .. } => {
+                RpcError::new(UNKNOWN_SCORE, e.to_string())
+            }
+            _ => RpcError::new(INVALID_PARAMS, e.to_string()),
+        })?;
+
+        let request = params
+            .inputs
+            .into_iter()
+            .fold(CalculationRequest::new(), |request, (field, value)| {
+                request.value(field, value)
+            });
+        request
+            .validate(score)
+            .map_err(|e| RpcError::new(INVALID_PARAMS, e.to_string()))?;
+        let result = request
+            .calculate(score)
+            .map_err(|e| RpcError::new(CALCULATION_FAILED, e.localized_message(language)))?;
+
+        let mut response = json!({
+            "score_id": id,
+            "total_score": result.total_score,
+            "risk": result.risk.text(language),
+            "risk_level": result.risk_level,
+            "recommendation": result.recommendation.text(language),
+            "result": result,
+        });
+        if let Some(details) = &result.details {
+            response["details"] = json!(details.text(language));
+        }
+        Ok(response)
+    }
+}
+
+/// Parameters of a method; absent parameters are an empty object
+fn params_of<T: for<'de> Deserialize<'de>>(params: Value) -> Result<T, RpcError> {
+    let params = if params.is_null() { json!({}) } else { params };
+    serde_json::from_value(params).map_err(|e| RpcError::new(INVALID_PARAMS, e.to_string()))
+}
+
+fn response(id: Value, result: Result<Value, RpcError>) -> Value {
+    match result {
+        Ok(result) => json!({"jsonrpc": "2.0", "id": id, "result": result}),
+        Err(error) => json!({"jsonrpc": "2.0", "id": id, "error": error}),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scores::load_bundled_scores;
+
+    fn answer(server: &RpcServer, line: &str) -> Value {
+        serde_json::from_str(&server.handle_line(line).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_stdio_requests() {
+        let server = RpcServer::new(load_bundled_scores());
+        let input = concat!(
+            r#"{"jsonrpc": "2.0", "id": 1, "method": "listScores", "params": {"specialty": "cardiology"}}"#,
+            "\n\n",
+            r#"{"jsonrpc": "2.0", "method": "listScores"}"#,
+            "\n",
+            r#"{"jsonrpc": "2.0", "id": "b", "method": "calculate", "params": {"score": "cha2ds2_va", "inputs": {"age": 72, "heart_failure": true, "hypertension": true}}}"#,
+            "\n",
+        );
+        let mut output = Vec::new();
+        server.serve(input.as_bytes(), &mut output).unwrap();
+        let responses: Vec<Value> = String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        // The notification gets no response
+        assert_eq!(responses.len(), 2);
+        assert_eq!(responses[0]["id"], 1);
+        let scores = responses[0]["result"].as_array().unwrap();
+        assert!(scores.iter().any(|score| score["id"] == "cha2ds2_va"));
+        assert_eq!(responses[1]["id"], "b");
+        assert_eq!(responses[1]["result"]["total_score"], 3);
+        assert_eq!(responses[1]["result"]["risk_level"], "High");
+        assert_eq!(responses[1]["result"]["result"]["total_score"], 3);
+    }
+
+    #[test]
+    fn test_provenance_matches_cli() {
+        let library = load_bundled_scores();
+        let server = RpcServer::new(library.clone());
+        let answer = server
+            .call(
+                "calculate",
+                json!({"score": "cha2ds2_va", "inputs": {"age": 72}}),
+            )
+            .unwrap();
+
+        // The command line sets every box not given to false
+        let (_, score) = find_score(&library, "cha2ds2_va").unwrap();
+        let arguments = ["--age".to_string(), "72".to_string()];
+        let inputs = crate::cli::parse_inputs(score, &arguments).unwrap();
+        let result = crate::scores::calculate_score(score, &inputs).unwrap();
+        assert_eq!(answer["result"]["provenance"], result.provenance);
+    }
+
+    #[test]
+    fn test_stdio_errors() {
+        let server = RpcServer::new(load_bundled_scores());
+        let code = |line: &str| answer(&server, line)["error"]["code"].clone();
+        assert_eq!(code("not json"), PARSE_ERROR);
+        assert_eq!(
+            code(r#"{"id": 1, "method": "listScores"}"#),
+            INVALID_REQUEST
+        );
+        assert_eq!(
+            code(r#"{"jsonrpc": "2.0", "id": 1, "method": "delete"}"#),
+            METHOD_NOT_FOUND
+        );
+        assert_eq!(
+            code(
+                r#"{"jsonrpc": "2.0", "id": 1, "method": "calculate", "params": {"score": "nonexistent"}}"#
+            ),
+            UNKNOWN_SCORE
+        );
+        assert_eq!(
+            code(
+                r#"{"jsonrpc": "2.0", "id": 1, "method": "calculate", "params": {"score": "cha2ds2_va", "inputs": {"smoker": true}}}"#
+            ),
+            INVALID_PARAMS
+        );
+        assert_eq!(
+            code(r#"{"jsonrpc": "2.0", "id": 1, "method": "calculate", "params": {}}"#),
+            INVALID_PARAMS
+        );
+    }
+}
@@ -0,0 +1,322 @@
+// bases.rs
+// Shared base definitions that scores can inherit from via `extends`
+
+use crate::config::{upgrade_legacy_references, upgrade_legacy_translations};
+use crate::scores::loader::{skip_or_fail, ScoreFormat, ScoreLoadError};
+use include_dir::Dir;
+use serde_yaml::{Mapping, Value};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::OnceLock;
+
+/// Directory (inside a scores directory) that holds base definitions
+pub const BASES_DIR: &str = "bases";
+
+/// Lists that are merged item by item, keyed by the given identity field;
+/// a child item with the same identity replaces the inherited one
+const MERGED_LISTS: [(&str, &str); 3] = [
+    ("inputs", "field"),
+    ("sections", "id"),
+    ("constraints", "expression"),
+];
+
+/// Partial score definitions that other scores can `extend`, keyed by base ID.
+///
+/// A base file is a score definition fragment (e.g. only `inputs`) in any score
+/// format, stored in a `bases/` directory. Its ID is the `id` key, or the file name.
+#[derive(Debug, Clone, Default)]
+pub struct BaseDefinitions {
+    bases: HashMap<String, Value>,
+}
+
+impl BaseDefinitions {
+    /// Bases shipped with the application (`scores/bases/`)
+    pub fn bundled() -> &'static BaseDefinitions {
+        static BUNDLED: OnceLock<BaseDefinitions> = OnceLock::new();
+        BUNDLED.get_or_init(|| {
+            let mut bases = BaseDefinitions::default();
+            if let Some(dir) = crate::scores::loader::bundled_dir().get_dir(BASES_DIR) {
+                bases.load_bundled(dir);
+            }
+            bases
+        })
+    }
+
+    /// Bundled bases plus those of the nearest scores directory containing `path`
+    /// (the closest ancestor with a `bases/` subdirectory), for validating single files
+    pub fn for_path(path: &Path) -> Result<BaseDefinitions, ScoreLoadError> {
+        let mut bases = Self::bundled().clone();
+        let start = if path.is_dir() {
+            Some(path)
+        } else {
+            path.parent()
+        };
+        if let Some(scores_dir) = start
+            .into_iter()
+            .flat_map(Path::ancestors)
+            .find(|dir| dir.join(BASES_DIR).is_dir())
+        {
+            bases.load_dir(scores_dir, false)?;
+        }
+        Ok(bases)
+    }
+
+    /// Add the bases in `<scores_dir>/bases/`, replacing bases with the same ID.
+    /// Invalid base files are skipped with a warning, or fail in strict mode.
+    pub fn load_dir(&mut self, scores_dir: &Path, strict: bool) -> Result<(), ScoreLoadError> {
+        let Ok(entries) = fs::read_dir(scores_dir.join(BASES_DIR)) else {
+            return Ok(());
+        };
+
+        let mut paths: Vec<_> = entries.filter_map(Result::ok).map(|e| e.path()).collect();
+        paths.sort();
+        for path in paths {
+            let Some(format) = ScoreFormat::from_path(&path) else {
+                continue;
+            };
+            let result = fs::read_to_string(&path)
+                .map_err(|e| e.to_string())
+                .and_then(|source| parse_value(&source, format))
+                .map_err(|reason| ScoreLoadError::InvalidScore {
+                    path: path.display().to_string(),
+                    reason,
+                });
+            match result {
+                Ok(base) => self.insert(&path, base),
+                Err(e) => skip_or_fail(strict, e, "Skipping base definition")?,
+            }
+        }
+        Ok(())
+    }
+
+    fn load_bundled(&mut self, dir: &Dir<'_>) {
+        for file in dir.files() {
+            let path = file.path();
+            let Some(format) = ScoreFormat::from_path(path) else {
+                continue;
+            };
+            match file
+                .contents_utf8()
+                .ok_or_else(|| "File is not valid UTF-8".to_string())
+                .and_then(|source| parse_value(source, format))
+            {
+                Ok(base) => self.insert(path, base),
+                Err(e) => {
+                    tracing::warn!(path = %path.display(), "Skipping bundled base: {}", e)
+                }
+            }
+        }
+    }
+
+    fn insert(&mut self, path: &Path, mut base: Value) {
+        upgrade_legacy_translations(&mut base);
+        upgrade_legacy_references(&mut base);
+        let id = base
+            .get("id")
+            .and_then(Value::as_str)
+            .map(String::from)
+            .or_else(|| path.file_stem().and_then(|s| s.to_str()).map(String::from))
+            .unwrap_or_default();
+        self.bases.insert(id, base);
+    }
+
+    /// Resolve `extends` in a (legacy-upgraded) score document, in place.
+    ///
+    /// Bases are applied in the listed order, then the score itself: keys of the score
+    /// replace inherited ones, except `inputs`, `sections` and `constraints` (merged by
+    /// `field` / `id` / `expression`) and `metadata` (merged key by key). Returns
+    /// whether the document extended anything.
+    pub fn apply(&self, document: &mut Value) -> Result<bool, String> {
+        if document.get("extends").is_none() {
+            return Ok(false);
+        }
+        let expanded = self.expand(document.clone(), &mut Vec::new())?;
+        *document = expanded;
+        Ok(true)
+    }
+
+    /// Merge the bases listed in `extends` under `document` (recursively)
+    fn expand(&self, mut document: Value, stack: &mut Vec<String>) -> Result<Value, String> {
+        let Some(map) = document.as_mapping_mut() else {
+            return Ok(document);
+        };
+        let Some(extends) = map.remove("extends") else {
+            return Ok(document);
+        };
+
+        let names = match extends {
+            Value::String(name) => vec![name],
+            Value::Sequence(items) => items
+                .into_iter()
+                .map(|item| match item {
+                    Value::String(name) => Ok(name),
+                    _ => Err("extends must list base IDs".to_string()),
+                })
+                .collect::<Result<_, _>>()?,
+            _ => return Err("extends must be a base ID or a list of base IDs".to_string()),
+        };
+
+        let mut merged = Value::Mapping(Mapping::new());
+        for name in names {
+            if stack.contains(&name) {
+                return Err(format!("Base '{}' extends itself", name));
+            }
+            let base = self
+                .bases
+                .get(&name)
+                .ok_or_else(|| format!("Unknown base '{}' in extends", name))?;
+
+            stack.push(name);
+            let mut base = self.expand(base.clone(), stack)?;
+            stack.pop();
+
+            // A base's own ID is not inherited
+            if let Some(map) = base.as_mapping_mut() {
+                map.remove("id");
+            }
+            merge(&mut merged, base);
+        }
+        merge(&mut merged, document);
+        Ok(merged)
+    }
+}
+
+/// Merge `overlay` into `target` (both mappings); see [`BaseDefinitions::apply`]
+fn merge(target: &mut Value, overlay: Value) {
+    let (Some(target), Value::Mapping(overlay)) = (target.as_mapping_mut(), overlay) else {
+        return;
+    };
+
+    for (key, value) in overlay {
+        let identity = key
+            .as_str()
+            .and_then(|k| MERGED_LISTS.iter().find(|(list, _)| *list == k))
+            .map(|(_, identity)| *identity);
+
+        match (target.get_mut(&key), value, identity) {
+            (Some(Value::Sequence(inherited)), Value::Sequence(items), Some(identity)) => {
+                for item in items {
+                    let id = item.get(identity).cloned();
+                    match inherited
+                        .iter_mut()
+                        .find(|existing| id.is_some() && existing.get(identity) == id.as_ref())
+                    {
+                        Some(existing) => *existing = item,
+                        None => inherited.push(item),
+                    }
+                }
+            }
+            (Some(Value::Mapping(inherited)), Value::Mapping(entries), _)
+                if key.as_str() == Some("metadata") =>
+            {
+                inherited.extend(entries);
+            }
+            (_, value, _) => {
+                target.insert(key, value);
+            }
+        }
+    }
+}
+
+/// Parse a base file into a document tree
+fn parse_value(source: &str, format: ScoreFormat) -> Result<Value, String> {
+    match format {
+        ScoreFormat::Yaml => serde_yaml::from_str(source).map_err(|e| e.to_string()),
+        ScoreFormat::Json => serde_json::from_str(source).map_err(|e| e.to_string()),
+        ScoreFormat::Toml => toml::from_str(source).map_err(|e| e.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bases(sources: &[(&str, &str)]) -> BaseDefinitions {
+        let mut bases = BaseDefinitions::default();
+        for (name, source) in sources {
+            bases.insert(
+                Path::new(&format!("{}.yaml", name)),
+                serde_yaml::from_str(source).unwrap(),
+            );
+        }
+        bases
+    }
+
+    #[test]
+    fn test_merge_inputs_and_metadata() {
+        let bases = bases(&[(
+            "base_demographics",
+            r#"
+inputs:
+  - field: "age"
+    type: "number"
+    label: {en: "Age", de: "Alter"}
+    points: 0
+  - field: "female"
+    type: "boolean"
+    label: {en: "Female", de: "Weiblich"}
+    points: 0
+metadata:
+  category: risk_stratification
+  tags: {en: ["demographics"]}
+"#,
+        )]);
+
+        let mut document: Value = serde_yaml::from_str(
+            r#"
+extends: base_demographics
+name: "Child"
+inputs:
+  - field: "female"
+    type: "boolean"
+    label: {en: "Female sex", de: "Weibliches Geschlecht"}
+    points: 1
+  - field: "diabetes"
+    type: "boolean"
+    label: "Diabetes"
+    points: 1
+metadata:
+  tags: {en: ["child"]}
+"#,
+        )
+        .unwrap();
+
+        assert!(bases.apply(&mut document).unwrap());
+        assert!(document.get("extends").is_none());
+        let fields: Vec<_> = document["inputs"]
+            .as_sequence()
+            .unwrap()
+            .iter()
+            .map(|input| input["field"].as_str().unwrap())
+            .collect();
+        assert_eq!(fields, vec!["age", "female", "diabetes"]);
+        assert_eq!(document["inputs"][1]["points"], 1);
+        assert_eq!(document["metadata"]["category"], "risk_stratification");
+        assert_eq!(document["metadata"]["tags"]["en"][0], "child");
+
+        // Documents without extends are left alone
+        assert!(!bases.apply(&mut document).unwrap());
+    }
+
+    #[test]
+    fn test_nested_unknown_and_cyclic_bases() {
+        let bases = bases(&[
+            ("a", "extends: b\nid: a\ninputs: [{field: \"x\"}]"),
+            ("b", "inputs: [{field: \"y\"}]"),
+            ("loop", "extends: loop"),
+        ]);
+
+        let mut document: Value = serde_yaml::from_str("extends: [a]\nid: child").unwrap();
+        bases.apply(&mut document).unwrap();
+        assert_eq!(document["id"], "child");
+        assert_eq!(document["inputs"][0]["field"], "y");
+        assert_eq!(document["inputs"][1]["field"], "x");
+
+        let mut unknown: Value = serde_yaml::from_str("extends: missing").unwrap();
+        assert!(bases.apply(&mut unknown).unwrap_err().contains("missing"));
+
+        let mut cyclic: Value = serde_yaml::from_str("extends: loop").unwrap();
+        assert!(bases.apply(&mut cyclic).is_err());
+    }
+}
@@ -2,9 +2,16 @@
 // Generic calculation engine for clinical scores
 
 use crate::config::{
-    InputField, InputType, InterpretationRule, PointCondition, PointsValue, RiskLevel,
-    ScoreDefinition, ScoreRange,
+    InputField, InputType, InterpretationRule, LocalizedText, PointCondition, PointsTable,
+    PointsValue, RiskLevel, ScoreDefinition, UnitSystem, FALLBACK_LANGUAGE,
 };
+use crate::scores::condition::{CompiledConditions, ScoreBounds};
+use crate::scores::dates::CalendarDate;
+use crate::scores::durations::format_duration;
+use crate::scores::provenance::provenance_hash;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use std::collections::HashMap;
 use thiserror::Error;
 
@@ -25,6 +32,9 @@ pub enum CalculationError {
         max: f64,
     },
 
+    #[error("Unknown input field '{field}'")]
+    UnknownField { field: String },
+
     #[error("Unknown dropdown option '{option}' for field '{field}'")]
     UnknownDropdownOption { field: String, option: String },
 
@@ -33,14 +43,55 @@ pub enum CalculationError {
 
     #[error("No interpretation found for score {score}")]
     NoInterpretation { score: i32 },
+
+    #[error("Constraint '{expression}' violated: {}", .message.text(FALLBACK_LANGUAGE))]
+    ConstraintViolated {
+        expression: String,
+        message: LocalizedText,
+    },
+}
+
+impl CalculationError {
+    /// Message suitable for display in the form, in the requested language.
+    ///
+    /// Constraint violations carry author-written translated messages; all other
+    /// errors fall back to their (English) `Display` text.
+    pub fn localized_message(&self, language: &str) -> String {
+        match self {
+            CalculationError::ConstraintViolated { message, .. } => {
+                message.text(language).to_string()
+            }
+            other => other.to_string(),
+        }
+    }
 }
 
 /// Input value types
-#[derive(Debug, Clone, PartialEq)]
+///
+/// Serialized as the plain JSON value (`true`, `72`, `"1956-03-14"`, `"class_ii"`,
+/// `{"value": 1.2, "unit": "mg/dL"}`), e.g. in history entries. Strings that are
+/// ISO dates read back as dates.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
 pub enum InputValue {
     Boolean(bool),
     Number(f64),
+    Date(CalendarDate),
     Dropdown(String),
+    /// A number in a unit other than the field's, e.g. a lab value in
+    /// conventional units
+    Quantity(Quantity),
+}
+
+/// A number with the unit it was entered in
+///
+/// Scored in the `unit` of its field: `unit` must be that unit or the field's
+/// `conventional_unit` (in any language, ignoring case), which is converted with
+/// [`InputField::unit_factor`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Quantity {
+    pub value: f64,
+    pub unit: String,
 }
 
 impl InputValue {
@@ -64,19 +115,65 @@ impl InputValue {
             _ => None,
         }
     }
+
+    pub fn as_date(&self) -> Option<CalendarDate> {
+        match self {
+            InputValue::Date(date) => Some(*date),
+            _ => None,
+        }
+    }
+
+    /// The number in the unit of `input_field`: numbers as they are, quantities
+    /// converted from the unit they were entered in; `None` for other values and
+    /// units the field does not have
+    pub fn number_in(&self, input_field: &InputField) -> Option<f64> {
+        match self {
+            InputValue::Number(n) => Some(*n),
+            InputValue::Quantity(quantity) => {
+                let system = input_field.unit_system_of(&quantity.unit)?;
+                Some(quantity.value * input_field.unit_factor(system))
+            }
+            _ => None,
+        }
+    }
 }
 
 /// Points breakdown for a single field
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct FieldScore {
     pub field: String,
-    pub label: String,
-    pub label_de: String,
+    pub label: LocalizedText,
     pub points: i32,
+    /// Entered value or selected option (see [`describe_input`]); `None` if left empty
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub selection: Option<LocalizedText>,
 }
 
 /// Result of a score calculation
-#[derive(Debug, Clone, PartialEq)]
+///
+/// Serialized with every text in all languages of the score, and the matched rule
+/// as in the score file:
+///
+/// ```json
+/// {
+///   "total_score": 3,
+///   "field_scores": [
+///     {"field": "age", "label": {"en": "Age", "de": "Alter"}, "points": 1,
+///      "selection": {"en": "72"}}
+///   ],
+///   "interpretation": {"score": "≥2", "risk": {"en": "Moderate-High"}, "risk_level": "High", ...},
+///   "risk_level": "High",
+///   "risk": {"en": "Moderate-High", "de": "Mittel-Hoch"},
+///   "recommendation": {"en": "...", "de": "..."},
+///   "details": {"en": "...", "de": "..."},
+///   "provenance": "9f2c…"
+/// }
+/// ```
+///
+/// `selection` and `details` are left out when not set, `provenance` in results
+/// saved before it was recorded. Fields are only added to
+/// this shape, never renamed or removed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CalculationResult {
     /// Total calculated score
     pub total_score: i32,
@@ -90,26 +187,31 @@ pub struct CalculationResult {
     /// Risk level (for color coding)
     pub risk_level: RiskLevel,
 
-    /// Risk description (English)
-    pub risk: String,
-
-    /// Risk description (German)
-    pub risk_de: String,
-
-    /// Clinical recommendation (English)
-    pub recommendation: String,
+    /// Risk description
+    pub risk: LocalizedText,
 
-    /// Clinical recommendation (German)
-    pub recommendation_de: String,
+    /// Clinical recommendation
+    pub recommendation: LocalizedText,
 
-    /// Optional details (English)
-    pub details: Option<String>,
+    /// Optional details
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub details: Option<LocalizedText>,
 
-    /// Optional details (German)
-    pub details_de: Option<String>,
+    /// Reproducibility hash of the score, its version, the inputs and the engine
+    /// version (see [`provenance_hash`])
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub provenance: String,
 }
 
 impl CalculationResult {
+    /// Fields that changed the total, in definition order.
+    ///
+    /// Includes negative (protective) points as well as positive ones; only
+    /// zero-point fields are left out.
+    pub fn contributing_fields(&self) -> impl Iterator<Item = &FieldScore> {
+        self.field_scores.iter().filter(|fs| fs.points != 0)
+    }
+
     /// Get points for a field by name (for testing)
     #[allow(dead_code)]
     pub fn get_field_points(&self, field_name: &str) -> Option<i32> {
@@ -123,11 +225,13 @@ impl CalculationResult {
 /// Calculate a score based on user inputs
 ///
 /// This is the core calculation engine. It:
-/// 1. Validates all inputs
-/// 2. Calculates points for each field
-/// 3. Sums the total score
-/// 4. Finds the matching interpretation rule
-/// 5. Returns the complete result
+/// 1. Derives elapsed times from dates (see [`elapsed_time`]), sets dropdowns
+///    not given to their preselected option and checks cross-field constraints
+/// 2. Validates all inputs
+/// 3. Calculates points for each field
+/// 4. Sums the total score
+/// 5. Finds the matching interpretation rule
+/// 6. Returns the complete result
 ///
 /// # Arguments
 ///
@@ -143,45 +247,70 @@ impl CalculationResult {
 /// Returns `CalculationError` if:
 /// - Required fields are missing
 /// - Input values are invalid
-/// - Numbers are out of range
+/// - Numbers are not finite or out of range
+/// - A duration is negative
 /// - Dropdown options don't exist
+/// - A date lies after the date its elapsed time is measured until
+/// - A cross-field constraint is violated
 /// - No interpretation matches the calculated score
 pub fn calculate_score(
     score_def: &ScoreDefinition,
     inputs: &HashMap<String, InputValue>,
 ) -> Result<CalculationResult, CalculationError> {
+    let values = scored_values(score_def, inputs)?;
+    // Numbers are checked before anything compares them, formulas included
+    for input_field in score_def.inputs.iter().filter(|field| field.is_numeric()) {
+        if let Some(value) = values
+            .get(&input_field.field)
+            .and_then(InputValue::as_number)
+        {
+            check_number(input_field, value)?;
+        }
+    }
+    crate::scores::constraints::check_constraints(score_def, &values)?;
+
     // If this score uses a formula, dispatch to formula engine
     if let Some(ref formula) = score_def.formula {
-        return calculate_formula_score(score_def, inputs, formula);
+        return calculate_formula_score(score_def, inputs, &values, formula);
     }
 
-    let mut total_score = 0;
+    let mut total_score: i32 = 0;
     let mut field_scores = Vec::new();
 
     // Calculate points for each input field
     for input_field in &score_def.inputs {
         let field_name = &input_field.field;
 
-        // Check if required field is present
-        if input_field.required && !inputs.contains_key(field_name) {
+        // Check if required field is present (or has a preselected option)
+        if input_field.required && !values.contains_key(field_name) {
             return Err(CalculationError::MissingRequiredField {
                 field: field_name.clone(),
             });
         }
 
         // Get input value
-        let points = match inputs.get(field_name) {
-            Some(input_value) => calculate_field_points(input_field, input_value)?,
+        let points = match values.get(field_name) {
+            Some(input_value) => {
+                calculate_field_points(score_def, input_field, input_value, &values)?
+            }
             None => 0, // Field not provided (e.g., unchecked boolean) = 0 points
         };
 
+        let selection = match (inputs.get(field_name), values.get(field_name)) {
+            (Some(InputValue::Date(date)), Some(InputValue::Number(elapsed))) => {
+                Some(describe_date(input_field, *date, Some(*elapsed)))
+            }
+            // As entered, or the preselected option of a dropdown not given
+            (_, Some(value)) => describe_input(input_field, value),
+            (_, None) => None,
+        };
         field_scores.push(FieldScore {
             field: field_name.clone(),
             label: input_field.label.clone(),
-            label_de: input_field.label_de.clone(),
             points,
+            selection,
         });
-        total_score += points;
+        total_score = total_score.saturating_add(points);
     }
 
     // Find matching interpretation
@@ -192,24 +321,272 @@ pub fn calculate_score(
         field_scores,
         risk_level: interpretation.risk_level,
         risk: interpretation.risk.clone(),
-        risk_de: interpretation.risk_de.clone(),
         recommendation: interpretation.recommendation.clone(),
-        recommendation_de: interpretation.recommendation_de.clone(),
         details: interpretation.details.clone(),
-        details_de: interpretation.details_de.clone(),
         interpretation: interpretation.clone(),
+        provenance: provenance_hash(score_def, inputs),
+    })
+}
+
+/// Running total of a form that is still being filled in
+#[derive(Debug, Clone, PartialEq)]
+pub struct PartialResult<'a> {
+    /// Sum of the points of all valid inputs entered so far
+    pub subtotal: i32,
+
+    /// Required fields that are still empty
+    pub missing_required: usize,
+
+    /// Interpretation band the subtotal currently falls into
+    pub interpretation: Option<&'a InterpretationRule>,
+}
+
+/// Sum the points of the inputs entered so far, without validating the form
+///
+/// Unlike [`calculate_score`], missing required fields, invalid values and
+/// constraint violations do not fail: invalid values simply count no points.
+/// Returns `None` for formula-based scores, which have no running total.
+pub fn calculate_partial<'a>(
+    score_def: &'a ScoreDefinition,
+    inputs: &HashMap<String, InputValue>,
+) -> Option<PartialResult<'a>> {
+    if score_def.formula.is_some() {
+        return None;
+    }
+
+    // Dates whose elapsed time cannot be derived yet count no points
+    let values = scored_values(score_def, inputs).unwrap_or(Cow::Borrowed(inputs));
+    let mut subtotal: i32 = 0;
+    let mut missing_required = 0;
+    for input_field in &score_def.inputs {
+        match values.get(&input_field.field) {
+            Some(input_value) => {
+                subtotal = subtotal.saturating_add(
+                    calculate_field_points(score_def, input_field, input_value, &values)
+                        .unwrap_or(0),
+                );
+            }
+            None if input_field.required => missing_required += 1,
+            None => {}
+        }
+    }
+
+    let interpretation = score_def.interpretation.iter().find(|rule| {
+        score_def
+            .conditions
+            .matches(&rule.score, subtotal)
+            .unwrap_or(false)
+    });
+
+    Some(PartialResult {
+        subtotal,
+        missing_required,
+        interpretation,
     })
 }
 
+/// Display text of an entered value: the selected option's label, the number with
+/// its unit (decimal comma in German), the date, the duration in days, hours and
+/// minutes, or yes/no for checkboxes
+pub fn describe_input(input_field: &InputField, value: &InputValue) -> Option<LocalizedText> {
+    match (input_field.input_type, value) {
+        (InputType::Boolean, InputValue::Boolean(checked)) => Some(if *checked {
+            LocalizedText::bilingual("Yes", "Ja")
+        } else {
+            LocalizedText::bilingual("No", "Nein")
+        }),
+        (InputType::Number, InputValue::Number(number)) => {
+            let with_unit = |number: String, language| match &input_field.unit {
+                Some(unit) => format!("{} {}", number, unit.text(language)),
+                None => number,
+            };
+            Some(LocalizedText::bilingual(
+                with_unit(number.to_string(), "en"),
+                with_unit(number.to_string().replace('.', ","), "de"),
+            ))
+        }
+        (InputType::Number, InputValue::Quantity(quantity)) => Some(LocalizedText::bilingual(
+            format!("{} {}", quantity.value, quantity.unit),
+            format!(
+                "{} {}",
+                quantity.value.to_string().replace('.', ","),
+                quantity.unit
+            ),
+        )),
+        (InputType::Dropdown, InputValue::Dropdown(selected)) => input_field
+            .options
+            .iter()
+            .find(|option| &option.value == selected)
+            .map(|option| option.label.clone()),
+        (InputType::Date, InputValue::Date(date)) => Some(describe_date(input_field, *date, None)),
+        (InputType::Duration, InputValue::Number(duration)) => {
+            let unit = input_field.duration_unit?;
+            let text = format_duration(*duration, unit);
+            Some(LocalizedText::bilingual(text.clone(), text))
+        }
+        _ => None,
+    }
+}
+
+/// Display text of a date (ISO in English, day first in German) with the elapsed
+/// time scored for it, e.g. "14.03.1956 (68 Jahre)"
+fn describe_date(
+    input_field: &InputField,
+    date: CalendarDate,
+    elapsed: Option<f64>,
+) -> LocalizedText {
+    let german = format!("{:02}.{:02}.{}", date.day(), date.month(), date.year());
+    let (Some(elapsed), Some(rule)) = (elapsed, &input_field.elapsed) else {
+        return LocalizedText::bilingual(date.to_string(), german);
+    };
+    let unit = input_field
+        .unit
+        .clone()
+        .unwrap_or_else(|| rule.unit.label());
+    let with_elapsed =
+        |date: String, language| format!("{} ({} {})", date, elapsed, unit.text(language));
+    LocalizedText::bilingual(
+        with_elapsed(date.to_string(), "en"),
+        with_elapsed(german, "de"),
+    )
+}
+
+/// Time elapsed since the date entered for `input_field` until the date entered for
+/// its `elapsed.until` field, in whole units
+///
+/// `Ok(None)` if the field is not scored by elapsed time or its date is not entered.
+/// Fails if the reference date is missing or the date lies after it.
+pub fn elapsed_time(
+    input_field: &InputField,
+    inputs: &HashMap<String, InputValue>,
+) -> Result<Option<i64>, CalculationError> {
+    let (Some(elapsed), Some(value)) = (&input_field.elapsed, inputs.get(&input_field.field))
+    else {
+        return Ok(None);
+    };
+    let date = expect_date(&input_field.field, value)?;
+    let until = match inputs.get(&elapsed.until) {
+        Some(value) => expect_date(&elapsed.until, value)?,
+        None => {
+            return Err(CalculationError::MissingRequiredField {
+                field: elapsed.until.clone(),
+            })
+        }
+    };
+
+    if date > until {
+        return Err(CalculationError::InvalidInput {
+            field: input_field.field.clone(),
+            reason: format!("Date lies after '{}'", elapsed.until),
+        });
+    }
+    Ok(Some(date.elapsed_until(until, elapsed.unit)))
+}
+
+fn expect_date(field: &str, value: &InputValue) -> Result<CalendarDate, CalculationError> {
+    value
+        .as_date()
+        .ok_or_else(|| CalculationError::InvalidInput {
+            field: field.to_string(),
+            reason: "Expected a date (YYYY-MM-DD)".to_string(),
+        })
+}
+
+/// Inputs as the scoring rules see them: quantities converted to the unit of
+/// their field, the date of each field with `elapsed` replaced by the elapsed
+/// time (see [`elapsed_time`]), and dropdowns not given set to their preselected
+/// option, as the form shows them
+///
+/// Borrows `inputs` if there is nothing to replace.
+fn scored_values<'a>(
+    score_def: &ScoreDefinition,
+    inputs: &'a HashMap<String, InputValue>,
+) -> Result<Cow<'a, HashMap<String, InputValue>>, CalculationError> {
+    let mut values = Cow::Borrowed(inputs);
+    for input_field in &score_def.inputs {
+        if let (InputType::Number, Some(value @ InputValue::Quantity(quantity))) =
+            (input_field.input_type, inputs.get(&input_field.field))
+        {
+            let number = value
+                .number_in(input_field)
+                .ok_or_else(|| unknown_unit(input_field, &quantity.unit))?;
+            values
+                .to_mut()
+                .insert(input_field.field.clone(), InputValue::Number(number));
+        } else if let Some(time) = elapsed_time(input_field, inputs)? {
+            values
+                .to_mut()
+                .insert(input_field.field.clone(), InputValue::Number(time as f64));
+        } else if let (InputType::Dropdown, None, Some(default)) = (
+            input_field.input_type,
+            inputs.get(&input_field.field),
+            input_field.default_option(),
+        ) {
+            values.to_mut().insert(
+                input_field.field.clone(),
+                InputValue::Dropdown(default.to_string()),
+            );
+        }
+    }
+    Ok(values)
+}
+
+/// Error for a quantity in a unit `input_field` does not have
+pub(crate) fn unknown_unit(input_field: &InputField, unit: &str) -> CalculationError {
+    let mut units: Vec<&str> = UnitSystem::ALL
+        .into_iter()
+        .filter_map(|system| input_field.unit_in(system))
+        .map(|unit| unit.text(FALLBACK_LANGUAGE))
+        .collect();
+    units.dedup();
+    let reason = if units.is_empty() {
+        format!("Unit '{}' given for a number without unit", unit)
+    } else {
+        format!("Unit '{}' is not {}", unit, units.join(" or "))
+    };
+    CalculationError::InvalidInput {
+        field: input_field.field.clone(),
+        reason,
+    }
+}
+
 /// Calculate points for a single input field
+///
+/// `score_def` and `inputs` are needed by points tables, whose column is
+/// selected by another field.
 fn calculate_field_points(
+    score_def: &ScoreDefinition,
     input_field: &InputField,
     input_value: &InputValue,
+    inputs: &HashMap<String, InputValue>,
 ) -> Result<i32, CalculationError> {
     match input_field.input_type {
         InputType::Boolean => calculate_boolean_points(input_field, input_value),
-        InputType::Number => calculate_number_points(input_field, input_value),
         InputType::Dropdown => calculate_dropdown_points(input_field, input_value),
+        InputType::Date if input_field.elapsed.is_none() => {
+            calculate_date_points(input_field, input_value)
+        }
+        InputType::Duration if input_value.as_number().is_some_and(|value| value < 0.0) => {
+            Err(CalculationError::InvalidInput {
+                field: input_field.field.clone(),
+                reason: "Duration cannot be negative".to_string(),
+            })
+        }
+        // Numbers, durations, and dates scored by the time elapsed since them
+        InputType::Number | InputType::Date | InputType::Duration => {
+            let column_value = match &input_field.points {
+                PointsValue::Table { table } => {
+                    table_column_value(score_def, inputs, &table.column_field)
+                }
+                _ => None,
+            };
+            calculate_number_points(
+                input_field,
+                input_value,
+                column_value,
+                &score_def.conditions,
+            )
+        }
     }
 }
 
@@ -228,7 +605,7 @@ fn calculate_boolean_points(
     if value {
         match &input_field.points {
             PointsValue::Fixed(points) => Ok(*points),
-            PointsValue::Conditional(_) => {
+            PointsValue::Conditional(_) | PointsValue::Table { .. } => {
                 // For boolean, conditional doesn't make sense, but handle it
                 Ok(0)
             }
@@ -239,18 +616,42 @@ fn calculate_boolean_points(
 }
 
 /// Calculate points for a numeric input
+///
+/// `column_value` selects the column of a points table (see [`table_column_value`]);
+/// `conditions` holds the score's parsed conditions.
 fn calculate_number_points(
     input_field: &InputField,
     input_value: &InputValue,
+    column_value: Option<f64>,
+    conditions: &CompiledConditions,
 ) -> Result<i32, CalculationError> {
-    let value = input_value
-        .as_number()
-        .ok_or_else(|| CalculationError::InvalidInput {
+    let value =
+        input_value
+            .number_in(input_field)
+            .ok_or_else(|| CalculationError::InvalidInput {
+                field: input_field.field.clone(),
+                reason: "Expected numeric value".to_string(),
+            })?;
+    check_number(input_field, value)?;
+
+    // Calculate points based on value
+    match &input_field.points {
+        PointsValue::Fixed(points) => Ok(*points),
+        PointsValue::Conditional(rules) => evaluate_conditions(rules, value, conditions),
+        PointsValue::Table { table } => evaluate_table(table, value, column_value, conditions),
+    }
+}
+
+/// Check that a number is finite and within the field's `min` and `max`
+fn check_number(input_field: &InputField, value: f64) -> Result<(), CalculationError> {
+    // NaN would pass both range checks below
+    if !value.is_finite() {
+        return Err(CalculationError::InvalidInput {
             field: input_field.field.clone(),
-            reason: "Expected numeric value".to_string(),
-        })?;
+            reason: "Expected a finite number".to_string(),
+        });
+    }
 
-    // Validate range if specified
     if let Some(min) = input_field.min {
         if value < min {
             return Err(CalculationError::OutOfRange {
@@ -272,12 +673,74 @@ fn calculate_number_points(
             });
         }
     }
+    Ok(())
+}
 
-    // Calculate points based on value
+/// Calculate points for a date scored as entered: its fixed points, if any
+fn calculate_date_points(
+    input_field: &InputField,
+    input_value: &InputValue,
+) -> Result<i32, CalculationError> {
+    expect_date(&input_field.field, input_value)?;
     match &input_field.points {
         PointsValue::Fixed(points) => Ok(*points),
-        PointsValue::Conditional(conditions) => evaluate_conditions(conditions, value),
+        // Rejected when loading: conditions and tables need a number
+        PointsValue::Conditional(_) | PointsValue::Table { .. } => Ok(0),
+    }
+}
+
+/// Value of the field selecting a points table column
+///
+/// Numbers are used as entered and booleans count as 1/0; an omitted boolean is
+/// unchecked (0). Other omitted fields have no value.
+fn table_column_value(
+    score_def: &ScoreDefinition,
+    inputs: &HashMap<String, InputValue>,
+    column_field: &str,
+) -> Option<f64> {
+    match inputs.get(column_field) {
+        Some(value) => value
+            .as_number()
+            .or_else(|| value.as_bool().map(|checked| f64::from(u8::from(checked)))),
+        None => score_def
+            .inputs
+            .iter()
+            .any(|input| input.field == column_field && input.input_type == InputType::Boolean)
+            .then_some(0.0),
+    }
+}
+
+/// Look up points in a table: the first matching row and the first matching column
+///
+/// Without a matching row or column (or a column value), the field scores 0 points.
+fn evaluate_table(
+    table: &PointsTable,
+    value: f64,
+    column_value: Option<f64>,
+    conditions: &CompiledConditions,
+) -> Result<i32, CalculationError> {
+    let Some(column_value) = column_value else {
+        return Ok(0);
+    };
+
+    let mut column = None;
+    for (i, condition) in table.columns.iter().enumerate() {
+        if conditions.evaluate(condition, column_value)? {
+            column = Some(i);
+            break;
+        }
+    }
+    let Some(column) = column else {
+        return Ok(0);
+    };
+
+    for row in &table.rows {
+        if conditions.evaluate(&row.condition, value)? {
+            return Ok(row.points.get(column).copied().unwrap_or(0));
+        }
     }
+
+    Ok(0)
 }
 
 /// Calculate points for a dropdown input
@@ -306,11 +769,15 @@ fn calculate_dropdown_points(
 }
 
 /// Evaluate conditional point rules
-fn evaluate_conditions(conditions: &[PointCondition], value: f64) -> Result<i32, CalculationError> {
+fn evaluate_conditions(
+    rules: &[PointCondition],
+    value: f64,
+    conditions: &CompiledConditions,
+) -> Result<i32, CalculationError> {
     // Evaluate conditions in order, return first match
-    for condition in conditions {
-        if evaluate_condition(&condition.condition, value)? {
-            return Ok(condition.points);
+    for rule in rules {
+        if conditions.evaluate(&rule.condition, value)? {
+            return Ok(rule.points);
         }
     }
 
@@ -318,209 +785,169 @@ fn evaluate_conditions(conditions: &[PointCondition], value: f64) -> Result<i32,
     Ok(0)
 }
 
-/// Evaluate a single condition (e.g., ">= 65", "< 50", ">= 30 && < 40")
-fn evaluate_condition(condition: &str, value: f64) -> Result<bool, CalculationError> {
-    let condition = condition.trim();
-
-    // Handle compound conditions with && (AND)
-    if condition.contains("&&") {
-        let parts: Vec<&str> = condition.split("&&").collect();
-        for part in parts {
-            if !evaluate_single_condition(part.trim(), value)? {
-                return Ok(false);
-            }
-        }
-        return Ok(true);
-    }
-
-    evaluate_single_condition(condition, value)
-}
-
-/// Evaluate a single comparison (e.g., ">= 65", "< 50")
-fn evaluate_single_condition(condition: &str, value: f64) -> Result<bool, CalculationError> {
-    let condition = condition.trim();
-
-    if let Some(threshold_str) = condition.strip_prefix(">=") {
-        let threshold: f64 =
-            threshold_str
-                .trim()
-                .parse()
-                .map_err(|_| CalculationError::ConditionParseError {
-                    condition: condition.to_string(),
-                    reason: "Invalid number after '>='".to_string(),
-                })?;
-        Ok(value >= threshold)
-    } else if let Some(threshold_str) = condition.strip_prefix("<=") {
-        let threshold: f64 =
-            threshold_str
-                .trim()
-                .parse()
-                .map_err(|_| CalculationError::ConditionParseError {
-                    condition: condition.to_string(),
-                    reason: "Invalid number after '<='".to_string(),
-                })?;
-        Ok(value <= threshold)
-    } else if let Some(threshold_str) = condition.strip_prefix('>') {
-        let threshold: f64 =
-            threshold_str
-                .trim()
-                .parse()
-                .map_err(|_| CalculationError::ConditionParseError {
-                    condition: condition.to_string(),
-                    reason: "Invalid number after '>'".to_string(),
-                })?;
-        Ok(value > threshold)
-    } else if let Some(threshold_str) = condition.strip_prefix('<') {
-        let threshold: f64 =
-            threshold_str
-                .trim()
-                .parse()
-                .map_err(|_| CalculationError::ConditionParseError {
-                    condition: condition.to_string(),
-                    reason: "Invalid number after '<'".to_string(),
-                })?;
-        Ok(value < threshold)
-    } else if let Some(threshold_str) = condition.strip_prefix("==") {
-        let threshold: f64 =
-            threshold_str
-                .trim()
-                .parse()
-                .map_err(|_| CalculationError::ConditionParseError {
-                    condition: condition.to_string(),
-                    reason: "Invalid number after '=='".to_string(),
-                })?;
-        Ok((value - threshold).abs() < f64::EPSILON)
-    } else if let Some(threshold_str) = condition.strip_prefix("!=") {
-        let threshold: f64 =
-            threshold_str
-                .trim()
-                .parse()
-                .map_err(|_| CalculationError::ConditionParseError {
-                    condition: condition.to_string(),
-                    reason: "Invalid number after '!='".to_string(),
-                })?;
-        Ok((value - threshold).abs() >= f64::EPSILON)
-    } else {
-        Err(CalculationError::ConditionParseError {
-            condition: condition.to_string(),
-            reason: "Unknown operator (expected: >=, <=, >, <, ==, !=)".to_string(),
-        })
-    }
-}
-
 /// Find the interpretation rule that matches the calculated score
 fn find_interpretation(
     score_def: &ScoreDefinition,
     total_score: i32,
-) -> Result<InterpretationRule, CalculationError> {
+) -> Result<&InterpretationRule, CalculationError> {
     for interp in &score_def.interpretation {
-        if matches_score_range(&interp.score, total_score)? {
-            return Ok(interp.clone());
+        if score_def.conditions.matches(&interp.score, total_score)? {
+            return Ok(interp);
         }
     }
 
     Err(CalculationError::NoInterpretation { score: total_score })
 }
 
-/// Check if a score matches a range specification
-fn matches_score_range(range: &ScoreRange, score: i32) -> Result<bool, CalculationError> {
-    match range {
-        ScoreRange::Exact(value) => Ok(score == *value),
-        ScoreRange::Range(range_str) => {
-            let range_str = range_str.trim();
-
-            // Handle ranges like "1-3"
-            if range_str.contains('-') {
-                let parts: Vec<&str> = range_str.split('-').collect();
-                if parts.len() == 2 {
-                    let min: i32 = parts[0].trim().parse().map_err(|_| {
-                        CalculationError::ConditionParseError {
-                            condition: range_str.to_string(),
-                            reason: "Invalid range format".to_string(),
-                        }
-                    })?;
-                    let max: i32 = parts[1].trim().parse().map_err(|_| {
-                        CalculationError::ConditionParseError {
-                            condition: range_str.to_string(),
-                            reason: "Invalid range format".to_string(),
-                        }
-                    })?;
-                    return Ok(score >= min && score <= max);
+/// Compute the lowest and highest total a point-based score can reach.
+///
+/// Each field contributes the range of its possible points, including 0 when the
+/// field can be left empty or no condition matches. Negative (protective) points
+/// lower the minimum. Returns `None` for formula-based scores.
+pub fn score_bounds(score_def: &ScoreDefinition) -> Option<(i32, i32)> {
+    if score_def.formula.is_some() {
+        return None;
+    }
+
+    let mut min_total: i32 = 0;
+    let mut max_total: i32 = 0;
+
+    for input_field in &score_def.inputs {
+        let mut possible: Vec<i32> = match input_field.input_type {
+            InputType::Boolean => match &input_field.points {
+                PointsValue::Fixed(points) => vec![0, *points],
+                PointsValue::Conditional(_) | PointsValue::Table { .. } => vec![0],
+            },
+            InputType::Number | InputType::Date | InputType::Duration => {
+                match &input_field.points {
+                    PointsValue::Fixed(points) => vec![*points],
+                    PointsValue::Conditional(conditions) => {
+                        // Any value may fall through all conditions (0 points)
+                        let mut points: Vec<i32> = conditions.iter().map(|c| c.points).collect();
+                        points.push(0);
+                        points
+                    }
+                    PointsValue::Table { table } => {
+                        // Any cell, or 0 when no row or column matches
+                        let mut points: Vec<i32> = table
+                            .rows
+                            .iter()
+                            .flat_map(|row| row.points.iter().copied())
+                            .collect();
+                        points.push(0);
+                        points
+                    }
                 }
             }
+            InputType::Dropdown => input_field.options.iter().map(|o| o.points).collect(),
+        };
 
-            // Handle comparisons like "≥3", ">=3", ">5", "<=2", "<10"
-            if range_str.starts_with("≥") || range_str.starts_with(">=") {
-                let threshold_str = range_str
-                    .trim_start_matches("≥")
-                    .trim_start_matches(">=")
-                    .trim();
-                let threshold: i32 =
-                    threshold_str
-                        .parse()
-                        .map_err(|_| CalculationError::ConditionParseError {
-                            condition: range_str.to_string(),
-                            reason: "Invalid number in range".to_string(),
-                        })?;
-                return Ok(score >= threshold);
-            }
+        // Optional fields may be left empty, contributing nothing
+        if !input_field.required || possible.is_empty() {
+            possible.push(0);
+        }
 
-            if range_str.starts_with("≤") || range_str.starts_with("<=") {
-                let threshold_str = range_str
-                    .trim_start_matches("≤")
-                    .trim_start_matches("<=")
-                    .trim();
-                let threshold: i32 =
-                    threshold_str
-                        .parse()
-                        .map_err(|_| CalculationError::ConditionParseError {
-                            condition: range_str.to_string(),
-                            reason: "Invalid number in range".to_string(),
-                        })?;
-                return Ok(score <= threshold);
-            }
+        min_total = min_total.saturating_add(possible.iter().copied().min().unwrap_or(0));
+        max_total = max_total.saturating_add(possible.iter().copied().max().unwrap_or(0));
+    }
 
-            if range_str.starts_with('>') && !range_str.starts_with(">=") {
-                let threshold_str = range_str.trim_start_matches('>').trim();
-                let threshold: i32 =
-                    threshold_str
-                        .parse()
-                        .map_err(|_| CalculationError::ConditionParseError {
-                            condition: range_str.to_string(),
-                            reason: "Invalid number in range".to_string(),
-                        })?;
-                return Ok(score > threshold);
-            }
+    Some((min_total, max_total))
+}
 
-            if range_str.starts_with('<') && !range_str.starts_with("<=") {
-                let threshold_str = range_str.trim_start_matches('<').trim();
-                let threshold: i32 =
-                    threshold_str
-                        .parse()
-                        .map_err(|_| CalculationError::ConditionParseError {
-                            condition: range_str.to_string(),
-                            reason: "Invalid number in range".to_string(),
-                        })?;
-                return Ok(score < threshold);
-            }
+/// A problem with how interpretation rules cover the achievable score range
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CoverageIssue {
+    /// Totals between `from` and `to` (inclusive) match no interpretation rule
+    Gap { from: i32, to: i32 },
+    /// Totals between `from` and `to` (inclusive) match several rules; the first one is used
+    Overlap { from: i32, to: i32 },
+}
 
-            // Handle plain number as exact match (e.g., "2" in YAML becomes Range("2"))
-            if let Ok(exact_value) = range_str.parse::<i32>() {
-                return Ok(score == exact_value);
+impl std::fmt::Display for CoverageIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (from, to, problem) = match *self {
+            CoverageIssue::Gap { from, to } => (from, to, "no interpretation"),
+            CoverageIssue::Overlap { from, to } => {
+                (from, to, "several interpretations (the first is used)")
             }
+        };
+        if from == to {
+            write!(f, "score {} has {}", from, problem)
+        } else {
+            write!(f, "scores {}\u{2013}{} have {}", from, to, problem)
+        }
+    }
+}
 
-            Err(CalculationError::ConditionParseError {
-                condition: range_str.to_string(),
-                reason: "Unrecognized range format".to_string(),
-            })
+/// Check that every achievable total of a point-based score (see [`score_bounds`])
+/// matches exactly one interpretation rule.
+///
+/// Returns the gaps and overlaps as contiguous spans, in ascending order. Formula-based
+/// scores are not checked.
+pub fn interpretation_coverage(score_def: &ScoreDefinition) -> Vec<CoverageIssue> {
+    let Some((min, max)) = score_bounds(score_def) else {
+        return Vec::new();
+    };
+
+    // Which rules match only changes where a rule's range starts or ends, so the
+    // first total of each stretch between such points stands for the whole stretch
+    let ranges: Vec<ScoreBounds> = score_def
+        .interpretation
+        .iter()
+        .filter_map(|rule| score_def.conditions.bounds(&rule.score).ok())
+        .collect();
+    let (min, end) = (i64::from(min), i64::from(max) + 1);
+    let mut starts = vec![min, end];
+    for range in &ranges {
+        starts.extend(range.min.map(i64::from));
+        starts.extend(range.max.map(|max| i64::from(max) + 1));
+    }
+    starts.retain(|start| (min..=end).contains(start));
+    starts.sort_unstable();
+    starts.dedup();
+
+    let mut issues: Vec<CoverageIssue> = Vec::new();
+    for stretch in starts.windows(2) {
+        // Both ends lie between the score's bounds
+        let (from, to) = (stretch[0] as i32, (stretch[1] - 1) as i32);
+        let matching = ranges.iter().filter(|range| range.contains(from)).count();
+        let issue = match matching {
+            0 => CoverageIssue::Gap { from, to },
+            1 => continue,
+            _ => CoverageIssue::Overlap { from, to },
+        };
+
+        // Extend the previous span if it is the same kind of issue and adjacent
+        match (issues.last_mut(), issue) {
+            (Some(CoverageIssue::Gap { to: last, .. }), CoverageIssue::Gap { to, .. })
+            | (Some(CoverageIssue::Overlap { to: last, .. }), CoverageIssue::Overlap { to, .. })
+                if i64::from(*last) + 1 == i64::from(from) =>
+            {
+                *last = to;
+            }
+            (_, issue) => issues.push(issue),
         }
     }
+    issues
+}
+
+/// Format points with an explicit sign for breakdown display (e.g., "+2", "−1", "0").
+/// Uses the typographic minus sign so negative entries stand out.
+pub fn format_signed_points(points: i32) -> String {
+    match points.cmp(&0) {
+        std::cmp::Ordering::Greater => format!("+{}", points),
+        std::cmp::Ordering::Less => format!("\u{2212}{}", points.unsigned_abs()),
+        std::cmp::Ordering::Equal => "0".to_string(),
+    }
 }
 
 /// Calculate a formula-based score (e.g., eGFR, KFRE)
+///
+/// `values` are the inputs as the scoring rules see them (see [`scored_values`]).
 fn calculate_formula_score(
     score_def: &ScoreDefinition,
     inputs: &HashMap<String, InputValue>,
+    values: &HashMap<String, InputValue>,
     formula: &str,
 ) -> Result<CalculationResult, CalculationError> {
     // Validate required fields
@@ -532,7 +959,7 @@ fn calculate_formula_score(
         }
     }
 
-    let result = crate::scores::formulas::calculate_formula(formula, inputs)?;
+    let result = crate::scores::formulas::calculate_formula(formula, values)?;
 
     // Find matching interpretation using the formula result
     let interpretation = find_interpretation(score_def, result.value)?;
@@ -542,102 +969,109 @@ fn calculate_formula_score(
         field_scores: result.field_scores,
         risk_level: interpretation.risk_level,
         risk: interpretation.risk.clone(),
-        risk_de: interpretation.risk_de.clone(),
         recommendation: interpretation.recommendation.clone(),
-        recommendation_de: interpretation.recommendation_de.clone(),
         details: interpretation.details.clone(),
-        details_de: interpretation.details_de.clone(),
         interpretation: interpretation.clone(),
+        provenance: provenance_hash(score_def, inputs),
     })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::config::{DropdownOption, Specialty};
+    use crate::config::{ConventionalUnit, DropdownOption, PointsTableRow, ScoreRange, Specialty};
 
     fn create_test_score() -> ScoreDefinition {
         ScoreDefinition {
-            name: "Test Score".to_string(),
-            name_de: "Test-Score".to_string(),
+            id: None,
+            name: LocalizedText::bilingual("Test Score", "Test-Score"),
+            aliases: Default::default(),
             specialty: Specialty::Cardiology,
-            specialty_de: "Kardiologie".to_string(),
+            sort_order: None,
             version: "1.0".to_string(),
             guideline_source: "Test".to_string(),
-            reference: "Test".to_string(),
-            reference_url: String::new(),
+            references: Vec::new(),
             validation_status: "draft".to_string(),
-            description: String::new(),
-            description_de: String::new(),
+            deprecated: false,
+            superseded_by: None,
+            description: LocalizedText::default(),
+            intended_use: None,
+            regulatory_note: None,
+            regions_validated: Vec::new(),
             inputs: vec![
                 InputField {
                     field: "age".to_string(),
                     input_type: InputType::Number,
-                    label: "Age".to_string(),
-                    label_de: "Alter".to_string(),
-                    unit: Some("years".to_string()),
-                    unit_de: Some("Jahre".to_string()),
+                    label: LocalizedText::bilingual("Age", "Alter"),
+                    unit: Some(LocalizedText::bilingual("years", "Jahre")),
                     points: PointsValue::Conditional(vec![
                         PointCondition {
                             condition: ">= 75".to_string(),
                             points: 2,
-                            label: Some("Age ≥75".to_string()),
-                            label_de: Some("Alter ≥75".to_string()),
+                            label: Some(LocalizedText::bilingual("Age ≥75", "Alter ≥75")),
                         },
                         PointCondition {
                             condition: ">= 65".to_string(),
                             points: 1,
-                            label: Some("Age 65-74".to_string()),
-                            label_de: Some("Alter 65-74".to_string()),
+                            label: Some(LocalizedText::bilingual("Age 65-74", "Alter 65-74")),
                         },
                     ]),
                     help: None,
-                    help_de: None,
                     min: Some(0.0),
                     max: Some(120.0),
                     options: vec![],
+                    default: None,
+                    conventional_unit: None,
+                    widget: None,
+                    loinc: Vec::new(),
+                    elapsed: None,
+                    duration_unit: None,
                     required: true,
                 },
                 InputField {
                     field: "hypertension".to_string(),
                     input_type: InputType::Boolean,
-                    label: "Hypertension".to_string(),
-                    label_de: "Hypertonie".to_string(),
+                    label: LocalizedText::bilingual("Hypertension", "Hypertonie"),
                     unit: None,
-                    unit_de: None,
                     points: PointsValue::Fixed(1),
                     help: None,
-                    help_de: None,
                     min: None,
                     max: None,
                     options: vec![],
+                    default: None,
+                    conventional_unit: None,
+                    widget: None,
+                    loinc: Vec::new(),
+                    elapsed: None,
+                    duration_unit: None,
                     required: true,
                 },
             ],
+            sections: vec![],
+            input_mode: None,
+            constraints: vec![],
             interpretation: vec![
                 InterpretationRule {
                     score: ScoreRange::Exact(0),
-                    risk: "Low".to_string(),
-                    risk_de: "Niedrig".to_string(),
+                    risk: LocalizedText::bilingual("Low", "Niedrig"),
+                    risk_lay: None,
                     risk_level: RiskLevel::Low,
-                    recommendation: "No action".to_string(),
-                    recommendation_de: "Keine Maßnahmen".to_string(),
+                    recommendation: LocalizedText::bilingual("No action", "Keine Maßnahmen"),
                     details: None,
-                    details_de: None,
                 },
                 InterpretationRule {
                     score: ScoreRange::Range("≥1".to_string()),
-                    risk: "High".to_string(),
-                    risk_de: "Hoch".to_string(),
+                    risk: LocalizedText::bilingual("High", "Hoch"),
+                    risk_lay: None,
                     risk_level: RiskLevel::High,
-                    recommendation: "Take action".to_string(),
-                    recommendation_de: "Maßnahmen ergreifen".to_string(),
+                    recommendation: LocalizedText::bilingual("Take action", "Maßnahmen ergreifen"),
                     details: None,
-                    details_de: None,
                 },
             ],
             formula: None,
-            metadata: HashMap::new(),
+            metadata: Default::default(),
+            test_cases: Vec::new(),
+            conditions: Default::default(),
         }
     }
 
@@ -651,11 +1085,33 @@ mod tests {
         let result = calculate_score(&score_def, &inputs).unwrap();
 
         assert_eq!(result.total_score, 2); // 1 for age 65-74, 1 for hypertension
-        assert_eq!(result.risk, "High");
+        assert_eq!(result.risk.text("en"), "High");
         assert_eq!(result.get_field_points("age"), Some(1));
         assert_eq!(result.get_field_points("hypertension"), Some(1));
     }
 
+    #[test]
+    fn test_result_json_shape() {
+        let score_def = create_test_score();
+        let inputs = HashMap::from([
+            ("age".to_string(), InputValue::Number(70.0)),
+            ("hypertension".to_string(), InputValue::Boolean(true)),
+        ]);
+        let result = calculate_score(&score_def, &inputs).unwrap();
+
+        let json = serde_json::to_value(&result).unwrap();
+        assert_eq!(json["total_score"], 2);
+        assert_eq!(json["risk_level"], "High");
+        assert_eq!(json["risk"]["en"], "High");
+        assert_eq!(json["field_scores"][0]["field"], "age");
+        assert_eq!(json["field_scores"][0]["points"], 1);
+        assert!(json["interpretation"]["recommendation"].is_object());
+        assert!(json.get("details").is_none());
+
+        let restored: CalculationResult = serde_json::from_value(json).unwrap();
+        assert_eq!(restored, result);
+    }
+
     #[test]
     fn test_calculate_score_age_thresholds() {
         let score_def = create_test_score();
@@ -695,6 +1151,70 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_quantity_is_converted_to_the_field_unit() {
+        let mut score_def = create_test_score();
+        score_def.inputs[0].conventional_unit = Some(ConventionalUnit {
+            unit: LocalizedText::bilingual("months", "Monate"),
+            factor: 1.0 / 12.0,
+        });
+        let quantity = |value, unit: &str| {
+            InputValue::Quantity(Quantity {
+                value,
+                unit: unit.to_string(),
+            })
+        };
+        let with_age = |age| {
+            HashMap::from([
+                ("age".to_string(), age),
+                ("hypertension".to_string(), InputValue::Boolean(false)),
+            ])
+        };
+
+        // 900 months are 75 years
+        let inputs: HashMap<String, InputValue> = serde_json::from_str(
+            r#"{"age": {"value": 900, "unit": "Monate"}, "hypertension": false}"#,
+        )
+        .unwrap();
+        let result = calculate_score(&score_def, &inputs).unwrap();
+        assert_eq!(result.get_field_points("age"), Some(2));
+        assert_eq!(
+            result.field_scores[0]
+                .selection
+                .as_ref()
+                .unwrap()
+                .text("en"),
+            "75 years"
+        );
+        assert_eq!(
+            result.provenance,
+            calculate_score(&score_def, &with_age(InputValue::Number(75.0)))
+                .unwrap()
+                .provenance
+        );
+
+        // The field's own unit is taken as it is, in any case
+        let result = calculate_score(&score_def, &with_age(quantity(70.0, " Years "))).unwrap();
+        assert_eq!(result.get_field_points("age"), Some(1));
+
+        // Converted values are range-checked in the field's unit
+        assert!(matches!(
+            calculate_score(&score_def, &with_age(quantity(1800.0, "months"))),
+            Err(CalculationError::OutOfRange { .. })
+        ));
+        for invalid in [quantity(70.0, "weeks"), quantity(f64::NAN, "years")] {
+            assert!(matches!(
+                calculate_score(&score_def, &with_age(invalid)),
+                Err(CalculationError::InvalidInput { .. })
+            ));
+        }
+        let inputs = HashMap::from([
+            ("age".to_string(), InputValue::Number(70.0)),
+            ("hypertension".to_string(), quantity(1.0, "years")),
+        ]);
+        assert!(calculate_score(&score_def, &inputs).is_err());
+    }
+
     #[test]
     fn test_out_of_range() {
         let score_def = create_test_score();
@@ -744,6 +1264,16 @@ mod tests {
         assert!(!evaluate_condition(">= 10 && < 20 && != 15", 15.0).unwrap());
     }
 
+    /// Evaluate a condition as a score built in code would, parsing it on the spot
+    fn evaluate_condition(condition: &str, value: f64) -> Result<bool, CalculationError> {
+        CompiledConditions::default().evaluate(condition, value)
+    }
+
+    /// Match a range as a score built in code would, parsing it on the spot
+    fn matches_score_range(range: &ScoreRange, score: i32) -> Result<bool, CalculationError> {
+        CompiledConditions::default().matches(range, score)
+    }
+
     #[test]
     fn test_matches_score_range() {
         // Exact match
@@ -762,39 +1292,309 @@ mod tests {
         assert!(!matches_score_range(&ScoreRange::Range("≥3".to_string()), 2).unwrap());
     }
 
+    #[test]
+    fn test_matches_negative_score_range() {
+        assert!(matches_score_range(&ScoreRange::Exact(-1), -1).unwrap());
+        assert!(matches_score_range(&ScoreRange::Range("-1".to_string()), -1).unwrap());
+        assert!(!matches_score_range(&ScoreRange::Range("-1".to_string()), 1).unwrap());
+
+        // Negative lower bound
+        assert!(matches_score_range(&ScoreRange::Range("-2-0".to_string()), -2).unwrap());
+        assert!(matches_score_range(&ScoreRange::Range("-2-0".to_string()), 0).unwrap());
+        assert!(!matches_score_range(&ScoreRange::Range("-2-0".to_string()), 1).unwrap());
+
+        // Both bounds negative, with and without spaces
+        assert!(matches_score_range(&ScoreRange::Range("-3--1".to_string()), -2).unwrap());
+        assert!(matches_score_range(&ScoreRange::Range("-3 - -1".to_string()), -1).unwrap());
+        assert!(!matches_score_range(&ScoreRange::Range("-3--1".to_string()), 0).unwrap());
+
+        // Comparisons with negative thresholds
+        assert!(matches_score_range(&ScoreRange::Range("<0".to_string()), -1).unwrap());
+        assert!(matches_score_range(&ScoreRange::Range("≤-1".to_string()), -1).unwrap());
+        assert!(!matches_score_range(&ScoreRange::Range("≥-1".to_string()), -2).unwrap());
+    }
+
+    #[test]
+    fn test_negative_points() {
+        let mut score_def = create_test_score();
+        score_def.inputs.push(InputField {
+            field: "statin".to_string(),
+            input_type: InputType::Boolean,
+            label: LocalizedText::bilingual("On statin therapy", "Statintherapie"),
+            unit: None,
+            points: PointsValue::Fixed(-1),
+            help: None,
+            min: None,
+            max: None,
+            options: vec![],
+            default: None,
+            conventional_unit: None,
+            widget: None,
+            loinc: Vec::new(),
+            elapsed: None,
+            duration_unit: None,
+            required: false,
+        });
+        score_def.interpretation.insert(
+            0,
+            InterpretationRule {
+                score: ScoreRange::Range("<0".to_string()),
+                risk: LocalizedText::bilingual("Very low", "Sehr niedrig"),
+                risk_lay: None,
+                risk_level: RiskLevel::VeryLow,
+                recommendation: LocalizedText::bilingual("No action", "Keine Maßnahmen"),
+                details: None,
+            },
+        );
+
+        let mut inputs = HashMap::new();
+        inputs.insert("age".to_string(), InputValue::Number(50.0));
+        inputs.insert("hypertension".to_string(), InputValue::Boolean(false));
+        inputs.insert("statin".to_string(), InputValue::Boolean(true));
+
+        let result = calculate_score(&score_def, &inputs).unwrap();
+        assert_eq!(result.total_score, -1);
+        assert_eq!(result.risk.text("en"), "Very low");
+        assert_eq!(result.get_field_points("statin"), Some(-1));
+
+        // Negative contributions are reported alongside positive ones
+        let contributing: Vec<&str> = result
+            .contributing_fields()
+            .map(|fs| fs.field.as_str())
+            .collect();
+        assert_eq!(contributing, vec!["statin"]);
+    }
+
+    #[test]
+    fn test_points_table() {
+        let mut score_def = create_test_score();
+        let table_field = |column_field: &str, columns: [&str; 2]| InputField {
+            field: "heart_rate".to_string(),
+            input_type: InputType::Number,
+            label: LocalizedText::bilingual("Heart rate", "Herzfrequenz"),
+            unit: None,
+            points: PointsValue::Table {
+                table: PointsTable {
+                    column_field: column_field.to_string(),
+                    columns: columns.iter().map(|c| c.to_string()).collect(),
+                    rows: vec![
+                        PointsTableRow {
+                            condition: ">= 100".to_string(),
+                            points: vec![2, 3],
+                            label: None,
+                        },
+                        PointsTableRow {
+                            condition: ">= 80".to_string(),
+                            points: vec![0, 1],
+                            label: None,
+                        },
+                    ],
+                },
+            },
+            help: None,
+            min: None,
+            max: None,
+            options: vec![],
+            default: None,
+            conventional_unit: None,
+            widget: None,
+            loinc: Vec::new(),
+            elapsed: None,
+            duration_unit: None,
+            required: false,
+        };
+        score_def.inputs.push(table_field("age", ["< 65", ">= 65"]));
+
+        let points = |score_def: &ScoreDefinition, inputs: &[(&str, InputValue)]| {
+            let inputs: HashMap<String, InputValue> = inputs
+                .iter()
+                .map(|(field, value)| (field.to_string(), value.clone()))
+                .collect();
+            calculate_score(score_def, &inputs)
+                .unwrap()
+                .get_field_points("heart_rate")
+        };
+        score_def.inputs[1].required = false;
+        let age = |years| ("age", InputValue::Number(years));
+        let rate = |bpm| ("heart_rate", InputValue::Number(bpm));
+
+        assert_eq!(points(&score_def, &[age(50.0), rate(110.0)]), Some(2));
+        assert_eq!(points(&score_def, &[age(70.0), rate(110.0)]), Some(3));
+        assert_eq!(points(&score_def, &[age(70.0), rate(85.0)]), Some(1));
+        // No matching row
+        assert_eq!(points(&score_def, &[age(70.0), rate(60.0)]), Some(0));
+
+        // Cells widen the score range
+        assert_eq!(score_bounds(&score_def), Some((0, 6)));
+
+        // Boolean column fields count as 1/0, and an omitted boolean as unchecked
+        score_def.inputs[2] = table_field("hypertension", ["== 0", "== 1"]);
+        let hypertension = ("hypertension", InputValue::Boolean(true));
+        assert_eq!(
+            points(&score_def, &[age(50.0), rate(110.0), hypertension]),
+            Some(3)
+        );
+        assert_eq!(points(&score_def, &[age(50.0), rate(110.0)]), Some(2));
+    }
+
+    #[test]
+    fn test_score_bounds() {
+        let mut score_def = create_test_score();
+        // age: 0..2, hypertension: 0..1
+        assert_eq!(score_bounds(&score_def), Some((0, 3)));
+
+        score_def.inputs.push(InputField {
+            field: "statin".to_string(),
+            input_type: InputType::Boolean,
+            label: LocalizedText::bilingual("On statin therapy", "Statintherapie"),
+            unit: None,
+            points: PointsValue::Fixed(-2),
+            help: None,
+            min: None,
+            max: None,
+            options: vec![],
+            default: None,
+            conventional_unit: None,
+            widget: None,
+            loinc: Vec::new(),
+            elapsed: None,
+            duration_unit: None,
+            required: false,
+        });
+        assert_eq!(score_bounds(&score_def), Some((-2, 3)));
+
+        score_def.formula = Some("ckd_epi_2021".to_string());
+        assert_eq!(score_bounds(&score_def), None);
+    }
+
+    #[test]
+    fn test_interpretation_coverage() {
+        let mut score_def = create_test_score();
+        // Range 0..3 covered by "0" and "≥1"
+        assert!(interpretation_coverage(&score_def).is_empty());
+
+        score_def.interpretation[1].score = ScoreRange::Range("2".to_string());
+        score_def.interpretation.push(InterpretationRule {
+            score: ScoreRange::Range("0-0".to_string()),
+            ..score_def.interpretation[0].clone()
+        });
+        let issues = interpretation_coverage(&score_def);
+        assert_eq!(
+            issues,
+            vec![
+                CoverageIssue::Overlap { from: 0, to: 0 },
+                CoverageIssue::Gap { from: 1, to: 1 },
+                CoverageIssue::Gap { from: 3, to: 3 },
+            ]
+        );
+        assert_eq!(issues[1].to_string(), "score 1 has no interpretation");
+
+        score_def.interpretation.truncate(1);
+        assert_eq!(
+            interpretation_coverage(&score_def)[0].to_string(),
+            "scores 1\u{2013}3 have no interpretation"
+        );
+
+        score_def.formula = Some("ckd_epi_2021".to_string());
+        assert!(interpretation_coverage(&score_def).is_empty());
+    }
+
+    #[test]
+    fn test_extreme_points_do_not_overflow() {
+        let mut score_def = create_test_score();
+        score_def.inputs[1].points = PointsValue::Fixed(i32::MAX);
+        score_def.inputs.push(InputField {
+            field: "diabetes".to_string(),
+            points: PointsValue::Fixed(i32::MIN),
+            required: false,
+            ..score_def.inputs[1].clone()
+        });
+        assert_eq!(score_bounds(&score_def), Some((i32::MIN, i32::MAX)));
+        assert_eq!(
+            interpretation_coverage(&score_def),
+            vec![CoverageIssue::Gap {
+                from: i32::MIN,
+                to: -1
+            }]
+        );
+
+        let inputs = HashMap::from([
+            ("age".to_string(), InputValue::Number(80.0)),
+            ("hypertension".to_string(), InputValue::Boolean(true)),
+        ]);
+        assert_eq!(
+            calculate_score(&score_def, &inputs).unwrap().total_score,
+            i32::MAX
+        );
+    }
+
+    #[test]
+    fn test_format_signed_points() {
+        assert_eq!(format_signed_points(2), "+2");
+        assert_eq!(format_signed_points(-1), "\u{2212}1");
+        assert_eq!(format_signed_points(0), "0");
+    }
+
+    #[test]
+    fn test_partial_calculation() {
+        let score_def = create_test_score();
+        let mut inputs = HashMap::new();
+
+        let partial = calculate_partial(&score_def, &inputs).unwrap();
+        assert_eq!(partial.subtotal, 0);
+        assert_eq!(partial.missing_required, 2);
+        assert_eq!(partial.interpretation.unwrap().risk.text("en"), "Low");
+
+        // Running total before the form is complete
+        inputs.insert("hypertension".to_string(), InputValue::Boolean(true));
+        let partial = calculate_partial(&score_def, &inputs).unwrap();
+        assert_eq!(partial.subtotal, 1);
+        assert_eq!(partial.missing_required, 1);
+        assert_eq!(partial.interpretation.unwrap().risk_level, RiskLevel::High);
+
+        // Out-of-range values count no points instead of failing
+        inputs.insert("age".to_string(), InputValue::Number(150.0));
+        let partial = calculate_partial(&score_def, &inputs).unwrap();
+        assert_eq!(partial.subtotal, 1);
+        assert_eq!(partial.missing_required, 0);
+
+        let mut formula_score = create_test_score();
+        formula_score.formula = Some("ckd_epi_2021".to_string());
+        assert!(calculate_partial(&formula_score, &inputs).is_none());
+    }
+
     #[test]
     fn test_dropdown_calculation() {
         let mut score_def = create_test_score();
         score_def.inputs.push(InputField {
             field: "severity".to_string(),
             input_type: InputType::Dropdown,
-            label: "Severity".to_string(),
-            label_de: "Schweregrad".to_string(),
+            label: LocalizedText::bilingual("Severity", "Schweregrad"),
             unit: None,
-            unit_de: None,
             points: PointsValue::Fixed(0),
             help: None,
-            help_de: None,
             min: None,
             max: None,
             options: vec![
                 DropdownOption {
                     value: "mild".to_string(),
-                    label: "Mild".to_string(),
-                    label_de: "Leicht".to_string(),
+                    label: LocalizedText::bilingual("Mild", "Leicht"),
                     points: 0,
                     description: None,
-                    description_de: None,
                 },
                 DropdownOption {
                     value: "severe".to_string(),
-                    label: "Severe".to_string(),
-                    label_de: "Schwer".to_string(),
+                    label: LocalizedText::bilingual("Severe", "Schwer"),
                     points: 3,
                     description: None,
-                    description_de: None,
                 },
             ],
+            default: None,
+            conventional_unit: None,
+            widget: None,
+            loinc: Vec::new(),
+            elapsed: None,
+            duration_unit: None,
             required: false,
         });
 
@@ -808,5 +1608,24 @@ mod tests {
 
         let result = calculate_score(&score_def, &inputs).unwrap();
         assert_eq!(result.get_field_points("severity"), Some(3));
+
+        // The breakdown shows what was entered for each criterion
+        let selection = |field: &str| {
+            result
+                .field_scores
+                .iter()
+                .find(|fs| fs.field == field)
+                .and_then(|fs| fs.selection.as_ref())
+                .map(|selection| selection.text("de").to_string())
+        };
+        assert_eq!(selection("severity"), Some("Schwer".to_string()));
+        assert_eq!(selection("hypertension"), Some("Nein".to_string()));
+
+        inputs.insert("age".to_string(), InputValue::Number(62.5));
+        let result = calculate_score(&score_def, &inputs).unwrap();
+        let age = result.field_scores.iter().find(|fs| fs.field == "age");
+        let age = age.and_then(|fs| fs.selection.as_ref()).unwrap();
+        assert_eq!(age.text("en"), "62.5 years");
+        assert_eq!(age.text("de"), "62,5 Jahre");
     }
 }
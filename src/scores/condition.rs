@@ -0,0 +1,635 @@
+// condition.rs
+// Tokenizer and parser for the conditions of conditional points (">= 30 && < 40")
+// and the score ranges of interpretation rules ("0-2", "≥3", "-3--1")
+//
+// Condition grammar; `&&` binds tighter than `||`:
+//
+//   condition  := and ("||" and)*
+//   and        := atom ("&&" atom)*
+//   atom       := comparison | "(" condition ")"
+//   comparison := operator number
+//   operator   := ">=" | "≥" | "<=" | "≤" | ">" | "<" | "==" | "!=" | "≠"
+//   number     := "-"? digits ("." digits)? | "true" | "false"
+//
+// `true` and `false` stand for 1 and 0, the value of a checked or unchecked box.
+// Score ranges are `integer`, `integer "-" integer` (both bounds included) or
+// `operator integer`.
+
+use crate::config::{PointsValue, ScoreDefinition, ScoreRange};
+use crate::scores::calculator::CalculationError;
+use crate::scores::constraints::{parse_constraint, ConstraintExpression, ConstraintOperator};
+use std::collections::HashMap;
+use std::fmt;
+
+/// A parsed condition on a number
+#[derive(Debug, Clone, PartialEq)]
+pub enum Condition {
+    /// The value compared with a threshold (`>= 65`)
+    Compare(ConstraintOperator, f64),
+    /// All of the conditions hold
+    And(Vec<Condition>),
+    /// Any of the conditions holds
+    Or(Vec<Condition>),
+}
+
+impl Condition {
+    pub fn evaluate(&self, value: f64) -> bool {
+        match self {
+            Condition::Compare(operator, threshold) => operator.compare(value, *threshold),
+            Condition::And(conditions) => conditions.iter().all(|c| c.evaluate(value)),
+            Condition::Or(conditions) => conditions.iter().any(|c| c.evaluate(value)),
+        }
+    }
+}
+
+/// Written back in the syntax it is parsed from, with parentheses where needed
+impl fmt::Display for Condition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Condition::Compare(operator, threshold) => {
+                write!(f, "{} {}", operator.symbol(), threshold)
+            }
+            Condition::And(conditions) => {
+                for (i, condition) in conditions.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, " && ")?;
+                    }
+                    match condition {
+                        Condition::Or(_) => write!(f, "({})", condition)?,
+                        _ => write!(f, "{}", condition)?,
+                    }
+                }
+                Ok(())
+            }
+            Condition::Or(conditions) => {
+                for (i, condition) in conditions.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, " || ")?;
+                    }
+                    match condition {
+                        Condition::Or(_) => write!(f, "({})", condition)?,
+                        _ => write!(f, "{}", condition)?,
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Inclusive bounds of a score range; `None` is unbounded
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScoreBounds {
+    pub min: Option<i32>,
+    pub max: Option<i32>,
+}
+
+impl ScoreBounds {
+    pub fn contains(&self, score: i32) -> bool {
+        self.min.is_none_or(|min| score >= min) && self.max.is_none_or(|max| score <= max)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Token {
+    /// Unsigned number; a minus sign is a token of its own
+    Number(f64),
+    Minus,
+    Operator(ConstraintOperator),
+    And,
+    Or,
+    Open,
+    Close,
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Number(number) => write!(f, "{}", number),
+            Token::Minus => write!(f, "-"),
+            Token::Operator(operator) => write!(f, "{}", operator.symbol()),
+            Token::And => write!(f, "&&"),
+            Token::Or => write!(f, "||"),
+            Token::Open => write!(f, "("),
+            Token::Close => write!(f, ")"),
+        }
+    }
+}
+
+/// Split `source` into tokens; the reason of the error if it has other characters
+fn tokenize(source: &str) -> Result<Vec<Token>, String> {
+    const SYMBOLS: [(&str, Token); 14] = [
+        (">=", Token::Operator(ConstraintOperator::GreaterOrEqual)),
+        ("<=", Token::Operator(ConstraintOperator::LessOrEqual)),
+        ("==", Token::Operator(ConstraintOperator::Equal)),
+        ("!=", Token::Operator(ConstraintOperator::NotEqual)),
+        ("&&", Token::And),
+        ("||", Token::Or),
+        ("≥", Token::Operator(ConstraintOperator::GreaterOrEqual)),
+        ("≤", Token::Operator(ConstraintOperator::LessOrEqual)),
+        ("≠", Token::Operator(ConstraintOperator::NotEqual)),
+        (">", Token::Operator(ConstraintOperator::Greater)),
+        ("<", Token::Operator(ConstraintOperator::Less)),
+        ("-", Token::Minus),
+        ("(", Token::Open),
+        (")", Token::Close),
+    ];
+
+    let mut tokens = Vec::new();
+    let mut rest = source;
+    while let Some(c) = rest.chars().next() {
+        if c.is_whitespace() {
+            rest = &rest[c.len_utf8()..];
+        } else if c.is_ascii_digit() || c == '.' {
+            let end = rest
+                .find(|c: char| !c.is_ascii_digit() && c != '.')
+                .unwrap_or(rest.len());
+            let number = rest[..end]
+                .parse()
+                .map_err(|_| format!("invalid number '{}'", &rest[..end]))?;
+            tokens.push(Token::Number(number));
+            rest = &rest[end..];
+        } else if let Some(word) = ["true", "false"]
+            .into_iter()
+            .find(|word| rest.starts_with(word))
+        {
+            tokens.push(Token::Number(if word == "true" { 1.0 } else { 0.0 }));
+            rest = &rest[word.len()..];
+        } else if let Some((symbol, token)) =
+            SYMBOLS.iter().find(|(symbol, _)| rest.starts_with(symbol))
+        {
+            tokens.push(*token);
+            rest = &rest[symbol.len()..];
+        } else {
+            return Err(format!("unexpected character '{}'", c));
+        }
+    }
+    Ok(tokens)
+}
+
+/// Parentheses nested deeper than this are rejected, so a hostile score file cannot
+/// overflow the stack of the recursive-descent parser
+const MAX_NESTING: usize = 32;
+
+/// Recursive-descent parser over the tokens
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+    /// Parentheses open at the current position
+    depth: usize,
+}
+
+impl Parser {
+    fn new(tokens: Vec<Token>) -> Self {
+        Self {
+            tokens,
+            position: 0,
+            depth: 0,
+        }
+    }
+
+    fn peek(&self) -> Option<Token> {
+        self.tokens.get(self.position).copied()
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.peek();
+        self.position += 1;
+        token
+    }
+
+    /// Error for the current token, or the end of the input
+    fn unexpected(&self, expected: &str) -> String {
+        match self.peek() {
+            Some(token) => format!("expected {}, found '{}'", expected, token),
+            None => format!("expected {}, found the end", expected),
+        }
+    }
+
+    fn condition(&mut self) -> Result<Condition, String> {
+        let mut alternatives = vec![self.and()?];
+        while self.peek() == Some(Token::Or) {
+            self.next();
+            alternatives.push(self.and()?);
+        }
+        Ok(if alternatives.len() == 1 {
+            alternatives.remove(0)
+        } else {
+            Condition::Or(alternatives)
+        })
+    }
+
+    fn and(&mut self) -> Result<Condition, String> {
+        let mut all = vec![self.atom()?];
+        while self.peek() == Some(Token::And) {
+            self.next();
+            all.push(self.atom()?);
+        }
+        Ok(if all.len() == 1 {
+            all.remove(0)
+        } else {
+            Condition::And(all)
+        })
+    }
+
+    fn atom(&mut self) -> Result<Condition, String> {
+        match self.peek() {
+            Some(Token::Open) => {
+                if self.depth == MAX_NESTING {
+                    return Err(format!(
+                        "parentheses nested deeper than {} levels",
+                        MAX_NESTING
+                    ));
+                }
+                self.next();
+                self.depth += 1;
+                let condition = self.condition()?;
+                self.depth -= 1;
+                if self.peek() != Some(Token::Close) {
+                    return Err(self.unexpected("')'"));
+                }
+                self.next();
+                Ok(condition)
+            }
+            Some(Token::Operator(operator)) => {
+                self.next();
+                Ok(Condition::Compare(operator, self.number()?))
+            }
+            _ => Err(self.unexpected("an operator (>=, <=, >, <, ==, !=) or '('")),
+        }
+    }
+
+    /// Number with an optional minus sign
+    fn number(&mut self) -> Result<f64, String> {
+        let negative = self.peek() == Some(Token::Minus);
+        if negative {
+            self.next();
+        }
+        match self.peek() {
+            Some(Token::Number(number)) => {
+                self.next();
+                Ok(if negative { -number } else { number })
+            }
+            _ => Err(self.unexpected("a number")),
+        }
+    }
+
+    /// Whole number with an optional minus sign
+    fn integer(&mut self) -> Result<i32, String> {
+        let number = self.number()?;
+        if number.fract() != 0.0 || number.abs() > f64::from(i32::MAX) {
+            return Err(format!("{} is not a whole number", number));
+        }
+        Ok(number as i32)
+    }
+
+    fn finish(&self) -> Result<(), String> {
+        match self.peek() {
+            None => Ok(()),
+            Some(_) => Err(self.unexpected("the end")),
+        }
+    }
+}
+
+fn parse_error(source: &str, reason: String) -> CalculationError {
+    CalculationError::ConditionParseError {
+        condition: source.to_string(),
+        reason,
+    }
+}
+
+/// Parse a condition such as ">= 65", ">= 30 && < 40" or "< 18 || > 65"
+pub fn parse_condition(source: &str) -> Result<Condition, CalculationError> {
+    let tokens = tokenize(source).map_err(|reason| parse_error(source, reason))?;
+    let mut parser = Parser::new(tokens);
+    parser
+        .condition()
+        .and_then(|condition| parser.finish().map(|()| condition))
+        .map_err(|reason| parse_error(source, reason))
+}
+
+/// Parse a score range such as "3", "0-2", "-3--1", "≥3" or "< 10"
+pub fn parse_score_range(source: &str) -> Result<ScoreBounds, CalculationError> {
+    let tokens = tokenize(source).map_err(|reason| parse_error(source, reason))?;
+    let mut parser = Parser::new(tokens);
+    let bounds = (|| {
+        let bounds = match parser.peek() {
+            Some(Token::Operator(operator)) => {
+                parser.next();
+                let value = parser.integer()?;
+                let beyond = |step: i32| {
+                    value
+                        .checked_add(step)
+                        .ok_or_else(|| format!("{} is out of range", value))
+                };
+                match operator {
+                    ConstraintOperator::GreaterOrEqual => ScoreBounds {
+                        min: Some(value),
+                        max: None,
+                    },
+                    ConstraintOperator::Greater => ScoreBounds {
+                        min: Some(beyond(1)?),
+                        max: None,
+                    },
+                    ConstraintOperator::LessOrEqual => ScoreBounds {
+                        min: None,
+                        max: Some(value),
+                    },
+                    ConstraintOperator::Less => ScoreBounds {
+                        min: None,
+                        max: Some(beyond(-1)?),
+                    },
+                    ConstraintOperator::Equal => ScoreBounds {
+                        min: Some(value),
+                        max: Some(value),
+                    },
+                    ConstraintOperator::NotEqual => {
+                        return Err("'!=' is not a range".to_string());
+                    }
+                }
+            }
+            _ => {
+                let min = parser.integer()?;
+                let max = if parser.peek() == Some(Token::Minus) {
+                    parser.next();
+                    parser.integer()?
+                } else {
+                    min
+                };
+                if min > max {
+                    return Err(format!("lower bound {} is above upper bound {}", min, max));
+                }
+                ScoreBounds {
+                    min: Some(min),
+                    max: Some(max),
+                }
+            }
+        };
+        parser.finish()?;
+        Ok(bounds)
+    })();
+    bounds.map_err(|reason| parse_error(source, reason))
+}
+
+/// The conditions, score ranges and constraints of a score definition, parsed once
+/// when it is loaded and keyed by their source text
+///
+/// Conditions not found here (definitions built in code, or changed after loading)
+/// are parsed when they are evaluated.
+#[derive(Debug, Clone, Default)]
+pub struct CompiledConditions {
+    conditions: HashMap<String, Condition>,
+    ranges: HashMap<String, ScoreBounds>,
+    constraints: HashMap<String, ConstraintExpression>,
+}
+
+impl CompiledConditions {
+    /// Parse the conditional points, points tables, interpretation ranges and
+    /// constraints of `score`
+    ///
+    /// The error names the input or interpretation rule with the malformed condition.
+    pub fn compile(score: &ScoreDefinition) -> Result<Self, String> {
+        let mut compiled = Self::default();
+        for input in &score.inputs {
+            let sources: Vec<&String> = match &input.points {
+                PointsValue::Fixed(_) => Vec::new(),
+                PointsValue::Conditional(conditions) => {
+                    conditions.iter().map(|c| &c.condition).collect()
+                }
+                PointsValue::Table { table } => table
+                    .columns
+                    .iter()
+                    .chain(table.rows.iter().map(|row| &row.condition))
+                    .collect(),
+            };
+            for source in sources {
+                if !compiled.conditions.contains_key(source) {
+                    let condition = parse_condition(source)
+                        .map_err(|e| format!("Input '{}': {}", input.field, e))?;
+                    compiled.conditions.insert(source.clone(), condition);
+                }
+            }
+        }
+        for (i, rule) in score.interpretation.iter().enumerate() {
+            if let ScoreRange::Range(source) = &rule.score {
+                if !compiled.ranges.contains_key(source) {
+                    let bounds = parse_score_range(source)
+                        .map_err(|e| format!("Interpretation rule {}: {}", i, e))?;
+                    compiled.ranges.insert(source.clone(), bounds);
+                }
+            }
+        }
+        for constraint in &score.constraints {
+            let expression = parse_constraint(&constraint.expression).map_err(|e| e.to_string())?;
+            compiled
+                .constraints
+                .insert(constraint.expression.clone(), expression);
+        }
+        Ok(compiled)
+    }
+
+    /// Whether `value` meets the condition written as `source`
+    pub fn evaluate(&self, source: &str, value: f64) -> Result<bool, CalculationError> {
+        match self.conditions.get(source) {
+            Some(condition) => Ok(condition.evaluate(value)),
+            None => Ok(parse_condition(source)?.evaluate(value)),
+        }
+    }
+
+    /// Bounds of the interpretation range `range`
+    pub fn bounds(&self, range: &ScoreRange) -> Result<ScoreBounds, CalculationError> {
+        match range {
+            ScoreRange::Exact(value) => Ok(ScoreBounds {
+                min: Some(*value),
+                max: Some(*value),
+            }),
+            ScoreRange::Range(source) => match self.ranges.get(source) {
+                Some(bounds) => Ok(*bounds),
+                None => parse_score_range(source),
+            },
+        }
+    }
+
+    /// The parsed constraint written as `source`, if it was compiled
+    pub fn constraint(&self, source: &str) -> Option<&ConstraintExpression> {
+        self.constraints.get(source)
+    }
+
+    /// Whether `score` falls into the interpretation range `range`
+    pub fn matches(&self, range: &ScoreRange, score: i32) -> Result<bool, CalculationError> {
+        Ok(self.bounds(range)?.contains(score))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_parse_condition() {
+        assert_eq!(
+            parse_condition(">= 30 && < 40").unwrap(),
+            Condition::And(vec![
+                Condition::Compare(ConstraintOperator::GreaterOrEqual, 30.0),
+                Condition::Compare(ConstraintOperator::Less, 40.0),
+            ])
+        );
+        assert_eq!(
+            parse_condition("≥-1.5").unwrap(),
+            Condition::Compare(ConstraintOperator::GreaterOrEqual, -1.5)
+        );
+        assert_eq!(
+            parse_condition("== true").unwrap(),
+            Condition::Compare(ConstraintOperator::Equal, 1.0)
+        );
+
+        // && binds tighter than ||
+        let condition = parse_condition("< 18 || >= 65 && < 75").unwrap();
+        assert!(condition.evaluate(10.0));
+        assert!(condition.evaluate(70.0));
+        assert!(!condition.evaluate(80.0));
+        let grouped = parse_condition("(< 18 || >= 65) && < 75").unwrap();
+        assert!(!grouped.evaluate(80.0));
+        assert!(grouped.evaluate(10.0));
+        let nested = |depth| format!("{}>= 1{}", "(".repeat(depth), ")".repeat(depth));
+        assert!(parse_condition(&nested(MAX_NESTING)).is_ok());
+        assert!(parse_condition(&nested(MAX_NESTING + 1)).is_err());
+
+        for malformed in [
+            "",
+            "65",
+            ">=",
+            ">= 6 5",
+            ">= 65 &&",
+            "(>= 65",
+            ">= 65)",
+            "=> 65",
+            ">= 1.2.3",
+            ">= abc",
+            "&& < 40",
+            ">= 65 & < 70",
+        ] {
+            assert!(
+                matches!(
+                    parse_condition(malformed),
+                    Err(CalculationError::ConditionParseError { .. })
+                ),
+                "{:?} parsed",
+                malformed
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_score_range() {
+        let bounds = |min, max| ScoreBounds { min, max };
+        assert_eq!(parse_score_range("3").unwrap(), bounds(Some(3), Some(3)));
+        assert_eq!(parse_score_range("0-2").unwrap(), bounds(Some(0), Some(2)));
+        assert_eq!(
+            parse_score_range("-3 - -1").unwrap(),
+            bounds(Some(-3), Some(-1))
+        );
+        assert_eq!(parse_score_range("≥3").unwrap(), bounds(Some(3), None));
+        assert_eq!(parse_score_range("> 140").unwrap(), bounds(Some(141), None));
+        assert_eq!(parse_score_range("<15").unwrap(), bounds(None, Some(14)));
+        for malformed in ["", "3-", "2-1", "1.5", "!= 3", "≥", "0-2-4", "3 points"] {
+            assert!(
+                parse_score_range(malformed).is_err(),
+                "{:?} parsed",
+                malformed
+            );
+        }
+    }
+
+    fn operator() -> impl Strategy<Value = (&'static str, ConstraintOperator)> {
+        prop_oneof![
+            Just((">=", ConstraintOperator::GreaterOrEqual)),
+            Just(("≥", ConstraintOperator::GreaterOrEqual)),
+            Just(("<=", ConstraintOperator::LessOrEqual)),
+            Just(("≤", ConstraintOperator::LessOrEqual)),
+            Just((">", ConstraintOperator::Greater)),
+            Just(("<", ConstraintOperator::Less)),
+            Just(("==", ConstraintOperator::Equal)),
+            Just(("!=", ConstraintOperator::NotEqual)),
+            Just(("≠", ConstraintOperator::NotEqual)),
+        ]
+    }
+
+    /// Random conditions of up to three levels
+    fn condition() -> impl Strategy<Value = Condition> {
+        let leaf = (operator(), -1000i32..1000, 0u8..4).prop_map(|((_, op), whole, tenths)| {
+            Condition::Compare(op, f64::from(whole) + f64::from(tenths) / 4.0)
+        });
+        leaf.prop_recursive(3, 12, 3, |inner| {
+            prop_oneof![
+                prop::collection::vec(inner.clone(), 2..4).prop_map(Condition::And),
+                prop::collection::vec(inner, 2..4).prop_map(Condition::Or),
+            ]
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn prop_parsers_never_panic(source in "\\PC{0,24}") {
+            let _ = parse_condition(&source);
+            let _ = parse_score_range(&source);
+        }
+
+        #[test]
+        fn prop_parsers_never_panic_on_near_misses(
+            source in "[-<>=!≥≤≠&|() .0-9tf]{0,16}"
+        ) {
+            let _ = parse_condition(&source);
+            let _ = parse_score_range(&source);
+        }
+
+        #[test]
+        fn prop_deep_nesting_is_an_error(depth in MAX_NESTING + 1..20_000) {
+            let source = format!("{}>= 1{}", "(".repeat(depth), ")".repeat(depth));
+            let error = parse_condition(&source).unwrap_err().to_string();
+            prop_assert!(error.contains("nested deeper"), "{}", error);
+        }
+
+        #[test]
+        fn prop_comparison_matches_operator(
+            (symbol, operator) in operator(),
+            threshold in -1000.0f64..1000.0,
+            value in -1000.0f64..1000.0,
+            before in "[ \t]{0,3}",
+            between in "[ \t]{0,3}",
+            after in "[ \t]{0,3}",
+        ) {
+            let source = format!("{}{}{}{}{}", before, symbol, between, threshold, after);
+            let condition = parse_condition(&source).unwrap();
+            prop_assert_eq!(condition.evaluate(value), operator.compare(value, threshold));
+            prop_assert_eq!(condition.evaluate(threshold), operator.compare(threshold, threshold));
+        }
+
+        #[test]
+        fn prop_display_round_trips(condition in condition(), value in -1000.0f64..1000.0) {
+            let parsed = parse_condition(&condition.to_string()).unwrap();
+            prop_assert_eq!(parsed.evaluate(value), condition.evaluate(value));
+            prop_assert_eq!(parsed.to_string(), condition.to_string());
+        }
+
+        #[test]
+        fn prop_and_binds_tighter_than_or(
+            a in condition(), b in condition(), c in condition(), value in -1000.0f64..1000.0
+        ) {
+            let source = format!("({}) || ({}) && ({})", a, b, c);
+            let expected = a.evaluate(value) || (b.evaluate(value) && c.evaluate(value));
+            prop_assert_eq!(parse_condition(&source).unwrap().evaluate(value), expected);
+        }
+
+        #[test]
+        fn prop_range_contains_its_bounds(min in -500i32..500, width in 0i32..100) {
+            let max = min + width;
+            let bounds = parse_score_range(&format!("{}-{}", min, max)).unwrap();
+            prop_assert!(bounds.contains(min) && bounds.contains(max));
+            prop_assert!(!bounds.contains(min - 1) && !bounds.contains(max + 1));
+            let at_least = parse_score_range(&format!("≥{}", min)).unwrap();
+            prop_assert_eq!(at_least, parse_score_range(&format!(">= {}", min)).unwrap());
+        }
+    }
+}
@@ -0,0 +1,278 @@
+// constraints.rs
+// Cross-field validation constraints (e.g., "diastolic_bp < systolic_bp")
+
+use crate::config::{Constraint, ScoreDefinition};
+use crate::scores::calculator::{CalculationError, InputValue};
+use std::collections::HashMap;
+
+/// Comparison operator in a constraint expression
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConstraintOperator {
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+    Equal,
+    NotEqual,
+}
+
+impl ConstraintOperator {
+    pub(crate) fn compare(self, left: f64, right: f64) -> bool {
+        match self {
+            ConstraintOperator::Less => left < right,
+            ConstraintOperator::LessOrEqual => left <= right,
+            ConstraintOperator::Greater => left > right,
+            ConstraintOperator::GreaterOrEqual => left >= right,
+            ConstraintOperator::Equal => (left - right).abs() < f64::EPSILON,
+            ConstraintOperator::NotEqual => (left - right).abs() >= f64::EPSILON,
+        }
+    }
+
+    /// ASCII spelling, as written in constraints and conditions
+    pub fn symbol(self) -> &'static str {
+        match self {
+            ConstraintOperator::Less => "<",
+            ConstraintOperator::LessOrEqual => "<=",
+            ConstraintOperator::Greater => ">",
+            ConstraintOperator::GreaterOrEqual => ">=",
+            ConstraintOperator::Equal => "==",
+            ConstraintOperator::NotEqual => "!=",
+        }
+    }
+}
+
+/// One side of a constraint expression: a field reference or a numeric literal
+#[derive(Debug, Clone, PartialEq)]
+pub enum Operand {
+    Field(String),
+    Literal(f64),
+}
+
+/// A parsed constraint expression (`<operand> <operator> <operand>`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConstraintExpression {
+    pub left: Operand,
+    pub operator: ConstraintOperator,
+    pub right: Operand,
+}
+
+impl ConstraintExpression {
+    /// Field identifiers referenced by this expression
+    pub fn fields(&self) -> Vec<&str> {
+        [&self.left, &self.right]
+            .into_iter()
+            .filter_map(|operand| match operand {
+                Operand::Field(name) => Some(name.as_str()),
+                Operand::Literal(_) => None,
+            })
+            .collect()
+    }
+}
+
+/// Parse a constraint expression such as "diastolic_bp < systolic_bp" or "egfr < 60"
+pub fn parse_constraint(expression: &str) -> Result<ConstraintExpression, CalculationError> {
+    // Two-character operators must be tried before their one-character prefixes
+    const OPERATORS: [(&str, ConstraintOperator); 8] = [
+        ("<=", ConstraintOperator::LessOrEqual),
+        (">=", ConstraintOperator::GreaterOrEqual),
+        ("==", ConstraintOperator::Equal),
+        ("!=", ConstraintOperator::NotEqual),
+        ("≤", ConstraintOperator::LessOrEqual),
+        ("≥", ConstraintOperator::GreaterOrEqual),
+        ("<", ConstraintOperator::Less),
+        (">", ConstraintOperator::Greater),
+    ];
+
+    let parse_error = |reason: &str| CalculationError::ConditionParseError {
+        condition: expression.to_string(),
+        reason: reason.to_string(),
+    };
+
+    let (position, symbol, operator) = OPERATORS
+        .iter()
+        .filter_map(|(symbol, op)| expression.find(symbol).map(|pos| (pos, *symbol, *op)))
+        .min_by_key(|(pos, symbol, _)| (*pos, std::cmp::Reverse(symbol.len())))
+        .ok_or_else(|| {
+            parse_error("Missing comparison operator (expected: <, <=, >, >=, ==, !=)")
+        })?;
+
+    let left = parse_operand(&expression[..position])
+        .ok_or_else(|| parse_error("Invalid left operand"))?;
+    let right = parse_operand(&expression[position + symbol.len()..])
+        .ok_or_else(|| parse_error("Invalid right operand"))?;
+
+    Ok(ConstraintExpression {
+        left,
+        operator,
+        right,
+    })
+}
+
+fn parse_operand(text: &str) -> Option<Operand> {
+    let text = text.trim();
+    if text.is_empty() {
+        return None;
+    }
+
+    if let Ok(value) = text.parse::<f64>() {
+        return Some(Operand::Literal(value));
+    }
+
+    let is_identifier = text.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && !text.starts_with(|c: char| c.is_ascii_digit());
+    is_identifier.then(|| Operand::Field(text.to_string()))
+}
+
+/// Resolve an operand to a number. Returns `Ok(None)` if a referenced field was not entered.
+///
+/// Dates count as days since 1970-01-01, so two dates compare in time order.
+fn resolve_operand(
+    operand: &Operand,
+    inputs: &HashMap<String, InputValue>,
+) -> Result<Option<f64>, CalculationError> {
+    match operand {
+        Operand::Literal(value) => Ok(Some(*value)),
+        Operand::Field(name) => match inputs.get(name) {
+            None => Ok(None),
+            Some(InputValue::Number(n)) => Ok(Some(*n)),
+            Some(InputValue::Boolean(b)) => Ok(Some(if *b { 1.0 } else { 0.0 })),
+            Some(InputValue::Date(date)) => Ok(Some(date.day_number() as f64)),
+            Some(InputValue::Dropdown(_)) => Err(CalculationError::InvalidInput {
+                field: name.clone(),
+                reason: "Dropdown fields cannot be used in numeric constraints".to_string(),
+            }),
+            // Converted to the field's unit before constraints are checked
+            Some(InputValue::Quantity(_)) => Err(CalculationError::InvalidInput {
+                field: name.clone(),
+                reason: "Only number fields take a unit".to_string(),
+            }),
+        },
+    }
+}
+
+/// Check a single constraint against the inputs.
+///
+/// Constraints that reference a field the user has not entered are skipped;
+/// missing required fields are reported separately by the calculator.
+pub fn check_constraint(
+    constraint: &Constraint,
+    inputs: &HashMap<String, InputValue>,
+) -> Result<(), CalculationError> {
+    check_expression(
+        constraint,
+        &parse_constraint(&constraint.expression)?,
+        inputs,
+    )
+}
+
+/// Check `constraint`, already parsed as `expression`, against the inputs
+fn check_expression(
+    constraint: &Constraint,
+    expression: &ConstraintExpression,
+    inputs: &HashMap<String, InputValue>,
+) -> Result<(), CalculationError> {
+    let left = resolve_operand(&expression.left, inputs)?;
+    let right = resolve_operand(&expression.right, inputs)?;
+
+    match (left, right) {
+        (Some(left), Some(right)) if !expression.operator.compare(left, right) => {
+            Err(CalculationError::ConstraintViolated {
+                expression: constraint.expression.clone(),
+                message: constraint.message.clone(),
+            })
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Check all constraints of a score, returning the first violation
+pub fn check_constraints(
+    score_def: &ScoreDefinition,
+    inputs: &HashMap<String, InputValue>,
+) -> Result<(), CalculationError> {
+    for constraint in &score_def.constraints {
+        match score_def.conditions.constraint(&constraint.expression) {
+            Some(expression) => check_expression(constraint, expression, inputs)?,
+            None => check_constraint(constraint, inputs)?,
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::LocalizedText;
+
+    fn bp_constraint() -> Constraint {
+        Constraint {
+            expression: "diastolic_bp < systolic_bp".to_string(),
+            message: LocalizedText::from("Diastolic BP must be lower than systolic BP").with(
+                "de",
+                "Diastolischer Blutdruck muss niedriger als systolischer sein".to_string(),
+            ),
+        }
+    }
+
+    #[test]
+    fn test_parse_constraint() {
+        let expr = parse_constraint("diastolic_bp < systolic_bp").unwrap();
+        assert_eq!(expr.left, Operand::Field("diastolic_bp".to_string()));
+        assert_eq!(expr.operator, ConstraintOperator::Less);
+        assert_eq!(expr.right, Operand::Field("systolic_bp".to_string()));
+
+        let expr = parse_constraint("egfr <= 65").unwrap();
+        assert_eq!(expr.operator, ConstraintOperator::LessOrEqual);
+        assert_eq!(expr.right, Operand::Literal(65.0));
+        assert_eq!(expr.fields(), vec!["egfr"]);
+
+        let expr = parse_constraint("age≥18").unwrap();
+        assert_eq!(expr.operator, ConstraintOperator::GreaterOrEqual);
+    }
+
+    #[test]
+    fn test_parse_constraint_errors() {
+        assert!(parse_constraint("egfr 60").is_err());
+        assert!(parse_constraint("< 60").is_err());
+        assert!(parse_constraint("egfr < ").is_err());
+        assert!(parse_constraint("egfr-total < 60").is_err());
+    }
+
+    #[test]
+    fn test_check_constraint() {
+        let constraint = bp_constraint();
+
+        let mut inputs = HashMap::new();
+        inputs.insert("systolic_bp".to_string(), InputValue::Number(120.0));
+        inputs.insert("diastolic_bp".to_string(), InputValue::Number(80.0));
+        assert!(check_constraint(&constraint, &inputs).is_ok());
+
+        inputs.insert("diastolic_bp".to_string(), InputValue::Number(130.0));
+        match check_constraint(&constraint, &inputs) {
+            Err(CalculationError::ConstraintViolated { message, .. }) => {
+                assert!(message.text("de").starts_with("Diastolischer"));
+            }
+            other => panic!("Expected ConstraintViolated, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_constraint_skipped_when_field_missing() {
+        let mut inputs = HashMap::new();
+        inputs.insert("systolic_bp".to_string(), InputValue::Number(120.0));
+        assert!(check_constraint(&bp_constraint(), &inputs).is_ok());
+    }
+
+    #[test]
+    fn test_constraints_parsed_at_load() {
+        let library = crate::scores::load_bundled_scores();
+        let kfre = library.get_score("kfre").unwrap();
+        assert!(kfre.conditions.constraint("egfr < 60").is_some());
+
+        let inputs = HashMap::from([("egfr".to_string(), InputValue::Number(75.0))]);
+        assert!(matches!(
+            check_constraints(kfre, &inputs),
+            Err(CalculationError::ConstraintViolated { expression, .. }) if expression == "egfr < 60"
+        ));
+    }
+}
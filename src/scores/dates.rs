@@ -0,0 +1,234 @@
+// dates.rs
+// Calendar dates of date inputs and the time elapsed between them
+//
+// Dates are entered and stored as ISO 8601 (`1956-03-14`). The calculation core
+// has no clock: the time elapsed since a date (e.g., the age from a date of birth)
+// is always measured until another date input, such as the date of assessment,
+// so the same inputs give the same result on any day.
+
+use crate::config::ElapsedUnit;
+use schemars::gen::SchemaGenerator;
+use schemars::schema::{InstanceType, Schema, SchemaObject};
+use schemars::JsonSchema;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+/// A day of the proleptic Gregorian calendar, years 1 to 9999
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CalendarDate {
+    year: i32,
+    month: u32,
+    day: u32,
+}
+
+impl CalendarDate {
+    /// The date, if it exists (e.g., not 29 February 2023)
+    pub fn new(year: i32, month: u32, day: u32) -> Option<Self> {
+        let valid = (1..=9999).contains(&year)
+            && (1..=12).contains(&month)
+            && (1..=days_in_month(year, month)).contains(&day);
+        valid.then_some(Self { year, month, day })
+    }
+
+    pub fn year(self) -> i32 {
+        self.year
+    }
+
+    pub fn month(self) -> u32 {
+        self.month
+    }
+
+    pub fn day(self) -> u32 {
+        self.day
+    }
+
+    /// Days since 1970-01-01 (negative before)
+    pub fn day_number(self) -> i64 {
+        // Days from civil, counting years from March so the leap day comes last
+        let year = i64::from(self.year) - i64::from(self.month <= 2);
+        let era = year.div_euclid(400);
+        let year_of_era = year - era * 400;
+        let month = i64::from(self.month);
+        let day_of_year =
+            (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + i64::from(self.day) - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        era * 146_097 + day_of_era - 719_468
+    }
+
+    /// Whole `unit`s from this date until `until`; negative if `until` is earlier
+    ///
+    /// Years are counted like an age: a year is complete on the same day and month,
+    /// and a year from 29 February is complete on 1 March in non-leap years.
+    pub fn elapsed_until(self, until: CalendarDate, unit: ElapsedUnit) -> i64 {
+        let days = until.day_number() - self.day_number();
+        match unit {
+            ElapsedUnit::Days => days,
+            ElapsedUnit::Weeks => days.div_euclid(7),
+            ElapsedUnit::Years => {
+                let years = i64::from(until.year - self.year);
+                let (later, earlier) = if days >= 0 {
+                    (until, self)
+                } else {
+                    (self, until)
+                };
+                let incomplete = (later.month, later.day) < (earlier.month, earlier.day);
+                years - years.signum() * i64::from(incomplete)
+            }
+        }
+    }
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        4 | 6 | 9 | 11 => 30,
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        _ => 31,
+    }
+}
+
+/// Error parsing a date that is not written `YYYY-MM-DD` or does not exist
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidDate(pub String);
+
+impl fmt::Display for InvalidDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "'{}' is not a date (YYYY-MM-DD)", self.0)
+    }
+}
+
+impl std::error::Error for InvalidDate {}
+
+impl FromStr for CalendarDate {
+    type Err = InvalidDate;
+
+    /// Parse an ISO 8601 date, `YYYY-MM-DD`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || InvalidDate(s.to_string());
+        let bytes = s.as_bytes();
+        if bytes.len() != 10
+            || bytes[4] != b'-'
+            || bytes[7] != b'-'
+            || !s
+                .bytes()
+                .enumerate()
+                .all(|(i, b)| i == 4 || i == 7 || b.is_ascii_digit())
+        {
+            return Err(invalid());
+        }
+        // Checked above to be ASCII digits
+        let number = |range: std::ops::Range<usize>| s[range].parse::<u32>().unwrap_or(0);
+        CalendarDate::new(number(0..4) as i32, number(5..7), number(8..10)).ok_or_else(invalid)
+    }
+}
+
+impl fmt::Display for CalendarDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+impl Serialize for CalendarDate {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for CalendarDate {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        text.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+impl JsonSchema for CalendarDate {
+    fn schema_name() -> String {
+        "CalendarDate".to_string()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        SchemaObject {
+            instance_type: Some(InstanceType::String.into()),
+            format: Some("date".to_string()),
+            ..Default::default()
+        }
+        .into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> CalendarDate {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse_and_display() {
+        assert_eq!(date("1956-03-14"), CalendarDate::new(1956, 3, 14).unwrap());
+        assert_eq!(date("2024-02-29").to_string(), "2024-02-29");
+        for invalid in [
+            "2023-02-29",
+            "1956-13-01",
+            "1956-3-14",
+            "14.03.1956",
+            "0000-01-01",
+            "",
+        ] {
+            assert!(invalid.parse::<CalendarDate>().is_err(), "{invalid}");
+        }
+        assert_eq!(date("1970-01-01").day_number(), 0);
+        assert_eq!(date("2000-03-01").day_number(), 11_017);
+        assert_eq!(date("1969-12-31").day_number(), -1);
+    }
+
+    #[test]
+    fn test_elapsed_until() {
+        let birth = date("1956-03-14");
+        assert_eq!(
+            birth.elapsed_until(date("2024-03-13"), ElapsedUnit::Years),
+            67
+        );
+        assert_eq!(
+            birth.elapsed_until(date("2024-03-14"), ElapsedUnit::Years),
+            68
+        );
+        assert_eq!(
+            date("2024-03-14").elapsed_until(birth, ElapsedUnit::Years),
+            -68
+        );
+
+        let leap_day = date("2020-02-29");
+        assert_eq!(
+            leap_day.elapsed_until(date("2021-02-28"), ElapsedUnit::Years),
+            0
+        );
+        assert_eq!(
+            leap_day.elapsed_until(date("2021-03-01"), ElapsedUnit::Years),
+            1
+        );
+
+        let last_period = date("2024-01-01");
+        assert_eq!(
+            last_period.elapsed_until(date("2024-03-01"), ElapsedUnit::Days),
+            60
+        );
+        assert_eq!(
+            last_period.elapsed_until(date("2024-03-01"), ElapsedUnit::Weeks),
+            8
+        );
+        assert_eq!(
+            last_period.elapsed_until(date("2023-12-31"), ElapsedUnit::Weeks),
+            -1
+        );
+    }
+
+    #[test]
+    fn test_serialized_as_iso_string() {
+        let json = serde_json::to_string(&date("1956-03-14")).unwrap();
+        assert_eq!(json, "\"1956-03-14\"");
+        assert!(serde_json::from_str::<CalendarDate>("\"class_ii\"").is_err());
+    }
+}
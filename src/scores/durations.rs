@@ -0,0 +1,146 @@
+// durations.rs
+// Time spans of duration inputs, as entered and as displayed
+//
+// A duration is scored as a number in its field's `duration_unit` (e.g., hours
+// since symptom onset), so requests and test cases give it as a plain number.
+// People enter it as they would write it down: `4:30`, `4 h 30 min`, `90 min`,
+// or a plain number in the field's unit.
+
+use crate::config::DurationUnit;
+
+/// The duration written in `text`, in `unit`; `None` if it is not a duration
+///
+/// Accepts hours and minutes (`4:30`), amounts with units (`1 d 6 h`, `4h30m`,
+/// `2,5 Std`) and plain numbers, which are taken to be in `unit`. Durations are
+/// never negative.
+pub fn parse_duration(text: &str, unit: DurationUnit) -> Option<f64> {
+    let text = text.trim().to_lowercase();
+    if text.is_empty() {
+        return None;
+    }
+    let minutes = if let Some((hours, minutes)) = text.split_once(':') {
+        clock_minutes(hours, minutes)?
+    } else if let Some(number) = parse_amount(&text) {
+        return Some(number);
+    } else {
+        amounts_minutes(&text)?
+    };
+    Some(minutes / unit.minutes())
+}
+
+/// Display text of a duration in `unit`, to the minute, e.g. "1 d 4 h 30 min"
+pub fn format_duration(value: f64, unit: DurationUnit) -> String {
+    let total = (value * unit.minutes()).round().max(0.0) as u64;
+    let parts: Vec<String> = [
+        (total / 1440, "d"),
+        (total % 1440 / 60, "h"),
+        (total % 60, "min"),
+    ]
+    .into_iter()
+    .filter(|(amount, _)| *amount > 0)
+    .map(|(amount, symbol)| format!("{} {}", amount, symbol))
+    .collect();
+    if parts.is_empty() {
+        "0 min".to_string()
+    } else {
+        parts.join(" ")
+    }
+}
+
+/// Minutes of `hours:minutes`, minutes written with two digits
+fn clock_minutes(hours: &str, minutes: &str) -> Option<f64> {
+    let minutes = minutes.trim();
+    if minutes.len() != 2 || !minutes.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let hours: u64 = hours.trim().parse().ok()?;
+    let minutes: u64 = minutes.parse().ok()?;
+    if minutes >= 60 {
+        return None;
+    }
+    hours
+        .checked_mul(60)?
+        .checked_add(minutes)
+        .map(|minutes| minutes as f64)
+}
+
+/// Minutes of a sequence of amounts with units, e.g. `1 d 6 h 30 min`
+fn amounts_minutes(text: &str) -> Option<f64> {
+    let mut rest = text;
+    let mut minutes = 0.0;
+    while !rest.is_empty() {
+        let amount_end = rest
+            .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == ','))
+            .unwrap_or(rest.len());
+        let amount = parse_amount(&rest[..amount_end])?;
+        rest = rest[amount_end..].trim_start();
+        let word_end = rest
+            .find(|c: char| !c.is_alphabetic())
+            .unwrap_or(rest.len());
+        minutes += amount * unit_minutes(&rest[..word_end])?;
+        rest = rest[word_end..].trim_start();
+    }
+    Some(minutes)
+}
+
+/// A non-negative number, with a decimal point or comma
+fn parse_amount(text: &str) -> Option<f64> {
+    text.replace(',', ".")
+        .parse::<f64>()
+        .ok()
+        .filter(|amount| amount.is_finite() && *amount >= 0.0)
+}
+
+/// Length in minutes of a unit word (English or German)
+fn unit_minutes(word: &str) -> Option<f64> {
+    let unit = match word {
+        "m" | "min" | "mins" | "minute" | "minutes" | "minuten" => DurationUnit::Minutes,
+        "h" | "hr" | "hrs" | "hour" | "hours" | "std" | "stunde" | "stunden" => DurationUnit::Hours,
+        "d" | "day" | "days" | "t" | "tag" | "tage" => DurationUnit::Days,
+        _ => return None,
+    };
+    Some(unit.minutes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_duration() {
+        let hours = |text| parse_duration(text, DurationUnit::Hours);
+        assert_eq!(hours("4:30"), Some(4.5));
+        assert_eq!(hours("4 h 30 min"), Some(4.5));
+        assert_eq!(hours("4h30m"), Some(4.5));
+        assert_eq!(hours("2,5 Std"), Some(2.5));
+        assert_eq!(hours("1 d 6 h"), Some(30.0));
+        assert_eq!(hours("3.5"), Some(3.5));
+        assert_eq!(parse_duration("1:15", DurationUnit::Minutes), Some(75.0));
+        assert_eq!(parse_duration("90", DurationUnit::Minutes), Some(90.0));
+        assert_eq!(parse_duration("36 h", DurationUnit::Days), Some(1.5));
+
+        assert_eq!(hours("99999999:00"), Some(99_999_999.0));
+        for invalid in [
+            "",
+            "-2",
+            "4:75",
+            "4:5",
+            "h",
+            "4 weeks",
+            "4 h x",
+            "nan",
+            "99999999999999999999:00",
+        ] {
+            assert_eq!(hours(invalid), None, "{invalid}");
+        }
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(4.5, DurationUnit::Hours), "4 h 30 min");
+        assert_eq!(format_duration(150.0, DurationUnit::Minutes), "2 h 30 min");
+        assert_eq!(format_duration(1.5, DurationUnit::Days), "1 d 12 h");
+        assert_eq!(format_duration(3.0, DurationUnit::Hours), "3 h");
+        assert_eq!(format_duration(0.0, DurationUnit::Minutes), "0 min");
+    }
+}
@@ -2,6 +2,7 @@
 // Built-in formula calculations for scores that aren't simple point sums
 // (e.g., eGFR CKD-EPI 2021, KFRE)
 
+use crate::config::LocalizedText;
 use crate::scores::calculator::{CalculationError, FieldScore, InputValue};
 use std::collections::HashMap;
 
@@ -75,33 +76,45 @@ fn calculate_egfr_ckd_epi_2021(
     let field_scores = vec![
         FieldScore {
             field: "age".to_string(),
-            label: format!("Age: {:.0} years", age),
-            label_de: format!("Alter: {:.0} Jahre", age),
+            label: LocalizedText::bilingual(
+                format!("Age: {:.0} years", age),
+                format!("Alter: {:.0} Jahre", age),
+            ),
             points: 0,
+            selection: None,
         },
         FieldScore {
             field: "sex".to_string(),
-            label: format!("Sex: {}", sex_label),
-            label_de: format!("Geschlecht: {}", sex_label_de),
+            label: LocalizedText::bilingual(
+                format!("Sex: {}", sex_label),
+                format!("Geschlecht: {}", sex_label_de),
+            ),
             points: 0,
+            selection: None,
         },
         FieldScore {
             field: "creatinine".to_string(),
-            label: format!(
-                "Creatinine: {:.0} μmol/L ({:.2} mg/dL)",
-                creatinine_umol, scr
-            ),
-            label_de: format!(
-                "Kreatinin: {:.0} μmol/L ({:.2} mg/dL)",
-                creatinine_umol, scr
+            label: LocalizedText::bilingual(
+                format!(
+                    "Creatinine: {:.0} μmol/L ({:.2} mg/dL)",
+                    creatinine_umol, scr
+                ),
+                format!(
+                    "Kreatinin: {:.0} μmol/L ({:.2} mg/dL)",
+                    creatinine_umol, scr
+                ),
             ),
             points: 0,
+            selection: None,
         },
         FieldScore {
             field: "result".to_string(),
-            label: format!("eGFR: {} mL/min/1.73m²", egfr_rounded),
-            label_de: format!("eGFR: {} mL/min/1,73m²", egfr_rounded),
+            label: LocalizedText::bilingual(
+                format!("eGFR: {} mL/min/1.73m²", egfr_rounded),
+                format!("eGFR: {} mL/min/1,73m²", egfr_rounded),
+            ),
             points: egfr_rounded,
+            selection: None,
         },
     ];
 
@@ -159,33 +172,48 @@ fn calculate_kfre_4var(
     let field_scores = vec![
         FieldScore {
             field: "age".to_string(),
-            label: format!("Age: {:.0} years", age),
-            label_de: format!("Alter: {:.0} Jahre", age),
+            label: LocalizedText::bilingual(
+                format!("Age: {:.0} years", age),
+                format!("Alter: {:.0} Jahre", age),
+            ),
             points: 0,
+            selection: None,
         },
         FieldScore {
             field: "sex".to_string(),
-            label: format!("Sex: {}", sex_label),
-            label_de: format!("Geschlecht: {}", sex_label_de),
+            label: LocalizedText::bilingual(
+                format!("Sex: {}", sex_label),
+                format!("Geschlecht: {}", sex_label_de),
+            ),
             points: 0,
+            selection: None,
         },
         FieldScore {
             field: "egfr".to_string(),
-            label: format!("eGFR: {:.0} mL/min/1.73m²", egfr),
-            label_de: format!("eGFR: {:.0} mL/min/1,73m²", egfr),
+            label: LocalizedText::bilingual(
+                format!("eGFR: {:.0} mL/min/1.73m²", egfr),
+                format!("eGFR: {:.0} mL/min/1,73m²", egfr),
+            ),
             points: 0,
+            selection: None,
         },
         FieldScore {
             field: "acr".to_string(),
-            label: format!("ACR: {:.1} mg/mmol ({:.0} mg/g)", acr_mg_mmol, acr_mg_g),
-            label_de: format!("ACR: {:.1} mg/mmol ({:.0} mg/g)", acr_mg_mmol, acr_mg_g),
+            label: LocalizedText::bilingual(
+                format!("ACR: {:.1} mg/mmol ({:.0} mg/g)", acr_mg_mmol, acr_mg_g),
+                format!("ACR: {:.1} mg/mmol ({:.0} mg/g)", acr_mg_mmol, acr_mg_g),
+            ),
             points: 0,
+            selection: None,
         },
         FieldScore {
             field: "result".to_string(),
-            label: format!("2-year risk: {}%", risk_percent),
-            label_de: format!("2-Jahres-Risiko: {}%", risk_percent),
+            label: LocalizedText::bilingual(
+                format!("2-year risk: {}%", risk_percent),
+                format!("2-Jahres-Risiko: {}%", risk_percent),
+            ),
             points: risk_percent,
+            selection: None,
         },
     ];
 
@@ -1,12 +1,33 @@
 // loader.rs
-// Loads clinical score definitions from YAML files
-
-use crate::config::{ScoreDefinition, Specialty};
+// Loads clinical score definitions from YAML, JSON or TOML files
+
+use crate::config::{
+    upgrade_legacy_references, upgrade_legacy_translations, InputField, InputType, InputWidget,
+    PointsTable, PointsValue, ScoreCategory, ScoreDefinition, Specialty, FALLBACK_LANGUAGE,
+};
+use crate::scores::bases::{BaseDefinitions, BASES_DIR};
+use crate::scores::calculator::interpretation_coverage;
+use crate::scores::condition::{parse_condition, CompiledConditions};
+use crate::scores::pack::{installed_packs, PackIntegrity, PackStatus, MANIFEST_FILE};
+#[cfg(feature = "app")]
+use iced::futures::channel::mpsc;
+#[cfg(feature = "app")]
+use iced::futures::Stream;
+use include_dir::{include_dir, Dir, File};
+use std::cmp::Ordering;
 use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 use thiserror::Error;
 
+/// Score library shipped with the application, embedded at compile time
+static BUNDLED_SCORES: Dir<'_> = include_dir!("$CARGO_MANIFEST_DIR/scores");
+
+/// The embedded `scores/` directory
+pub(crate) fn bundled_dir() -> &'static Dir<'static> {
+    &BUNDLED_SCORES
+}
+
 /// Errors that can occur when loading score definitions
 #[derive(Error, Debug)]
 pub enum ScoreLoadError {
@@ -28,11 +49,110 @@ pub enum ScoreLoadError {
         source: serde_yaml::Error,
     },
 
+    #[error("Failed to parse JSON in {path}: {source}")]
+    JsonParse {
+        path: String,
+        source: serde_json::Error,
+    },
+
+    #[error("Failed to parse TOML in {path}: {source}")]
+    TomlParse {
+        path: String,
+        source: toml::de::Error,
+    },
+
     #[error("Invalid score definition in {path}: {reason}")]
     InvalidScore { path: String, reason: String },
 
     #[error("No scores directory found at {path}")]
     ScoresDirectoryNotFound { path: String },
+
+    #[error("Score pack {path} failed verification: {reason}")]
+    InvalidPack { path: String, reason: String },
+
+    #[error("Duplicate score id '{id}' in {first} and {second}")]
+    DuplicateScoreId {
+        id: String,
+        first: String,
+        second: String,
+    },
+}
+
+/// File format of a score definition, determined by its extension
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScoreFormat {
+    /// `.yaml` / `.yml` (the format used by the bundled library)
+    Yaml,
+    /// `.json`, e.g. for definitions generated by other tools
+    Json,
+    /// `.toml`
+    Toml,
+}
+
+impl ScoreFormat {
+    /// Format for a file path, or `None` if the extension is not a score format
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "yaml" | "yml" => Some(ScoreFormat::Yaml),
+            "json" => Some(ScoreFormat::Json),
+            "toml" => Some(ScoreFormat::Toml),
+            _ => None,
+        }
+    }
+}
+
+/// Where a score definition in the library was loaded from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScoreOrigin {
+    /// Shipped with the application (embedded at compile time)
+    Bundled,
+    /// Loaded from a score directory on disk (user or hospital-specific)
+    Directory(PathBuf),
+}
+
+/// Criteria for `ScoreLibrary::filter`; unset criteria match every score
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ScoreFilter {
+    pub specialty: Option<Specialty>,
+    pub category: Option<ScoreCategory>,
+    /// Exact tag match (any language, case-insensitive)
+    pub tag: Option<String>,
+    /// Substring of the name, aliases, tags or clinical question (any language, case-insensitive)
+    pub text: Option<String>,
+}
+
+impl ScoreFilter {
+    /// Whether `score` satisfies every set criterion
+    pub fn matches(&self, score: &ScoreDefinition) -> bool {
+        let metadata = &score.metadata;
+
+        if self.specialty.is_some_and(|s| s != score.specialty) {
+            return false;
+        }
+        if self.category.is_some() && self.category != metadata.category {
+            return false;
+        }
+        if let Some(tag) = &self.tag {
+            if !metadata.has_tag(tag) {
+                return false;
+            }
+        }
+        if let Some(text) = &self.text {
+            let needle = text.to_lowercase();
+            let found = score
+                .name
+                .values()
+                .chain(score.aliases.values().flatten())
+                .chain(metadata.tags.values().flatten())
+                .chain(metadata.clinical_question.iter().flat_map(|q| q.values()))
+                .any(|haystack| haystack.to_lowercase().contains(&needle));
+            if !found {
+                return false;
+            }
+        }
+        true
+    }
 }
 
 /// Collection of loaded score definitions organized by specialty
@@ -41,34 +161,175 @@ pub struct ScoreLibrary {
     /// All loaded scores, keyed by score ID (filename without extension)
     pub scores: HashMap<String, ScoreDefinition>,
 
-    /// Scores organized by specialty for quick filtering
+    /// Score IDs by specialty, in display order (see [`ScoreLibrary::iter`])
     pub by_specialty: HashMap<Specialty, Vec<String>>,
 
-    /// Path where scores were loaded from
-    #[allow(dead_code)]
-    pub source_path: PathBuf,
+    /// Origin of each score, keyed by score ID
+    pub origins: HashMap<String, ScoreOrigin>,
+
+    /// Installed score packs found in the loaded directories, with their integrity
+    pub packs: Vec<PackStatus>,
+
+    /// Score IDs keyed by normalized name or alias (see [`normalize_alias`])
+    pub aliases: HashMap<String, Vec<String>>,
 }
 
 impl ScoreLibrary {
+    fn empty() -> Self {
+        Self {
+            scores: HashMap::new(),
+            by_specialty: HashMap::new(),
+            origins: HashMap::new(),
+            packs: Vec::new(),
+            aliases: HashMap::new(),
+        }
+    }
+
+    /// Add a score, replacing any existing score with the same ID
+    pub fn insert(&mut self, score_id: String, score: ScoreDefinition, origin: ScoreOrigin) {
+        if let Some(previous) = self.scores.get(&score_id) {
+            if let Some(ids) = self.by_specialty.get_mut(&previous.specialty) {
+                ids.retain(|id| id != &score_id);
+            }
+            self.aliases.retain(|_, ids| {
+                ids.retain(|id| id != &score_id);
+                !ids.is_empty()
+            });
+        }
+
+        for alias in score.name.values().chain(score.aliases.values().flatten()) {
+            let ids = self.aliases.entry(normalize_alias(alias)).or_default();
+            if !ids.contains(&score_id) {
+                ids.push(score_id.clone());
+            }
+        }
+        // The list is kept sorted, so the new score is inserted at its place
+        // rather than sorting the whole list on every insert
+        let ids = self.by_specialty.entry(score.specialty).or_default();
+        let position = ids.partition_point(|id| {
+            display_order((id, &self.scores[id]), (&score_id, &score)) == Ordering::Less
+        });
+        ids.insert(position, score_id.clone());
+        self.origins.insert(score_id.clone(), origin);
+        self.scores.insert(score_id, score);
+    }
+
+    /// All scores with their IDs, in display order: by specialty (see
+    /// [`Specialty::ALL`]), then by `sort_order`, English name and ID
+    ///
+    /// The order does not depend on the interface language, so lists keep their
+    /// order when the language is switched.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &ScoreDefinition)> {
+        Specialty::ALL
+            .iter()
+            .filter_map(|specialty| self.by_specialty.get(specialty))
+            .flatten()
+            .map(|id| (id.as_str(), &self.scores[id]))
+    }
+
+    /// Where a score was loaded from
+    pub fn origin(&self, score_id: &str) -> Option<&ScoreOrigin> {
+        self.origins.get(score_id)
+    }
+
+    /// The score that supersedes `score_id`, if it names one and it is loaded
+    pub fn replacement_for(&self, score_id: &str) -> Option<(&str, &ScoreDefinition)> {
+        let replacement_id = self.scores.get(score_id)?.superseded_by.as_deref()?;
+        self.scores
+            .get_key_value(replacement_id)
+            .map(|(id, score)| (id.as_str(), score))
+    }
+
+    /// Log a warning for every `superseded_by` that names a score not in the library
+    fn warn_missing_replacements(&self) {
+        for (score_id, score) in self.iter() {
+            if let Some(replacement) = &score.superseded_by {
+                if !self.scores.contains_key(replacement) {
+                    tracing::warn!(
+                        score = score_id,
+                        "Score '{}' is superseded by unknown score '{}'",
+                        score_id,
+                        replacement
+                    );
+                }
+            }
+        }
+    }
+
+    /// Log a warning for every achievable total a score cannot interpret
+    /// (see [`interpretation_coverage`]); such totals fail at calculation time
+    fn warn_interpretation_coverage(&self) {
+        let mut ids: Vec<&String> = self.scores.keys().collect();
+        ids.sort();
+        for score_id in ids {
+            for issue in interpretation_coverage(&self.scores[score_id]) {
+                tracing::warn!(score = %score_id, "Score '{}': {}", score_id, issue);
+            }
+        }
+    }
+
+    /// IDs of the scores whose name or alias (in any language) is `name`,
+    /// ignoring case, spaces and punctuation
+    pub fn find_by_alias(&self, name: &str) -> Vec<&str> {
+        self.aliases
+            .get(&normalize_alias(name))
+            .map(|ids| ids.iter().map(String::as_str).collect())
+            .unwrap_or_default()
+    }
+
     /// Get a score by its ID
     pub fn get_score(&self, score_id: &str) -> Option<&ScoreDefinition> {
         self.scores.get(score_id)
     }
 
-    /// Get all scores for a specific specialty
+    /// Get all scores for a specific specialty, in display order
     pub fn get_scores_for_specialty(&self, specialty: Specialty) -> Vec<&ScoreDefinition> {
+        self.specialty_scores(specialty)
+            .into_iter()
+            .map(|(_, score)| score)
+            .collect()
+    }
+
+    /// All scores of a specialty with their IDs, in display order
+    pub fn specialty_scores(&self, specialty: Specialty) -> Vec<(&str, &ScoreDefinition)> {
         self.by_specialty
             .get(&specialty)
-            .map(|ids| ids.iter().filter_map(|id| self.scores.get(id)).collect())
+            .map(|ids| {
+                ids.iter()
+                    .filter_map(|id| self.scores.get_key_value(id))
+                    .map(|(id, score)| (id.as_str(), score))
+                    .collect()
+            })
             .unwrap_or_default()
     }
 
-    /// Get all available specialties
-    #[allow(dead_code)]
+    /// Get all specialties that have at least one score, in display order
+    /// (see [`Specialty::ALL`])
     pub fn get_specialties(&self) -> Vec<Specialty> {
-        let mut specialties: Vec<_> = self.by_specialty.keys().copied().collect();
-        specialties.sort_by_key(|s| format!("{:?}", s));
-        specialties
+        Specialty::ALL
+            .into_iter()
+            .filter(|s| self.by_specialty.get(s).is_some_and(|ids| !ids.is_empty()))
+            .collect()
+    }
+
+    /// Specialties that have scores, in display order, with the number of scores in each
+    pub fn specialty_counts(&self) -> Vec<(Specialty, usize)> {
+        self.get_specialties()
+            .into_iter()
+            .map(|specialty| (specialty, self.by_specialty[&specialty].len()))
+            .collect()
+    }
+
+    /// All scores matching `filter`, sorted by score ID
+    pub fn filter(&self, filter: &ScoreFilter) -> Vec<(&str, &ScoreDefinition)> {
+        let mut matches: Vec<_> = self
+            .scores
+            .iter()
+            .filter(|(_, score)| filter.matches(score))
+            .map(|(id, score)| (id.as_str(), score))
+            .collect();
+        matches.sort_by_key(|(id, _)| *id);
+        matches
     }
 
     /// Get total number of loaded scores
@@ -77,10 +338,36 @@ impl ScoreLibrary {
     }
 }
 
+/// Order of two scores of the same specialty in lists: scores with a `sort_order`
+/// first (lowest first), then by English name, ignoring case, and by ID
+fn display_order(
+    (a_id, a): (&str, &ScoreDefinition),
+    (b_id, b): (&str, &ScoreDefinition),
+) -> Ordering {
+    let key = |score: &ScoreDefinition| {
+        (
+            score.sort_order.is_none(),
+            score.sort_order,
+            score.name.text(FALLBACK_LANGUAGE).to_lowercase(),
+        )
+    };
+    key(a).cmp(&key(b)).then_with(|| a_id.cmp(b_id))
+}
+
+/// Key for the alias index: lowercase letters and digits only, so that
+/// "CHA2DS2-VASc", "cha2ds2 vasc" and "CHA2DS2VASc" are the same name
+pub fn normalize_alias(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
 /// Load all score definitions from a directory
 ///
-/// Recursively scans the directory for .yaml files and loads them as score definitions.
-/// Returns a ScoreLibrary containing all successfully loaded scores.
+/// Recursively scans the directory for score files (YAML, JSON or TOML) and loads them.
+/// Returns a ScoreLibrary containing all successfully loaded scores; invalid files
+/// are skipped with a warning (see [`load_all_scores_strict`]).
 ///
 /// # Arguments
 ///
@@ -103,8 +390,20 @@ impl ScoreLibrary {
 /// println!("Loaded {} scores", library.count());
 /// ```
 pub fn load_all_scores<P: AsRef<Path>>(scores_dir: P) -> Result<ScoreLibrary, ScoreLoadError> {
-    let scores_dir = scores_dir.as_ref();
+    load_directory(scores_dir.as_ref(), false)
+}
+
+/// Like [`load_all_scores`], but fails on the first invalid score file (or pack that
+/// fails verification) instead of skipping it
+///
+/// For CI pipelines and deployments that must guarantee the full library loaded.
+pub fn load_all_scores_strict<P: AsRef<Path>>(
+    scores_dir: P,
+) -> Result<ScoreLibrary, ScoreLoadError> {
+    load_directory(scores_dir.as_ref(), true)
+}
 
+fn load_directory(scores_dir: &Path, strict: bool) -> Result<ScoreLibrary, ScoreLoadError> {
     // Check if directory exists
     if !scores_dir.exists() {
         return Err(ScoreLoadError::ScoresDirectoryNotFound {
@@ -112,52 +411,341 @@ pub fn load_all_scores<P: AsRef<Path>>(scores_dir: P) -> Result<ScoreLibrary, Sc
         });
     }
 
-    let mut scores = HashMap::new();
-    let mut by_specialty: HashMap<Specialty, Vec<String>> = HashMap::new();
+    let mut bases = BaseDefinitions::bundled().clone();
+    bases.load_dir(scores_dir, strict)?;
+
+    let score_files = score_files_to_load(scores_dir)?;
+    let mut library = ScoreLibrary::empty();
+    let total = score_files.len();
+    load_scores_into(
+        &mut library,
+        scores_dir,
+        score_files,
+        strict,
+        &bases,
+        &mut ProgressReporter::new(total, &mut |_| {}),
+    )?;
+    library.warn_missing_replacements();
+    library.warn_interpretation_coverage();
+    Ok(library)
+}
+
+/// Load the score library embedded in the binary
+///
+/// Works regardless of the working directory the application is launched from.
+pub fn load_bundled_scores() -> ScoreLibrary {
+    let files = bundled_score_files();
+    let total = files.len();
+    bundled_library(files, false, &mut ProgressReporter::new(total, &mut |_| {}))
+        .unwrap_or_else(|_| ScoreLibrary::empty())
+}
+
+/// Score files of the embedded library (templates and bases excluded)
+fn bundled_score_files() -> Vec<&'static File<'static>> {
+    let mut files = Vec::new();
+    let mut dirs = vec![&BUNDLED_SCORES];
+    while let Some(dir) = dirs.pop() {
+        dirs.extend(dir.dirs().filter(|sub| !sub.path().ends_with(BASES_DIR)));
+        files.extend(dir.files().filter(|file| {
+            let path = file.path();
+            ScoreFormat::from_path(path).is_some() && !path.to_string_lossy().contains("template")
+        }));
+    }
+    files
+}
+
+fn bundled_library(
+    files: Vec<&File>,
+    strict: bool,
+    progress: &mut ProgressReporter,
+) -> Result<ScoreLibrary, ScoreLoadError> {
+    let mut library = ScoreLibrary::empty();
+    let mut seen_ids = HashMap::new();
+
+    for file in files {
+        let path = file.path();
+        let result = file
+            .contents_utf8()
+            .ok_or_else(|| ScoreLoadError::InvalidScore {
+                path: path.display().to_string(),
+                reason: "File is not valid UTF-8".to_string(),
+            })
+            .and_then(|contents| parse_score(contents, path, BaseDefinitions::bundled()));
+        progress.file_done();
+
+        match result {
+            Ok(score) => {
+                let score_id = score_id_for(&score, path);
+                if let Err(e) = check_unique_id(&mut seen_ids, &score_id, path) {
+                    skip_or_fail(strict, e, "Skipping bundled score")?;
+                    continue;
+                }
+                library.insert(score_id, score, ScoreOrigin::Bundled);
+            }
+            Err(e) => skip_or_fail(
+                strict,
+                e,
+                &format!("Failed to load bundled score {:?}", path),
+            )?,
+        }
+    }
+
+    Ok(library)
+}
+
+/// Load the bundled library and merge scores from additional directories
+///
+/// Directories are merged in order: definitions override bundled ones (and those
+/// from earlier directories) with the same ID (filename without extension); new
+/// IDs are added. Missing directories are skipped.
+pub fn load_score_library<P: AsRef<Path>>(dirs: &[P]) -> Result<ScoreLibrary, ScoreLoadError> {
+    merge_score_library(dirs, false, &mut |_| {})
+}
+
+/// Like [`load_score_library`], but fails on the first invalid score file (bundled or
+/// from any directory) instead of skipping it
+pub fn load_score_library_strict<P: AsRef<Path>>(
+    dirs: &[P],
+) -> Result<ScoreLibrary, ScoreLoadError> {
+    merge_score_library(dirs, true, &mut |_| {})
+}
+
+/// Like [`load_score_library`] (or [`load_score_library_strict`]), calling `on_progress`
+/// once before the first file and after every processed score file
+///
+/// The total is known up front: all directories are scanned before loading starts.
+pub fn load_score_library_with_progress<P: AsRef<Path>>(
+    dirs: &[P],
+    strict: bool,
+    mut on_progress: impl FnMut(LoadProgress),
+) -> Result<ScoreLibrary, ScoreLoadError> {
+    merge_score_library(dirs, strict, &mut on_progress)
+}
+
+/// Load the score library on a background thread, streaming per-file progress
+///
+/// The stream yields [`LoadEvent::Progress`] events and ends with a single
+/// [`LoadEvent::Finished`] carrying the loaded library or the first error.
+#[cfg(feature = "app")]
+pub fn load_score_library_stream(
+    dirs: Vec<PathBuf>,
+    strict: bool,
+) -> impl Stream<Item = LoadEvent> + Send + 'static {
+    let (sender, receiver) = mpsc::unbounded();
+    std::thread::spawn(move || {
+        let result = merge_score_library(&dirs, strict, &mut |progress| {
+            let _ = sender.unbounded_send(LoadEvent::Progress(progress));
+        });
+        let _ = sender.unbounded_send(LoadEvent::Finished(result));
+    });
+    receiver
+}
+
+/// Number of score files processed so far during a library load
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct LoadProgress {
+    /// Files processed (loaded or skipped)
+    pub loaded: usize,
+    /// Files found in the bundled library and all directories
+    pub total: usize,
+}
+
+/// Event of an asynchronous library load (see [`load_score_library_stream`])
+#[derive(Debug)]
+pub enum LoadEvent {
+    Progress(LoadProgress),
+    Finished(Result<ScoreLibrary, ScoreLoadError>),
+}
+
+/// Counts processed files and forwards the progress to a callback
+struct ProgressReporter<'a> {
+    progress: LoadProgress,
+    report: &'a mut dyn FnMut(LoadProgress),
+}
+
+impl<'a> ProgressReporter<'a> {
+    fn new(total: usize, report: &'a mut dyn FnMut(LoadProgress)) -> Self {
+        let progress = LoadProgress { loaded: 0, total };
+        report(progress);
+        Self { progress, report }
+    }
+
+    fn file_done(&mut self) {
+        self.progress.loaded += 1;
+        (self.report)(self.progress);
+    }
+}
+
+fn merge_score_library<P: AsRef<Path>>(
+    dirs: &[P],
+    strict: bool,
+    on_progress: &mut dyn FnMut(LoadProgress),
+) -> Result<ScoreLibrary, ScoreLoadError> {
+    // Find every file first so progress can be reported against a known total
+    let bundled_files = bundled_score_files();
+    let mut dir_files = Vec::new();
+    for dir in dirs {
+        let dir = dir.as_ref();
+        if dir.is_dir() {
+            dir_files.push((dir, score_files_to_load(dir)?));
+        }
+    }
+    let total = bundled_files.len() + dir_files.iter().map(|(_, f)| f.len()).sum::<usize>();
+    let mut progress = ProgressReporter::new(total, on_progress);
+
+    let mut library = bundled_library(bundled_files, strict, &mut progress)?;
+
+    // Bases from every directory are available to scores in all directories
+    let mut bases = BaseDefinitions::bundled().clone();
+    for dir in dirs {
+        bases.load_dir(dir.as_ref(), strict)?;
+    }
+
+    for (dir, score_files) in dir_files {
+        load_scores_into(
+            &mut library,
+            dir,
+            score_files,
+            strict,
+            &bases,
+            &mut progress,
+        )?;
+    }
+
+    library.warn_missing_replacements();
+    library.warn_interpretation_coverage();
+    tracing::info!(
+        files = total,
+        scores = library.count(),
+        "Loaded the score library"
+    );
+    Ok(library)
+}
+
+/// Default location for user-provided score definitions
+/// (e.g., `~/.local/share/klinscore/scores` on Linux, or below the portable data folder)
+pub fn user_scores_dir() -> Option<PathBuf> {
+    Some(crate::paths::data_dir()?.join("scores"))
+}
+
+/// Score files below `scores_dir`, without templates
+fn score_files_to_load(scores_dir: &Path) -> Result<Vec<PathBuf>, ScoreLoadError> {
+    let mut score_files = find_score_files(scores_dir)?;
+    score_files.retain(|path| !path.to_string_lossy().contains("template"));
+    Ok(score_files)
+}
+
+/// Load `score_files` (found below `scores_dir`) into `library`, overriding existing IDs
+///
+/// Score IDs must be unique within the directory; overriding only applies
+/// across directories (and over the bundled library).
+fn load_scores_into(
+    library: &mut ScoreLibrary,
+    scores_dir: &Path,
+    score_files: Vec<PathBuf>,
+    strict: bool,
+    bases: &BaseDefinitions,
+    progress: &mut ProgressReporter,
+) -> Result<(), ScoreLoadError> {
+    let packs = installed_packs(scores_dir);
+    let mut seen_ids = HashMap::new();
+
+    for pack in &packs {
+        if let PackIntegrity::Failed(reason) = &pack.integrity {
+            let error = ScoreLoadError::InvalidPack {
+                path: pack.dir.display().to_string(),
+                reason: reason.clone(),
+            };
+            skip_or_fail(strict, error, "Not loading score pack")?;
+        }
+    }
 
-    // Recursively find all .yaml files
-    let yaml_files = find_yaml_files(scores_dir)?;
+    for file_path in score_files {
+        progress.file_done();
 
-    for file_path in yaml_files {
-        // Skip template files
-        if file_path.to_string_lossy().contains("template") {
-            continue;
+        // Inside an installed pack, only verified files listed in its manifest are loaded
+        if let Some(pack) = packs.iter().find(|pack| file_path.starts_with(&pack.dir)) {
+            if !pack.allows(&file_path) {
+                continue;
+            }
         }
 
         // Try to load the score
-        match load_score_from_file(&file_path) {
+        let result = fs::read_to_string(&file_path)
+            .map_err(|e| ScoreLoadError::FileRead {
+                path: file_path.display().to_string(),
+                source: e,
+            })
+            .and_then(|contents| parse_score(&contents, &file_path, bases));
+        match result {
             Ok(score) => {
-                // Generate score ID from filename
-                let score_id = file_path
-                    .file_stem()
-                    .and_then(|s| s.to_str())
-                    .unwrap_or("unknown")
-                    .to_string();
-
-                // Add to specialty index
-                by_specialty
-                    .entry(score.specialty)
-                    .or_default()
-                    .push(score_id.clone());
-
-                // Store the score
-                scores.insert(score_id, score);
+                let score_id = score_id_for(&score, &file_path);
+                check_unique_id(&mut seen_ids, &score_id, &file_path)?;
+                library.insert(
+                    score_id,
+                    score,
+                    ScoreOrigin::Directory(scores_dir.to_path_buf()),
+                );
             }
             Err(e) => {
-                // Log warning but continue loading other scores
-                eprintln!("Warning: Failed to load score from {:?}: {}", file_path, e);
+                // Log warning but continue loading other scores (unless strict)
+                skip_or_fail(
+                    strict,
+                    e,
+                    &format!("Failed to load score from {:?}", file_path),
+                )?;
             }
         }
     }
 
-    Ok(ScoreLibrary {
-        scores,
-        by_specialty,
-        source_path: scores_dir.to_path_buf(),
+    library.packs.extend(packs);
+    Ok(())
+}
+
+/// Skip an invalid file with a warning, or fail with `error` in strict mode
+pub(crate) fn skip_or_fail(
+    strict: bool,
+    error: ScoreLoadError,
+    context: &str,
+) -> Result<(), ScoreLoadError> {
+    if strict {
+        return Err(error);
+    }
+    tracing::warn!("{}: {}", context, error);
+    Ok(())
+}
+
+/// Score ID: the explicit `id` field, or the file name (without extension) for legacy files
+pub fn score_id_for(score: &ScoreDefinition, path: &Path) -> String {
+    score.id.clone().unwrap_or_else(|| {
+        path.file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("unknown")
+            .to_string()
     })
 }
 
-/// Load a single score definition from a YAML file
+/// Record `score_id` as seen, failing if another file already uses it
+fn check_unique_id(
+    seen_ids: &mut HashMap<String, PathBuf>,
+    score_id: &str,
+    path: &Path,
+) -> Result<(), ScoreLoadError> {
+    if let Some(first) = seen_ids.get(score_id) {
+        return Err(ScoreLoadError::DuplicateScoreId {
+            id: score_id.to_string(),
+            first: first.display().to_string(),
+            second: path.display().to_string(),
+        });
+    }
+    seen_ids.insert(score_id.to_string(), path.to_path_buf());
+    Ok(())
+}
+
+/// Load a single score definition from a YAML, JSON or TOML file
+///
+/// The format is taken from the file extension; unknown extensions are read as YAML.
+/// `extends` is resolved against the bundled base definitions.
 pub fn load_score_from_file<P: AsRef<Path>>(
     file_path: P,
 ) -> Result<ScoreDefinition, ScoreLoadError> {
@@ -169,25 +757,385 @@ pub fn load_score_from_file<P: AsRef<Path>>(
         source: e,
     })?;
 
-    // Parse YAML
-    let score: ScoreDefinition =
-        serde_yaml::from_str(&contents).map_err(|e| ScoreLoadError::YamlParse {
-            path: file_path.display().to_string(),
-            source: e,
-        })?;
+    parse_score(&contents, file_path, BaseDefinitions::bundled())
+}
+
+/// Parse and validate a score definition in the format given by `file_path`
+pub(crate) fn parse_score(
+    contents: &str,
+    file_path: &Path,
+    bases: &BaseDefinitions,
+) -> Result<ScoreDefinition, ScoreLoadError> {
+    let path = file_path.display().to_string();
+    let mut score = match ScoreFormat::from_path(file_path).unwrap_or(ScoreFormat::Yaml) {
+        ScoreFormat::Yaml => parse_score_source(contents, bases)
+            .map_err(|source| ScoreLoadError::YamlParse { path, source })?,
+        ScoreFormat::Json => parse_json_score_source(contents, bases)
+            .map_err(|source| ScoreLoadError::JsonParse { path, source })?,
+        ScoreFormat::Toml => parse_toml_score_source(contents, bases)
+            .map_err(|source| ScoreLoadError::TomlParse { path, source })?,
+    };
 
     // Validate the score
-    validate_score(&score, file_path)?;
+    validate_score(&mut score, file_path)?;
 
     Ok(score)
 }
 
-/// Validate a score definition
-fn validate_score(score: &ScoreDefinition, file_path: &Path) -> Result<(), ScoreLoadError> {
+/// Deserialize a score definition, accepting the legacy `label`/`label_de` layout and
+/// resolving `extends` against `bases`.
+///
+/// Errors in files in the current per-language layout without `extends` carry their
+/// line and column; other files are rewritten first (errors then carry the field
+/// path only).
+pub(crate) fn parse_score_source(
+    source: &str,
+    bases: &BaseDefinitions,
+) -> Result<ScoreDefinition, serde_yaml::Error> {
+    let mut document: serde_yaml::Value = serde_yaml::from_str(source)?;
+    if prepare_document::<serde_yaml::Error>(&mut document, bases)? {
+        serde_yaml::from_value(document)
+    } else {
+        from_unprepared(document, || serde_yaml::from_str(source))
+    }
+}
+
+/// JSON counterpart of [`parse_score_source`]
+pub(crate) fn parse_json_score_source(
+    source: &str,
+    bases: &BaseDefinitions,
+) -> Result<ScoreDefinition, serde_json::Error> {
+    let mut document: serde_yaml::Value = serde_json::from_str(source)?;
+    if prepare_document::<serde_json::Error>(&mut document, bases)? {
+        from_prepared(document)
+    } else {
+        from_unprepared(document, || serde_json::from_str(source))
+    }
+}
+
+/// TOML counterpart of [`parse_score_source`]
+pub(crate) fn parse_toml_score_source(
+    source: &str,
+    bases: &BaseDefinitions,
+) -> Result<ScoreDefinition, toml::de::Error> {
+    let mut document: serde_yaml::Value = toml::from_str(source)?;
+    if prepare_document::<toml::de::Error>(&mut document, bases)? {
+        from_prepared(document)
+    } else {
+        from_unprepared(document, || toml::from_str(source))
+    }
+}
+
+/// Upgrade the legacy layout and resolve `extends`; returns whether anything changed
+fn prepare_document<E: serde::de::Error>(
+    document: &mut serde_yaml::Value,
+    bases: &BaseDefinitions,
+) -> Result<bool, E> {
+    let upgraded = upgrade_legacy_translations(document) | upgrade_legacy_references(document);
+    let extended = bases.apply(document).map_err(E::custom)?;
+    Ok(upgraded || extended)
+}
+
+/// Deserialize a document that needed no rewriting
+///
+/// Deserializing the parsed document is much faster than parsing the source a
+/// second time; the source is parsed again only on failure, for an error with
+/// line and column.
+fn from_unprepared<E>(
+    document: serde_yaml::Value,
+    parse_source: impl FnOnce() -> Result<ScoreDefinition, E>,
+) -> Result<ScoreDefinition, E> {
+    serde_yaml::from_value(document).or_else(|_| parse_source())
+}
+
+/// Deserialize a rewritten document, reporting errors in the caller's error type
+fn from_prepared<E: serde::de::Error>(document: serde_yaml::Value) -> Result<ScoreDefinition, E> {
+    serde_yaml::from_value(document).map_err(E::custom)
+}
+
+/// Check a dropdown's options: at least two, unique non-empty values, English and
+/// German labels, and a default that is one of the options (required for required fields)
+fn validate_dropdown(input: &InputField) -> Result<(), String> {
+    if input.options.len() < 2 {
+        return Err(format!(
+            "Dropdown '{}' needs at least two options",
+            input.field
+        ));
+    }
+
+    for (i, option) in input.options.iter().enumerate() {
+        if option.value.trim().is_empty() {
+            return Err(format!(
+                "Option {} of dropdown '{}' has an empty value",
+                i, input.field
+            ));
+        }
+        if input.options[..i].iter().any(|o| o.value == option.value) {
+            return Err(format!(
+                "Dropdown '{}' has duplicate option value '{}'",
+                input.field, option.value
+            ));
+        }
+        let labelled = |language| {
+            option
+                .label
+                .exact(language)
+                .is_some_and(|label| !label.trim().is_empty())
+        };
+        if !labelled("en") || !labelled("de") {
+            return Err(format!(
+                "Option '{}' needs an English and a German label",
+                option.value
+            ));
+        }
+    }
+
+    match &input.default {
+        Some(Some(default)) if !input.options.iter().any(|o| &o.value == default) => Err(format!(
+            "Default of dropdown '{}' is not one of its options: '{}'",
+            input.field, default
+        )),
+        None if input.required => Err(format!(
+            "Required dropdown '{}' needs a default (an option value, or ~ for no preselection)",
+            input.field
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Check that the `widget` hint suits the field type
+fn validate_widget(input: &InputField) -> Result<(), String> {
+    match (input.widget, input.input_type) {
+        (Some(InputWidget::PickList | InputWidget::Radio), InputType::Dropdown) | (None, _) => {
+            Ok(())
+        }
+        (Some(InputWidget::Slider), InputType::Number) => match (input.min, input.max) {
+            (Some(min), Some(max)) if min < max => Ok(()),
+            _ => Err(format!(
+                "Slider '{}' needs a min below its max",
+                input.field
+            )),
+        },
+        (Some(InputWidget::Slider), _) => Err(format!(
+            "Widget Slider of '{}' is only supported for number fields",
+            input.field
+        )),
+        (Some(widget), _) => Err(format!(
+            "Widget {:?} of '{}' is only supported for dropdown fields",
+            widget, input.field
+        )),
+    }
+}
+
+/// Check that a conventional unit belongs to a number field with an SI unit and
+/// has a positive conversion factor
+fn validate_conventional_unit(input: &InputField) -> Result<(), String> {
+    let Some(conventional) = &input.conventional_unit else {
+        return Ok(());
+    };
+    if input.input_type != InputType::Number {
+        return Err(format!(
+            "Conventional unit of '{}' is only supported for number fields",
+            input.field
+        ));
+    }
+    if input.unit.is_none() {
+        return Err(format!(
+            "Conventional unit of '{}' needs the SI unit in `unit`",
+            input.field
+        ));
+    }
+    if input.widget == Some(InputWidget::Slider) {
+        return Err(format!(
+            "Slider '{}' cannot have a conventional unit",
+            input.field
+        ));
+    }
+    if !(conventional.factor.is_finite() && conventional.factor > 0.0) {
+        return Err(format!(
+            "Conventional unit of '{}' needs a positive factor, got {}",
+            input.field, conventional.factor
+        ));
+    }
+    Ok(())
+}
+
+/// Check that duration fields, and only they, declare the unit they are scored in
+fn validate_duration(input: &InputField) -> Result<(), String> {
+    match (input.duration_unit, input.input_type) {
+        (Some(_), InputType::Duration) => match input.min {
+            Some(min) if min < 0.0 => Err(format!(
+                "Duration '{}' cannot have a negative min",
+                input.field
+            )),
+            _ => Ok(()),
+        },
+        (None, InputType::Duration) => Err(format!(
+            "Duration '{}' needs a `duration_unit` (minutes, hours or days)",
+            input.field
+        )),
+        (Some(_), _) => Err(format!(
+            "Duration unit of '{}' is only supported for duration fields",
+            input.field
+        )),
+        (None, _) => Ok(()),
+    }
+}
+
+/// Check that `elapsed` belongs to a date field and is measured until another date
+/// field, and that dates without it score fixed points
+fn validate_date(input: &InputField, score: &ScoreDefinition) -> Result<(), String> {
+    let elapsed = match (&input.elapsed, input.input_type) {
+        (Some(elapsed), InputType::Date) => elapsed,
+        (Some(_), _) => {
+            return Err(format!(
+                "Elapsed time of '{}' is only supported for date fields",
+                input.field
+            ))
+        }
+        (None, InputType::Date) if !matches!(input.points, PointsValue::Fixed(_)) => {
+            return Err(format!(
+                "Date '{}' needs `elapsed` to score by conditions or a table",
+                input.field
+            ))
+        }
+        (None, _) => return Ok(()),
+    };
+
+    match score
+        .inputs
+        .iter()
+        .find(|other| other.field == elapsed.until)
+    {
+        Some(other) if other.field == input.field => Err(format!(
+            "Date '{}' cannot be measured until itself",
+            input.field
+        )),
+        Some(other) if other.input_type != InputType::Date => Err(format!(
+            "Date '{}' is measured until '{}', which is not a date field",
+            input.field, other.field
+        )),
+        Some(_) => Ok(()),
+        None => Err(format!(
+            "Date '{}' is measured until unknown field '{}'",
+            input.field, elapsed.until
+        )),
+    }
+}
+
+/// Check a points table: a number field (or a date scored by elapsed time), a
+/// number or boolean column field, and one parseable condition per row and column
+/// with a points entry for every column
+fn validate_points_table(
+    input: &InputField,
+    table: &PointsTable,
+    score: &ScoreDefinition,
+) -> Result<(), String> {
+    if !input.is_numeric() {
+        return Err(format!(
+            "Points table of '{}' is only supported for number fields",
+            input.field
+        ));
+    }
+
+    let column_input = score
+        .inputs
+        .iter()
+        .find(|other| other.field == table.column_field);
+    match column_input {
+        Some(other) if other.field == input.field => {
+            return Err(format!(
+                "Points table of '{}' cannot select columns by its own value",
+                input.field
+            ))
+        }
+        Some(other) if !(other.is_numeric() || other.input_type == InputType::Boolean) => {
+            return Err(format!(
+                "Column field '{}' of points table '{}' must be a number or boolean field",
+                other.field, input.field
+            ))
+        }
+        Some(_) => {}
+        None => {
+            return Err(format!(
+                "Points table of '{}' references unknown column field '{}'",
+                input.field, table.column_field
+            ))
+        }
+    }
+
+    if table.columns.is_empty() || table.rows.is_empty() {
+        return Err(format!(
+            "Points table of '{}' needs at least one row and one column",
+            input.field
+        ));
+    }
+
+    let conditions = table
+        .columns
+        .iter()
+        .chain(table.rows.iter().map(|row| &row.condition));
+    for condition in conditions {
+        if let Err(e) = parse_condition(condition) {
+            return Err(format!("Points table of '{}': {}", input.field, e));
+        }
+    }
+
+    for (i, row) in table.rows.iter().enumerate() {
+        if row.points.len() != table.columns.len() {
+            return Err(format!(
+                "Row {} of points table '{}' has {} points for {} columns",
+                i,
+                input.field,
+                row.points.len(),
+                table.columns.len()
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Whether `id` is a valid score (or pack) identifier: lowercase letters, digits, underscores
+pub(crate) fn is_valid_id(id: &str) -> bool {
+    !id.is_empty()
+        && id
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
+
+/// Validate a score definition and parse its conditions and score ranges into
+/// `score.conditions`, so that calculations need not parse them again
+pub(crate) fn validate_score(
+    score: &mut ScoreDefinition,
+    file_path: &Path,
+) -> Result<(), ScoreLoadError> {
     let path = file_path.display().to_string();
 
+    // Check the explicit id, if present
+    if let Some(id) = &score.id {
+        if !is_valid_id(id) {
+            return Err(ScoreLoadError::InvalidScore {
+                path,
+                reason: format!(
+                    "Invalid id '{}' (use lowercase letters, digits and underscores)",
+                    id
+                ),
+            });
+        }
+    }
+
+    if let Some(replacement) = &score.superseded_by {
+        if replacement.is_empty() || score.id.as_ref() == Some(replacement) {
+            return Err(ScoreLoadError::InvalidScore {
+                path,
+                reason: format!("Invalid superseded_by '{}'", replacement),
+            });
+        }
+    }
+
     // Check required fields
-    if score.name.is_empty() {
+    if score.name.is_blank() {
         return Err(ScoreLoadError::InvalidScore {
             path,
             reason: "Score name is empty".to_string(),
@@ -208,6 +1156,38 @@ fn validate_score(score: &ScoreDefinition, file_path: &Path) -> Result<(), Score
         });
     }
 
+    if score
+        .aliases
+        .values()
+        .flatten()
+        .any(|alias| alias.trim().is_empty())
+    {
+        return Err(ScoreLoadError::InvalidScore {
+            path,
+            reason: "aliases contains an empty entry".to_string(),
+        });
+    }
+
+    if score
+        .regions_validated
+        .iter()
+        .any(|region| region.trim().is_empty())
+    {
+        return Err(ScoreLoadError::InvalidScore {
+            path,
+            reason: "regions_validated contains an empty entry".to_string(),
+        });
+    }
+
+    for (i, reference) in score.references.iter().enumerate() {
+        if reference.is_empty() {
+            return Err(ScoreLoadError::InvalidScore {
+                path,
+                reason: format!("Reference {} has neither a citation nor authors/title", i),
+            });
+        }
+    }
+
     // Validate input fields
     for (i, input) in score.inputs.iter().enumerate() {
         if input.field.is_empty() {
@@ -217,7 +1197,7 @@ fn validate_score(score: &ScoreDefinition, file_path: &Path) -> Result<(), Score
             });
         }
 
-        if input.label.is_empty() {
+        if input.label.is_blank() {
             return Err(ScoreLoadError::InvalidScore {
                 path,
                 reason: format!("Input field '{}' has empty label", input.field),
@@ -236,15 +1216,167 @@ fn validate_score(score: &ScoreDefinition, file_path: &Path) -> Result<(), Score
                 reason: format!("Duplicate field name: '{}'", input.field),
             });
         }
-    }
 
-    Ok(())
-}
+        if input.input_type == InputType::Dropdown {
+            validate_dropdown(input).map_err(|reason| ScoreLoadError::InvalidScore {
+                path: path.clone(),
+                reason,
+            })?;
+        }
 
-/// Recursively find all .yaml files in a directory
-fn find_yaml_files<P: AsRef<Path>>(dir: P) -> Result<Vec<PathBuf>, ScoreLoadError> {
+        validate_widget(input).map_err(|reason| ScoreLoadError::InvalidScore {
+            path: path.clone(),
+            reason,
+        })?;
+
+        validate_date(input, score).map_err(|reason| ScoreLoadError::InvalidScore {
+            path: path.clone(),
+            reason,
+        })?;
+
+        validate_duration(input).map_err(|reason| ScoreLoadError::InvalidScore {
+            path: path.clone(),
+            reason,
+        })?;
+
+        validate_conventional_unit(input).map_err(|reason| ScoreLoadError::InvalidScore {
+            path: path.clone(),
+            reason,
+        })?;
+
+        if let PointsValue::Table { table } = &input.points {
+            validate_points_table(input, table, score).map_err(|reason| {
+                ScoreLoadError::InvalidScore {
+                    path: path.clone(),
+                    reason,
+                }
+            })?;
+        }
+    }
+
+    // Validate sections
+    for (i, section) in score.sections.iter().enumerate() {
+        if section.id.is_empty() {
+            return Err(ScoreLoadError::InvalidScore {
+                path,
+                reason: format!("Section {} has empty id", i),
+            });
+        }
+
+        if score.sections.iter().filter(|s| s.id == section.id).count() > 1 {
+            return Err(ScoreLoadError::InvalidScore {
+                path,
+                reason: format!("Duplicate section id: '{}'", section.id),
+            });
+        }
+
+        for field in &section.fields {
+            if !score.inputs.iter().any(|input| &input.field == field) {
+                return Err(ScoreLoadError::InvalidScore {
+                    path,
+                    reason: format!(
+                        "Section '{}' references unknown field '{}'",
+                        section.id, field
+                    ),
+                });
+            }
+
+            let occurrences = score
+                .sections
+                .iter()
+                .flat_map(|s| s.fields.iter())
+                .filter(|f| *f == field)
+                .count();
+            if occurrences > 1 {
+                return Err(ScoreLoadError::InvalidScore {
+                    path,
+                    reason: format!("Field '{}' is listed in more than one section", field),
+                });
+            }
+        }
+    }
+
+    // Validate result codes
+    if let Some(code) = score
+        .metadata
+        .codes
+        .iter()
+        .find(|c| c.code.trim().is_empty())
+    {
+        return Err(ScoreLoadError::InvalidScore {
+            path,
+            reason: format!("Empty {:?} code in metadata", code.system),
+        });
+    }
+
+    // Validate input codes
+    if let Some(input) = score
+        .inputs
+        .iter()
+        .find(|input| input.loinc.iter().any(|code| code.trim().is_empty()))
+    {
+        return Err(ScoreLoadError::InvalidScore {
+            path,
+            reason: format!("Empty LOINC code for input '{}'", input.field),
+        });
+    }
+
+    // Validate test cases (their results are checked by `klinscore-cli test`)
+    for (index, case) in score.test_cases.iter().enumerate() {
+        if case.expected.is_empty() {
+            return Err(ScoreLoadError::InvalidScore {
+                path,
+                reason: format!(
+                    "Test case {} expects nothing: set a total, risk_level or risk",
+                    index + 1
+                ),
+            });
+        }
+        if let Some(field) = case
+            .inputs
+            .keys()
+            .find(|field| !score.inputs.iter().any(|input| input.field == **field))
+        {
+            return Err(ScoreLoadError::InvalidScore {
+                path,
+                reason: format!("Test case {} has unknown input '{}'", index + 1, field),
+            });
+        }
+    }
+
+    // Validate constraints
+    for constraint in &score.constraints {
+        let expression = crate::scores::constraints::parse_constraint(&constraint.expression)
+            .map_err(|e| ScoreLoadError::InvalidScore {
+                path: path.clone(),
+                reason: e.to_string(),
+            })?;
+
+        for field in expression.fields() {
+            if !score.inputs.iter().any(|input| input.field == field) {
+                return Err(ScoreLoadError::InvalidScore {
+                    path,
+                    reason: format!(
+                        "Constraint '{}' references unknown field '{}'",
+                        constraint.expression, field
+                    ),
+                });
+            }
+        }
+    }
+
+    score.conditions = CompiledConditions::compile(score)
+        .map_err(|reason| ScoreLoadError::InvalidScore { path, reason })?;
+
+    Ok(())
+}
+
+/// Recursively find all score files (.yaml, .yml, .json, .toml) in a directory
+///
+/// Manifests of installed score packs and `bases/` directories are skipped.
+pub(crate) fn find_score_files<P: AsRef<Path>>(dir: P) -> Result<Vec<PathBuf>, ScoreLoadError> {
     let dir = dir.as_ref();
-    let mut yaml_files = Vec::new();
+    let mut score_files = Vec::new();
 
     let entries = fs::read_dir(dir).map_err(|e| ScoreLoadError::DirectoryRead {
         path: dir.display().to_string(),
@@ -260,31 +1392,36 @@ fn find_yaml_files<P: AsRef<Path>>(dir: P) -> Result<Vec<PathBuf>, ScoreLoadErro
         let path = entry.path();
 
         if path.is_dir() {
-            // Recursively search subdirectories
-            let mut sub_files = find_yaml_files(&path)?;
-            yaml_files.append(&mut sub_files);
-        } else if path.is_file() {
-            // Check if it's a .yaml or .yml file
-            if let Some(ext) = path.extension() {
-                if ext == "yaml" || ext == "yml" {
-                    yaml_files.push(path);
-                }
+            // Base definitions are fragments, not scores
+            if path.ends_with(BASES_DIR) {
+                continue;
             }
+            // Recursively search subdirectories
+            let mut sub_files = find_score_files(&path)?;
+            score_files.append(&mut sub_files);
+        } else if path.is_file()
+            && ScoreFormat::from_path(&path).is_some()
+            && !path.ends_with(MANIFEST_FILE)
+        {
+            score_files.push(path);
         }
     }
 
-    Ok(yaml_files)
+    Ok(score_files)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::{CodeSystem, LocalizedText, PointsValue, UnitSystem};
+    use crate::scores::{calculate_score, CalculationError, InputValue};
+    use std::collections::HashMap;
     use std::fs;
     use std::io::Write;
     use tempfile::TempDir;
 
     #[test]
-    fn test_find_yaml_files() {
+    fn test_find_score_files() {
         let temp_dir = TempDir::new().unwrap();
         let base_path = temp_dir.path();
 
@@ -292,15 +1429,17 @@ mod tests {
         fs::create_dir_all(base_path.join("cardiology")).unwrap();
         fs::create_dir_all(base_path.join("nephrology")).unwrap();
 
-        // Create some YAML files
+        // Create some score files
         fs::File::create(base_path.join("cardiology/score1.yaml")).unwrap();
         fs::File::create(base_path.join("cardiology/score2.yml")).unwrap();
         fs::File::create(base_path.join("nephrology/score3.yaml")).unwrap();
-        fs::File::create(base_path.join("readme.txt")).unwrap(); // Non-YAML file
+        fs::File::create(base_path.join("nephrology/score4.json")).unwrap();
+        fs::File::create(base_path.join("nephrology/score5.toml")).unwrap();
+        fs::File::create(base_path.join("readme.txt")).unwrap(); // Not a score file
 
-        let yaml_files = find_yaml_files(base_path).unwrap();
+        let yaml_files = find_score_files(base_path).unwrap();
 
-        assert_eq!(yaml_files.len(), 3);
+        assert_eq!(yaml_files.len(), 5);
         assert!(yaml_files
             .iter()
             .any(|p| p.ends_with("cardiology/score1.yaml")));
@@ -310,6 +1449,62 @@ mod tests {
         assert!(yaml_files
             .iter()
             .any(|p| p.ends_with("nephrology/score3.yaml")));
+        assert!(yaml_files
+            .iter()
+            .any(|p| p.ends_with("nephrology/score5.toml")));
+    }
+
+    #[test]
+    fn test_load_json_and_toml_scores() {
+        let temp_dir = TempDir::new().unwrap();
+        let yaml_score = load_score_from_file("scores/cardiology/has_bled.yaml").unwrap();
+        let expected = serde_json::to_value(&yaml_score).unwrap();
+
+        let json_file = temp_dir.path().join("has_bled.json");
+        fs::write(
+            &json_file,
+            serde_json::to_string_pretty(&yaml_score).unwrap(),
+        )
+        .unwrap();
+        let json_score = load_score_from_file(&json_file).unwrap();
+        assert_eq!(serde_json::to_value(&json_score).unwrap(), expected);
+
+        let toml_file = temp_dir.path().join("has_bled.toml");
+        fs::write(&toml_file, toml::to_string(&yaml_score).unwrap()).unwrap();
+        let toml_score = load_score_from_file(&toml_file).unwrap();
+        assert_eq!(serde_json::to_value(&toml_score).unwrap(), expected);
+
+        // The legacy `_de` layout is upgraded in every format
+        fs::write(
+            &json_file,
+            r#"{
+  "name": "Test Score",
+  "name_de": "Test-Score",
+  "specialty": "Cardiology",
+  "version": "1.0",
+  "guideline_source": "Test",
+  "reference": "Test",
+  "validation_status": "draft",
+  "inputs": [{"field": "age", "type": "number", "label": "Age", "label_de": "Alter", "points": 0}],
+  "interpretation": [
+    {"score": 0, "risk": "Low", "risk_de": "Niedrig", "risk_level": "Low", "recommendation": "Test", "recommendation_de": "Test"}
+  ]
+}"#,
+        )
+        .unwrap();
+        let legacy = load_score_from_file(&json_file).unwrap();
+        assert_eq!(legacy.inputs[0].label.text("de"), "Alter");
+
+        // Parse errors name the format
+        fs::write(&toml_file, "name = ").unwrap();
+        assert!(matches!(
+            load_score_from_file(&toml_file),
+            Err(ScoreLoadError::TomlParse { .. })
+        ));
+
+        // Both formats are picked up when loading a directory
+        let library = load_all_scores(temp_dir.path()).unwrap();
+        assert_eq!(library.count(), 1);
     }
 
     #[test]
@@ -345,35 +1540,1071 @@ interpretation:
         file.write_all(yaml_content.as_bytes()).unwrap();
 
         let score = load_score_from_file(&score_file).unwrap();
-        assert_eq!(score.name, "Test Score");
+        assert_eq!(score.name.text("en"), "Test Score");
+        assert_eq!(score.name.text("de"), "Test-Score");
         assert_eq!(score.specialty, Specialty::Cardiology);
         assert_eq!(score.inputs.len(), 1);
     }
 
     #[test]
     fn test_validate_score_empty_name() {
-        let score = ScoreDefinition {
-            name: String::new(), // Invalid: empty name
-            name_de: "Test".to_string(),
+        let mut score = ScoreDefinition {
+            id: None,
+            name: LocalizedText::new().with("de", String::new()), // Invalid: empty name
+            aliases: Default::default(),
             specialty: Specialty::Cardiology,
-            specialty_de: "Kardiologie".to_string(),
+            sort_order: None,
             version: "1.0".to_string(),
             guideline_source: "Test".to_string(),
-            reference: "Test".to_string(),
-            reference_url: String::new(),
+            references: Vec::new(),
             validation_status: "draft".to_string(),
-            description: String::new(),
-            description_de: String::new(),
+            deprecated: false,
+            superseded_by: None,
+            description: LocalizedText::default(),
+            intended_use: None,
+            regulatory_note: None,
+            regions_validated: Vec::new(),
             inputs: vec![],
+            sections: vec![],
+            input_mode: None,
+            constraints: vec![],
             interpretation: vec![],
             formula: None,
-            metadata: HashMap::new(),
+            metadata: Default::default(),
+            test_cases: Vec::new(),
+            conditions: Default::default(),
         };
 
-        let result = validate_score(&score, Path::new("test.yaml"));
+        let result = validate_score(&mut score, Path::new("test.yaml"));
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_validate_score_sections() {
+        let temp_dir = TempDir::new().unwrap();
+        let score_file = temp_dir.path().join("sections.yaml");
+
+        let yaml_content = r#"
+name: "Section Test"
+name_de: "Abschnitt-Test"
+specialty: Anesthesiology
+specialty_de: "Anästhesiologie"
+version: "1.0"
+guideline_source: "Test"
+reference: "Test"
+validation_status: "draft"
+inputs:
+  - field: "stroke"
+    type: "boolean"
+    label: "Stroke"
+    label_de: "Schlaganfall"
+    points: 5
+sections:
+  - id: "five_points"
+    label: "5-point factors"
+    label_de: "Faktoren mit 5 Punkten"
+    fields: ["stroke", "fracture"]
+interpretation:
+  - score: 0
+    risk: "Low"
+    risk_de: "Niedrig"
+    risk_level: Low
+    recommendation: "Test"
+    recommendation_de: "Test"
+"#;
+
+        fs::write(&score_file, yaml_content).unwrap();
+
+        let result = load_score_from_file(&score_file);
+        match result {
+            Err(ScoreLoadError::InvalidScore { reason, .. }) => {
+                assert!(reason.contains("unknown field 'fracture'"));
+            }
+            other => panic!("Expected InvalidScore, got {:?}", other),
+        }
+
+        fs::write(&score_file, yaml_content.replace(", \"fracture\"", "")).unwrap();
+        assert!(load_score_from_file(&score_file).is_ok());
+    }
+
+    #[test]
+    fn test_validate_score_constraints() {
+        let temp_dir = TempDir::new().unwrap();
+        let score_file = temp_dir.path().join("constraints.yaml");
+
+        let yaml_content = r#"
+name: "Constraint Test"
+name_de: "Bedingungs-Test"
+specialty: Cardiology
+specialty_de: "Kardiologie"
+version: "1.0"
+guideline_source: "Test"
+reference: "Test"
+validation_status: "draft"
+inputs:
+  - field: "systolic_bp"
+    type: "number"
+    label: "Systolic BP"
+    label_de: "Systolischer Blutdruck"
+    points: 0
+constraints:
+  - expression: "diastolic_bp < systolic_bp"
+    message: "Diastolic BP must be lower than systolic BP"
+    message_de: "Diastolischer Blutdruck muss niedriger als systolischer sein"
+interpretation:
+  - score: 0
+    risk: "Low"
+    risk_de: "Niedrig"
+    risk_level: Low
+    recommendation: "Test"
+    recommendation_de: "Test"
+"#;
+
+        fs::write(&score_file, yaml_content).unwrap();
+
+        match load_score_from_file(&score_file) {
+            Err(ScoreLoadError::InvalidScore { reason, .. }) => {
+                assert!(reason.contains("unknown field 'diastolic_bp'"));
+            }
+            other => panic!("Expected InvalidScore, got {:?}", other),
+        }
+
+        fs::write(
+            &score_file,
+            yaml_content.replace("diastolic_bp < systolic_bp", "systolic_bp >= 50"),
+        )
+        .unwrap();
+        assert!(load_score_from_file(&score_file).is_ok());
+    }
+
+    #[test]
+    fn test_validate_score_dropdowns() {
+        let temp_dir = TempDir::new().unwrap();
+        let score_file = temp_dir.path().join("dropdowns.yaml");
+
+        let yaml_content = r#"
+name: "Dropdown Test"
+specialty: Nephrology
+version: "1.0"
+guideline_source: "Test"
+references: [{citation: "Test"}]
+validation_status: "draft"
+inputs:
+  - field: "sex"
+    type: "dropdown"
+    label: {en: "Sex", de: "Geschlecht"}
+    default: ~
+    points: 0
+    options:
+      - value: "male"
+        label: {en: "Male", de: "Männlich"}
+        points: 0
+      - value: "female"
+        label: {en: "Female", de: "Weiblich"}
+        points: 1
+interpretation:
+  - score: "0-1"
+    risk: "Low"
+    risk_level: Low
+    recommendation: "Test"
+"#;
+
+        fs::write(&score_file, yaml_content).unwrap();
+        let score = load_score_from_file(&score_file).unwrap();
+        assert_eq!(score.inputs[0].default, Some(None));
+
+        let preselected = yaml_content.replace("default: ~", "default: \"female\"");
+        fs::write(&score_file, &preselected).unwrap();
+        let score = load_score_from_file(&score_file).unwrap();
+        assert_eq!(score.inputs[0].default_option(), Some("female"));
+
+        let invalid = [
+            ("    default: ~\n", "", "needs a default"),
+            ("default: ~", "default: \"other\"", "not one of its options"),
+            ("\"female\"", "\"male\"", "duplicate option value 'male'"),
+            ("{en: \"Female\", de: \"Weiblich\"}", "\"Female\"", "German label"),
+            (
+                "type: \"dropdown\"",
+                "type: \"number\"\n    widget: radio",
+                "only supported for dropdown fields",
+            ),
+            (
+                "default: ~",
+                "default: ~\n    widget: slider",
+                "only supported for number fields",
+            ),
+            (
+                "type: \"dropdown\"",
+                "type: \"number\"\n    widget: slider\n    min: 1",
+                "needs a min below its max",
+            ),
+            (
+                "      - value: \"female\"\n        label: {en: \"Female\", de: \"Weiblich\"}\n        points: 1\n",
+                "",
+                "at least two options",
+            ),
+        ];
+        for (from, to, expected) in invalid {
+            fs::write(&score_file, yaml_content.replace(from, to)).unwrap();
+            match load_score_from_file(&score_file) {
+                Err(ScoreLoadError::InvalidScore { reason, .. }) => {
+                    assert!(reason.contains(expected), "{}: {}", expected, reason);
+                }
+                other => panic!("Expected InvalidScore for {}, got {:?}", expected, other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_validate_conventional_unit() {
+        let temp_dir = TempDir::new().unwrap();
+        let score_file = temp_dir.path().join("units.yaml");
+
+        let yaml_content = r#"
+name: "Unit Test"
+specialty: Nephrology
+version: "1.0"
+guideline_source: "Test"
+references: [{citation: "Test"}]
+validation_status: "draft"
+inputs:
+  - field: "creatinine"
+    type: "number"
+    label: {en: "Creatinine", de: "Kreatinin"}
+    unit: "μmol/L"
+    conventional_unit:
+      unit: "mg/dL"
+      factor: 88.4
+    min: 10
+    max: 2000
+    points: 0
+interpretation:
+  - score: "0-1"
+    risk: "Low"
+    risk_level: Low
+    recommendation: "Test"
+"#;
+
+        fs::write(&score_file, yaml_content).unwrap();
+        let score = load_score_from_file(&score_file).unwrap();
+        let creatinine = &score.inputs[0];
+        assert_eq!(creatinine.unit_factor(UnitSystem::Conventional), 88.4);
+        assert_eq!(creatinine.unit_factor(UnitSystem::Si), 1.0);
+        assert_eq!(
+            creatinine
+                .unit_in(UnitSystem::Conventional)
+                .unwrap()
+                .text("en"),
+            "mg/dL"
+        );
+
+        let invalid = [
+            ("factor: 88.4", "factor: 0", "needs a positive factor"),
+            ("    unit: \"μmol/L\"\n", "", "needs the SI unit"),
+            (
+                "type: \"number\"",
+                "type: \"boolean\"",
+                "only supported for number fields",
+            ),
+            (
+                "max: 2000",
+                "max: 2000\n    widget: slider",
+                "cannot have a conventional unit",
+            ),
+        ];
+        for (from, to, expected) in invalid {
+            fs::write(&score_file, yaml_content.replace(from, to)).unwrap();
+            match load_score_from_file(&score_file) {
+                Err(ScoreLoadError::InvalidScore { reason, .. }) => {
+                    assert!(reason.contains(expected), "{}: {}", expected, reason);
+                }
+                other => panic!("Expected InvalidScore for {}, got {:?}", expected, other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_validate_date() {
+        let temp_dir = TempDir::new().unwrap();
+        let score_file = temp_dir.path().join("dates.yaml");
+
+        let yaml_content = r#"
+name: "Date Test"
+specialty: Cardiology
+version: "1.0"
+guideline_source: "Test"
+references: [{citation: "Test"}]
+validation_status: "draft"
+inputs:
+  - field: "birth_date"
+    type: "date"
+    label: {en: "Date of birth", de: "Geburtsdatum"}
+    elapsed: {unit: years, until: assessment_date}
+    min: 18
+    points:
+      - condition: ">= 75"
+        points: 2
+      - condition: ">= 65"
+        points: 1
+  - field: "assessment_date"
+    type: "date"
+    label: {en: "Date of assessment", de: "Untersuchungsdatum"}
+    points: 0
+interpretation:
+  - score: "0-2"
+    risk: "Low"
+    risk_level: Low
+    recommendation: "Test"
+"#;
+
+        fs::write(&score_file, yaml_content).unwrap();
+        let score = load_score_from_file(&score_file).unwrap();
+        let date = |s: &str| InputValue::Date(s.parse().unwrap());
+        let mut inputs = HashMap::from([
+            ("birth_date".to_string(), date("1950-06-01")),
+            ("assessment_date".to_string(), date("2025-05-31")),
+        ]);
+        assert_eq!(calculate_score(&score, &inputs).unwrap().total_score, 1);
+        inputs.insert("assessment_date".to_string(), date("2025-06-01"));
+        let result = calculate_score(&score, &inputs).unwrap();
+        assert_eq!(result.total_score, 2);
+        assert_eq!(
+            result.field_scores[0]
+                .selection
+                .as_ref()
+                .unwrap()
+                .text("de"),
+            "01.06.1950 (75 Jahre)"
+        );
+
+        inputs.insert("birth_date".to_string(), date("2025-06-02"));
+        assert!(matches!(
+            calculate_score(&score, &inputs),
+            Err(CalculationError::InvalidInput { field, .. }) if field == "birth_date"
+        ));
+        inputs.remove("assessment_date");
+        assert!(matches!(
+            calculate_score(&score, &inputs),
+            Err(CalculationError::MissingRequiredField { field }) if field == "assessment_date"
+        ));
+
+        let invalid = [
+            (
+                "until: assessment_date",
+                "until: visit_date",
+                "until unknown field 'visit_date'",
+            ),
+            (
+                "until: assessment_date",
+                "until: birth_date",
+                "cannot be measured until itself",
+            ),
+            (
+                "type: \"date\"\n    label: {en: \"Date of assessment\"",
+                "type: \"number\"\n    label: {en: \"Date of assessment\"",
+                "which is not a date field",
+            ),
+            (
+                "type: \"date\"\n    label: {en: \"Date of birth\"",
+                "type: \"number\"\n    label: {en: \"Date of birth\"",
+                "only supported for date fields",
+            ),
+            (
+                "    elapsed: {unit: years, until: assessment_date}\n",
+                "",
+                "needs `elapsed` to score by conditions",
+            ),
+        ];
+        for (from, to, expected) in invalid {
+            fs::write(&score_file, yaml_content.replace(from, to)).unwrap();
+            match load_score_from_file(&score_file) {
+                Err(ScoreLoadError::InvalidScore { reason, .. }) => {
+                    assert!(reason.contains(expected), "{}: {}", expected, reason);
+                }
+                other => panic!("Expected InvalidScore for {}, got {:?}", expected, other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_validate_duration() {
+        let temp_dir = TempDir::new().unwrap();
+        let score_file = temp_dir.path().join("durations.yaml");
+
+        let yaml_content = r#"
+name: "Duration Test"
+specialty: Neurology
+version: "1.0"
+guideline_source: "Test"
+references: [{citation: "Test"}]
+validation_status: "draft"
+inputs:
+  - field: "onset"
+    type: "duration"
+    label: {en: "Time since symptom onset", de: "Zeit seit Symptombeginn"}
+    duration_unit: hours
+    max: 24
+    points:
+      - condition: "> 4.5"
+        points: 1
+interpretation:
+  - score: "0-1"
+    risk: "Low"
+    risk_level: Low
+    recommendation: "Test"
+"#;
+
+        fs::write(&score_file, yaml_content).unwrap();
+        let score = load_score_from_file(&score_file).unwrap();
+        let mut inputs = HashMap::from([("onset".to_string(), InputValue::Number(4.5))]);
+        let result = calculate_score(&score, &inputs).unwrap();
+        assert_eq!(result.total_score, 0);
+        assert_eq!(
+            result.field_scores[0]
+                .selection
+                .as_ref()
+                .unwrap()
+                .text("de"),
+            "4 h 30 min"
+        );
+        inputs.insert("onset".to_string(), InputValue::Number(5.0));
+        assert_eq!(calculate_score(&score, &inputs).unwrap().total_score, 1);
+        inputs.insert("onset".to_string(), InputValue::Number(-1.0));
+        assert!(matches!(
+            calculate_score(&score, &inputs),
+            Err(CalculationError::InvalidInput { field, .. }) if field == "onset"
+        ));
+
+        let invalid = [
+            ("    duration_unit: hours\n", "", "needs a `duration_unit`"),
+            (
+                "type: \"duration\"",
+                "type: \"number\"",
+                "only supported for duration fields",
+            ),
+            ("max: 24", "min: -1", "cannot have a negative min"),
+        ];
+        for (from, to, expected) in invalid {
+            fs::write(&score_file, yaml_content.replace(from, to)).unwrap();
+            match load_score_from_file(&score_file) {
+                Err(ScoreLoadError::InvalidScore { reason, .. }) => {
+                    assert!(reason.contains(expected), "{}: {}", expected, reason);
+                }
+                other => panic!("Expected InvalidScore for {}, got {:?}", expected, other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_validate_result_codes() {
+        let temp_dir = TempDir::new().unwrap();
+        let score_file = temp_dir.path().join("codes.yaml");
+
+        let yaml_content = r#"
+name: "Code Test"
+specialty: Nephrology
+version: "1.0"
+guideline_source: "Test"
+references: [{citation: "Test"}]
+validation_status: "draft"
+inputs:
+  - field: "a"
+    type: "boolean"
+    label: "A"
+    points: 1
+interpretation:
+  - score: "0-1"
+    risk: "Low"
+    risk_level: Low
+    recommendation: "Test"
+metadata:
+  codes:
+    - {system: snomed, code: "CODE"}
+"#;
+
+        fs::write(&score_file, yaml_content).unwrap();
+        let score = load_score_from_file(&score_file).unwrap();
+        assert_eq!(score.metadata.codes[0].system, CodeSystem::Snomed);
+        assert_eq!(
+            score.metadata.codes[0].system.uri(),
+            "http://snomed.info/sct"
+        );
+
+        fs::write(&score_file, yaml_content.replace("\"CODE\"", "\" \"")).unwrap();
+        match load_score_from_file(&score_file) {
+            Err(ScoreLoadError::InvalidScore { reason, .. }) => {
+                assert!(reason.contains("Empty Snomed code"), "{}", reason);
+            }
+            other => panic!("Expected InvalidScore, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_validate_test_cases() {
+        let temp_dir = TempDir::new().unwrap();
+        let score_file = temp_dir.path().join("cases.yaml");
+
+        let yaml_content = r#"
+name: "Test Case Test"
+specialty: Nephrology
+version: "1.0"
+guideline_source: "Test"
+references: [{citation: "Test"}]
+validation_status: "draft"
+inputs:
+  - field: "a"
+    type: "boolean"
+    label: "A"
+    points: 1
+interpretation:
+  - score: "0-1"
+    risk: "Low"
+    risk_level: Low
+    recommendation: "Test"
+test_cases:
+  - name: "A checked"
+    inputs: {a: true}
+    expected: {total: 1, risk: "Low"}
+"#;
+
+        fs::write(&score_file, yaml_content).unwrap();
+        let score = load_score_from_file(&score_file).unwrap();
+        assert_eq!(score.test_cases[0].expected.total, Some(1));
+        assert!(crate::scores::run_test_cases(&score).is_empty());
+
+        for (from, to, message) in [
+            ("{total: 1, risk: \"Low\"}", "{}", "expects nothing"),
+            ("{a: true}", "{b: true}", "unknown input 'b'"),
+        ] {
+            fs::write(&score_file, yaml_content.replace(from, to)).unwrap();
+            match load_score_from_file(&score_file) {
+                Err(ScoreLoadError::InvalidScore { reason, .. }) => {
+                    assert!(reason.contains(message), "{}", reason);
+                }
+                other => panic!("Expected InvalidScore, got {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_validate_points_table() {
+        let temp_dir = TempDir::new().unwrap();
+        let score_file = temp_dir.path().join("table.yaml");
+
+        let yaml_content = r#"
+name: "Table Test"
+specialty: Pediatrics
+version: "1.0"
+guideline_source: "Test"
+references: [{citation: "Test"}]
+validation_status: "draft"
+inputs:
+  - field: "age"
+    type: "number"
+    label: {en: "Age", de: "Alter"}
+    points: 0
+  - field: "heart_rate"
+    type: "number"
+    label: {en: "Heart rate", de: "Herzfrequenz"}
+    points:
+      table:
+        column_field: "age"
+        columns: ["< 1", ">= 1"]
+        rows:
+          - condition: ">= 160"
+            points: [1, 2]
+            label: {en: "Tachycardia", de: "Tachykardie"}
+interpretation:
+  - score: "0-2"
+    risk: "Low"
+    risk_level: Low
+    recommendation: "Test"
+"#;
+
+        fs::write(&score_file, yaml_content).unwrap();
+        let score = load_score_from_file(&score_file).unwrap();
+        match &score.inputs[1].points {
+            PointsValue::Table { table } => {
+                assert_eq!(table.column_field, "age");
+                assert_eq!(table.rows[0].points, vec![1, 2]);
+            }
+            other => panic!("Expected a points table, got {:?}", other),
+        }
+
+        let invalid = [
+            (
+                "points: [1, 2]",
+                "points: [1]",
+                "has 1 points for 2 columns",
+            ),
+            (
+                "column_field: \"age\"",
+                "column_field: \"weight\"",
+                "unknown column field",
+            ),
+            (
+                "column_field: \"age\"",
+                "column_field: \"heart_rate\"",
+                "its own value",
+            ),
+            ("\">= 160\"", "\"about 160\"", "unexpected character"),
+            (
+                "    type: \"number\"\n    label: {en: \"Heart",
+                "    type: \"boolean\"\n    label: {en: \"Heart",
+                "only supported for number fields",
+            ),
+        ];
+        for (from, to, expected) in invalid {
+            fs::write(&score_file, yaml_content.replace(from, to)).unwrap();
+            match load_score_from_file(&score_file) {
+                Err(ScoreLoadError::InvalidScore { reason, .. }) => {
+                    assert!(reason.contains(expected), "{}: {}", expected, reason);
+                }
+                other => panic!("Expected InvalidScore for {}, got {:?}", expected, other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_conditions_parsed_at_load() {
+        let temp_dir = TempDir::new().unwrap();
+        let score_file = temp_dir.path().join("conditions.yaml");
+
+        let yaml_content = r#"
+name: "Condition Test"
+specialty: Cardiology
+version: "1.0"
+guideline_source: "Test"
+references: [{citation: "Test"}]
+validation_status: "draft"
+inputs:
+  - field: "age"
+    type: "number"
+    label: {en: "Age", de: "Alter"}
+    points:
+      - condition: ">= 65"
+        points: 1
+interpretation:
+  - score: "0"
+    risk: {en: "Low", de: "Niedrig"}
+    risk_level: Low
+    recommendation: {en: "Test", de: "Test"}
+  - score: "≥1"
+    risk: {en: "High", de: "Hoch"}
+    risk_level: High
+    recommendation: {en: "Test", de: "Test"}
+"#;
+        fs::write(&score_file, yaml_content).unwrap();
+        let score = load_score_from_file(&score_file).unwrap();
+        assert!(score.conditions.evaluate(">= 65", 70.0).unwrap());
+        assert!(score
+            .conditions
+            .matches(&score.interpretation[1].score, 2)
+            .unwrap());
+
+        // Syntax errors fail the load instead of the calculation
+        let invalid = [
+            ("\">= 65\"", "\">= 6 5\"", "Input 'age'"),
+            ("\"≥1\"", "\"≥ one\"", "Interpretation rule 1"),
+        ];
+        for (from, to, expected) in invalid {
+            fs::write(&score_file, yaml_content.replace(from, to)).unwrap();
+            match load_score_from_file(&score_file) {
+                Err(ScoreLoadError::InvalidScore { reason, .. }) => {
+                    assert!(reason.contains(expected), "{}: {}", expected, reason);
+                }
+                other => panic!("Expected InvalidScore for {}, got {:?}", expected, other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_load_bundled_scores() {
+        let bundled = load_bundled_scores();
+        let from_disk = load_all_scores("scores").unwrap();
+
+        assert_eq!(bundled.count(), from_disk.count());
+        assert!(bundled.get_score("cha2ds2_va").is_some());
+        assert_eq!(bundled.origin("cha2ds2_va"), Some(&ScoreOrigin::Bundled));
+    }
+
+    #[test]
+    fn test_extends_base_definitions() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join("bases")).unwrap();
+        fs::write(
+            temp_dir.path().join("bases/base_cardiac_inputs.yaml"),
+            r#"
+inputs:
+  - field: "age"
+    type: "number"
+    label: {en: "Age", de: "Alter"}
+    unit: "years"
+    points:
+      - condition: ">= 65"
+        points: 1
+  - field: "female"
+    type: "boolean"
+    label: {en: "Female", de: "Weiblich"}
+    points: 1
+"#,
+        )
+        .unwrap();
+        fs::write(
+            temp_dir.path().join("child.yaml"),
+            r#"
+id: "child_score"
+extends: base_cardiac_inputs
+name: {en: "Child Score", de: "Kind-Score"}
+specialty: Cardiology
+version: "1.0"
+guideline_source: "Test"
+reference: "Test"
+validation_status: "draft"
+inputs:
+  - field: "female"
+    type: "boolean"
+    label: {en: "Female", de: "Weiblich"}
+    points: 0
+  - field: "diabetes"
+    type: "boolean"
+    label: {en: "Diabetes", de: "Diabetes"}
+    points: 1
+interpretation:
+  - score: "0-2"
+    risk: "Low"
+    risk_level: Low
+    recommendation: "None"
+"#,
+        )
+        .unwrap();
+
+        let library = load_all_scores(temp_dir.path()).unwrap();
+        assert_eq!(library.count(), 1, "bases are not loaded as scores");
+        let score = library.get_score("child_score").unwrap();
+        let fields: Vec<_> = score.inputs.iter().map(|i| i.field.as_str()).collect();
+        assert_eq!(fields, vec!["age", "female", "diabetes"]);
+        assert!(matches!(score.inputs[1].points, PointsValue::Fixed(0)));
+
+        // Unknown bases are reported like any other parse error
+        fs::remove_dir_all(temp_dir.path().join("bases")).unwrap();
+        let error = load_all_scores_strict(temp_dir.path()).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("Unknown base 'base_cardiac_inputs'"));
+    }
+
+    #[test]
+    fn test_strict_loading_fails_on_invalid_file() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::copy(
+            "scores/cardiology/has_bled.yaml",
+            temp_dir.path().join("has_bled.yaml"),
+        )
+        .unwrap();
+        fs::write(
+            temp_dir.path().join("broken.yaml"),
+            "name: {en: \"Broken\"}",
+        )
+        .unwrap();
+
+        // Lenient loading skips the broken file
+        assert_eq!(load_all_scores(temp_dir.path()).unwrap().count(), 1);
+
+        assert!(matches!(
+            load_all_scores_strict(temp_dir.path()),
+            Err(ScoreLoadError::YamlParse { path, .. }) if path.ends_with("broken.yaml")
+        ));
+        assert!(load_score_library_strict(&[temp_dir.path()]).is_err());
+
+        // The bundled library on its own is valid in strict mode
+        fs::remove_file(temp_dir.path().join("broken.yaml")).unwrap();
+        let library = load_score_library_strict(&[temp_dir.path()]).unwrap();
+        assert_eq!(library.count(), load_bundled_scores().count());
+    }
+
+    #[test]
+    fn test_load_progress() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::copy(
+            "scores/cardiology/has_bled.yaml",
+            temp_dir.path().join("local_score.yaml"),
+        )
+        .unwrap();
+        fs::write(
+            temp_dir.path().join("broken.yaml"),
+            "name: {en: \"Broken\"}",
+        )
+        .unwrap();
+        fs::write(temp_dir.path().join("score_template.yaml"), "").unwrap();
+
+        // One report before the first file, then one per file (skipped ones included)
+        let mut reports = Vec::new();
+        load_score_library_with_progress(&[temp_dir.path()], false, |p| reports.push(p)).unwrap();
+        let total = load_bundled_scores().count() + 2;
+        assert_eq!(reports.len(), total + 1);
+        assert_eq!(reports[0], LoadProgress { loaded: 0, total });
+        assert!(reports
+            .iter()
+            .enumerate()
+            .all(|(i, p)| p.loaded == i && p.total == total));
+
+        // The stream ends with the loaded library (or the error in strict mode)
+        let stream = |strict| {
+            iced::futures::executor::block_on_stream(load_score_library_stream(
+                vec![temp_dir.path().to_path_buf()],
+                strict,
+            ))
+            .collect::<Vec<_>>()
+        };
+        let events = stream(false);
+        assert_eq!(events.len(), total + 2);
+        assert!(matches!(
+            events.last(),
+            Some(LoadEvent::Finished(Ok(library))) if library.count() == total - 2
+        ));
+        assert!(matches!(
+            stream(true).last(),
+            Some(LoadEvent::Finished(Err(ScoreLoadError::YamlParse { .. })))
+        ));
+    }
+
+    #[test]
+    fn test_user_scores_override_bundled() {
+        let temp_dir = TempDir::new().unwrap();
+
+        // Same ID as a bundled score, moved to another specialty
+        let override_yaml = fs::read_to_string("scores/cardiology/has_bled.yaml")
+            .unwrap()
+            .replace("en: \"HAS-BLED", "en: \"Local HAS-BLED")
+            .replace("specialty: Cardiology", "specialty: InternalMedicine");
+        fs::write(temp_dir.path().join("has_bled.yaml"), override_yaml).unwrap();
+
+        let library = load_score_library(&[temp_dir.path()]).unwrap();
+        let bundled_count = load_bundled_scores().count();
+
+        assert_eq!(library.count(), bundled_count);
+        assert!(library
+            .get_score("has_bled")
+            .unwrap()
+            .name
+            .text("en")
+            .starts_with("Local HAS-BLED"));
+        assert!(!library.by_specialty[&Specialty::Cardiology].contains(&"has_bled".to_string()));
+        assert_eq!(
+            library
+                .get_scores_for_specialty(Specialty::InternalMedicine)
+                .len(),
+            1
+        );
+        assert_eq!(
+            library.origin("has_bled"),
+            Some(&ScoreOrigin::Directory(temp_dir.path().to_path_buf()))
+        );
+        assert_eq!(library.origin("grace"), Some(&ScoreOrigin::Bundled));
+
+        // A missing user directory falls back to the bundled library
+        let library = load_score_library(&[temp_dir.path().join("missing")]).unwrap();
+        assert_eq!(library.count(), bundled_count);
+    }
+
+    #[test]
+    fn test_explicit_id_survives_rename() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = fs::read_to_string("scores/cardiology/has_bled.yaml").unwrap();
+        fs::write(temp_dir.path().join("has_bled_2024.yaml"), &source).unwrap();
+
+        let library = load_all_scores(temp_dir.path()).unwrap();
+        assert!(library.get_score("has_bled").is_some());
+        assert!(library.get_score("has_bled_2024").is_none());
+
+        // Legacy files without an id fall back to the filename
+        fs::write(
+            temp_dir.path().join("has_bled_2024.yaml"),
+            source.replace("id: \"has_bled\"\n", ""),
+        )
+        .unwrap();
+        let library = load_all_scores(temp_dir.path()).unwrap();
+        assert!(library.get_score("has_bled_2024").is_some());
+    }
+
+    #[test]
+    fn test_duplicate_score_ids_rejected() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = fs::read_to_string("scores/cardiology/has_bled.yaml").unwrap();
+        fs::write(temp_dir.path().join("a.yaml"), &source).unwrap();
+        fs::write(temp_dir.path().join("b.yaml"), &source).unwrap();
+
+        match load_all_scores(temp_dir.path()) {
+            Err(ScoreLoadError::DuplicateScoreId { id, .. }) => assert_eq!(id, "has_bled"),
+            other => panic!("Expected DuplicateScoreId, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_invalid_score_id_rejected() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("has_bled.yaml");
+        let source = fs::read_to_string("scores/cardiology/has_bled.yaml")
+            .unwrap()
+            .replace("id: \"has_bled\"", "id: \"HAS-BLED\"");
+        fs::write(&path, source).unwrap();
+
+        match load_score_from_file(&path) {
+            Err(ScoreLoadError::InvalidScore { reason, .. }) => {
+                assert!(reason.contains("Invalid id 'HAS-BLED'"))
+            }
+            other => panic!("Expected InvalidScore, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_replacement_for_superseded_score() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = fs::read_to_string("scores/cardiology/cha2ds2_va.yaml").unwrap();
+        fs::write(temp_dir.path().join("cha2ds2_va.yaml"), &source).unwrap();
+        fs::write(
+            temp_dir.path().join("chads2.yaml"),
+            source.replace(
+                "id: \"cha2ds2_va\"",
+                "id: \"chads2\"\nsuperseded_by: \"cha2ds2_va\"",
+            ),
+        )
+        .unwrap();
+
+        let library = load_all_scores(temp_dir.path()).unwrap();
+        assert!(library.get_score("chads2").unwrap().is_deprecated());
+        assert!(!library.get_score("cha2ds2_va").unwrap().is_deprecated());
+
+        let (replacement_id, _) = library.replacement_for("chads2").unwrap();
+        assert_eq!(replacement_id, "cha2ds2_va");
+        assert!(library.replacement_for("cha2ds2_va").is_none());
+    }
+
+    #[test]
+    fn test_alias_index() {
+        let mut library = load_bundled_scores();
+        assert_eq!(library.find_by_alias("CHA2DS2 VASc"), vec!["cha2ds2_va"]);
+        assert_eq!(
+            library.find_by_alias("Schlaganfallrisiko bei Vorhofflimmern"),
+            vec!["cha2ds2_va"]
+        );
+        assert!(library.find_by_alias("nonexistent").is_empty());
+
+        // Replacing a score drops its old aliases
+        let mut renamed = library.get_score("grace").unwrap().clone();
+        renamed.aliases = Default::default();
+        library.insert("grace".to_string(), renamed, ScoreOrigin::Bundled);
+        assert!(library.find_by_alias("ACS risk").is_empty());
+        assert_eq!(library.find_by_alias("GRACE ACS Risk Score"), vec!["grace"]);
+    }
+
+    #[test]
+    fn test_display_order() {
+        let mut library = load_bundled_scores();
+        let ids = |library: &ScoreLibrary, specialty| -> Vec<String> {
+            library
+                .iter()
+                .filter(|(_, score)| score.specialty == specialty)
+                .map(|(id, _)| id.to_string())
+                .collect()
+        };
+        assert_eq!(
+            ids(&library, Specialty::Cardiology),
+            vec!["cha2ds2_va", "grace", "has_bled"]
+        );
+
+        // Specialties in display order, each listed once
+        let specialties: Vec<Specialty> = library.iter().map(|(_, score)| score.specialty).fold(
+            Vec::new(),
+            |mut seen, specialty| {
+                if seen.last() != Some(&specialty) {
+                    seen.push(specialty);
+                }
+                seen
+            },
+        );
+        assert_eq!(specialties, library.get_specialties());
+        assert_eq!(library.iter().count(), library.count());
+
+        // An explicit sort_order comes first
+        let mut has_bled = library.get_score("has_bled").unwrap().clone();
+        has_bled.sort_order = Some(1);
+        library.insert("has_bled".to_string(), has_bled, ScoreOrigin::Bundled);
+        assert_eq!(
+            ids(&library, Specialty::Cardiology),
+            vec!["has_bled", "cha2ds2_va", "grace"]
+        );
+        assert_eq!(
+            library.get_scores_for_specialty(Specialty::Cardiology)[0].sort_order,
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn test_specialty_scores_with_shared_name() {
+        let mut library = load_bundled_scores();
+        let grace = library.get_score("grace").unwrap().clone();
+        library.insert(
+            "grace_local".to_string(),
+            grace,
+            ScoreOrigin::Directory(PathBuf::from("local")),
+        );
+
+        let cardiology = library.specialty_scores(Specialty::Cardiology);
+        let ids: Vec<&str> = cardiology.iter().map(|(id, _)| *id).collect();
+        assert_eq!(ids, vec!["cha2ds2_va", "grace", "grace_local", "has_bled"]);
+        assert_eq!(cardiology[1].1.name, cardiology[2].1.name);
+        assert_eq!(
+            library
+                .get_scores_for_specialty(Specialty::Cardiology)
+                .len(),
+            4
+        );
+    }
+
+    #[test]
+    fn test_filter_by_metadata() {
+        let library = load_bundled_scores();
+
+        let by_tag = library.filter(&ScoreFilter {
+            tag: Some("Atrial Fibrillation".to_string()),
+            ..Default::default()
+        });
+        let ids: Vec<&str> = by_tag.iter().map(|(id, _)| *id).collect();
+        assert_eq!(ids, vec!["cha2ds2_va", "has_bled"]);
+
+        let screening = library.filter(&ScoreFilter {
+            category: Some(ScoreCategory::Screening),
+            ..Default::default()
+        });
+        assert_eq!(screening.len(), 1);
+        assert_eq!(screening[0].0, "stop_bang");
+
+        // German text search across tags and clinical question
+        let nephrology_risk = library.filter(&ScoreFilter {
+            specialty: Some(Specialty::Nephrology),
+            category: Some(ScoreCategory::RiskStratification),
+            text: Some("nierenersatz".to_string()),
+            ..Default::default()
+        });
+        assert_eq!(nephrology_risk.len(), 1);
+        assert_eq!(nephrology_risk[0].0, "kfre");
+
+        // Aliases are searched too
+        let by_alias = library.filter(&ScoreFilter {
+            text: Some("lee-index".to_string()),
+            ..Default::default()
+        });
+        assert_eq!(by_alias.len(), 1);
+        assert_eq!(by_alias[0].0, "rcri");
+
+        assert_eq!(
+            library.filter(&ScoreFilter::default()).len(),
+            library.count()
+        );
+    }
+
     #[test]
     fn test_load_all_scores() {
         // Test loading from the actual scores directory
@@ -437,7 +2668,8 @@ interpretation:
 
         // Test get_specialties
         let specialties = library.get_specialties();
-        assert!(specialties.contains(&Specialty::Cardiology));
+        assert_eq!(specialties, vec![Specialty::Cardiology]);
+        assert_eq!(library.specialty_counts(), vec![(Specialty::Cardiology, 1)]);
 
         // Test count
         assert_eq!(library.count(), 1);
@@ -1,7 +1,35 @@
 // Scores module - score loading and calculation
+//
+// `calculator`, `condition`, `constraints`, `dates`, `durations`, `formulas`, `numbers`,
+// `provenance`, `request` and `test_cases` are the calculation core; loading score files and
+// packs and importing questionnaires and third-party calculators needs the `loader` feature.
+#[cfg(feature = "loader")]
+pub mod bases;
 pub mod calculator;
+pub mod condition;
+pub mod constraints;
+pub mod dates;
+pub mod durations;
 pub mod formulas;
+#[cfg(feature = "loader")]
 pub mod loader;
+pub mod numbers;
+#[cfg(feature = "loader")]
+pub mod pack;
+pub mod provenance;
+#[cfg(feature = "loader")]
+pub mod questionnaire;
+pub mod request;
+pub mod test_cases;
+#[cfg(feature = "loader")]
+pub mod third_party;
+#[cfg(feature = "loader")]
+pub mod validation;
 
 pub use calculator::*;
+pub use dates::CalendarDate;
+#[cfg(feature = "loader")]
 pub use loader::*;
+pub use provenance::{provenance_hash, verify_provenance};
+pub use request::CalculationRequest;
+pub use test_cases::{run_test_case, run_test_cases, TestCaseFailure};
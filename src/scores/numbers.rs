@@ -0,0 +1,39 @@
+// numbers.rs
+// Numbers as people type them, with a decimal point or a decimal comma
+//
+// Shared by the input form, which follows the interface language, and the command
+// line, which accepts either separator.
+
+/// Parse a typed number; with `decimal_comma`, "1,36" as well as "1.36"
+///
+/// Thin spaces may group thousands ("10 000"). Anything else is rejected, and so
+/// are numbers too large to be finite.
+pub fn parse_decimal(input: &str, decimal_comma: bool) -> Option<f64> {
+    let number: String = input
+        .trim()
+        .chars()
+        .filter(|c| !matches!(c, '\u{2009}' | '\u{202F}'))
+        .collect();
+
+    let number = if decimal_comma {
+        // "1.234,5" is ambiguous (thousands point or typo), so no mixing
+        if number.contains('.') && number.contains(',') {
+            return None;
+        }
+        number.replace(',', ".")
+    } else {
+        number
+    };
+
+    let digits = number.strip_prefix(['-', '+']).unwrap_or(&number);
+    if digits.is_empty()
+        || digits.matches('.').count() > 1
+        || !digits.chars().all(|c| c.is_ascii_digit() || c == '.')
+    {
+        return None;
+    }
+    number
+        .parse()
+        .ok()
+        .filter(|number: &f64| number.is_finite())
+}
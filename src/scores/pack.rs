@@ -0,0 +1,713 @@
+// pack.rs
+// Score packs: zip bundles (.klinpack) of score definitions with a manifest
+
+use crate::config::{LocalizedText, ScoreDefinition};
+use crate::scores::bases::BaseDefinitions;
+use crate::scores::loader::{is_valid_id, parse_score, score_id_for, ScoreFormat, ScoreLoadError};
+use ed25519_dalek::{Signature, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io::Read;
+use std::path::{Component, Path, PathBuf};
+use thiserror::Error;
+
+/// Manifest file inside a pack (and inside its install directory)
+pub const MANIFEST_FILE: &str = "klinpack.yaml";
+
+/// Optional detached ed25519 signature (hex) over the exact bytes of the manifest
+pub const SIGNATURE_FILE: &str = "klinpack.sig";
+
+/// Subdirectory of the user scores directory that holds installed packs
+pub const PACKS_DIR: &str = "packs";
+
+/// Errors that can occur when reading or installing a score pack
+#[derive(Error, Debug)]
+pub enum PackError {
+    #[error("Failed to read score pack {path}: {source}")]
+    Read {
+        path: String,
+        source: std::io::Error,
+    },
+
+    #[error("Invalid score pack archive: {0}")]
+    Archive(#[from] zip::result::ZipError),
+
+    #[error("Score pack has no {MANIFEST_FILE}")]
+    MissingManifest,
+
+    #[error("Invalid pack manifest: {0}")]
+    InvalidManifest(String),
+
+    #[error("File '{0}' is listed in the manifest but missing from the pack")]
+    MissingFile(String),
+
+    #[error("Unsupported file name in score pack: '{0}'")]
+    InvalidFileName(String),
+
+    #[error(transparent)]
+    InvalidScore(#[from] ScoreLoadError),
+
+    #[error("Score pack failed integrity check: {0}")]
+    Integrity(String),
+
+    #[error("Failed to install score pack into {path}: {source}")]
+    Install {
+        path: String,
+        source: std::io::Error,
+    },
+}
+
+/// Pack description, stored as `klinpack.yaml` at the root of the archive
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PackManifest {
+    /// Stable identifier; also the install directory name
+    pub id: String,
+    pub name: LocalizedText,
+    pub version: String,
+    #[serde(default)]
+    pub publisher: Option<String>,
+    #[serde(default)]
+    pub description: Option<LocalizedText>,
+    /// Score files in the archive (paths relative to the archive root)
+    pub scores: Vec<String>,
+    /// SHA-256 (hex) of each score file, keyed by file name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub checksums: BTreeMap<String, String>,
+    /// ed25519 public key (hex) whose signature is in `klinpack.sig`
+    #[serde(default)]
+    pub signing_key: Option<String>,
+}
+
+/// Integrity of a score pack, established from its checksums and signature
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PackIntegrity {
+    /// The manifest carries no checksums
+    Unverified,
+    /// All score files match their SHA-256 checksums
+    Verified,
+    /// Checksums match and the manifest is signed by `key` (hex public key), which
+    /// is not among the trusted publisher keys
+    Untrusted { key: String },
+    /// Checksums match and the manifest is signed by `key`, a trusted publisher key
+    Signed { key: String },
+    /// Verification failed; the pack's scores are not loaded
+    Failed(String),
+}
+
+impl PackIntegrity {
+    pub fn is_failed(&self) -> bool {
+        matches!(self, PackIntegrity::Failed(_))
+    }
+
+    /// [`Signed`](Self::Signed) if the pack is signed by one of `trusted_keys`
+    ///
+    /// Verification only establishes which key signed a pack, and that key comes
+    /// with the pack; whether its publisher is trusted is up to the caller.
+    pub fn trusted(self, trusted_keys: &[String]) -> Self {
+        match self {
+            PackIntegrity::Untrusted { key }
+                if trusted_keys
+                    .iter()
+                    .any(|trusted| parse_public_key(trusted).as_ref() == Some(&key)) =>
+            {
+                PackIntegrity::Signed { key }
+            }
+            integrity => integrity,
+        }
+    }
+}
+
+/// An installed pack found while loading a score directory
+#[derive(Debug, Clone)]
+pub struct PackStatus {
+    /// Install directory (`<scores dir>/packs/<id>`)
+    pub dir: PathBuf,
+    /// `None` if the manifest could not be read
+    pub manifest: Option<PackManifest>,
+    pub integrity: PackIntegrity,
+}
+
+impl PackStatus {
+    /// Whether the score file at `path` (inside `dir`) may be loaded: it must be
+    /// listed in the manifest of a pack that passed verification
+    pub fn allows(&self, path: &Path) -> bool {
+        let (Some(manifest), false) = (&self.manifest, self.integrity.is_failed()) else {
+            return false;
+        };
+        path.strip_prefix(&self.dir).is_ok_and(|relative| {
+            manifest
+                .scores
+                .iter()
+                .any(|name| Path::new(name) == relative)
+        })
+    }
+}
+
+/// A score definition file from a pack
+#[derive(Debug, Clone)]
+pub struct PackFile {
+    /// Path inside the archive, as listed in the manifest
+    pub name: String,
+    pub contents: String,
+    pub score_id: String,
+    pub score: ScoreDefinition,
+}
+
+/// A validated score pack, ready to install
+#[derive(Debug, Clone)]
+pub struct ScorePack {
+    pub manifest: PackManifest,
+    /// Manifest as found in the archive (the signature covers these exact bytes)
+    pub manifest_source: String,
+    pub signature: Option<String>,
+    pub integrity: PackIntegrity,
+    pub files: Vec<PackFile>,
+}
+
+/// Result of installing a pack
+#[derive(Debug, Clone)]
+pub struct InstalledPack {
+    pub manifest: PackManifest,
+    pub integrity: PackIntegrity,
+    /// Directory the pack was installed into
+    pub dir: PathBuf,
+    pub score_ids: Vec<String>,
+}
+
+/// Read and validate a `.klinpack` archive.
+///
+/// Every score listed in the manifest must parse and pass the same validation as
+/// score files on disk, and score IDs must be unique within the pack. If the manifest
+/// carries checksums (and a signature), they must match.
+pub fn read_pack<P: AsRef<Path>>(pack_path: P) -> Result<ScorePack, PackError> {
+    let pack_path = pack_path.as_ref();
+    let file = fs::File::open(pack_path).map_err(|e| PackError::Read {
+        path: pack_path.display().to_string(),
+        source: e,
+    })?;
+    let mut archive = zip::ZipArchive::new(file)?;
+
+    let manifest_source = match read_entry(&mut archive, MANIFEST_FILE) {
+        Err(PackError::MissingFile(_)) => return Err(PackError::MissingManifest),
+        other => other?,
+    };
+    let manifest: PackManifest = serde_yaml::from_str(&manifest_source)
+        .map_err(|e| PackError::InvalidManifest(e.to_string()))?;
+    validate_manifest(&manifest)?;
+    let signature = match read_entry(&mut archive, SIGNATURE_FILE) {
+        Err(PackError::MissingFile(_)) => None,
+        other => Some(other?),
+    };
+
+    let mut contents = Vec::new();
+    for name in &manifest.scores {
+        contents.push((name.as_str(), read_entry(&mut archive, name)?));
+    }
+    let integrity = verify_integrity(
+        &manifest,
+        &manifest_source,
+        signature.as_deref(),
+        contents.iter().map(|(name, text)| (*name, text.as_bytes())),
+    );
+    if let PackIntegrity::Failed(reason) = integrity {
+        return Err(PackError::Integrity(reason));
+    }
+
+    let mut files = Vec::new();
+    let mut seen_ids: HashMap<String, String> = HashMap::new();
+    for (name, contents) in contents {
+        let name = name.to_string();
+        let score = parse_score(&contents, Path::new(&name), BaseDefinitions::bundled())?;
+        let score_id = score_id_for(&score, Path::new(&name));
+        if let Some(first) = seen_ids.insert(score_id.clone(), name.clone()) {
+            return Err(ScoreLoadError::DuplicateScoreId {
+                id: score_id,
+                first,
+                second: name,
+            }
+            .into());
+        }
+        files.push(PackFile {
+            name,
+            contents,
+            score_id,
+            score,
+        });
+    }
+
+    Ok(ScorePack {
+        manifest,
+        manifest_source,
+        signature,
+        integrity,
+        files,
+    })
+}
+
+/// Validate a pack and install it below `scores_dir` (usually
+/// [`user_scores_dir`](crate::scores::user_scores_dir)).
+///
+/// The pack is written to `<scores_dir>/packs/<pack id>/`, replacing an earlier
+/// installation of the same pack. Nothing is written if validation fails.
+pub fn install_pack<P: AsRef<Path>, Q: AsRef<Path>>(
+    pack_path: P,
+    scores_dir: Q,
+) -> Result<InstalledPack, PackError> {
+    let pack = read_pack(pack_path)?;
+    let dir = scores_dir.as_ref().join(PACKS_DIR).join(&pack.manifest.id);
+    let install_error = |e| PackError::Install {
+        path: dir.display().to_string(),
+        source: e,
+    };
+
+    if dir.exists() {
+        fs::remove_dir_all(&dir).map_err(install_error)?;
+    }
+    fs::create_dir_all(&dir).map_err(install_error)?;
+
+    // Written verbatim so the signature can be checked again on every load
+    fs::write(dir.join(MANIFEST_FILE), &pack.manifest_source).map_err(install_error)?;
+    if let Some(signature) = &pack.signature {
+        fs::write(dir.join(SIGNATURE_FILE), signature).map_err(install_error)?;
+    }
+
+    for file in &pack.files {
+        let target = dir.join(&file.name);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent).map_err(install_error)?;
+        }
+        fs::write(&target, &file.contents).map_err(install_error)?;
+    }
+
+    Ok(InstalledPack {
+        score_ids: pack.files.iter().map(|f| f.score_id.clone()).collect(),
+        manifest: pack.manifest,
+        integrity: pack.integrity,
+        dir,
+    })
+}
+
+/// Check the installed packs in `<scores_dir>/packs/`.
+///
+/// Files are re-verified on every load, so scores edited after installation are
+/// detected (and not loaded) when the pack carries checksums.
+pub fn installed_packs(scores_dir: &Path) -> Vec<PackStatus> {
+    let Ok(entries) = fs::read_dir(scores_dir.join(PACKS_DIR)) else {
+        return Vec::new();
+    };
+
+    let mut packs: Vec<PackStatus> = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|dir| dir.join(MANIFEST_FILE).is_file())
+        .map(|dir| {
+            let (manifest, integrity) = match check_installed_pack(&dir) {
+                Ok((manifest, integrity)) => (Some(manifest), integrity),
+                Err(e) => (None, PackIntegrity::Failed(e.to_string())),
+            };
+            PackStatus {
+                dir,
+                manifest,
+                integrity,
+            }
+        })
+        .collect();
+    packs.sort_by(|a, b| a.dir.cmp(&b.dir));
+    packs
+}
+
+/// Read an installed pack's manifest and verify its files
+fn check_installed_pack(dir: &Path) -> Result<(PackManifest, PackIntegrity), PackError> {
+    let read = |name: &str| {
+        fs::read(dir.join(name)).map_err(|e| PackError::Read {
+            path: dir.join(name).display().to_string(),
+            source: e,
+        })
+    };
+
+    let manifest_source = String::from_utf8_lossy(&read(MANIFEST_FILE)?).into_owned();
+    let manifest: PackManifest = serde_yaml::from_str(&manifest_source)
+        .map_err(|e| PackError::InvalidManifest(e.to_string()))?;
+    validate_manifest(&manifest)?;
+    let signature = read(SIGNATURE_FILE)
+        .ok()
+        .map(|bytes| String::from_utf8_lossy(&bytes).into_owned());
+
+    let mut contents = Vec::new();
+    for name in &manifest.scores {
+        contents.push((name.as_str(), read(name)?));
+    }
+    let integrity = verify_integrity(
+        &manifest,
+        &manifest_source,
+        signature.as_deref(),
+        contents
+            .iter()
+            .map(|(name, bytes)| (*name, bytes.as_slice())),
+    );
+    Ok((manifest, integrity))
+}
+
+/// Verify score files against the manifest's checksums, and the manifest against
+/// its signature
+///
+/// A valid signature is [`Untrusted`](PackIntegrity::Untrusted): the key is
+/// taken from the manifest, so anyone can sign a pack. See [`PackIntegrity::trusted`].
+pub fn verify_integrity<'a>(
+    manifest: &PackManifest,
+    manifest_source: &str,
+    signature: Option<&str>,
+    files: impl IntoIterator<Item = (&'a str, &'a [u8])>,
+) -> PackIntegrity {
+    if manifest.checksums.is_empty() {
+        if signature.is_some() || manifest.signing_key.is_some() {
+            // A signature over a manifest without checksums would not cover the scores
+            return PackIntegrity::Failed("signed pack has no checksums".to_string());
+        }
+        return PackIntegrity::Unverified;
+    }
+
+    for (name, contents) in files {
+        let Some(expected) = manifest.checksums.get(name) else {
+            return PackIntegrity::Failed(format!("no checksum for '{name}'"));
+        };
+        if !sha256_hex(contents).eq_ignore_ascii_case(expected.trim()) {
+            return PackIntegrity::Failed(format!("checksum mismatch for '{name}'"));
+        }
+    }
+
+    match (signature, &manifest.signing_key) {
+        (None, None) => PackIntegrity::Verified,
+        (Some(signature), Some(key)) => {
+            if verify_signature(key, signature, manifest_source.as_bytes()) {
+                PackIntegrity::Untrusted {
+                    key: key.trim().to_ascii_lowercase(),
+                }
+            } else {
+                PackIntegrity::Failed("invalid signature".to_string())
+            }
+        }
+        (Some(_), None) => PackIntegrity::Failed(format!(
+            "{SIGNATURE_FILE} present but the manifest has no signing_key"
+        )),
+        (None, Some(_)) => PackIntegrity::Failed(format!("{SIGNATURE_FILE} is missing")),
+    }
+}
+
+/// SHA-256 of `bytes` as lowercase hex, the format used in `checksums`
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    encode_hex(&Sha256::digest(bytes))
+}
+
+/// An ed25519 public key written as hex, in lowercase; `None` if `text` is not one
+pub fn parse_public_key(text: &str) -> Option<String> {
+    let key = text.trim().to_ascii_lowercase();
+    decode_hex(&key)
+        .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+        .and_then(|bytes| VerifyingKey::from_bytes(&bytes).ok())
+        .map(|_| key)
+}
+
+/// Check a hex ed25519 signature with a hex public key
+pub(crate) fn verify_signature(key: &str, signature: &str, message: &[u8]) -> bool {
+    let key = decode_hex(key.trim())
+        .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+        .and_then(|bytes| VerifyingKey::from_bytes(&bytes).ok());
+    let signature = decode_hex(signature.trim())
+        .and_then(|bytes| <[u8; 64]>::try_from(bytes).ok())
+        .map(|bytes| Signature::from_bytes(&bytes));
+
+    match (key, signature) {
+        (Some(key), Some(signature)) => key.verify_strict(message, &signature).is_ok(),
+        _ => false,
+    }
+}
+
+pub(crate) fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+pub(crate) fn decode_hex(text: &str) -> Option<Vec<u8>> {
+    if !text.len().is_multiple_of(2) || !text.is_ascii() {
+        return None;
+    }
+    (0..text.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&text[i..i + 2], 16).ok())
+        .collect()
+}
+
+/// Check the manifest before any score is read
+fn validate_manifest(manifest: &PackManifest) -> Result<(), PackError> {
+    if !is_valid_id(&manifest.id) {
+        return Err(PackError::InvalidManifest(format!(
+            "Invalid id '{}' (use lowercase letters, digits and underscores)",
+            manifest.id
+        )));
+    }
+    if manifest.name.is_blank() {
+        return Err(PackError::InvalidManifest("Pack name is empty".to_string()));
+    }
+    if manifest.scores.is_empty() {
+        return Err(PackError::InvalidManifest(
+            "Pack must list at least one score file".to_string(),
+        ));
+    }
+
+    for name in &manifest.scores {
+        // Only plain relative paths to score files; nothing that could escape the
+        // install directory or shadow the manifest
+        let path = Path::new(name);
+        let plain = path
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)));
+        if !plain || ScoreFormat::from_path(path).is_none() || name == MANIFEST_FILE {
+            return Err(PackError::InvalidFileName(name.clone()));
+        }
+    }
+    Ok(())
+}
+
+/// Read a UTF-8 text entry from the archive
+fn read_entry(archive: &mut zip::ZipArchive<fs::File>, name: &str) -> Result<String, PackError> {
+    let mut entry = match archive.by_name(name) {
+        Ok(entry) => entry,
+        Err(zip::result::ZipError::FileNotFound) => {
+            return Err(PackError::MissingFile(name.to_string()))
+        }
+        Err(e) => return Err(e.into()),
+    };
+    let mut contents = String::new();
+    entry
+        .read_to_string(&mut contents)
+        .map_err(|e| PackError::Read {
+            path: name.to_string(),
+            source: e,
+        })?;
+    Ok(contents)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::TempDir;
+
+    const MANIFEST: &str = r#"
+id: "test_pack"
+name: {en: "Test Pack", de: "Testpaket"}
+version: "2026.1"
+publisher: "Test Hospital"
+scores:
+  - "cardiology/has_bled.yaml"
+  - "grace.yaml"
+"#;
+
+    fn write_pack(path: &Path, entries: &[(&str, &str)]) {
+        let mut writer = zip::ZipWriter::new(fs::File::create(path).unwrap());
+        for (name, contents) in entries {
+            writer
+                .start_file(*name, zip::write::SimpleFileOptions::default())
+                .unwrap();
+            writer.write_all(contents.as_bytes()).unwrap();
+        }
+        writer.finish().unwrap();
+    }
+
+    fn bundled(name: &str) -> String {
+        fs::read_to_string(Path::new("scores/cardiology").join(name)).unwrap()
+    }
+
+    #[test]
+    fn test_install_pack() {
+        let temp_dir = TempDir::new().unwrap();
+        let pack_path = temp_dir.path().join("test.klinpack");
+        let has_bled = bundled("has_bled.yaml");
+        let grace = bundled("grace.yaml");
+        write_pack(
+            &pack_path,
+            &[
+                (MANIFEST_FILE, MANIFEST),
+                ("cardiology/has_bled.yaml", &has_bled),
+                ("grace.yaml", &grace),
+                ("README.txt", "not listed, not installed"),
+            ],
+        );
+
+        let scores_dir = temp_dir.path().join("scores");
+        let installed = install_pack(&pack_path, &scores_dir).unwrap();
+        assert_eq!(installed.manifest.name.text("de"), "Testpaket");
+        assert_eq!(installed.score_ids, vec!["has_bled", "grace"]);
+        assert_eq!(installed.dir, scores_dir.join("packs/test_pack"));
+        assert!(installed.dir.join("cardiology/has_bled.yaml").is_file());
+        assert!(!installed.dir.join("README.txt").exists());
+
+        // The manifest is not mistaken for a score when loading the directory
+        let library = crate::scores::load_all_scores(&scores_dir).unwrap();
+        assert_eq!(library.count(), 2);
+        assert!(library.get_score("grace").is_some());
+
+        // Reinstalling replaces the earlier installation
+        write_pack(
+            &pack_path,
+            &[
+                (MANIFEST_FILE, &MANIFEST.replace("  - \"grace.yaml\"\n", "")),
+                ("cardiology/has_bled.yaml", &has_bled),
+            ],
+        );
+        install_pack(&pack_path, &scores_dir).unwrap();
+        assert!(!installed.dir.join("grace.yaml").exists());
+    }
+
+    #[test]
+    fn test_invalid_packs_are_rejected() {
+        let temp_dir = TempDir::new().unwrap();
+        let pack_path = temp_dir.path().join("bad.klinpack");
+        let scores_dir = temp_dir.path().join("scores");
+        let grace = bundled("grace.yaml");
+
+        write_pack(&pack_path, &[("grace.yaml", &grace)]);
+        assert!(matches!(
+            read_pack(&pack_path),
+            Err(PackError::MissingManifest)
+        ));
+
+        write_pack(
+            &pack_path,
+            &[(MANIFEST_FILE, MANIFEST), ("grace.yaml", &grace)],
+        );
+        assert!(matches!(
+            read_pack(&pack_path),
+            Err(PackError::MissingFile(name)) if name == "cardiology/has_bled.yaml"
+        ));
+
+        let escaping = MANIFEST.replace("cardiology/has_bled.yaml", "../has_bled.yaml");
+        write_pack(
+            &pack_path,
+            &[(MANIFEST_FILE, &escaping), ("grace.yaml", &grace)],
+        );
+        assert!(matches!(
+            read_pack(&pack_path),
+            Err(PackError::InvalidFileName(_))
+        ));
+
+        // One invalid score rejects the whole pack and nothing is installed
+        write_pack(
+            &pack_path,
+            &[
+                (MANIFEST_FILE, MANIFEST),
+                ("cardiology/has_bled.yaml", "name: {en: \"Broken\"}"),
+                ("grace.yaml", &grace),
+            ],
+        );
+        assert!(matches!(
+            install_pack(&pack_path, &scores_dir),
+            Err(PackError::InvalidScore(_))
+        ));
+        assert!(!scores_dir.exists());
+
+        fs::write(&pack_path, "not a zip").unwrap();
+        assert!(matches!(read_pack(&pack_path), Err(PackError::Archive(_))));
+    }
+
+    #[test]
+    fn test_checksums_and_signature() {
+        use ed25519_dalek::{Signer, SigningKey};
+
+        let temp_dir = TempDir::new().unwrap();
+        let pack_path = temp_dir.path().join("signed.klinpack");
+        let scores_dir = temp_dir.path().join("scores");
+        let grace = bundled("grace.yaml");
+
+        let signing_key = SigningKey::from_bytes(&[7; 32]);
+        let key_hex = encode_hex(&signing_key.verifying_key().to_bytes());
+
+        let manifest = format!(
+            "id: \"signed_pack\"\nname: \"Signed\"\nversion: \"1\"\nscores: [\"grace.yaml\"]\n\
+             checksums:\n  grace.yaml: \"{}\"\nsigning_key: \"{}\"\n",
+            sha256_hex(grace.as_bytes()),
+            key_hex
+        );
+        let signature = encode_hex(&signing_key.sign(manifest.as_bytes()).to_bytes());
+
+        write_pack(
+            &pack_path,
+            &[
+                (MANIFEST_FILE, &manifest),
+                (SIGNATURE_FILE, &signature),
+                ("grace.yaml", &grace),
+            ],
+        );
+        let installed = install_pack(&pack_path, &scores_dir).unwrap();
+        let untrusted = PackIntegrity::Untrusted {
+            key: key_hex.clone(),
+        };
+        assert_eq!(installed.integrity, untrusted);
+
+        // Only a trusted key makes the signature count
+        let other_key = encode_hex(&SigningKey::from_bytes(&[8; 32]).verifying_key().to_bytes());
+        assert_eq!(untrusted.clone().trusted(&[other_key]), untrusted);
+        assert_eq!(
+            untrusted
+                .clone()
+                .trusted(&[format!(" {} ", key_hex.to_uppercase())]),
+            PackIntegrity::Signed {
+                key: key_hex.clone()
+            }
+        );
+
+        // Integrity is checked again when the installed pack is loaded
+        let library = crate::scores::load_all_scores(&scores_dir).unwrap();
+        assert_eq!(library.count(), 1);
+        assert_eq!(library.packs[0].integrity, untrusted);
+
+        // Editing an installed score invalidates the pack
+        let installed_score = installed.dir.join("grace.yaml");
+        fs::write(&installed_score, grace.replace("GRACE", "Local GRACE")).unwrap();
+        let library = crate::scores::load_all_scores(&scores_dir).unwrap();
+        assert_eq!(library.count(), 0);
+        assert!(library.packs[0].integrity.is_failed());
+
+        // Tampered archives are rejected at import
+        let tampered = grace.replace("GRACE", "Local GRACE");
+        write_pack(
+            &pack_path,
+            &[
+                (MANIFEST_FILE, &manifest),
+                (SIGNATURE_FILE, &signature),
+                ("grace.yaml", &tampered),
+            ],
+        );
+        assert!(matches!(
+            read_pack(&pack_path),
+            Err(PackError::Integrity(_))
+        ));
+
+        let resigned_elsewhere = manifest.replace("name: \"Signed\"", "name: \"Forged\"");
+        write_pack(
+            &pack_path,
+            &[
+                (MANIFEST_FILE, &resigned_elsewhere),
+                (SIGNATURE_FILE, &signature),
+                ("grace.yaml", &grace),
+            ],
+        );
+        assert!(matches!(
+            read_pack(&pack_path),
+            Err(PackError::Integrity(_))
+        ));
+
+        // Checksums without a signature still verify the files
+        let unsigned = manifest.replace(&format!("signing_key: \"{}\"\n", key_hex), "");
+        write_pack(
+            &pack_path,
+            &[(MANIFEST_FILE, &unsigned), ("grace.yaml", &grace)],
+        );
+        assert_eq!(
+            read_pack(&pack_path).unwrap().integrity,
+            PackIntegrity::Verified
+        );
+    }
+}
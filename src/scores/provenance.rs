@@ -0,0 +1,177 @@
+// provenance.rs
+// Reproducibility hash of a calculation
+//
+// The hash covers everything that determines a result: the score id and the
+// version of its definition, the inputs and the version of the calculation
+// engine. Recomputing it from the same definition and inputs verifies an
+// exported result; a changed definition, input or engine gives a different hash.
+
+use crate::config::{InputType, ScoreDefinition, FALLBACK_LANGUAGE};
+use crate::scores::calculator::InputValue;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+
+/// Version of the calculation engine, part of every provenance hash
+pub const ENGINE_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// What the provenance hash covers, serialized as compact JSON with sorted keys
+#[derive(Serialize)]
+struct ProvenanceRecord<'a> {
+    engine: &'a str,
+    inputs: BTreeMap<&'a str, InputValue>,
+    score: &'a str,
+    version: &'a str,
+}
+
+/// SHA-256 (hex) of a calculation of `score_def` with `inputs`
+///
+/// The score is identified by its `id`, or its English name for legacy
+/// definitions without one. The inputs are hashed as the calculation sees them
+/// (see [`canonical_inputs`]), sorted by field name.
+pub fn provenance_hash(
+    score_def: &ScoreDefinition,
+    inputs: &HashMap<String, InputValue>,
+) -> String {
+    let record = ProvenanceRecord {
+        engine: ENGINE_VERSION,
+        inputs: canonical_inputs(score_def, inputs),
+        score: score_def
+            .id
+            .as_deref()
+            .unwrap_or_else(|| score_def.name.text(FALLBACK_LANGUAGE)),
+        version: &score_def.version,
+    };
+    let mut hasher = Sha256::new();
+    serde_json::to_writer(&mut hasher, &record).expect("provenance record serializes");
+    let mut hex = String::with_capacity(64);
+    for byte in hasher.finalize() {
+        let _ = write!(hex, "{byte:02x}");
+    }
+    hex
+}
+
+/// Inputs that give the same calculation written the same way, whichever client
+/// entered them: fields the score does not define and unchecked boxes (the same as
+/// not given) are left out, and dropdowns not given take their preselected option
+fn canonical_inputs<'a>(
+    score_def: &'a ScoreDefinition,
+    inputs: &HashMap<String, InputValue>,
+) -> BTreeMap<&'a str, InputValue> {
+    score_def
+        .inputs
+        .iter()
+        .filter_map(|input| {
+            let value = match (input.input_type, inputs.get(&input.field)) {
+                (InputType::Boolean, Some(InputValue::Boolean(false))) => return None,
+                // As scored, so a value in conventional units hashes like its SI value
+                (_, Some(value @ InputValue::Quantity(_))) => value
+                    .number_in(input)
+                    .map(InputValue::Number)
+                    .unwrap_or_else(|| value.clone()),
+                (_, Some(value)) => value.clone(),
+                (InputType::Dropdown, None) => {
+                    InputValue::Dropdown(input.default_option()?.to_string())
+                }
+                (_, None) => return None,
+            };
+            Some((input.field.as_str(), value))
+        })
+        .collect()
+}
+
+/// Whether `hash` is the provenance hash of a calculation of `score_def` with `inputs`
+pub fn verify_provenance(
+    score_def: &ScoreDefinition,
+    inputs: &HashMap<String, InputValue>,
+    hash: &str,
+) -> bool {
+    provenance_hash(score_def, inputs).eq_ignore_ascii_case(hash.trim())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scores::{calculate_score, load_bundled_scores};
+
+    fn score() -> ScoreDefinition {
+        load_bundled_scores()
+            .get_score("cha2ds2_va")
+            .unwrap()
+            .clone()
+    }
+
+    fn inputs(age: f64) -> HashMap<String, InputValue> {
+        HashMap::from([
+            ("age".to_string(), InputValue::Number(age)),
+            ("hypertension".to_string(), InputValue::Boolean(true)),
+        ])
+    }
+
+    #[test]
+    fn test_hash_is_attached_to_result() {
+        let score = score();
+        let result = calculate_score(&score, &inputs(70.0)).unwrap();
+        assert_eq!(result.provenance.len(), 64);
+        assert!(verify_provenance(&score, &inputs(70.0), &result.provenance));
+        assert!(verify_provenance(
+            &score,
+            &inputs(70.0),
+            &result.provenance.to_uppercase()
+        ));
+    }
+
+    #[test]
+    fn test_hash_changes_with_inputs_and_definition() {
+        let score = score();
+        let hash = provenance_hash(&score, &inputs(70.0));
+        // Same points, different input: a different calculation
+        assert_ne!(hash, provenance_hash(&score, &inputs(71.0)));
+
+        let mut revised = score.clone();
+        revised.version = "2025-v1".to_string();
+        assert_ne!(hash, provenance_hash(&revised, &inputs(70.0)));
+
+        let mut renamed = score.clone();
+        renamed.id = Some("other_score".to_string());
+        assert_ne!(hash, provenance_hash(&renamed, &inputs(70.0)));
+    }
+
+    #[test]
+    fn test_hash_ignores_unknown_fields() {
+        let score = score();
+        let mut with_extra = inputs(70.0);
+        with_extra.insert("unused".to_string(), InputValue::Boolean(true));
+        assert_eq!(
+            provenance_hash(&score, &inputs(70.0)),
+            provenance_hash(&score, &with_extra)
+        );
+    }
+
+    #[test]
+    fn test_hash_is_canonical() {
+        // An unchecked box is the same calculation as a box not given
+        let score = score();
+        let mut unchecked = inputs(70.0);
+        unchecked.insert("diabetes".to_string(), InputValue::Boolean(false));
+        assert_eq!(
+            provenance_hash(&score, &inputs(70.0)),
+            provenance_hash(&score, &unchecked)
+        );
+
+        // So is a dropdown not given and its preselected option
+        let mut asa = load_bundled_scores().get_score("asa").unwrap().clone();
+        asa.inputs[0].default = Some(Some("asa_2".to_string()));
+        let preselected = HashMap::from([(
+            "asa_class".to_string(),
+            InputValue::Dropdown("asa_2".to_string()),
+        )]);
+        let not_given = calculate_score(&asa, &HashMap::new()).unwrap();
+        assert_eq!(not_given.total_score, 2);
+        assert_eq!(
+            not_given.provenance,
+            calculate_score(&asa, &preselected).unwrap().provenance
+        );
+    }
+}
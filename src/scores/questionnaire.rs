@@ -0,0 +1,584 @@
+// questionnaire.rs
+// Converts a FHIR R4 Questionnaire with scoring extensions into a score definition,
+// so questionnaires maintained on an institution's FHIR server can be calculated
+//
+// Mapping:
+// - `choice` items with `answerOption`s become dropdowns; the points of an option
+//   come from its `ordinalValue`, `itemWeight` or `iso21090-CO-value` extension
+//   (on the option or on its `valueCoding`)
+// - `boolean` items become checkboxes, worth the weight extension on the item
+// - `integer`, `decimal` and `quantity` items become number fields (no points), with
+//   `minValue`, `maxValue` and `questionnaire-unit`
+// - `group` items become sections; nested groups are flattened into the outermost one
+// - display, text, date and other unscored items are left out, as are calculated
+//   items (the questionnaire's own total)
+//
+// Labels take the questionnaire's language plus any `translation` extensions.
+// FHIR has no standard for interpretation bands, so the score gets one band over
+// its whole range; the bands are meant to be added to the written score file.
+
+use crate::config::{
+    CodeSystem, DropdownOption, InputField, InputSection, InputType, InterpretationRule,
+    LocalizedText, PointsValue, Reference, RiskLevel, ScoreCode, ScoreDefinition, ScoreMetadata,
+    ScoreRange, Specialty, FALLBACK_LANGUAGE,
+};
+use crate::scores::calculator::score_bounds;
+use crate::scores::loader::{is_valid_id, validate_score, ScoreLoadError};
+use serde_json::Value;
+use std::path::Path;
+use thiserror::Error;
+
+/// Extensions carrying the points of an answer or a boolean item
+const WEIGHT_EXTENSIONS: [&str; 3] = [
+    "http://hl7.org/fhir/StructureDefinition/ordinalValue",
+    "http://hl7.org/fhir/StructureDefinition/itemWeight",
+    "http://hl7.org/fhir/StructureDefinition/iso21090-CO-value",
+];
+
+const TRANSLATION_EXTENSION: &str = "http://hl7.org/fhir/StructureDefinition/translation";
+const UNIT_EXTENSION: &str = "http://hl7.org/fhir/StructureDefinition/questionnaire-unit";
+const MIN_VALUE_EXTENSION: &str = "http://hl7.org/fhir/StructureDefinition/minValue";
+const MAX_VALUE_EXTENSION: &str = "http://hl7.org/fhir/StructureDefinition/maxValue";
+const CALCULATED_EXTENSION: &str =
+    "http://hl7.org/fhir/uv/sdc/StructureDefinition/sdc-questionnaire-calculatedExpression";
+
+/// Item types that are not scored and left out
+const UNSCORED_TYPES: [&str; 9] = [
+    "display",
+    "string",
+    "text",
+    "date",
+    "dateTime",
+    "time",
+    "url",
+    "attachment",
+    "reference",
+];
+
+#[derive(Debug, Error)]
+pub enum QuestionnaireError {
+    #[error("Invalid JSON: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("Expected a FHIR Questionnaire, found resource type '{0}'")]
+    NotAQuestionnaire(String),
+
+    #[error("Questionnaire has no name, title or id to derive the score ID from")]
+    MissingId,
+
+    #[error("Item '{link_id}': {reason}")]
+    UnsupportedItem { link_id: String, reason: String },
+
+    #[error("Questionnaire has no scored items")]
+    NoScoredItems,
+
+    #[error("Converted score is invalid: {0}")]
+    InvalidScore(#[from] ScoreLoadError),
+}
+
+/// Convert a FHIR Questionnaire (JSON) into a score definition and its ID
+///
+/// The ID is taken from the questionnaire's `name`, else its `id` or `title`, in
+/// lowercase with underscores (e.g., "PHQ9" becomes `phq9`).
+pub fn score_from_questionnaire(
+    source: &str,
+) -> Result<(String, ScoreDefinition), QuestionnaireError> {
+    let questionnaire: Value = serde_json::from_str(source)?;
+    let resource_type = string(&questionnaire, "resourceType").unwrap_or_default();
+    if resource_type != "Questionnaire" {
+        return Err(QuestionnaireError::NotAQuestionnaire(
+            resource_type.to_string(),
+        ));
+    }
+
+    let id = ["name", "id", "title"]
+        .into_iter()
+        .filter_map(|key| string(&questionnaire, key))
+        .map(identifier)
+        .find(|id| is_valid_id(id))
+        .ok_or(QuestionnaireError::MissingId)?;
+    let language = string(&questionnaire, "language")
+        .and_then(|language| language.split(['-', '_']).next())
+        .unwrap_or(FALLBACK_LANGUAGE)
+        .to_string();
+
+    let mut converter = Converter {
+        language,
+        inputs: Vec::new(),
+        sections: Vec::new(),
+    };
+    for item in array(&questionnaire, "item") {
+        converter.item(item, None)?;
+    }
+    if converter.inputs.is_empty() {
+        return Err(QuestionnaireError::NoScoredItems);
+    }
+
+    let name = text(&questionnaire, "title", &converter.language)
+        .unwrap_or_else(|| converter.localized(id.clone()));
+    let url = string(&questionnaire, "url");
+    let references = url
+        .map(|url| Reference {
+            citation: Some(name.text(&converter.language).to_string()),
+            url: Some(url.to_string()),
+            ..Reference::default()
+        })
+        .into_iter()
+        .collect();
+    let codes = array(&questionnaire, "code")
+        .iter()
+        .filter_map(|coding| {
+            let system = match string(coding, "system")? {
+                "http://loinc.org" => CodeSystem::Loinc,
+                "http://snomed.info/sct" => CodeSystem::Snomed,
+                _ => return None,
+            };
+            Some(ScoreCode {
+                system,
+                code: string(coding, "code")?.to_string(),
+                display: string(coding, "display").map(str::to_string),
+            })
+        })
+        .collect();
+
+    let mut score = ScoreDefinition {
+        id: Some(id.clone()),
+        name,
+        aliases: Default::default(),
+        specialty: Specialty::Other,
+        sort_order: None,
+        version: string(&questionnaire, "version")
+            .unwrap_or("1.0")
+            .to_string(),
+        guideline_source: string(&questionnaire, "publisher")
+            .unwrap_or("FHIR Questionnaire")
+            .to_string(),
+        references,
+        validation_status: "draft".to_string(),
+        deprecated: false,
+        superseded_by: None,
+        description: text(&questionnaire, "description", &converter.language).unwrap_or_default(),
+        intended_use: text(&questionnaire, "purpose", &converter.language),
+        regulatory_note: None,
+        regions_validated: Vec::new(),
+        inputs: converter.inputs,
+        sections: converter.sections,
+        input_mode: None,
+        constraints: Vec::new(),
+        interpretation: Vec::new(),
+        formula: None,
+        metadata: ScoreMetadata {
+            codes,
+            notes: Some(format!(
+                "Imported from FHIR Questionnaire {}",
+                url.unwrap_or(&id)
+            )),
+            ..ScoreMetadata::default()
+        },
+        test_cases: Vec::new(),
+        conditions: Default::default(),
+    };
+    let (min, max) = score_bounds(&score).unwrap_or((0, 0));
+    score.interpretation.push(InterpretationRule {
+        score: ScoreRange::Range(format!("{}-{}", min, max)),
+        risk: LocalizedText::bilingual("Total score", "Gesamtpunktzahl"),
+        risk_lay: None,
+        risk_level: RiskLevel::None,
+        recommendation: LocalizedText::bilingual(
+            "No interpretation defined",
+            "Keine Interpretation hinterlegt",
+        ),
+        details: None,
+    });
+
+    validate_score(&mut score, Path::new(&format!("{}.json", id)))?;
+    Ok((id, score))
+}
+
+/// Collects the inputs and sections while walking the item tree
+struct Converter {
+    /// Primary language of the questionnaire's texts
+    language: String,
+    inputs: Vec<InputField>,
+    sections: Vec<InputSection>,
+}
+
+impl Converter {
+    /// Convert `item` and its children; `section` is the index of the outermost group
+    fn item(&mut self, item: &Value, section: Option<usize>) -> Result<(), QuestionnaireError> {
+        let link_id = string(item, "linkId").unwrap_or_default();
+        let unsupported = |reason: &str| QuestionnaireError::UnsupportedItem {
+            link_id: link_id.to_string(),
+            reason: reason.to_string(),
+        };
+        let item_type = string(item, "type").unwrap_or_default();
+        if UNSCORED_TYPES.contains(&item_type)
+            || extension(item, CALCULATED_EXTENSION).is_some()
+            || item["readOnly"] == true
+        {
+            return Ok(());
+        }
+        let label = text(item, "text", &self.language)
+            .unwrap_or_else(|| self.localized(link_id.to_string()));
+
+        if item_type == "group" {
+            let section = section.unwrap_or_else(|| {
+                self.sections.push(InputSection {
+                    id: identifier(link_id),
+                    label,
+                    fields: Vec::new(),
+                });
+                self.sections.len() - 1
+            });
+            for child in array(item, "item") {
+                self.item(child, Some(section))?;
+            }
+            return Ok(());
+        }
+        if !array(item, "item").is_empty() {
+            return Err(unsupported("only groups may have nested items"));
+        }
+
+        let mut input = InputField {
+            field: unique_field_name(link_id, "item", &self.inputs),
+            input_type: InputType::Number,
+            label,
+            unit: None,
+            points: PointsValue::Fixed(0),
+            help: None,
+            min: None,
+            max: None,
+            options: Vec::new(),
+            default: None,
+            conventional_unit: None,
+            widget: None,
+            loinc: Vec::new(),
+            elapsed: None,
+            duration_unit: None,
+            required: item["required"] == true,
+        };
+        match item_type {
+            "boolean" => {
+                input.input_type = InputType::Boolean;
+                input.points = PointsValue::Fixed(weight(item, link_id)?.unwrap_or(0));
+            }
+            "integer" | "decimal" | "quantity" => {
+                input.min = extension(item, MIN_VALUE_EXTENSION).and_then(number_value);
+                input.max = extension(item, MAX_VALUE_EXTENSION).and_then(number_value);
+                input.unit = extension(item, UNIT_EXTENSION)
+                    .and_then(|unit| {
+                        let coding = &unit["valueCoding"];
+                        string(coding, "display").or_else(|| string(coding, "code"))
+                    })
+                    .map(|unit| self.localized(unit.to_string()));
+            }
+            "choice" | "coding" | "open-choice" => {
+                input.input_type = InputType::Dropdown;
+                if item.get("answerValueSet").is_some() {
+                    return Err(unsupported(
+                        "answers from a value set are not supported, list them as answerOption",
+                    ));
+                }
+                for option in array(item, "answerOption") {
+                    input.options.push(self.option(option, link_id)?);
+                }
+                if input.required {
+                    input.default = Some(None);
+                }
+            }
+            other => return Err(unsupported(&format!("unsupported item type '{}'", other))),
+        }
+
+        if let Some(section) = section {
+            self.sections[section].fields.push(input.field.clone());
+        }
+        self.inputs.push(input);
+        Ok(())
+    }
+
+    fn option(&self, option: &Value, link_id: &str) -> Result<DropdownOption, QuestionnaireError> {
+        let coding = &option["valueCoding"];
+        let (value, label) = if coding.is_object() {
+            let code = string(coding, "code").unwrap_or_default().to_string();
+            let label = text(coding, "display", &self.language)
+                .unwrap_or_else(|| self.localized(code.clone()));
+            (code, label)
+        } else if let Some(label) = text(option, "valueString", &self.language) {
+            (label.text(&self.language).to_string(), label)
+        } else if let Some(number) = option["valueInteger"].as_i64() {
+            (number.to_string(), self.localized(number.to_string()))
+        } else {
+            return Err(QuestionnaireError::UnsupportedItem {
+                link_id: link_id.to_string(),
+                reason: "answer options must be codings, strings or integers".to_string(),
+            });
+        };
+
+        let points = match weight(option, link_id)? {
+            Some(points) => Some(points),
+            None => weight(coding, link_id)?,
+        };
+        Ok(DropdownOption {
+            value,
+            label: bilingual(label, &self.language),
+            points: points.unwrap_or(0),
+            description: None,
+        })
+    }
+
+    fn localized(&self, text: String) -> LocalizedText {
+        LocalizedText::new().with(self.language.clone(), text)
+    }
+}
+
+/// Points from a weight extension of `element`, which must be whole
+fn weight(element: &Value, link_id: &str) -> Result<Option<i32>, QuestionnaireError> {
+    let Some(value) = WEIGHT_EXTENSIONS
+        .into_iter()
+        .find_map(|url| extension(element, url))
+        .and_then(number_value)
+    else {
+        return Ok(None);
+    };
+    if value.fract() != 0.0 || value.abs() > f64::from(i32::MAX) {
+        return Err(QuestionnaireError::UnsupportedItem {
+            link_id: link_id.to_string(),
+            reason: format!("weight {} is not a whole number", value),
+        });
+    }
+    Ok(Some(value as i32))
+}
+
+fn extension<'a>(element: &'a Value, url: &str) -> Option<&'a Value> {
+    array(element, "extension")
+        .iter()
+        .find(|extension| string(extension, "url") == Some(url))
+}
+
+/// `valueInteger` or `valueDecimal` of an extension
+fn number_value(extension: &Value) -> Option<f64> {
+    extension["valueInteger"]
+        .as_f64()
+        .or_else(|| extension["valueDecimal"].as_f64())
+}
+
+pub(super) fn string<'a>(element: &'a Value, key: &str) -> Option<&'a str> {
+    element.get(key)?.as_str()
+}
+
+pub(super) fn array<'a>(element: &'a Value, key: &str) -> &'a [Value] {
+    element
+        .get(key)
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+}
+
+/// Lowercase identifier with underscores ("PHQ-9" becomes "phq_9")
+pub(super) fn identifier(text: &str) -> String {
+    let mut id = String::new();
+    for c in text.chars() {
+        if c.is_ascii_alphanumeric() {
+            id.push(c.to_ascii_lowercase());
+        } else if !id.is_empty() && !id.ends_with('_') {
+            id.push('_');
+        }
+    }
+    id.trim_end_matches('_').to_string()
+}
+
+/// `name` as a field name not yet used by `inputs`; names that do not start with
+/// a letter get `prefix`, and taken names a number ("age" becomes "age_2")
+pub(super) fn unique_field_name(name: &str, prefix: &str, inputs: &[InputField]) -> String {
+    let base = identifier(name);
+    let base = if base.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        base
+    } else {
+        format!("{}_{}", prefix, base)
+    };
+    let taken = |name: &str| inputs.iter().any(|input| input.field == name);
+    (1..)
+        .map(|n| {
+            if n == 1 {
+                base.clone()
+            } else {
+                format!("{}_{}", base, n)
+            }
+        })
+        .find(|name| !taken(name))
+        .unwrap()
+}
+
+/// Text of `key` with its translations; `None` if blank
+///
+/// Reads a string (in `language`), an object by language, `<key>_<lang>` keys
+/// and FHIR translation extensions under `_<key>`.
+pub(super) fn text(element: &Value, key: &str, language: &str) -> Option<LocalizedText> {
+    let mut text = LocalizedText::new();
+    match element.get(key) {
+        Some(Value::String(value)) => text.insert(language, value.clone()),
+        Some(Value::Object(values)) => {
+            for (language, value) in values {
+                if let Some(value) = value.as_str() {
+                    text.insert(language.clone(), value.to_string());
+                }
+            }
+        }
+        _ => {}
+    }
+    let prefix = format!("{}_", key);
+    for (name, value) in element.as_object()? {
+        let Some(language) = name.strip_prefix(&prefix) else {
+            continue;
+        };
+        if let (2, Some(value)) = (language.len(), value.as_str()) {
+            text.insert(language, value.to_string());
+        }
+    }
+    let translations = element
+        .get(format!("_{}", key))
+        .map(|extensions| array(extensions, "extension"))
+        .unwrap_or_default();
+    for translation in translations {
+        if string(translation, "url") != Some(TRANSLATION_EXTENSION) {
+            continue;
+        }
+        let part = |name: &str| {
+            array(translation, "extension")
+                .iter()
+                .find(|part| string(part, "url") == Some(name))
+                .cloned()
+        };
+        let language = part("lang").and_then(|lang| lang["valueCode"].as_str().map(str::to_string));
+        let content =
+            part("content").and_then(|content| content["valueString"].as_str().map(str::to_string));
+        if let (Some(language), Some(content)) = (language, content) {
+            text.insert(language, content);
+        }
+    }
+    (!text.is_blank()).then_some(text)
+}
+
+/// `text` with English and German filled in from `language` where missing, as
+/// dropdown options need both
+pub(super) fn bilingual(mut text: LocalizedText, language: &str) -> LocalizedText {
+    let primary = text.text(language).to_string();
+    for language in [FALLBACK_LANGUAGE, "de"] {
+        if text.exact(language).is_none() {
+            text.insert(language, primary.clone());
+        }
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scores::calculate_score;
+    use crate::scores::CalculationRequest;
+
+    /// Two PHQ-2 items (LOINC-coded answers with ordinal values) in a group, a
+    /// weighted boolean and a calculated total
+    const PHQ: &str = r#"{
+        "resourceType": "Questionnaire",
+        "id": "phq-2-example",
+        "name": "PHQ2",
+        "title": "Patient Health Questionnaire-2",
+        "_title": {"extension": [{
+            "url": "http://hl7.org/fhir/StructureDefinition/translation",
+            "extension": [
+                {"url": "lang", "valueCode": "de"},
+                {"url": "content", "valueString": "Gesundheitsfragebogen für Patienten-2"}
+            ]
+        }]},
+        "url": "http://example.org/Questionnaire/phq-2",
+        "code": [{"system": "http://loinc.org", "code": "55757-9"}],
+        "item": [
+            {"linkId": "intro", "type": "display", "text": "Over the last two weeks..."},
+            {"linkId": "phq", "type": "group", "text": "PHQ-2", "item": [
+                {"linkId": "/44250-9", "type": "choice", "required": true,
+                 "text": "Little interest or pleasure in doing things",
+                 "answerOption": [
+                    {"valueCoding": {"code": "LA6568-5", "display": "Not at all",
+                     "extension": [{"url": "http://hl7.org/fhir/StructureDefinition/ordinalValue", "valueDecimal": 0}]}},
+                    {"valueCoding": {"code": "LA6569-3", "display": "Several days"},
+                     "extension": [{"url": "http://hl7.org/fhir/StructureDefinition/itemWeight", "valueDecimal": 1}]},
+                    {"valueCoding": {"code": "LA6570-1", "display": "More than half the days",
+                     "extension": [{"url": "http://hl7.org/fhir/StructureDefinition/ordinalValue", "valueDecimal": 2}]}}
+                 ]},
+                {"linkId": "/44255-8", "type": "choice",
+                 "text": "Feeling down, depressed, or hopeless",
+                 "answerOption": [
+                    {"valueString": "no", "extension": [{"url": "http://hl7.org/fhir/StructureDefinition/itemWeight", "valueInteger": 0}]},
+                    {"valueString": "yes", "extension": [{"url": "http://hl7.org/fhir/StructureDefinition/itemWeight", "valueInteger": 3}]}
+                 ]}
+            ]},
+            {"linkId": "history", "type": "boolean", "text": "Earlier episode",
+             "extension": [{"url": "http://hl7.org/fhir/StructureDefinition/itemWeight", "valueInteger": 1}]},
+            {"linkId": "total", "type": "integer", "readOnly": true, "text": "Total"}
+        ]
+    }"#;
+
+    #[test]
+    fn test_questionnaire_import() {
+        let (id, score) = score_from_questionnaire(PHQ).unwrap();
+        assert_eq!(id, "phq2");
+        assert_eq!(
+            score.name.text("de"),
+            "Gesundheitsfragebogen für Patienten-2"
+        );
+        assert_eq!(score.metadata.codes[0].code, "55757-9");
+        assert_eq!(
+            score.references[0].link().unwrap(),
+            "http://example.org/Questionnaire/phq-2"
+        );
+
+        let fields: Vec<&str> = score
+            .inputs
+            .iter()
+            .map(|input| input.field.as_str())
+            .collect();
+        assert_eq!(fields, ["item_44250_9", "item_44255_8", "history"]);
+        assert_eq!(score.sections[0].fields, ["item_44250_9", "item_44255_8"]);
+        let interest = &score.inputs[0];
+        assert_eq!(interest.input_type, InputType::Dropdown);
+        assert!(interest.required);
+        let points: Vec<i32> = interest.options.iter().map(|o| o.points).collect();
+        assert_eq!(points, [0, 1, 2]);
+        assert_eq!(interest.options[1].label.text("de"), "Several days");
+        assert_eq!(
+            score.interpretation[0].score,
+            ScoreRange::Range("0-6".to_string())
+        );
+
+        let inputs = CalculationRequest::new()
+            .choice("item_44250_9", "LA6570-1")
+            .choice("item_44255_8", "yes")
+            .flag("history");
+        let result = calculate_score(&score, inputs.inputs()).unwrap();
+        assert_eq!(result.total_score, 6);
+
+        let yaml = score.to_yaml().unwrap();
+        let reloaded: ScoreDefinition = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(reloaded.inputs.len(), 3);
+    }
+
+    #[test]
+    fn test_questionnaire_errors() {
+        assert!(matches!(
+            score_from_questionnaire(r#"{"resourceType": "Patient"}"#),
+            Err(QuestionnaireError::NotAQuestionnaire(found)) if found == "Patient"
+        ));
+        let fractional = PHQ.replace("\"valueDecimal\": 2", "\"valueDecimal\": 2.5");
+        assert!(matches!(
+            score_from_questionnaire(&fractional),
+            Err(QuestionnaireError::UnsupportedItem { link_id, .. }) if link_id == "/44250-9"
+        ));
+        assert!(matches!(
+            score_from_questionnaire(
+                r#"{"resourceType": "Questionnaire", "name": "empty", "item": []}"#
+            ),
+            Err(QuestionnaireError::NoScoredItems)
+        ));
+    }
+}
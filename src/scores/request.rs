@@ -0,0 +1,208 @@
+// request.rs
+// Typed builder for the inputs of a calculation, instead of assembling a
+// `HashMap<String, InputValue>` by hand
+
+use crate::config::{InputType, ScoreDefinition};
+use crate::scores::calculator::{
+    calculate_score, unknown_unit, CalculationError, CalculationResult, InputValue, Quantity,
+};
+use crate::scores::dates::CalendarDate;
+use std::collections::HashMap;
+
+/// Inputs of a calculation
+///
+/// ```
+/// # use klinscore::scores::CalculationRequest;
+/// let request = CalculationRequest::new()
+///     .number("age", 72.0)
+///     .flag("hypertension")
+///     .choice("sex", "male");
+/// assert_eq!(request.inputs().len(), 3);
+/// ```
+///
+/// Fields not given count as not entered, as in the form: unchecked for booleans.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CalculationRequest {
+    inputs: HashMap<String, InputValue>,
+}
+
+impl CalculationRequest {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set a number input (in the unit of the score definition)
+    pub fn number(self, field: impl Into<String>, value: f64) -> Self {
+        self.value(field, InputValue::Number(value))
+    }
+
+    /// Set a number input in `unit`: the unit of the score definition or the
+    /// field's conventional unit (e.g., creatinine in "mg/dL")
+    pub fn quantity(self, field: impl Into<String>, value: f64, unit: impl Into<String>) -> Self {
+        self.value(
+            field,
+            InputValue::Quantity(Quantity {
+                value,
+                unit: unit.into(),
+            }),
+        )
+    }
+
+    /// Check a boolean input
+    pub fn flag(self, field: impl Into<String>) -> Self {
+        self.boolean(field, true)
+    }
+
+    /// Set a boolean input
+    pub fn boolean(self, field: impl Into<String>, value: bool) -> Self {
+        self.value(field, InputValue::Boolean(value))
+    }
+
+    /// Select a dropdown option by its value
+    pub fn choice(self, field: impl Into<String>, option: impl Into<String>) -> Self {
+        self.value(field, InputValue::Dropdown(option.into()))
+    }
+
+    /// Set a date input (e.g., a date of birth)
+    pub fn date(self, field: impl Into<String>, date: CalendarDate) -> Self {
+        self.value(field, InputValue::Date(date))
+    }
+
+    /// Set any input, replacing an earlier value of the field
+    pub fn value(mut self, field: impl Into<String>, value: InputValue) -> Self {
+        self.inputs.insert(field.into(), value);
+        self
+    }
+
+    pub fn inputs(&self) -> &HashMap<String, InputValue> {
+        &self.inputs
+    }
+
+    pub fn into_inputs(self) -> HashMap<String, InputValue> {
+        self.inputs
+    }
+
+    /// Check that every input belongs to `score` and has the type of its field,
+    /// that numbers are finite, that units are the field's and that selected
+    /// options exist
+    ///
+    /// [`calculate_score`] ignores inputs the score does not have, so a misspelled
+    /// field would otherwise silently count as not entered.
+    pub fn validate(&self, score: &ScoreDefinition) -> Result<(), CalculationError> {
+        for (field_name, value) in &self.inputs {
+            let field = score
+                .inputs
+                .iter()
+                .find(|input| input.field == *field_name)
+                .ok_or_else(|| CalculationError::UnknownField {
+                    field: field_name.clone(),
+                })?;
+            let expected = match (field.input_type, value) {
+                (InputType::Number | InputType::Duration, InputValue::Number(number))
+                    if !number.is_finite() =>
+                {
+                    "expected a finite number"
+                }
+                (InputType::Number, InputValue::Quantity(quantity)) => {
+                    if field.unit_system_of(&quantity.unit).is_none() {
+                        return Err(unknown_unit(field, &quantity.unit));
+                    }
+                    if quantity.value.is_finite() {
+                        continue;
+                    }
+                    "expected a finite number"
+                }
+                (InputType::Boolean, InputValue::Boolean(_))
+                | (InputType::Number | InputType::Duration, InputValue::Number(_))
+                | (InputType::Date, InputValue::Date(_)) => continue,
+                (InputType::Dropdown, InputValue::Dropdown(option)) => {
+                    if field.options.iter().any(|o| o.value == *option) {
+                        continue;
+                    }
+                    return Err(CalculationError::UnknownDropdownOption {
+                        field: field_name.clone(),
+                        option: option.clone(),
+                    });
+                }
+                (InputType::Boolean, _) => "expected a flag",
+                (InputType::Number, _) => "expected a number",
+                (InputType::Duration, _) => "expected a number in the field's duration unit",
+                (InputType::Dropdown, _) => "expected an option",
+                (InputType::Date, _) => "expected a date",
+            };
+            return Err(CalculationError::InvalidInput {
+                field: field_name.clone(),
+                reason: expected.to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Validate the inputs and calculate `score`
+    pub fn calculate(
+        &self,
+        score: &ScoreDefinition,
+    ) -> Result<CalculationResult, CalculationError> {
+        self.validate(score)?;
+        calculate_score(score, &self.inputs)
+    }
+}
+
+impl From<CalculationRequest> for HashMap<String, InputValue> {
+    fn from(request: CalculationRequest) -> Self {
+        request.inputs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scores::load_bundled_scores;
+
+    #[test]
+    fn test_request_validation() {
+        let library = load_bundled_scores();
+        let score = library.get_score("egfr_ckd_epi_2021").unwrap();
+        let request = CalculationRequest::new()
+            .number("age", 60.0)
+            .choice("sex", "female")
+            .number("creatinine", 80.0);
+        assert!(request.calculate(score).is_ok());
+
+        assert!(matches!(
+            request.clone().flag("smoker").validate(score),
+            Err(CalculationError::UnknownField { field }) if field == "smoker"
+        ));
+        assert!(matches!(
+            request.clone().choice("sex", "unknown").validate(score),
+            Err(CalculationError::UnknownDropdownOption { .. })
+        ));
+        assert!(matches!(
+            request.clone().flag("age").validate(score),
+            Err(CalculationError::InvalidInput { .. })
+        ));
+        assert!(matches!(
+            request
+                .clone()
+                .quantity("creatinine", 0.9, "mmol/L")
+                .validate(score),
+            Err(CalculationError::InvalidInput { .. })
+        ));
+        assert!(matches!(
+            request
+                .clone()
+                .quantity("age", 60.0, "mg/dL")
+                .validate(score),
+            Err(CalculationError::InvalidInput { .. })
+        ));
+
+        // 0.905 mg/dL creatinine is 80 μmol/L
+        let conventional = request.clone().quantity("creatinine", 80.0 / 88.4, "mg/dL");
+        assert_eq!(
+            conventional.calculate(score).unwrap().total_score,
+            request.calculate(score).unwrap().total_score
+        );
+        let inputs: HashMap<String, InputValue> = request.into();
+        assert_eq!(inputs["sex"], InputValue::Dropdown("female".to_string()));
+    }
+}
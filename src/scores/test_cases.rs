@@ -0,0 +1,140 @@
+// test_cases.rs
+// Runs the validation cases shipped in score definitions (`test_cases`), so every
+// score carries executable checks against its published examples
+
+use crate::config::{InputType, ScoreDefinition, TestCase};
+use crate::scores::calculator::InputValue;
+use crate::scores::request::CalculationRequest;
+
+/// A test case that did not give its expected result
+#[derive(Debug, Clone, PartialEq)]
+pub struct TestCaseFailure {
+    /// Position of the case in `test_cases`, from 1
+    pub case: usize,
+    pub name: Option<String>,
+    pub reason: String,
+}
+
+impl std::fmt::Display for TestCaseFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.name {
+            Some(name) => write!(f, "test case {} ({}): {}", self.case, name, self.reason),
+            None => write!(f, "test case {}: {}", self.case, self.reason),
+        }
+    }
+}
+
+/// Run every test case of `score`, returning those that fail
+pub fn run_test_cases(score: &ScoreDefinition) -> Vec<TestCaseFailure> {
+    score
+        .test_cases
+        .iter()
+        .enumerate()
+        .filter_map(|(index, case)| {
+            run_test_case(score, case)
+                .err()
+                .map(|reason| TestCaseFailure {
+                    case: index + 1,
+                    name: case.name.clone(),
+                    reason,
+                })
+        })
+        .collect()
+}
+
+/// Calculate `case` and compare the result with its expectation
+pub fn run_test_case(score: &ScoreDefinition, case: &TestCase) -> Result<(), String> {
+    let mut request = CalculationRequest::new();
+    for (field, value) in &case.inputs {
+        request = request.value(field.clone(), value.clone());
+    }
+    for input in &score.inputs {
+        if input.input_type == InputType::Boolean && !case.inputs.contains_key(&input.field) {
+            request = request.value(input.field.clone(), InputValue::Boolean(false));
+        }
+    }
+    let result = request
+        .calculate(score)
+        .map_err(|e| format!("calculation failed: {}", e))?;
+
+    let expected = &case.expected;
+    if let Some(total) = expected.total {
+        if result.total_score != total {
+            return Err(format!(
+                "expected total {}, got {}",
+                total, result.total_score
+            ));
+        }
+    }
+    if let Some(level) = expected.risk_level {
+        if result.risk_level != level {
+            return Err(format!(
+                "expected risk level {:?}, got {:?}",
+                level, result.risk_level
+            ));
+        }
+    }
+    if let Some(risk) = &expected.risk {
+        if !result.risk.values().any(|text| text == risk) {
+            return Err(format!(
+                "expected risk '{}', got '{}'",
+                risk,
+                result.risk.text("en")
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{ExpectedResult, RiskLevel};
+    use crate::scores::load_bundled_scores;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_run_test_cases() {
+        let library = load_bundled_scores();
+        let mut score = library.get_score("cha2ds2_va").unwrap().clone();
+        score.test_cases = vec![
+            TestCase {
+                name: Some("passes".to_string()),
+                inputs: BTreeMap::from([
+                    ("age".to_string(), InputValue::Number(72.0)),
+                    ("hypertension".to_string(), InputValue::Boolean(true)),
+                ]),
+                expected: ExpectedResult {
+                    total: Some(2),
+                    risk_level: Some(RiskLevel::High),
+                    ..ExpectedResult::default()
+                },
+            },
+            TestCase {
+                name: Some("wrong total".to_string()),
+                inputs: BTreeMap::from([("age".to_string(), InputValue::Number(50.0))]),
+                expected: ExpectedResult {
+                    total: Some(1),
+                    ..ExpectedResult::default()
+                },
+            },
+            TestCase {
+                name: None,
+                inputs: BTreeMap::from([("smoker".to_string(), InputValue::Boolean(true))]),
+                expected: ExpectedResult {
+                    total: Some(0),
+                    ..ExpectedResult::default()
+                },
+            },
+        ];
+
+        let failures = run_test_cases(&score);
+        assert_eq!(failures.len(), 2);
+        assert_eq!(
+            failures[0].to_string(),
+            "test case 2 (wrong total): expected total 1, got 0"
+        );
+        assert_eq!(failures[1].case, 3);
+        assert!(failures[1].reason.contains("smoker"));
+    }
+}
@@ -0,0 +1,606 @@
+// third_party.rs
+// Converts score descriptions in the JSON formats of other calculator collections
+// (MDCalc- and OpenRiskCalc-style) into score definitions, as a starting point for
+// adding their scores to the library
+//
+// Accepted shape (alternative key names in parentheses):
+//
+//   {
+//     "slug" (id, short_name): "wells_pe",
+//     "title" (name, full_title): "Wells' Criteria for PE",
+//     "description" (purpose): "...",
+//     "specialty" (category): "Emergency Medicine",
+//     "references" (evidence): ["citation", {"citation" (text): "...", "url" (href): "..."}],
+//     "input_schema" (inputs, variables, fields): [
+//       {"name" (id, key): "hr", "type": "toggle", "label_en": "Heart rate > 100", "points": 1.5},
+//       {"name": "age", "type": "textbox", "label": "Age", "unit": "years",
+//        "ranges": [{"min": 65, "points": 1}]},
+//       {"name": "asa", "type": "radio", "label": "ASA class",
+//        "options": [{"label": "I", "value": 0}, {"label": "II", "value": 1}]}
+//     ],
+//     "interpretation" (results, risk_groups): [
+//       {"min" (from): 0, "max" (to): 1, "risk" (label, category): "Low",
+//        "recommendation" (description, text): "...", "risk_level": "low"}
+//     ]
+//   }
+//
+// Texts are strings, objects by language ({"en": ..., "de": ...}) or `<key>_<lang>`
+// keys (`label_en`, `label_de`). Number ranges include `min` and exclude `max`;
+// interpretation bands include both. Toggles and two-option radios answered No/Yes
+// become checkboxes, worth the points of Yes.
+//
+// What cannot be expressed in a score file is left out and reported as a warning:
+// formulas, computed inputs, conditional display, fractional points, unknown input
+// types, unit conversions. The converted score is a draft to be checked against the
+// original publication.
+
+use crate::config::{
+    DropdownOption, InputField, InputType, InterpretationRule, LocalizedText, PointCondition,
+    PointsValue, Reference, RiskLevel, ScoreDefinition, ScoreMetadata, ScoreRange, Specialty,
+    FALLBACK_LANGUAGE,
+};
+use crate::scores::calculator::{interpretation_coverage, score_bounds};
+use crate::scores::loader::{is_valid_id, validate_score, ScoreLoadError};
+use crate::scores::questionnaire::{array, bilingual, identifier, string, text, unique_field_name};
+use serde_json::Value;
+use std::path::Path;
+use thiserror::Error;
+
+/// Input types that carry no points and are left out without a warning
+const DISPLAY_TYPES: [&str; 7] = [
+    "info", "header", "heading", "display", "label", "note", "text",
+];
+
+/// Keys that make an input's visibility depend on other inputs
+const CONDITIONAL_KEYS: [&str; 5] = [
+    "show_if",
+    "visible_if",
+    "depends_on",
+    "enable_when",
+    "conditions",
+];
+
+#[derive(Debug, Error)]
+pub enum ThirdPartyError {
+    #[error("Invalid JSON: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("Expected a JSON object describing one calculator")]
+    NotAnObject,
+
+    #[error("Calculator has no slug, id or name to derive the score ID from")]
+    MissingId,
+
+    #[error("Calculator has no inputs that could be converted")]
+    NoScoredInputs,
+
+    #[error("Converted score is invalid: {0}")]
+    InvalidScore(#[from] ScoreLoadError),
+}
+
+/// A converted score with what was left out of it
+#[derive(Debug, Clone)]
+pub struct ImportedScore {
+    pub id: String,
+    pub score: ScoreDefinition,
+    /// Constructs that were not converted, to be added by hand
+    pub warnings: Vec<String>,
+}
+
+/// Convert a third-party calculator description (JSON) into a score definition
+pub fn score_from_calculator_json(source: &str) -> Result<ImportedScore, ThirdPartyError> {
+    let calculator: Value = serde_json::from_str(source)?;
+    if !calculator.is_object() {
+        return Err(ThirdPartyError::NotAnObject);
+    }
+    let id = ["slug", "id", "short_name", "name", "title"]
+        .into_iter()
+        .filter_map(|key| string(&calculator, key))
+        .map(identifier)
+        .find(|id| is_valid_id(id))
+        .ok_or(ThirdPartyError::MissingId)?;
+
+    let mut converter = Converter {
+        inputs: Vec::new(),
+        warnings: Vec::new(),
+    };
+    if let Some(key) = ["formula", "expression", "equation"]
+        .into_iter()
+        .find(|key| calculator.get(*key).is_some())
+    {
+        converter.warn(format!(
+            "the {} was not converted; write it as a `formula` in the score file",
+            key
+        ));
+    }
+    for input in first_array(
+        &calculator,
+        &["input_schema", "inputs", "variables", "fields"],
+    ) {
+        converter.input(input);
+    }
+    if converter.inputs.is_empty() {
+        return Err(ThirdPartyError::NoScoredInputs);
+    }
+
+    let name = ["title", "full_title", "name"]
+        .into_iter()
+        .find_map(|key| text(&calculator, key, FALLBACK_LANGUAGE))
+        .unwrap_or_else(|| LocalizedText::from(id.as_str()));
+    let specialty = first_string(&calculator, &["specialty", "category"])
+        .and_then(|name| {
+            Specialty::ALL.into_iter().find(|specialty| {
+                [specialty.english(), specialty.german()]
+                    .iter()
+                    .any(|candidate| candidate.eq_ignore_ascii_case(name.trim()))
+            })
+        })
+        .unwrap_or(Specialty::Other);
+    let references = first_array(&calculator, &["references", "evidence"])
+        .iter()
+        .filter_map(|reference| {
+            if let Some(citation) = reference.as_str() {
+                return Some(Reference {
+                    citation: Some(citation.to_string()),
+                    ..Reference::default()
+                });
+            }
+            let citation = first_string(reference, &["citation", "text", "title"]);
+            let url = first_string(reference, &["url", "href", "link"]);
+            (citation.is_some() || url.is_some()).then(|| Reference {
+                citation: citation.map(str::to_string),
+                url: url.map(str::to_string),
+                ..Reference::default()
+            })
+        })
+        .collect();
+
+    let mut score = ScoreDefinition {
+        id: Some(id.clone()),
+        name,
+        aliases: Default::default(),
+        specialty,
+        sort_order: None,
+        version: string(&calculator, "version").unwrap_or("1.0").to_string(),
+        guideline_source: first_string(&calculator, &["source", "publisher"])
+            .unwrap_or("Imported calculator")
+            .to_string(),
+        references,
+        validation_status: "draft".to_string(),
+        deprecated: false,
+        superseded_by: None,
+        description: ["description", "purpose", "when_to_use"]
+            .into_iter()
+            .find_map(|key| text(&calculator, key, FALLBACK_LANGUAGE))
+            .unwrap_or_default(),
+        intended_use: None,
+        regulatory_note: None,
+        regions_validated: Vec::new(),
+        inputs: converter.inputs,
+        sections: Vec::new(),
+        input_mode: None,
+        constraints: Vec::new(),
+        interpretation: Vec::new(),
+        formula: None,
+        metadata: ScoreMetadata {
+            notes: Some(format!("Imported from calculator description '{}'", id)),
+            ..ScoreMetadata::default()
+        },
+        test_cases: Vec::new(),
+        conditions: Default::default(),
+    };
+    let mut warnings = converter.warnings;
+
+    for band in first_array(&calculator, &["interpretation", "results", "risk_groups"]) {
+        match interpretation_rule(band) {
+            Ok(rule) => score.interpretation.push(rule),
+            Err(reason) => warnings.push(format!("interpretation band left out: {}", reason)),
+        }
+    }
+    if score.interpretation.is_empty() {
+        let (min, max) = score_bounds(&score).unwrap_or((0, 0));
+        score.interpretation.push(InterpretationRule {
+            score: ScoreRange::Range(format!("{}-{}", min, max)),
+            risk: LocalizedText::bilingual("Total score", "Gesamtpunktzahl"),
+            risk_lay: None,
+            risk_level: RiskLevel::None,
+            recommendation: LocalizedText::bilingual(
+                "No interpretation defined",
+                "Keine Interpretation hinterlegt",
+            ),
+            details: None,
+        });
+        warnings.push("no interpretation bands; one band over all totals was added".to_string());
+    }
+    warnings.extend(
+        interpretation_coverage(&score)
+            .into_iter()
+            .map(|issue| format!("interpretation: {}", issue)),
+    );
+
+    validate_score(&mut score, Path::new(&format!("{}.json", id)))?;
+    Ok(ImportedScore {
+        id,
+        score,
+        warnings,
+    })
+}
+
+/// Collects the inputs and warnings
+struct Converter {
+    inputs: Vec<InputField>,
+    warnings: Vec<String>,
+}
+
+impl Converter {
+    fn warn(&mut self, warning: String) {
+        self.warnings.push(warning);
+    }
+
+    /// Convert one input, or leave it out with a warning
+    fn input(&mut self, input: &Value) {
+        let name = first_string(input, &["name", "id", "key"]).unwrap_or_default();
+        let input_type = string(input, "type")
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        if DISPLAY_TYPES.contains(&input_type.as_str()) {
+            return;
+        }
+        let field = match self.field(input, name, &input_type) {
+            Ok(field) => field,
+            Err(reason) => {
+                self.warn(format!("input '{}' left out: {}", name, reason));
+                return;
+            }
+        };
+        if let Some(key) = CONDITIONAL_KEYS
+            .into_iter()
+            .find(|key| !input[*key].is_null())
+        {
+            self.warn(format!(
+                "input '{}': `{}` (conditional display) was not converted; the input is always shown",
+                name, key
+            ));
+        }
+        self.inputs.push(field);
+    }
+
+    fn field(&mut self, input: &Value, name: &str, input_type: &str) -> Result<InputField, String> {
+        if name.is_empty() {
+            return Err("it has no name".to_string());
+        }
+        if input.get("formula").is_some() || input_type == "calculated" {
+            return Err("computed inputs are not supported".to_string());
+        }
+
+        let label = ["label", "title", "text", "question"]
+            .into_iter()
+            .find_map(|key| text(input, key, FALLBACK_LANGUAGE))
+            .unwrap_or_else(|| LocalizedText::from(name));
+        let mut field = InputField {
+            field: unique_field_name(name, "input", &self.inputs),
+            input_type: InputType::Number,
+            label,
+            unit: None,
+            points: PointsValue::Fixed(0),
+            help: ["help", "tooltip", "explanation", "tips"]
+                .into_iter()
+                .find_map(|key| text(input, key, FALLBACK_LANGUAGE)),
+            min: number(input, &["min", "minimum"]),
+            max: number(input, &["max", "maximum"]),
+            options: Vec::new(),
+            default: None,
+            conventional_unit: None,
+            widget: None,
+            loinc: Vec::new(),
+            elapsed: None,
+            duration_unit: None,
+            required: input["optional"] != true && input["required"] != false,
+        };
+        let options = first_array(input, &["options", "choices", "answers"]);
+
+        match input_type {
+            "toggle" | "radio" | "dropdown" | "select" | "choice" | "option" | "yes_no"
+            | "boolean" | "bool" | "checkbox"
+                if !options.is_empty() =>
+            {
+                match yes_no_points(options) {
+                    Some(points) => {
+                        field.input_type = InputType::Boolean;
+                        field.points = PointsValue::Fixed(points?);
+                    }
+                    None => options_of(&mut field, options)?,
+                }
+            }
+            "toggle" | "yes_no" | "boolean" | "bool" | "checkbox" => {
+                field.input_type = InputType::Boolean;
+                let points = number(input, &["points", "score", "weight", "value_if_true"]);
+                field.points = PointsValue::Fixed(whole(points.unwrap_or(0.0))?);
+            }
+            "number" | "numeric" | "textbox" | "integer" | "float" | "decimal" | "slider" => {
+                field.unit = self.unit(input, name);
+                let ranges = first_array(input, &["ranges", "points", "scoring", "thresholds"]);
+                if !ranges.is_empty() {
+                    let conditions = ranges
+                        .iter()
+                        .map(point_condition)
+                        .collect::<Result<Vec<_>, _>>()?;
+                    field.points = PointsValue::Conditional(conditions);
+                }
+            }
+            "" => return Err("it has no type".to_string()),
+            other => return Err(format!("unsupported input type '{}'", other)),
+        }
+        Ok(field)
+    }
+
+    /// The input's unit; of several (a unit toggle), the first, with a warning
+    fn unit(&mut self, input: &Value, name: &str) -> Option<LocalizedText> {
+        let unit = input.get("unit").or_else(|| input.get("units"))?;
+        let first = match unit {
+            Value::Array(units) => {
+                if units.len() > 1 {
+                    self.warn(format!(
+                        "input '{}': only the first of its units was kept; declare the others \
+                         as `conventional_unit`",
+                        name
+                    ));
+                }
+                units.first()?
+            }
+            unit => unit,
+        };
+        let first = first
+            .as_str()
+            .or_else(|| first_string(first, &["name", "label", "unit"]))?;
+        Some(LocalizedText::from(first))
+    }
+}
+
+/// For options that are exactly No and Yes (in any order), the points of Yes, which
+/// must be whole; `None` if the options are anything else or No is worth points
+fn yes_no_points(options: &[Value]) -> Option<Result<i32, String>> {
+    if options.len() != 2 {
+        return None;
+    }
+    let answer = |option: &Value| {
+        let label = ["label", "text", "title", "name"]
+            .into_iter()
+            .find_map(|key| text(option, key, FALLBACK_LANGUAGE))?;
+        let label = label.text(FALLBACK_LANGUAGE).trim().to_ascii_lowercase();
+        let points = number(option, &["points", "score", "value", "weight"]).unwrap_or(0.0);
+        Some((label, points))
+    };
+    let mut answers = [answer(&options[0])?, answer(&options[1])?];
+    answers.sort_by(|a, b| a.0.cmp(&b.0));
+    match answers {
+        [(no, no_points), (yes, yes_points)] if no == "no" && yes == "yes" && no_points == 0.0 => {
+            Some(whole(yes_points))
+        }
+        _ => None,
+    }
+}
+
+/// Fill the dropdown options of `field`
+fn options_of(field: &mut InputField, options: &[Value]) -> Result<(), String> {
+    field.input_type = InputType::Dropdown;
+    for option in options {
+        let label = ["label", "text", "title", "name"]
+            .into_iter()
+            .find_map(|key| text(option, key, FALLBACK_LANGUAGE))
+            .ok_or("an option has no label")?;
+        let points = number(option, &["points", "score", "value", "weight"]).unwrap_or(0.0);
+        let value = first_string(option, &["code", "id", "key"])
+            .map(identifier)
+            .unwrap_or_else(|| identifier(label.text(FALLBACK_LANGUAGE)));
+        if value.is_empty() || field.options.iter().any(|o| o.value == value) {
+            return Err(format!(
+                "option '{}' has no distinct value",
+                label.text(FALLBACK_LANGUAGE)
+            ));
+        }
+        field.options.push(DropdownOption {
+            value,
+            label: bilingual(label, FALLBACK_LANGUAGE),
+            points: whole(points)?,
+            description: None,
+        });
+    }
+    if field.required {
+        field.default = Some(None);
+    }
+    Ok(())
+}
+
+/// Points of a number range: `{"min", "max", "points"}` or `{"condition", "points"}`
+fn point_condition(range: &Value) -> Result<PointCondition, String> {
+    let points = whole(number(range, &["points", "score", "value"]).unwrap_or(0.0))?;
+    let condition = match string(range, "condition") {
+        Some(condition) => condition.to_string(),
+        None => {
+            let bounds = [
+                number(range, &["min", "from"]).map(|min| format!(">= {}", min)),
+                number(range, &["max", "to"]).map(|max| format!("< {}", max)),
+            ];
+            let bounds: Vec<String> = bounds.into_iter().flatten().collect();
+            if bounds.is_empty() {
+                return Err("a range has neither min nor max".to_string());
+            }
+            bounds.join(" && ")
+        }
+    };
+    Ok(PointCondition {
+        condition,
+        points,
+        label: ["label", "text"]
+            .into_iter()
+            .find_map(|key| text(range, key, FALLBACK_LANGUAGE)),
+    })
+}
+
+/// Interpretation rule of a band, whose bounds must be whole
+fn interpretation_rule(band: &Value) -> Result<InterpretationRule, String> {
+    let min = number(band, &["min", "from", "score_min"])
+        .map(whole)
+        .transpose()?;
+    let max = number(band, &["max", "to", "score_max"])
+        .map(whole)
+        .transpose()?;
+    let score = match (min, max) {
+        (Some(min), Some(max)) if min == max => ScoreRange::Exact(min),
+        (Some(min), Some(max)) => ScoreRange::Range(format!("{}-{}", min, max)),
+        (Some(min), None) => ScoreRange::Range(format!("≥{}", min)),
+        (None, Some(max)) => ScoreRange::Range(format!("≤{}", max)),
+        (None, None) => return Err("it has neither min nor max".to_string()),
+    };
+    let risk = ["risk", "label", "category", "name"]
+        .into_iter()
+        .find_map(|key| text(band, key, FALLBACK_LANGUAGE))
+        .ok_or("it has no risk label")?;
+    let risk_level = match string(band, "risk_level")
+        .or_else(|| string(band, "severity"))
+        .map(|level| level.to_ascii_lowercase().replace(['_', '-', ' '], ""))
+        .as_deref()
+    {
+        Some("verylow") => RiskLevel::VeryLow,
+        Some("low") => RiskLevel::Low,
+        Some("moderate" | "intermediate" | "medium") => RiskLevel::Moderate,
+        Some("high") => RiskLevel::High,
+        Some("veryhigh") => RiskLevel::VeryHigh,
+        Some("critical") => RiskLevel::Critical,
+        _ => RiskLevel::None,
+    };
+    Ok(InterpretationRule {
+        score,
+        risk,
+        risk_lay: None,
+        risk_level,
+        recommendation: ["recommendation", "description", "text", "management"]
+            .into_iter()
+            .find_map(|key| text(band, key, FALLBACK_LANGUAGE))
+            .unwrap_or_default(),
+        details: None,
+    })
+}
+
+/// Points must be whole: KlinScore scores add integers
+fn whole(points: f64) -> Result<i32, String> {
+    if points.fract() != 0.0 || points.abs() > f64::from(i32::MAX) {
+        return Err(format!("{} points is not a whole number", points));
+    }
+    Ok(points as i32)
+}
+
+fn first_string<'a>(element: &'a Value, keys: &[&str]) -> Option<&'a str> {
+    keys.iter().find_map(|key| string(element, key))
+}
+
+fn first_array<'a>(element: &'a Value, keys: &[&str]) -> &'a [Value] {
+    keys.iter()
+        .map(|key| array(element, key))
+        .find(|values| !values.is_empty())
+        .unwrap_or_default()
+}
+
+/// A number, also when given as a numeric string
+fn number(element: &Value, keys: &[&str]) -> Option<f64> {
+    keys.iter().find_map(|key| match element.get(*key)? {
+        Value::Number(number) => number.as_f64(),
+        Value::String(text) => text.trim().parse().ok(),
+        _ => None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scores::{calculate_score, CalculationRequest};
+
+    /// MDCalc-style description with a toggle, a radio, an age input with ranges,
+    /// an info text, a computed input and a conditionally shown input
+    const CALCULATOR: &str = r#"{
+        "slug": "example-risk",
+        "title": "Example Risk Score",
+        "title_de": "Beispiel-Risikoscore",
+        "specialty": "Cardiology",
+        "references": ["Doe J. An example score. 2020", {"text": "Validation", "url": "https://example.org/v"}],
+        "input_schema": [
+            {"type": "info", "label": "Use in adults only"},
+            {"name": "chf", "type": "toggle", "label_en": "Heart failure", "label_de": "Herzinsuffizienz",
+             "options": [{"label": "No", "value": 0}, {"label": "Yes", "value": 1}]},
+            {"name": "stage", "type": "radio", "label": "Stage",
+             "options": [{"label": "Early", "value": 0}, {"label": "Late", "value": 2}]},
+            {"name": "age", "type": "textbox", "label": "Age", "unit": ["years"],
+             "ranges": [{"min": 65, "max": 75, "points": 1}, {"min": 75, "points": 2}]},
+            {"name": "bmi", "type": "calculated", "formula": "weight / height^2"},
+            {"name": "smoker", "type": "checkbox", "label": "Smoker", "points": 1,
+             "show_if": {"age": ">= 18"}}
+        ],
+        "interpretation": [
+            {"min": 0, "max": 1, "risk": "Low", "risk_level": "low", "recommendation": "Reassure"},
+            {"min": 2, "risk": "High", "risk_level": "High", "recommendation": "Refer"}
+        ]
+    }"#;
+
+    #[test]
+    fn test_calculator_import() {
+        let imported = score_from_calculator_json(CALCULATOR).unwrap();
+        let score = &imported.score;
+        assert_eq!(imported.id, "example_risk");
+        assert_eq!(score.name.text("de"), "Beispiel-Risikoscore");
+        assert_eq!(score.specialty, Specialty::Cardiology);
+        assert_eq!(score.references.len(), 2);
+
+        let fields: Vec<&str> = score
+            .inputs
+            .iter()
+            .map(|input| input.field.as_str())
+            .collect();
+        assert_eq!(fields, ["chf", "stage", "age", "smoker"]);
+        assert_eq!(score.inputs[0].input_type, InputType::Boolean);
+        assert_eq!(score.inputs[0].label.text("de"), "Herzinsuffizienz");
+        assert_eq!(score.inputs[1].options[1].value, "late");
+        assert_eq!(
+            score.interpretation[1].score,
+            ScoreRange::Range("≥2".to_string())
+        );
+
+        assert_eq!(imported.warnings.len(), 2);
+        assert!(imported.warnings[0].contains("'bmi'"));
+        assert!(imported.warnings[1].contains("show_if"));
+
+        let inputs = CalculationRequest::new()
+            .flag("chf")
+            .choice("stage", "late")
+            .number("age", 80.0)
+            .boolean("smoker", false);
+        let result = calculate_score(score, inputs.inputs()).unwrap();
+        assert_eq!(result.total_score, 5);
+        assert_eq!(result.risk_level, RiskLevel::High);
+    }
+
+    #[test]
+    fn test_calculator_import_errors() {
+        assert!(matches!(
+            score_from_calculator_json("[]"),
+            Err(ThirdPartyError::NotAnObject)
+        ));
+        assert!(matches!(
+            score_from_calculator_json(r#"{"slug": "empty", "inputs": [{"type": "info"}]}"#),
+            Err(ThirdPartyError::NoScoredInputs)
+        ));
+
+        // Fractional points leave the input out; bands are then added
+        let fractional = r#"{"slug": "wells", "inputs": [
+            {"name": "hr", "type": "toggle", "points": 1.5},
+            {"name": "cancer", "type": "toggle", "points": 1}
+        ]}"#;
+        let imported = score_from_calculator_json(fractional).unwrap();
+        assert_eq!(imported.score.inputs.len(), 1);
+        assert!(imported.warnings[0].contains("1.5 points"));
+        assert_eq!(
+            imported.score.interpretation[0].score,
+            ScoreRange::Range("0-1".to_string())
+        );
+    }
+}
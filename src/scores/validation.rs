@@ -0,0 +1,306 @@
+// validation.rs
+// Score definition schema and author-facing validation diagnostics
+
+use crate::config::ScoreDefinition;
+use crate::scores::bases::BaseDefinitions;
+use crate::scores::loader::{
+    find_score_files, parse_json_score_source, parse_score_source, parse_toml_score_source,
+    validate_score, ScoreFormat, ScoreLoadError,
+};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A validation problem in a score definition file
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    /// 1-based line number, if the problem could be located
+    pub line: Option<usize>,
+    /// 1-based column number, if the problem could be located
+    pub column: Option<usize>,
+    /// Description of the problem (including the YAML path, e.g. "inputs[2].points")
+    pub message: String,
+}
+
+impl Diagnostic {
+    /// Render the diagnostic with the offending source line and a caret marker
+    pub fn render(&self, file: &str, source: &str) -> String {
+        let Some(line) = self.line else {
+            return format!("{}: error: {}", file, self.message);
+        };
+        let column = self.column.unwrap_or(1);
+
+        let mut out = format!("{}:{}:{}: error: {}", file, line, column, self.message);
+        if let Some(source_line) = source.lines().nth(line - 1) {
+            let gutter = line.to_string().len();
+            out.push_str(&format!(
+                "\n{:>w$} |\n{} | {}\n{:>w$} | {}^",
+                "",
+                line,
+                source_line,
+                "",
+                " ".repeat(column.saturating_sub(1)),
+                w = gutter
+            ));
+        }
+        out
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.line, self.column) {
+            (Some(line), Some(column)) => write!(f, "{}:{}: {}", line, column, self.message),
+            (Some(line), None) => write!(f, "{}: {}", line, self.message),
+            _ => write!(f, "{}", self.message),
+        }
+    }
+}
+
+/// JSON Schema for score definition files, generated from `ScoreDefinition`.
+///
+/// Suitable for editor integration (e.g., yaml-language-server's `# yaml-language-server: $schema=`).
+pub fn score_json_schema() -> serde_json::Value {
+    let schema = schemars::schema_for!(ScoreDefinition);
+    serde_json::to_value(schema).expect("generated schema is always valid JSON")
+}
+
+/// Validate score definition YAML source.
+///
+/// Structural problems (missing keys, wrong types, unknown enum values) are reported
+/// with the exact line and column from the YAML parser. Semantic problems (duplicate
+/// fields, unknown section fields, invalid constraints, ...) are located on a
+/// best-effort basis by searching for the identifier the error refers to.
+pub fn validate_score_source(source: &str) -> Result<ScoreDefinition, Diagnostic> {
+    validate_score_source_as(source, ScoreFormat::Yaml)
+}
+
+/// Validate score definition source in the given format (see [`validate_score_source`])
+pub fn validate_score_source_as(
+    source: &str,
+    format: ScoreFormat,
+) -> Result<ScoreDefinition, Diagnostic> {
+    validate_score_source_with(source, format, BaseDefinitions::bundled())
+}
+
+/// Validate score definition source, resolving `extends` against `bases`
+pub fn validate_score_source_with(
+    source: &str,
+    format: ScoreFormat,
+    bases: &BaseDefinitions,
+) -> Result<ScoreDefinition, Diagnostic> {
+    let parsed = match format {
+        ScoreFormat::Yaml => parse_score_source(source, bases).map_err(|e| {
+            let location = e.location();
+            Diagnostic {
+                line: location.as_ref().map(|l| l.line()),
+                column: location.as_ref().map(|l| l.column()),
+                message: strip_location_suffix(e.to_string()),
+            }
+        }),
+        ScoreFormat::Json => parse_json_score_source(source, bases).map_err(|e| {
+            // serde_json reports line 0 when the error has no position
+            let located = e.line() > 0;
+            Diagnostic {
+                line: located.then(|| e.line()),
+                column: located.then(|| e.column()),
+                message: strip_location_suffix(e.to_string()),
+            }
+        }),
+        ScoreFormat::Toml => parse_toml_score_source(source, bases).map_err(|e| {
+            let (line, column) = e.span().map(|span| line_column(source, span.start)).unzip();
+            Diagnostic {
+                line,
+                column,
+                message: e.message().trim_end().to_string(),
+            }
+        }),
+    };
+    let mut score = parsed?;
+
+    validate_score(&mut score, Path::new("")).map_err(|e| {
+        let reason = match e {
+            ScoreLoadError::InvalidScore { reason, .. } => reason,
+            other => other.to_string(),
+        };
+        let (line, column) = locate_identifier(source, &reason).unzip();
+        Diagnostic {
+            line,
+            column,
+            message: reason,
+        }
+    })?;
+
+    Ok(score)
+}
+
+/// Validate a single score definition file (format taken from the extension, bases
+/// from the enclosing scores directory)
+pub fn validate_score_file<P: AsRef<Path>>(path: P) -> Result<ScoreDefinition, Diagnostic> {
+    let path = path.as_ref();
+    let located = |message: String| Diagnostic {
+        line: None,
+        column: None,
+        message,
+    };
+    let source = fs::read_to_string(path).map_err(|e| located(e.to_string()))?;
+    let bases = BaseDefinitions::for_path(path).map_err(|e| located(e.to_string()))?;
+    validate_score_source_with(
+        &source,
+        ScoreFormat::from_path(path).unwrap_or(ScoreFormat::Yaml),
+        &bases,
+    )
+}
+
+/// The location is reported separately; drop the parser's " at line X column Y" suffix
+fn strip_location_suffix(mut message: String) -> String {
+    if let Some(index) = message.rfind(" at line ") {
+        message.truncate(index);
+    }
+    message
+}
+
+/// 1-based line and column of a byte offset in `source`
+fn line_column(source: &str, offset: usize) -> (usize, usize) {
+    let before = &source[..offset.min(source.len())];
+    let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+    (
+        before.matches('\n').count() + 1,
+        before[line_start..].chars().count() + 1,
+    )
+}
+
+/// Collect score files to validate: a file as-is, or all score files below a
+/// directory (templates are skipped, as in `load_all_scores`)
+pub fn score_files_in<P: AsRef<Path>>(path: P) -> Result<Vec<PathBuf>, ScoreLoadError> {
+    let path = path.as_ref();
+    if path.is_file() {
+        return Ok(vec![path.to_path_buf()]);
+    }
+
+    let mut files: Vec<PathBuf> = find_score_files(path)?
+        .into_iter()
+        .filter(|file| !file.to_string_lossy().contains("template"))
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+/// Find the line/column of the last quoted identifier mentioned in a validation message
+/// (e.g., "Section 'a' references unknown field 'b'" → first occurrence of `b`)
+fn locate_identifier(source: &str, message: &str) -> Option<(usize, usize)> {
+    let identifier = message.rsplit('\'').nth(1).filter(|id| !id.is_empty())?;
+
+    source.lines().enumerate().find_map(|(index, line)| {
+        let content = line.split('#').next().unwrap_or("");
+        [
+            format!("\"{}\"", identifier),
+            format!("'{}'", identifier),
+            format!(": {}", identifier),
+        ]
+        .iter()
+        .find_map(|needle| content.find(needle.as_str()))
+        .map(|column| (index + 1, column + 1))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VALID_SCORE: &str = r#"name: {en: "Test Score", de: "Test-Score"}
+specialty: Cardiology
+version: "1.0"
+guideline_source: "Test"
+references: [{citation: "Test"}]
+validation_status: "draft"
+inputs:
+  - field: "age"
+    type: "number"
+    label: {en: "Age", de: "Alter"}
+    points: 0
+interpretation:
+  - score: 0
+    risk: {en: "Low", de: "Niedrig"}
+    risk_level: Low
+    recommendation: {en: "Test", de: "Test"}
+"#;
+
+    #[test]
+    fn test_valid_source() {
+        assert!(validate_score_source(VALID_SCORE).is_ok());
+    }
+
+    #[test]
+    fn test_structural_error_has_location() {
+        let source = VALID_SCORE.replace("risk_level: Low", "risk_level: Lowish");
+        let diagnostic = validate_score_source(&source).unwrap_err();
+
+        assert_eq!(diagnostic.line, Some(15));
+        assert!(diagnostic.column.is_some());
+        assert!(diagnostic.message.contains("interpretation[0].risk_level"));
+        assert!(!diagnostic.message.contains(" at line "));
+
+        let rendered = diagnostic.render("test.yaml", &source);
+        assert!(rendered.starts_with("test.yaml:15:"));
+        assert!(rendered.contains("risk_level: Lowish"));
+    }
+
+    #[test]
+    fn test_semantic_error_is_located() {
+        let source = VALID_SCORE.replace(
+            "interpretation:",
+            "sections:\n  - id: \"labs\"\n    label: {en: \"Labs\", de: \"Labor\"}\n    fields: [\"creatinine\"]\ninterpretation:",
+        );
+        let diagnostic = validate_score_source(&source).unwrap_err();
+
+        assert!(diagnostic.message.contains("unknown field 'creatinine'"));
+        assert_eq!(diagnostic.line, Some(15));
+    }
+
+    #[test]
+    fn test_legacy_translation_layout() {
+        let legacy = VALID_SCORE
+            .replace(
+                "name: {en: \"Test Score\", de: \"Test-Score\"}",
+                "name: \"Test Score\"\nname_de: \"Test-Score\"\nspecialty_de: \"Kardiologie\"",
+            )
+            .replace(
+                "label: {en: \"Age\", de: \"Alter\"}",
+                "label: \"Age\"\n    label_de: \"Alter\"",
+            );
+        let score = validate_score_source(&legacy).unwrap();
+        assert_eq!(score.name.text("de"), "Test-Score");
+        assert_eq!(score.inputs[0].label.text("de"), "Alter");
+
+        // Upgraded documents are re-parsed from a value tree without positions
+        let diagnostic =
+            validate_score_source(&legacy.replace("risk_level: Low", "risk_level: Lowish"))
+                .unwrap_err();
+        assert!(diagnostic.line.is_none());
+        assert!(diagnostic.message.contains("Lowish"));
+    }
+
+    #[test]
+    fn test_json_and_toml_errors_are_located() {
+        let json =
+            "{\n  \"name\": \"Test\",\n  \"specialty\": \"Cardiology\",\n  \"version\": 1\n}";
+        let diagnostic = validate_score_source_as(json, ScoreFormat::Json).unwrap_err();
+        assert_eq!(diagnostic.line, Some(4));
+        assert!(!diagnostic.message.contains(" at line "));
+
+        let toml = "name = \"Test\"\nspecialty = \"Cardiology\"\nversion = 1\n";
+        let diagnostic = validate_score_source_as(toml, ScoreFormat::Toml).unwrap_err();
+        assert_eq!(diagnostic.line, Some(3));
+        assert_eq!(diagnostic.column, Some(11));
+    }
+
+    #[test]
+    fn test_schema_describes_score_definition() {
+        let schema = score_json_schema();
+        let required = schema["required"].as_array().unwrap();
+        assert!(required.iter().any(|r| r == "name"));
+        assert!(required.iter().any(|r| r == "interpretation"));
+        assert!(schema["properties"]["inputs"].is_object());
+    }
+}
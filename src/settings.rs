@@ -1,8 +1,13 @@
 // settings.rs
 // User preferences and application settings
 
+use crate::config::{RiskLevel, RiskPalette, UnitSystem};
+use crate::export::deidentify::{ExportAnonymization, TimestampPrecision};
+use crate::paths;
 use serde::{Deserialize, Serialize};
 use std::fmt;
+use std::path::PathBuf;
+use std::sync::LazyLock;
 
 /// Application theme options
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -11,9 +16,46 @@ pub enum AppTheme {
     Light,
     Dark,
     Sepia,
+    /// White text on black with yellow accents
+    HighContrast,
+    /// User-defined colors, see [`CustomTheme`]
+    Custom,
 }
 
-pub const ALL_THEMES: [AppTheme; 3] = [AppTheme::Light, AppTheme::Dark, AppTheme::Sepia];
+pub const ALL_THEMES: [AppTheme; 5] = [
+    AppTheme::Light,
+    AppTheme::Dark,
+    AppTheme::Sepia,
+    AppTheme::HighContrast,
+    AppTheme::Custom,
+];
+
+/// Sepia theme, built once since iced derives the full set of widget colors on creation
+static SEPIA_THEME: LazyLock<iced::Theme> = LazyLock::new(|| {
+    iced::Theme::custom(
+        "Sepia".to_string(),
+        iced::theme::Palette {
+            background: AppTheme::Sepia.background_color(),
+            text: AppTheme::Sepia.text_color(),
+            primary: iced::Color::from_rgb(0.55, 0.35, 0.18), // Sienna
+            success: iced::Color::from_rgb(0.33, 0.45, 0.18), // Olive
+            danger: iced::Color::from_rgb(0.64, 0.2, 0.13),   // Brick red
+        },
+    )
+});
+
+static HIGH_CONTRAST_THEME: LazyLock<iced::Theme> = LazyLock::new(|| {
+    iced::Theme::custom(
+        "High contrast".to_string(),
+        iced::theme::Palette {
+            background: AppTheme::HighContrast.background_color(),
+            text: AppTheme::HighContrast.text_color(),
+            primary: iced::Color::from_rgb(1.0, 1.0, 0.0),
+            success: iced::Color::from_rgb(0.4, 1.0, 0.4),
+            danger: iced::Color::from_rgb(1.0, 0.45, 0.45),
+        },
+    )
+});
 
 impl AppTheme {
     pub fn all() -> &'static [AppTheme] {
@@ -25,59 +67,285 @@ impl AppTheme {
             AppTheme::Light => "Light",
             AppTheme::Dark => "Dark",
             AppTheme::Sepia => "Sepia",
-        }
-    }
-
-    pub fn german(self) -> &'static str {
-        match self {
-            AppTheme::Light => "Hell",
-            AppTheme::Dark => "Dunkel",
-            AppTheme::Sepia => "Sepia",
+            AppTheme::HighContrast => "High contrast",
+            AppTheme::Custom => "Custom",
         }
     }
 
     /// Get the Iced theme for this app theme
+    ///
+    /// `Custom` needs the user's colors; use [`Settings::iced_theme`] for it.
     pub fn iced_theme(self) -> iced::Theme {
         match self {
             AppTheme::Light => iced::Theme::Light,
             AppTheme::Dark => iced::Theme::Dark,
-            AppTheme::Sepia => iced::Theme::TokyoNight, // Closest to sepia
+            AppTheme::Sepia => SEPIA_THEME.clone(),
+            AppTheme::HighContrast => HIGH_CONTRAST_THEME.clone(),
+            AppTheme::Custom => CustomTheme::default().iced_theme(),
+        }
+    }
+
+    /// Risk colors readable on this theme's background
+    pub fn risk_palette(self) -> RiskPalette {
+        match self {
+            AppTheme::Light => RiskPalette::Light,
+            AppTheme::Dark => RiskPalette::Dark,
+            AppTheme::Sepia => RiskPalette::Sepia,
+            AppTheme::HighContrast => RiskPalette::Dark,
+            AppTheme::Custom => CustomTheme::default().risk_palette,
         }
     }
 
     /// Background color for the theme
-    #[allow(dead_code)]
     pub fn background_color(&self) -> iced::Color {
         match self {
             AppTheme::Light => iced::Color::from_rgb(1.0, 1.0, 1.0),
             AppTheme::Dark => iced::Color::from_rgb(0.12, 0.12, 0.12),
             AppTheme::Sepia => iced::Color::from_rgb(0.96, 0.92, 0.85), // Warm sepia
+            AppTheme::HighContrast => iced::Color::BLACK,
+            AppTheme::Custom => CustomTheme::default().background_color(),
         }
     }
 
     /// Text color for the theme
-    #[allow(dead_code)]
     pub fn text_color(&self) -> iced::Color {
         match self {
             AppTheme::Light => iced::Color::from_rgb(0.0, 0.0, 0.0),
             AppTheme::Dark => iced::Color::from_rgb(0.9, 0.9, 0.9),
             AppTheme::Sepia => iced::Color::from_rgb(0.2, 0.15, 0.1), // Dark brown for sepia
+            AppTheme::HighContrast => iced::Color::WHITE,
+            AppTheme::Custom => CustomTheme::default().text_color(),
+        }
+    }
+}
+
+/// User-defined theme colors, as hex codes ("#RRGGBB") typed in Settings
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CustomTheme {
+    pub background: String,
+    pub primary: String,
+    /// Risk colors; pick the palette made for a similar background
+    pub risk_palette: RiskPalette,
+}
+
+impl Default for CustomTheme {
+    fn default() -> Self {
+        THEME_PRESETS[0].theme()
+    }
+}
+
+/// A starting point for a custom theme
+#[derive(Debug, Clone, Copy)]
+pub struct ThemePreset {
+    /// Id of the translated name in the UI catalogs
+    pub message_id: &'static str,
+    background: &'static str,
+    primary: &'static str,
+    risk_palette: RiskPalette,
+}
+
+impl ThemePreset {
+    pub fn theme(&self) -> CustomTheme {
+        CustomTheme {
+            background: self.background.to_string(),
+            primary: self.primary.to_string(),
+            risk_palette: self.risk_palette,
         }
     }
 }
 
+pub const THEME_PRESETS: [ThemePreset; 2] = [
+    ThemePreset {
+        message_id: "theme-preset-hospital-blue",
+        background: "#F4F7FB",
+        primary: "#005EB8",
+        risk_palette: RiskPalette::Light,
+    },
+    ThemePreset {
+        message_id: "theme-preset-high-contrast",
+        background: "#000000",
+        primary: "#FFFF00",
+        risk_palette: RiskPalette::Dark,
+    },
+];
+
+impl CustomTheme {
+    /// Background color; white while the typed hex code is invalid
+    pub fn background_color(&self) -> iced::Color {
+        parse_hex_color(&self.background).unwrap_or(iced::Color::WHITE)
+    }
+
+    /// Black or white text, whichever reads better on the background
+    pub fn text_color(&self) -> iced::Color {
+        let background = self.background_color();
+        let brightness = 0.299 * background.r + 0.587 * background.g + 0.114 * background.b;
+        if brightness > 0.5 {
+            iced::Color::from_rgb(0.0, 0.0, 0.0)
+        } else {
+            iced::Color::from_rgb(1.0, 1.0, 1.0)
+        }
+    }
+
+    pub fn iced_theme(&self) -> iced::Theme {
+        let risk_color = |level: RiskLevel| {
+            let (r, g, b) = level.rgb_in(self.risk_palette);
+            iced::Color::from_rgb(r, g, b)
+        };
+        iced::Theme::custom(
+            "Custom".to_string(),
+            iced::theme::Palette {
+                background: self.background_color(),
+                text: self.text_color(),
+                primary: parse_hex_color(&self.primary)
+                    .unwrap_or(iced::Color::from_rgb(0.0, 0.37, 0.72)),
+                success: risk_color(RiskLevel::VeryLow),
+                danger: risk_color(RiskLevel::VeryHigh),
+            },
+        )
+    }
+}
+
+/// Parse a "#RRGGBB" (or "RRGGBB") hex color code
+pub fn parse_hex_color(code: &str) -> Option<iced::Color> {
+    let hex = code.trim();
+    let hex = hex.strip_prefix('#').unwrap_or(hex);
+    if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    Some(iced::Color::from_rgb8(
+        channel(0)?,
+        channel(2)?,
+        channel(4)?,
+    ))
+}
+
 impl fmt::Display for AppTheme {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.label())
     }
 }
 
+/// Automatic pruning of the calculation history, to keep the history file
+/// small and satisfy data-minimization policies
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HistoryRetention {
+    #[default]
+    KeepAll,
+    /// Keep the newest N entries
+    LastEntries(u32),
+    /// Keep the entries of today and the N-1 days before
+    LastDays(u32),
+}
+
+impl HistoryRetention {
+    /// Limits offered when switching to a mode
+    pub const DEFAULT_ENTRIES: u32 = 500;
+    pub const DEFAULT_DAYS: u32 = 90;
+
+    /// The three modes for a picker, keeping the current limit of the selected one
+    pub fn choices(self) -> [HistoryRetention; 3] {
+        [
+            HistoryRetention::KeepAll,
+            match self {
+                HistoryRetention::LastEntries(n) => HistoryRetention::LastEntries(n),
+                _ => HistoryRetention::LastEntries(Self::DEFAULT_ENTRIES),
+            },
+            match self {
+                HistoryRetention::LastDays(n) => HistoryRetention::LastDays(n),
+                _ => HistoryRetention::LastDays(Self::DEFAULT_DAYS),
+            },
+        ]
+    }
+
+    /// Number of entries or days kept; `None` when keeping everything
+    pub fn limit(self) -> Option<u32> {
+        match self {
+            HistoryRetention::KeepAll => None,
+            HistoryRetention::LastEntries(n) | HistoryRetention::LastDays(n) => Some(n),
+        }
+    }
+
+    /// Same mode with another limit
+    pub fn with_limit(self, limit: u32) -> Self {
+        match self {
+            HistoryRetention::KeepAll => HistoryRetention::KeepAll,
+            HistoryRetention::LastEntries(_) => HistoryRetention::LastEntries(limit),
+            HistoryRetention::LastDays(_) => HistoryRetention::LastDays(limit),
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            HistoryRetention::KeepAll => "Keep all",
+            HistoryRetention::LastEntries(_) => "Last N entries",
+            HistoryRetention::LastDays(_) => "Last N days",
+        }
+    }
+
+    /// Id of the translated mode name in the UI catalogs
+    pub fn message_id(self) -> &'static str {
+        match self {
+            HistoryRetention::KeepAll => "retention-keep-all",
+            HistoryRetention::LastEntries(_) => "retention-last-entries",
+            HistoryRetention::LastDays(_) => "retention-last-days",
+        }
+    }
+}
+
+impl fmt::Display for HistoryRetention {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.label())
+    }
+}
+
 /// Application settings
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Settings {
     pub theme: AppTheme,
     pub show_help_hints: bool,
     pub auto_calculate: bool,
+    /// Timestamp precision kept in de-identified research exports
+    pub research_timestamp_precision: TimestampPrecision,
+    /// Additional score directories (e.g., hospital-specific scores), merged in order
+    pub extra_score_dirs: Vec<PathBuf>,
+    /// Refuse to start with an incomplete library if any score file is invalid
+    pub strict_score_loading: bool,
+    /// Colors used with `AppTheme::Custom`
+    pub custom_theme: CustomTheme,
+    /// Open long scores (see `InputMode::WIZARD_MIN_INPUTS`) as a step-by-step wizard
+    pub wizard_for_long_scores: bool,
+    /// Larger hit targets and bold focus outlines, e.g. for touch screens on ward carts
+    pub touch_mode: bool,
+    /// Hide the optional patient label, e.g. in deployments that must not store identifiers
+    pub disable_patient_labels: bool,
+    /// Pruning of old history entries, applied on startup and after each save
+    pub history_retention: HistoryRetention,
+    /// Folder the save dialog for exports opens in; `None` uses the documents folder
+    pub export_dir: Option<PathBuf>,
+    /// Units lab values are entered in where a score offers both
+    pub unit_system: UnitSystem,
+    /// Institution lines printed at the top of every PDF report page
+    pub report_header: String,
+    /// Lines printed at the bottom of every PDF report page
+    pub report_footer: String,
+    /// PDF reports in German and English
+    pub bilingual_reports: bool,
+    /// QR code with the result or its checksum on PDF reports
+    pub report_qr_code: bool,
+    /// Identifying fields removed from exports
+    pub export_anonymization: ExportAnonymization,
+    /// Append a SHA-256 seal to PDF and JSON exports
+    pub seal_exports: bool,
+    /// Sign the seal with the local key
+    pub sign_exports: bool,
+    /// Base URL of the FHIR server inputs are prefilled from; empty disables prefilling
+    pub fhir_endpoint: String,
+    /// Append diagnostics to `klinscore.log` in the data folder
+    pub log_file: bool,
+    /// ed25519 public keys (hex) of score pack publishers whose signatures are trusted
+    pub trusted_pack_keys: Vec<String>,
 }
 
 impl Default for Settings {
@@ -86,6 +354,26 @@ impl Default for Settings {
             theme: AppTheme::Light,
             show_help_hints: true,
             auto_calculate: false,
+            research_timestamp_precision: TimestampPrecision::Date,
+            extra_score_dirs: Vec::new(),
+            strict_score_loading: false,
+            custom_theme: CustomTheme::default(),
+            wizard_for_long_scores: false,
+            touch_mode: false,
+            disable_patient_labels: false,
+            history_retention: HistoryRetention::KeepAll,
+            export_dir: None,
+            unit_system: UnitSystem::Si,
+            report_header: String::new(),
+            report_footer: String::new(),
+            bilingual_reports: false,
+            report_qr_code: false,
+            export_anonymization: ExportAnonymization::default(),
+            seal_exports: false,
+            sign_exports: false,
+            fhir_endpoint: String::new(),
+            log_file: false,
+            trusted_pack_keys: Vec::new(),
         }
     }
 }
@@ -94,4 +382,114 @@ impl Settings {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Iced theme of the selected theme, including the user's custom colors
+    pub fn iced_theme(&self) -> iced::Theme {
+        match self.theme {
+            AppTheme::Custom => self.custom_theme.iced_theme(),
+            theme => theme.iced_theme(),
+        }
+    }
+
+    /// Folder the save dialog for exports opens in
+    pub fn export_dir(&self) -> Option<PathBuf> {
+        self.export_dir.clone().or_else(paths::documents_dir)
+    }
+
+    /// Risk colors of the selected theme
+    pub fn risk_palette(&self) -> RiskPalette {
+        match self.theme {
+            AppTheme::Custom => self.custom_theme.risk_palette,
+            theme => theme.risk_palette(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::RiskLevel;
+
+    /// WCAG relative luminance of an sRGB color
+    fn luminance((r, g, b): (f32, f32, f32)) -> f32 {
+        let linear = |c: f32| {
+            if c <= 0.03928 {
+                c / 12.92
+            } else {
+                ((c + 0.055) / 1.055).powf(2.4)
+            }
+        };
+        0.2126 * linear(r) + 0.7152 * linear(g) + 0.0722 * linear(b)
+    }
+
+    #[test]
+    fn test_parse_hex_color() {
+        assert_eq!(
+            parse_hex_color("#005EB8"),
+            Some(iced::Color::from_rgb8(0x00, 0x5E, 0xB8))
+        );
+        assert_eq!(
+            parse_hex_color(" ffff00 "),
+            Some(iced::Color::from_rgb8(0xFF, 0xFF, 0x00))
+        );
+        assert_eq!(parse_hex_color("#05B"), None);
+        assert_eq!(parse_hex_color("#GG0000"), None);
+
+        // Typing an invalid code keeps the theme usable
+        let custom = CustomTheme {
+            background: "#12".to_string(),
+            ..CustomTheme::default()
+        };
+        assert_eq!(custom.background_color(), iced::Color::WHITE);
+        assert_eq!(custom.text_color(), iced::Color::from_rgb(0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_sepia_theme_palette() {
+        let palette = AppTheme::Sepia.iced_theme().palette();
+        assert_eq!(palette.background, AppTheme::Sepia.background_color());
+        assert_eq!(palette.text, AppTheme::Sepia.text_color());
+    }
+
+    #[test]
+    fn test_risk_colors_contrast() {
+        let levels = [
+            RiskLevel::VeryLow,
+            RiskLevel::Low,
+            RiskLevel::Moderate,
+            RiskLevel::High,
+            RiskLevel::VeryHigh,
+            RiskLevel::Critical,
+            RiskLevel::None,
+        ];
+        let custom_presets = THEME_PRESETS.iter().map(|preset| {
+            let theme = preset.theme();
+            (preset.message_id, theme.iced_theme(), theme.risk_palette)
+        });
+        let themes = AppTheme::all()
+            .iter()
+            .map(|theme| (theme.label(), theme.iced_theme(), theme.risk_palette()))
+            .chain(custom_presets);
+
+        for (theme, iced_theme, palette) in themes {
+            let background = iced_theme.palette().background;
+            let background = luminance((background.r, background.g, background.b));
+            for level in levels {
+                let color = luminance(level.rgb_in(palette));
+                let (light, dark) = if color > background {
+                    (color, background)
+                } else {
+                    (background, color)
+                };
+                let contrast = (light + 0.05) / (dark + 0.05);
+                assert!(
+                    contrast >= 4.5,
+                    "{:?} on {:?}: contrast {:.2}",
+                    level,
+                    theme,
+                    contrast
+                );
+            }
+        }
+    }
 }
@@ -0,0 +1,218 @@
+// i18n.rs
+// Translated UI texts: one Fluent catalog per language in `locales/`
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use fluent_bundle::concurrent::FluentBundle;
+use fluent_bundle::FluentResource;
+use unic_langid::LanguageIdentifier;
+
+pub use fluent_bundle::{FluentArgs, FluentValue};
+
+use super::Language;
+use crate::config::{RiskLevel, ScoreCategory, Specialty, UnitSystem};
+use crate::export::deidentify::TimestampPrecision;
+use crate::scores::pack::PackIntegrity;
+
+/// Catalogs compiled into the binary. All of them define the same messages
+/// (see the tests); English is the fallback for anything not found.
+const CATALOGS: [(Language, &str); 5] = [
+    (Language::English, include_str!("../../locales/en.ftl")),
+    (Language::German, include_str!("../../locales/de.ftl")),
+    (Language::French, include_str!("../../locales/fr.ftl")),
+    (Language::Spanish, include_str!("../../locales/es.ftl")),
+    (Language::Italian, include_str!("../../locales/it.ftl")),
+];
+
+type Bundle = FluentBundle<FluentResource>;
+
+fn bundles() -> &'static HashMap<Language, Bundle> {
+    static BUNDLES: OnceLock<HashMap<Language, Bundle>> = OnceLock::new();
+    BUNDLES.get_or_init(|| {
+        CATALOGS
+            .iter()
+            .map(|&(language, source)| {
+                let (bundle, problems) = bundle(language, source);
+                for problem in problems {
+                    tracing::warn!(language = language.code(), %problem, "Skipping catalog entry");
+                }
+                (language, bundle)
+            })
+            .collect()
+    })
+}
+
+/// Bundle of one catalog and the problems loading it: entries with syntax errors
+/// are skipped, and a message defined twice keeps its first text. The test below
+/// keeps the shipped catalogs free of problems.
+fn bundle(language: Language, source: &str) -> (Bundle, Vec<String>) {
+    let locale: LanguageIdentifier = language.code().parse().unwrap_or_default();
+    let mut bundle = FluentBundle::new_concurrent(vec![locale]);
+    // Iced renders the text as is, so no Unicode isolation marks around arguments
+    bundle.set_use_isolating(false);
+    let mut problems = Vec::new();
+    let resource =
+        FluentResource::try_new(source.to_string()).unwrap_or_else(|(resource, errors)| {
+            problems.extend(errors.iter().map(|error| error.to_string()));
+            resource
+        });
+    if let Err(errors) = bundle.add_resource(resource) {
+        problems.extend(errors.iter().map(|error| error.to_string()));
+    }
+    (bundle, problems)
+}
+
+fn format(language: Language, id: &str, args: Option<&FluentArgs>) -> Option<String> {
+    let bundle = bundles().get(&language)?;
+    let pattern = bundle.get_message(id)?.value()?;
+    let mut errors = Vec::new();
+    Some(
+        bundle
+            .format_pattern(pattern, args, &mut errors)
+            .into_owned(),
+    )
+}
+
+/// Text of message `id` with its variables set from `args`; falls back to
+/// English, then to the id itself
+pub fn message_with(language: Language, id: &str, args: &FluentArgs) -> String {
+    format(language, id, Some(args))
+        .or_else(|| format(Language::English, id, Some(args)))
+        .unwrap_or_else(|| id.to_string())
+}
+
+/// Text of message `id`, which has no variables
+pub fn message(language: Language, id: &str) -> String {
+    format(language, id, None)
+        .or_else(|| format(Language::English, id, None))
+        .unwrap_or_else(|| id.to_string())
+}
+
+/// Name of a specialty
+pub fn specialty_name(language: Language, specialty: Specialty) -> String {
+    message(language, &format!("specialty-{:?}", specialty))
+}
+
+/// Name of a score category
+pub fn category_name(language: Language, category: ScoreCategory) -> String {
+    message(language, &format!("category-{:?}", category))
+}
+
+/// Name of a risk level, e.g. "very high"
+pub fn risk_level_name(language: Language, level: RiskLevel) -> String {
+    message(language, &format!("risk-level-{:?}", level))
+}
+
+/// Name of a timestamp precision of the research export
+pub fn precision_name(language: Language, precision: TimestampPrecision) -> String {
+    message(language, &format!("precision-{:?}", precision))
+}
+
+/// Name of a unit system for lab values
+pub fn unit_system_name(language: Language, unit_system: UnitSystem) -> String {
+    message(language, &format!("unit-system-{:?}", unit_system))
+}
+
+/// Result of a score pack's integrity check
+pub fn pack_integrity_text(language: Language, integrity: &PackIntegrity) -> String {
+    match integrity {
+        PackIntegrity::Unverified => message(language, "pack-unverified"),
+        PackIntegrity::Verified => message(language, "pack-verified"),
+        PackIntegrity::Untrusted { key } => {
+            crate::tr!(language, "pack-untrusted", key = key.as_str())
+        }
+        PackIntegrity::Signed { key } => crate::tr!(language, "pack-signed", key = key.as_str()),
+        PackIntegrity::Failed(reason) => {
+            crate::tr!(language, "pack-failed", reason = reason.as_str())
+        }
+    }
+}
+
+/// Translated UI text: `tr!(language, "message-id")`, or with variables
+/// `tr!(language, "history-count", count = 3)`
+#[macro_export]
+macro_rules! tr {
+    ($language:expr, $id:expr $(,)?) => {
+        $crate::ui::i18n::message($language, $id)
+    };
+    ($language:expr, $id:expr, $($name:ident = $value:expr),+ $(,)?) => {{
+        let mut args = $crate::ui::i18n::FluentArgs::new();
+        $(args.set(stringify!($name), $value);)+
+        $crate::ui::i18n::message_with($language, $id, &args)
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Ids of the messages in a catalog: the lines starting with `id =`
+    fn message_ids(source: &str) -> Vec<&str> {
+        source
+            .lines()
+            .filter(|line| line.starts_with(|c: char| c.is_ascii_alphabetic()))
+            .filter_map(|line| line.split_once(" =").map(|(id, _)| id))
+            .collect()
+    }
+
+    #[test]
+    fn test_catalogs_complete() {
+        let english = message_ids(CATALOGS[0].1);
+        assert!(!english.is_empty());
+        for (language, source) in CATALOGS {
+            let (_, problems) = bundle(language, source);
+            assert!(
+                problems.is_empty(),
+                "{:?} catalog: {:?}",
+                language,
+                problems
+            );
+            let ids = message_ids(source);
+            let missing: Vec<&&str> = english.iter().filter(|id| !ids.contains(id)).collect();
+            assert!(missing.is_empty(), "{:?} lacks {:?}", language, missing);
+            let extra: Vec<&&str> = ids.iter().filter(|id| !english.contains(id)).collect();
+            assert!(extra.is_empty(), "{:?} has extra {:?}", language, extra);
+        }
+    }
+
+    #[test]
+    fn test_catalog_problems_are_reported() {
+        let source = "back = Back\nback = Again\nbroken = {\n";
+        let (bundle, problems) = bundle(Language::English, source);
+        assert_eq!(problems.len(), 2, "{:?}", problems);
+        let back = bundle.get_message("back").unwrap().value().unwrap();
+        let mut errors = Vec::new();
+        assert_eq!(bundle.format_pattern(back, None, &mut errors), "Back");
+    }
+
+    #[test]
+    fn test_message_formatting() {
+        assert_eq!(tr!(Language::English, "back"), "← Back");
+        assert_eq!(tr!(Language::German, "back"), "← Zurück");
+        assert_eq!(tr!(Language::English, "scores-count", count = 1), "1 score");
+        assert_eq!(tr!(Language::German, "scores-count", count = 3), "3 Scores");
+        // French counts 0 as singular
+        assert_eq!(tr!(Language::French, "scores-count", count = 0), "0 score");
+        // Unknown ids show up as themselves instead of an empty label
+        assert_eq!(tr!(Language::Italian, "no-such-message"), "no-such-message");
+    }
+
+    #[test]
+    fn test_specialty_names_match_config() {
+        for specialty in Specialty::ALL {
+            assert_eq!(
+                specialty_name(Language::English, specialty),
+                specialty.english()
+            );
+            assert_eq!(
+                specialty_name(Language::German, specialty),
+                specialty.german()
+            );
+        }
+        assert_eq!(
+            risk_level_name(Language::German, RiskLevel::VeryHigh),
+            RiskLevel::VeryHigh.label("de")
+        );
+    }
+}
@@ -0,0 +1,126 @@
+// layout.rs
+// Window width breakpoints and touch sizing for the responsive layout
+
+use iced::widget::text_input;
+use iced::{Length, Theme};
+
+/// Layout for the current window width and input device
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ScreenLayout {
+    /// Narrow window, e.g. half a screen next to the EHR: reduced paddings,
+    /// full-width buttons and header buttons behind a menu
+    compact: bool,
+    /// Touch screen mode: hit targets of at least [`Self::MIN_TOUCH_TARGET`]
+    /// pixels and bold focus outlines
+    touch: bool,
+}
+
+impl ScreenLayout {
+    /// Windows narrower than this (logical pixels) use the compact layout
+    pub const COMPACT_BELOW: f32 = 800.0;
+
+    /// Minimum height of buttons and other controls in touch mode
+    pub const MIN_TOUCH_TARGET: f32 = 44.0;
+
+    pub fn for_width(width: f32) -> Self {
+        Self {
+            compact: width < Self::COMPACT_BELOW,
+            touch: false,
+        }
+    }
+
+    pub fn with_touch(self, touch: bool) -> Self {
+        Self { touch, ..self }
+    }
+
+    pub fn is_compact(self) -> bool {
+        self.compact
+    }
+
+    pub fn is_touch(self) -> bool {
+        self.touch
+    }
+
+    /// Padding of `regular` pixels, reduced to at most 10 in the compact layout
+    pub fn padding(self, regular: u16) -> u16 {
+        if self.compact {
+            regular.min(10)
+        } else {
+            regular
+        }
+    }
+
+    /// Width of `regular` pixels, or the full available width in the compact layout
+    pub fn fixed_width(self, regular: f32) -> Length {
+        if self.compact {
+            Length::Fill
+        } else {
+            Length::Fixed(regular)
+        }
+    }
+
+    /// Padding inside a button: in touch mode at least 14 pixels, which makes
+    /// buttons with text of size 14 or more at least 44 pixels high
+    pub fn button_padding(self, regular: u16) -> u16 {
+        if self.touch {
+            regular.max(14)
+        } else {
+            regular
+        }
+    }
+
+    /// Size of checkboxes and radio buttons (box edge in pixels)
+    pub fn toggle_size(self, regular: f32) -> f32 {
+        if self.touch {
+            regular.max(28.0)
+        } else {
+            regular
+        }
+    }
+
+    /// Text input style; in touch mode the focused input gets a bold outline
+    /// in the theme's primary color
+    pub fn text_input_style(self) -> impl Fn(&Theme, text_input::Status) -> text_input::Style {
+        move |theme, status| {
+            let mut style = text_input::default(theme, status);
+            if self.touch && status == text_input::Status::Focused {
+                style.border = style
+                    .border
+                    .width(3)
+                    .color(theme.extended_palette().primary.strong.color);
+            }
+            style
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_breakpoint() {
+        // Half of a 1280px screen is compact, the default window is not
+        assert!(ScreenLayout::for_width(640.0).is_compact());
+        assert!(!ScreenLayout::for_width(1000.0).is_compact());
+        assert_eq!(ScreenLayout::for_width(640.0).padding(50), 10);
+        assert_eq!(ScreenLayout::for_width(1000.0).padding(50), 50);
+        assert_eq!(
+            ScreenLayout::for_width(640.0).fixed_width(400.0),
+            Length::Fill
+        );
+    }
+
+    #[test]
+    fn test_touch_targets() {
+        let touch = ScreenLayout::for_width(1000.0).with_touch(true);
+        // Text of size 14 is about 18 pixels high (line height 1.3)
+        let button_height = 14.0 * 1.3 + 2.0 * f32::from(touch.button_padding(8));
+        assert!(button_height >= ScreenLayout::MIN_TOUCH_TARGET);
+        assert_eq!(touch.button_padding(20), 20);
+        assert_eq!(touch.toggle_size(16.0), 28.0);
+
+        assert!(touch.is_touch() && !touch.is_compact());
+        assert_eq!(ScreenLayout::default().button_padding(8), 8);
+    }
+}
@@ -1,8 +1,17 @@
 // UI module
+pub mod i18n;
+pub mod layout;
+pub mod modal;
 pub mod result_display;
 pub mod score_input;
 pub mod specialty_selection;
+pub mod toast;
+pub mod trend_chart;
 
+pub use layout::*;
+pub use modal::*;
 pub use result_display::*;
 pub use score_input::*;
 pub use specialty_selection::*;
+pub use toast::*;
+pub use trend_chart::*;
@@ -0,0 +1,50 @@
+// modal.rs
+// Dialog shown over the dimmed main content
+
+use iced::widget::{center, container, mouse_area, opaque, stack};
+use iced::{Color, Element};
+
+/// Show `dialog` centered over `base`, which is dimmed and ignores input.
+/// Clicking outside the dialog sends `on_blur`.
+pub fn modal<'a, Message>(
+    base: impl Into<Element<'a, Message>>,
+    dialog: impl Into<Element<'a, Message>>,
+    on_blur: Message,
+) -> Element<'a, Message>
+where
+    Message: Clone + 'a,
+{
+    let dialog = container(dialog)
+        .padding(20)
+        .max_width(420)
+        .style(|theme: &iced::Theme| {
+            let palette = theme.palette();
+            container::Style {
+                background: Some(iced::Background::Color(palette.background)),
+                border: iced::Border {
+                    color: Color {
+                        a: 0.3,
+                        ..palette.text
+                    },
+                    width: 1.0,
+                    radius: 8.0.into(),
+                },
+                ..Default::default()
+            }
+        });
+
+    stack![
+        base.into(),
+        opaque(
+            mouse_area(center(opaque(dialog)).style(|_theme| container::Style {
+                background: Some(iced::Background::Color(Color {
+                    a: 0.6,
+                    ..Color::BLACK
+                })),
+                ..Default::default()
+            }))
+            .on_press(on_blur)
+        )
+    ]
+    .into()
+}
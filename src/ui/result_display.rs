@@ -1,91 +1,98 @@
 // result_display.rs
 // Display calculation results with risk visualization
 
-use crate::config::RiskLevel;
-use crate::scores::CalculationResult;
-use crate::ui::Language;
+use crate::config::{RiskLevel, RiskPalette};
+use crate::scores::{format_signed_points, CalculationResult};
+use crate::tr;
+use crate::ui::{i18n, Language, ScreenLayout};
 use iced::{
-    widget::{button, column, container, row, text},
+    widget::{button, checkbox, column, container, horizontal_rule, pick_list, row, text},
     Alignment, Color, Element, Length,
 };
 
+/// Messages emitted by the buttons of the result view
+#[derive(Debug, Clone)]
+pub struct ResultActions<Message> {
+    pub recalculate: Message,
+    pub back: Message,
+    pub export_csv: Message,
+    pub export_json: Message,
+    pub export_pdf: Message,
+    pub export_markdown: Message,
+    pub export_fhir: Message,
+    /// Exports with the template of the given display name
+    pub export_template: fn(String) -> Message,
+    /// Copies a plain-text summary to the clipboard
+    pub copy_summary: Message,
+    /// Copies the Markdown summary to the clipboard
+    pub copy_markdown: Message,
+    pub export_patient_pdf: Message,
+    pub print: Message,
+    /// Toggles whether the breakdown lists criteria without points
+    pub toggle_zero_points: fn(bool) -> Message,
+}
+
 /// Display calculation result with color-coded risk
+///
+/// `templates` are the display names of the export templates offered below the
+/// export buttons.
 pub fn result_display_view<'a, Message>(
     result: &'a CalculationResult,
     language: Language,
-    on_recalculate: Message,
-    on_back: Message,
-    on_export_csv: Message,
-    on_export_json: Message,
-    on_export_pdf: Message,
+    layout: ScreenLayout,
+    palette: RiskPalette,
+    show_zero_points: bool,
+    templates: Vec<String>,
+    actions: ResultActions<Message>,
 ) -> Element<'a, Message>
 where
     Message: Clone + 'a,
 {
-    let score_label = match language {
-        Language::German => "Score:",
-        Language::English => "Score:",
-    };
-
-    let risk_label = match language {
-        Language::German => "Risiko:",
-        Language::English => "Risk:",
-    };
-
-    let recommendation_label = match language {
-        Language::German => "Empfehlung:",
-        Language::English => "Recommendation:",
-    };
-
-    let risk_text = match language {
-        Language::German => &result.risk_de,
-        Language::English => &result.risk,
-    };
-
-    let recommendation_text = match language {
-        Language::German => &result.recommendation_de,
-        Language::English => &result.recommendation,
-    };
-
-    let details_text = match language {
-        Language::German => result.details_de.as_ref(),
-        Language::English => result.details.as_ref(),
-    };
+    let risk_text = result.risk.text(language.code());
+    let recommendation_text = result.recommendation.text(language.code());
+    let details_text = result
+        .details
+        .as_ref()
+        .map(|details| details.text(language.code()));
 
     // Get color for risk level
-    let risk_color = get_risk_color(result.risk_level);
-
-    let recalculate_label = match language {
-        Language::German => "Neu berechnen",
-        Language::English => "Calculate Again",
-    };
-
-    let back_label = match language {
-        Language::German => "← Zurück",
-        Language::English => "← Back",
-    };
-
-    let export_label = match language {
-        Language::German => "Exportieren:",
-        Language::English => "Export:",
-    };
+    let risk_color = get_risk_color(result.risk_level, palette);
 
     let mut content_widgets = vec![
         // Score value - large and prominent
-        text(format!("{} {}", score_label, result.total_score))
-            .size(48)
-            .into(),
+        text(tr!(
+            language,
+            "result-score",
+            score = result.total_score.to_string()
+        ))
+        .size(48)
+        .into(),
         // Risk level with color
+        // Icon and level name so the risk does not rely on color alone
         container(
-            text(format!("{} {}", risk_label, risk_text))
+            column![
+                text(format!(
+                    "{} {}",
+                    result.risk_level.icon(),
+                    tr!(language, "result-risk", risk = risk_text)
+                ))
                 .size(32)
                 .color(risk_color),
+                text(tr!(
+                    language,
+                    "result-risk-level",
+                    level = i18n::risk_level_name(language, result.risk_level)
+                ))
+                .size(16),
+            ]
+            .spacing(5)
+            .align_x(Alignment::Center),
         )
         .padding(15)
         .into(),
         // Recommendation
         column![
-            text(recommendation_label).size(18),
+            text(tr!(language, "recommendation")).size(18),
             text(recommendation_text).size(16),
         ]
         .spacing(5)
@@ -96,92 +103,103 @@ where
     // Add details if present
     if let Some(details) = details_text {
         content_widgets.push(
-            column![text("Details:").size(14), text(details).size(12),]
-                .spacing(5)
-                .padding(10)
-                .into(),
+            column![
+                text(tr!(language, "result-details")).size(14),
+                text(details).size(12),
+            ]
+            .spacing(5)
+            .padding(10)
+            .into(),
         );
     }
 
     // Add breakdown of points
     if !result.field_scores.is_empty() {
-        let breakdown_label = match language {
-            Language::German => "Punkteverteilung:",
-            Language::English => "Points Breakdown:",
-        };
-
-        let points_label = match language {
-            Language::German => "Pkt.",
-            Language::English => "pts",
-        };
-
-        let breakdown_items: Vec<Element<'a, Message>> = result
-            .field_scores
-            .iter()
-            .filter(|fs| fs.points != 0)
-            .map(|fs| {
-                let label = match language {
-                    Language::German => &fs.label_de,
-                    Language::English => &fs.label,
-                };
-                text(format!("  {} {} — {}", fs.points, points_label, label))
-                    .size(14)
-                    .into()
-            })
-            .collect();
-
-        if !breakdown_items.is_empty() {
-            content_widgets.push(
-                column![
-                    text(breakdown_label).size(16),
-                    column(breakdown_items).spacing(3)
-                ]
-                .spacing(5)
-                .padding(10)
-                .into(),
-            );
-        }
+        content_widgets.push(breakdown_table(
+            result,
+            language,
+            layout,
+            show_zero_points,
+            actions.toggle_zero_points,
+        ));
     }
 
     // Export buttons row
     content_widgets.push(
         row![
-            text(export_label).size(14),
+            text(tr!(language, "result-export")).size(14),
+            button(text(tr!(language, "result-copy")).size(14))
+                .on_press(actions.copy_summary)
+                .padding(layout.button_padding(8)),
             button(text("CSV").size(14))
-                .on_press(on_export_csv)
-                .padding(8),
+                .on_press(actions.export_csv)
+                .padding(layout.button_padding(8)),
             button(text("JSON").size(14))
-                .on_press(on_export_json)
-                .padding(8),
+                .on_press(actions.export_json)
+                .padding(layout.button_padding(8)),
             button(text("PDF").size(14))
-                .on_press(on_export_pdf)
-                .padding(8),
+                .on_press(actions.export_pdf)
+                .padding(layout.button_padding(8)),
+            button(text("Markdown").size(14))
+                .on_press(actions.export_markdown)
+                .padding(layout.button_padding(8)),
+            button(text(tr!(language, "result-copy-markdown")).size(14))
+                .on_press(actions.copy_markdown)
+                .padding(layout.button_padding(8)),
+            button(text("FHIR").size(14))
+                .on_press(actions.export_fhir)
+                .padding(layout.button_padding(8)),
+            button(text(tr!(language, "result-patient-copy")).size(14))
+                .on_press(actions.export_patient_pdf)
+                .padding(layout.button_padding(8)),
         ]
         .spacing(10)
         .align_y(Alignment::Center)
         .padding(10)
+        // Buttons move to a second line in narrow windows
+        .wrap()
         .into(),
     );
 
+    if !templates.is_empty() {
+        content_widgets.push(
+            row![
+                text(tr!(language, "result-template")).size(14),
+                pick_list(templates, None::<String>, actions.export_template)
+                    .placeholder(tr!(language, "result-template-placeholder"))
+                    .text_size(14)
+                    .padding(layout.button_padding(8)),
+            ]
+            .spacing(10)
+            .align_y(Alignment::Center)
+            .padding([0, 10])
+            .into(),
+        );
+    }
+
     // Add action buttons
     content_widgets.push(
         row![
-            button(text(recalculate_label).size(18))
-                .on_press(on_recalculate)
-                .padding(12),
-            button(text(back_label).size(18))
-                .on_press(on_back)
-                .padding(12),
+            button(text(tr!(language, "result-calculate-again")).size(18))
+                .on_press(actions.recalculate)
+                .padding(layout.button_padding(12)),
+            button(text(tr!(language, "result-print")).size(18))
+                .on_press(actions.print)
+                .padding(layout.button_padding(12)),
+            button(text(tr!(language, "back")).size(18))
+                .on_press(actions.back)
+                .padding(layout.button_padding(12)),
         ]
         .spacing(15)
-        .padding(20)
+        .padding(layout.padding(20))
+        .wrap()
         .into(),
     );
 
     let content = column(content_widgets)
         .spacing(20)
         .align_x(Alignment::Center)
-        .padding(30)
+        .padding(layout.padding(30))
         .max_width(700);
 
     container(content)
@@ -190,8 +208,86 @@ where
         .into()
 }
 
+/// Two-column table of criteria and their points, with a total row
+///
+/// Criteria that scored no points are listed only when `show_all` is set.
+fn breakdown_table<'a, Message>(
+    result: &'a CalculationResult,
+    language: Language,
+    layout: ScreenLayout,
+    show_all: bool,
+    on_toggle: fn(bool) -> Message,
+) -> Element<'a, Message>
+where
+    Message: Clone + 'a,
+{
+    let muted = Color::from_rgb(0.5, 0.5, 0.5);
+    let table_row = |criterion: Element<'a, Message>, points: Element<'a, Message>| {
+        row![
+            container(criterion).width(Length::Fill),
+            container(points)
+                .width(Length::Fixed(80.0))
+                .align_x(Alignment::End),
+        ]
+        .spacing(10)
+        .align_y(Alignment::Center)
+    };
+
+    let mut rows = column![table_row(
+        text(tr!(language, "breakdown-criterion")).size(14).into(),
+        text(tr!(language, "breakdown-points")).size(14).into(),
+    )]
+    .spacing(6);
+    rows = rows.push(horizontal_rule(1));
+
+    let mut listed = 0;
+    for field_score in &result.field_scores {
+        if field_score.points == 0 && !show_all {
+            continue;
+        }
+        listed += 1;
+        let color = (field_score.points == 0).then_some(muted);
+
+        let mut criterion = column![text(field_score.label.text(language.code()))
+            .size(14)
+            .color_maybe(color)];
+        if let Some(selection) = &field_score.selection {
+            criterion = criterion.push(text(selection.text(language.code())).size(12).color(muted));
+        }
+        rows = rows.push(table_row(
+            criterion.into(),
+            text(format_signed_points(field_score.points))
+                .size(14)
+                .color_maybe(color)
+                .into(),
+        ));
+    }
+    if listed == 0 {
+        rows = rows.push(text(tr!(language, "no-points")).size(12).color(muted));
+    }
+
+    rows = rows.push(horizontal_rule(1));
+    rows = rows.push(table_row(
+        text(tr!(language, "breakdown-total")).size(14).into(),
+        text(result.total_score.to_string()).size(14).into(),
+    ));
+
+    column![
+        text(tr!(language, "points-breakdown")).size(16),
+        checkbox(tr!(language, "breakdown-show-all"), show_all)
+            .on_toggle(on_toggle)
+            .size(layout.toggle_size(16.0))
+            .text_size(13),
+        rows,
+    ]
+    .spacing(8)
+    .padding(10)
+    .max_width(600)
+    .into()
+}
+
 /// Get color for risk level
-fn get_risk_color(level: RiskLevel) -> Color {
-    let (r, g, b) = level.rgb();
+fn get_risk_color(level: RiskLevel, palette: RiskPalette) -> Color {
+    let (r, g, b) = level.rgb_in(palette);
     Color::from_rgb(r, g, b)
 }
@@ -1,14 +1,27 @@
 // score_input.rs
 // Dynamic form generator for score inputs
 
-use crate::config::{InputField, InputType, ScoreDefinition};
-use crate::scores::InputValue;
-use crate::ui::Language;
+use crate::config::{
+    ConventionalUnit, DurationUnit, ElapsedUnit, InputField, InputSection, InputType, InputWidget,
+    RiskPalette, ScoreDefinition, UnitSystem, FALLBACK_LANGUAGE,
+};
+use crate::scores::durations::{format_duration, parse_duration};
+use crate::scores::numbers;
+use crate::scores::{
+    calculate_partial, describe_input, elapsed_time, format_signed_points, CalculationError,
+    CalendarDate, InputValue, Quantity,
+};
+use crate::tr;
+use crate::ui::{i18n, Language, ScreenLayout};
+use chrono::Datelike;
 use iced::{
-    widget::{button, checkbox, column, container, pick_list, row, text, text_input},
-    Element, Length,
+    widget::{
+        button, checkbox, column, container, pick_list, progress_bar, radio, row, slider, text,
+        text_input, tooltip,
+    },
+    Alignment, Element, Length,
 };
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
 
 /// A displayable dropdown option for pick_list
@@ -29,6 +42,32 @@ impl fmt::Display for DropdownItem {
 pub struct ScoreInputState {
     pub inputs: HashMap<String, InputValue>,
     pub text_buffers: HashMap<String, String>,
+    /// IDs of form sections the user has collapsed
+    pub collapsed_sections: HashSet<String>,
+    /// Number fields whose text is not a valid number (and have no value in `inputs`)
+    pub invalid_numbers: HashSet<String>,
+    /// Date fields whose text is not a valid date (and have no value in `inputs`)
+    pub invalid_dates: HashSet<String>,
+    /// Duration fields whose text is not a valid duration (and have no value in `inputs`)
+    pub invalid_durations: HashSet<String>,
+    /// Current page in wizard mode (one page per section, then the review page);
+    /// `None` shows the whole form
+    pub wizard_step: Option<usize>,
+    /// Optional patient label (e.g., "Bed 12") stored with the calculation;
+    /// `None` hides the field
+    pub patient_label: Option<String>,
+    /// Patient ID to prefill inputs from the FHIR server with; `None` hides the
+    /// field (no server configured, or no input of the score declares LOINC codes)
+    pub fhir_patient_id: Option<String>,
+    /// Outcome of the last prefill, shown below the patient ID
+    pub fhir_status: Option<String>,
+    /// Units lab values are typed in; values typed in conventional units are
+    /// kept as quantities in that unit, which the calculation converts
+    pub unit_system: UnitSystem,
+    /// Fields typed in their conventional unit
+    conventional_units: HashMap<String, ConventionalUnit>,
+    /// Units the duration fields are scored in
+    duration_units: HashMap<String, DurationUnit>,
 }
 
 impl Default for ScoreInputState {
@@ -42,25 +81,355 @@ impl ScoreInputState {
         Self {
             inputs: HashMap::new(),
             text_buffers: HashMap::new(),
+            collapsed_sections: HashSet::new(),
+            invalid_numbers: HashSet::new(),
+            invalid_dates: HashSet::new(),
+            invalid_durations: HashSet::new(),
+            wizard_step: None,
+            patient_label: None,
+            fhir_patient_id: None,
+            fhir_status: None,
+            unit_system: UnitSystem::Si,
+            conventional_units: HashMap::new(),
+            duration_units: HashMap::new(),
+        }
+    }
+
+    /// Empty form for `score`, with dropdown defaults preselected, today's date in
+    /// the dates elapsed times are measured until, and lab values typed in
+    /// `unit_system`
+    pub fn for_score(score: &ScoreDefinition, unit_system: UnitSystem) -> Self {
+        let mut state = Self::new();
+        state.unit_system = unit_system;
+        state.conventional_units = conventional_units(score, unit_system);
+        for input in &score.inputs {
+            if let Some(unit) = input.duration_unit {
+                state.duration_units.insert(input.field.clone(), unit);
+            }
+            if let Some(value) = input.default_option() {
+                state.update_dropdown(input.field.clone(), value.to_string());
+            }
+            if let Some(elapsed) = &input.elapsed {
+                state.set_date(elapsed.until.clone(), today());
+            }
+        }
+        state
+    }
+
+    /// Form for `score` pre-filled with previously entered `inputs`, e.g. from a
+    /// history entry; values of fields the score no longer has are dropped
+    pub fn with_inputs(
+        score: &ScoreDefinition,
+        inputs: &HashMap<String, InputValue>,
+        language: Language,
+        unit_system: UnitSystem,
+    ) -> Self {
+        let mut state = Self::for_score(score, unit_system);
+        state.fill_inputs(score, inputs, language);
+        state
+    }
+
+    /// Set the inputs of `score` in `values` (e.g., prefilled from the patient's
+    /// record), keeping everything else entered
+    pub fn fill_inputs(
+        &mut self,
+        score: &ScoreDefinition,
+        values: &HashMap<String, InputValue>,
+        language: Language,
+    ) {
+        for input in &score.inputs {
+            match values.get(&input.field) {
+                Some(InputValue::Number(duration)) if input.input_type == InputType::Duration => {
+                    self.set_duration(&input.field, *duration);
+                }
+                Some(InputValue::Number(number)) => {
+                    self.set_number(&input.field, *number, language);
+                }
+                Some(value @ InputValue::Quantity(_)) => {
+                    self.set_quantity(input, value, language);
+                }
+                Some(InputValue::Date(date)) => self.set_date(input.field.clone(), *date),
+                Some(value) => {
+                    self.inputs.insert(input.field.clone(), value.clone());
+                }
+                None => {}
+            }
+        }
+    }
+
+    /// Type lab values of `score` in `unit_system`, showing entered values in it
+    pub fn set_unit_system(
+        &mut self,
+        score: &ScoreDefinition,
+        unit_system: UnitSystem,
+        language: Language,
+    ) {
+        self.unit_system = unit_system;
+        self.conventional_units = conventional_units(score, unit_system);
+        for input in score
+            .inputs
+            .iter()
+            .filter(|input| input.input_type == InputType::Number)
+        {
+            if let Some(value) = self.inputs.get(&input.field).cloned() {
+                self.set_quantity(input, &value, language);
+            }
+        }
+    }
+
+    /// Show the SI value `number` in the unit being typed and store it unchanged
+    fn set_number(&mut self, field: &str, number: f64, language: Language) {
+        let shown = match self.conventional_units.get(field) {
+            // Converted values are shown to two decimals, the stored value stays exact
+            Some(conventional) => ((number / conventional.factor) * 100.0).round() / 100.0,
+            None => number,
+        };
+        self.show_number(field, shown, language);
+        self.inputs
+            .insert(field.to_string(), InputValue::Number(number));
+    }
+
+    /// Show a number or quantity of `input` in the unit being typed: as it is if
+    /// entered in that unit, else converted from its SI value
+    fn set_quantity(&mut self, input: &InputField, value: &InputValue, language: Language) {
+        match value {
+            InputValue::Quantity(quantity)
+                if self.conventional_units.contains_key(&input.field)
+                    && input.unit_system_of(&quantity.unit) == Some(UnitSystem::Conventional) =>
+            {
+                self.show_number(&input.field, quantity.value, language);
+                self.inputs.insert(input.field.clone(), value.clone());
+            }
+            _ => {
+                if let Some(number) = value.number_in(input) {
+                    self.set_number(&input.field, number, language);
+                }
+            }
         }
     }
 
+    fn show_number(&mut self, field: &str, number: f64, language: Language) {
+        let text = if language.decimal_comma() {
+            number.to_string().replace('.', ",")
+        } else {
+            number.to_string()
+        };
+        self.text_buffers.insert(field.to_string(), text);
+        self.invalid_numbers.remove(field);
+    }
+
+    /// Show the duration written out (e.g., "4 h 30 min") and store it unchanged
+    fn set_duration(&mut self, field: &str, duration: f64) {
+        if let Some(&unit) = self.duration_units.get(field) {
+            self.text_buffers
+                .insert(field.to_string(), format_duration(duration, unit));
+        }
+        self.invalid_durations.remove(field);
+        self.inputs
+            .insert(field.to_string(), InputValue::Number(duration));
+    }
+
+    /// Entered patient label, if the field is shown and not blank
+    pub fn patient_label(&self) -> Option<String> {
+        self.patient_label
+            .as_deref()
+            .map(str::trim)
+            .filter(|label| !label.is_empty())
+            .map(str::to_string)
+    }
+
     pub fn update_boolean(&mut self, field: String, value: bool) {
         self.inputs.insert(field, InputValue::Boolean(value));
     }
 
-    pub fn update_number_text(&mut self, field: String, value: String) {
+    /// Store the typed text and its value, as a quantity if typed in a conventional
+    /// unit; unparsable text clears the value
+    pub fn update_number_text(&mut self, field: String, value: String, language: Language) {
         self.text_buffers.insert(field.clone(), value.clone());
 
-        // Try to parse as number
-        if let Ok(num) = value.parse::<f64>() {
-            self.inputs.insert(field, InputValue::Number(num));
+        if value.trim().is_empty() {
+            self.inputs.remove(&field);
+            self.invalid_numbers.remove(&field);
+            return;
+        }
+
+        match parse_decimal(&value, language) {
+            Some(num) => {
+                let value = match self.conventional_units.get(&field) {
+                    Some(conventional) => InputValue::Quantity(Quantity {
+                        value: num,
+                        unit: conventional.unit.text(FALLBACK_LANGUAGE).to_string(),
+                    }),
+                    None => InputValue::Number(num),
+                };
+                self.invalid_numbers.remove(&field);
+                self.inputs.insert(field, value);
+            }
+            None => {
+                self.inputs.remove(&field);
+                self.invalid_numbers.insert(field);
+            }
         }
     }
 
     pub fn update_dropdown(&mut self, field: String, value: String) {
         self.inputs.insert(field, InputValue::Dropdown(value));
     }
+
+    /// Store the typed text and its date; unparsable text clears the date
+    pub fn update_date_text(&mut self, field: String, value: String, language: Language) {
+        self.text_buffers.insert(field.clone(), value.clone());
+
+        if value.trim().is_empty() {
+            self.inputs.remove(&field);
+            self.invalid_dates.remove(&field);
+            return;
+        }
+
+        match parse_date(&value, language) {
+            Some(date) => {
+                self.invalid_dates.remove(&field);
+                self.inputs.insert(field, InputValue::Date(date));
+            }
+            None => {
+                self.inputs.remove(&field);
+                self.invalid_dates.insert(field);
+            }
+        }
+    }
+
+    /// Store the typed text and its duration in the field's unit; unparsable text
+    /// clears the value
+    pub fn update_duration_text(&mut self, field: String, value: String) {
+        self.text_buffers.insert(field.clone(), value.clone());
+
+        if value.trim().is_empty() {
+            self.inputs.remove(&field);
+            self.invalid_durations.remove(&field);
+            return;
+        }
+
+        let duration = self
+            .duration_units
+            .get(&field)
+            .and_then(|&unit| parse_duration(&value, unit));
+        match duration {
+            Some(duration) => {
+                self.invalid_durations.remove(&field);
+                self.inputs.insert(field, InputValue::Number(duration));
+            }
+            None => {
+                self.inputs.remove(&field);
+                self.invalid_durations.insert(field);
+            }
+        }
+    }
+
+    /// Set a date (e.g., today's), shown in the form's language
+    pub fn set_date(&mut self, field: String, date: CalendarDate) {
+        // Without typed text, the field shows the stored date
+        self.text_buffers.remove(&field);
+        self.invalid_dates.remove(&field);
+        self.inputs.insert(field, InputValue::Date(date));
+    }
+
+    pub fn toggle_section(&mut self, section_id: String) {
+        if !self.collapsed_sections.remove(&section_id) {
+            self.collapsed_sections.insert(section_id);
+        }
+    }
+
+    /// Switch between the whole form and the wizard, starting at its first page
+    pub fn toggle_wizard(&mut self) {
+        self.wizard_step = match self.wizard_step {
+            Some(_) => None,
+            None => Some(0),
+        };
+    }
+}
+
+/// Conventional units of the fields of `score` typed in them in `unit_system`
+fn conventional_units(
+    score: &ScoreDefinition,
+    unit_system: UnitSystem,
+) -> HashMap<String, ConventionalUnit> {
+    if unit_system != UnitSystem::Conventional {
+        return HashMap::new();
+    }
+    score
+        .inputs
+        .iter()
+        .filter_map(|input| Some((input.field.clone(), input.conventional_unit.clone()?)))
+        .collect()
+}
+
+/// Parse a number as typed in `language`
+///
+/// Languages written with a decimal comma (see [`Language::decimal_comma`]) accept
+/// a comma ("1,36") as well as a point, English only a point; see
+/// [`numbers::parse_decimal`].
+pub fn parse_decimal(input: &str, language: Language) -> Option<f64> {
+    numbers::parse_decimal(input, language.decimal_comma())
+}
+
+/// Today's date on this computer
+pub fn today() -> CalendarDate {
+    let today = chrono::Local::now().date_naive();
+    CalendarDate::new(today.year(), today.month(), today.day())
+        .expect("the current year lies between 1 and 9999")
+}
+
+/// Parse a date as typed in `language`
+///
+/// YYYY-MM-DD is always accepted; languages other than English also take the
+/// day first, as in "14.03.1956" or "14/03/1956".
+pub fn parse_date(input: &str, language: Language) -> Option<CalendarDate> {
+    let input = input.trim();
+    if let Ok(date) = input.parse() {
+        return Some(date);
+    }
+    if language == Language::English {
+        return None;
+    }
+
+    let parts: Vec<&str> = input.split(['.', '/']).collect();
+    let [day, month, year] = parts.as_slice() else {
+        return None;
+    };
+    let number = |part: &str, digits: std::ops::RangeInclusive<usize>| {
+        (digits.contains(&part.len()) && part.chars().all(|c| c.is_ascii_digit()))
+            .then(|| part.parse::<u32>().ok())
+            .flatten()
+    };
+    CalendarDate::new(
+        number(year, 4..=4)? as i32,
+        number(month, 1..=2)?,
+        number(day, 1..=2)?,
+    )
+}
+
+/// A date as written in `language`: YYYY-MM-DD in English, day first otherwise
+pub fn format_date(date: CalendarDate, language: Language) -> String {
+    let separator = match language {
+        Language::English => return date.to_string(),
+        Language::German => '.',
+        Language::French | Language::Spanish | Language::Italian => '/',
+    };
+    format!(
+        "{:02}{separator}{:02}{separator}{}",
+        date.day(),
+        date.month(),
+        date.year()
+    )
+}
+
+/// Catalog message of an elapsed time without a `unit` in the score file
+fn elapsed_message(unit: ElapsedUnit) -> &'static str {
+    match unit {
+        ElapsedUnit::Years => "elapsed-years",
+        ElapsedUnit::Weeks => "elapsed-weeks",
+        ElapsedUnit::Days => "elapsed-days",
+    }
 }
 
 /// Messages for score input interactions
@@ -68,65 +437,191 @@ impl ScoreInputState {
 pub enum InputMessage {
     BooleanChanged(String, bool),
     NumberTextChanged(String, String),
+    DateTextChanged(String, String),
+    /// Set a date field, e.g. to today
+    DateSelected(String, CalendarDate),
+    DurationTextChanged(String, String),
     #[allow(dead_code)]
     DropdownSelected(String, String),
+    SectionToggled(String),
+    /// Switch between the whole form and the wizard
+    WizardToggled,
+    /// Go to a wizard page (the page after the last section is the review page)
+    WizardStepSelected(usize),
+    PatientLabelChanged(String),
+    FhirPatientIdChanged(String),
+    /// Fetch the patient's data from the FHIR server and fill in what matches
+    PrefillFromFhir,
     Calculate,
     Reset,
 }
 
 /// Generate dynamic input form for a score
+///
+/// With `show_help`, fields that have help text get an info icon showing it on hover.
 pub fn score_input_form<'a, Message>(
     score: &'a ScoreDefinition,
     state: &'a ScoreInputState,
     language: Language,
+    layout: ScreenLayout,
+    palette: RiskPalette,
+    show_help: bool,
     on_message: impl Fn(InputMessage) -> Message + 'a + Copy,
 ) -> Element<'a, Message>
 where
     Message: Clone + 'a,
 {
-    let title = match language {
-        Language::German => &score.name_de,
-        Language::English => &score.name,
-    };
+    if state.wizard_step.is_some() {
+        return wizard_form(
+            score, state, language, layout, palette, show_help, on_message,
+        );
+    }
 
-    let description = match language {
-        Language::German => &score.description_de,
-        Language::English => &score.description,
-    };
+    let title = score.name.text(language.code());
+    let description = score.description.text(language.code());
 
-    // Generate input fields
-    let input_widgets: Vec<Element<'a, Message>> = score
-        .inputs
-        .iter()
-        .map(|input_field| generate_input_widget(input_field, state, language, on_message))
-        .collect();
+    // Generate input fields, grouped into collapsible sections if the score defines them
+    let mut input_widgets: Vec<Element<'a, Message>> = Vec::new();
+    for (section, fields) in score.grouped_inputs() {
+        let collapsed = match section {
+            Some(section) => {
+                let is_collapsed = state.collapsed_sections.contains(&section.id);
+                input_widgets.push(section_header(
+                    section,
+                    fields.len(),
+                    is_collapsed,
+                    language,
+                    layout,
+                    on_message,
+                ));
+                is_collapsed
+            }
+            None => false,
+        };
 
-    let calculate_label = match language {
-        Language::German => "Berechnen",
-        Language::English => "Calculate",
-    };
-
-    let reset_label = match language {
-        Language::German => "Zurücksetzen",
-        Language::English => "Reset",
-    };
+        if !collapsed {
+            input_widgets.extend(fields.into_iter().map(|input_field| {
+                generate_input_widget(input_field, state, language, layout, show_help, on_message)
+            }));
+        }
+    }
 
     let form_content = column![
         text(title).size(28),
         text(description).size(14),
-        column(input_widgets).spacing(15).padding(20),
+        patient_label_input(state, language, layout, on_message),
+        fhir_prefill_input(state, language, layout, on_message),
+        wizard_toggle(false, language, layout, on_message),
+        subtotal_bar(score, state, language, palette),
+        column(input_widgets)
+            .spacing(15)
+            .padding(layout.padding(20)),
         row![
-            button(text(calculate_label).size(18))
+            button(text(tr!(language, "calculate")).size(18))
                 .on_press(on_message(InputMessage::Calculate))
-                .padding(12),
-            button(text(reset_label).size(16))
+                .padding(layout.button_padding(12)),
+            button(text(tr!(language, "reset")).size(16))
                 .on_press(on_message(InputMessage::Reset))
-                .padding(12),
+                .padding(layout.button_padding(12)),
         ]
         .spacing(15),
     ]
     .spacing(20)
-    .padding(20)
+    .padding(layout.padding(20))
+    .max_width(600);
+
+    container(form_content)
+        .width(Length::Fill)
+        .center_x(Length::Fill)
+        .into()
+}
+
+/// Step-by-step form at `state.wizard_step`: one page per section with progress,
+/// then a review page
+fn wizard_form<'a, Message>(
+    score: &'a ScoreDefinition,
+    state: &'a ScoreInputState,
+    language: Language,
+    layout: ScreenLayout,
+    palette: RiskPalette,
+    show_help: bool,
+    on_message: impl Fn(InputMessage) -> Message + 'a + Copy,
+) -> Element<'a, Message>
+where
+    Message: Clone + 'a,
+{
+    let groups = score.grouped_inputs();
+    let review_step = groups.len();
+    let step = state.wizard_step.unwrap_or(0).min(review_step);
+
+    let general_label = tr!(language, "wizard-general");
+
+    let page_title = match groups.get(step) {
+        Some((Some(section), _)) => section.label.text(language.code()).to_string(),
+        Some((None, _)) => general_label.clone(),
+        None => tr!(language, "wizard-review"),
+    };
+    let progress = tr!(
+        language,
+        "wizard-step",
+        step = step + 1,
+        total = review_step + 1,
+        title = page_title
+    );
+
+    let page: Element<'a, Message> = match groups.into_iter().nth(step) {
+        Some((_, fields)) => column(fields.into_iter().map(|input_field| {
+            generate_input_widget(input_field, state, language, layout, show_help, on_message)
+        }))
+        .spacing(15)
+        .into(),
+        None => wizard_review(score, state, &general_label, language, layout, on_message),
+    };
+
+    let mut navigation = row![].spacing(15);
+    if step > 0 {
+        navigation = navigation.push(
+            button(text(tr!(language, "back")).size(16))
+                .on_press(on_message(InputMessage::WizardStepSelected(step - 1)))
+                .padding(layout.button_padding(12))
+                .style(button::secondary),
+        );
+    }
+    navigation = if step < review_step {
+        navigation.push(
+            button(text(tr!(language, "wizard-next")).size(18))
+                .on_press(on_message(InputMessage::WizardStepSelected(step + 1)))
+                .padding(layout.button_padding(12)),
+        )
+    } else {
+        navigation.push(
+            button(text(tr!(language, "calculate")).size(18))
+                .on_press(on_message(InputMessage::Calculate))
+                .padding(layout.button_padding(12)),
+        )
+    };
+    navigation = navigation.push(
+        button(text(tr!(language, "reset")).size(16))
+            .on_press(on_message(InputMessage::Reset))
+            .padding(layout.button_padding(12)),
+    );
+
+    let form_content = column![
+        text(score.name.text(language.code())).size(28),
+        patient_label_input(state, language, layout, on_message),
+        fhir_prefill_input(state, language, layout, on_message),
+        wizard_toggle(true, language, layout, on_message),
+        subtotal_bar(score, state, language, palette),
+        column![
+            text(progress).size(16),
+            progress_bar(0.0..=(review_step + 1) as f32, (step + 1) as f32).height(8),
+        ]
+        .spacing(8),
+        container(page).padding(layout.padding(20)),
+        navigation,
+    ]
+    .spacing(20)
+    .padding(layout.padding(20))
     .max_width(600);
 
     container(form_content)
@@ -135,25 +630,253 @@ where
         .into()
 }
 
+/// Review page of the wizard: every input with its entered value, per section,
+/// with a button back to each section's page
+fn wizard_review<'a, Message>(
+    score: &'a ScoreDefinition,
+    state: &'a ScoreInputState,
+    general_label: &str,
+    language: Language,
+    layout: ScreenLayout,
+    on_message: impl Fn(InputMessage) -> Message + 'a + Copy,
+) -> Element<'a, Message>
+where
+    Message: Clone + 'a,
+{
+    let missing_label = tr!(language, "wizard-missing");
+    let missing_color = iced::Color::from_rgb(0.8, 0.1, 0.1);
+
+    let sections =
+        score
+            .grouped_inputs()
+            .into_iter()
+            .enumerate()
+            .map(|(step, (section, fields))| {
+                let title = section.map_or_else(
+                    || general_label.to_string(),
+                    |section| section.label.text(language.code()).to_string(),
+                );
+                let header = row![
+                    container(text(title).size(18)).width(Length::Fill),
+                    button(text(tr!(language, "wizard-edit")).size(14))
+                        .on_press(on_message(InputMessage::WizardStepSelected(step)))
+                        .padding(layout.button_padding(6))
+                        .style(button::secondary),
+                ]
+                .align_y(Alignment::Center);
+
+                let entries = fields.into_iter().map(|input_field| {
+                    let value = state
+                        .inputs
+                        .get(&input_field.field)
+                        .and_then(|value| describe_input(input_field, value));
+                    let value: Element<'a, Message> = match value {
+                        Some(value) => text(value.text(language.code()).to_string())
+                            .size(14)
+                            .into(),
+                        None if input_field.required => text(missing_label.clone())
+                            .size(14)
+                            .color(missing_color)
+                            .into(),
+                        None => text("–").size(14).into(),
+                    };
+                    row![
+                        container(text(input_field.label.text(language.code())).size(14))
+                            .width(Length::Fill),
+                        value,
+                    ]
+                    .spacing(10)
+                    .into()
+                });
+
+                column![header, column(entries).spacing(4)]
+                    .spacing(8)
+                    .into()
+            });
+
+    column(sections).spacing(20).into()
+}
+
+/// Running point total of the inputs so far and the interpretation band it falls into
+///
+/// Empty for formula-based scores, which have no running total.
+fn subtotal_bar<'a, Message: 'a>(
+    score: &'a ScoreDefinition,
+    state: &ScoreInputState,
+    language: Language,
+    palette: RiskPalette,
+) -> Element<'a, Message> {
+    let Some(partial) = calculate_partial(score, &state.inputs) else {
+        return column![].into();
+    };
+
+    let mut bar = row![text(tr!(
+        language,
+        "subtotal",
+        points = partial.subtotal.to_string()
+    ))
+    .size(16)]
+    .spacing(10)
+    .align_y(Alignment::Center);
+
+    if let Some(rule) = partial.interpretation {
+        let (r, g, b) = rule.risk_level.rgb_in(palette);
+        bar = bar.push(
+            text(format!(
+                "→ {} {} ({})",
+                rule.risk_level.icon(),
+                rule.risk.text(language.code()),
+                i18n::risk_level_name(language, rule.risk_level)
+            ))
+            .size(16)
+            .color(iced::Color::from_rgb(r, g, b)),
+        );
+    }
+
+    if partial.missing_required > 0 {
+        let missing = tr!(language, "required-open", count = partial.missing_required);
+        bar = bar.push(text(missing).size(13));
+    }
+
+    container(bar)
+        .padding(10)
+        .width(Length::Fill)
+        .style(container::rounded_box)
+        .into()
+}
+
+/// Optional patient label above the form; empty if patient labels are disabled
+fn patient_label_input<'a, Message>(
+    state: &'a ScoreInputState,
+    language: Language,
+    layout: ScreenLayout,
+    on_message: impl Fn(InputMessage) -> Message + 'a + Copy,
+) -> Element<'a, Message>
+where
+    Message: Clone + 'a,
+{
+    let Some(patient_label) = &state.patient_label else {
+        return column![].into();
+    };
+
+    row![
+        text(tr!(language, "patient-label")).size(14),
+        text_input(&tr!(language, "patient-placeholder"), patient_label)
+            .on_input(move |value| on_message(InputMessage::PatientLabelChanged(value)))
+            .padding(layout.button_padding(6))
+            .width(layout.fixed_width(260.0))
+            .style(layout.text_input_style()),
+    ]
+    .spacing(10)
+    .align_y(Alignment::Center)
+    .into()
+}
+
+/// Patient ID and button to prefill the form from the FHIR server; empty unless
+/// prefilling is available
+fn fhir_prefill_input<'a, Message>(
+    state: &'a ScoreInputState,
+    language: Language,
+    layout: ScreenLayout,
+    on_message: impl Fn(InputMessage) -> Message + 'a + Copy,
+) -> Element<'a, Message>
+where
+    Message: Clone + 'a,
+{
+    let Some(patient_id) = &state.fhir_patient_id else {
+        return column![].into();
+    };
+
+    let prefill = button(text(tr!(language, "fhir-prefill")).size(14))
+        .on_press_maybe(
+            (!patient_id.trim().is_empty()).then(|| on_message(InputMessage::PrefillFromFhir)),
+        )
+        .padding(layout.button_padding(6));
+    column![row![
+        text(tr!(language, "fhir-patient-id")).size(14),
+        text_input(&tr!(language, "fhir-patient-placeholder"), patient_id)
+            .on_input(move |value| on_message(InputMessage::FhirPatientIdChanged(value)))
+            .on_submit(on_message(InputMessage::PrefillFromFhir))
+            .padding(layout.button_padding(6))
+            .width(layout.fixed_width(200.0))
+            .style(layout.text_input_style()),
+        prefill,
+    ]
+    .spacing(10)
+    .align_y(Alignment::Center)]
+    .push_maybe(
+        state
+            .fhir_status
+            .as_deref()
+            .map(|status| text(status).size(13)),
+    )
+    .spacing(6)
+    .into()
+}
+
+/// Button switching between the whole form and the wizard
+fn wizard_toggle<'a, Message>(
+    wizard: bool,
+    language: Language,
+    layout: ScreenLayout,
+    on_message: impl Fn(InputMessage) -> Message + 'a + Copy,
+) -> Element<'a, Message>
+where
+    Message: Clone + 'a,
+{
+    let label = if wizard {
+        tr!(language, "wizard-off")
+    } else {
+        tr!(language, "wizard-on")
+    };
+
+    button(text(label).size(14))
+        .on_press(on_message(InputMessage::WizardToggled))
+        .padding(layout.button_padding(6))
+        .style(button::text)
+        .into()
+}
+
+/// Header button for a collapsible form section
+fn section_header<'a, Message>(
+    section: &'a InputSection,
+    field_count: usize,
+    collapsed: bool,
+    language: Language,
+    layout: ScreenLayout,
+    on_message: impl Fn(InputMessage) -> Message + 'a + Copy,
+) -> Element<'a, Message>
+where
+    Message: Clone + 'a,
+{
+    let label = section.label.text(language.code());
+
+    let marker = if collapsed { "▶" } else { "▼" };
+
+    button(text(format!("{} {} ({})", marker, label, field_count)).size(18))
+        .on_press(on_message(InputMessage::SectionToggled(section.id.clone())))
+        .padding(layout.button_padding(8))
+        .width(Length::Fill)
+        .style(button::secondary)
+        .into()
+}
+
 /// Generate a single input widget based on field type
 fn generate_input_widget<'a, Message>(
     field: &'a InputField,
     state: &'a ScoreInputState,
     language: Language,
+    layout: ScreenLayout,
+    show_help: bool,
     on_message: impl Fn(InputMessage) -> Message + 'a + Copy,
 ) -> Element<'a, Message>
 where
     Message: Clone + 'a,
 {
-    let label_text = match language {
-        Language::German => &field.label_de,
-        Language::English => &field.label,
-    };
-
-    let unit_text = field.unit.as_ref().and_then(|_| match language {
-        Language::German => field.unit_de.as_ref().or(field.unit.as_ref()),
-        Language::English => field.unit.as_ref(),
-    });
+    let label_text = field.label.text(language.code());
+    let unit_text = field
+        .unit_in(state.unit_system)
+        .map(|unit| unit.text(language.code()));
 
     let label_with_unit = if let Some(unit) = unit_text {
         format!("{} ({})", label_text, unit)
@@ -161,6 +884,21 @@ where
         label_text.to_string()
     };
 
+    let help = field
+        .help
+        .as_ref()
+        .map(|help| help.text(language.code()))
+        .filter(|help| show_help && !help.trim().is_empty());
+    let label_row = |label: Element<'a, Message>| -> Element<'a, Message> {
+        match help {
+            Some(help) => row![label, help_icon(help)]
+                .spacing(8)
+                .align_y(Alignment::Center)
+                .into(),
+            None => label,
+        }
+    };
+
     match field.input_type {
         InputType::Boolean => {
             let is_checked = state
@@ -170,11 +908,50 @@ where
                 .unwrap_or(false);
 
             let field_name = field.field.clone();
-            let cb = checkbox(label_with_unit, is_checked).on_toggle(move |checked| {
-                on_message(InputMessage::BooleanChanged(field_name.clone(), checked))
-            });
+            let cb = checkbox(label_with_unit, is_checked)
+                .on_toggle(move |checked| {
+                    on_message(InputMessage::BooleanChanged(field_name.clone(), checked))
+                })
+                .size(layout.toggle_size(16.0));
 
-            container(cb).padding(10).into()
+            container(label_row(cb.into())).padding(10).into()
+        }
+
+        InputType::Number if field.effective_widget() == Some(InputWidget::Slider) => {
+            // Validation guarantees both bounds for sliders
+            let min = field.min.unwrap_or(0.0);
+            let max = field.max.unwrap_or(min + 1.0);
+            let value = state
+                .inputs
+                .get(&field.field)
+                .and_then(|v| v.number_in(field));
+
+            let field_name = field.field.clone();
+            let control = slider(min..=max, value.unwrap_or(min), move |v: f64| {
+                on_message(InputMessage::NumberTextChanged(
+                    field_name.clone(),
+                    v.to_string(),
+                ))
+            })
+            .step(1.0)
+            .height(layout.toggle_size(16.0))
+            .width(layout.fixed_width(320.0));
+
+            // Until moved, the slider rests at `min` without setting a value
+            let display = match value {
+                Some(v) => v.to_string(),
+                None => "–".to_string(),
+            };
+
+            column![
+                label_row(text(label_with_unit).size(16).into()),
+                row![control, text(display).size(16)]
+                    .spacing(15)
+                    .align_y(Alignment::Center),
+            ]
+            .spacing(5)
+            .padding(10)
+            .into()
         }
 
         InputType::Number => {
@@ -184,11 +961,15 @@ where
                 .cloned()
                 .unwrap_or_default();
 
-            let placeholder = if let (Some(min), Some(max)) = (field.min, field.max) {
+            // Bounds are in SI units; show them in the unit being typed
+            let factor = field.unit_factor(state.unit_system);
+            let bound = |value: f64| ((value / factor) * 100.0).round() / 100.0;
+            let (min, max) = (field.min.map(bound), field.max.map(bound));
+            let placeholder = if let (Some(min), Some(max)) = (min, max) {
                 format!("{} - {}", min, max)
-            } else if let Some(min) = field.min {
+            } else if let Some(min) = min {
                 format!(">= {}", min)
-            } else if let Some(max) = field.max {
+            } else if let Some(max) = max {
                 format!("<= {}", max)
             } else {
                 String::new()
@@ -199,28 +980,172 @@ where
                 .on_input(move |value| {
                     on_message(InputMessage::NumberTextChanged(field_name.clone(), value))
                 })
-                .padding(8)
-                .width(Length::Fixed(200.0));
+                .padding(layout.button_padding(8))
+                .width(Length::Fixed(200.0))
+                .style(layout.text_input_style());
 
-            column![text(label_with_unit).size(16), input,]
+            let mut widget = column![label_row(text(label_with_unit).size(16).into()), input,]
                 .spacing(5)
-                .padding(10)
+                .padding(10);
+            if state.invalid_numbers.contains(&field.field) {
+                widget = widget.push(
+                    text(tr!(language, "invalid-number"))
+                        .size(13)
+                        .color(iced::Color::from_rgb(0.8, 0.1, 0.1)),
+                );
+            }
+            widget.into()
+        }
+
+        InputType::Duration => {
+            let text_value = state
+                .text_buffers
+                .get(&field.field)
+                .cloned()
+                .unwrap_or_default();
+            // The unit the duration is scored in, unless the score file names one
+            let label = match (unit_text, field.duration_unit) {
+                (None, Some(unit)) => {
+                    format!("{} ({})", label_text, unit.label().text(language.code()))
+                }
+                _ => label_with_unit,
+            };
+
+            let field_name = field.field.clone();
+            let input = text_input(&tr!(language, "duration-placeholder"), &text_value)
+                .on_input(move |value| {
+                    on_message(InputMessage::DurationTextChanged(field_name.clone(), value))
+                })
+                .padding(layout.button_padding(8))
+                .width(Length::Fixed(200.0))
+                .style(layout.text_input_style());
+
+            let mut widget = column![label_row(text(label).size(16).into()), input]
+                .spacing(5)
+                .padding(10);
+            if state.invalid_durations.contains(&field.field) {
+                widget = widget.push(
+                    text(tr!(language, "invalid-duration"))
+                        .size(13)
+                        .color(iced::Color::from_rgb(0.8, 0.1, 0.1)),
+                );
+            } else if let (Some(duration), Some(unit)) = (
+                state.inputs.get(&field.field).and_then(|v| v.as_number()),
+                field.duration_unit,
+            ) {
+                // How the typed text was read, e.g. "= 4 h 30 min" for "4,5"
+                widget =
+                    widget.push(text(format!("= {}", format_duration(duration, unit))).size(13));
+            }
+            widget.into()
+        }
+
+        InputType::Date => {
+            let text_value = state
+                .text_buffers
+                .get(&field.field)
+                .cloned()
+                .or_else(|| {
+                    let date = state.inputs.get(&field.field)?.as_date()?;
+                    Some(format_date(date, language))
+                })
+                .unwrap_or_default();
+
+            let field_name = field.field.clone();
+            let input = text_input(&tr!(language, "date-placeholder"), &text_value)
+                .on_input(move |value| {
+                    on_message(InputMessage::DateTextChanged(field_name.clone(), value))
+                })
+                .padding(layout.button_padding(8))
+                .width(Length::Fixed(200.0))
+                .style(layout.text_input_style());
+            let today_button = button(text(tr!(language, "date-today")).size(14))
+                .on_press(on_message(InputMessage::DateSelected(
+                    field.field.clone(),
+                    today(),
+                )))
+                .padding(layout.button_padding(8))
+                .style(button::secondary);
+
+            // The unit belongs to the elapsed time shown below, not to the date
+            let mut widget = column![
+                label_row(text(label_text).size(16).into()),
+                row![input, today_button]
+                    .spacing(10)
+                    .align_y(Alignment::Center),
+            ]
+            .spacing(5)
+            .padding(10);
+            let error_color = iced::Color::from_rgb(0.8, 0.1, 0.1);
+            if state.invalid_dates.contains(&field.field) {
+                widget = widget.push(
+                    text(tr!(language, "invalid-date"))
+                        .size(13)
+                        .color(error_color),
+                );
+            } else if let Some(elapsed) = &field.elapsed {
+                match elapsed_time(field, &state.inputs) {
+                    Ok(Some(time)) => {
+                        let time = match unit_text {
+                            Some(unit) => format!("{} {}", time, unit),
+                            None => tr!(language, elapsed_message(elapsed.unit), count = time),
+                        };
+                        widget = widget.push(text(format!("= {}", time)).size(13));
+                    }
+                    Err(CalculationError::InvalidInput { .. }) => {
+                        widget = widget.push(
+                            text(tr!(language, "date-after-reference"))
+                                .size(13)
+                                .color(error_color),
+                        );
+                    }
+                    _ => {}
+                }
+            }
+            widget.into()
+        }
+
+        InputType::Dropdown if field.effective_widget() == Some(InputWidget::Radio) => {
+            let selected = state
+                .inputs
+                .get(&field.field)
+                .and_then(|v| v.as_string())
+                .and_then(|value| field.options.iter().position(|o| o.value == value));
+
+            // Every option with its points, so the choice is visible at a glance
+            let buttons = field.options.iter().enumerate().map(|(i, opt)| {
+                let label = format!(
+                    "{} ({})",
+                    opt.label.text(language.code()),
+                    format_signed_points(opt.points)
+                );
+                radio(label, i, selected, |_| {
+                    on_message(InputMessage::DropdownSelected(
+                        field.field.clone(),
+                        opt.value.clone(),
+                    ))
+                })
+                .size(layout.toggle_size(16.0))
+                .text_size(14)
                 .into()
+            });
+
+            column![
+                label_row(text(label_with_unit).size(16).into()),
+                column(buttons).spacing(8),
+            ]
+            .spacing(8)
+            .padding(10)
+            .into()
         }
 
         InputType::Dropdown => {
             let options: Vec<DropdownItem> = field
                 .options
                 .iter()
-                .map(|opt| {
-                    let display = match language {
-                        Language::German => &opt.label_de,
-                        Language::English => &opt.label,
-                    };
-                    DropdownItem {
-                        value: opt.value.clone(),
-                        display: display.clone(),
-                    }
+                .map(|opt| DropdownItem {
+                    value: opt.value.clone(),
+                    display: opt.label.text(language.code()).to_string(),
                 })
                 .collect();
 
@@ -233,10 +1158,7 @@ where
                 });
 
             let field_name = field.field.clone();
-            let placeholder = match language {
-                Language::German => "Bitte auswählen...",
-                Language::English => "Please select...",
-            };
+            let placeholder = tr!(language, "select-placeholder");
 
             let picker = pick_list(options, selected, move |item: DropdownItem| {
                 on_message(InputMessage::DropdownSelected(
@@ -245,12 +1167,179 @@ where
                 ))
             })
             .placeholder(placeholder)
-            .width(Length::Fixed(400.0));
+            .padding(layout.button_padding(5))
+            .width(layout.fixed_width(400.0));
 
-            column![text(label_with_unit).size(16), picker,]
+            column![label_row(text(label_with_unit).size(16).into()), picker,]
                 .spacing(5)
                 .padding(10)
                 .into()
         }
     }
 }
+
+/// Info icon that shows a field's help text as a tooltip
+fn help_icon<'a, Message: 'a>(help: &'a str) -> Element<'a, Message> {
+    tooltip(
+        text("ⓘ").size(16),
+        container(text(help).size(13))
+            .padding(8)
+            .max_width(360)
+            .style(container::rounded_box),
+        tooltip::Position::Top,
+    )
+    .gap(4)
+    .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_decimal() {
+        assert_eq!(parse_decimal("1,36", Language::German), Some(1.36));
+        assert_eq!(parse_decimal("1.36", Language::German), Some(1.36));
+        assert_eq!(parse_decimal("1.36", Language::English), Some(1.36));
+        assert_eq!(parse_decimal(" -2 ", Language::English), Some(-2.0));
+        assert_eq!(
+            parse_decimal("10\u{2009}000", Language::German),
+            Some(10000.0)
+        );
+        assert_eq!(
+            parse_decimal("10\u{202F}000,5", Language::German),
+            Some(10000.5)
+        );
+
+        assert_eq!(parse_decimal("1,36", Language::English), None);
+        assert_eq!(parse_decimal("1.234,5", Language::German), None);
+        assert_eq!(parse_decimal("1.2.3", Language::English), None);
+        assert_eq!(parse_decimal("abc", Language::German), None);
+        assert_eq!(parse_decimal("inf", Language::English), None);
+        assert_eq!(parse_decimal("1e3", Language::English), None);
+        assert_eq!(parse_decimal("-", Language::English), None);
+        assert_eq!(parse_decimal(&"9".repeat(400), Language::English), None);
+    }
+
+    #[test]
+    fn test_invalid_number_clears_value() {
+        let mut state = ScoreInputState::new();
+        let field = "creatinine".to_string();
+
+        state.update_number_text(field.clone(), "1,36".to_string(), Language::German);
+        assert_eq!(state.inputs[&field].as_number(), Some(1.36));
+
+        // No stale value once the text stops parsing
+        state.update_number_text(field.clone(), "1,36".to_string(), Language::English);
+        assert!(!state.inputs.contains_key(&field));
+        assert!(state.invalid_numbers.contains(&field));
+
+        state.update_number_text(field.clone(), String::new(), Language::English);
+        assert!(state.invalid_numbers.is_empty());
+    }
+
+    #[test]
+    fn test_duration_text() {
+        let mut state = ScoreInputState::new();
+        let field = "onset".to_string();
+        state
+            .duration_units
+            .insert(field.clone(), DurationUnit::Hours);
+
+        state.update_duration_text(field.clone(), "4:30".to_string());
+        assert_eq!(state.inputs[&field], InputValue::Number(4.5));
+
+        state.update_duration_text(field.clone(), "4:75".to_string());
+        assert!(!state.inputs.contains_key(&field));
+        assert!(state.invalid_durations.contains(&field));
+
+        state.set_duration(&field, 1.25);
+        assert_eq!(state.text_buffers[&field], "1 h 15 min");
+        assert!(state.invalid_durations.is_empty());
+    }
+
+    #[test]
+    fn test_with_inputs_restores_form() {
+        let score = crate::scores::load_score_from_file("scores/cardiology/cha2ds2_va.yaml")
+            .expect("Failed to load CHA2DS2-VA");
+        let inputs = HashMap::from([
+            ("age".to_string(), InputValue::Number(72.5)),
+            ("hypertension".to_string(), InputValue::Boolean(true)),
+            ("removed_field".to_string(), InputValue::Boolean(true)),
+        ]);
+
+        let state = ScoreInputState::with_inputs(&score, &inputs, Language::German, UnitSystem::Si);
+        assert_eq!(state.inputs["age"], InputValue::Number(72.5));
+        assert_eq!(state.text_buffers["age"], "72,5");
+        assert_eq!(state.inputs["hypertension"], InputValue::Boolean(true));
+        assert!(!state.inputs.contains_key("removed_field"));
+
+        // History files store the plain values
+        let json = serde_json::to_string(&inputs["age"]).unwrap();
+        assert_eq!(json, "72.5");
+        let parsed: HashMap<String, InputValue> =
+            serde_json::from_str(r#"{"age": 72, "hypertension": true, "nyha": "class_ii"}"#)
+                .unwrap();
+        assert_eq!(parsed["age"], InputValue::Number(72.0));
+        assert_eq!(parsed["nyha"], InputValue::Dropdown("class_ii".to_string()));
+    }
+
+    #[test]
+    fn test_conventional_units_are_converted() {
+        let score = crate::scores::load_score_from_file("scores/nephrology/egfr_ckd_epi_2021.yaml")
+            .expect("Failed to load eGFR");
+        let mut state = ScoreInputState::for_score(&score, UnitSystem::Conventional);
+
+        // 1,2 mg/dL is scored as 106,08 μmol/L
+        state.update_number_text(
+            "creatinine".to_string(),
+            "1,2".to_string(),
+            Language::German,
+        );
+        // Kept as typed; the calculation converts it
+        let creatinine = state.inputs["creatinine"].clone();
+        assert_eq!(
+            creatinine,
+            InputValue::Quantity(Quantity {
+                value: 1.2,
+                unit: "mg/dL".to_string(),
+            })
+        );
+        let field = score
+            .inputs
+            .iter()
+            .find(|i| i.field == "creatinine")
+            .unwrap();
+        assert!((creatinine.number_in(field).unwrap() - 106.08).abs() < 1e-9);
+        state.update_number_text("age".to_string(), "60".to_string(), Language::German);
+        assert_eq!(state.inputs["age"], InputValue::Number(60.0));
+
+        // Switching units keeps the values and shows them in the new unit
+        state.set_unit_system(&score, UnitSystem::Si, Language::English);
+        assert_eq!(state.text_buffers["creatinine"], "106.08");
+        assert_eq!(state.text_buffers["age"], "60");
+
+        let inputs = HashMap::from([("creatinine".to_string(), InputValue::Number(100.0))]);
+        let state = ScoreInputState::with_inputs(
+            &score,
+            &inputs,
+            Language::English,
+            UnitSystem::Conventional,
+        );
+        assert_eq!(state.text_buffers["creatinine"], "1.13");
+        assert_eq!(state.inputs["creatinine"], InputValue::Number(100.0));
+
+        // Quantities from the history are shown as typed, or converted to SI
+        let typed = HashMap::from([("creatinine".to_string(), creatinine.clone())]);
+        let state = ScoreInputState::with_inputs(
+            &score,
+            &typed,
+            Language::German,
+            UnitSystem::Conventional,
+        );
+        assert_eq!(state.text_buffers["creatinine"], "1,2");
+        assert_eq!(state.inputs["creatinine"], creatinine);
+        let state = ScoreInputState::with_inputs(&score, &typed, Language::English, UnitSystem::Si);
+        assert_eq!(state.text_buffers["creatinine"], "106.08");
+    }
+}
@@ -2,79 +2,69 @@
 // UI for selecting medical specialty
 
 use crate::config::Specialty;
+use crate::tr;
+use crate::ui::{i18n, ScreenLayout};
 use iced::{
     widget::{button, column, container, text},
     Alignment, Element, Length,
 };
 
-/// Create the specialty selection view
+/// Create the specialty selection view with one button per specialty,
+/// showing how many scores each specialty has
 pub fn specialty_selection_view<'a, Message>(
     language: Language,
+    layout: ScreenLayout,
+    specialties: &[(Specialty, usize)],
     on_select: impl Fn(Specialty) -> Message + 'a,
     on_back: Message,
 ) -> Element<'a, Message>
 where
     Message: Clone + 'a,
 {
-    let title = match language {
-        Language::German => "Fachgebiet auswählen",
-        Language::English => "Select Medical Specialty",
-    };
-
-    let subtitle = match language {
-        Language::German => "Wählen Sie das Fachgebiet für die Score-Berechnung",
-        Language::English => "Choose the medical specialty for score calculation",
-    };
+    let title = tr!(language, "specialty-selection-title");
+    let subtitle = tr!(language, "specialty-selection-subtitle");
 
     // Specialty buttons
-    let specialties = vec![
-        (Specialty::Cardiology, "🫀", "Cardiology", "Kardiologie"),
-        (Specialty::Nephrology, "🩺", "Nephrology", "Nephrologie"),
-        (
-            Specialty::Anesthesiology,
-            "💉",
-            "Anesthesiology",
-            "Anästhesiologie",
-        ),
-    ];
-
     let buttons: Vec<Element<'a, Message>> = specialties
-        .into_iter()
-        .map(|(specialty, icon, name_en, name_de)| {
-            let label = match language {
-                Language::German => format!("{} {}", icon, name_de),
-                Language::English => format!("{} {}", icon, name_en),
-            };
+        .iter()
+        .map(|&(specialty, count)| {
+            let label = format!(
+                "{} {}",
+                specialty.icon(),
+                i18n::specialty_name(language, specialty)
+            );
+            let count_label = tr!(language, "scores-count", count = count);
 
-            button(text(label).size(24))
+            button(column![text(label).size(24), text(count_label).size(14)].spacing(4))
                 .on_press(on_select(specialty))
                 .padding(20)
-                .width(Length::Fixed(350.0))
+                .width(layout.fixed_width(350.0))
                 .into()
         })
         .collect();
 
-    let specialty_buttons: Element<'a, Message> = column(buttons)
-        .spacing(15)
-        .align_x(Alignment::Center)
-        .into();
-
-    let back_label = match language {
-        Language::German => "← Zurück",
-        Language::English => "← Back",
+    let specialty_buttons: Element<'a, Message> = if buttons.is_empty() {
+        text(tr!(language, "no-scores-loaded")).size(16).into()
+    } else {
+        column(buttons)
+            .spacing(15)
+            .align_x(Alignment::Center)
+            .into()
     };
 
+    let back_label = tr!(language, "back");
+
     let content = column![
         text(title).size(36),
         text(subtitle).size(16),
         specialty_buttons,
         button(text(back_label).size(18))
             .on_press(on_back)
-            .padding(10),
+            .padding(layout.button_padding(10)),
     ]
     .spacing(30)
     .align_x(Alignment::Center)
-    .padding(50);
+    .padding(layout.padding(50));
 
     container(content)
         .width(Length::Fill)
@@ -83,8 +73,55 @@ where
 }
 
 /// Language setting for UI
-#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
 pub enum Language {
     German,
     English,
+    French,
+    Spanish,
+    Italian,
+}
+
+impl Language {
+    /// All UI languages, in the order of the language picker
+    pub const ALL: [Language; 5] = [
+        Language::German,
+        Language::English,
+        Language::French,
+        Language::Spanish,
+        Language::Italian,
+    ];
+
+    /// Language code used to look up translations in score definitions
+    pub fn code(self) -> &'static str {
+        match self {
+            Language::German => "de",
+            Language::English => "en",
+            Language::French => "fr",
+            Language::Spanish => "es",
+            Language::Italian => "it",
+        }
+    }
+
+    /// Name of the language in the language itself, for the language picker
+    pub fn native_name(self) -> &'static str {
+        match self {
+            Language::German => "Deutsch",
+            Language::English => "English",
+            Language::French => "Français",
+            Language::Spanish => "Español",
+            Language::Italian => "Italiano",
+        }
+    }
+
+    /// Whether numbers are written with a decimal comma (1,36)
+    pub fn decimal_comma(self) -> bool {
+        self != Language::English
+    }
+}
+
+impl std::fmt::Display for Language {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.native_name())
+    }
 }
@@ -0,0 +1,86 @@
+// toast.rs
+// Dismissible notifications shown above the main content
+
+use super::i18n::{self, FluentArgs, FluentValue};
+use super::Language;
+use iced::{
+    widget::{button, column, container, row, text},
+    Alignment, Element, Length,
+};
+
+/// A notification that stays visible until dismissed. It keeps the message id
+/// and its variables, so it follows a change of the UI language.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Toast {
+    pub message_id: String,
+    pub args: Vec<(String, FluentValue<'static>)>,
+}
+
+impl Toast {
+    pub fn new(message_id: impl Into<String>) -> Self {
+        Self {
+            message_id: message_id.into(),
+            args: Vec::new(),
+        }
+    }
+
+    /// Set the variable `name` of the message
+    pub fn with_arg(mut self, name: &str, value: impl Into<FluentValue<'static>>) -> Self {
+        self.args.push((name.to_string(), value.into()));
+        self
+    }
+
+    pub fn text(&self, language: Language) -> String {
+        let args: FluentArgs = self
+            .args
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.clone()))
+            .collect();
+        i18n::message_with(language, &self.message_id, &args)
+    }
+}
+
+/// Render a stack of toasts; `on_dismiss` receives the index of the closed toast
+pub fn toast_stack_view<'a, Message>(
+    toasts: &'a [Toast],
+    language: Language,
+    on_dismiss: impl Fn(usize) -> Message + 'a,
+) -> Element<'a, Message>
+where
+    Message: Clone + 'a,
+{
+    let items: Vec<Element<'a, Message>> = toasts
+        .iter()
+        .enumerate()
+        .map(|(index, toast)| {
+            container(
+                row![
+                    text(toast.text(language)).size(14).width(Length::Fill),
+                    button(text("✕").size(14))
+                        .on_press(on_dismiss(index))
+                        .padding(4)
+                        .style(button::text),
+                ]
+                .spacing(10)
+                .align_y(Alignment::Center),
+            )
+            .padding(10)
+            .width(Length::Fill)
+            .style(|theme: &iced::Theme| {
+                let accent = theme.palette().danger;
+                container::Style {
+                    background: Some(iced::Background::Color(iced::Color { a: 0.1, ..accent })),
+                    border: iced::Border {
+                        color: accent,
+                        width: 1.0,
+                        radius: 6.0.into(),
+                    },
+                    ..Default::default()
+                }
+            })
+            .into()
+        })
+        .collect();
+
+    column(items).spacing(8).into()
+}
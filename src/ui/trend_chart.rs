@@ -0,0 +1,199 @@
+// trend_chart.rs
+// Small line chart of a value over time (e.g., eGFR of one patient)
+
+use iced::alignment;
+use iced::mouse;
+use iced::widget::canvas::{self, Canvas, Frame, Geometry, Path, Stroke, Text};
+use iced::{Element, Length, Pixels, Point, Rectangle, Renderer, Size, Theme};
+
+/// One value of a trend
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrendPoint {
+    /// Time of the value in seconds; only differences matter
+    pub time: i64,
+    pub value: f64,
+    /// Shown below the first and last point (e.g., the date)
+    pub label: String,
+}
+
+/// Space left of the plot for the value axis and below it for the dates
+const AXIS_WIDTH: f32 = 40.0;
+const LABEL_HEIGHT: f32 = 16.0;
+const DOT_RADIUS: f32 = 3.0;
+
+/// Line chart of `points` (oldest first), spaced by time
+pub fn trend_chart<'a, Message: 'a>(points: Vec<TrendPoint>) -> Element<'a, Message> {
+    Canvas::new(TrendChart { points })
+        .width(Length::Fill)
+        .height(110)
+        .into()
+}
+
+struct TrendChart {
+    points: Vec<TrendPoint>,
+}
+
+/// Value range of the axis; a constant value gets a range of ±1 around it
+fn value_range(points: &[TrendPoint]) -> (f64, f64) {
+    let min = points.iter().map(|p| p.value).fold(f64::INFINITY, f64::min);
+    let max = points
+        .iter()
+        .map(|p| p.value)
+        .fold(f64::NEG_INFINITY, f64::max);
+    if min < max {
+        (min, max)
+    } else {
+        (min - 1.0, max + 1.0)
+    }
+}
+
+/// Position of each point inside `plot`: time on x, value on y (larger values higher up)
+fn plot_positions(points: &[TrendPoint], plot: Rectangle) -> Vec<Point> {
+    let (min, max) = value_range(points);
+    let first = points.first().map_or(0, |p| p.time);
+    let last = points.last().map_or(0, |p| p.time);
+    let span = (last - first).max(1) as f32;
+    points
+        .iter()
+        .enumerate()
+        .map(|(index, point)| {
+            // Points at the same time (or a single point) are spread evenly
+            let x = if last > first {
+                (point.time - first) as f32 / span
+            } else {
+                index as f32 / (points.len().max(2) - 1) as f32
+            };
+            let y = ((point.value - min) / (max - min)) as f32;
+            Point::new(plot.x + x * plot.width, plot.y + (1.0 - y) * plot.height)
+        })
+        .collect()
+}
+
+impl<Message> canvas::Program<Message> for TrendChart {
+    type State = ();
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &Renderer,
+        theme: &Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<Geometry> {
+        let mut frame = Frame::new(renderer, bounds.size());
+        let palette = theme.extended_palette();
+        let text_color = palette.background.base.text;
+        let plot = Rectangle::new(
+            Point::new(AXIS_WIDTH, DOT_RADIUS + 6.0),
+            Size::new(
+                (bounds.width - AXIS_WIDTH - DOT_RADIUS - 30.0).max(1.0),
+                (bounds.height - LABEL_HEIGHT - DOT_RADIUS - 12.0).max(1.0),
+            ),
+        );
+
+        // Value axis: a faint line at the lowest and highest value
+        let (min, max) = value_range(&self.points);
+        let grid = Stroke::default()
+            .with_width(1.0)
+            .with_color(palette.background.strong.color);
+        for (value, y) in [(max, plot.y), (min, plot.y + plot.height)] {
+            frame.stroke(
+                &Path::line(Point::new(plot.x, y), Point::new(plot.x + plot.width, y)),
+                grid,
+            );
+            frame.fill_text(Text {
+                content: format!("{:.0}", value),
+                position: Point::new(AXIS_WIDTH - 6.0, y),
+                color: text_color,
+                size: Pixels(11.0),
+                horizontal_alignment: alignment::Horizontal::Right,
+                vertical_alignment: alignment::Vertical::Center,
+                ..Text::default()
+            });
+        }
+
+        let positions = plot_positions(&self.points, plot);
+        let line = Path::new(|builder| {
+            for (index, position) in positions.iter().enumerate() {
+                if index == 0 {
+                    builder.move_to(*position);
+                } else {
+                    builder.line_to(*position);
+                }
+            }
+        });
+        frame.stroke(
+            &line,
+            Stroke::default()
+                .with_width(2.0)
+                .with_color(palette.primary.strong.color),
+        );
+        for position in &positions {
+            frame.fill(
+                &Path::circle(*position, DOT_RADIUS),
+                palette.primary.strong.color,
+            );
+        }
+
+        // Dates of the first and last value
+        let edges = [
+            (
+                self.points.first(),
+                positions.first(),
+                alignment::Horizontal::Left,
+            ),
+            (
+                self.points.last(),
+                positions.last(),
+                alignment::Horizontal::Right,
+            ),
+        ];
+        for (point, position, horizontal_alignment) in edges {
+            if let (Some(point), Some(position)) = (point, position) {
+                frame.fill_text(Text {
+                    content: point.label.clone(),
+                    position: Point::new(position.x, bounds.height - LABEL_HEIGHT),
+                    color: text_color,
+                    size: Pixels(11.0),
+                    horizontal_alignment,
+                    vertical_alignment: alignment::Vertical::Top,
+                    ..Text::default()
+                });
+            }
+        }
+
+        vec![frame.into_geometry()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(time: i64, value: f64) -> TrendPoint {
+        TrendPoint {
+            time,
+            value,
+            label: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_trend_positions() {
+        let plot = Rectangle::new(Point::ORIGIN, Size::new(100.0, 50.0));
+
+        // Spaced by time; the highest value at the top
+        let points = vec![point(0, 60.0), point(10, 45.0), point(40, 30.0)];
+        let positions = plot_positions(&points, plot);
+        assert_eq!(positions[0], Point::new(0.0, 0.0));
+        assert_eq!(positions[1], Point::new(25.0, 25.0));
+        assert_eq!(positions[2], Point::new(100.0, 50.0));
+
+        // A constant value sits in the middle
+        let flat = vec![point(0, 60.0), point(0, 60.0)];
+        assert_eq!(value_range(&flat), (59.0, 61.0));
+        let positions = plot_positions(&flat, plot);
+        assert_eq!(positions[0], Point::new(0.0, 25.0));
+        assert_eq!(positions[1], Point::new(100.0, 25.0));
+    }
+}
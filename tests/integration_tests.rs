@@ -3,8 +3,12 @@
 // Each score is tested with realistic clinical scenarios based on published literature
 
 use klinscore::config::Specialty;
-use klinscore::scores::{calculate_score, load_all_scores, InputValue};
-use std::collections::HashMap;
+use klinscore::export::ExportRecord;
+use klinscore::scores::validation::score_files_in;
+use klinscore::scores::{
+    calculate_score, interpretation_coverage, load_all_scores, load_all_scores_strict,
+    load_score_from_file, run_test_cases, verify_provenance, CalculationError, CalculationRequest,
+};
 
 // ============================================================
 // Library & Loading Tests
@@ -28,11 +32,75 @@ fn test_end_to_end_workflow() {
     let score = library
         .get_score("cha2ds2_va")
         .expect("CHA2DS2-VA score not found");
-    assert_eq!(score.name, "CHA2DS2-VA Score");
+    assert_eq!(score.name.text("en"), "CHA2DS2-VA Score");
     assert_eq!(score.specialty, Specialty::Cardiology);
     assert_eq!(score.inputs.len(), 6);
 }
 
+#[test]
+fn test_library_loads_in_strict_mode() {
+    // Every shipped score file must be valid, not just skipped with a warning
+    let library = load_all_scores_strict("scores/").expect("Invalid score file in scores/");
+    assert_eq!(library.count(), 9);
+}
+
+#[test]
+fn test_score_test_cases() {
+    // Every shipped score carries validation cases, and all of them pass
+    let library = load_all_scores_strict("scores/").expect("Invalid score file in scores/");
+    for (id, score) in &library.scores {
+        assert!(!score.test_cases.is_empty(), "{} has no test cases", id);
+        let failures = run_test_cases(score);
+        assert!(
+            failures.is_empty(),
+            "{}: {}",
+            id,
+            failures
+                .iter()
+                .map(|failure| failure.to_string())
+                .collect::<Vec<_>>()
+                .join("; ")
+        );
+    }
+}
+
+#[test]
+fn test_score_files_round_trip() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let mut files = score_files_in("scores/").unwrap();
+    files.push("scores/templates/score_template.yaml".into());
+
+    for file in files {
+        let source = std::fs::read_to_string(&file).unwrap();
+        let score = load_score_from_file(&file).unwrap();
+        let written = score.to_yaml_preserving(&source).unwrap();
+
+        let copy = temp_dir.path().join(file.file_name().unwrap());
+        std::fs::write(&copy, &written).unwrap();
+        let reloaded = load_score_from_file(&copy).unwrap();
+        assert_eq!(
+            serde_yaml::to_value(&reloaded).unwrap(),
+            serde_yaml::to_value(&score).unwrap(),
+            "{}",
+            file.display()
+        );
+
+        // Writing again changes nothing
+        assert_eq!(reloaded.to_yaml_preserving(&written).unwrap(), written);
+    }
+
+    // Key order and comments of hand-authored files survive
+    let source = std::fs::read_to_string("scores/templates/score_template.yaml").unwrap();
+    let score = load_score_from_file("scores/templates/score_template.yaml").unwrap();
+    let written = score.to_yaml_preserving(&source).unwrap();
+    assert!(written.starts_with("# Clinical Score Definition Template\n"));
+    assert!(written.contains("# Name of the score\nname:\n  en: Your Score Name\n  de:"));
+    assert!(
+        written.contains("\n  default: null  # Preselected option value; ~ = user must choose\n")
+    );
+    assert!(written.contains("\n  min: 0.0  # Minimum allowed value\n"));
+}
+
 #[test]
 fn test_score_library_methods() {
     let library = load_all_scores("scores/").expect("Failed to load scores");
@@ -42,6 +110,14 @@ fn test_score_library_methods() {
     assert!(specialties.contains(&Specialty::Cardiology));
     assert!(specialties.contains(&Specialty::Nephrology));
     assert!(specialties.contains(&Specialty::Anesthesiology));
+    assert_eq!(
+        library.specialty_counts(),
+        vec![
+            (Specialty::Cardiology, 3),
+            (Specialty::Nephrology, 2),
+            (Specialty::Anesthesiology, 4),
+        ]
+    );
 
     let cardio_scores = library.get_scores_for_specialty(Specialty::Cardiology);
     assert_eq!(cardio_scores.len(), 3, "Should have 3 cardiology scores");
@@ -66,6 +142,83 @@ fn test_score_library_methods() {
     assert!(library.get_score("rcri").is_some());
     assert!(library.get_score("stop_bang").is_some());
     assert!(library.get_score("nonexistent_score").is_none());
+
+    for (score_id, score) in &library.scores {
+        let issues = interpretation_coverage(score);
+        assert!(issues.is_empty(), "{}: {:?}", score_id, issues);
+    }
+
+    assert_eq!(library.find_by_alias("chads-vasc"), vec!["cha2ds2_va"]);
+    assert_eq!(library.find_by_alias("Lee-Index"), vec!["rcri"]);
+    assert_eq!(library.find_by_alias("HAS-BLED Score"), vec!["has_bled"]);
+
+    for (score_id, score) in &library.scores {
+        assert!(
+            !score.references.is_empty(),
+            "{} has no references",
+            score_id
+        );
+        assert!(score.references.iter().all(|r| r.link().is_some()));
+    }
+}
+
+#[test]
+fn test_patient_copy_uses_lay_language() {
+    let library = load_all_scores("scores/").unwrap();
+    let score = library.get_score("cha2ds2_va").unwrap();
+
+    let inputs = CalculationRequest::new()
+        .number("age", 72.0)
+        .flag("hypertension");
+
+    let result = inputs.calculate(score).unwrap();
+
+    let clinician = ExportRecord::from_result(&result, score.name.text("de"), true);
+    assert!(!clinician.recommendation.is_empty());
+    assert!(!clinician.patient_copy);
+
+    let patient = ExportRecord::patient_copy(&result, score.name.text("de"), true);
+    assert!(patient.patient_copy);
+    assert!(patient.recommendation.is_empty());
+    assert!(patient.details.is_empty());
+    assert!(patient.risk.starts_with("Ihr Risiko"));
+    assert_eq!(patient.total_score, clinician.total_score);
+}
+
+#[test]
+fn test_patient_copy_falls_back_without_lay_text() {
+    let library = load_all_scores("scores/").unwrap();
+    let score = library.get_score("stop_bang").unwrap();
+
+    let inputs = CalculationRequest::new().flag("snoring");
+
+    let result = inputs.calculate(score).unwrap();
+    let patient = ExportRecord::patient_copy(&result, score.name.text("en"), false);
+    assert_eq!(patient.risk, result.risk.text("en"));
+    assert!(patient.recommendation.is_empty());
+}
+
+#[test]
+fn test_exported_result_verifies_against_definition() {
+    let library = load_all_scores("scores/").unwrap();
+    let score = library.get_score("cha2ds2_va").unwrap();
+    let request = CalculationRequest::new()
+        .number("age", 72.0)
+        .flag("hypertension");
+    let result = request.calculate(score).unwrap();
+
+    let record = ExportRecord::from_result(&result, score.name.text("en"), false);
+    let json = klinscore::export::json_export::export_to_json(&record).unwrap();
+    let imported: ExportRecord = serde_json::from_str(&json).unwrap();
+    let provenance = imported
+        .provenance
+        .expect("export carries the provenance hash");
+
+    let inputs = request.into();
+    assert!(verify_provenance(score, &inputs, &provenance));
+    let mut revised = score.clone();
+    revised.version = "2099-v1".to_string();
+    assert!(!verify_provenance(&revised, &inputs, &provenance));
 }
 
 // ============================================================
@@ -80,15 +233,15 @@ fn test_cha2ds2va_high_risk_patient() {
     let library = load_all_scores("scores/").unwrap();
     let score = library.get_score("cha2ds2_va").unwrap();
 
-    let mut inputs = HashMap::new();
-    inputs.insert("age".to_string(), InputValue::Number(72.0));
-    inputs.insert("heart_failure".to_string(), InputValue::Boolean(true));
-    inputs.insert("hypertension".to_string(), InputValue::Boolean(true));
+    let inputs = CalculationRequest::new()
+        .number("age", 72.0)
+        .flag("heart_failure")
+        .flag("hypertension");
 
-    let result = calculate_score(score, &inputs).unwrap();
+    let result = inputs.calculate(score).unwrap();
     assert_eq!(result.total_score, 3);
-    assert_eq!(result.risk, "Moderate-High");
-    assert!(result.recommendation.contains("anticoagulation"));
+    assert_eq!(result.risk.text("en"), "Moderate-High");
+    assert!(result.recommendation.text("en").contains("anticoagulation"));
 }
 
 #[test]
@@ -98,13 +251,12 @@ fn test_cha2ds2va_zero_risk_no_booleans() {
     let library = load_all_scores("scores/").unwrap();
     let score = library.get_score("cha2ds2_va").unwrap();
 
-    let mut inputs = HashMap::new();
-    inputs.insert("age".to_string(), InputValue::Number(50.0));
+    let inputs = CalculationRequest::new().number("age", 50.0);
     // No boolean fields provided = all unchecked = 0 points each
 
-    let result = calculate_score(score, &inputs).unwrap();
+    let result = inputs.calculate(score).unwrap();
     assert_eq!(result.total_score, 0);
-    assert_eq!(result.risk, "Low");
+    assert_eq!(result.risk.text("en"), "Low");
 }
 
 #[test]
@@ -114,17 +266,17 @@ fn test_cha2ds2va_max_score() {
     let library = load_all_scores("scores/").unwrap();
     let score = library.get_score("cha2ds2_va").unwrap();
 
-    let mut inputs = HashMap::new();
-    inputs.insert("age".to_string(), InputValue::Number(80.0));
-    inputs.insert("heart_failure".to_string(), InputValue::Boolean(true));
-    inputs.insert("hypertension".to_string(), InputValue::Boolean(true));
-    inputs.insert("diabetes".to_string(), InputValue::Boolean(true));
-    inputs.insert("stroke_tia".to_string(), InputValue::Boolean(true));
-    inputs.insert("vascular_disease".to_string(), InputValue::Boolean(true));
+    let inputs = CalculationRequest::new()
+        .number("age", 80.0)
+        .flag("heart_failure")
+        .flag("hypertension")
+        .flag("diabetes")
+        .flag("stroke_tia")
+        .flag("vascular_disease");
 
-    let result = calculate_score(score, &inputs).unwrap();
+    let result = inputs.calculate(score).unwrap();
     assert_eq!(result.total_score, 8);
-    assert_eq!(result.risk, "Moderate-High"); // ≥2 interpretation
+    assert_eq!(result.risk.text("en"), "Moderate-High"); // ≥2 interpretation
 }
 
 #[test]
@@ -141,10 +293,10 @@ fn test_cha2ds2va_age_boundaries() {
     ];
 
     for (age, expected_points) in test_cases {
-        let mut inputs = HashMap::new();
-        inputs.insert("age".to_string(), InputValue::Number(age));
+        let inputs = CalculationRequest::new().number("age", age);
 
-        let result = calculate_score(score, &inputs)
+        let result = inputs
+            .calculate(score)
             .unwrap_or_else(|e| panic!("Calculation failed for age {}: {}", age, e));
         assert_eq!(
             result.get_field_points("age"),
@@ -161,8 +313,8 @@ fn test_cha2ds2va_missing_age_fails() {
     let score = library.get_score("cha2ds2_va").unwrap();
 
     // Age is required - should fail without it
-    let inputs = HashMap::new();
-    let result = calculate_score(score, &inputs);
+    let inputs = CalculationRequest::new();
+    let result = inputs.calculate(score);
     assert!(result.is_err(), "Should fail with missing required age");
 }
 
@@ -177,10 +329,10 @@ fn test_has_bled_low_risk() {
     let library = load_all_scores("scores/").unwrap();
     let score = library.get_score("has_bled").unwrap();
 
-    let inputs = HashMap::new(); // All booleans unchecked = 0
-    let result = calculate_score(score, &inputs).unwrap();
+    let inputs = CalculationRequest::new(); // All booleans unchecked = 0
+    let result = inputs.calculate(score).unwrap();
     assert_eq!(result.total_score, 0);
-    assert_eq!(result.risk, "Low Bleeding Risk");
+    assert_eq!(result.risk.text("en"), "Low Bleeding Risk");
 }
 
 #[test]
@@ -189,18 +341,15 @@ fn test_has_bled_high_risk() {
     let library = load_all_scores("scores/").unwrap();
     let score = library.get_score("has_bled").unwrap();
 
-    let mut inputs = HashMap::new();
-    inputs.insert("hypertension".to_string(), InputValue::Boolean(true));
-    inputs.insert(
-        "abnormal_renal_function".to_string(),
-        InputValue::Boolean(true),
-    );
-    inputs.insert("stroke".to_string(), InputValue::Boolean(true));
-    inputs.insert("elderly".to_string(), InputValue::Boolean(true));
+    let inputs = CalculationRequest::new()
+        .flag("hypertension")
+        .flag("abnormal_renal_function")
+        .flag("stroke")
+        .flag("elderly");
 
-    let result = calculate_score(score, &inputs).unwrap();
+    let result = inputs.calculate(score).unwrap();
     assert_eq!(result.total_score, 4);
-    assert_eq!(result.risk, "High Bleeding Risk");
+    assert_eq!(result.risk.text("en"), "High Bleeding Risk");
 }
 
 #[test]
@@ -209,17 +358,17 @@ fn test_has_bled_boundary() {
     let library = load_all_scores("scores/").unwrap();
     let score = library.get_score("has_bled").unwrap();
 
-    let mut inputs = HashMap::new();
-    inputs.insert("hypertension".to_string(), InputValue::Boolean(true));
-    inputs.insert("elderly".to_string(), InputValue::Boolean(true));
-    let result = calculate_score(score, &inputs).unwrap();
+    let inputs = CalculationRequest::new()
+        .flag("hypertension")
+        .flag("elderly");
+    let result = inputs.calculate(score).unwrap();
     assert_eq!(result.total_score, 2);
-    assert_eq!(result.risk, "Low Bleeding Risk");
+    assert_eq!(result.risk.text("en"), "Low Bleeding Risk");
 
-    inputs.insert("stroke".to_string(), InputValue::Boolean(true));
-    let result = calculate_score(score, &inputs).unwrap();
+    let inputs = inputs.flag("stroke");
+    let result = inputs.calculate(score).unwrap();
     assert_eq!(result.total_score, 3);
-    assert_eq!(result.risk, "High Bleeding Risk");
+    assert_eq!(result.risk.text("en"), "High Bleeding Risk");
 }
 
 // ============================================================
@@ -236,20 +385,17 @@ fn test_grace_low_risk_patient() {
     let library = load_all_scores("scores/").unwrap();
     let score = library.get_score("grace").unwrap();
 
-    let mut inputs = HashMap::new();
-    inputs.insert("age".to_string(), InputValue::Number(45.0));
-    inputs.insert("heart_rate".to_string(), InputValue::Number(75.0));
-    inputs.insert("systolic_bp".to_string(), InputValue::Number(130.0));
-    inputs.insert("creatinine".to_string(), InputValue::Number(80.0));
-    inputs.insert(
-        "killip_class".to_string(),
-        InputValue::Dropdown("killip_1".to_string()),
-    );
+    let inputs = CalculationRequest::new()
+        .number("age", 45.0)
+        .number("heart_rate", 75.0)
+        .number("systolic_bp", 130.0)
+        .number("creatinine", 80.0)
+        .choice("killip_class", "killip_1");
     // No boolean fields = all false
 
-    let result = calculate_score(score, &inputs).unwrap();
+    let result = inputs.calculate(score).unwrap();
     assert_eq!(result.total_score, 75);
-    assert_eq!(result.risk, "Low Risk");
+    assert_eq!(result.risk.text("en"), "Low Risk");
 }
 
 #[test]
@@ -261,21 +407,18 @@ fn test_grace_high_risk_patient() {
     let library = load_all_scores("scores/").unwrap();
     let score = library.get_score("grace").unwrap();
 
-    let mut inputs = HashMap::new();
-    inputs.insert("age".to_string(), InputValue::Number(75.0));
-    inputs.insert("heart_rate".to_string(), InputValue::Number(110.0));
-    inputs.insert("systolic_bp".to_string(), InputValue::Number(90.0));
-    inputs.insert("creatinine".to_string(), InputValue::Number(200.0));
-    inputs.insert("st_deviation".to_string(), InputValue::Boolean(true));
-    inputs.insert("elevated_enzymes".to_string(), InputValue::Boolean(true));
-    inputs.insert(
-        "killip_class".to_string(),
-        InputValue::Dropdown("killip_2".to_string()),
-    );
+    let inputs = CalculationRequest::new()
+        .number("age", 75.0)
+        .number("heart_rate", 110.0)
+        .number("systolic_bp", 90.0)
+        .number("creatinine", 200.0)
+        .flag("st_deviation")
+        .flag("elevated_enzymes")
+        .choice("killip_class", "killip_2");
 
-    let result = calculate_score(score, &inputs).unwrap();
+    let result = inputs.calculate(score).unwrap();
     assert_eq!(result.total_score, 235);
-    assert_eq!(result.risk, "High Risk");
+    assert_eq!(result.risk.text("en"), "High Risk");
 }
 
 #[test]
@@ -287,20 +430,17 @@ fn test_grace_intermediate_risk() {
     let library = load_all_scores("scores/").unwrap();
     let score = library.get_score("grace").unwrap();
 
-    let mut inputs = HashMap::new();
-    inputs.insert("age".to_string(), InputValue::Number(65.0));
-    inputs.insert("heart_rate".to_string(), InputValue::Number(85.0));
-    inputs.insert("systolic_bp".to_string(), InputValue::Number(140.0));
-    inputs.insert("creatinine".to_string(), InputValue::Number(100.0));
-    inputs.insert("elevated_enzymes".to_string(), InputValue::Boolean(true));
-    inputs.insert(
-        "killip_class".to_string(),
-        InputValue::Dropdown("killip_1".to_string()),
-    );
+    let inputs = CalculationRequest::new()
+        .number("age", 65.0)
+        .number("heart_rate", 85.0)
+        .number("systolic_bp", 140.0)
+        .number("creatinine", 100.0)
+        .flag("elevated_enzymes")
+        .choice("killip_class", "killip_1");
 
-    let result = calculate_score(score, &inputs).unwrap();
+    let result = inputs.calculate(score).unwrap();
     assert_eq!(result.total_score, 112);
-    assert_eq!(result.risk, "Intermediate Risk");
+    assert_eq!(result.risk.text("en"), "Intermediate Risk");
 }
 
 #[test]
@@ -310,23 +450,19 @@ fn test_grace_compound_conditions() {
     let score = library.get_score("grace").unwrap();
 
     // Test age boundaries: age 35 should match ">= 30 && < 40" = 8 points
-    let mut inputs = HashMap::new();
-    inputs.insert("age".to_string(), InputValue::Number(35.0));
-    inputs.insert("heart_rate".to_string(), InputValue::Number(60.0));
-    inputs.insert("systolic_bp".to_string(), InputValue::Number(120.0));
-    inputs.insert("creatinine".to_string(), InputValue::Number(80.0));
-    inputs.insert(
-        "killip_class".to_string(),
-        InputValue::Dropdown("killip_1".to_string()),
-    );
-
-    let result = calculate_score(score, &inputs).unwrap();
+    let inputs = CalculationRequest::new()
+        .number("age", 35.0)
+        .number("heart_rate", 60.0)
+        .number("systolic_bp", 120.0)
+        .number("creatinine", 80.0)
+        .choice("killip_class", "killip_1");
+
+    let result = inputs.calculate(score).unwrap();
     assert_eq!(result.get_field_points("age"), Some(8));
 
     // Age 55 should match ">= 50 && < 60" = 41 points
-    let mut inputs2 = inputs.clone();
-    inputs2.insert("age".to_string(), InputValue::Number(55.0));
-    let result2 = calculate_score(score, &inputs2).unwrap();
+    let inputs2 = inputs.clone().number("age", 55.0);
+    let result2 = inputs2.calculate(score).unwrap();
     assert_eq!(result2.get_field_points("age"), Some(41));
 }
 
@@ -336,8 +472,8 @@ fn test_grace_missing_required_number_fails() {
     let library = load_all_scores("scores/").unwrap();
     let score = library.get_score("grace").unwrap();
 
-    let inputs = HashMap::new(); // Empty = missing age
-    let result = calculate_score(score, &inputs);
+    let inputs = CalculationRequest::new(); // Empty = missing age
+    let result = inputs.calculate(score);
     assert!(result.is_err());
 }
 
@@ -351,15 +487,11 @@ fn test_asa_class_1() {
     let library = load_all_scores("scores/").unwrap();
     let score = library.get_score("asa").unwrap();
 
-    let mut inputs = HashMap::new();
-    inputs.insert(
-        "asa_class".to_string(),
-        InputValue::Dropdown("asa_1".to_string()),
-    );
+    let inputs = CalculationRequest::new().choice("asa_class", "asa_1");
 
-    let result = calculate_score(score, &inputs).unwrap();
+    let result = inputs.calculate(score).unwrap();
     assert_eq!(result.total_score, 1);
-    assert_eq!(result.risk, "ASA I - Minimal Risk");
+    assert_eq!(result.risk.text("en"), "ASA I - Minimal Risk");
 }
 
 #[test]
@@ -367,15 +499,11 @@ fn test_asa_class_3() {
     let library = load_all_scores("scores/").unwrap();
     let score = library.get_score("asa").unwrap();
 
-    let mut inputs = HashMap::new();
-    inputs.insert(
-        "asa_class".to_string(),
-        InputValue::Dropdown("asa_3".to_string()),
-    );
+    let inputs = CalculationRequest::new().choice("asa_class", "asa_3");
 
-    let result = calculate_score(score, &inputs).unwrap();
+    let result = inputs.calculate(score).unwrap();
     assert_eq!(result.total_score, 3);
-    assert_eq!(result.risk, "ASA III - Moderate Risk");
+    assert_eq!(result.risk.text("en"), "ASA III - Moderate Risk");
 }
 
 #[test]
@@ -383,17 +511,14 @@ fn test_asa_class_5_with_emergency() {
     let library = load_all_scores("scores/").unwrap();
     let score = library.get_score("asa").unwrap();
 
-    let mut inputs = HashMap::new();
-    inputs.insert(
-        "asa_class".to_string(),
-        InputValue::Dropdown("asa_5".to_string()),
-    );
-    inputs.insert("emergency".to_string(), InputValue::Boolean(true));
+    let inputs = CalculationRequest::new()
+        .choice("asa_class", "asa_5")
+        .flag("emergency");
 
-    let result = calculate_score(score, &inputs).unwrap();
+    let result = inputs.calculate(score).unwrap();
     // Emergency has points: 0, so total = 5
     assert_eq!(result.total_score, 5);
-    assert_eq!(result.risk, "ASA V - Extreme Risk");
+    assert_eq!(result.risk.text("en"), "ASA V - Extreme Risk");
 }
 
 #[test]
@@ -401,8 +526,8 @@ fn test_asa_missing_class_fails() {
     let library = load_all_scores("scores/").unwrap();
     let score = library.get_score("asa").unwrap();
 
-    let inputs = HashMap::new(); // Dropdown is required
-    let result = calculate_score(score, &inputs);
+    let inputs = CalculationRequest::new(); // Dropdown is required
+    let result = inputs.calculate(score);
     assert!(result.is_err());
 }
 
@@ -416,10 +541,10 @@ fn test_rcri_zero_risk_factors() {
     let library = load_all_scores("scores/").unwrap();
     let score = library.get_score("rcri").unwrap();
 
-    let inputs = HashMap::new(); // No risk factors = 0
-    let result = calculate_score(score, &inputs).unwrap();
+    let inputs = CalculationRequest::new(); // No risk factors = 0
+    let result = inputs.calculate(score).unwrap();
     assert_eq!(result.total_score, 0);
-    assert_eq!(result.risk, "Class I - Very Low Risk");
+    assert_eq!(result.risk.text("en"), "Class I - Very Low Risk");
 }
 
 #[test]
@@ -427,16 +552,13 @@ fn test_rcri_two_risk_factors() {
     let library = load_all_scores("scores/").unwrap();
     let score = library.get_score("rcri").unwrap();
 
-    let mut inputs = HashMap::new();
-    inputs.insert("high_risk_surgery".to_string(), InputValue::Boolean(true));
-    inputs.insert(
-        "ischemic_heart_disease".to_string(),
-        InputValue::Boolean(true),
-    );
+    let inputs = CalculationRequest::new()
+        .flag("high_risk_surgery")
+        .flag("ischemic_heart_disease");
 
-    let result = calculate_score(score, &inputs).unwrap();
+    let result = inputs.calculate(score).unwrap();
     assert_eq!(result.total_score, 2);
-    assert_eq!(result.risk, "Class III - Moderate Risk");
+    assert_eq!(result.risk.text("en"), "Class III - Moderate Risk");
 }
 
 #[test]
@@ -444,23 +566,17 @@ fn test_rcri_max_score() {
     let library = load_all_scores("scores/").unwrap();
     let score = library.get_score("rcri").unwrap();
 
-    let mut inputs = HashMap::new();
-    inputs.insert("high_risk_surgery".to_string(), InputValue::Boolean(true));
-    inputs.insert(
-        "ischemic_heart_disease".to_string(),
-        InputValue::Boolean(true),
-    );
-    inputs.insert("heart_failure".to_string(), InputValue::Boolean(true));
-    inputs.insert(
-        "cerebrovascular_disease".to_string(),
-        InputValue::Boolean(true),
-    );
-    inputs.insert("diabetes_insulin".to_string(), InputValue::Boolean(true));
-    inputs.insert("renal_insufficiency".to_string(), InputValue::Boolean(true));
+    let inputs = CalculationRequest::new()
+        .flag("high_risk_surgery")
+        .flag("ischemic_heart_disease")
+        .flag("heart_failure")
+        .flag("cerebrovascular_disease")
+        .flag("diabetes_insulin")
+        .flag("renal_insufficiency");
 
-    let result = calculate_score(score, &inputs).unwrap();
+    let result = inputs.calculate(score).unwrap();
     assert_eq!(result.total_score, 6);
-    assert_eq!(result.risk, "Class IV - High Risk");
+    assert_eq!(result.risk.text("en"), "Class IV - High Risk");
 }
 
 // ============================================================
@@ -474,10 +590,10 @@ fn test_stop_bang_low_risk() {
     let score = library.get_score("stop_bang").unwrap();
 
     // No risk factors
-    let inputs = HashMap::new();
-    let result = calculate_score(score, &inputs).unwrap();
+    let inputs = CalculationRequest::new();
+    let result = inputs.calculate(score).unwrap();
     assert_eq!(result.total_score, 0);
-    assert_eq!(result.risk, "Low Risk for OSA");
+    assert_eq!(result.risk.text("en"), "Low Risk for OSA");
 }
 
 #[test]
@@ -486,17 +602,17 @@ fn test_stop_bang_high_risk() {
     let library = load_all_scores("scores/").unwrap();
     let score = library.get_score("stop_bang").unwrap();
 
-    let mut inputs = HashMap::new();
-    inputs.insert("snoring".to_string(), InputValue::Boolean(true));
-    inputs.insert("tired".to_string(), InputValue::Boolean(true));
-    inputs.insert("observed".to_string(), InputValue::Boolean(true));
-    inputs.insert("pressure".to_string(), InputValue::Boolean(true));
-    inputs.insert("bmi".to_string(), InputValue::Boolean(true));
-    inputs.insert("age".to_string(), InputValue::Boolean(true));
+    let inputs = CalculationRequest::new()
+        .flag("snoring")
+        .flag("tired")
+        .flag("observed")
+        .flag("pressure")
+        .flag("bmi")
+        .flag("age");
 
-    let result = calculate_score(score, &inputs).unwrap();
+    let result = inputs.calculate(score).unwrap();
     assert_eq!(result.total_score, 6);
-    assert_eq!(result.risk, "High Risk for OSA");
+    assert_eq!(result.risk.text("en"), "High Risk for OSA");
 }
 
 #[test]
@@ -504,14 +620,14 @@ fn test_stop_bang_intermediate() {
     let library = load_all_scores("scores/").unwrap();
     let score = library.get_score("stop_bang").unwrap();
 
-    let mut inputs = HashMap::new();
-    inputs.insert("snoring".to_string(), InputValue::Boolean(true));
-    inputs.insert("tired".to_string(), InputValue::Boolean(true));
-    inputs.insert("pressure".to_string(), InputValue::Boolean(true));
+    let inputs = CalculationRequest::new()
+        .flag("snoring")
+        .flag("tired")
+        .flag("pressure");
 
-    let result = calculate_score(score, &inputs).unwrap();
+    let result = inputs.calculate(score).unwrap();
     assert_eq!(result.total_score, 3);
-    assert_eq!(result.risk, "Intermediate Risk for OSA");
+    assert_eq!(result.risk.text("en"), "Intermediate Risk for OSA");
 }
 
 // ============================================================
@@ -525,10 +641,10 @@ fn test_caprini_very_low_risk() {
     let score = library.get_score("caprini").unwrap();
 
     // No risk factors
-    let inputs = HashMap::new();
-    let result = calculate_score(score, &inputs).unwrap();
+    let inputs = CalculationRequest::new();
+    let result = inputs.calculate(score).unwrap();
     assert_eq!(result.total_score, 0);
-    assert_eq!(result.risk, "Very Low Risk");
+    assert_eq!(result.risk.text("en"), "Very Low Risk");
 }
 
 #[test]
@@ -537,12 +653,12 @@ fn test_caprini_low_risk() {
     let library = load_all_scores("scores/").unwrap();
     let score = library.get_score("caprini").unwrap();
 
-    let mut inputs = HashMap::new();
-    inputs.insert("major_surgery".to_string(), InputValue::Boolean(true)); // 2pt
+    let inputs = CalculationRequest::new();
+    let inputs = inputs.flag("major_surgery"); // 2pt
 
-    let result = calculate_score(score, &inputs).unwrap();
+    let result = inputs.calculate(score).unwrap();
     assert_eq!(result.total_score, 2);
-    assert_eq!(result.risk, "Low Risk");
+    assert_eq!(result.risk.text("en"), "Low Risk");
 }
 
 #[test]
@@ -551,13 +667,13 @@ fn test_caprini_moderate_risk() {
     let library = load_all_scores("scores/").unwrap();
     let score = library.get_score("caprini").unwrap();
 
-    let mut inputs = HashMap::new();
-    inputs.insert("age_41_60".to_string(), InputValue::Boolean(true)); // 1pt
-    inputs.insert("major_surgery".to_string(), InputValue::Boolean(true)); // 2pt
+    let inputs = CalculationRequest::new();
+    let inputs = inputs.flag("age_41_60"); // 1pt
+    let inputs = inputs.flag("major_surgery"); // 2pt
 
-    let result = calculate_score(score, &inputs).unwrap();
+    let result = inputs.calculate(score).unwrap();
     assert_eq!(result.total_score, 3);
-    assert_eq!(result.risk, "Moderate Risk");
+    assert_eq!(result.risk.text("en"), "Moderate Risk");
 }
 
 #[test]
@@ -566,13 +682,13 @@ fn test_caprini_high_risk() {
     let library = load_all_scores("scores/").unwrap();
     let score = library.get_score("caprini").unwrap();
 
-    let mut inputs = HashMap::new();
-    inputs.insert("history_vte".to_string(), InputValue::Boolean(true)); // 3pt
-    inputs.insert("major_surgery".to_string(), InputValue::Boolean(true)); // 2pt
+    let inputs = CalculationRequest::new();
+    let inputs = inputs.flag("history_vte"); // 3pt
+    let inputs = inputs.flag("major_surgery"); // 2pt
 
-    let result = calculate_score(score, &inputs).unwrap();
+    let result = inputs.calculate(score).unwrap();
     assert_eq!(result.total_score, 5);
-    assert_eq!(result.risk, "High Risk");
+    assert_eq!(result.risk.text("en"), "High Risk");
 }
 
 #[test]
@@ -581,15 +697,15 @@ fn test_caprini_mixed_point_values() {
     let library = load_all_scores("scores/").unwrap();
     let score = library.get_score("caprini").unwrap();
 
-    let mut inputs = HashMap::new();
-    inputs.insert("minor_surgery".to_string(), InputValue::Boolean(true)); // 1pt
-    inputs.insert("bmi_gt_30".to_string(), InputValue::Boolean(true)); // 2pt
-    inputs.insert("family_history_vte".to_string(), InputValue::Boolean(true)); // 3pt
-    inputs.insert("stroke".to_string(), InputValue::Boolean(true)); // 5pt
+    let inputs = CalculationRequest::new();
+    let inputs = inputs.flag("minor_surgery"); // 1pt
+    let inputs = inputs.flag("bmi_gt_30"); // 2pt
+    let inputs = inputs.flag("family_history_vte"); // 3pt
+    let inputs = inputs.flag("stroke"); // 5pt
 
-    let result = calculate_score(score, &inputs).unwrap();
+    let result = inputs.calculate(score).unwrap();
     assert_eq!(result.total_score, 11); // 1 + 2 + 3 + 5
-    assert_eq!(result.risk, "High Risk"); // ≥5
+    assert_eq!(result.risk.text("en"), "High Risk"); // ≥5
 }
 
 // ============================================================
@@ -623,8 +739,8 @@ fn test_egfr_required_fields() {
     let library = load_all_scores("scores/").unwrap();
     let score = library.get_score("egfr_ckd_epi_2021").unwrap();
 
-    let inputs = HashMap::new();
-    let result = calculate_score(score, &inputs);
+    let inputs = CalculationRequest::new();
+    let result = inputs.calculate(score);
     assert!(result.is_err(), "Should fail without required age");
 }
 
@@ -634,12 +750,12 @@ fn test_egfr_calculation_with_all_fields() {
     let library = load_all_scores("scores/").unwrap();
     let score = library.get_score("egfr_ckd_epi_2021").unwrap();
 
-    let mut inputs = HashMap::new();
-    inputs.insert("age".to_string(), InputValue::Number(55.0));
-    inputs.insert("sex".to_string(), InputValue::Dropdown("male".to_string()));
-    inputs.insert("creatinine".to_string(), InputValue::Number(100.0));
+    let inputs = CalculationRequest::new()
+        .number("age", 55.0)
+        .choice("sex", "male")
+        .number("creatinine", 100.0);
 
-    let result = calculate_score(score, &inputs).unwrap();
+    let result = inputs.calculate(score).unwrap();
     // 55M Scr=100μmol/L (1.13mg/dL) -> eGFR ~76 (G2 stage)
     assert!(
         result.total_score >= 70 && result.total_score <= 85,
@@ -647,9 +763,9 @@ fn test_egfr_calculation_with_all_fields() {
         result.total_score
     );
     assert!(
-        result.risk.contains("G2"),
+        result.risk.text("en").contains("G2"),
         "Should be stage G2, got: {}",
-        result.risk
+        result.risk.text("en")
     );
 }
 
@@ -659,15 +775,12 @@ fn test_egfr_stage_g3a() {
     let library = load_all_scores("scores/").unwrap();
     let score = library.get_score("egfr_ckd_epi_2021").unwrap();
 
-    let mut inputs = HashMap::new();
-    inputs.insert("age".to_string(), InputValue::Number(70.0));
-    inputs.insert(
-        "sex".to_string(),
-        InputValue::Dropdown("female".to_string()),
-    );
-    inputs.insert("creatinine".to_string(), InputValue::Number(120.0));
+    let inputs = CalculationRequest::new()
+        .number("age", 70.0)
+        .choice("sex", "female")
+        .number("creatinine", 120.0);
 
-    let result = calculate_score(score, &inputs).unwrap();
+    let result = inputs.calculate(score).unwrap();
     // Should fall in G3a range (45-59)
     assert!(
         result.total_score >= 40 && result.total_score <= 60,
@@ -699,8 +812,8 @@ fn test_kfre_required_fields() {
     let library = load_all_scores("scores/").unwrap();
     let score = library.get_score("kfre").unwrap();
 
-    let inputs = HashMap::new();
-    let result = calculate_score(score, &inputs);
+    let inputs = CalculationRequest::new();
+    let result = inputs.calculate(score);
     assert!(result.is_err());
 }
 
@@ -710,16 +823,13 @@ fn test_kfre_calculation_high_risk() {
     let library = load_all_scores("scores/").unwrap();
     let score = library.get_score("kfre").unwrap();
 
-    let mut inputs = HashMap::new();
-    inputs.insert("age".to_string(), InputValue::Number(65.0));
-    inputs.insert(
-        "sex".to_string(),
-        InputValue::Dropdown("female".to_string()),
-    );
-    inputs.insert("egfr".to_string(), InputValue::Number(35.0));
-    inputs.insert("acr".to_string(), InputValue::Number(30.0));
+    let inputs = CalculationRequest::new()
+        .number("age", 65.0)
+        .choice("sex", "female")
+        .number("egfr", 35.0)
+        .number("acr", 30.0);
 
-    let result = calculate_score(score, &inputs).unwrap();
+    let result = inputs.calculate(score).unwrap();
     // Low eGFR + high ACR -> risk should be > 2%
     assert!(
         result.total_score >= 2,
@@ -734,16 +844,13 @@ fn test_kfre_calculation_low_risk() {
     let library = load_all_scores("scores/").unwrap();
     let score = library.get_score("kfre").unwrap();
 
-    let mut inputs = HashMap::new();
-    inputs.insert("age".to_string(), InputValue::Number(50.0));
-    inputs.insert(
-        "sex".to_string(),
-        InputValue::Dropdown("female".to_string()),
-    );
-    inputs.insert("egfr".to_string(), InputValue::Number(55.0));
-    inputs.insert("acr".to_string(), InputValue::Number(3.0));
+    let inputs = CalculationRequest::new()
+        .number("age", 50.0)
+        .choice("sex", "female")
+        .number("egfr", 55.0)
+        .number("acr", 3.0);
 
-    let result = calculate_score(score, &inputs).unwrap();
+    let result = inputs.calculate(score).unwrap();
     // Higher eGFR + low ACR -> very low risk (<3%)
     assert!(
         result.total_score <= 3,
@@ -752,6 +859,27 @@ fn test_kfre_calculation_low_risk() {
     );
 }
 
+#[test]
+fn test_kfre_rejects_egfr_outside_validated_range() {
+    // KFRE is validated for CKD G3-G5 only; eGFR 75 must be rejected before calculation
+    let library = load_all_scores("scores/").unwrap();
+    let score = library.get_score("kfre").unwrap();
+
+    let inputs = CalculationRequest::new()
+        .number("age", 50.0)
+        .choice("sex", "female")
+        .number("egfr", 75.0)
+        .number("acr", 3.0);
+
+    let err = inputs.calculate(score).unwrap_err();
+    assert!(matches!(
+        err,
+        klinscore::scores::CalculationError::ConstraintViolated { .. }
+    ));
+    assert!(err.localized_message("de").contains("G3–G5"));
+    assert!(err.localized_message("en").contains("validated"));
+}
+
 // ============================================================
 // Edge Cases & Cross-cutting Concerns
 // ============================================================
@@ -769,8 +897,8 @@ fn test_boolean_fields_default_to_zero_when_missing() {
         ("caprini", true),
     ] {
         let score = library.get_score(score_id).unwrap();
-        let inputs = HashMap::new();
-        let result = calculate_score(score, &inputs);
+        let inputs = CalculationRequest::new();
+        let result = inputs.calculate(score);
 
         if expected_zero_scores {
             let result = result.unwrap_or_else(|e| {
@@ -790,10 +918,10 @@ fn test_out_of_range_validation() {
     let library = load_all_scores("scores/").unwrap();
     let score = library.get_score("cha2ds2_va").unwrap();
 
-    let mut inputs = HashMap::new();
-    inputs.insert("age".to_string(), InputValue::Number(150.0)); // max is 120
+    let inputs = CalculationRequest::new();
+    let inputs = inputs.number("age", 150.0); // max is 120
 
-    let result = calculate_score(score, &inputs);
+    let result = inputs.calculate(score);
     assert!(result.is_err(), "Should reject age > 120");
 }
 
@@ -802,12 +930,52 @@ fn test_invalid_dropdown_option() {
     let library = load_all_scores("scores/").unwrap();
     let score = library.get_score("asa").unwrap();
 
-    let mut inputs = HashMap::new();
-    inputs.insert(
-        "asa_class".to_string(),
-        InputValue::Dropdown("invalid_option".to_string()),
-    );
+    let inputs = CalculationRequest::new().choice("asa_class", "invalid_option");
 
-    let result = calculate_score(score, &inputs);
+    let result = inputs.calculate(score);
     assert!(result.is_err(), "Should reject invalid dropdown option");
+
+    // The calculator checks options itself, without the request's validation
+    let result = calculate_score(score, inputs.inputs());
+    assert!(
+        matches!(result, Err(CalculationError::UnknownDropdownOption { .. })),
+        "Calculator should reject invalid dropdown option, got {:?}",
+        result
+    );
+}
+
+#[test]
+fn test_non_finite_numbers_rejected() {
+    let library = load_all_scores("scores/").unwrap();
+    let score = library.get_score("cha2ds2_va").unwrap();
+
+    for age in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+        let inputs = CalculationRequest::new().number("age", age);
+        assert!(inputs.validate(score).is_err(), "Should reject age {}", age);
+        let result = calculate_score(score, inputs.inputs());
+        assert!(
+            matches!(result, Err(CalculationError::InvalidInput { .. })),
+            "Calculator should reject age {}, got {:?}",
+            age,
+            result
+        );
+    }
+
+    // Formula scores check numbers too, including their range
+    let egfr = library.get_score("egfr_ckd_epi_2021").unwrap();
+    let inputs = |creatinine| {
+        CalculationRequest::new()
+            .number("age", 60.0)
+            .choice("sex", "female")
+            .number("creatinine", creatinine)
+    };
+    assert!(inputs(80.0).calculate(egfr).is_ok());
+    assert!(matches!(
+        calculate_score(egfr, inputs(f64::NAN).inputs()),
+        Err(CalculationError::InvalidInput { .. })
+    ));
+    assert!(matches!(
+        calculate_score(egfr, inputs(5000.0).inputs()),
+        Err(CalculationError::OutOfRange { .. })
+    ));
 }